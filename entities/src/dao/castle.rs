@@ -1,4 +1,7 @@
-use sea_orm::{DeriveActiveEnum, EnumIter};
+use crate::DBPool;
+use crate::entities::castle;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{DbErr, DeriveActiveEnum, EntityTrait, EnumIter};
 
 #[derive(EnumIter, DeriveActiveEnum, Clone, Debug, Copy, PartialEq, Eq, Default)]
 #[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "CastleSide")]
@@ -11,4 +14,65 @@ pub enum CastleSide {
     #[sea_orm(string_value = "DARK")]
     Dark,
     
-}
\ No newline at end of file
+}
+
+#[allow(clippy::missing_errors_doc)]
+impl castle::Model {
+    /// Castles owned by a clan, castles without a row belong to the npcs.
+    pub async fn load_all(db_pool: &DBPool) -> Result<Vec<castle::Model>, DbErr> {
+        castle::Entity::find().all(db_pool).await
+    }
+
+    /// Saves the owner, tax rate, treasury and siege schedule of the castle.
+    pub async fn save(db_pool: &DBPool, castle: castle::Model) -> Result<(), DbErr> {
+        let active_model: castle::ActiveModel = castle.into();
+        castle::Entity::insert(active_model)
+            .on_conflict(
+                OnConflict::column(castle::Column::Id)
+                    .update_columns([
+                        castle::Column::ClanId,
+                        castle::Column::TaxRate,
+                        castle::Column::Treasury,
+                        castle::Column::SiegeStartsAt,
+                        castle::Column::RegistrationEndsAt,
+                    ])
+                    .to_owned(),
+            )
+            .exec(db_pool)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_factories::factories::{char_factory, clan_factory, user_factory};
+    use test_utils::utils::get_test_db;
+
+    #[tokio::test]
+    async fn test_save_castle_owner() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let char = char_factory(&db_pool, |mut c| {
+            c.user_id = user.id;
+            c
+        })
+        .await;
+        let clan = clan_factory(&db_pool, |mut c| {
+            c.leader_id = char.id;
+            c
+        })
+        .await;
+        let mut castle = castle::Model {
+            id: 1,
+            name: "Gludio".to_string(),
+            clan_id: clan.id,
+            ..Default::default()
+        };
+        castle::Model::save(&db_pool, castle.clone()).await.unwrap();
+        castle.tax_rate = 10;
+        castle::Model::save(&db_pool, castle.clone()).await.unwrap();
+        assert_eq!(castle::Model::load_all(&db_pool).await.unwrap(), vec![castle]);
+    }
+}
@@ -1,9 +1,27 @@
 use crate::dao::item::LocType;
-use crate::entities::{character, clan_ally, item, user};
+use crate::entities::{character, character_macro, character_shortcut, clan_ally, item, user};
 use crate::DBPool;
 use chrono::{Duration, Utc};
 use sea_orm::entity::prelude::*;
 use sea_orm::{ActiveValue, DbErr, JoinType, Order, QueryOrder, QuerySelect};
+use std::collections::HashMap;
+
+/// Character with everything that is loaded together with it on login.
+pub type CharWithData = (
+    character::Model,
+    Vec<item::Model>,
+    Option<clan_ally::Model>,
+    Vec<character_macro::Model>,
+    Vec<character_shortcut::Model>,
+);
+
+fn group_by_char<T>(models: Vec<T>, char_id: impl Fn(&T) -> i32) -> HashMap<i32, Vec<T>> {
+    let mut map: HashMap<_, Vec<T>> = HashMap::new();
+    for m in models {
+        map.entry(char_id(&m)).or_default().push(m);
+    }
+    map
+}
 
 #[allow(clippy::missing_errors_doc)]
 impl character::Model {
@@ -14,6 +32,24 @@ impl character::Model {
             .await?
             .is_some())
     }
+    pub async fn find_by_name(
+        db_pool: &DBPool,
+        name: &str,
+    ) -> Result<Option<character::Model>, DbErr> {
+        character::Entity::find()
+            .filter(character::Column::Name.eq(name))
+            .one(db_pool)
+            .await
+    }
+    pub async fn find_by_ids(
+        db_pool: &DBPool,
+        ids: &[i32],
+    ) -> Result<Vec<character::Model>, DbErr> {
+        character::Entity::find()
+            .filter(character::Column::Id.is_in(ids.iter().copied()))
+            .all(db_pool)
+            .await
+    }
     pub async fn create_char(
         db_pool: &DBPool,
         c: character::Model,
@@ -57,8 +93,8 @@ impl character::Model {
     pub async fn load_chars_with_data(
         db_pool: &DBPool,
         username: &str,
-        loc_type: LocType,
-    ) -> anyhow::Result<Vec<(character::Model, Vec<item::Model>, Option<clan_ally::Model>)>> {
+        loc_types: &[LocType],
+    ) -> anyhow::Result<Vec<CharWithData>> {
         let characters = character::Entity::find()
             .order_by(character::Column::CreatedAt, Order::Asc)
             .join(JoinType::InnerJoin, character::Relation::User.def())
@@ -69,25 +105,32 @@ impl character::Model {
 
         let char_ids:Vec<_> = characters.iter().map(|(c, _)| c.id).collect();
 
-        // Fetch all items for these characters in a single query
+        // Fetch all items, macros and shortcuts for these characters in a single query each
         let items = item::Entity::find()
-            .filter(item::Column::Owner.is_in(char_ids))
-            .filter(item::Column::Loc.eq(loc_type))
+            .filter(item::Column::Owner.is_in(char_ids.clone()))
+            .filter(item::Column::Loc.is_in(loc_types.iter().copied()))
             .all(db_pool)
             .await?;
-        // Create a HashMap for O(1) lookup, grouping items by character_id
-        let mut items_map: std::collections::HashMap<_, Vec<item::Model>> =
-            std::collections::HashMap::new();
-
-        for item in items {
-            items_map.entry(item.owner).or_default().push(item);
-        }
+        let macros = character_macro::Entity::find()
+            .filter(character_macro::Column::CharId.is_in(char_ids.clone()))
+            .all(db_pool)
+            .await?;
+        let shortcuts = character_shortcut::Entity::find()
+            .filter(character_shortcut::Column::CharId.is_in(char_ids))
+            .all(db_pool)
+            .await?;
+        // Group by character_id for O(1) lookup
+        let mut items_map = group_by_char(items, |i| i.owner);
+        let mut macros_map = group_by_char(macros, |m| m.char_id);
+        let mut shortcuts_map = group_by_char(shortcuts, |sc| sc.char_id);
         // Build the result
         let result = characters
             .into_iter()
             .map(|(char_model, clan)| {
                 let items = items_map.remove(&char_model.id).unwrap_or_default();
-                (char_model, items, clan)
+                let macros = macros_map.remove(&char_model.id).unwrap_or_default();
+                let shortcuts = shortcuts_map.remove(&char_model.id).unwrap_or_default();
+                (char_model, items, clan, macros, shortcuts)
             })
             .collect();
         Ok(result)
@@ -106,6 +149,23 @@ impl character::Model {
             y: ActiveValue::Set(char.y),
             z: ActiveValue::Set(char.z),
             heading: ActiveValue::Set(char.heading),
+            level: ActiveValue::Set(char.level),
+            exp: ActiveValue::Set(char.exp),
+            sp: ActiveValue::Set(char.sp),
+            class_id: ActiveValue::Set(char.class_id),
+            max_hp: ActiveValue::Set(char.max_hp),
+            max_mp: ActiveValue::Set(char.max_mp),
+            max_cp: ActiveValue::Set(char.max_cp),
+            bookmark_slot: ActiveValue::Set(char.bookmark_slot),
+            vitality_points: ActiveValue::Set(char.vitality_points),
+            variables: ActiveValue::Set(char.variables.clone()),
+            rec_left: ActiveValue::Set(char.rec_left),
+            rec_have: ActiveValue::Set(char.rec_have),
+            rec_bonus_time: ActiveValue::Set(char.rec_bonus_time),
+            reputation: ActiveValue::Set(char.reputation),
+            pvp_kills: ActiveValue::Set(char.pvp_kills),
+            pk_kills: ActiveValue::Set(char.pk_kills),
+            transform_id: ActiveValue::Set(char.transform_id),
             // todo implement the rest
             ..Default::default()
         };
@@ -142,7 +202,7 @@ mod tests {
         })
         .await;
 
-        let chars = character::Model::load_chars_with_data(&db_pool, "admin", LocType::Paperdoll)
+        let chars = character::Model::load_chars_with_data(&db_pool, "admin", &[LocType::Paperdoll])
             .await
             .unwrap();
         assert_eq!(chars.len(), 1);
@@ -152,6 +212,39 @@ mod tests {
         assert_eq!(chars[0].1[0].item_id, item.item_id);
     }
     #[tokio::test]
+    async fn test_load_macros_and_shortcuts() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let char = char_factory(&db_pool, |mut c| {
+            c.user_id = user.id;
+            c
+        })
+        .await;
+        let m = character_macro::Model {
+            char_id: char.id,
+            id: 1000,
+            name: "Buff".to_string(),
+            commands: serde_json::json!([]),
+            ..Default::default()
+        };
+        character_macro::Model::save(&db_pool, m.clone()).await.unwrap();
+        let sc = character_shortcut::Model {
+            char_id: char.id,
+            slot: 1,
+            shortcut_type: 4,
+            shortcut_id: 1000,
+            ..Default::default()
+        };
+        character_shortcut::Model::save(&db_pool, sc.clone()).await.unwrap();
+
+        let chars = character::Model::load_chars_with_data(&db_pool, "admin", &[])
+            .await
+            .unwrap();
+        assert_eq!(chars.len(), 1);
+        assert_eq!(chars[0].3, vec![m]);
+        assert_eq!(chars[0].4, vec![sc]);
+    }
+    #[tokio::test]
     async fn test_find_by_username() {
         let db_pool = get_test_db().await;
         let user = user_factory(&db_pool, |u| u).await;
@@ -228,4 +321,24 @@ mod tests {
         assert_eq!(new_char.name, "Tester");
         assert!(new_char.last_access.is_some());
     }
+    #[tokio::test]
+    async fn test_update_char() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let mut char = char_factory(&db_pool, |mut c| {
+            c.user_id = user.id;
+            c
+        })
+        .await;
+        char.vitality_points = 20_000;
+        char.variables = serde_json::json!({ "vitalityItemsUsed": 2 });
+        character::Model::update_char(&db_pool, &char).await.unwrap();
+        let stored = character::Entity::find_by_id(char.id)
+            .one(&db_pool)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.vitality_points, 20_000);
+        assert_eq!(stored.variables, char.variables);
+    }
 }
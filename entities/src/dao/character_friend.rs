@@ -0,0 +1,176 @@
+use crate::DBPool;
+use crate::entities::{character, character_friend};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ColumnTrait, DbErr, DeriveActiveEnum, EntityTrait, EnumIter, QueryFilter};
+
+#[derive(EnumIter, DeriveActiveEnum, Clone, Debug, Copy, PartialEq, Eq, Default)]
+#[sea_orm(rs_type = "i16", db_type = "SmallInteger")]
+pub enum FriendRelation {
+    #[default]
+    #[sea_orm(num_value = 0)]
+    Friend,
+    #[sea_orm(num_value = 1)]
+    Block,
+}
+
+#[allow(clippy::missing_errors_doc)]
+impl character_friend::Model {
+    /// Friends and blocked characters of the char together with their character models.
+    pub async fn char_relations(
+        db_pool: &DBPool,
+        char_id: i32,
+    ) -> Result<Vec<(character_friend::Model, character::Model)>, DbErr> {
+        let relations = character_friend::Entity::find()
+            .filter(character_friend::Column::CharId.eq(char_id))
+            .all(db_pool)
+            .await?;
+        let chars = character::Entity::find()
+            .filter(character::Column::Id.is_in(relations.iter().map(|r| r.friend_id)))
+            .all(db_pool)
+            .await?;
+        Ok(relations
+            .into_iter()
+            .filter_map(|r| {
+                let ch = chars.iter().find(|c| c.id == r.friend_id)?.clone();
+                Some((r, ch))
+            })
+            .collect())
+    }
+
+    /// If the char has the other one on its block list
+    pub async fn is_blocked(db_pool: &DBPool, char_id: i32, other_id: i32) -> Result<bool, DbErr> {
+        Ok(character_friend::Entity::find_by_id((char_id, other_id))
+            .one(db_pool)
+            .await?
+            .is_some_and(|r| r.relation == FriendRelation::Block))
+    }
+
+    pub async fn save(db_pool: &DBPool, relation: character_friend::Model) -> Result<(), DbErr> {
+        let active_model: character_friend::ActiveModel = relation.into();
+        character_friend::Entity::insert(active_model)
+            .on_conflict(
+                OnConflict::columns([
+                    character_friend::Column::CharId,
+                    character_friend::Column::FriendId,
+                ])
+                .update_column(character_friend::Column::Relation)
+                .to_owned(),
+            )
+            .exec(db_pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Friendship is mutual, so it's saved for both characters.
+    pub async fn add_friends(db_pool: &DBPool, char_id: i32, friend_id: i32) -> Result<(), DbErr> {
+        for (char_id, friend_id) in [(char_id, friend_id), (friend_id, char_id)] {
+            Self::save(
+                db_pool,
+                character_friend::Model {
+                    char_id,
+                    friend_id,
+                    relation: FriendRelation::Friend,
+                },
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    pub async fn remove_friends(
+        db_pool: &DBPool,
+        char_id: i32,
+        friend_id: i32,
+    ) -> Result<(), DbErr> {
+        for (char_id, friend_id) in [(char_id, friend_id), (friend_id, char_id)] {
+            Self::delete(db_pool, char_id, friend_id).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn delete(db_pool: &DBPool, char_id: i32, friend_id: i32) -> Result<(), DbErr> {
+        character_friend::Entity::delete_by_id((char_id, friend_id))
+            .exec(db_pool)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_factories::factories::{char_factory, user_factory};
+    use test_utils::utils::get_test_db;
+
+    #[tokio::test]
+    async fn test_friends_and_blocks() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let mut chars = Vec::new();
+        for name in ["First", "Second", "Third"] {
+            chars.push(
+                char_factory(&db_pool, |mut c| {
+                    c.user_id = user.id;
+                    c.name = name.to_string();
+                    c
+                })
+                .await,
+            );
+        }
+        let (first, second, third) = (chars[0].id, chars[1].id, chars[2].id);
+        character_friend::Model::add_friends(&db_pool, first, second)
+            .await
+            .unwrap();
+        character_friend::Model::save(
+            &db_pool,
+            character_friend::Model {
+                char_id: first,
+                friend_id: third,
+                relation: FriendRelation::Block,
+            },
+        )
+        .await
+        .unwrap();
+        let mut relations = character_friend::Model::char_relations(&db_pool, first)
+            .await
+            .unwrap();
+        relations.sort_by_key(|(r, _)| r.friend_id);
+        assert_eq!(relations.len(), 2);
+        assert_eq!(relations[0].0.relation, FriendRelation::Friend);
+        assert_eq!(relations[0].1.name, "Second");
+        assert_eq!(relations[1].0.relation, FriendRelation::Block);
+        assert_eq!(relations[1].1.name, "Third");
+        assert!(
+            character_friend::Model::is_blocked(&db_pool, first, third)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !character_friend::Model::is_blocked(&db_pool, first, second)
+                .await
+                .unwrap()
+        );
+        let relations = character_friend::Model::char_relations(&db_pool, second)
+            .await
+            .unwrap();
+        assert_eq!(relations.len(), 1);
+        assert_eq!(relations[0].1.name, "First");
+
+        character_friend::Model::remove_friends(&db_pool, second, first)
+            .await
+            .unwrap();
+        assert!(
+            character_friend::Model::char_relations(&db_pool, second)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            character_friend::Model::char_relations(&db_pool, first)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use crate::DBPool;
+use crate::entities::character_henna;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ColumnTrait, DbErr, EntityTrait, QueryFilter};
+
+#[allow(clippy::missing_errors_doc)]
+impl character_henna::Model {
+    pub async fn char_hennas(
+        db_pool: &DBPool,
+        char_id: i32,
+        class_index: i32,
+    ) -> Result<Vec<character_henna::Model>, DbErr> {
+        character_henna::Entity::find()
+            .filter(character_henna::Column::CharId.eq(char_id))
+            .filter(character_henna::Column::ClassIndex.eq(class_index))
+            .all(db_pool)
+            .await
+    }
+
+    /// Puts the dye into its slot, replacing whatever was there.
+    pub async fn save(db_pool: &DBPool, henna: character_henna::Model) -> Result<(), DbErr> {
+        let active_model: character_henna::ActiveModel = henna.into();
+        character_henna::Entity::insert(active_model)
+            .on_conflict(
+                OnConflict::columns([
+                    character_henna::Column::CharId,
+                    character_henna::Column::ClassIndex,
+                    character_henna::Column::Slot,
+                ])
+                .update_column(character_henna::Column::DyeId)
+                .to_owned(),
+            )
+            .exec(db_pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn delete(
+        db_pool: &DBPool,
+        char_id: i32,
+        slot: i16,
+        class_index: i32,
+    ) -> Result<(), DbErr> {
+        character_henna::Entity::delete_by_id((char_id, slot, class_index))
+            .exec(db_pool)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_factories::factories::{char_factory, user_factory};
+    use test_utils::utils::get_test_db;
+
+    #[tokio::test]
+    async fn test_save_and_delete() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let char = char_factory(&db_pool, |mut c| {
+            c.user_id = user.id;
+            c
+        })
+        .await;
+        let mut henna = character_henna::Model {
+            char_id: char.id,
+            slot: 1,
+            class_index: 0,
+            dye_id: 1,
+        };
+        character_henna::Model::save(&db_pool, henna.clone())
+            .await
+            .unwrap();
+        henna.dye_id = 7;
+        character_henna::Model::save(&db_pool, henna.clone())
+            .await
+            .unwrap();
+        let saved = character_henna::Model::char_hennas(&db_pool, char.id, 0)
+            .await
+            .unwrap();
+        assert_eq!(saved, vec![henna]);
+        assert!(
+            character_henna::Model::char_hennas(&db_pool, char.id, 1)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        character_henna::Model::delete(&db_pool, char.id, 1, 0)
+            .await
+            .unwrap();
+        let saved = character_henna::Model::char_hennas(&db_pool, char.id, 0)
+            .await
+            .unwrap();
+        assert!(saved.is_empty());
+    }
+}
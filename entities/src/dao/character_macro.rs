@@ -0,0 +1,90 @@
+use crate::DBPool;
+use crate::entities::character_macro;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ColumnTrait, DbErr, EntityTrait, QueryFilter};
+
+#[allow(clippy::missing_errors_doc)]
+impl character_macro::Model {
+    pub async fn char_macros(
+        db_pool: &DBPool,
+        char_id: i32,
+    ) -> Result<Vec<character_macro::Model>, DbErr> {
+        character_macro::Entity::find()
+            .filter(character_macro::Column::CharId.eq(char_id))
+            .all(db_pool)
+            .await
+    }
+
+    /// Inserts a new macro or overwrites the one with the same id.
+    pub async fn save(db_pool: &DBPool, m: character_macro::Model) -> Result<(), DbErr> {
+        let active_model: character_macro::ActiveModel = m.into();
+        character_macro::Entity::insert(active_model)
+            .on_conflict(
+                OnConflict::columns([character_macro::Column::CharId, character_macro::Column::Id])
+                    .update_columns([
+                        character_macro::Column::Icon,
+                        character_macro::Column::Name,
+                        character_macro::Column::Description,
+                        character_macro::Column::Acronym,
+                        character_macro::Column::Commands,
+                    ])
+                    .to_owned(),
+            )
+            .exec(db_pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn delete(db_pool: &DBPool, char_id: i32, id: i32) -> Result<(), DbErr> {
+        character_macro::Entity::delete_by_id((char_id, id))
+            .exec(db_pool)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_factories::factories::{char_factory, user_factory};
+    use serde_json::json;
+    use test_utils::utils::get_test_db;
+
+    #[tokio::test]
+    async fn test_save_and_delete() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let char = char_factory(&db_pool, |mut c| {
+            c.user_id = user.id;
+            c
+        })
+        .await;
+        let mut m = character_macro::Model {
+            char_id: char.id,
+            id: 1000,
+            icon: 1,
+            name: "Buff".to_string(),
+            description: String::new(),
+            acronym: "B".to_string(),
+            commands: json!([{ "macro_type": "Text", "d1": 0, "d2": 0, "cmd": "hi" }]),
+        };
+        character_macro::Model::save(&db_pool, m.clone())
+            .await
+            .unwrap();
+        m.name = "Heal".to_string();
+        character_macro::Model::save(&db_pool, m.clone())
+            .await
+            .unwrap();
+        let saved = character_macro::Model::char_macros(&db_pool, char.id)
+            .await
+            .unwrap();
+        assert_eq!(saved, vec![m]);
+        character_macro::Model::delete(&db_pool, char.id, 1000)
+            .await
+            .unwrap();
+        let saved = character_macro::Model::char_macros(&db_pool, char.id)
+            .await
+            .unwrap();
+        assert!(saved.is_empty());
+    }
+}
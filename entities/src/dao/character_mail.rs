@@ -0,0 +1,475 @@
+use crate::DBPool;
+use crate::dao::item::LocType;
+use crate::entities::{character, character_mail, item};
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, Condition, DatabaseTransaction, DbErr, EntityTrait,
+    QueryFilter, QueryOrder, QuerySelect, TransactionTrait,
+};
+
+/// How the recipient pays for a COD mail: the adena stack the payment is taken from and
+/// when the mail carrying the payment to the sender expires.
+#[derive(Debug, Clone, Copy)]
+pub struct CodPayment {
+    pub adena_id: i32,
+    pub expire_at: DateTimeWithTimeZone,
+}
+
+#[allow(clippy::missing_errors_doc)]
+impl character_mail::Model {
+    #[must_use]
+    pub fn has_attachments(&self) -> bool {
+        !self.items.is_empty()
+    }
+
+    /// Cash on delivery, the recipient has to pay to get the attachments
+    #[must_use]
+    pub fn is_cod(&self) -> bool {
+        self.request_adena > 0
+    }
+
+    /// Received mails with the names of their senders (None for deleted chars)
+    pub async fn inbox(
+        db_pool: &DBPool,
+        char_id: i32,
+    ) -> Result<Vec<(character_mail::Model, Option<String>)>, DbErr> {
+        let mails = character_mail::Entity::find()
+            .filter(character_mail::Column::Recipient.eq(char_id))
+            .filter(character_mail::Column::IsDeleted.eq(false))
+            .order_by_desc(character_mail::Column::Id)
+            .all(db_pool)
+            .await?;
+        Self::with_names(db_pool, mails, |m| m.sender).await
+    }
+
+    /// Sent mails which are still waiting in the recipient's inbox,
+    /// with the names of their recipients
+    pub async fn outbox(
+        db_pool: &DBPool,
+        char_id: i32,
+    ) -> Result<Vec<(character_mail::Model, Option<String>)>, DbErr> {
+        let mails = character_mail::Entity::find()
+            .filter(character_mail::Column::Sender.eq(char_id))
+            .filter(character_mail::Column::IsDeleted.eq(false))
+            .filter(character_mail::Column::IsReturned.eq(false))
+            .order_by_desc(character_mail::Column::Id)
+            .all(db_pool)
+            .await?;
+        Self::with_names(db_pool, mails, |m| m.recipient).await
+    }
+
+    async fn with_names(
+        db_pool: &DBPool,
+        mails: Vec<character_mail::Model>,
+        char_id: impl Fn(&character_mail::Model) -> Option<i32>,
+    ) -> Result<Vec<(character_mail::Model, Option<String>)>, DbErr> {
+        let chars = character::Entity::find()
+            .filter(character::Column::Id.is_in(mails.iter().filter_map(&char_id)))
+            .all(db_pool)
+            .await?;
+        Ok(mails
+            .into_iter()
+            .map(|m| {
+                let name = char_id(&m)
+                    .and_then(|id| chars.iter().find(|c| c.id == id))
+                    .map(|c| c.name.clone());
+                (m, name)
+            })
+            .collect())
+    }
+
+    pub async fn find_by_id(
+        db_pool: &DBPool,
+        id: i32,
+    ) -> Result<Option<character_mail::Model>, DbErr> {
+        character_mail::Entity::find_by_id(id).one(db_pool).await
+    }
+
+    pub async fn attachments(db_pool: &DBPool, mail_id: i32) -> Result<Vec<item::Model>, DbErr> {
+        item::Entity::find()
+            .filter(item::Column::Loc.eq(LocType::Mail))
+            .filter(item::Column::LocData.eq(mail_id))
+            .all(db_pool)
+            .await
+    }
+
+    /// Saves the mail and moves `(item id, count)` attachments out of the sender's inventory,
+    /// splitting stacks when only a part of them is sent. The `(item id, count)` fee is taken
+    /// from the adena of the sender in the same transaction. Nothing is changed if any of
+    /// the items is missing or the sender can't pay.
+    pub async fn send(
+        db_pool: &DBPool,
+        mail: character_mail::Model,
+        attachments: &[(i32, i64)],
+        fee: (i32, i64),
+    ) -> Result<character_mail::Model, DbErr> {
+        let txn = db_pool.begin().await?;
+        let mut active_model: character_mail::ActiveModel = mail.into();
+        active_model.id = ActiveValue::NotSet;
+        let mail = active_model.insert(&txn).await?;
+        let mut attached = Vec::with_capacity(attachments.len());
+        for &(id, count) in attachments {
+            let item = item::Entity::find_by_id(id)
+                .lock_exclusive()
+                .one(&txn)
+                .await?
+                .filter(|i| {
+                    Some(i.owner) == mail.sender
+                        && i.loc == LocType::Inventory
+                        && count > 0
+                        && i.count >= count
+                })
+                .ok_or_else(|| DbErr::RecordNotFound(format!("Item {id} can't be attached")))?;
+            let moved = if item.count == count {
+                let mut am: item::ActiveModel = item.into();
+                am.loc = ActiveValue::Set(LocType::Mail);
+                am.loc_data = ActiveValue::Set(mail.id);
+                am.update(&txn).await?
+            } else {
+                let mut split = item.clone();
+                let mut am: item::ActiveModel = item.into();
+                am.count = ActiveValue::Set(split.count - count);
+                am.update(&txn).await?;
+                split.count = count;
+                split.loc = LocType::Mail;
+                split.loc_data = mail.id;
+                let mut am: item::ActiveModel = split.into();
+                am.id = ActiveValue::NotSet;
+                am.insert(&txn).await?
+            };
+            attached.push(moved.id.to_string());
+        }
+        let (adena_id, fee) = fee;
+        if let Some(sender) = mail.sender {
+            pay(&txn, adena_id, sender, fee).await?;
+        }
+        let mut active_model: character_mail::ActiveModel = mail.into();
+        active_model.items = ActiveValue::Set(attached.join(","));
+        let mail = active_model.update(&txn).await?;
+        txn.commit().await?;
+        Ok(mail)
+    }
+
+    /// Saves the mail with newly created attachments (e.g. adena paid for a COD mail).
+    pub async fn send_new_items(
+        db_pool: &DBPool,
+        mail: character_mail::Model,
+        items: Vec<item::Model>,
+    ) -> Result<character_mail::Model, DbErr> {
+        let txn = db_pool.begin().await?;
+        let mail = insert_with_new_items(&txn, mail, items).await?;
+        txn.commit().await?;
+        Ok(mail)
+    }
+
+    /// Moves the attachments to the inventory of the char, the mail no longer asks for payment.
+    /// When the recipient takes the attachments of a COD mail, the payment is taken from its
+    /// adena and mailed to the sender in the same transaction, the mail carrying it is
+    /// returned too. Fails without changes when the mail has no attachments anymore
+    /// (e.g. the sender cancelled it meanwhile) or the recipient can't pay.
+    pub async fn take_attachments(
+        db_pool: &DBPool,
+        mail_id: i32,
+        char_id: i32,
+        payment: Option<CodPayment>,
+    ) -> Result<
+        (
+            character_mail::Model,
+            Vec<item::Model>,
+            Option<character_mail::Model>,
+        ),
+        DbErr,
+    > {
+        let txn = db_pool.begin().await?;
+        let mail = character_mail::Entity::find_by_id(mail_id)
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .filter(|m| m.has_attachments() && !m.is_deleted)
+            .ok_or_else(|| DbErr::RecordNotFound(format!("Mail {mail_id} has no attachments")))?;
+        let items = item::Entity::find()
+            .filter(item::Column::Loc.eq(LocType::Mail))
+            .filter(item::Column::LocData.eq(mail.id))
+            .lock_exclusive()
+            .all(&txn)
+            .await?;
+        if items.is_empty() {
+            return Err(DbErr::RecordNotFound(format!(
+                "Attachments of mail {mail_id} are gone"
+            )));
+        }
+        let mut payment_mail = None;
+        if mail.is_cod() && mail.recipient == Some(char_id) {
+            let payment =
+                payment.ok_or_else(|| DbErr::Custom(format!("Mail {mail_id} must be paid for")))?;
+            let amount = i64::from(mail.request_adena);
+            let adena = pay(&txn, payment.adena_id, char_id, amount).await?;
+            if let Some(sender) = mail.sender {
+                let paid = character_mail::Model {
+                    id: 0,
+                    subject: mail.subject.clone(),
+                    message: None,
+                    items: String::new(),
+                    sender: Some(char_id),
+                    recipient: Some(sender),
+                    expire_at: payment.expire_at,
+                    request_adena: 0,
+                    is_unread: true,
+                    is_returned: false,
+                    is_deleted: false,
+                };
+                let adena = item::Model {
+                    owner: sender,
+                    item_id: adena.item_id,
+                    count: amount,
+                    ..Default::default()
+                };
+                payment_mail = Some(insert_with_new_items(&txn, paid, vec![adena]).await?);
+            }
+        }
+        let mut taken = Vec::with_capacity(items.len());
+        for item in items {
+            let mut am: item::ActiveModel = item.into();
+            am.owner = ActiveValue::Set(char_id);
+            am.loc = ActiveValue::Set(LocType::Inventory);
+            am.loc_data = ActiveValue::Set(0);
+            taken.push(am.update(&txn).await?);
+        }
+        let mut active_model: character_mail::ActiveModel = mail.into();
+        active_model.items = ActiveValue::Set(String::new());
+        active_model.request_adena = ActiveValue::Set(0);
+        let mail = active_model.update(&txn).await?;
+        txn.commit().await?;
+        Ok((mail, taken, payment_mail))
+    }
+
+    /// Sends the mail back, the sender becomes the recipient and doesn't have to pay. The mail
+    /// is locked like in [`Self::take_attachments`], it fails without changes when the
+    /// attachments are gone meanwhile or the mail was returned already.
+    pub async fn return_to_sender(
+        db_pool: &DBPool,
+        mail_id: i32,
+        expire_at: DateTimeWithTimeZone,
+    ) -> Result<character_mail::Model, DbErr> {
+        let txn = db_pool.begin().await?;
+        let mail = character_mail::Entity::find_by_id(mail_id)
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .filter(|m| m.has_attachments() && !m.is_returned && !m.is_deleted)
+            .ok_or_else(|| DbErr::RecordNotFound(format!("Mail {mail_id} can't be returned")))?;
+        let (sender, recipient) = (mail.sender, mail.recipient);
+        let mut active_model: character_mail::ActiveModel = mail.into();
+        active_model.sender = ActiveValue::Set(recipient);
+        active_model.recipient = ActiveValue::Set(sender);
+        active_model.request_adena = ActiveValue::Set(0);
+        active_model.is_unread = ActiveValue::Set(true);
+        active_model.is_returned = ActiveValue::Set(true);
+        active_model.expire_at = ActiveValue::Set(expire_at);
+        let mail = active_model.update(&txn).await?;
+        txn.commit().await?;
+        Ok(mail)
+    }
+
+    pub async fn update(
+        db_pool: &DBPool,
+        mail: character_mail::Model,
+    ) -> Result<character_mail::Model, DbErr> {
+        let active_model: character_mail::ActiveModel = mail.into();
+        active_model.reset_all().update(db_pool).await
+    }
+
+    /// Mails which are not deleted and have expired by `now`, except the returned ones
+    /// still holding items, which wait for their owner
+    pub async fn expired(
+        db_pool: &DBPool,
+        now: DateTimeWithTimeZone,
+    ) -> Result<Vec<character_mail::Model>, DbErr> {
+        character_mail::Entity::find()
+            .filter(character_mail::Column::IsDeleted.eq(false))
+            .filter(character_mail::Column::ExpireAt.lte(now))
+            .filter(
+                Condition::any()
+                    .add(character_mail::Column::IsReturned.eq(false))
+                    .add(character_mail::Column::Items.eq("")),
+            )
+            .all(db_pool)
+            .await
+    }
+}
+
+/// Takes `count` from the inventory stack of the owner, deleting the stack when it runs out.
+/// Returns the stack as it was before.
+async fn pay(
+    txn: &DatabaseTransaction,
+    id: i32,
+    owner: i32,
+    count: i64,
+) -> Result<item::Model, DbErr> {
+    let stack = item::Entity::find_by_id(id)
+        .lock_exclusive()
+        .one(txn)
+        .await?
+        .filter(|i| i.owner == owner && i.loc == LocType::Inventory && i.count >= count)
+        .ok_or_else(|| DbErr::RecordNotFound(format!("Not enough items {id} to pay {count}")))?;
+    if stack.count == count {
+        item::Entity::delete_by_id(id).exec(txn).await?;
+    } else {
+        let mut am: item::ActiveModel = stack.clone().into();
+        am.count = ActiveValue::Set(stack.count - count);
+        am.update(txn).await?;
+    }
+    Ok(stack)
+}
+
+async fn insert_with_new_items(
+    txn: &DatabaseTransaction,
+    mail: character_mail::Model,
+    items: Vec<item::Model>,
+) -> Result<character_mail::Model, DbErr> {
+    let mut active_model: character_mail::ActiveModel = mail.into();
+    active_model.id = ActiveValue::NotSet;
+    let mail = active_model.insert(txn).await?;
+    let mut attached = Vec::with_capacity(items.len());
+    for mut item in items {
+        item.loc = LocType::Mail;
+        item.loc_data = mail.id;
+        let mut am: item::ActiveModel = item.into();
+        am.id = ActiveValue::NotSet;
+        attached.push(am.insert(txn).await?.id.to_string());
+    }
+    let mut active_model: character_mail::ActiveModel = mail.into();
+    active_model.items = ActiveValue::Set(attached.join(","));
+    active_model.update(txn).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_factories::factories::{char_factory, item_factory, user_factory};
+    use chrono::{Duration, Utc};
+    use test_utils::utils::get_test_db;
+
+    #[tokio::test]
+    async fn test_send_take_and_return() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let sender = char_factory(&db_pool, |mut c| {
+            c.user_id = user.id;
+            c.name = "Sender".to_string();
+            c
+        })
+        .await;
+        let recipient = char_factory(&db_pool, |mut c| {
+            c.user_id = user.id;
+            c.name = "Recipient".to_string();
+            c
+        })
+        .await;
+        let stack = item_factory(&db_pool, |mut i| {
+            i.owner = sender.id;
+            i.item_id = 57;
+            i.count = 100;
+            i.loc = LocType::Inventory;
+            i
+        })
+        .await;
+        let mail = character_mail::Model {
+            id: 0,
+            subject: "Hi".to_string(),
+            message: None,
+            items: String::new(),
+            sender: Some(sender.id),
+            recipient: Some(recipient.id),
+            expire_at: (Utc::now() + Duration::days(1)).fixed_offset(),
+            request_adena: 0,
+            is_unread: true,
+            is_returned: false,
+            is_deleted: false,
+        };
+        // not enough items, nothing is saved
+        assert!(
+            character_mail::Model::send(&db_pool, mail.clone(), &[(stack.id, 101)], (stack.id, 0))
+                .await
+                .is_err()
+        );
+        assert!(
+            character_mail::Model::inbox(&db_pool, recipient.id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        // the fee can't be paid with what is left of the stack
+        assert!(
+            character_mail::Model::send(&db_pool, mail.clone(), &[(stack.id, 40)], (stack.id, 61))
+                .await
+                .is_err()
+        );
+        let mail = character_mail::Model::send(&db_pool, mail, &[(stack.id, 40)], (stack.id, 10))
+            .await
+            .unwrap();
+        assert!(mail.has_attachments());
+        let attachments = character_mail::Model::attachments(&db_pool, mail.id)
+            .await
+            .unwrap();
+        assert_eq!(attachments.len(), 1);
+        assert_eq!(attachments[0].count, 40);
+        let left = item::Entity::find_by_id(stack.id)
+            .one(&db_pool)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(left.count, 50);
+        let inbox = character_mail::Model::inbox(&db_pool, recipient.id)
+            .await
+            .unwrap();
+        assert_eq!(inbox.len(), 1);
+        assert_eq!(inbox[0].1.as_deref(), Some("Sender"));
+        let outbox = character_mail::Model::outbox(&db_pool, sender.id)
+            .await
+            .unwrap();
+        assert_eq!(outbox[0].1.as_deref(), Some("Recipient"));
+
+        let expire_at = (Utc::now() + Duration::days(1)).fixed_offset();
+        let returned = character_mail::Model::return_to_sender(&db_pool, mail.id, expire_at)
+            .await
+            .unwrap();
+        assert!(returned.is_returned);
+        // it goes back only once
+        assert!(
+            character_mail::Model::return_to_sender(&db_pool, mail.id, expire_at)
+                .await
+                .is_err()
+        );
+        assert_eq!(returned.recipient, Some(sender.id));
+        assert!(
+            character_mail::Model::outbox(&db_pool, sender.id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        let (mail, taken, payment) =
+            character_mail::Model::take_attachments(&db_pool, returned.id, sender.id, None)
+                .await
+                .unwrap();
+        assert!(payment.is_none());
+        assert!(!mail.has_attachments());
+        // the attachments can be taken only once
+        assert!(
+            character_mail::Model::take_attachments(&db_pool, mail.id, sender.id, None)
+                .await
+                .is_err()
+        );
+        assert_eq!(taken.len(), 1);
+        assert_eq!(taken[0].owner, sender.id);
+        assert_eq!(taken[0].loc, LocType::Inventory);
+        assert!(
+            character_mail::Model::expired(&db_pool, Utc::now().fixed_offset())
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
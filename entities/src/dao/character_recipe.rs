@@ -0,0 +1,84 @@
+use crate::DBPool;
+use crate::entities::character_recipe;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ColumnTrait, DbErr, EntityTrait, QueryFilter};
+
+#[allow(clippy::missing_errors_doc)]
+impl character_recipe::Model {
+    /// Recipe ids of the recipe book of the char.
+    pub async fn char_recipes(db_pool: &DBPool, char_id: i32) -> Result<Vec<i32>, DbErr> {
+        Ok(character_recipe::Entity::find()
+            .filter(character_recipe::Column::CharId.eq(char_id))
+            .all(db_pool)
+            .await?
+            .into_iter()
+            .map(|r| r.recipe_id)
+            .collect())
+    }
+
+    /// Registers the recipe, nothing happens when it is already in the book.
+    pub async fn save(db_pool: &DBPool, char_id: i32, recipe_id: i32) -> Result<(), DbErr> {
+        let active_model: character_recipe::ActiveModel =
+            character_recipe::Model { char_id, recipe_id }.into();
+        character_recipe::Entity::insert(active_model)
+            .on_conflict(
+                OnConflict::columns([
+                    character_recipe::Column::CharId,
+                    character_recipe::Column::RecipeId,
+                ])
+                .do_nothing()
+                .to_owned(),
+            )
+            .do_nothing()
+            .exec(db_pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn delete(db_pool: &DBPool, char_id: i32, recipe_id: i32) -> Result<(), DbErr> {
+        character_recipe::Entity::delete_by_id((char_id, recipe_id))
+            .exec(db_pool)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_factories::factories::{char_factory, user_factory};
+    use test_utils::utils::get_test_db;
+
+    #[tokio::test]
+    async fn test_save_and_delete() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let char = char_factory(&db_pool, |mut c| {
+            c.user_id = user.id;
+            c
+        })
+        .await;
+        character_recipe::Model::save(&db_pool, char.id, 1)
+            .await
+            .unwrap();
+        character_recipe::Model::save(&db_pool, char.id, 3)
+            .await
+            .unwrap();
+        // registering twice is fine
+        character_recipe::Model::save(&db_pool, char.id, 1)
+            .await
+            .unwrap();
+        let mut saved = character_recipe::Model::char_recipes(&db_pool, char.id)
+            .await
+            .unwrap();
+        saved.sort_unstable();
+        assert_eq!(saved, vec![1, 3]);
+        character_recipe::Model::delete(&db_pool, char.id, 1)
+            .await
+            .unwrap();
+        let saved = character_recipe::Model::char_recipes(&db_pool, char.id)
+            .await
+            .unwrap();
+        assert_eq!(saved, vec![3]);
+    }
+}
@@ -0,0 +1,102 @@
+use crate::DBPool;
+use crate::entities::character_shortcut;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ColumnTrait, DbErr, EntityTrait, QueryFilter};
+
+#[allow(clippy::missing_errors_doc)]
+impl character_shortcut::Model {
+    pub async fn char_shortcuts(
+        db_pool: &DBPool,
+        char_id: i32,
+    ) -> Result<Vec<character_shortcut::Model>, DbErr> {
+        character_shortcut::Entity::find()
+            .filter(character_shortcut::Column::CharId.eq(char_id))
+            .all(db_pool)
+            .await
+    }
+
+    /// Puts the shortcut into its slot, replacing whatever was there.
+    pub async fn save(db_pool: &DBPool, sc: character_shortcut::Model) -> Result<(), DbErr> {
+        let active_model: character_shortcut::ActiveModel = sc.into();
+        character_shortcut::Entity::insert(active_model)
+            .on_conflict(
+                OnConflict::columns([
+                    character_shortcut::Column::CharId,
+                    character_shortcut::Column::ClassIndex,
+                    character_shortcut::Column::Page,
+                    character_shortcut::Column::Slot,
+                ])
+                .update_columns([
+                    character_shortcut::Column::ShortcutType,
+                    character_shortcut::Column::ShortcutId,
+                    character_shortcut::Column::Level,
+                    character_shortcut::Column::SubLevel,
+                    character_shortcut::Column::CharacterType,
+                ])
+                .to_owned(),
+            )
+            .exec(db_pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn delete(
+        db_pool: &DBPool,
+        char_id: i32,
+        slot: i16,
+        page: i16,
+        class_index: i32,
+    ) -> Result<(), DbErr> {
+        character_shortcut::Entity::delete_by_id((char_id, slot, page, class_index))
+            .exec(db_pool)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_factories::factories::{char_factory, user_factory};
+    use test_utils::utils::get_test_db;
+
+    #[tokio::test]
+    async fn test_save_and_delete() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let char = char_factory(&db_pool, |mut c| {
+            c.user_id = user.id;
+            c
+        })
+        .await;
+        let mut sc = character_shortcut::Model {
+            char_id: char.id,
+            slot: 3,
+            page: 1,
+            class_index: 0,
+            shortcut_type: 2,
+            shortcut_id: 3,
+            level: 1,
+            sub_level: 0,
+            character_type: 1,
+        };
+        character_shortcut::Model::save(&db_pool, sc.clone())
+            .await
+            .unwrap();
+        sc.shortcut_id = 56;
+        character_shortcut::Model::save(&db_pool, sc.clone())
+            .await
+            .unwrap();
+        let saved = character_shortcut::Model::char_shortcuts(&db_pool, char.id)
+            .await
+            .unwrap();
+        assert_eq!(saved, vec![sc]);
+        character_shortcut::Model::delete(&db_pool, char.id, 3, 1, 0)
+            .await
+            .unwrap();
+        let saved = character_shortcut::Model::char_shortcuts(&db_pool, char.id)
+            .await
+            .unwrap();
+        assert!(saved.is_empty());
+    }
+}
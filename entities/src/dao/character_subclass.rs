@@ -0,0 +1,262 @@
+use crate::DBPool;
+use crate::entities::{
+    character_henna, character_shortcut, character_subclass, character_subclass_effect, skill,
+};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder, TransactionTrait};
+
+#[allow(clippy::missing_errors_doc)]
+impl character_subclass::Model {
+    pub async fn char_subclasses(
+        db_pool: &DBPool,
+        char_id: i32,
+    ) -> Result<Vec<character_subclass::Model>, DbErr> {
+        character_subclass::Entity::find()
+            .filter(character_subclass::Column::CharId.eq(char_id))
+            .order_by_asc(character_subclass::Column::ClassIndex)
+            .all(db_pool)
+            .await
+    }
+
+    /// Saves the progress of the class, replacing the class that had the index before.
+    pub async fn save(db_pool: &DBPool, sub: character_subclass::Model) -> Result<(), DbErr> {
+        let active_model: character_subclass::ActiveModel = sub.into();
+        character_subclass::Entity::insert(active_model)
+            .on_conflict(
+                OnConflict::columns([
+                    character_subclass::Column::CharId,
+                    character_subclass::Column::ClassIndex,
+                ])
+                .update_columns([
+                    character_subclass::Column::ClassId,
+                    character_subclass::Column::Level,
+                    character_subclass::Column::Exp,
+                    character_subclass::Column::Sp,
+                ])
+                .to_owned(),
+            )
+            .exec(db_pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Adds a new class with the skills it starts with.
+    pub async fn add(
+        db_pool: &DBPool,
+        sub: character_subclass::Model,
+        skills: Vec<skill::Model>,
+    ) -> Result<(), DbErr> {
+        let txn = db_pool.begin().await?;
+        character_subclass::Entity::insert(character_subclass::ActiveModel::from(sub))
+            .exec(&txn)
+            .await?;
+        if !skills.is_empty() {
+            skill::Entity::insert_many(skills.into_iter().map(skill::ActiveModel::from))
+                .exec(&txn)
+                .await?;
+        }
+        txn.commit().await
+    }
+
+    /// Keeps the effects of a class that is no longer active, replacing what it had before.
+    pub async fn save_effects(
+        db_pool: &DBPool,
+        char_id: i32,
+        class_index: i32,
+        effects: Vec<character_subclass_effect::Model>,
+    ) -> Result<(), DbErr> {
+        let txn = db_pool.begin().await?;
+        character_subclass_effect::Entity::delete_many()
+            .filter(character_subclass_effect::Column::CharId.eq(char_id))
+            .filter(character_subclass_effect::Column::ClassIndex.eq(class_index))
+            .exec(&txn)
+            .await?;
+        if !effects.is_empty() {
+            let models = effects
+                .into_iter()
+                .map(character_subclass_effect::ActiveModel::from);
+            character_subclass_effect::Entity::insert_many(models)
+                .exec(&txn)
+                .await?;
+        }
+        txn.commit().await
+    }
+
+    /// Removes and returns the kept effects of the class that becomes active.
+    pub async fn take_effects(
+        db_pool: &DBPool,
+        char_id: i32,
+        class_index: i32,
+    ) -> Result<Vec<character_subclass_effect::Model>, DbErr> {
+        let txn = db_pool.begin().await?;
+        let effects = character_subclass_effect::Entity::find()
+            .filter(character_subclass_effect::Column::CharId.eq(char_id))
+            .filter(character_subclass_effect::Column::ClassIndex.eq(class_index))
+            .all(&txn)
+            .await?;
+        character_subclass_effect::Entity::delete_many()
+            .filter(character_subclass_effect::Column::CharId.eq(char_id))
+            .filter(character_subclass_effect::Column::ClassIndex.eq(class_index))
+            .exec(&txn)
+            .await?;
+        txn.commit().await?;
+        Ok(effects)
+    }
+
+    /// Removes the class together with its skills, hennas, shortcuts and effects.
+    pub async fn delete_with_class_data(
+        db_pool: &DBPool,
+        char_id: i32,
+        class_index: i32,
+    ) -> Result<(), DbErr> {
+        let txn = db_pool.begin().await?;
+        skill::Entity::delete_many()
+            .filter(skill::Column::CharId.eq(char_id))
+            .filter(skill::Column::ClassIndex.eq(class_index))
+            .exec(&txn)
+            .await?;
+        character_henna::Entity::delete_many()
+            .filter(character_henna::Column::CharId.eq(char_id))
+            .filter(character_henna::Column::ClassIndex.eq(class_index))
+            .exec(&txn)
+            .await?;
+        character_shortcut::Entity::delete_many()
+            .filter(character_shortcut::Column::CharId.eq(char_id))
+            .filter(character_shortcut::Column::ClassIndex.eq(class_index))
+            .exec(&txn)
+            .await?;
+        character_subclass_effect::Entity::delete_many()
+            .filter(character_subclass_effect::Column::CharId.eq(char_id))
+            .filter(character_subclass_effect::Column::ClassIndex.eq(class_index))
+            .exec(&txn)
+            .await?;
+        character_subclass::Entity::delete_by_id((char_id, class_index))
+            .exec(&txn)
+            .await?;
+        txn.commit().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_factories::factories::{char_factory, user_factory};
+    use test_utils::utils::get_test_db;
+
+    #[tokio::test]
+    async fn test_save_and_delete() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let char = char_factory(&db_pool, |mut c| {
+            c.user_id = user.id;
+            c
+        })
+        .await;
+        let mut sub = character_subclass::Model {
+            char_id: char.id,
+            class_index: 1,
+            class_id: 2,
+            level: 40,
+            exp: 0,
+            sp: 0,
+        };
+        character_subclass::Model::save(&db_pool, sub.clone())
+            .await
+            .unwrap();
+        sub.level = 41;
+        sub.exp = 100;
+        character_subclass::Model::save(&db_pool, sub.clone())
+            .await
+            .unwrap();
+        let skill = skill::Model {
+            id: 1,
+            char_id: char.id,
+            level: 1,
+            sub_level: 0,
+            class_index: 1,
+        };
+        skill::Model::insert_skills(
+            &db_pool,
+            vec![
+                skill.clone(),
+                skill::Model {
+                    class_index: 0,
+                    ..skill
+                },
+            ],
+        )
+        .await
+        .unwrap();
+        let saved = character_subclass::Model::char_subclasses(&db_pool, char.id)
+            .await
+            .unwrap();
+        assert_eq!(saved, vec![sub]);
+        character_subclass::Model::delete_with_class_data(&db_pool, char.id, 1)
+            .await
+            .unwrap();
+        assert!(
+            character_subclass::Model::char_subclasses(&db_pool, char.id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        let skills = skill::Model::char_skills(&db_pool, char.id).await.unwrap();
+        assert_eq!(skills.len(), 1);
+        assert_eq!(skills[0].class_index, 0);
+    }
+
+    #[tokio::test]
+    async fn test_effects_are_kept_per_class() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let char = char_factory(&db_pool, |mut c| {
+            c.user_id = user.id;
+            c
+        })
+        .await;
+        let effect = character_subclass_effect::Model {
+            char_id: char.id,
+            class_index: 1,
+            skill_id: 1068,
+            skill_level: 3,
+            caster_id: char.id,
+            abnormal_type: Some("PA_UP".to_string()),
+            abnormal_level: 3,
+            end_time: chrono::Utc::now().into(),
+        };
+        character_subclass::Model::save_effects(&db_pool, char.id, 1, vec![effect.clone()])
+            .await
+            .unwrap();
+        assert!(
+            character_subclass::Model::take_effects(&db_pool, char.id, 0)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        let taken = character_subclass::Model::take_effects(&db_pool, char.id, 1)
+            .await
+            .unwrap();
+        assert_eq!(taken.len(), 1);
+        assert_eq!(taken[0].skill_id, 1068);
+        // taken effects are active again, they are not kept twice
+        assert!(
+            character_subclass::Model::take_effects(&db_pool, char.id, 1)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        character_subclass::Model::save_effects(&db_pool, char.id, 1, vec![effect])
+            .await
+            .unwrap();
+        character_subclass::Model::delete_with_class_data(&db_pool, char.id, 1)
+            .await
+            .unwrap();
+        assert!(
+            character_subclass::Model::take_effects(&db_pool, char.id, 1)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
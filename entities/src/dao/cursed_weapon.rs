@@ -0,0 +1,163 @@
+use crate::DBPool;
+use crate::entities::{character, cursed_weapon, item};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, DbErr, EntityTrait, QueryFilter, TransactionTrait,
+};
+
+#[allow(clippy::missing_errors_doc)]
+impl cursed_weapon::Model {
+    /// Cursed weapons which have an owner, the others are not in the world.
+    pub async fn load_all(db_pool: &DBPool) -> Result<Vec<cursed_weapon::Model>, DbErr> {
+        cursed_weapon::Entity::find().all(db_pool).await
+    }
+
+    pub async fn save(db_pool: &DBPool, weapon: cursed_weapon::Model) -> Result<(), DbErr> {
+        let active_model: cursed_weapon::ActiveModel = weapon.into();
+        cursed_weapon::Entity::insert(active_model)
+            .on_conflict(
+                OnConflict::column(cursed_weapon::Column::ItemId)
+                    .update_columns([
+                        cursed_weapon::Column::CharId,
+                        cursed_weapon::Column::PlayerReputation,
+                        cursed_weapon::Column::PlayerPkKills,
+                        cursed_weapon::Column::Kills,
+                        cursed_weapon::Column::ExpiresAt,
+                    ])
+                    .to_owned(),
+            )
+            .exec(db_pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn delete(db_pool: &DBPool, item_id: i32) -> Result<(), DbErr> {
+        cursed_weapon::Entity::delete_by_id(item_id)
+            .exec(db_pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Takes the weapon from an owner who is not in game: the reputation and the pk kills the
+    /// owner had before are given back and the weapon items are destroyed, wherever they are.
+    pub async fn release_offline(
+        db_pool: &DBPool,
+        weapon: &cursed_weapon::Model,
+    ) -> Result<(), DbErr> {
+        let txn = db_pool.begin().await?;
+        character::ActiveModel {
+            id: ActiveValue::Set(weapon.char_id),
+            reputation: ActiveValue::Set(weapon.player_reputation),
+            pk_kills: ActiveValue::Set(weapon.player_pk_kills),
+            transform_id: ActiveValue::Set(0),
+            ..Default::default()
+        }
+        .update(&txn)
+        .await?;
+        item::Entity::delete_many()
+            .filter(item::Column::ItemId.eq(weapon.item_id))
+            .exec(&txn)
+            .await?;
+        cursed_weapon::Entity::delete_by_id(weapon.item_id)
+            .exec(&txn)
+            .await?;
+        txn.commit().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dao::item::LocType;
+    use crate::test_factories::factories::{char_factory, user_factory};
+    use chrono::{TimeDelta, Utc};
+    use test_utils::utils::get_test_db;
+
+    #[tokio::test]
+    async fn test_release_offline() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let char = char_factory(&db_pool, |mut c| {
+            c.user_id = user.id;
+            c.reputation = 9_999_999;
+            c.pk_kills = 12;
+            c.transform_id = 301;
+            c
+        })
+        .await;
+        item::Model::create(
+            &db_pool,
+            item::Model {
+                owner: char.id,
+                item_id: 8190,
+                count: 1,
+                loc: LocType::Inventory,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        // a copy somebody else stashed away is found too
+        let other = char_factory(&db_pool, |mut c| {
+            c.user_id = user.id;
+            c.name = "Other".to_string();
+            c
+        })
+        .await;
+        item::Model::create(
+            &db_pool,
+            item::Model {
+                owner: other.id,
+                item_id: 8190,
+                count: 1,
+                loc: LocType::Warehouse,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        let mut weapon = cursed_weapon::Model {
+            item_id: 8190,
+            char_id: char.id,
+            player_reputation: 0,
+            player_pk_kills: 2,
+            kills: 1,
+            expires_at: (Utc::now() + TimeDelta::hours(5)).fixed_offset(),
+        };
+        cursed_weapon::Model::save(&db_pool, weapon.clone())
+            .await
+            .unwrap();
+        weapon.kills = 10;
+        cursed_weapon::Model::save(&db_pool, weapon.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            cursed_weapon::Model::load_all(&db_pool).await.unwrap(),
+            vec![weapon.clone()]
+        );
+
+        cursed_weapon::Model::release_offline(&db_pool, &weapon)
+            .await
+            .unwrap();
+        assert!(
+            cursed_weapon::Model::load_all(&db_pool)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        let char = character::Entity::find_by_id(char.id)
+            .one(&db_pool)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(char.reputation, 0);
+        assert_eq!(char.pk_kills, 2);
+        assert_eq!(char.transform_id, 0);
+        let items = item::Entity::find()
+            .filter(item::Column::ItemId.eq(8190))
+            .all(&db_pool)
+            .await
+            .unwrap();
+        assert!(items.is_empty());
+    }
+}
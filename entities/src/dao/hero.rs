@@ -0,0 +1,140 @@
+use crate::DBPool;
+use crate::entities::{hero, hero_diary};
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect,
+};
+
+#[allow(clippy::missing_errors_doc)]
+impl hero::Model {
+    /// Heroes whose status has not run out at the time.
+    pub async fn load_active(
+        db_pool: &DBPool,
+        now: DateTimeWithTimeZone,
+    ) -> Result<Vec<hero::Model>, DbErr> {
+        hero::Entity::find()
+            .filter(hero::Column::ExpiresAt.gt(now))
+            .all(db_pool)
+            .await
+    }
+
+    pub async fn find_by_char_id(
+        db_pool: &DBPool,
+        char_id: i32,
+    ) -> Result<Option<hero::Model>, DbErr> {
+        hero::Entity::find_by_id(char_id).one(db_pool).await
+    }
+
+    /// Heroes keep their row after the status runs out, the count tells how often they were one.
+    pub async fn save(db_pool: &DBPool, hero: hero::Model) -> Result<(), DbErr> {
+        let active_model: hero::ActiveModel = hero.into();
+        hero::Entity::insert(active_model)
+            .on_conflict(
+                OnConflict::column(hero::Column::CharId)
+                    .update_columns([
+                        hero::Column::ClassId,
+                        hero::Column::Count,
+                        hero::Column::ExpiresAt,
+                        hero::Column::Message,
+                    ])
+                    .to_owned(),
+            )
+            .exec(db_pool)
+            .await?;
+        Ok(())
+    }
+}
+
+#[allow(clippy::missing_errors_doc)]
+impl hero_diary::Model {
+    pub async fn add(
+        db_pool: &DBPool,
+        char_id: i32,
+        created_at: DateTimeWithTimeZone,
+        text: &str,
+    ) -> Result<hero_diary::Model, DbErr> {
+        hero_diary::ActiveModel {
+            id: ActiveValue::NotSet,
+            char_id: ActiveValue::Set(char_id),
+            created_at: ActiveValue::Set(created_at),
+            text: ActiveValue::Set(text.to_string()),
+        }
+        .insert(db_pool)
+        .await
+    }
+
+    /// The latest entries of the hero, newest first.
+    pub async fn latest(
+        db_pool: &DBPool,
+        char_id: i32,
+        limit: u64,
+    ) -> Result<Vec<hero_diary::Model>, DbErr> {
+        hero_diary::Entity::find()
+            .filter(hero_diary::Column::CharId.eq(char_id))
+            .order_by_desc(hero_diary::Column::Id)
+            .limit(limit)
+            .all(db_pool)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_factories::factories::{char_factory, user_factory};
+    use chrono::{TimeDelta, Utc};
+    use test_utils::utils::get_test_db;
+
+    #[tokio::test]
+    async fn test_heroes_and_diary() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let char = char_factory(&db_pool, |mut c| {
+            c.user_id = user.id;
+            c
+        })
+        .await;
+        let now = Utc::now().fixed_offset();
+        let mut hero = hero::Model {
+            char_id: char.id,
+            class_id: 88,
+            count: 1,
+            expires_at: now + TimeDelta::days(30),
+            message: None,
+        };
+        hero::Model::save(&db_pool, hero.clone()).await.unwrap();
+        assert_eq!(
+            hero::Model::load_active(&db_pool, now).await.unwrap(),
+            vec![hero.clone()]
+        );
+        hero.message = Some("Glory".to_string());
+        hero.expires_at = now - TimeDelta::days(1);
+        hero::Model::save(&db_pool, hero.clone()).await.unwrap();
+        assert!(
+            hero::Model::load_active(&db_pool, now)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            hero::Model::find_by_char_id(&db_pool, char.id)
+                .await
+                .unwrap(),
+            Some(hero)
+        );
+
+        hero_diary::Model::add(&db_pool, char.id, now, "Became a hero.")
+            .await
+            .unwrap();
+        hero_diary::Model::add(&db_pool, char.id, now, "Won a game.")
+            .await
+            .unwrap();
+        let diary = hero_diary::Model::latest(&db_pool, char.id, 1)
+            .await
+            .unwrap();
+        assert_eq!(diary.len(), 1);
+        assert_eq!(diary[0].text, "Won a game.");
+    }
+}
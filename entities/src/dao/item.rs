@@ -1,5 +1,7 @@
+use crate::DBPool;
 use crate::entities::item;
-use sea_orm::{DeriveActiveEnum, EnumIter};
+use sea_orm::entity::prelude::*;
+use sea_orm::{ActiveValue, DbErr, DeriveActiveEnum, EnumIter, QuerySelect, TransactionTrait};
 use serde_json::Value;
 
 #[derive(EnumIter, DeriveActiveEnum, Clone, Debug, Copy, PartialEq, Eq, Default)]
@@ -94,4 +96,510 @@ impl item::Model {
         }
         None
     }
+
+    /// Inserts a new item, the id is assigned by the database.
+    pub async fn create(db_pool: &DBPool, item: item::Model) -> Result<item::Model, DbErr> {
+        let mut active_model: item::ActiveModel = item.into();
+        active_model.id = ActiveValue::NotSet;
+        active_model.insert(db_pool).await
+    }
+
+    /// Saves a new stack size, the item is deleted when nothing is left.
+    pub async fn update_count(db_pool: &DBPool, id: i32, count: i64) -> Result<(), DbErr> {
+        if count <= 0 {
+            item::Entity::delete_by_id(id).exec(db_pool).await?;
+            return Ok(());
+        }
+        let active_model = item::ActiveModel {
+            id: ActiveValue::Set(id),
+            count: ActiveValue::Set(count),
+            ..Default::default()
+        };
+        active_model.update(db_pool).await?;
+        Ok(())
+    }
+
+    /// Deletes the items of the kinds the char owns, wherever they are kept.
+    pub async fn delete_owned(
+        db_pool: &DBPool,
+        owner: i32,
+        item_ids: &[i32],
+    ) -> Result<u64, DbErr> {
+        let deleted = item::Entity::delete_many()
+            .filter(item::Column::Owner.eq(owner))
+            .filter(item::Column::ItemId.is_in(item_ids.iter().copied()))
+            .exec(db_pool)
+            .await?;
+        Ok(deleted.rows_affected)
+    }
+
+    /// Deletes every item of the kind, whoever owns it and wherever it is kept.
+    pub async fn delete_by_item_id(db_pool: &DBPool, item_id: i32) -> Result<u64, DbErr> {
+        let deleted = item::Entity::delete_many()
+            .filter(item::Column::ItemId.eq(item_id))
+            .exec(db_pool)
+            .await?;
+        Ok(deleted.rows_affected)
+    }
+
+    /// Saves an enchant in one transaction: the scroll stack gets its new size (deleted when
+    /// empty) and the item its new enchant level, if it changed.
+    pub async fn enchant(
+        db_pool: &DBPool,
+        (scroll_id, scroll_count): (i32, i64),
+        id: i32,
+        enchant_level: Option<i32>,
+    ) -> Result<(), DbErr> {
+        let txn = db_pool.begin().await?;
+        if scroll_count <= 0 {
+            item::Entity::delete_by_id(scroll_id).exec(&txn).await?;
+        } else {
+            let scroll = item::ActiveModel {
+                id: ActiveValue::Set(scroll_id),
+                count: ActiveValue::Set(scroll_count),
+                ..Default::default()
+            };
+            scroll.update(&txn).await?;
+        }
+        if let Some(enchant_level) = enchant_level {
+            let active_model = item::ActiveModel {
+                id: ActiveValue::Set(id),
+                enchant_level: ActiveValue::Set(enchant_level),
+                ..Default::default()
+            };
+            active_model.update(&txn).await?;
+        }
+        txn.commit().await
+    }
+
+    /// Saves a trade in one transaction: the stacks get their new sizes (the empty ones are
+    /// deleted) and the new items are inserted. Nothing is saved when any of the changes fails.
+    pub async fn exchange(
+        db_pool: &DBPool,
+        counts: &[(i32, i64)],
+        created: Vec<item::Model>,
+    ) -> Result<Vec<item::Model>, DbErr> {
+        let txn = db_pool.begin().await?;
+        for &(id, count) in counts {
+            if count <= 0 {
+                item::Entity::delete_by_id(id).exec(&txn).await?;
+            } else {
+                let active_model = item::ActiveModel {
+                    id: ActiveValue::Set(id),
+                    count: ActiveValue::Set(count),
+                    ..Default::default()
+                };
+                active_model.update(&txn).await?;
+            }
+        }
+        let mut inserted = Vec::with_capacity(created.len());
+        for item in created {
+            let mut active_model: item::ActiveModel = item.into();
+            active_model.id = ActiveValue::NotSet;
+            inserted.push(active_model.insert(&txn).await?);
+        }
+        txn.commit().await?;
+        Ok(inserted)
+    }
+
+    /// Items kept at the place, e.g. all items in the warehouse of a clan.
+    pub async fn items_at(db_pool: &DBPool, place: ItemPlace) -> Result<Vec<item::Model>, DbErr> {
+        let mut query = item::Entity::find().filter(item::Column::Loc.eq(place.loc));
+        query = match place.loc {
+            LocType::ClanWh | LocType::Pet => {
+                query.filter(item::Column::LocData.eq(place.loc_data))
+            }
+            _ => query.filter(item::Column::Owner.eq(place.owner)),
+        };
+        query.all(db_pool).await
+    }
+
+    /// Moves `(item id, count, stack id)` items from one place to another in one transaction.
+    /// A part of a stack is split into a new item, when the stack id is set the items are
+    /// added to that stack of the destination instead. The `(item id, count)` fee, if any, is
+    /// taken from the inventory of the owner of `from` in the same transaction. The rows are
+    /// locked while they are checked, so concurrent moves can't take the same items twice.
+    /// Nothing is moved if any of the items is missing or the fee can't be paid.
+    /// Returns the items as they are at the destination.
+    pub async fn transfer(
+        db_pool: &DBPool,
+        from: ItemPlace,
+        to: ItemPlace,
+        items: &[(i32, i64, Option<i32>)],
+        fee: Option<(i32, i64)>,
+    ) -> Result<Vec<item::Model>, DbErr> {
+        let txn = db_pool.begin().await?;
+        let mut moved = Vec::with_capacity(items.len());
+        for &(id, count, stack_id) in items {
+            let item = item::Entity::find_by_id(id)
+                .lock_exclusive()
+                .one(&txn)
+                .await?
+                .filter(|i| from.contains(i) && count > 0 && i.count >= count)
+                .ok_or_else(|| DbErr::RecordNotFound(format!("Item {id} can't be moved")))?;
+            if let Some(stack_id) = stack_id {
+                let stack = item::Entity::find_by_id(stack_id)
+                    .lock_exclusive()
+                    .one(&txn)
+                    .await?
+                    .filter(|s| to.contains(s) && s.item_id == item.item_id)
+                    .ok_or_else(|| DbErr::RecordNotFound(format!("No stack {stack_id}")))?;
+                let stack_count = stack.count + count;
+                let mut am: item::ActiveModel = stack.into();
+                am.count = ActiveValue::Set(stack_count);
+                moved.push(am.update(&txn).await?);
+                if item.count == count {
+                    item::Entity::delete_by_id(id).exec(&txn).await?;
+                } else {
+                    let left = item.count - count;
+                    let mut am: item::ActiveModel = item.into();
+                    am.count = ActiveValue::Set(left);
+                    am.update(&txn).await?;
+                }
+            } else if item.count == count {
+                let mut am: item::ActiveModel = item.into();
+                am.owner = ActiveValue::Set(to.owner);
+                am.loc = ActiveValue::Set(to.loc);
+                am.loc_data = ActiveValue::Set(to.loc_data);
+                moved.push(am.update(&txn).await?);
+            } else {
+                let mut split = item.clone();
+                let mut am: item::ActiveModel = item.into();
+                am.count = ActiveValue::Set(split.count - count);
+                am.update(&txn).await?;
+                split.count = count;
+                split.owner = to.owner;
+                split.loc = to.loc;
+                split.loc_data = to.loc_data;
+                let mut am: item::ActiveModel = split.into();
+                am.id = ActiveValue::NotSet;
+                moved.push(am.insert(&txn).await?);
+            }
+        }
+        if let Some((id, count)) = fee {
+            let payer = ItemPlace::new(from.owner, LocType::Inventory);
+            let stack = item::Entity::find_by_id(id)
+                .lock_exclusive()
+                .one(&txn)
+                .await?
+                .filter(|i| payer.contains(i) && i.count >= count)
+                .ok_or_else(|| DbErr::RecordNotFound(format!("Fee {count} can't be paid")))?;
+            if stack.count == count {
+                item::Entity::delete_by_id(id).exec(&txn).await?;
+            } else {
+                let left = stack.count - count;
+                let mut am: item::ActiveModel = stack.into();
+                am.count = ActiveValue::Set(left);
+                am.update(&txn).await?;
+            }
+        }
+        txn.commit().await?;
+        Ok(moved)
+    }
+}
+
+/// Where items are kept, e.g. the inventory or the warehouse of a char.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ItemPlace {
+    pub owner: i32,
+    pub loc: LocType,
+    pub loc_data: i32,
+}
+
+impl ItemPlace {
+    #[must_use]
+    pub fn new(owner: i32, loc: LocType) -> Self {
+        Self {
+            owner,
+            loc,
+            loc_data: 0,
+        }
+    }
+
+    /// The clan warehouse is shared by the members, its items are found by the clan id in
+    /// `loc_data` and the owner is only the char who deposited them.
+    #[must_use]
+    pub fn clan_warehouse(depositor: i32, clan_id: i32) -> Self {
+        Self {
+            owner: depositor,
+            loc: LocType::ClanWh,
+            loc_data: clan_id,
+        }
+    }
+
+    /// Items a pet carries go with its collar to whoever has it, they are found by the DB id of
+    /// the collar in `loc_data` and the owner is only the char who gave them.
+    #[must_use]
+    pub fn pet(owner: i32, control_item_id: i32) -> Self {
+        Self {
+            owner,
+            loc: LocType::Pet,
+            loc_data: control_item_id,
+        }
+    }
+
+    #[must_use]
+    pub fn contains(&self, item: &item::Model) -> bool {
+        if item.loc != self.loc {
+            return false;
+        }
+        match self.loc {
+            LocType::ClanWh | LocType::Pet => item.loc_data == self.loc_data,
+            _ => item.owner == self.owner,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::item;
+    use crate::test_factories::factories::{char_factory, item_factory, user_factory};
+    use test_utils::utils::get_test_db;
+
+    #[tokio::test]
+    async fn test_update_count() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let char = char_factory(&db_pool, |mut c| {
+            c.user_id = user.id;
+            c
+        })
+        .await;
+        let it = item_factory(&db_pool, |mut i| {
+            i.owner = char.id;
+            i.count = 10;
+            i.loc = LocType::Inventory;
+            i
+        })
+        .await;
+        item::Model::update_count(&db_pool, it.id, 4).await.unwrap();
+        let saved = item::Entity::find_by_id(it.id)
+            .one(&db_pool)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(saved.count, 4);
+        item::Model::update_count(&db_pool, it.id, 0).await.unwrap();
+        let deleted = item::Entity::find_by_id(it.id).one(&db_pool).await.unwrap();
+        assert!(deleted.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_delete_owned() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let char = char_factory(&db_pool, |mut c| {
+            c.user_id = user.id;
+            c
+        })
+        .await;
+        for (item_id, loc) in [
+            (6611, LocType::Inventory),
+            (6611, LocType::Warehouse),
+            (6612, LocType::Freight),
+            (57, LocType::Warehouse),
+        ] {
+            item_factory(&db_pool, |mut i| {
+                i.owner = char.id;
+                i.item_id = item_id;
+                i.loc = loc;
+                i
+            })
+            .await;
+        }
+        let deleted = item::Model::delete_owned(&db_pool, char.id, &[6611, 6612])
+            .await
+            .unwrap();
+        assert_eq!(deleted, 3);
+        let left = item::Entity::find().all(&db_pool).await.unwrap();
+        assert_eq!(left.len(), 1);
+        assert_eq!(left[0].item_id, 57);
+    }
+
+    #[tokio::test]
+    async fn test_enchant() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let char = char_factory(&db_pool, |mut c| {
+            c.user_id = user.id;
+            c
+        })
+        .await;
+        let it = item_factory(&db_pool, |mut i| {
+            i.owner = char.id;
+            i.item_id = 127;
+            i.count = 1;
+            i
+        })
+        .await;
+        let scroll = item_factory(&db_pool, |mut i| {
+            i.owner = char.id;
+            i.item_id = 955;
+            i.count = 1;
+            i
+        })
+        .await;
+        item::Model::enchant(&db_pool, (scroll.id, 0), it.id, Some(7))
+            .await
+            .unwrap();
+        let saved = item::Entity::find_by_id(it.id)
+            .one(&db_pool)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!((saved.enchant_level, saved.count), (7, 1));
+        let scroll = item::Entity::find_by_id(scroll.id).one(&db_pool).await.unwrap();
+        assert!(scroll.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_exchange() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let char = char_factory(&db_pool, |mut c| {
+            c.user_id = user.id;
+            c
+        })
+        .await;
+        let adena = item_factory(&db_pool, |mut i| {
+            i.owner = char.id;
+            i.item_id = 57;
+            i.count = 1000;
+            i
+        })
+        .await;
+        let sword = item_factory(&db_pool, |mut i| {
+            i.owner = char.id;
+            i.item_id = 1;
+            i.count = 1;
+            i
+        })
+        .await;
+        let created = item::Model::exchange(
+            &db_pool,
+            &[(adena.id, 500), (sword.id, 0)],
+            vec![item::Model {
+                owner: char.id,
+                item_id: 4,
+                count: 1,
+                enchant_level: 3,
+                ..Default::default()
+            }],
+        )
+        .await
+        .unwrap();
+        assert_eq!(created.len(), 1);
+        let items = item::Entity::find()
+            .filter(item::Column::Owner.eq(char.id))
+            .all(&db_pool)
+            .await
+            .unwrap();
+        assert_eq!(items.len(), 2);
+        assert!(items.iter().any(|i| i.id == adena.id && i.count == 500));
+        assert!(items.iter().any(|i| i.item_id == 4 && i.enchant_level == 3));
+
+        // the missing stack fails the whole exchange
+        let result = item::Model::exchange(
+            &db_pool,
+            &[(adena.id, 100), (sword.id, 1)],
+            vec![],
+        )
+        .await;
+        assert!(result.is_err());
+        let adena = item::Entity::find_by_id(adena.id).one(&db_pool).await.unwrap();
+        assert_eq!(adena.unwrap().count, 500);
+    }
+
+    #[tokio::test]
+    async fn test_transfer() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let char = char_factory(&db_pool, |mut c| {
+            c.user_id = user.id;
+            c
+        })
+        .await;
+        let inventory = ItemPlace::new(char.id, LocType::Inventory);
+        let warehouse = ItemPlace::new(char.id, LocType::Warehouse);
+        let adena = item_factory(&db_pool, |mut i| {
+            i.owner = char.id;
+            i.item_id = 57;
+            i.count = 1000;
+            i.loc = LocType::Inventory;
+            i
+        })
+        .await;
+        let sword = item_factory(&db_pool, |mut i| {
+            i.owner = char.id;
+            i.item_id = 1;
+            i.loc = LocType::Inventory;
+            i
+        })
+        .await;
+        // the fee can't be paid with the adena left, nothing is moved
+        let result = item::Model::transfer(
+            &db_pool,
+            inventory,
+            warehouse,
+            &[(adena.id, 300, None), (sword.id, 1, None)],
+            Some((adena.id, 701)),
+        )
+        .await;
+        assert!(result.is_err());
+        assert!(item::Model::items_at(&db_pool, warehouse).await.unwrap().is_empty());
+
+        let moved = item::Model::transfer(
+            &db_pool,
+            inventory,
+            warehouse,
+            &[(adena.id, 300, None), (sword.id, 1, None)],
+            Some((adena.id, 100)),
+        )
+        .await
+        .unwrap();
+        assert_eq!(moved.len(), 2);
+        assert!(moved.iter().all(|i| i.loc == LocType::Warehouse));
+        let stored = item::Model::items_at(&db_pool, warehouse).await.unwrap();
+        assert_eq!(stored.len(), 2);
+        let stored_adena = stored.iter().find(|i| i.item_id == 57).unwrap();
+        assert_eq!(stored_adena.count, 300);
+
+        // the rest of the adena goes to the stack in the warehouse
+        let moved = item::Model::transfer(
+            &db_pool,
+            inventory,
+            warehouse,
+            &[(adena.id, 600, Some(stored_adena.id))],
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(moved[0].id, stored_adena.id);
+        assert_eq!(moved[0].count, 900);
+        assert!(item::Model::items_at(&db_pool, inventory).await.unwrap().is_empty());
+
+        // the sword isn't in the inventory anymore
+        let result = item::Model::transfer(
+            &db_pool,
+            inventory,
+            warehouse,
+            &[(sword.id, 1, None)],
+            None,
+        )
+        .await;
+        assert!(result.is_err());
+
+        // clan warehouse items are found by the clan
+        let clan_wh = ItemPlace::clan_warehouse(char.id, 5);
+        item::Model::transfer(&db_pool, warehouse, clan_wh, &[(sword.id, 1, None)], None)
+            .await
+            .unwrap();
+        let clan_items = item::Model::items_at(&db_pool, ItemPlace::clan_warehouse(0, 5))
+            .await
+            .unwrap();
+        assert_eq!(clan_items.len(), 1);
+        assert!(clan_wh.contains(&clan_items[0]));
+        assert!(!ItemPlace::clan_warehouse(char.id, 6).contains(&clan_items[0]));
+    }
 }
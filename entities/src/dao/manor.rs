@@ -0,0 +1,203 @@
+use crate::DBPool;
+use crate::entities::{castle_manor_procure, castle_manor_production};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ColumnTrait, DbErr, EntityTrait, QueryFilter, TransactionTrait};
+
+#[allow(clippy::missing_errors_doc)]
+impl castle_manor_production::Model {
+    /// Seeds the castles sell in the current and the next manor period.
+    pub async fn load_all(db_pool: &DBPool) -> Result<Vec<castle_manor_production::Model>, DbErr> {
+        castle_manor_production::Entity::find().all(db_pool).await
+    }
+
+    pub async fn save(db_pool: &DBPool, row: castle_manor_production::Model) -> Result<(), DbErr> {
+        let active_model: castle_manor_production::ActiveModel = row.into();
+        castle_manor_production::Entity::insert(active_model)
+            .on_conflict(
+                OnConflict::columns([
+                    castle_manor_production::Column::CastleId,
+                    castle_manor_production::Column::SeedId,
+                    castle_manor_production::Column::NextPeriod,
+                ])
+                .update_columns([
+                    castle_manor_production::Column::Amount,
+                    castle_manor_production::Column::StartAmount,
+                    castle_manor_production::Column::Price,
+                ])
+                .to_owned(),
+            )
+            .exec(db_pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn delete(
+        db_pool: &DBPool,
+        castle_id: i32,
+        seed_id: i32,
+        next_period: bool,
+    ) -> Result<(), DbErr> {
+        castle_manor_production::Entity::delete_by_id((castle_id, seed_id, next_period))
+            .exec(db_pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Replaces the rows of both periods of the castle, when a period ends.
+    pub async fn replace_castle(
+        db_pool: &DBPool,
+        castle_id: i32,
+        rows: Vec<castle_manor_production::Model>,
+    ) -> Result<(), DbErr> {
+        let txn = db_pool.begin().await?;
+        castle_manor_production::Entity::delete_many()
+            .filter(castle_manor_production::Column::CastleId.eq(castle_id))
+            .exec(&txn)
+            .await?;
+        if !rows.is_empty() {
+            let active_models: Vec<castle_manor_production::ActiveModel> =
+                rows.into_iter().map(Into::into).collect();
+            castle_manor_production::Entity::insert_many(active_models)
+                .exec(&txn)
+                .await?;
+        }
+        txn.commit().await
+    }
+}
+
+#[allow(clippy::missing_errors_doc)]
+impl castle_manor_procure::Model {
+    /// Crops the castles buy in the current and the next manor period.
+    pub async fn load_all(db_pool: &DBPool) -> Result<Vec<castle_manor_procure::Model>, DbErr> {
+        castle_manor_procure::Entity::find().all(db_pool).await
+    }
+
+    pub async fn save(db_pool: &DBPool, row: castle_manor_procure::Model) -> Result<(), DbErr> {
+        let active_model: castle_manor_procure::ActiveModel = row.into();
+        castle_manor_procure::Entity::insert(active_model)
+            .on_conflict(
+                OnConflict::columns([
+                    castle_manor_procure::Column::CastleId,
+                    castle_manor_procure::Column::CropId,
+                    castle_manor_procure::Column::NextPeriod,
+                ])
+                .update_columns([
+                    castle_manor_procure::Column::Amount,
+                    castle_manor_procure::Column::StartAmount,
+                    castle_manor_procure::Column::Price,
+                ])
+                .to_owned(),
+            )
+            .exec(db_pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn delete(
+        db_pool: &DBPool,
+        castle_id: i32,
+        crop_id: i32,
+        next_period: bool,
+    ) -> Result<(), DbErr> {
+        castle_manor_procure::Entity::delete_by_id((castle_id, crop_id, next_period))
+            .exec(db_pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Replaces the rows of both periods of the castle, when a period ends.
+    pub async fn replace_castle(
+        db_pool: &DBPool,
+        castle_id: i32,
+        rows: Vec<castle_manor_procure::Model>,
+    ) -> Result<(), DbErr> {
+        let txn = db_pool.begin().await?;
+        castle_manor_procure::Entity::delete_many()
+            .filter(castle_manor_procure::Column::CastleId.eq(castle_id))
+            .exec(&txn)
+            .await?;
+        if !rows.is_empty() {
+            let active_models: Vec<castle_manor_procure::ActiveModel> =
+                rows.into_iter().map(Into::into).collect();
+            castle_manor_procure::Entity::insert_many(active_models)
+                .exec(&txn)
+                .await?;
+        }
+        txn.commit().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_utils::utils::get_test_db;
+
+    fn seed(seed_id: i32, next_period: bool) -> castle_manor_production::Model {
+        castle_manor_production::Model {
+            castle_id: 1,
+            seed_id,
+            next_period,
+            amount: 100,
+            start_amount: 100,
+            price: 120,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_manor_rows() {
+        let db_pool = get_test_db().await;
+        let mut current = seed(5016, false);
+        castle_manor_production::Model::save(&db_pool, current.clone())
+            .await
+            .unwrap();
+        castle_manor_production::Model::save(&db_pool, seed(5016, true))
+            .await
+            .unwrap();
+        current.amount = 40;
+        castle_manor_production::Model::save(&db_pool, current.clone())
+            .await
+            .unwrap();
+        let rows = castle_manor_production::Model::load_all(&db_pool)
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 2);
+        assert!(rows.contains(&current));
+
+        castle_manor_production::Model::delete(&db_pool, 1, 5016, true)
+            .await
+            .unwrap();
+        assert_eq!(
+            castle_manor_production::Model::load_all(&db_pool)
+                .await
+                .unwrap(),
+            vec![current]
+        );
+
+        let crop = castle_manor_procure::Model {
+            castle_id: 1,
+            crop_id: 5073,
+            next_period: false,
+            amount: 10,
+            start_amount: 10,
+            price: 400,
+        };
+        castle_manor_procure::Model::replace_castle(&db_pool, 1, vec![crop.clone()])
+            .await
+            .unwrap();
+        castle_manor_production::Model::replace_castle(&db_pool, 1, vec![seed(5017, true)])
+            .await
+            .unwrap();
+        assert_eq!(
+            castle_manor_production::Model::load_all(&db_pool)
+                .await
+                .unwrap(),
+            vec![seed(5017, true)]
+        );
+        assert_eq!(
+            castle_manor_procure::Model::load_all(&db_pool)
+                .await
+                .unwrap(),
+            vec![crop]
+        );
+    }
+}
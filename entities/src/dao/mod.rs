@@ -3,4 +3,20 @@ pub mod user;
 pub mod item;
 pub mod clan_ally;
 pub mod castle;
-mod char_skill;
\ No newline at end of file
+pub mod siege_clan;
+mod siege_schedule;
+mod cursed_weapon;
+mod hero;
+mod olympiad;
+mod pet;
+mod manor;
+mod char_skill;
+pub mod character_friend;
+mod character_henna;
+pub mod character_mail;
+mod character_macro;
+mod character_recipe;
+mod character_shortcut;
+mod character_subclass;
+mod teleport_bookmark;
+mod quest;
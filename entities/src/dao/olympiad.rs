@@ -0,0 +1,137 @@
+use crate::DBPool;
+use crate::entities::{olympiad_noble, olympiad_period};
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{DbErr, EntityTrait};
+
+#[allow(clippy::missing_errors_doc)]
+impl olympiad_noble::Model {
+    /// Points and match records of every noble taking part in the olympiad.
+    pub async fn load_all(db_pool: &DBPool) -> Result<Vec<olympiad_noble::Model>, DbErr> {
+        olympiad_noble::Entity::find().all(db_pool).await
+    }
+
+    pub async fn save(db_pool: &DBPool, noble: olympiad_noble::Model) -> Result<(), DbErr> {
+        let active_model: olympiad_noble::ActiveModel = noble.into();
+        olympiad_noble::Entity::insert(active_model)
+            .on_conflict(
+                OnConflict::column(olympiad_noble::Column::CharId)
+                    .update_columns([
+                        olympiad_noble::Column::ClassId,
+                        olympiad_noble::Column::Points,
+                        olympiad_noble::Column::Matches,
+                        olympiad_noble::Column::Wins,
+                        olympiad_noble::Column::Losses,
+                        olympiad_noble::Column::Draws,
+                    ])
+                    .to_owned(),
+            )
+            .exec(db_pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Starts a new period: every noble gets the same points and a clean record.
+    pub async fn reset_all(db_pool: &DBPool, points: i32) -> Result<(), DbErr> {
+        olympiad_noble::Entity::update_many()
+            .col_expr(olympiad_noble::Column::Points, Expr::value(points))
+            .col_expr(olympiad_noble::Column::Matches, Expr::value(0))
+            .col_expr(olympiad_noble::Column::Wins, Expr::value(0))
+            .col_expr(olympiad_noble::Column::Losses, Expr::value(0))
+            .col_expr(olympiad_noble::Column::Draws, Expr::value(0))
+            .exec(db_pool)
+            .await?;
+        Ok(())
+    }
+}
+
+#[allow(clippy::missing_errors_doc)]
+impl olympiad_period::Model {
+    /// The olympiad keeps a single row with its current period.
+    pub const ID: i32 = 1;
+
+    pub async fn load(db_pool: &DBPool) -> Result<Option<olympiad_period::Model>, DbErr> {
+        olympiad_period::Entity::find_by_id(Self::ID)
+            .one(db_pool)
+            .await
+    }
+
+    pub async fn save(db_pool: &DBPool, period: olympiad_period::Model) -> Result<(), DbErr> {
+        let active_model: olympiad_period::ActiveModel = period.into();
+        olympiad_period::Entity::insert(active_model)
+            .on_conflict(
+                OnConflict::column(olympiad_period::Column::Id)
+                    .update_columns([
+                        olympiad_period::Column::Period,
+                        olympiad_period::Column::EndsAt,
+                    ])
+                    .to_owned(),
+            )
+            .exec(db_pool)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_factories::factories::{char_factory, user_factory};
+    use test_utils::utils::get_test_db;
+
+    #[tokio::test]
+    async fn test_noble_points_and_period() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let char = char_factory(&db_pool, |mut c| {
+            c.user_id = user.id;
+            c
+        })
+        .await;
+        let mut noble = olympiad_noble::Model {
+            char_id: char.id,
+            class_id: 88,
+            points: 10,
+            ..Default::default()
+        };
+        olympiad_noble::Model::save(&db_pool, noble.clone())
+            .await
+            .unwrap();
+        noble.points = 13;
+        noble.matches = 1;
+        noble.wins = 1;
+        olympiad_noble::Model::save(&db_pool, noble.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            olympiad_noble::Model::load_all(&db_pool).await.unwrap(),
+            vec![noble.clone()]
+        );
+        olympiad_noble::Model::reset_all(&db_pool, 10)
+            .await
+            .unwrap();
+        let reset = olympiad_noble::Model::load_all(&db_pool).await.unwrap();
+        assert_eq!(
+            (reset[0].points, reset[0].matches, reset[0].wins),
+            (10, 0, 0)
+        );
+
+        assert!(
+            olympiad_period::Model::load(&db_pool)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        let period = olympiad_period::Model {
+            id: olympiad_period::Model::ID,
+            period: 2,
+            ends_at: chrono::Utc::now().fixed_offset(),
+        };
+        olympiad_period::Model::save(&db_pool, period.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            olympiad_period::Model::load(&db_pool).await.unwrap(),
+            Some(period)
+        );
+    }
+}
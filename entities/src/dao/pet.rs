@@ -0,0 +1,117 @@
+use crate::DBPool;
+use crate::entities::pet;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{DbErr, EntityTrait};
+
+#[allow(clippy::missing_errors_doc)]
+impl pet::Model {
+    /// The pet living in the collar, None before it is summoned the first time.
+    pub async fn find(db_pool: &DBPool, item_id: i32) -> Result<Option<pet::Model>, DbErr> {
+        pet::Entity::find_by_id(item_id).one(db_pool).await
+    }
+
+    pub async fn save(db_pool: &DBPool, pet: pet::Model) -> Result<(), DbErr> {
+        let active_model: pet::ActiveModel = pet.into();
+        pet::Entity::insert(active_model)
+            .on_conflict(
+                OnConflict::column(pet::Column::ItemId)
+                    .update_columns([
+                        pet::Column::Name,
+                        pet::Column::Level,
+                        pet::Column::Exp,
+                        pet::Column::CurHp,
+                        pet::Column::CurMp,
+                        pet::Column::Fed,
+                    ])
+                    .to_owned(),
+            )
+            .exec(db_pool)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dao::item::{ItemPlace, LocType};
+    use crate::entities::item;
+    use crate::test_factories::factories::{char_factory, item_factory, user_factory};
+    use test_utils::utils::get_test_db;
+
+    #[tokio::test]
+    async fn test_save_pet() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let char = char_factory(&db_pool, |mut c| {
+            c.user_id = user.id;
+            c
+        })
+        .await;
+        let collar = item_factory(&db_pool, |mut i| {
+            i.owner = char.id;
+            i.item_id = 2375;
+            i.count = 1;
+            i.loc = LocType::Inventory;
+            i
+        })
+        .await;
+        let food = item_factory(&db_pool, |mut i| {
+            i.owner = char.id;
+            i.item_id = 2515;
+            i.count = 10;
+            i.loc = LocType::Inventory;
+            i
+        })
+        .await;
+        assert!(
+            pet::Model::find(&db_pool, collar.id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        let mut pet = pet::Model {
+            item_id: collar.id,
+            name: None,
+            level: 15,
+            exp: 254_331,
+            cur_hp: 627.0,
+            cur_mp: 187.0,
+            fed: 1200,
+        };
+        pet::Model::save(&db_pool, pet.clone()).await.unwrap();
+        pet.level = 16;
+        pet.exp = 331_868;
+        pet.fed = 900;
+        pet::Model::save(&db_pool, pet.clone()).await.unwrap();
+        assert_eq!(
+            pet::Model::find(&db_pool, collar.id).await.unwrap(),
+            Some(pet)
+        );
+
+        // the pet carries the food, it is found by the collar
+        let inventory = ItemPlace::new(char.id, LocType::Inventory);
+        let pet_place = ItemPlace::pet(char.id, collar.id);
+        item::Model::transfer(&db_pool, inventory, pet_place, &[(food.id, 4, None)], None)
+            .await
+            .unwrap();
+        let carried = item::Model::items_at(&db_pool, pet_place).await.unwrap();
+        assert_eq!(carried.len(), 1);
+        assert_eq!(carried[0].count, 4);
+        assert!(pet_place.contains(&carried[0]));
+        assert!(!ItemPlace::pet(char.id, collar.id + 1).contains(&carried[0]));
+        // the next owner of the collar finds them too
+        let next_owner = ItemPlace::pet(char.id + 1, collar.id);
+        assert!(next_owner.contains(&carried[0]));
+        assert_eq!(
+            item::Model::items_at(&db_pool, next_owner).await.unwrap(),
+            carried
+        );
+        assert!(
+            item::Model::items_at(&db_pool, ItemPlace::pet(char.id, collar.id + 1))
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
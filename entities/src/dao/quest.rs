@@ -0,0 +1,76 @@
+use crate::DBPool;
+use crate::entities::quest;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ColumnTrait, DbErr, EntityTrait, QueryFilter};
+
+#[allow(clippy::missing_errors_doc)]
+impl quest::Model {
+    pub async fn char_quests(db_pool: &DBPool, char_id: i32) -> Result<Vec<quest::Model>, DbErr> {
+        quest::Entity::find()
+            .filter(quest::Column::CharId.eq(char_id))
+            .all(db_pool)
+            .await
+    }
+
+    /// Inserts the quest or replaces its variables.
+    pub async fn save(db_pool: &DBPool, quest: quest::Model) -> Result<(), DbErr> {
+        let active_model: quest::ActiveModel = quest.into();
+        quest::Entity::insert(active_model)
+            .on_conflict(
+                OnConflict::columns([quest::Column::CharId, quest::Column::Name])
+                    .update_columns([quest::Column::QuestId, quest::Column::Variables])
+                    .to_owned(),
+            )
+            .exec(db_pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn delete(db_pool: &DBPool, char_id: i32, name: &str) -> Result<(), DbErr> {
+        quest::Entity::delete_by_id((char_id, name.to_string()))
+            .exec(db_pool)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_factories::factories::{char_factory, user_factory};
+    use sea_orm::JsonValue;
+    use test_utils::utils::get_test_db;
+
+    #[tokio::test]
+    async fn test_save_and_delete() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let char = char_factory(&db_pool, |mut c| {
+            c.user_id = user.id;
+            c
+        })
+        .await;
+        let mut quest = quest::Model {
+            char_id: char.id,
+            quest_id: 1,
+            name: "Q00001_LettersOfLove".to_string(),
+            variables: serde_json::json!({"state": "started", "condition": 1}),
+        };
+        quest::Model::save(&db_pool, quest.clone()).await.unwrap();
+        quest.variables = serde_json::json!({"state": "started", "condition": 2});
+        quest::Model::save(&db_pool, quest.clone()).await.unwrap();
+        let saved = quest::Model::char_quests(&db_pool, char.id).await.unwrap();
+        assert_eq!(saved.len(), 1);
+        assert_eq!(saved[0].variables["condition"], JsonValue::from(2));
+
+        quest::Model::delete(&db_pool, char.id, &quest.name)
+            .await
+            .unwrap();
+        assert!(
+            quest::Model::char_quests(&db_pool, char.id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
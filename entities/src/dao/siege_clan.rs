@@ -0,0 +1,100 @@
+use crate::DBPool;
+use crate::entities::siege_clan;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ColumnTrait, DbErr, DeriveActiveEnum, EntityTrait, EnumIter, QueryFilter};
+
+#[derive(EnumIter, DeriveActiveEnum, Clone, Debug, Copy, PartialEq, Eq, Default, Hash)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "SiegeSide")]
+pub enum SiegeSide {
+    #[default]
+    #[sea_orm(string_value = "ATTACKER")]
+    Attacker,
+    #[sea_orm(string_value = "DEFENDER")]
+    Defender,
+}
+
+#[allow(clippy::missing_errors_doc)]
+impl siege_clan::Model {
+    /// Clans registered for the sieges of all castles.
+    pub async fn load_all(db_pool: &DBPool) -> Result<Vec<siege_clan::Model>, DbErr> {
+        siege_clan::Entity::find().all(db_pool).await
+    }
+
+    /// Registers the clan for the siege or moves it to the other side.
+    pub async fn save(db_pool: &DBPool, siege_clan: siege_clan::Model) -> Result<(), DbErr> {
+        let active_model: siege_clan::ActiveModel = siege_clan.into();
+        siege_clan::Entity::insert(active_model)
+            .on_conflict(
+                OnConflict::columns([siege_clan::Column::CastleId, siege_clan::Column::ClanId])
+                    .update_column(siege_clan::Column::Side)
+                    .to_owned(),
+            )
+            .exec(db_pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn delete(db_pool: &DBPool, castle_id: i32, clan_id: i32) -> Result<(), DbErr> {
+        siege_clan::Entity::delete_by_id((castle_id, clan_id))
+            .exec(db_pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Clears the registrations once the siege of the castle is over.
+    pub async fn delete_castle_clans(db_pool: &DBPool, castle_id: i32) -> Result<(), DbErr> {
+        siege_clan::Entity::delete_many()
+            .filter(siege_clan::Column::CastleId.eq(castle_id))
+            .exec(db_pool)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_factories::factories::{char_factory, clan_factory, user_factory};
+    use test_utils::utils::get_test_db;
+
+    #[tokio::test]
+    async fn test_register_and_clear() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let char = char_factory(&db_pool, |mut c| {
+            c.user_id = user.id;
+            c
+        })
+        .await;
+        let clan = clan_factory(&db_pool, |mut c| {
+            c.leader_id = char.id;
+            c
+        })
+        .await;
+        let mut registration = siege_clan::Model {
+            castle_id: 1,
+            clan_id: clan.id,
+            side: SiegeSide::Attacker,
+        };
+        siege_clan::Model::save(&db_pool, registration.clone())
+            .await
+            .unwrap();
+        registration.side = SiegeSide::Defender;
+        siege_clan::Model::save(&db_pool, registration.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            siege_clan::Model::load_all(&db_pool).await.unwrap(),
+            vec![registration]
+        );
+        siege_clan::Model::delete_castle_clans(&db_pool, 1)
+            .await
+            .unwrap();
+        assert!(
+            siege_clan::Model::load_all(&db_pool)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
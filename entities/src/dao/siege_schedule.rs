@@ -0,0 +1,56 @@
+use crate::DBPool;
+use crate::entities::siege_schedule;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{DbErr, EntityTrait};
+
+#[allow(clippy::missing_errors_doc)]
+impl siege_schedule::Model {
+    /// Sieges of the castles owned by the npcs.
+    pub async fn load_all(db_pool: &DBPool) -> Result<Vec<siege_schedule::Model>, DbErr> {
+        siege_schedule::Entity::find().all(db_pool).await
+    }
+
+    pub async fn save(db_pool: &DBPool, schedule: siege_schedule::Model) -> Result<(), DbErr> {
+        let active_model: siege_schedule::ActiveModel = schedule.into();
+        siege_schedule::Entity::insert(active_model)
+            .on_conflict(
+                OnConflict::column(siege_schedule::Column::CastleId)
+                    .update_columns([
+                        siege_schedule::Column::SiegeStartsAt,
+                        siege_schedule::Column::RegistrationEndsAt,
+                    ])
+                    .to_owned(),
+            )
+            .exec(db_pool)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_utils::utils::get_test_db;
+
+    #[tokio::test]
+    async fn test_save_schedule() {
+        let db_pool = get_test_db().await;
+        let now = chrono::Utc::now().fixed_offset();
+        let mut schedule = siege_schedule::Model {
+            castle_id: 1,
+            siege_starts_at: now,
+            registration_ends_at: now,
+        };
+        siege_schedule::Model::save(&db_pool, schedule.clone())
+            .await
+            .unwrap();
+        schedule.siege_starts_at = now + chrono::TimeDelta::days(14);
+        siege_schedule::Model::save(&db_pool, schedule.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            siege_schedule::Model::load_all(&db_pool).await.unwrap(),
+            vec![schedule]
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use crate::DBPool;
+use crate::entities::teleport_bookmark;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ColumnTrait, DbErr, EntityTrait, QueryFilter};
+
+#[allow(clippy::missing_errors_doc)]
+impl teleport_bookmark::Model {
+    pub async fn char_bookmarks(
+        db_pool: &DBPool,
+        char_id: i32,
+    ) -> Result<Vec<teleport_bookmark::Model>, DbErr> {
+        teleport_bookmark::Entity::find()
+            .filter(teleport_bookmark::Column::CharId.eq(char_id))
+            .all(db_pool)
+            .await
+    }
+
+    /// Inserts a new bookmark or overwrites the one with the same id.
+    pub async fn save(db_pool: &DBPool, bookmark: teleport_bookmark::Model) -> Result<(), DbErr> {
+        let active_model: teleport_bookmark::ActiveModel = bookmark.into();
+        teleport_bookmark::Entity::insert(active_model)
+            .on_conflict(
+                OnConflict::columns([
+                    teleport_bookmark::Column::CharId,
+                    teleport_bookmark::Column::Id,
+                ])
+                .update_columns([
+                    teleport_bookmark::Column::X,
+                    teleport_bookmark::Column::Y,
+                    teleport_bookmark::Column::Z,
+                    teleport_bookmark::Column::Icon,
+                    teleport_bookmark::Column::Tag,
+                    teleport_bookmark::Column::Name,
+                ])
+                .to_owned(),
+            )
+            .exec(db_pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn delete(db_pool: &DBPool, char_id: i32, id: i32) -> Result<(), DbErr> {
+        teleport_bookmark::Entity::delete_by_id((char_id, id))
+            .exec(db_pool)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_factories::factories::{char_factory, user_factory};
+    use test_utils::utils::get_test_db;
+
+    #[tokio::test]
+    async fn test_save_and_delete() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let char = char_factory(&db_pool, |mut c| {
+            c.user_id = user.id;
+            c
+        })
+        .await;
+        let mut bookmark = teleport_bookmark::Model {
+            char_id: char.id,
+            id: 1,
+            x: 10,
+            y: 20,
+            z: 30,
+            icon: 1,
+            tag: "T".to_string(),
+            name: "Home".to_string(),
+        };
+        teleport_bookmark::Model::save(&db_pool, bookmark.clone())
+            .await
+            .unwrap();
+        bookmark.name = "Town".to_string();
+        teleport_bookmark::Model::save(&db_pool, bookmark.clone())
+            .await
+            .unwrap();
+        let saved = teleport_bookmark::Model::char_bookmarks(&db_pool, char.id)
+            .await
+            .unwrap();
+        assert_eq!(saved, vec![bookmark]);
+        teleport_bookmark::Model::delete(&db_pool, char.id, 1)
+            .await
+            .unwrap();
+        let saved = teleport_bookmark::Model::char_bookmarks(&db_pool, char.id)
+            .await
+            .unwrap();
+        assert!(saved.is_empty());
+    }
+}
@@ -1,6 +1,8 @@
-use crate::entities::user::{Column, Entity, Model};
+use crate::entities::user::{ActiveModel, Column, Entity, Model};
 use anyhow::anyhow;
 use argon2::{Argon2, PasswordHash, PasswordVerifier};
+use chrono::{DateTime, Utc};
+use sea_orm::ActiveValue;
 use sea_orm::entity::prelude::*;
 use tokio::task::spawn_blocking;
 use tracing::error;
@@ -63,12 +65,31 @@ impl Model {
             .await?
             .ok_or_else(|| anyhow!("User not found {username}"))
     }
+
+    /// When the premium of the account runs out, None if it has none
+    #[must_use]
+    pub fn get_premium_expire(&self) -> Option<DateTime<Utc>> {
+        self.premium_expire.map(|t| t.with_timezone(&Utc))
+    }
+
+    /// # Errors
+    /// - when the account is not found or the update fails
+    pub async fn set_premium_expire(
+        db_pool: &DatabaseConnection,
+        username: &str,
+        premium_expire: Option<DateTime<Utc>>,
+    ) -> anyhow::Result<Model> {
+        let mut active_model: ActiveModel = Self::find_by_username(db_pool, username).await?.into();
+        active_model.premium_expire = ActiveValue::Set(premium_expire.map(|t| t.fixed_offset()));
+        Ok(active_model.update(db_pool).await?)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::dao::user;
     use crate::test_factories::factories::user_factory;
+    use chrono::DateTime;
     use test_utils::utils::get_test_db;
 
     #[tokio::test]
@@ -116,4 +137,19 @@ mod tests {
             .unwrap();
         assert_eq!(user.username, the_user.username);
     }
+    #[tokio::test]
+    async fn test_set_premium_expire() {
+        let db_pool = get_test_db().await;
+        let the_user = user_factory(&db_pool, |u| u).await;
+        assert!(the_user.get_premium_expire().is_none());
+        let expire = DateTime::from_timestamp(1_753_500_000, 0);
+        let user = user::Model::set_premium_expire(&db_pool, "admin", expire)
+            .await
+            .unwrap();
+        assert_eq!(user.get_premium_expire(), expire);
+        let user = user::Model::find_by_username(&db_pool, "admin")
+            .await
+            .unwrap();
+        assert_eq!(user.get_premium_expire(), expire);
+    }
 }
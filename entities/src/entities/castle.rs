@@ -16,6 +16,7 @@ pub struct Model {
     pub registration_ends_at: Option<DateTimeWithTimeZone>,
     pub show_npc_crest: bool,
     pub ticket_buy_count: i16,
+    pub tax_rate: i32,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
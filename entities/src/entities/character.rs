@@ -70,6 +70,9 @@ pub struct Model {
     pub faction: i8,
     pub pc_cafe_points: i32,
     pub clan_id: Option<i32>,
+    pub rec_left: u16,
+    pub rec_have: u16,
+    pub rec_bonus_time: u32,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
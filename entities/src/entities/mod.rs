@@ -3,11 +3,29 @@
 pub mod prelude;
 
 pub mod castle;
+pub mod castle_manor_procure;
+pub mod castle_manor_production;
 pub mod character;
+pub mod character_friend;
+pub mod character_henna;
+pub mod character_macro;
 pub mod character_mail;
+pub mod character_recipe;
+pub mod character_shortcut;
+pub mod character_subclass;
+pub mod character_subclass_effect;
 pub mod clan_ally;
 pub mod crest;
+pub mod cursed_weapon;
+pub mod hero;
+pub mod hero_diary;
 pub mod item;
+pub mod olympiad_noble;
+pub mod olympiad_period;
+pub mod pet;
 pub mod quest;
+pub mod siege_clan;
+pub mod siege_schedule;
 pub mod skill;
+pub mod teleport_bookmark;
 pub mod user;
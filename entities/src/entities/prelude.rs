@@ -1,11 +1,29 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.3
 
 pub use super::castle::Entity as Castle;
+pub use super::castle_manor_procure::Entity as CastleManorProcure;
+pub use super::castle_manor_production::Entity as CastleManorProduction;
 pub use super::character::Entity as Character;
+pub use super::character_friend::Entity as CharacterFriend;
+pub use super::character_henna::Entity as CharacterHenna;
+pub use super::character_macro::Entity as CharacterMacro;
 pub use super::character_mail::Entity as CharacterMail;
+pub use super::character_recipe::Entity as CharacterRecipe;
+pub use super::character_shortcut::Entity as CharacterShortcut;
+pub use super::character_subclass::Entity as CharacterSubclass;
+pub use super::character_subclass_effect::Entity as CharacterSubclassEffect;
 pub use super::clan_ally::Entity as ClanAlly;
 pub use super::crest::Entity as Crest;
+pub use super::cursed_weapon::Entity as CursedWeapon;
+pub use super::hero::Entity as Hero;
+pub use super::hero_diary::Entity as HeroDiary;
 pub use super::item::Entity as Item;
+pub use super::olympiad_noble::Entity as OlympiadNoble;
+pub use super::olympiad_period::Entity as OlympiadPeriod;
+pub use super::pet::Entity as Pet;
 pub use super::quest::Entity as Quest;
+pub use super::siege_clan::Entity as SiegeClan;
+pub use super::siege_schedule::Entity as SiegeSchedule;
 pub use super::skill::Entity as Skill;
+pub use super::teleport_bookmark::Entity as TeleportBookmark;
 pub use super::user::Entity as User;
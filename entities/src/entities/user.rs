@@ -13,6 +13,7 @@ pub struct Model {
     pub ban_ip: Option<String>,
     pub password: String,
     pub ban_duration: Option<i64>,
+    pub premium_expire: Option<DateTimeWithTimeZone>,
 }
 impl Display for Model {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
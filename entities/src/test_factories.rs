@@ -2,7 +2,7 @@
 pub mod factories {
     use crate::dao::item::LocType;
     use crate::entities;
-    use crate::entities::{character, user};
+    use crate::entities::{character, clan_ally, user};
     use sea_orm::{ActiveModelTrait, ActiveValue, IntoActiveModel, TryIntoModel};
     use test_utils::utils::DBPool;
 
@@ -81,4 +81,25 @@ pub mod factories {
             .try_into_model()
             .unwrap()
     }
+
+    /// The leader is required, there is no default char to lead the clan.
+    #[allow(clippy::missing_panics_doc)]
+    pub async fn clan_factory<F>(db_pool: &DBPool, create_fn: F) -> clan_ally::Model
+    where
+        F: FnOnce(clan_ally::Model) -> clan_ally::Model,
+    {
+        let model = clan_ally::Model {
+            name: "Clan".to_string(),
+            level: 5,
+            ..Default::default()
+        };
+        let mut active_model = create_fn(model).into_active_model();
+        active_model.id = ActiveValue::NotSet;
+        active_model
+            .insert(db_pool)
+            .await
+            .unwrap()
+            .try_into_model()
+            .unwrap()
+    }
 }
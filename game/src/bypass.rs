@@ -0,0 +1,713 @@
+//! Html dialogs and the bypass router.
+//!
+//! Dialog pages come from the [`HtmlCache`](l2_core::data::html::HtmlCache). The last page sent
+//! to the client is remembered with the actions it offers, and a link clicked by the player is
+//! only accepted when it is one of them, so a forged `RequestBypassToServer` can't reach npc
+//! services the player was never shown. Admin commands skip that check, they are for GMs only.
+
+use crate::packets::to_client;
+use crate::packets::to_client::NpcHtmlMessage;
+use crate::pl_client::PlayerClient;
+use anyhow::{Context, bail};
+use chrono::TimeDelta;
+use l2_core::data::html::{HtmlAction, HtmlCache, fill_html, fill_html_markup};
+use l2_core::data::quests::QuestEvent;
+use l2_core::game_objects::player::warehouse::WarehouseKind;
+use std::fmt::Write;
+use tracing::{info, warn};
+
+/// The npc a dialog was opened for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DialogNpc {
+    pub object_id: i32,
+    pub npc_id: i32,
+    pub name: String,
+}
+
+/// The dialog currently shown to the player.
+#[derive(Debug, Clone, Default)]
+pub struct HtmlDialog {
+    pub npc: Option<DialogNpc>,
+    pub actions: Vec<HtmlAction>,
+}
+
+impl HtmlDialog {
+    #[must_use]
+    pub fn allows_bypass(&self, command: &str) -> bool {
+        self.actions.iter().any(|a| a.allows_bypass(command))
+    }
+
+    #[must_use]
+    pub fn allows_link(&self, path: &str) -> bool {
+        self.actions
+            .iter()
+            .any(|a| matches!(a, HtmlAction::Link(p) if p == path))
+    }
+}
+
+/// Services of an npc, `npc_<objectId>_<command>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NpcCommand {
+    /// Chat page, 0 is the first page
+    Chat(u32),
+    /// Quests the npc takes part in
+    Quest,
+    QuestAccept(i32),
+    TeleportList,
+    Teleport(i32),
+    Buy(i32),
+    Multisell(i32),
+    /// Warehouse windows
+    DepositP,
+    WithdrawP,
+    DepositC,
+    WithdrawC,
+    /// Freight to the other chars of the account
+    PackageDeposit,
+    PackageWithdraw,
+    Subclass(SubclassCommand),
+    Siege(SiegeCommand),
+    Olympiad(OlympiadCommand),
+    Manor(ManorCommand),
+    Hero(HeroCommand),
+}
+
+/// Subclass services of village masters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubclassCommand {
+    Menu,
+    AddList,
+    /// Class id of the new subclass
+    Add(i32),
+    ChangeList,
+    /// Class index to make active
+    Change(i32),
+    CancelList,
+    /// Class index of the subclass to remove
+    Cancel(i32),
+}
+
+/// Services of castle siege managers and chamberlains
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SiegeCommand {
+    Info,
+    RegisterAttacker,
+    RegisterDefender,
+    Unregister,
+    TaxInfo,
+    /// Tax rate in percent
+    SetTax(i32),
+}
+
+/// Services of olympiad managers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OlympiadCommand {
+    /// Points and record of the noble in the current period
+    Info,
+    RegisterClassed,
+    RegisterNonClassed,
+    Unregister,
+    /// Running games to watch
+    Games,
+    /// Stadium id of the game to watch
+    Watch(i32),
+}
+
+/// Services of the monument of heroes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeroCommand {
+    /// Current heroes with their messages
+    List,
+    /// Char id of the hero
+    Diary(i32),
+    Weapons,
+    /// Item id of the hero weapon to take
+    TakeWeapon(i32),
+}
+
+/// Services of manor managers and castle chamberlains
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManorCommand {
+    /// Seeds and crops of the current period
+    Info,
+    /// Settings of the next period, for the castle lord
+    Next,
+    SetSeed { seed_id: i32, amount: i32, price: i32 },
+    SetCrop { crop_id: i32, amount: i32, price: i32 },
+    BuySeed { seed_id: i32, count: i32 },
+    SellCrop { crop_id: i32, count: i32 },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Bypass {
+    Npc {
+        object_id: i32,
+        command: NpcCommand,
+    },
+    /// `admin_<command> [args]`
+    Admin {
+        command: String,
+        args: Vec<String>,
+    },
+}
+
+impl Bypass {
+    pub fn parse(bypass: &str) -> anyhow::Result<Self> {
+        if let Some(admin) = bypass.strip_prefix("admin_") {
+            let mut parts = admin.split_whitespace();
+            let command = parts.next().context("Empty admin command")?.to_string();
+            return Ok(Self::Admin {
+                command,
+                args: parts.map(ToString::to_string).collect(),
+            });
+        }
+        let Some(npc) = bypass.strip_prefix("npc_") else {
+            bail!("Unknown bypass");
+        };
+        let (object_id, command) = npc.split_once('_').context("No npc command")?;
+        let mut parts = command.split_whitespace();
+        let name = parts.next().context("No npc command")?;
+        let mut arg =
+            || -> anyhow::Result<i32> { Ok(parts.next().context("Missing argument")?.parse()?) };
+        let command = match name {
+            "Chat" => NpcCommand::Chat(u32::try_from(arg()?)?),
+            "Quest" => NpcCommand::Quest,
+            "QuestAccept" => NpcCommand::QuestAccept(arg()?),
+            "TeleportList" => NpcCommand::TeleportList,
+            "Teleport" => NpcCommand::Teleport(arg()?),
+            "Buy" => NpcCommand::Buy(arg()?),
+            "Multisell" => NpcCommand::Multisell(arg()?),
+            "DepositP" => NpcCommand::DepositP,
+            "WithdrawP" => NpcCommand::WithdrawP,
+            "DepositC" => NpcCommand::DepositC,
+            "WithdrawC" => NpcCommand::WithdrawC,
+            "PackageDeposit" => NpcCommand::PackageDeposit,
+            "PackageWithdraw" => NpcCommand::PackageWithdraw,
+            "Subclass" => NpcCommand::Subclass(SubclassCommand::Menu),
+            "SubclassAddList" => NpcCommand::Subclass(SubclassCommand::AddList),
+            "SubclassAdd" => NpcCommand::Subclass(SubclassCommand::Add(arg()?)),
+            "SubclassChangeList" => NpcCommand::Subclass(SubclassCommand::ChangeList),
+            "SubclassChange" => NpcCommand::Subclass(SubclassCommand::Change(arg()?)),
+            "SubclassCancelList" => NpcCommand::Subclass(SubclassCommand::CancelList),
+            "SubclassCancel" => NpcCommand::Subclass(SubclassCommand::Cancel(arg()?)),
+            "SiegeInfo" => NpcCommand::Siege(SiegeCommand::Info),
+            "SiegeAttack" => NpcCommand::Siege(SiegeCommand::RegisterAttacker),
+            "SiegeDefend" => NpcCommand::Siege(SiegeCommand::RegisterDefender),
+            "SiegeUnregister" => NpcCommand::Siege(SiegeCommand::Unregister),
+            "CastleTax" => NpcCommand::Siege(SiegeCommand::TaxInfo),
+            "CastleSetTax" => NpcCommand::Siege(SiegeCommand::SetTax(arg()?)),
+            "Olympiad" => NpcCommand::Olympiad(OlympiadCommand::Info),
+            "OlympiadClassed" => NpcCommand::Olympiad(OlympiadCommand::RegisterClassed),
+            "OlympiadNonClassed" => NpcCommand::Olympiad(OlympiadCommand::RegisterNonClassed),
+            "OlympiadUnregister" => NpcCommand::Olympiad(OlympiadCommand::Unregister),
+            "OlympiadGames" => NpcCommand::Olympiad(OlympiadCommand::Games),
+            "OlympiadWatch" => NpcCommand::Olympiad(OlympiadCommand::Watch(arg()?)),
+            "Manor" => NpcCommand::Manor(ManorCommand::Info),
+            "ManorNext" => NpcCommand::Manor(ManorCommand::Next),
+            "ManorSetSeed" => NpcCommand::Manor(ManorCommand::SetSeed {
+                seed_id: arg()?,
+                amount: arg()?,
+                price: arg()?,
+            }),
+            "ManorSetCrop" => NpcCommand::Manor(ManorCommand::SetCrop {
+                crop_id: arg()?,
+                amount: arg()?,
+                price: arg()?,
+            }),
+            "ManorBuySeed" => NpcCommand::Manor(ManorCommand::BuySeed {
+                seed_id: arg()?,
+                count: arg()?,
+            }),
+            "ManorSellCrop" => NpcCommand::Manor(ManorCommand::SellCrop {
+                crop_id: arg()?,
+                count: arg()?,
+            }),
+            "Heroes" => NpcCommand::Hero(HeroCommand::List),
+            "HeroDiary" => NpcCommand::Hero(HeroCommand::Diary(arg()?)),
+            "HeroWeapons" => NpcCommand::Hero(HeroCommand::Weapons),
+            "HeroWeapon" => NpcCommand::Hero(HeroCommand::TakeWeapon(arg()?)),
+            _ => bail!("Unknown npc command {name}"),
+        };
+        Ok(Self::Npc {
+            object_id: object_id.parse()?,
+            command,
+        })
+    }
+}
+
+impl PlayerClient {
+    /// Sends the page from the html cache, `%objectId%` and `%npcName%` are filled for the npc.
+    pub async fn show_html(
+        &mut self,
+        npc: Option<DialogNpc>,
+        path: &str,
+        vars: &[(&str, &str)],
+    ) -> anyhow::Result<()> {
+        let Some(page) = self.controller.html_cache.get(path) else {
+            warn!("Missing html page {path}");
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        };
+        self.send_html(npc, &page, vars, &[]).await
+    }
+
+    /// Like [`Self::show_html`] with lists or other markup made by the server, see
+    /// [`fill_html_markup`].
+    pub async fn show_html_markup(
+        &mut self,
+        npc: Option<DialogNpc>,
+        path: &str,
+        vars: &[(&str, &str)],
+        markup: &[(&str, &str)],
+    ) -> anyhow::Result<()> {
+        let Some(page) = self.controller.html_cache.get(path) else {
+            warn!("Missing html page {path}");
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        };
+        self.send_html(npc, &page, vars, markup).await
+    }
+
+    /// Sends the page and remembers the actions it offers. `vars` are text and get escaped,
+    /// `markup` is put in as is.
+    pub async fn send_html(
+        &mut self,
+        npc: Option<DialogNpc>,
+        html: &str,
+        vars: &[(&str, &str)],
+        markup: &[(&str, &str)],
+    ) -> anyhow::Result<()> {
+        let object_id = npc.as_ref().map_or(0, |n| n.object_id);
+        let object_id_var = object_id.to_string();
+        // caller values go first, generated lists may use the npc placeholders as well
+        let mut html = fill_html_markup(html, markup);
+        html = fill_html(&html, vars);
+        html = fill_html(&html, &[("objectId", &object_id_var)]);
+        if let Some(npc) = &npc {
+            html = fill_html(&html, &[("npcName", &npc.name)]);
+        }
+        self.html_dialog = HtmlDialog {
+            npc,
+            actions: HtmlAction::extract(&html),
+        };
+        self.send_packet(NpcHtmlMessage::new(object_id, &html, 0)?)
+            .await
+    }
+
+    /// Chat page of the npc, `default/<npcId>.htm` or `default/<npcId>-<page>.htm`.
+    pub async fn show_npc_chat(&mut self, npc: DialogNpc, page: u32) -> anyhow::Result<()> {
+        let mut path = if page == 0 {
+            format!("default/{}.htm", npc.npc_id)
+        } else {
+            format!("default/{}-{page}.htm", npc.npc_id)
+        };
+        if page == 0 && !self.controller.html_cache.exists(&path) {
+            path = "default/npc.htm".to_string();
+        }
+        self.show_html(Some(npc), &path, &[]).await
+    }
+
+    /// Link clicked in a dialog, `RequestBypassToServer`.
+    pub async fn handle_bypass(&mut self, bypass: &str) -> anyhow::Result<()> {
+        let bypass = bypass.trim();
+        let parsed = match Bypass::parse(bypass) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                warn!("Invalid bypass {bypass}: {e}");
+                return self.send_packet(to_client::ActionFailed::normal()?).await;
+            }
+        };
+        let (object_id, command) = match parsed {
+            Bypass::Admin { command, args } => {
+                return self.handle_admin_command(&command, &args).await;
+            }
+            Bypass::Npc { object_id, command } => (object_id, command),
+        };
+        let npc = self
+            .html_dialog
+            .npc
+            .clone()
+            .filter(|npc| npc.object_id == object_id);
+        let Some(npc) = npc.filter(|_| self.html_dialog.allows_bypass(bypass)) else {
+            warn!("Bypass {bypass} was not offered to the player, possible cheating");
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        };
+        self.handle_npc_command(npc, command).await
+    }
+
+    /// Service of the npc the player talks to.
+    pub async fn handle_npc_command(
+        &mut self,
+        npc: DialogNpc,
+        command: NpcCommand,
+    ) -> anyhow::Result<()> {
+        match command {
+            NpcCommand::Chat(page) => self.show_npc_chat(npc, page).await,
+            NpcCommand::Quest => self.talk_about_quests(npc).await,
+            NpcCommand::QuestAccept(quest_id) => self.accept_quest_from_npc(npc, quest_id).await,
+            NpcCommand::TeleportList => self.show_teleport_list(npc).await,
+            NpcCommand::Teleport(location_id) => {
+                self.teleport_by_list(npc.npc_id, location_id).await
+            }
+            NpcCommand::Buy(list_id) => self.show_buy_list(&npc, list_id).await,
+            NpcCommand::Multisell(list_id) => self.show_multisell(&npc, list_id).await,
+            NpcCommand::DepositP => self.show_deposit_list(WarehouseKind::Private).await,
+            NpcCommand::WithdrawP => self.show_withdraw_list(WarehouseKind::Private).await,
+            NpcCommand::DepositC => self.show_deposit_list(WarehouseKind::Clan).await,
+            NpcCommand::WithdrawC => self.show_withdraw_list(WarehouseKind::Clan).await,
+            NpcCommand::PackageDeposit => self.show_package_to_list().await,
+            NpcCommand::PackageWithdraw => self.show_withdraw_list(WarehouseKind::Freight).await,
+            NpcCommand::Subclass(command) => self.handle_subclass_command(npc, command).await,
+            NpcCommand::Siege(command) => self.handle_siege_command(npc, command).await,
+            NpcCommand::Olympiad(command) => self.handle_olympiad_command(npc, command).await,
+            NpcCommand::Manor(command) => self.handle_manor_command(npc, command).await,
+            NpcCommand::Hero(command) => self.handle_hero_command(npc, command).await,
+        }
+    }
+
+    /// Html link clicked in a dialog, `RequestLinkHtml`.
+    pub async fn handle_link(&mut self, path: &str) -> anyhow::Result<()> {
+        if !HtmlCache::is_valid_path(path) || !self.html_dialog.allows_link(path) {
+            warn!("Link {path} was not offered to the player, possible cheating");
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        }
+        let npc = self.html_dialog.npc.clone();
+        self.show_html(npc, path, &[]).await
+    }
+
+    /// Moves the quests waiting for this npc forward, otherwise lists the quests it offers.
+    async fn talk_about_quests(&mut self, npc: DialogNpc) -> anyhow::Result<()> {
+        let controller = self.controller.clone();
+        let reacted = self
+            .notify_quest_event(QuestEvent::Talk { npc_id: npc.npc_id })
+            .await?;
+        let player = self.try_get_selected_char()?;
+        let in_progress = reacted.first().copied().or_else(|| {
+            player
+                .quests
+                .iter()
+                .filter(|q| q.is_started())
+                .map(|q| q.get_id())
+                .find(|id| {
+                    controller
+                        .quests
+                        .get_quest(*id)
+                        .is_some_and(|t| t.start_npc == npc.npc_id)
+                })
+        });
+        if let Some(quest_id) = in_progress {
+            let name = controller
+                .quests
+                .get_quest(quest_id)
+                .map_or_else(String::new, |t| t.name.clone());
+            let page = if player.get_quest(quest_id).is_some_and(|q| q.is_started()) {
+                "quests/progress.htm"
+            } else {
+                "quests/completed.htm"
+            };
+            return self.show_html(Some(npc), page, &[("quest", &name)]).await;
+        }
+        let mut links = String::new();
+        for template in controller.quests.quests_of_npc(npc.npc_id) {
+            let done = player
+                .get_quest(template.id)
+                .is_some_and(|q| q.is_completed());
+            if !done || template.repeatable {
+                writeln!(
+                    links,
+                    "<a action=\"bypass -h npc_{}_QuestAccept {}\">{}</a><br>",
+                    npc.object_id, template.id, template.name
+                )?;
+            }
+        }
+        if links.is_empty() {
+            return self.show_html(Some(npc), "quests/none.htm", &[]).await;
+        }
+        self.show_html_markup(Some(npc), "quests/list.htm", &[], &[("quests", &links)])
+            .await
+    }
+
+    async fn accept_quest_from_npc(&mut self, npc: DialogNpc, quest_id: i32) -> anyhow::Result<()> {
+        let Some(template) = self.controller.quests.get_quest(quest_id).cloned() else {
+            warn!("Unknown quest {quest_id}");
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        };
+        if template.start_npc != npc.npc_id {
+            warn!("Quest {quest_id} is not offered by npc {}", npc.npc_id);
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        }
+        let page = if self.start_quest(quest_id).await? {
+            "quests/accepted.htm"
+        } else {
+            "quests/refused.htm"
+        };
+        self.show_html(Some(npc), page, &[("quest", &template.name)])
+            .await
+    }
+
+    async fn show_teleport_list(&mut self, npc: DialogNpc) -> anyhow::Result<()> {
+        let controller = self.controller.clone();
+        let Some(list) = controller.teleports.get_list(npc.npc_id) else {
+            warn!("Npc {} has no teleports", npc.npc_id);
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        };
+        let mut locations = String::new();
+        for location in &list.locations {
+            writeln!(
+                locations,
+                "<a action=\"bypass -h npc_{}_Teleport {}\">{} - {} Adena</a><br>",
+                npc.object_id, location.id, location.name, location.price
+            )?;
+        }
+        self.show_html_markup(
+            Some(npc),
+            "teleport/list.htm",
+            &[],
+            &[("locations", &locations)],
+        )
+        .await
+    }
+
+    async fn handle_admin_command(&mut self, command: &str, args: &[String]) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        if !player.is_gm() {
+            warn!(
+                "{} tried to use admin command {command} without rights",
+                player.char_model.name
+            );
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        }
+        info!(
+            "{} used admin command {command} {args:?}",
+            player.char_model.name
+        );
+        let parsed: Result<Vec<i64>, _> = args.iter().map(|a| a.parse::<i64>()).collect();
+        match (command, parsed.as_deref()) {
+            ("teleport", Ok([x, y, z])) => {
+                self.teleport_to(i32::try_from(*x)?, i32::try_from(*y)?, i32::try_from(*z)?)
+                    .await
+            }
+            ("give_item", Ok([item_id, count])) => {
+                self.add_item_by_item_id(i32::try_from(*item_id)?, *count)
+                    .await
+            }
+            ("html", _) if args.len() == 1 => self.show_html(None, &args[0], &[]).await,
+            ("time_skip", Ok([minutes])) if *minutes < 0 => {
+                warn!("Game time can't be moved back, {minutes} minutes asked");
+                self.send_packet(to_client::ActionFailed::normal()?).await
+            }
+            ("time_skip", Ok([minutes])) => {
+                self.controller.clock.advance(TimeDelta::minutes(*minutes));
+                info!("Game time is now {}", self.controller.clock.now());
+                Ok(())
+            }
+            ("premium", _) if args.len() == 2 => {
+                let days = args[1].parse::<i64>()?;
+                self.grant_premium(&args[0], days).await
+            }
+            ("hero", _) if args.len() == 2 => {
+                let days = args[1].parse::<i64>()?;
+                self.grant_hero(&args[0], days).await
+            }
+            ("reload_html", _) => {
+                self.controller.html_cache.reload();
+                Ok(())
+            }
+            _ => {
+                warn!("Unknown admin command {command} {args:?}");
+                self.send_packet(to_client::ActionFailed::normal()?).await
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packets::from_client::request_bypass_to_server::RequestBypassToServer;
+    use crate::pl_client::GetCharInfo;
+    use crate::test_utils::test::{on_actor, spawn_player};
+    use test_utils::utils::get_test_db;
+
+    fn roxxy() -> DialogNpc {
+        DialogNpc {
+            object_id: 1_000_000,
+            npc_id: 30006,
+            name: "Roxxy".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_bypass() {
+        assert_eq!(
+            Bypass::parse("npc_7_Chat 1").unwrap(),
+            Bypass::Npc {
+                object_id: 7,
+                command: NpcCommand::Chat(1)
+            }
+        );
+        assert_eq!(
+            Bypass::parse("npc_7_Quest").unwrap(),
+            Bypass::Npc {
+                object_id: 7,
+                command: NpcCommand::Quest
+            }
+        );
+        assert_eq!(
+            Bypass::parse("admin_teleport 1 2 3").unwrap(),
+            Bypass::Admin {
+                command: "teleport".to_string(),
+                args: vec!["1".to_string(), "2".to_string(), "3".to_string()]
+            }
+        );
+        assert_eq!(
+            Bypass::parse("npc_7_Multisell 1001").unwrap(),
+            Bypass::Npc {
+                object_id: 7,
+                command: NpcCommand::Multisell(1001)
+            }
+        );
+        assert_eq!(
+            Bypass::parse("npc_7_SubclassChange 2").unwrap(),
+            Bypass::Npc {
+                object_id: 7,
+                command: NpcCommand::Subclass(SubclassCommand::Change(2))
+            }
+        );
+        assert_eq!(
+            Bypass::parse("npc_7_CastleSetTax 10").unwrap(),
+            Bypass::Npc {
+                object_id: 7,
+                command: NpcCommand::Siege(SiegeCommand::SetTax(10))
+            }
+        );
+        assert_eq!(
+            Bypass::parse("npc_7_OlympiadWatch 3").unwrap(),
+            Bypass::Npc {
+                object_id: 7,
+                command: NpcCommand::Olympiad(OlympiadCommand::Watch(3))
+            }
+        );
+        assert_eq!(
+            Bypass::parse("npc_7_HeroWeapon 6611").unwrap(),
+            Bypass::Npc {
+                object_id: 7,
+                command: NpcCommand::Hero(HeroCommand::TakeWeapon(6611))
+            }
+        );
+        assert_eq!(
+            Bypass::parse("npc_7_ManorSetSeed 5016 100 120").unwrap(),
+            Bypass::Npc {
+                object_id: 7,
+                command: NpcCommand::Manor(ManorCommand::SetSeed {
+                    seed_id: 5016,
+                    amount: 100,
+                    price: 120
+                })
+            }
+        );
+        assert!(Bypass::parse("npc_7_ManorSellCrop 5073").is_err());
+        assert!(Bypass::parse("npc_7_SubclassAdd").is_err());
+        assert!(Bypass::parse("npc_7_Teleport").is_err());
+        assert!(Bypass::parse("npc_x_Quest").is_err());
+        assert!(Bypass::parse("npc_7_Unknown").is_err());
+        assert!(Bypass::parse("_bbshome").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_only_offered_bypass_is_accepted() {
+        let db_pool = get_test_db().await;
+        let (_, actor, _conn) = spawn_player(&db_pool, 2).await;
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        let start = (player.get_x(), player.get_y());
+
+        // nothing was shown yet, the forged teleport is rejected
+        actor
+            .ask(RequestBypassToServer {
+                command: "npc_1000000_Teleport 1".to_string(),
+            })
+            .await
+            .unwrap();
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert_eq!((player.get_x(), player.get_y()), start);
+        let actions = on_actor(&actor, |pl| {
+            Box::pin(async move {
+                pl.show_npc_chat(roxxy(), 0).await?;
+                Ok(pl.html_dialog.actions.clone())
+            })
+        })
+        .await;
+        assert!(actions.contains(&HtmlAction::Bypass("npc_1000000_TeleportList".to_string())));
+        actor
+            .ask(RequestBypassToServer {
+                command: "npc_1000000_TeleportList".to_string(),
+            })
+            .await
+            .unwrap();
+        let dialog = on_actor(&actor, |pl| {
+            Box::pin(async move { Ok(pl.html_dialog.clone()) })
+        })
+        .await;
+        assert_eq!(dialog.npc, Some(roxxy()));
+        assert_eq!(dialog.actions.len(), 5);
+        assert!(dialog.allows_bypass("npc_1000000_Teleport 1"));
+        // another npc
+        assert!(!dialog.allows_bypass("npc_1000001_Teleport 1"));
+        actor
+            .ask(RequestBypassToServer {
+                command: "npc_1000001_Teleport 1".to_string(),
+            })
+            .await
+            .unwrap();
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert_eq!((player.get_x(), player.get_y()), start);
+
+        // free teleport to the village
+        actor
+            .ask(RequestBypassToServer {
+                command: "npc_1000000_Teleport 1".to_string(),
+            })
+            .await
+            .unwrap();
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert_eq!((player.get_x(), player.get_y()), (-84318, 244579));
+    }
+
+    #[tokio::test]
+    async fn test_accept_quest_in_dialog() {
+        let db_pool = get_test_db().await;
+        let (_, actor, _conn) = spawn_player(&db_pool, 2).await;
+        let npc = DialogNpc {
+            object_id: 7,
+            npc_id: 30048,
+            name: "Darin".to_string(),
+        };
+        let dialog = on_actor(&actor, move |pl| {
+            Box::pin(async move {
+                pl.show_npc_chat(npc, 0).await?;
+                pl.handle_bypass("npc_7_Quest").await?;
+                Ok(pl.html_dialog.clone())
+            })
+        })
+        .await;
+        assert!(dialog.allows_bypass("npc_7_QuestAccept 1"));
+        // quests of another npc can't be accepted here
+        on_actor(&actor, |pl| {
+            Box::pin(pl.handle_bypass("npc_7_QuestAccept 261"))
+        })
+        .await;
+        assert!(
+            actor
+                .ask(GetCharInfo)
+                .await
+                .unwrap()
+                .get_quest(261)
+                .is_none()
+        );
+
+        on_actor(&actor, |pl| {
+            Box::pin(pl.handle_bypass("npc_7_QuestAccept 1"))
+        })
+        .await;
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert!(player.get_quest(1).unwrap().is_started());
+    }
+}
@@ -1,20 +1,39 @@
 use crate::ls_client::LoginServerClient;
-use crate::managers::ClanAllyManager;
+use crate::managers::{
+    ClanAllyManager, CursedWeaponsManager, GameClock, ManorManager, OlympiadManager,
+    RecommendationManager, SiegeManager, VitalityManager, ZoneManager,
+};
 use crate::packets::to_client::{CharInfo, RelationChanged};
-use crate::pl_client::{GetCharInfo, PlayerClient};
+use crate::pl_client::{BoxedClosure, DoLater, GetCharInfo, PlayerClient};
 use anyhow::anyhow;
+use chrono::{DateTime, Utc};
 use dashmap::DashMap;
 use entities::DBPool;
-use entities::entities::character;
+use entities::entities::hero;
 use kameo::actor::ActorRef;
 use l2_core::config::gs::GSServerConfig;
 use l2_core::config::traits::{ConfigDirLoader, ConfigFileLoader};
 use l2_core::data::action_list::ActionList;
 use l2_core::data::base_stat::BaseStat;
+use l2_core::data::buy_lists::BuyListData;
+use l2_core::data::castles::CastleData;
 use l2_core::data::char_template::ClassTemplates;
+use l2_core::data::cursed_weapons::CursedWeaponsData;
+use l2_core::data::enchant::EnchantData;
+use l2_core::data::fishing::FishData;
 use l2_core::data::exp_table::ExpTable;
+use l2_core::data::hennas::HennaData;
+use l2_core::data::html::HtmlCache;
+use l2_core::data::items::ItemsData;
+use l2_core::data::multisell::MultisellData;
+use l2_core::data::manor::ManorData;
+use l2_core::data::olympiad::OlympiadData;
+use l2_core::data::quests::QuestData;
 use l2_core::data::skill_tree_data::SkillTreesData;
 use l2_core::data::skills::SkillsData;
+use l2_core::data::recipes::RecipeData;
+use l2_core::data::summons::SummonData;
+use l2_core::data::teleports::TeleportData;
 use l2_core::errors::KameoAnyhowExt;
 use l2_core::game_objects::player::Player;
 use l2_core::geoengine::GeoEngine;
@@ -23,11 +42,21 @@ use l2_core::shared_packets::common::SendablePacket;
 use l2_core::traits::IpBan;
 use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
 use tracing::{info, warn};
 
 pub type PacketFilter = Box<dyn Fn(&str, &ActorRef<PlayerClient>) -> bool + Send>;
 
+/// A character which has entered the game world
+#[derive(Clone, Debug)]
+pub struct InGameChar {
+    pub account: String,
+    pub name: String,
+    pub object_id: i32,
+    pub actor: ActorRef<PlayerClient>,
+}
+
 #[derive(Clone, Debug)]
 pub struct GameController {
     cfg: Arc<GSServerConfig>,
@@ -40,11 +69,38 @@ pub struct GameController {
     online_chars: DashMap<String, Option<ActorRef<PlayerClient>>>,
     pub base_stats_table: BaseStat,
     pub skills: SkillsData,
-    pub hero_list: DashMap<i32, character::Model>,
+    pub items_data: ItemsData,
+    pub zone_manager: ZoneManager,
+    pub teleports: TeleportData,
+    pub hennas: HennaData,
+    pub quests: QuestData,
+    pub buy_lists: BuyListData,
+    pub multisell: MultisellData,
+    pub castles: CastleData,
+    pub manor: ManorData,
+    pub cursed_weapons: CursedWeaponsData,
+    pub fish: FishData,
+    pub summons: SummonData,
+    pub recipes: RecipeData,
+    pub enchant: EnchantData,
+    pub html_cache: HtmlCache,
+    /// Hero records by char id, expired ones are dropped once they are noticed
+    pub hero_list: DashMap<i32, hero::Model>,
     pub clan_ally_manager: Arc<RwLock<ClanAllyManager>>,
+    pub siege_manager: Arc<SiegeManager>,
+    pub olympiad_manager: Arc<OlympiadManager>,
+    pub manor_manager: Arc<ManorManager>,
+    pub vitality_manager: Arc<VitalityManager>,
+    pub recommendation_manager: Arc<RecommendationManager>,
+    pub cursed_weapons_manager: Arc<CursedWeaponsManager>,
+    pub clock: Arc<GameClock>,
     pub geo_engine: Arc<GeoEngine>,
     // Global registry: world object_id -> player actor
     player_by_object_id: DashMap<i32, ActorRef<PlayerClient>>,
+    // Summons in the world registry: summon object_id -> owner object_id
+    summon_owners: DashMap<i32, i32>,
+    // Characters in game: char id -> character
+    chars_in_game: DashMap<i32, InGameChar>,
 }
 
 impl GameController {
@@ -55,6 +111,23 @@ impl GameController {
         let class_templates = ClassTemplates::load();
         let base_stats = BaseStat::load();
         let skills = SkillsData::load();
+        let items_data = ItemsData::load();
+        let zone_manager = ZoneManager::load();
+        let teleports = TeleportData::load();
+        let hennas = HennaData::load();
+        let quests = QuestData::load();
+        let buy_lists = BuyListData::load();
+        let multisell = MultisellData::load();
+        let castles = CastleData::load();
+        let clock = Arc::new(GameClock::default());
+        let siege_manager = SiegeManager::load(db_pool, &castles, clock.now()).await;
+        let olympiad_manager =
+            OlympiadManager::load(db_pool, OlympiadData::load(), clock.now()).await;
+        let manor_manager = ManorManager::load(db_pool, clock.now()).await;
+        let cursed_weapons_manager = CursedWeaponsManager::load(db_pool).await;
+        let heroes = hero::Model::load_active(db_pool, clock.now().fixed_offset())
+            .await
+            .expect("Failed to load heroes, can not continue...");
         let geo_engine = Arc::new(GeoEngine::new(Path::new("config/data/geo")));
         GameController {
             exp_table,
@@ -65,12 +138,36 @@ impl GameController {
             skill_trees_data,
             base_stats_table: base_stats,
             skills,
+            items_data,
+            zone_manager,
+            teleports,
+            hennas,
+            quests,
+            buy_lists,
+            multisell,
+            castles,
+            manor: ManorData::load(),
+            cursed_weapons: CursedWeaponsData::load(),
+            fish: FishData::load(),
+            summons: SummonData::load(),
+            recipes: RecipeData::load(),
+            enchant: EnchantData::load(),
+            html_cache: HtmlCache::default(),
             class_templates: Arc::new(class_templates),
-            hero_list: DashMap::new(),
+            hero_list: heroes.into_iter().map(|h| (h.char_id, h)).collect(),
             online_chars: DashMap::new(),
             clan_ally_manager: Arc::new(RwLock::new(ClanAllyManager::new(db_pool.clone()).await)),
+            siege_manager: Arc::new(siege_manager),
+            olympiad_manager: Arc::new(olympiad_manager),
+            manor_manager: Arc::new(manor_manager),
+            vitality_manager: Arc::new(VitalityManager::new(clock.now())),
+            recommendation_manager: Arc::new(RecommendationManager::new(clock.now())),
+            cursed_weapons_manager: Arc::new(cursed_weapons_manager),
+            clock,
             geo_engine,
             player_by_object_id: DashMap::new(),
+            summon_owners: DashMap::new(),
+            chars_in_game: DashMap::new(),
         }
     }
     pub async fn set_ls_actor(&self, actor: ActorRef<LoginServerClient>) {
@@ -114,10 +211,106 @@ impl GameController {
     ) -> Option<ActorRef<PlayerClient>> {
         self.online_chars.insert(account.to_string(), pl)?
     }
+    /// Actor of the client the account is logged in with
+    pub fn get_online_account(&self, account: &str) -> Option<ActorRef<PlayerClient>> {
+        self.online_chars.get(account).and_then(|a| a.clone())
+    }
     pub fn logout_account(&self, account: &str) {
         self.online_chars.remove(account);
+        let in_game: Vec<i32> = self
+            .chars_in_game
+            .iter()
+            .filter(|c| c.account == account)
+            .map(|c| *c.key())
+            .collect();
+        for char_id in in_game {
+            self.leave_game(char_id);
+        }
         info!("Logged out online account: {}", account);
     }
+
+    /// Registers the character as in game and lets its friends know it's online.
+    pub fn enter_game(&self, char_id: i32, char: InGameChar) {
+        self.chars_in_game.insert(char_id, char);
+        self.notify_friends(char_id);
+    }
+
+    /// Removes the character from the game and lets its friends know it's offline.
+    pub fn leave_game(&self, char_id: i32) {
+        self.olympiad_manager.on_leave(char_id);
+        if self.chars_in_game.remove(&char_id).is_some() {
+            self.notify_friends(char_id);
+        }
+    }
+
+    /// When the hero status of the char runs out, None if it is not a hero (anymore)
+    pub fn hero_until(&self, char_id: i32) -> Option<DateTime<Utc>> {
+        self.hero_list
+            .get(&char_id)
+            .map(|h| h.expires_at.to_utc())
+            .filter(|t| *t > self.clock.now())
+    }
+
+    /// Items bound to their owner, they can't be stored, mailed or sold
+    pub fn is_bound_item(&self, item_id: i32) -> bool {
+        self.cursed_weapons.get(item_id).is_some()
+            || self
+                .items_data
+                .get_template(item_id)
+                .is_some_and(|t| t.hero)
+    }
+
+    pub fn get_char_in_game(&self, char_id: i32) -> Option<InGameChar> {
+        self.chars_in_game.get(&char_id).map(|c| c.clone())
+    }
+
+    /// Case-insensitive lookup, returns the char id together with the character
+    pub fn find_char_in_game(&self, name: &str) -> Option<(i32, InGameChar)> {
+        self.chars_in_game
+            .iter()
+            .find(|c| c.name.eq_ignore_ascii_case(name))
+            .map(|c| (*c.key(), c.value().clone()))
+    }
+
+    pub fn chars_in_game_ids(&self) -> Vec<i32> {
+        self.chars_in_game.iter().map(|c| *c.key()).collect()
+    }
+
+    /// Runs the callback on the actor of the char if it is in game
+    pub fn run_on_char(&self, char_id: i32, callback: BoxedClosure) {
+        if let Some(char) = self.get_char_in_game(char_id) {
+            PlayerClient::do_later(
+                char.actor,
+                DoLater {
+                    delay: Duration::ZERO,
+                    callback,
+                },
+            );
+        }
+    }
+
+    /// Every character in game refreshes its friend list if the char is its friend.
+    fn notify_friends(&self, char_id: i32) {
+        let actors: Vec<_> = self
+            .chars_in_game
+            .iter()
+            .filter(|c| *c.key() != char_id)
+            .map(|c| c.actor.clone())
+            .collect();
+        // the caller may be one of the actors, so don't wait for them
+        tokio::spawn(async move {
+            for actor in actors {
+                let _ = actor
+                    .tell(DoLater {
+                        delay: Duration::ZERO,
+                        callback: Box::new(move |pl: &mut PlayerClient| {
+                            Box::pin(pl.on_friend_status_changed(char_id))
+                        }),
+                    })
+                    .await;
+            }
+        });
+    }
     pub async fn add_player_to_world(
         &self,
         p: &Player,
@@ -175,6 +368,37 @@ impl GameController {
         });
         Ok(())
     }
+    /// Sends the relation of the player to everyone in the world and theirs to the player,
+    /// for relations changing while the players see each other (e.g. a siege starts).
+    pub fn broadcast_relations(&self, player: Player, actor: ActorRef<PlayerClient>) {
+        let others: Vec<_> = self
+            .all_players()
+            .into_iter()
+            .filter(|(object_id, _)| *object_id != player.get_object_id())
+            .map(|(_, other)| other)
+            .collect();
+        tokio::spawn(async move {
+            for other_actor in others {
+                let Ok(other) = other_actor.ask(GetCharInfo).await else {
+                    continue;
+                };
+                for (from, to, to_actor) in [
+                    (&player, &other, &other_actor),
+                    (&other, &player, &actor),
+                ] {
+                    let packet = RelationChanged::builder()
+                        .add_relation(from, from.get_relation(to), from.is_auto_attackable(to))
+                        .finish();
+                    match packet {
+                        Ok(packet) => {
+                            let _ = to_actor.tell(HandleOutboundPacket { packet }).await;
+                        }
+                        Err(e) => warn!("Failed to build relation packet: {e}"),
+                    }
+                }
+            }
+        });
+    }
     pub fn broadcast_packet_with_filter(
         &self,
         packet: impl SendablePacket + Clone + Send + 'static,
@@ -234,9 +458,26 @@ impl GameController {
     pub fn all_players(&self) -> Vec<(i32, ActorRef<PlayerClient>)> {
         self.player_by_object_id
             .iter()
+            .filter(|entry| !self.summon_owners.contains_key(entry.key()))
             .map(|entry| (*entry.key(), entry.value().clone()))
             .collect()
     }
+
+    /// Puts the summon into the world registry, messages to it go to the actor of the owner.
+    pub fn register_summon(&self, summon_id: i32, owner_id: i32, owner: ActorRef<PlayerClient>) {
+        self.summon_owners.insert(summon_id, owner_id);
+        self.player_by_object_id.insert(summon_id, owner);
+    }
+
+    pub fn unregister_summon(&self, summon_id: i32) {
+        self.summon_owners.remove(&summon_id);
+        self.player_by_object_id.remove(&summon_id);
+    }
+
+    /// Object id of the owner of the summon.
+    pub fn get_summon_owner(&self, summon_id: i32) -> Option<i32> {
+        self.summon_owners.get(&summon_id).map(|r| *r)
+    }
 }
 
 #[cfg(test)]
@@ -249,6 +490,8 @@ impl GameController {
         let class_templates = ClassTemplates::load();
         let base_stats = BaseStat::load();
         let geo_engine = Arc::new(GeoEngine::new(Path::new("config/data/geo")));
+        let castles = CastleData::load();
+        let siege_manager = SiegeManager::new(&castles, vec![], vec![], vec![], chrono::Utc::now());
         GameController {
             db_pool: get_test_db().await,
             exp_table,
@@ -261,8 +504,37 @@ impl GameController {
             hero_list: DashMap::new(),
             online_chars: DashMap::new(),
             clan_ally_manager: Arc::new(RwLock::new(ClanAllyManager::default())),
+            siege_manager: Arc::new(siege_manager),
+            olympiad_manager: Arc::new(OlympiadManager::new(
+                OlympiadData::load(),
+                vec![],
+                None,
+                chrono::Utc::now(),
+            )),
+            manor_manager: Arc::new(ManorManager::new(vec![], vec![], chrono::Utc::now())),
+            vitality_manager: Arc::new(VitalityManager::new(chrono::Utc::now())),
+            recommendation_manager: Arc::new(RecommendationManager::new(chrono::Utc::now())),
+            cursed_weapons_manager: Arc::new(CursedWeaponsManager::default()),
+            clock: Arc::new(GameClock::default()),
             player_by_object_id: DashMap::new(),
+            summon_owners: DashMap::new(),
+            chars_in_game: DashMap::new(),
             skills: Default::default(),
+            items_data: ItemsData::load(),
+            zone_manager: ZoneManager::load(),
+            teleports: TeleportData::load(),
+            hennas: HennaData::load(),
+            quests: QuestData::load(),
+            buy_lists: BuyListData::load(),
+            multisell: MultisellData::load(),
+            castles,
+            manor: ManorData::load(),
+            cursed_weapons: CursedWeaponsData::load(),
+            fish: FishData::load(),
+            summons: SummonData::load(),
+            recipes: RecipeData::load(),
+            enchant: EnchantData::load(),
+            html_cache: HtmlCache::default(),
             geo_engine,
         }
     }
@@ -1,4 +1,5 @@
 use crate::packets::from_client::action::Action;
+use crate::packets::from_client::appearing::Appearing;
 use crate::packets::from_client::attack::Attack;
 use crate::packets::from_client::auth::AuthLogin;
 use crate::packets::from_client::char_create::CreateCharRequest;
@@ -7,20 +8,69 @@ use crate::packets::from_client::char_select::SelectChar;
 use crate::packets::from_client::delete_char::DeleteChar;
 use crate::packets::from_client::enter_world::EnterWorld;
 use crate::packets::from_client::extended::{
-    CheckCharName, GoLobby, RequestKeyMapping, RequestManorList, RequestUserBanInfo,
+    CheckCharName, GoLobby, RequestAutoSoulShot, RequestBookmarkSlotInfo, RequestCancelSentPost,
+    RequestCursedWeaponList, RequestCursedWeaponLocation, RequestDeleteBookmarkSlot,
+    RequestDeleteReceivedPost, RequestExCancelEnchantItem, RequestExTryToPutEnchantTargetItem,
+    RequestKeyMapping, RequestManorList, RequestModifyBookmarkSlot, RequestPostAttachment,
+    RequestPostItemList, RequestReceivedPost, RequestReceivedPostList, RequestRejectPostAttachment,
+    RequestSaveBookmarkSlot, RequestSendPost, RequestSentPost, RequestSentPostList,
+    RequestTeleportBookmark, RequestUserBanInfo, RequestVoteNew, RequestWriteHeroWords,
     SelectedQuestZoneId, SendClientIni,
 };
 use crate::packets::from_client::logout::Logout;
 use crate::packets::from_client::move_to_location::RequestMoveToLocation;
+use crate::packets::from_client::multi_sell_choose::MultiSellChoose;
 use crate::packets::from_client::new_char_request::NewCharacterRequest;
 use crate::packets::from_client::noop::NoOp;
 use crate::packets::from_client::protocol::ProtocolVersion;
 use crate::packets::from_client::req_skill_cooltime::ReqSkillCoolTime;
+use crate::packets::from_client::request_action_use::RequestActionUse;
+use crate::packets::from_client::request_answer_friend_invite::RequestAnswerFriendInvite;
+use crate::packets::from_client::request_block::RequestBlock;
+use crate::packets::from_client::request_buy_item::RequestBuyItem;
+use crate::packets::from_client::request_bypass_to_server::RequestBypassToServer;
 use crate::packets::from_client::request_cancel_target::RequestCancelTarget;
+use crate::packets::from_client::request_delete_macro::RequestDeleteMacro;
+use crate::packets::from_client::request_enchant_item::RequestEnchantItem;
+use crate::packets::from_client::request_friend_del::RequestFriendDel;
+use crate::packets::from_client::request_friend_invite::RequestFriendInvite;
+use crate::packets::from_client::request_friend_list::RequestFriendList;
+use crate::packets::from_client::request_get_item_from_pet::RequestGetItemFromPet;
+use crate::packets::from_client::request_give_item_to_pet::RequestGiveItemToPet;
+use crate::packets::from_client::request_henna_equip::RequestHennaEquip;
+use crate::packets::from_client::request_henna_item_info::RequestHennaItemInfo;
+use crate::packets::from_client::request_henna_item_list::RequestHennaItemList;
+use crate::packets::from_client::request_henna_item_remove_info::RequestHennaItemRemoveInfo;
+use crate::packets::from_client::request_henna_remove::RequestHennaRemove;
+use crate::packets::from_client::request_henna_remove_list::RequestHennaRemoveList;
+use crate::packets::from_client::request_link_html::RequestLinkHtml;
 use crate::packets::from_client::request_magic_skill_use::RequestMagicSkillUse;
+use crate::packets::from_client::request_make_macro::RequestMakeMacro;
+use crate::packets::from_client::request_package_send::RequestPackageSend;
+use crate::packets::from_client::request_package_sendable_item_list::RequestPackageSendableItemList;
+use crate::packets::from_client::request_pet_use_item::RequestPetUseItem;
+use crate::packets::from_client::request_quest_abort::RequestQuestAbort;
+use crate::packets::from_client::request_quest_list::RequestQuestList;
+use crate::packets::from_client::request_recipe_book_destroy::RequestRecipeBookDestroy;
+use crate::packets::from_client::request_recipe_book_open::RequestRecipeBookOpen;
+use crate::packets::from_client::request_recipe_item_make_info::RequestRecipeItemMakeInfo;
+use crate::packets::from_client::request_recipe_item_make_self::RequestRecipeItemMakeSelf;
+use crate::packets::from_client::request_recipe_shop_list_set::RequestRecipeShopListSet;
+use crate::packets::from_client::request_recipe_shop_make_info::RequestRecipeShopMakeInfo;
+use crate::packets::from_client::request_recipe_shop_make_item::RequestRecipeShopMakeItem;
+use crate::packets::from_client::request_recipe_shop_manage_quit::RequestRecipeShopManageQuit;
+use crate::packets::from_client::request_recipe_shop_message_set::RequestRecipeShopMessageSet;
+use crate::packets::from_client::request_sell_item::RequestSellItem;
+use crate::packets::from_client::request_send_friend_msg::RequestSendFriendMsg;
+use crate::packets::from_client::request_shortcut_del::RequestShortcutDel;
+use crate::packets::from_client::request_shortcut_reg::RequestShortcutReg;
 use crate::packets::from_client::request_skill_list::RequestSkillList;
 use crate::packets::from_client::restart::RequestRestart;
+use crate::packets::from_client::say2::Say2;
+use crate::packets::from_client::send_ware_house_deposit_list::SendWareHouseDepositList;
+use crate::packets::from_client::send_ware_house_with_draw_list::SendWareHouseWithDrawList;
 use crate::packets::from_client::stop_move::StopMove;
+use crate::packets::from_client::use_item::UseItem;
 use crate::packets::from_client::validate_position::ValidatePosition;
 use anyhow::bail;
 use bytes::BytesMut;
@@ -58,6 +108,72 @@ pub enum PlayerPackets {
     RequestMagicSkillUse(RequestMagicSkillUse),
     RequestSkillList(RequestSkillList),
     RequestCancelTarget(RequestCancelTarget),
+    RequestAutoSoulShot(RequestAutoSoulShot),
+    Appearing(Appearing),
+    UseItem(UseItem),
+    RequestBookmarkSlotInfo(RequestBookmarkSlotInfo),
+    RequestSaveBookmarkSlot(RequestSaveBookmarkSlot),
+    RequestModifyBookmarkSlot(RequestModifyBookmarkSlot),
+    RequestDeleteBookmarkSlot(RequestDeleteBookmarkSlot),
+    RequestTeleportBookmark(RequestTeleportBookmark),
+    RequestMakeMacro(RequestMakeMacro),
+    RequestDeleteMacro(RequestDeleteMacro),
+    RequestShortcutReg(RequestShortcutReg),
+    RequestShortcutDel(RequestShortcutDel),
+    RequestAnswerFriendInvite(RequestAnswerFriendInvite),
+    RequestBlock(RequestBlock),
+    RequestFriendDel(RequestFriendDel),
+    RequestFriendInvite(RequestFriendInvite),
+    RequestFriendList(RequestFriendList),
+    RequestSendFriendMsg(RequestSendFriendMsg),
+    RequestPostItemList(RequestPostItemList),
+    RequestReceivedPostList(RequestReceivedPostList),
+    RequestDeleteReceivedPost(RequestDeleteReceivedPost),
+    RequestReceivedPost(RequestReceivedPost),
+    RequestPostAttachment(RequestPostAttachment),
+    RequestRejectPostAttachment(RequestRejectPostAttachment),
+    RequestSentPostList(RequestSentPostList),
+    RequestSentPost(RequestSentPost),
+    RequestCancelSentPost(RequestCancelSentPost),
+    RequestSendPost(RequestSendPost),
+    RequestHennaEquip(RequestHennaEquip),
+    RequestHennaItemInfo(RequestHennaItemInfo),
+    RequestHennaItemList(RequestHennaItemList),
+    RequestHennaItemRemoveInfo(RequestHennaItemRemoveInfo),
+    RequestHennaRemove(RequestHennaRemove),
+    RequestHennaRemoveList(RequestHennaRemoveList),
+    RequestQuestList(RequestQuestList),
+    RequestQuestAbort(RequestQuestAbort),
+    RequestLinkHtml(RequestLinkHtml),
+    RequestBypassToServer(RequestBypassToServer),
+    RequestBuyItem(RequestBuyItem),
+    RequestSellItem(RequestSellItem),
+    MultiSellChoose(MultiSellChoose),
+    SendWareHouseDepositList(SendWareHouseDepositList),
+    SendWareHouseWithDrawList(SendWareHouseWithDrawList),
+    RequestPackageSendableItemList(RequestPackageSendableItemList),
+    RequestPackageSend(RequestPackageSend),
+    RequestVoteNew(RequestVoteNew),
+    RequestWriteHeroWords(RequestWriteHeroWords),
+    RequestCursedWeaponList(RequestCursedWeaponList),
+    RequestCursedWeaponLocation(RequestCursedWeaponLocation),
+    RequestActionUse(RequestActionUse),
+    RequestGiveItemToPet(RequestGiveItemToPet),
+    RequestGetItemFromPet(RequestGetItemFromPet),
+    RequestPetUseItem(RequestPetUseItem),
+    RequestRecipeBookDestroy(RequestRecipeBookDestroy),
+    RequestRecipeBookOpen(RequestRecipeBookOpen),
+    RequestRecipeItemMakeInfo(RequestRecipeItemMakeInfo),
+    RequestRecipeItemMakeSelf(RequestRecipeItemMakeSelf),
+    RequestRecipeShopListSet(RequestRecipeShopListSet),
+    RequestRecipeShopMakeInfo(RequestRecipeShopMakeInfo),
+    RequestRecipeShopMakeItem(RequestRecipeShopMakeItem),
+    RequestRecipeShopManageQuit(RequestRecipeShopManageQuit),
+    RequestRecipeShopMessageSet(RequestRecipeShopMessageSet),
+    RequestEnchantItem(RequestEnchantItem),
+    RequestExTryToPutEnchantTargetItem(RequestExTryToPutEnchantTargetItem),
+    RequestExCancelEnchantItem(RequestExCancelEnchantItem),
+    Say2(Say2),
 }
 
 pub fn build_client_packet(mut data: BytesMut) -> anyhow::Result<PlayerPackets> {
@@ -102,6 +218,130 @@ pub fn build_client_packet(mut data: BytesMut) -> anyhow::Result<PlayerPackets>
         RequestCancelTarget::PACKET_ID => Ok(PlayerPackets::RequestCancelTarget(
             RequestCancelTarget::read(data)?,
         )),
+        Appearing::PACKET_ID => Ok(PlayerPackets::Appearing(Appearing::read(data)?)),
+        UseItem::PACKET_ID => Ok(PlayerPackets::UseItem(UseItem::read(data)?)),
+        RequestMakeMacro::PACKET_ID => Ok(PlayerPackets::RequestMakeMacro(RequestMakeMacro::read(
+            data,
+        )?)),
+        RequestDeleteMacro::PACKET_ID => Ok(PlayerPackets::RequestDeleteMacro(
+            RequestDeleteMacro::read(data)?,
+        )),
+        RequestShortcutReg::PACKET_ID => Ok(PlayerPackets::RequestShortcutReg(
+            RequestShortcutReg::read(data)?,
+        )),
+        RequestShortcutDel::PACKET_ID => Ok(PlayerPackets::RequestShortcutDel(
+            RequestShortcutDel::read(data)?,
+        )),
+        RequestAnswerFriendInvite::PACKET_ID => Ok(PlayerPackets::RequestAnswerFriendInvite(
+            RequestAnswerFriendInvite::read(data)?,
+        )),
+        RequestBlock::PACKET_ID => Ok(PlayerPackets::RequestBlock(RequestBlock::read(data)?)),
+        RequestFriendDel::PACKET_ID => Ok(PlayerPackets::RequestFriendDel(RequestFriendDel::read(
+            data,
+        )?)),
+        RequestFriendInvite::PACKET_ID => Ok(PlayerPackets::RequestFriendInvite(
+            RequestFriendInvite::read(data)?,
+        )),
+        RequestFriendList::PACKET_ID => Ok(PlayerPackets::RequestFriendList(
+            RequestFriendList::read(data)?,
+        )),
+        RequestSendFriendMsg::PACKET_ID => Ok(PlayerPackets::RequestSendFriendMsg(
+            RequestSendFriendMsg::read(data)?,
+        )),
+        RequestHennaEquip::PACKET_ID => Ok(PlayerPackets::RequestHennaEquip(
+            RequestHennaEquip::read(data)?,
+        )),
+        RequestHennaItemInfo::PACKET_ID => Ok(PlayerPackets::RequestHennaItemInfo(
+            RequestHennaItemInfo::read(data)?,
+        )),
+        RequestHennaItemList::PACKET_ID => Ok(PlayerPackets::RequestHennaItemList(
+            RequestHennaItemList::read(data)?,
+        )),
+        RequestHennaItemRemoveInfo::PACKET_ID => Ok(PlayerPackets::RequestHennaItemRemoveInfo(
+            RequestHennaItemRemoveInfo::read(data)?,
+        )),
+        RequestHennaRemove::PACKET_ID => Ok(PlayerPackets::RequestHennaRemove(
+            RequestHennaRemove::read(data)?,
+        )),
+        RequestHennaRemoveList::PACKET_ID => Ok(PlayerPackets::RequestHennaRemoveList(
+            RequestHennaRemoveList::read(data)?,
+        )),
+        RequestQuestList::PACKET_ID => Ok(PlayerPackets::RequestQuestList(RequestQuestList::read(
+            data,
+        )?)),
+        RequestQuestAbort::PACKET_ID => Ok(PlayerPackets::RequestQuestAbort(
+            RequestQuestAbort::read(data)?,
+        )),
+        RequestLinkHtml::PACKET_ID => {
+            Ok(PlayerPackets::RequestLinkHtml(RequestLinkHtml::read(data)?))
+        }
+        RequestBypassToServer::PACKET_ID => Ok(PlayerPackets::RequestBypassToServer(
+            RequestBypassToServer::read(data)?,
+        )),
+        RequestBuyItem::PACKET_ID => Ok(PlayerPackets::RequestBuyItem(RequestBuyItem::read(data)?)),
+        RequestSellItem::PACKET_ID => {
+            Ok(PlayerPackets::RequestSellItem(RequestSellItem::read(data)?))
+        }
+        MultiSellChoose::PACKET_ID => {
+            Ok(PlayerPackets::MultiSellChoose(MultiSellChoose::read(data)?))
+        }
+        SendWareHouseDepositList::PACKET_ID => Ok(PlayerPackets::SendWareHouseDepositList(
+            SendWareHouseDepositList::read(data)?,
+        )),
+        SendWareHouseWithDrawList::PACKET_ID => Ok(PlayerPackets::SendWareHouseWithDrawList(
+            SendWareHouseWithDrawList::read(data)?,
+        )),
+        RequestPackageSendableItemList::PACKET_ID => {
+            Ok(PlayerPackets::RequestPackageSendableItemList(
+                RequestPackageSendableItemList::read(data)?,
+            ))
+        }
+        RequestPackageSend::PACKET_ID => Ok(PlayerPackets::RequestPackageSend(
+            RequestPackageSend::read(data)?,
+        )),
+        RequestActionUse::PACKET_ID => Ok(PlayerPackets::RequestActionUse(RequestActionUse::read(
+            data,
+        )?)),
+        RequestGiveItemToPet::PACKET_ID => Ok(PlayerPackets::RequestGiveItemToPet(
+            RequestGiveItemToPet::read(data)?,
+        )),
+        RequestGetItemFromPet::PACKET_ID => Ok(PlayerPackets::RequestGetItemFromPet(
+            RequestGetItemFromPet::read(data)?,
+        )),
+        RequestPetUseItem::PACKET_ID => Ok(PlayerPackets::RequestPetUseItem(
+            RequestPetUseItem::read(data)?,
+        )),
+        RequestRecipeBookDestroy::PACKET_ID => Ok(PlayerPackets::RequestRecipeBookDestroy(
+            RequestRecipeBookDestroy::read(data)?,
+        )),
+        RequestRecipeBookOpen::PACKET_ID => Ok(PlayerPackets::RequestRecipeBookOpen(
+            RequestRecipeBookOpen::read(data)?,
+        )),
+        RequestRecipeItemMakeInfo::PACKET_ID => Ok(PlayerPackets::RequestRecipeItemMakeInfo(
+            RequestRecipeItemMakeInfo::read(data)?,
+        )),
+        RequestRecipeItemMakeSelf::PACKET_ID => Ok(PlayerPackets::RequestRecipeItemMakeSelf(
+            RequestRecipeItemMakeSelf::read(data)?,
+        )),
+        RequestRecipeShopListSet::PACKET_ID => Ok(PlayerPackets::RequestRecipeShopListSet(
+            RequestRecipeShopListSet::read(data)?,
+        )),
+        RequestRecipeShopMakeInfo::PACKET_ID => Ok(PlayerPackets::RequestRecipeShopMakeInfo(
+            RequestRecipeShopMakeInfo::read(data)?,
+        )),
+        RequestRecipeShopMakeItem::PACKET_ID => Ok(PlayerPackets::RequestRecipeShopMakeItem(
+            RequestRecipeShopMakeItem::read(data)?,
+        )),
+        RequestRecipeShopManageQuit::PACKET_ID => Ok(PlayerPackets::RequestRecipeShopManageQuit(
+            RequestRecipeShopManageQuit::read(data)?,
+        )),
+        RequestRecipeShopMessageSet::PACKET_ID => Ok(PlayerPackets::RequestRecipeShopMessageSet(
+            RequestRecipeShopMessageSet::read(data)?,
+        )),
+        RequestEnchantItem::PACKET_ID => Ok(PlayerPackets::RequestEnchantItem(
+            RequestEnchantItem::read(data)?,
+        )),
+        Say2::PACKET_ID => Ok(PlayerPackets::Say2(Say2::read(data)?)),
         0xD0 => build_ex_client_packet(data),
         _ => {
             error!("Unknown Player packet ID: 0x{:02X}", packet_id[0]);
@@ -133,6 +373,74 @@ pub fn build_ex_client_packet(mut data: BytesMut) -> anyhow::Result<PlayerPacket
         RequestKeyMapping::EX_PACKET_ID => Ok(PlayerPackets::RequestKeyMapping(
             RequestKeyMapping::read(data)?,
         )),
+        RequestAutoSoulShot::EX_PACKET_ID => Ok(PlayerPackets::RequestAutoSoulShot(
+            RequestAutoSoulShot::read(data)?,
+        )),
+        RequestPostItemList::EX_PACKET_ID => Ok(PlayerPackets::RequestPostItemList(
+            RequestPostItemList::read(data)?,
+        )),
+        RequestReceivedPostList::EX_PACKET_ID => Ok(PlayerPackets::RequestReceivedPostList(
+            RequestReceivedPostList::read(data)?,
+        )),
+        RequestDeleteReceivedPost::EX_PACKET_ID => Ok(PlayerPackets::RequestDeleteReceivedPost(
+            RequestDeleteReceivedPost::read(data)?,
+        )),
+        RequestReceivedPost::EX_PACKET_ID => Ok(PlayerPackets::RequestReceivedPost(
+            RequestReceivedPost::read(data)?,
+        )),
+        RequestPostAttachment::EX_PACKET_ID => Ok(PlayerPackets::RequestPostAttachment(
+            RequestPostAttachment::read(data)?,
+        )),
+        RequestRejectPostAttachment::EX_PACKET_ID => Ok(
+            PlayerPackets::RequestRejectPostAttachment(RequestRejectPostAttachment::read(data)?),
+        ),
+        RequestSentPostList::EX_PACKET_ID => Ok(PlayerPackets::RequestSentPostList(
+            RequestSentPostList::read(data)?,
+        )),
+        RequestSentPost::EX_PACKET_ID => {
+            Ok(PlayerPackets::RequestSentPost(RequestSentPost::read(data)?))
+        }
+        RequestCancelSentPost::EX_PACKET_ID => Ok(PlayerPackets::RequestCancelSentPost(
+            RequestCancelSentPost::read(data)?,
+        )),
+        RequestSendPost::EX_PACKET_ID => {
+            Ok(PlayerPackets::RequestSendPost(RequestSendPost::read(data)?))
+        }
+        RequestBookmarkSlotInfo::EX_PACKET_ID => Ok(PlayerPackets::RequestBookmarkSlotInfo(
+            RequestBookmarkSlotInfo::read(data)?,
+        )),
+        RequestSaveBookmarkSlot::EX_PACKET_ID => Ok(PlayerPackets::RequestSaveBookmarkSlot(
+            RequestSaveBookmarkSlot::read(data)?,
+        )),
+        RequestModifyBookmarkSlot::EX_PACKET_ID => Ok(PlayerPackets::RequestModifyBookmarkSlot(
+            RequestModifyBookmarkSlot::read(data)?,
+        )),
+        RequestDeleteBookmarkSlot::EX_PACKET_ID => Ok(PlayerPackets::RequestDeleteBookmarkSlot(
+            RequestDeleteBookmarkSlot::read(data)?,
+        )),
+        RequestTeleportBookmark::EX_PACKET_ID => Ok(PlayerPackets::RequestTeleportBookmark(
+            RequestTeleportBookmark::read(data)?,
+        )),
+        RequestVoteNew::EX_PACKET_ID => {
+            Ok(PlayerPackets::RequestVoteNew(RequestVoteNew::read(data)?))
+        }
+        RequestWriteHeroWords::EX_PACKET_ID => Ok(PlayerPackets::RequestWriteHeroWords(
+            RequestWriteHeroWords::read(data)?,
+        )),
+        RequestCursedWeaponList::EX_PACKET_ID => Ok(PlayerPackets::RequestCursedWeaponList(
+            RequestCursedWeaponList::read(data)?,
+        )),
+        RequestCursedWeaponLocation::EX_PACKET_ID => Ok(
+            PlayerPackets::RequestCursedWeaponLocation(RequestCursedWeaponLocation::read(data)?),
+        ),
+        RequestExTryToPutEnchantTargetItem::EX_PACKET_ID => {
+            Ok(PlayerPackets::RequestExTryToPutEnchantTargetItem(
+                RequestExTryToPutEnchantTargetItem::read(data)?,
+            ))
+        }
+        RequestExCancelEnchantItem::EX_PACKET_ID => Ok(PlayerPackets::RequestExCancelEnchantItem(
+            RequestExCancelEnchantItem::read(data)?,
+        )),
         _ => {
             error!("Unknown extended client packet ID: 0x{:x}", packet_id);
             Ok(PlayerPackets::NoOp(NoOp::read(data)?))
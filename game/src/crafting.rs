@@ -0,0 +1,835 @@
+//! Crafting.
+//!
+//! Recipes are learned from recipe items into the dwarven or the common recipe book, the level
+//! of Create Item or Create Common Item limits the recipes a player can learn and use. A craft
+//! costs MP and uses up the ingredients either way, the product only comes with the success
+//! rate of the recipe.
+//!
+//! A manufacturer sits down with a manufacture shop and crafts the recipes it offers for others:
+//! the customer brings the ingredients and pays the fee of the shop, the manufacturer spends the
+//! MP. The customer's items move only once the manufacturer has crafted, and the fee reaches the
+//! manufacturer only after the customer has paid it.
+
+use crate::packets::to_client::{
+    ActionFailed, CharInfo, RecipeBookItemList, RecipeItemMakeInfo, RecipeShopItemInfo,
+    RecipeShopManageList, RecipeShopMsg, RecipeShopSellList, StatusUpdate, StatusUpdateType,
+    SystemMessage, SystemMessageType, UserInfo,
+};
+use crate::pl_client::{GetCharInfo, PlayerClient};
+use entities::entities::character_recipe;
+use kameo::actor::ActorRef;
+use kameo::message::{Context, Message};
+use l2_core::data::recipes::RecipeTemplate;
+use l2_core::errors::KameoAnyhowExt;
+use l2_core::game_objects::player::inventory::Inventory;
+use l2_core::game_objects::player::user_info::UserInfoType;
+use l2_core::game_objects::player::{ManufactureItem, Player};
+use l2_core::game_objects::private_store_types::PrivateStoreType;
+use l2_core::game_objects::zone::ZoneId;
+use l2_core::traits::conversion::ToU32Rounded;
+use rand::RngExt;
+use tracing::{instrument, warn};
+
+/// Characters of the message over the head of a manufacturer
+const MAX_SHOP_MESSAGE_LENGTH: usize = 29;
+
+/// Inventory items the ingredients of the recipe are taken from, `(object id, count)`. None when
+/// some are missing.
+fn take_ingredients(player: &Player, recipe: &RecipeTemplate) -> Option<Vec<(i32, i64)>> {
+    recipe
+        .ingredients
+        .iter()
+        .map(|ingredient| {
+            let item = player.inventory.get_item_by_item_id(ingredient.item_id)?;
+            (item.item_model.count >= ingredient.count)
+                .then(|| (item.object_id.value(), ingredient.count))
+        })
+        .collect()
+}
+
+fn roll_craft(recipe: &RecipeTemplate) -> bool {
+    rand::rng().random_range(0..100) < recipe.success_rate
+}
+
+fn get_mp(player: &Player) -> anyhow::Result<(u32, u32)> {
+    Ok((
+        player.stats.current_mp.to_u32_rounded()?,
+        player.get_max_mp().to_u32_rounded()?,
+    ))
+}
+
+/// What the manufacturer answers to an order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ManufactureReply {
+    Refused(&'static str),
+    Crafted {
+        success: bool,
+        cur_mp: u32,
+        max_mp: u32,
+    },
+}
+
+/// A customer orders a craft in the manufacture shop, the fee is the one the customer saw.
+#[derive(Debug, Clone)]
+pub struct Manufacture {
+    pub recipe_id: i32,
+    pub fee: i64,
+    pub customer_name: String,
+}
+
+/// The fee of a crafted order, sent to the manufacturer once the customer has paid it.
+#[derive(Debug, Clone, Copy)]
+pub struct ManufactureFee {
+    pub fee: i64,
+}
+
+impl PlayerClient {
+    async fn refuse_craft(&mut self, reason: &str) -> anyhow::Result<()> {
+        self.send_packet(SystemMessage::text(reason)?).await?;
+        self.send_packet(ActionFailed::normal()?).await
+    }
+
+    /// Spends the MP of a craft, false when there is not enough.
+    async fn spend_craft_mp(&mut self, mp_cost: u32) -> anyhow::Result<bool> {
+        let player = self.try_get_selected_char_mut()?;
+        let mp_cost = f64::from(mp_cost);
+        if player.stats.current_mp < mp_cost {
+            return Ok(false);
+        }
+        player.stats.current_mp -= mp_cost;
+        player.sync_vitals_to_model();
+        let mut su = StatusUpdate::new(player.get_object_id())?;
+        su.add_update(StatusUpdateType::CurMp, player.stats.current_mp as i32)?;
+        self.send_packet(su).await?;
+        Ok(true)
+    }
+
+    /// The recipe item is used up and its recipe goes into the book.
+    pub async fn learn_recipe(&mut self, item_id: i32) -> anyhow::Result<()> {
+        let controller = self.controller.clone();
+        let Some(recipe) = controller.recipes.by_recipe_item(item_id) else {
+            warn!("Item {item_id} teaches no recipe");
+            return self.send_packet(ActionFailed::normal()?).await;
+        };
+        let player = self.try_get_selected_char()?;
+        let char_id = player.char_model.id;
+        if let Some(reason) = player.check_learn_recipe(&controller.recipes, recipe) {
+            return self.refuse_craft(reason).await;
+        }
+        if player.is_manufacturing() {
+            return self
+                .refuse_craft("You may not alter your recipe book while engaged in manufacturing.")
+                .await;
+        }
+        if !self.destroy_item_by_item_id(item_id, 1).await? {
+            return Ok(());
+        }
+        character_recipe::Model::save(&self.db_pool, char_id, recipe.id).await?;
+        self.try_get_selected_char_mut()?
+            .recipe_book
+            .insert(recipe.id);
+        self.send_packet(SystemMessage::text(&format!(
+            "{} has been added to the recipe book.",
+            recipe.name
+        ))?)
+        .await?;
+        self.send_recipe_book(recipe.dwarven).await
+    }
+
+    pub async fn send_recipe_book(&mut self, dwarven: bool) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let recipe_ids: Vec<i32> = player
+            .get_recipe_book(&self.controller.recipes, dwarven)
+            .iter()
+            .map(|r| r.id)
+            .collect();
+        let packet =
+            RecipeBookItemList::new(dwarven, player.get_max_mp().to_u32_rounded()?, &recipe_ids)?;
+        self.send_packet(packet).await
+    }
+
+    /// The recipe is torn out of the book.
+    pub async fn forget_recipe(&mut self, recipe_id: i32) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let char_id = player.char_model.id;
+        if !player.has_recipe(recipe_id) {
+            return self.send_packet(ActionFailed::normal()?).await;
+        }
+        if player.is_manufacturing() {
+            return self
+                .refuse_craft("You may not alter your recipe book while engaged in manufacturing.")
+                .await;
+        }
+        character_recipe::Model::delete(&self.db_pool, char_id, recipe_id).await?;
+        self.try_get_selected_char_mut()?
+            .recipe_book
+            .remove(&recipe_id);
+        let dwarven = self
+            .controller
+            .recipes
+            .get(recipe_id)
+            .is_none_or(|r| r.dwarven);
+        self.send_recipe_book(dwarven).await
+    }
+
+    /// The craft window of a recipe from the book.
+    pub async fn send_recipe_make_info(&mut self, recipe_id: i32) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let Some(recipe) = self
+            .controller
+            .recipes
+            .get(recipe_id)
+            .filter(|_| player.has_recipe(recipe_id))
+        else {
+            return self.send_packet(ActionFailed::normal()?).await;
+        };
+        let (cur_mp, max_mp) = get_mp(player)?;
+        let packet = RecipeItemMakeInfo::new(recipe.id, recipe.dwarven, cur_mp, max_mp, None)?;
+        self.send_packet(packet).await
+    }
+
+    /// Crafts a recipe from the book with own MP and ingredients.
+    pub async fn craft_self(&mut self, recipe_id: i32) -> anyhow::Result<()> {
+        let controller = self.controller.clone();
+        let player = self.try_get_selected_char()?;
+        let Some(recipe) = controller
+            .recipes
+            .get(recipe_id)
+            .filter(|_| player.has_recipe(recipe_id))
+        else {
+            return self.send_packet(ActionFailed::normal()?).await;
+        };
+        let take = take_ingredients(player, recipe);
+        let refusal = if player.is_dead() {
+            Some("You can't craft now.")
+        } else if player.get_private_store_type() != PrivateStoreType::None {
+            Some("You can't craft while operating a private store.")
+        } else if player.get_craft_level(recipe.dwarven) < recipe.level {
+            Some("Your create item level is too low to make this item.")
+        } else if take.is_none() {
+            Some("You don't have the materials to make this item.")
+        } else {
+            None
+        };
+        if let Some(reason) = refusal {
+            return self.refuse_craft(reason).await;
+        }
+        if self.is_casting() {
+            return self.send_packet(ActionFailed::normal()?).await;
+        }
+        if !self.spend_craft_mp(recipe.mp_cost).await? {
+            self.send_packet(SystemMessage::new(SystemMessageType::NotEnoughMp)?)
+                .await?;
+            return self.send_packet(ActionFailed::normal()?).await;
+        }
+        let success = roll_craft(recipe);
+        self.hand_over_craft(recipe, take.unwrap_or_default(), success)
+            .await?;
+        let (cur_mp, max_mp) = get_mp(self.try_get_selected_char()?)?;
+        let packet =
+            RecipeItemMakeInfo::new(recipe.id, recipe.dwarven, cur_mp, max_mp, Some(success))?;
+        self.send_packet(packet).await
+    }
+
+    /// The ingredients in `take` are taken, the product is given when the craft succeeded.
+    async fn hand_over_craft(
+        &mut self,
+        recipe: &RecipeTemplate,
+        take: Vec<(i32, i64)>,
+        success: bool,
+    ) -> anyhow::Result<()> {
+        let product = recipe.product;
+        let give = if success {
+            vec![(product.item_id, product.count, 0)]
+        } else {
+            vec![]
+        };
+        self.exchange_items(&take, &give).await?;
+        let product_name = &self
+            .controller
+            .items_data
+            .try_get_template(product.item_id)?
+            .name;
+        let message = if success {
+            format!("You have created {product_name} ({}).", product.count)
+        } else {
+            format!("You failed to create {product_name}.")
+        };
+        self.send_packet(SystemMessage::text(&message)?).await
+    }
+
+    /// The manufacture shop setup for the dwarven or the common book. An open shop closes.
+    pub async fn manage_manufacture_shop(&mut self, dwarven: bool) -> anyhow::Result<()> {
+        if self.try_get_selected_char()?.is_manufacturing() {
+            self.quit_manufacture_shop().await?;
+        }
+        let player = self.try_get_selected_char()?;
+        let refusal = if player.is_dead() || player.is_mounted() {
+            Some("You can't open a private workshop now.")
+        } else if player.get_private_store_type() != PrivateStoreType::None {
+            Some("You are already operating a private store.")
+        } else if player.is_inside_zone(ZoneId::NoStore) {
+            Some("You can't open a private workshop here.")
+        } else if player.get_craft_level(dwarven) == 0 {
+            Some("You don't have the ability to create items.")
+        } else {
+            None
+        };
+        if let Some(reason) = refusal {
+            return self.refuse_craft(reason).await;
+        }
+        let recipe_ids: Vec<i32> = player
+            .get_recipe_book(&self.controller.recipes, dwarven)
+            .iter()
+            .map(|r| r.id)
+            .collect();
+        let player = self.try_get_selected_char_mut()?;
+        if player.manufacture.dwarven != dwarven {
+            player.manufacture.dwarven = dwarven;
+            player.manufacture.items.clear();
+        }
+        let packet = RecipeShopManageList::new(
+            player.get_object_id(),
+            player.inventory.get_adena(),
+            &player.manufacture,
+            &recipe_ids,
+        )?;
+        self.send_packet(packet).await
+    }
+
+    pub async fn set_manufacture_message(&mut self, message: String) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char_mut()?;
+        player.manufacture.message = message.chars().take(MAX_SHOP_MESSAGE_LENGTH).collect();
+        if player.is_manufacturing() {
+            let packet = RecipeShopMsg::new(player.get_object_id(), &player.manufacture.message)?;
+            self.controller.broadcast_packet(packet);
+        }
+        Ok(())
+    }
+
+    /// The manufacturer sits down and offers the recipes, an empty list closes the shop.
+    pub async fn open_manufacture_shop(
+        &mut self,
+        items: Vec<ManufactureItem>,
+    ) -> anyhow::Result<()> {
+        if items.is_empty() {
+            return self.quit_manufacture_shop().await;
+        }
+        let controller = self.controller.clone();
+        let player = self.try_get_selected_char()?;
+        let dwarven = player.manufacture.dwarven;
+        let offered = |item: &ManufactureItem| {
+            item.cost >= 0
+                && player.has_recipe(item.recipe_id)
+                && controller
+                    .recipes
+                    .get(item.recipe_id)
+                    .is_some_and(|r| r.dwarven == dwarven)
+        };
+        let refusal = if player.is_dead() || player.is_mounted() {
+            Some("You can't open a private workshop now.")
+        } else if player.is_inside_zone(ZoneId::NoStore) {
+            Some("You can't open a private workshop here.")
+        } else if !items.iter().all(offered) {
+            Some("The recipe can't be offered in the private workshop.")
+        } else {
+            None
+        };
+        if let Some(reason) = refusal {
+            return self.refuse_craft(reason).await;
+        }
+        self.stop_movement();
+        let player = self.try_get_selected_char_mut()?;
+        player.manufacture.items = items;
+        player.set_private_store_type(PrivateStoreType::Manufacture);
+        self.send_store_state().await
+    }
+
+    /// The manufacturer stands up, the offers stay for the next time.
+    pub async fn quit_manufacture_shop(&mut self) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char_mut()?;
+        if !player.is_manufacturing() {
+            return Ok(());
+        }
+        player.set_private_store_type(PrivateStoreType::None);
+        self.send_store_state().await
+    }
+
+    /// Everyone around sees the store open or close.
+    async fn send_store_state(&mut self) -> anyhow::Result<()> {
+        let controller = self.controller.clone();
+        let user_name = self.try_get_user()?.username.clone();
+        let player = self.try_get_selected_char()?.clone();
+        controller.broadcast_packet_with_filter(
+            CharInfo::new(&player, &controller.get_cfg())?,
+            Some(Box::new(move |acc, _| !acc.eq(&user_name))),
+        );
+        self.send_packet(UserInfo::new(&player, UserInfoType::all(), &controller).await?)
+            .await?;
+        if player.is_manufacturing() {
+            controller.broadcast_packet(RecipeShopMsg::new(
+                player.get_object_id(),
+                &player.manufacture.message,
+            )?);
+        }
+        Ok(())
+    }
+
+    /// The customer walks up to a player, the offers show when the player runs a manufacture
+    /// shop.
+    pub async fn show_manufacture_shop(
+        &mut self,
+        manufacturer: &ActorRef<PlayerClient>,
+    ) -> anyhow::Result<()> {
+        let manufacturer = manufacturer.ask(GetCharInfo).await.anyhow()?;
+        if !manufacturer.is_manufacturing() {
+            return Ok(());
+        }
+        let (cur_mp, max_mp) = get_mp(&manufacturer)?;
+        let packet = RecipeShopSellList::new(
+            manufacturer.get_object_id(),
+            cur_mp,
+            max_mp,
+            self.try_get_selected_char()?.inventory.get_adena(),
+            &manufacturer.manufacture.items,
+        )?;
+        self.send_packet(packet).await
+    }
+
+    /// The offer of the manufacture shop, None when the shop is gone or doesn't offer it.
+    async fn get_manufacture_offer(
+        &self,
+        manufacturer_id: i32,
+        recipe_id: i32,
+    ) -> anyhow::Result<Option<(ActorRef<PlayerClient>, Player, ManufactureItem)>> {
+        if manufacturer_id == self.try_get_selected_char()?.get_object_id() {
+            return Ok(None);
+        }
+        let Some(actor) = self.controller.get_player_by_object_id(manufacturer_id) else {
+            return Ok(None);
+        };
+        let manufacturer = actor.ask(GetCharInfo).await.anyhow()?;
+        let offer = manufacturer
+            .manufacture
+            .get_item(recipe_id)
+            .copied()
+            .filter(|_| manufacturer.is_manufacturing());
+        Ok(offer.map(|o| (actor, manufacturer, o)))
+    }
+
+    /// The craft window of a recipe in a manufacture shop.
+    pub async fn send_manufacture_item_info(
+        &mut self,
+        manufacturer_id: i32,
+        recipe_id: i32,
+    ) -> anyhow::Result<()> {
+        let Some((_, manufacturer, _)) = self
+            .get_manufacture_offer(manufacturer_id, recipe_id)
+            .await?
+        else {
+            return self.send_packet(ActionFailed::normal()?).await;
+        };
+        let (cur_mp, max_mp) = get_mp(&manufacturer)?;
+        let packet = RecipeShopItemInfo::new(manufacturer_id, recipe_id, cur_mp, max_mp, None)?;
+        self.send_packet(packet).await
+    }
+
+    /// The customer orders a craft. The ingredients and the fee stay in the inventory while the
+    /// manufacturer crafts: the actor of the customer handles nothing else until the reply, so
+    /// they can't go anywhere meanwhile. Once crafted they are taken and the product is given in
+    /// one exchange, then the manufacturer gets the fee. Returns the reply of the manufacturer,
+    /// None when the order wasn't placed.
+    pub async fn order_manufacture(
+        &mut self,
+        manufacturer_id: i32,
+        recipe_id: i32,
+    ) -> anyhow::Result<Option<ManufactureReply>> {
+        let controller = self.controller.clone();
+        let Some(recipe) = controller.recipes.get(recipe_id) else {
+            self.send_packet(ActionFailed::normal()?).await?;
+            return Ok(None);
+        };
+        let Some((actor, _, offer)) = self
+            .get_manufacture_offer(manufacturer_id, recipe_id)
+            .await?
+        else {
+            self.send_packet(ActionFailed::normal()?).await?;
+            return Ok(None);
+        };
+        let player = self.try_get_selected_char()?;
+        let customer_name = player.char_model.name.clone();
+        let Some(mut take) = take_ingredients(player, recipe) else {
+            self.refuse_craft("You don't have the materials to make this item.")
+                .await?;
+            return Ok(None);
+        };
+        if player.is_dead() || player.get_private_store_type() != PrivateStoreType::None {
+            self.send_packet(ActionFailed::normal()?).await?;
+            return Ok(None);
+        }
+        if offer.cost > 0 {
+            let adena = player
+                .inventory
+                .get_item_by_item_id(Inventory::ADENA_ID)
+                .filter(|a| a.item_model.count >= offer.cost);
+            let Some(adena) = adena else {
+                self.send_packet(SystemMessage::new(
+                    SystemMessageType::YouDoNotHaveEnoughAdena,
+                )?)
+                .await?;
+                self.send_packet(ActionFailed::normal()?).await?;
+                return Ok(None);
+            };
+            take.push((adena.object_id.value(), offer.cost));
+        }
+        let reply = actor
+            .ask(Manufacture {
+                recipe_id,
+                fee: offer.cost,
+                customer_name,
+            })
+            .await
+            .anyhow()?;
+        match reply {
+            ManufactureReply::Refused(reason) => self.refuse_craft(reason).await?,
+            ManufactureReply::Crafted {
+                success,
+                cur_mp,
+                max_mp,
+            } => {
+                self.hand_over_craft(recipe, take, success).await?;
+                if offer.cost > 0 {
+                    actor
+                        .ask(ManufactureFee { fee: offer.cost })
+                        .await
+                        .anyhow()?;
+                }
+                let packet = RecipeShopItemInfo::new(
+                    manufacturer_id,
+                    recipe_id,
+                    cur_mp,
+                    max_mp,
+                    Some(success),
+                )?;
+                self.send_packet(packet).await?;
+            }
+        }
+        Ok(Some(reply))
+    }
+}
+
+impl Message<Manufacture> for PlayerClient {
+    type Reply = anyhow::Result<ManufactureReply>;
+
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: Manufacture,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> Self::Reply {
+        let controller = self.controller.clone();
+        let player = self.try_get_selected_char()?;
+        let Some(recipe) = controller.recipes.get(msg.recipe_id) else {
+            return Ok(ManufactureReply::Refused("The recipe is unknown."));
+        };
+        let offered = player.is_manufacturing()
+            && player.has_recipe(recipe.id)
+            && player
+                .manufacture
+                .get_item(recipe.id)
+                .is_some_and(|i| i.cost == msg.fee);
+        if !offered {
+            return Ok(ManufactureReply::Refused(
+                "The private workshop has changed its offers.",
+            ));
+        }
+        if player.get_craft_level(recipe.dwarven) < recipe.level {
+            return Ok(ManufactureReply::Refused(
+                "The manufacturer can't make this item.",
+            ));
+        }
+        if !self.spend_craft_mp(recipe.mp_cost).await? {
+            return Ok(ManufactureReply::Refused(
+                "The manufacturer doesn't have enough MP.",
+            ));
+        }
+        let success = roll_craft(recipe);
+        let product_name = &controller
+            .items_data
+            .try_get_template(recipe.product.item_id)?
+            .name;
+        let message = if success {
+            format!("You have created {product_name} for {}.", msg.customer_name)
+        } else {
+            format!(
+                "You failed to create {product_name} for {}.",
+                msg.customer_name
+            )
+        };
+        self.send_packet(SystemMessage::text(&message)?).await?;
+        let (cur_mp, max_mp) = get_mp(self.try_get_selected_char()?)?;
+        Ok(ManufactureReply::Crafted {
+            success,
+            cur_mp,
+            max_mp,
+        })
+    }
+}
+
+impl Message<ManufactureFee> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: ManufactureFee,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> Self::Reply {
+        self.add_item_by_item_id(Inventory::ADENA_ID, msg.fee).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controller::GameController;
+    use crate::test_utils::test::{enter_game, get_gs_config, on_actor, settle, spawn_player};
+    use entities::entities::skill;
+    use l2_core::game_objects::creature::skill::Skill;
+    use std::sync::Arc;
+    use test_utils::utils::get_test_db;
+
+    const ARROW_RECIPE: i32 = 1;
+
+    fn make_crafter(pl: &mut PlayerClient) -> anyhow::Result<()> {
+        let player = pl.try_get_selected_char_mut()?;
+        player.skills = Some(vec![Skill::from_model(skill::Model {
+            id: RecipeTemplate::DWARVEN_CRAFT_SKILL,
+            level: 1,
+            ..Default::default()
+        })]);
+        player.char_model.cur_hp = 100.0;
+        player.stats.current_hp = 100.0;
+        player.stats.current_mp = 100.0;
+        Ok(())
+    }
+
+    fn set_mp(pl: &mut PlayerClient, mp: f64) -> anyhow::Result<()> {
+        let player = pl.try_get_selected_char_mut()?;
+        player.stats.current_mp = mp;
+        player.sync_vitals_to_model();
+        Ok(())
+    }
+
+    fn count_of(pl: &PlayerClient, item_id: i32) -> i64 {
+        pl.try_get_selected_char()
+            .unwrap()
+            .inventory
+            .get_item_by_item_id(item_id)
+            .map_or(0, |i| i.item_model.count)
+    }
+
+    #[tokio::test]
+    async fn test_craft_self() {
+        let db_pool = get_test_db().await;
+        let (char_id, actor, _conn) = spawn_player(&db_pool, 20).await;
+        on_actor(&actor, move |pl| {
+            Box::pin(async move {
+                make_crafter(pl)?;
+                for item_id in [1666, 1786] {
+                    pl.add_item_by_item_id(item_id, 1).await?;
+                    pl.learn_recipe(item_id).await?;
+                }
+                let player = pl.try_get_selected_char()?;
+                assert!(player.has_recipe(ARROW_RECIPE));
+                // steel needs Create Item level 2
+                assert!(!player.has_recipe(2));
+                assert_eq!(count_of(pl, 1666), 0);
+                assert_eq!(count_of(pl, 1786), 1);
+
+                pl.craft_self(ARROW_RECIPE).await?;
+                assert_eq!(count_of(pl, 17), 0);
+                assert!((pl.try_get_selected_char()?.stats.current_mp - 100.0).abs() < 0.01);
+
+                pl.add_item_by_item_id(1864, 5).await?;
+                pl.add_item_by_item_id(1869, 2).await?;
+                pl.craft_self(ARROW_RECIPE).await?;
+                assert_eq!(count_of(pl, 17), 500);
+                assert_eq!(count_of(pl, 1864), 1);
+                assert_eq!(count_of(pl, 1869), 0);
+                assert!((pl.try_get_selected_char()?.stats.current_mp - 70.0).abs() < 0.01);
+
+                pl.forget_recipe(ARROW_RECIPE).await?;
+                assert!(!pl.try_get_selected_char()?.has_recipe(ARROW_RECIPE));
+                Ok(())
+            })
+        })
+        .await;
+        assert!(
+            character_recipe::Model::char_recipes(&db_pool, char_id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_manufacture() {
+        let db_pool = get_test_db().await;
+        let (_, actor, _conn) = spawn_player(&db_pool, 20).await;
+        on_actor(&actor, move |pl| {
+            Box::pin(async move {
+                make_crafter(pl)?;
+                let player = pl.try_get_selected_char_mut()?;
+                player.recipe_book.insert(ARROW_RECIPE);
+                pl.manage_manufacture_shop(true).await?;
+                pl.set_manufacture_message("Arrows for all".to_string())
+                    .await?;
+                // recipes of the book only
+                let steel = ManufactureItem {
+                    recipe_id: 2,
+                    cost: 10,
+                };
+                pl.open_manufacture_shop(vec![steel]).await?;
+                assert!(!pl.try_get_selected_char()?.is_manufacturing());
+                let arrows = ManufactureItem {
+                    recipe_id: ARROW_RECIPE,
+                    cost: 100,
+                };
+                pl.open_manufacture_shop(vec![arrows]).await?;
+                let player = pl.try_get_selected_char()?;
+                assert!(player.is_manufacturing());
+                assert!(player.is_sitting());
+                assert_eq!(player.manufacture.message, "Arrows for all");
+                Ok(())
+            })
+        })
+        .await;
+        let order = |fee| Manufacture {
+            recipe_id: ARROW_RECIPE,
+            fee,
+            customer_name: "Customer".to_string(),
+        };
+        let reply = actor.ask(order(50)).await.unwrap();
+        assert!(matches!(reply, ManufactureReply::Refused(_)));
+        let reply = actor.ask(order(100)).await.unwrap();
+        assert!(matches!(
+            reply,
+            ManufactureReply::Crafted {
+                success: true,
+                cur_mp: 70,
+                ..
+            }
+        ));
+        on_actor(&actor, move |pl| {
+            Box::pin(async move {
+                // the fee comes once the customer has paid it
+                assert_eq!(count_of(pl, Inventory::ADENA_ID), 0);
+                pl.quit_manufacture_shop().await?;
+                let player = pl.try_get_selected_char()?;
+                assert!(!player.is_manufacturing());
+                assert_eq!(player.manufacture.items.len(), 1);
+                Ok(())
+            })
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_order_manufacture() {
+        let db_pool = get_test_db().await;
+        let controller = Arc::new(GameController::from_config(Arc::new(get_gs_config())).await);
+        let (_, maker, _maker_conn) = enter_game(&controller, &db_pool, "Maker", &[]).await;
+        let materials = [(Inventory::ADENA_ID, 150), (1864, 4), (1869, 2)];
+        let (_, customer, _conn) = enter_game(&controller, &db_pool, "Customer", &materials).await;
+        let maker_id = on_actor(&maker, |pl| {
+            Box::pin(async move {
+                make_crafter(pl)?;
+                pl.try_get_selected_char_mut()?
+                    .recipe_book
+                    .insert(ARROW_RECIPE);
+                pl.manage_manufacture_shop(true).await?;
+                let arrows = ManufactureItem {
+                    recipe_id: ARROW_RECIPE,
+                    cost: 100,
+                };
+                pl.open_manufacture_shop(vec![arrows]).await?;
+                Ok(pl.try_get_selected_char()?.get_object_id())
+            })
+        })
+        .await;
+        controller.register_player_object(maker_id, maker.clone());
+        on_actor(&customer, |pl| {
+            Box::pin(async move {
+                let player = pl.try_get_selected_char_mut()?;
+                player.char_model.cur_hp = 100.0;
+                player.stats.current_hp = 100.0;
+                Ok(())
+            })
+        })
+        .await;
+        settle(&maker).await;
+        settle(&customer).await;
+        let order = |actor: &ActorRef<PlayerClient>| {
+            let actor = actor.clone();
+            async move {
+                on_actor(&actor, move |pl| {
+                    Box::pin(pl.order_manufacture(maker_id, ARROW_RECIPE))
+                })
+                .await
+            }
+        };
+
+        // the maker is out of MP, nothing moves
+        on_actor(&maker, |pl| Box::pin(async move { set_mp(pl, 10.0) })).await;
+        let reply = order(&customer).await;
+        assert_eq!(
+            reply,
+            Some(ManufactureReply::Refused(
+                "The manufacturer doesn't have enough MP."
+            ))
+        );
+        on_actor(&customer, |pl| {
+            Box::pin(async move {
+                assert_eq!(count_of(pl, Inventory::ADENA_ID), 150);
+                assert_eq!(count_of(pl, 1864), 4);
+                assert_eq!(count_of(pl, 1869), 2);
+                Ok(())
+            })
+        })
+        .await;
+        on_actor(&maker, |pl| {
+            Box::pin(async move {
+                assert_eq!(count_of(pl, Inventory::ADENA_ID), 0);
+                set_mp(pl, 100.0)
+            })
+        })
+        .await;
+
+        let reply = order(&customer).await;
+        assert!(matches!(
+            reply,
+            Some(ManufactureReply::Crafted {
+                success: true,
+                cur_mp: 70,
+                ..
+            })
+        ));
+        on_actor(&customer, |pl| {
+            Box::pin(async move {
+                assert_eq!(count_of(pl, Inventory::ADENA_ID), 50);
+                assert_eq!(count_of(pl, 1864), 0);
+                assert_eq!(count_of(pl, 17), 500);
+                Ok(())
+            })
+        })
+        .await;
+        on_actor(&maker, |pl| {
+            Box::pin(async move {
+                assert_eq!(count_of(pl, Inventory::ADENA_ID), 100);
+                Ok(())
+            })
+        })
+        .await;
+    }
+}
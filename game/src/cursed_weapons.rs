@@ -0,0 +1,435 @@
+//! Cursed weapons.
+//!
+//! A cursed weapon (Zariche, Akamanah) may drop from any monster a player kills, but only one of
+//! each exists in the world. The owner turns into a demon, gets the skill of the weapon and the
+//! highest reputation, and the weapon grows a stage every few kills of the owner. Every kill
+//! also takes some minutes from the time the weapon has left, when it runs out the weapon
+//! vanishes and the owner gets back the reputation and pk kills from before. When the owner
+//! dies the weapon either vanishes or passes to the killer, with the kills and the time left.
+//! The weapons in the world are tracked by the
+//! [`CursedWeaponsManager`](crate::managers::CursedWeaponsManager).
+//!
+//! The server spawns no monsters and keeps no items on the ground yet, so a dropped weapon goes
+//! straight to the killer, whoever handles the monster calls
+//! [`PlayerClient::roll_cursed_weapon_drop`].
+
+use crate::controller::GameController;
+use crate::packets::to_client::extended::{CursedWeaponList, CursedWeaponLocation};
+use crate::packets::to_client::{CharInfo, SkillList, SystemMessage, UserInfo};
+use crate::pl_client::{DoLater, PlayerClient};
+use entities::entities::{cursed_weapon, item, skill};
+use l2_core::data::cursed_weapons::DROP_RATE_BASE;
+use l2_core::game_objects::creature::skill::Skill;
+use l2_core::game_objects::cursed_weapon::OwnedCursedWeapon;
+use l2_core::game_objects::player::user_info::UserInfoType;
+use rand::RngExt;
+use std::time::Duration;
+use tracing::warn;
+
+/// Reputation of the owner while the weapon is held
+const CURSED_REPUTATION: u32 = 9_999_999;
+
+/// Tells everyone the weapon has left the world.
+pub fn announce_cursed_weapon_gone(
+    controller: &GameController,
+    item_id: i32,
+) -> anyhow::Result<()> {
+    let name = controller
+        .cursed_weapons
+        .get(item_id)
+        .map_or("The cursed weapon", |t| t.name.as_str());
+    controller.broadcast_packet(SystemMessage::text(&format!("{name} has disappeared."))?);
+    Ok(())
+}
+
+impl PlayerClient {
+    /// Gives the owner back the weapon state when entering the world, the reputation and the
+    /// transformation are stored with the char.
+    pub fn restore_cursed_weapon(&mut self) -> anyhow::Result<()> {
+        let controller = self.controller.clone();
+        let player = self.try_get_selected_char_mut()?;
+        let owned = controller
+            .cursed_weapons_manager
+            .owned_by(player.char_model.id)
+            .and_then(|w| {
+                let template = controller.cursed_weapons.get(w.item_id)?;
+                Some(OwnedCursedWeapon {
+                    item_id: w.item_id,
+                    stage: template.stage(u32::try_from(w.kills).unwrap_or_default()),
+                })
+            });
+        player.cursed_weapon = owned;
+        if let Some(owned) = owned {
+            controller
+                .cursed_weapons_manager
+                .set_location(owned.item_id, *player.get_location());
+        }
+        self.set_cursed_weapon_skill()
+    }
+
+    /// The owner has the skill of the weapon at the level of its stage.
+    fn set_cursed_weapon_skill(&mut self) -> anyhow::Result<()> {
+        let controller = self.controller.clone();
+        let player = self.try_get_selected_char_mut()?;
+        let char_id = player.char_model.id;
+        let class_index = player.get_class_index();
+        let owned = player
+            .cursed_weapon
+            .and_then(|o| Some((controller.cursed_weapons.get(o.item_id)?.skill_id, o.stage)));
+        let skills = player.skills.get_or_insert_with(Vec::new);
+        skills.retain(|s| {
+            !controller
+                .cursed_weapons
+                .all()
+                .any(|t| t.skill_id == s.model.id)
+        });
+        if let Some((skill_id, stage)) = owned {
+            skills.push(Skill::from_model(skill::Model {
+                id: skill_id,
+                char_id,
+                level: i16::from(stage),
+                sub_level: 0,
+                class_index,
+            }));
+        }
+        Ok(())
+    }
+
+    /// Rolls the drop of the cursed weapons not in the world yet after the player killed a
+    /// monster, returns the item id of the weapon the player got.
+    pub async fn roll_cursed_weapon_drop(&mut self) -> anyhow::Result<Option<i32>> {
+        let controller = self.controller.clone();
+        let player = self.try_get_selected_char()?;
+        if player.cursed_weapon.is_some() || player.is_in_olympiad_mode() {
+            return Ok(None);
+        }
+        let dropped = controller.cursed_weapons.all().find(|t| {
+            !controller.cursed_weapons_manager.is_in_world(t.item_id)
+                && rand::rng().random_range(0..DROP_RATE_BASE) < t.drop_rate
+        });
+        let Some(template) = dropped else {
+            return Ok(None);
+        };
+        let weapon = cursed_weapon::Model {
+            item_id: template.item_id,
+            char_id: player.char_model.id,
+            expires_at: (controller.clock.now() + template.duration()).fixed_offset(),
+            ..Default::default()
+        };
+        Ok(self
+            .take_cursed_weapon(weapon)
+            .await?
+            .then_some(template.item_id))
+    }
+
+    /// Makes the player the owner of the weapon, keeping its kills and the time it has left.
+    /// Returns false if the player can't take it or somebody owns it already.
+    pub async fn take_cursed_weapon(
+        &mut self,
+        mut weapon: cursed_weapon::Model,
+    ) -> anyhow::Result<bool> {
+        let controller = self.controller.clone();
+        let Some(template) = controller.cursed_weapons.get(weapon.item_id) else {
+            warn!("Item {} is not a cursed weapon", weapon.item_id);
+            return Ok(false);
+        };
+        let player = self.try_get_selected_char()?;
+        if player.cursed_weapon.is_some() {
+            return Ok(false);
+        }
+        weapon.char_id = player.char_model.id;
+        weapon.player_reputation = player.char_model.reputation;
+        weapon.player_pk_kills = player.char_model.pk_kills;
+        if !controller
+            .cursed_weapons_manager
+            .claim(weapon.clone(), *player.get_location())
+        {
+            return Ok(false);
+        }
+        let stored = match cursed_weapon::Model::save(&self.db_pool, weapon.clone()).await {
+            Ok(()) => self.add_item_by_item_id(weapon.item_id, 1).await,
+            Err(e) => Err(e.into()),
+        };
+        if let Err(e) = stored {
+            // nobody got the weapon, it stays out of the world
+            controller.cursed_weapons_manager.remove(weapon.item_id);
+            cursed_weapon::Model::delete(&self.db_pool, weapon.item_id).await?;
+            return Err(e);
+        }
+        let player = self.try_get_selected_char_mut()?;
+        player.cursed_weapon = Some(OwnedCursedWeapon {
+            item_id: weapon.item_id,
+            stage: template.stage(u32::try_from(weapon.kills).unwrap_or_default()),
+        });
+        player.char_model.reputation = CURSED_REPUTATION;
+        player.char_model.pk_kills = weapon.kills;
+        player.char_model.transform_id = template.transform_id;
+        player.stats.current_hp = player.get_max_hp();
+        player.stats.current_mp = player.get_max_mp();
+        player.stats.current_cp = player.get_max_cp();
+        player.sync_vitals_to_model();
+        self.set_cursed_weapon_skill()?;
+        self.send_cursed_weapon_state().await?;
+        let name = self.try_get_selected_char()?.char_model.name.clone();
+        controller.broadcast_packet(SystemMessage::text(&format!(
+            "{name} has become the owner of {}.",
+            template.name
+        ))?);
+        controller.broadcast_packet(CursedWeaponLocation::new(
+            &controller.cursed_weapons_manager.locations(),
+        )?);
+        Ok(true)
+    }
+
+    /// Takes the weapon away from its owner, who becomes a human again with the reputation and
+    /// the pk kills from before. Returns the weapon as it was, e.g. to hand it over.
+    async fn release_cursed_weapon(&mut self) -> anyhow::Result<Option<cursed_weapon::Model>> {
+        let controller = self.controller.clone();
+        let player = self.try_get_selected_char_mut()?;
+        let Some(owned) = player.cursed_weapon.take() else {
+            return Ok(None);
+        };
+        let weapon = controller.cursed_weapons_manager.remove(owned.item_id);
+        if let Some(weapon) = &weapon {
+            player.char_model.reputation = weapon.player_reputation;
+            player.char_model.pk_kills = weapon.player_pk_kills;
+        }
+        player.char_model.transform_id = 0;
+        cursed_weapon::Model::delete(&self.db_pool, owned.item_id).await?;
+        if !self.destroy_item_by_item_id(owned.item_id, 1).await? {
+            warn!(
+                "Cursed weapon {} was not carried by its owner",
+                owned.item_id
+            );
+        }
+        // a copy kept anywhere else leaves the world too
+        item::Model::delete_by_item_id(&self.db_pool, owned.item_id).await?;
+        self.set_cursed_weapon_skill()?;
+        self.send_cursed_weapon_state().await?;
+        Ok(weapon)
+    }
+
+    /// The time of the weapon ran out, it leaves the world.
+    pub async fn end_cursed_weapon(&mut self) -> anyhow::Result<()> {
+        if let Some(weapon) = self.release_cursed_weapon().await? {
+            announce_cursed_weapon_gone(&self.controller, weapon.item_id)?;
+        }
+        Ok(())
+    }
+
+    /// The owner killed a player: the weapon may reach the next stage and loses some time.
+    pub async fn increase_cursed_weapon_kills(&mut self) -> anyhow::Result<()> {
+        let controller = self.controller.clone();
+        let player = self.try_get_selected_char()?;
+        let Some(owned) = player.cursed_weapon else {
+            return Ok(());
+        };
+        let (Some(mut weapon), Some(template)) = (
+            controller.cursed_weapons_manager.get(owned.item_id),
+            controller.cursed_weapons.get(owned.item_id),
+        ) else {
+            return Ok(());
+        };
+        weapon.kills += 1;
+        weapon.expires_at -= template.duration_lost();
+        let stage = template.stage(u32::try_from(weapon.kills).unwrap_or_default());
+        controller
+            .cursed_weapons_manager
+            .update_weapon(weapon.clone());
+        cursed_weapon::Model::save(&self.db_pool, weapon.clone()).await?;
+        let player = self.try_get_selected_char_mut()?;
+        player.char_model.pk_kills = weapon.kills;
+        player.cursed_weapon = Some(OwnedCursedWeapon { stage, ..owned });
+        if stage != owned.stage {
+            self.set_cursed_weapon_skill()?;
+            self.send_packet(SystemMessage::text(&format!(
+                "{} has reached stage {stage}.",
+                template.name
+            ))?)
+            .await?;
+        }
+        self.send_cursed_weapon_state().await
+    }
+
+    /// The owner was killed: the weapon vanishes or passes to the killer.
+    pub async fn on_cursed_weapon_owner_death(&mut self, killer_id: i32) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let Some(owned) = player.cursed_weapon else {
+            return Ok(());
+        };
+        let disappears = self
+            .controller
+            .cursed_weapons
+            .get(owned.item_id)
+            .is_none_or(|t| rand::rng().random_range(0..100) < t.disappear_chance);
+        let killer = self.controller.get_player_by_object_id(killer_id);
+        let Some(weapon) = self.release_cursed_weapon().await? else {
+            return Ok(());
+        };
+        let Some(killer) = killer.filter(|_| !disappears) else {
+            return announce_cursed_weapon_gone(&self.controller, weapon.item_id);
+        };
+        PlayerClient::do_later(
+            killer,
+            DoLater {
+                delay: Duration::ZERO,
+                callback: Box::new(move |pl: &mut PlayerClient| {
+                    Box::pin(async move {
+                        let item_id = weapon.item_id;
+                        if !pl.take_cursed_weapon(weapon).await? {
+                            announce_cursed_weapon_gone(&pl.controller, item_id)?;
+                        }
+                        Ok(())
+                    })
+                }),
+            },
+        );
+        Ok(())
+    }
+
+    /// The owner and everyone around see the transformation and the stage.
+    async fn send_cursed_weapon_state(&mut self) -> anyhow::Result<()> {
+        let controller = self.controller.clone();
+        let user_name = self.try_get_user()?.username.clone();
+        let player = self.try_get_selected_char()?;
+        let skill_list = SkillList::new(player, &controller.skills)?;
+        let user_info = UserInfo::new(player, UserInfoType::all(), &controller).await?;
+        controller.broadcast_packet_with_filter(
+            CharInfo::new(player, &controller.get_cfg())?,
+            Some(Box::new(move |acc, _| !acc.eq(&user_name))),
+        );
+        self.send_packet(skill_list).await?;
+        self.send_packet(user_info).await
+    }
+
+    /// The owner tells the manager where the weapon is.
+    pub fn report_cursed_weapon_location(&mut self) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        if let Some(owned) = player.cursed_weapon {
+            self.controller
+                .cursed_weapons_manager
+                .set_location(owned.item_id, *player.get_location());
+        }
+        Ok(())
+    }
+
+    pub async fn send_cursed_weapon_list(&mut self) -> anyhow::Result<()> {
+        let ids: Vec<i32> = self
+            .controller
+            .cursed_weapons
+            .all()
+            .map(|t| t.item_id)
+            .collect();
+        self.send_packet(CursedWeaponList::new(&ids)?).await
+    }
+
+    pub async fn send_cursed_weapon_location(&mut self) -> anyhow::Result<()> {
+        self.report_cursed_weapon_location()?;
+        let locations = self.controller.cursed_weapons_manager.locations();
+        self.send_packet(CursedWeaponLocation::new(&locations)?)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::controller::GameController;
+    use crate::managers::CursedWeaponsManager;
+    use crate::pl_client::GetCharInfo;
+    use crate::test_utils::test::{enter_game, get_gs_config, on_actor, settle};
+    use chrono::TimeDelta;
+    use entities::entities::cursed_weapon;
+    use l2_core::game_objects::player::Player;
+    use std::sync::Arc;
+    use test_utils::utils::get_test_db;
+
+    fn has_cursed_skill(player: &Player, skill_id: i32, level: i16) -> bool {
+        player.skills.as_ref().is_some_and(|s| {
+            s.iter()
+                .any(|s| s.model.id == skill_id && s.model.level == level)
+        })
+    }
+
+    fn weapon(item_id: i32, kills: i32, hours: i64) -> cursed_weapon::Model {
+        cursed_weapon::Model {
+            item_id,
+            kills,
+            expires_at: (chrono::Utc::now() + TimeDelta::hours(hours)).fixed_offset(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cursed_weapon_lifecycle() {
+        let db_pool = get_test_db().await;
+        let controller = Arc::new(GameController::from_config(Arc::new(get_gs_config())).await);
+        let (_, owner, _conn) = enter_game(&controller, &db_pool, "Owner", &[]).await;
+        let (_, killer, _killer_conn) = enter_game(&controller, &db_pool, "Killer", &[]).await;
+        let killer_object_id = killer.ask(GetCharInfo).await.unwrap().get_object_id();
+        controller.register_player_object(killer_object_id, killer.clone());
+
+        on_actor(&owner, |pl| {
+            Box::pin(async move {
+                assert!(pl.take_cursed_weapon(weapon(8190, 9, 5)).await?);
+                // one at a time
+                assert!(!pl.take_cursed_weapon(weapon(8689, 0, 5)).await?);
+                pl.increase_cursed_weapon_kills().await
+            })
+        })
+        .await;
+        let player = owner.ask(GetCharInfo).await.unwrap();
+        assert_eq!(player.get_cursed_weapon().map(|c| c.get_lvl()), Some(2));
+        assert!(has_cursed_skill(&player, 3603, 2));
+        assert_eq!(player.get_transform_id(), 301);
+        assert_eq!(player.char_model.pk_kills, 10);
+        assert!(player.inventory.get_item_by_item_id(8190).is_some());
+        assert!(controller.is_bound_item(8190) && controller.is_bound_item(8689));
+        let owned = controller.cursed_weapons_manager.get(8190).unwrap();
+        assert_eq!(owned.kills, 10);
+        assert_eq!(
+            cursed_weapon::Model::load_all(&db_pool).await.unwrap(),
+            vec![owned.clone()]
+        );
+
+        // the killer gets the weapon unless it disappears
+        on_actor(&owner, move |pl| {
+            Box::pin(pl.on_cursed_weapon_owner_death(killer_object_id))
+        })
+        .await;
+        settle(&killer).await;
+        let player = owner.ask(GetCharInfo).await.unwrap();
+        assert!(player.get_cursed_weapon().is_none());
+        assert!(!has_cursed_skill(&player, 3603, 2));
+        assert_eq!(player.get_transform_id(), 0);
+        assert_eq!(player.char_model.pk_kills, 0);
+        assert!(player.inventory.get_item_by_item_id(8190).is_none());
+        if let Some(passed) = controller.cursed_weapons_manager.get(8190) {
+            assert_eq!((passed.kills, passed.expires_at), (10, owned.expires_at));
+            let player = killer.ask(GetCharInfo).await.unwrap();
+            assert!(has_cursed_skill(&player, 3603, 2));
+            assert!(player.inventory.get_item_by_item_id(8190).is_some());
+        } else {
+            assert!(!controller.cursed_weapons_manager.is_in_world(8190));
+        }
+
+        // the weapon leaves the world once its time is up
+        on_actor(&owner, |pl| {
+            Box::pin(async move {
+                assert!(pl.take_cursed_weapon(weapon(8689, 0, 1)).await?);
+                Ok(())
+            })
+        })
+        .await;
+        controller.clock.advance(TimeDelta::hours(2));
+        let expired = CursedWeaponsManager::update(&controller, &db_pool)
+            .await
+            .unwrap();
+        assert_eq!(expired, vec![8689]);
+        settle(&owner).await;
+        let player = owner.ask(GetCharInfo).await.unwrap();
+        assert!(player.get_cursed_weapon().is_none());
+        assert!(!has_cursed_skill(&player, 3629, 1));
+        assert!(player.inventory.get_item_by_item_id(8689).is_none());
+        assert!(!controller.cursed_weapons_manager.is_in_world(8689));
+    }
+}
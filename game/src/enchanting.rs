@@ -0,0 +1,340 @@
+//! Enchanting items with scrolls.
+//!
+//! Using a scroll opens the enchant window, the player puts a weapon or armor of the grade of
+//! the scroll into it and confirms. Up to the safe level the enchant always succeeds, past it
+//! the chance comes from the enchant table. A failed enchant does what the scroll says: the
+//! item breaks into crystals, its enchant level goes back to 0 or, for blessed scrolls, stays.
+//! The bonus of the enchant level adds to the stats while the item is worn.
+
+use crate::packets::to_client::extended::ExPutEnchantTargetItemResult;
+use crate::packets::to_client::{
+    CharInfo, ChooseInventoryItem, EnchantResult, EnchantResultType, InventoryUpdate,
+    SystemMessage, UserInfo,
+};
+use crate::pl_client::PlayerClient;
+use entities::dao::item::LocType;
+use entities::entities::item;
+use l2_core::data::enchant::{EnchantFailure, EnchantTable};
+use l2_core::game_objects::player::EnchantRequest;
+use l2_core::game_objects::player::paper_doll::PaperDoll;
+use l2_core::game_objects::player::user_info::UserInfoType;
+use l2_core::game_objects::private_store_types::PrivateStoreType;
+use rand::RngExt;
+
+impl PlayerClient {
+    /// The player uses an enchant scroll, the client asks for the item to enchant.
+    pub async fn use_enchant_scroll(&mut self, scroll_object_id: i32) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        if player.enchant_request.is_some() {
+            return self
+                .send_packet(SystemMessage::text(
+                    "Another enchantment is in progress. Please complete it first.",
+                )?)
+                .await;
+        }
+        if player.get_private_store_type() != PrivateStoreType::None {
+            return self
+                .send_packet(SystemMessage::text(
+                    "You can't enchant while operating a private store.",
+                )?)
+                .await;
+        }
+        let Some(scroll_item_id) = player
+            .get_item(scroll_object_id)
+            .map(|i| i.item_model.item_id)
+        else {
+            return Ok(());
+        };
+        self.try_get_selected_char_mut()?.enchant_request = Some(EnchantRequest {
+            scroll_object_id,
+            item_object_id: None,
+        });
+        self.send_packet(ChooseInventoryItem::new(scroll_item_id)?)
+            .await
+    }
+
+    /// The table the scroll of the started enchant enchants the item by,
+    /// otherwise why the item can't be enchanted.
+    fn check_enchant(&self, object_id: i32) -> anyhow::Result<Result<EnchantTable, &'static str>> {
+        let controller = &self.controller;
+        let player = self.try_get_selected_char()?;
+        let cursed = player
+            .get_item(object_id)
+            .is_some_and(|i| controller.cursed_weapons.is_cursed(i.item_model.item_id));
+        if cursed {
+            return Ok(Err("Does not fit strengthening conditions of the scroll."));
+        }
+        Ok(player
+            .check_enchant_target(&controller.items_data, &controller.enchant, object_id)
+            .cloned())
+    }
+
+    /// The player puts the item into the enchant window.
+    pub async fn put_enchant_target(&mut self, object_id: i32) -> anyhow::Result<()> {
+        if let Err(reason) = self.check_enchant(object_id)? {
+            self.try_get_selected_char_mut()?.enchant_request = None;
+            self.send_packet(SystemMessage::text(reason)?).await?;
+            return self
+                .send_packet(ExPutEnchantTargetItemResult::new(0)?)
+                .await;
+        }
+        if let Some(request) = self.try_get_selected_char_mut()?.enchant_request.as_mut() {
+            request.item_object_id = Some(object_id);
+        }
+        self.send_packet(ExPutEnchantTargetItemResult::new(object_id)?)
+            .await
+    }
+
+    /// The player closes the enchant window, the scroll is kept.
+    pub async fn cancel_enchant(&mut self) -> anyhow::Result<()> {
+        self.try_get_selected_char_mut()?.enchant_request = None;
+        self.send_packet(EnchantResult::cancelled()?).await
+    }
+
+    /// The player confirms the enchant of the item.
+    pub async fn enchant_item(&mut self, object_id: i32) -> anyhow::Result<()> {
+        self.try_enchant(object_id, |rate| rand::rng().random_range(0..100) < rate)
+            .await
+    }
+
+    /// Takes the scroll and enchants the item when the roll succeeds with the chance in
+    /// percent, otherwise the scroll decides what happens to the item.
+    async fn try_enchant(
+        &mut self,
+        object_id: i32,
+        roll: impl FnOnce(u8) -> bool,
+    ) -> anyhow::Result<()> {
+        let controller = self.controller.clone();
+        let player = self.try_get_selected_char()?;
+        let scroll = player.get_enchant_scroll(&controller.items_data);
+        let request = player.enchant_request;
+        let (Some(scroll), Some(request)) = (scroll, request) else {
+            return self.cancel_enchant().await;
+        };
+        if request.item_object_id != Some(object_id) {
+            return self.cancel_enchant().await;
+        }
+        let table = match self.check_enchant(object_id)? {
+            Ok(table) => table,
+            Err(reason) => {
+                self.send_packet(SystemMessage::text(reason)?).await?;
+                return self.cancel_enchant().await;
+            }
+        };
+        let player = self.try_get_selected_char_mut()?;
+        player.enchant_request = None;
+        let Some(item) = player.get_item(object_id).cloned() else {
+            return self.cancel_enchant().await;
+        };
+        let template = controller
+            .items_data
+            .try_get_template(item.item_model.item_id)?;
+        let level = item.get_enchant_level();
+        let success = roll(table.success_rate(level));
+        let worn = item.item_model.loc == LocType::Paperdoll;
+
+        if !success && scroll.failure == EnchantFailure::Crystallize {
+            let crystal_id = template.crystal_type.crystal_item_id().unwrap_or_default();
+            let crystals = if crystal_id > 0 {
+                template.crystal_count
+            } else {
+                0
+            };
+            let give = [(crystal_id, crystals, 0)];
+            let give = if crystals > 0 { &give[..] } else { &[] };
+            self.exchange_items(&[(request.scroll_object_id, 1), (object_id, 1)], give)
+                .await?;
+            let result = if crystals > 0 {
+                EnchantResultType::Crystallized
+            } else {
+                EnchantResultType::NoCrystals
+            };
+            self.send_packet(EnchantResult::new(result, crystal_id, crystals, 0)?)
+                .await?;
+            self.send_packet(SystemMessage::text(&format!(
+                "The enchantment has failed! Your {} has been crystallized.",
+                template.name
+            ))?)
+            .await?;
+        } else {
+            let player = self.try_get_selected_char()?;
+            let Some(scroll_item) = player
+                .get_item(request.scroll_object_id)
+                .filter(|i| i.item_model.count >= 1)
+            else {
+                return self.cancel_enchant().await;
+            };
+            let (new_level, result) = match (success, scroll.failure) {
+                (true, _) => (level + 1, EnchantResultType::Success),
+                (false, EnchantFailure::Reset) => (0, EnchantResultType::Reset),
+                (false, _) => (level, EnchantResultType::Stayed),
+            };
+            let changed = (new_level != level).then_some(new_level);
+            item::Model::enchant(
+                &self.db_pool,
+                (scroll_item.item_model.id, scroll_item.item_model.count - 1),
+                item.item_model.id,
+                changed,
+            )
+            .await?;
+            let player = self.try_get_selected_char_mut()?;
+            let scroll_item = player.inventory.reduce_count(request.scroll_object_id, 1)?;
+            self.send_packet(InventoryUpdate::single(&scroll_item)?)
+                .await?;
+            if changed.is_some() {
+                let player = self.try_get_selected_char_mut()?;
+                if let Some(item) = player.inventory.items.get_mut(&object_id) {
+                    item.item_model.enchant_level = new_level;
+                    let updated = item.clone();
+                    self.send_packet(InventoryUpdate::single(&updated)?).await?;
+                }
+            }
+            self.send_packet(EnchantResult::new(result, 0, 0, new_level)?)
+                .await?;
+            if success && new_level >= table.announce_level {
+                let player = self.try_get_selected_char()?;
+                controller.broadcast_packet(SystemMessage::text(&format!(
+                    "{} has successfully enchanted {} to +{new_level}!",
+                    player.char_model.name, template.name
+                ))?);
+            }
+        }
+        if worn {
+            self.send_enchanted_look().await?;
+        }
+        Ok(())
+    }
+
+    /// The worn items changed: the stats take the new enchant bonuses and everyone around sees
+    /// the new look.
+    async fn send_enchanted_look(&mut self) -> anyhow::Result<()> {
+        let controller = self.controller.clone();
+        let user_name = self.try_get_user()?.username.clone();
+        let player = self.try_get_selected_char_mut()?;
+        player.paperdoll = PaperDoll::restore_visible_inventory(&player.inventory.items);
+        player.apply_enchant_stats(&controller.items_data, &controller.enchant);
+        let player = player.clone();
+        controller.broadcast_packet_with_filter(
+            CharInfo::new(&player, &controller.get_cfg())?,
+            Some(Box::new(move |acc, _| !acc.eq(&user_name))),
+        );
+        self.send_packet(UserInfo::new(&player, UserInfoType::all(), &controller).await?)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::test::{on_actor, spawn_player};
+    use test_utils::utils::get_test_db;
+
+    const CRIMSON_SWORD: i32 = 127;
+    const FLAMBERGE: i32 = 71;
+    const SCROLL: i32 = 955;
+    const CRYSTAL_SCROLL: i32 = 957;
+    const BLESSED_SCROLL: i32 = 6575;
+    const D_CRYSTAL: i32 = 1458;
+
+    /// Only the enchants which can't fail succeed
+    fn unlucky(rate: u8) -> bool {
+        rate >= 100
+    }
+
+    fn object_id(pl: &PlayerClient, item_id: i32) -> i32 {
+        pl.try_get_selected_char()
+            .unwrap()
+            .inventory
+            .get_item_by_item_id(item_id)
+            .map(|i| i.object_id.value())
+            .unwrap()
+    }
+
+    fn enchant_level(pl: &PlayerClient, object_id: i32) -> Option<i32> {
+        pl.try_get_selected_char()
+            .unwrap()
+            .get_item(object_id)
+            .map(|i| i.get_enchant_level())
+    }
+
+    #[tokio::test]
+    async fn test_enchant_item() {
+        let db_pool = get_test_db().await;
+        let (_, actor, _conn) = spawn_player(&db_pool, 40).await;
+        on_actor(&actor, move |pl| {
+            Box::pin(async move {
+                for (item_id, count) in [
+                    (CRIMSON_SWORD, 1),
+                    (FLAMBERGE, 1),
+                    (SCROLL, 2),
+                    (CRYSTAL_SCROLL, 1),
+                    (BLESSED_SCROLL, 1),
+                ] {
+                    pl.add_item_by_item_id(item_id, count).await?;
+                }
+                let sword = object_id(pl, CRIMSON_SWORD);
+
+                // the D-grade scroll doesn't fit a C-grade weapon
+                pl.use_enchant_scroll(object_id(pl, SCROLL)).await?;
+                pl.put_enchant_target(object_id(pl, FLAMBERGE)).await?;
+                assert!(pl.try_get_selected_char()?.enchant_request.is_none());
+
+                // below the safe level the enchant can't fail
+                pl.use_enchant_scroll(object_id(pl, SCROLL)).await?;
+                pl.put_enchant_target(sword).await?;
+                pl.try_enchant(sword, unlucky).await?;
+                assert_eq!(enchant_level(pl, sword), Some(1));
+                assert!(pl.try_get_selected_char()?.enchant_request.is_none());
+
+                // a blessed scroll keeps the level, a crystal scroll resets it
+                pl.use_enchant_scroll(object_id(pl, BLESSED_SCROLL)).await?;
+                pl.put_enchant_target(sword).await?;
+                pl.try_get_selected_char_mut()?
+                    .inventory
+                    .items
+                    .get_mut(&sword)
+                    .unwrap()
+                    .item_model
+                    .enchant_level = 5;
+                pl.try_enchant(sword, unlucky).await?;
+                assert_eq!(enchant_level(pl, sword), Some(5));
+                pl.use_enchant_scroll(object_id(pl, CRYSTAL_SCROLL)).await?;
+                pl.put_enchant_target(sword).await?;
+                pl.try_enchant(sword, unlucky).await?;
+                assert_eq!(enchant_level(pl, sword), Some(0));
+
+                // cancelled enchants keep the scroll
+                pl.use_enchant_scroll(object_id(pl, SCROLL)).await?;
+                pl.cancel_enchant().await?;
+                let scroll = object_id(pl, SCROLL);
+                assert_eq!(
+                    pl.try_get_selected_char()?
+                        .get_item(scroll)
+                        .unwrap()
+                        .item_model
+                        .count,
+                    1
+                );
+
+                // a failed normal scroll breaks the item into crystals
+                pl.try_get_selected_char_mut()?
+                    .inventory
+                    .items
+                    .get_mut(&sword)
+                    .unwrap()
+                    .item_model
+                    .enchant_level = 4;
+                pl.use_enchant_scroll(scroll).await?;
+                pl.put_enchant_target(sword).await?;
+                pl.try_enchant(sword, unlucky).await?;
+                let player = pl.try_get_selected_char()?;
+                assert!(player.get_item(sword).is_none());
+                assert!(player.get_item(scroll).is_none());
+                let crystals = player.inventory.get_item_by_item_id(D_CRYSTAL).unwrap();
+                assert_eq!(crystals.item_model.count, 764);
+                Ok(())
+            })
+        })
+        .await;
+    }
+}
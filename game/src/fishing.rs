@@ -0,0 +1,511 @@
+//! Fishing.
+//!
+//! With a fishing rod in the right hand and a lure in the left one the player casts the lure
+//! (the Fishing skill) into the water in front of them. The lure has to land on water which the
+//! player can see, checked against the water zones and the geodata, and one lure is used up
+//! with every cast. After a few seconds a fish of the grade of the lure bites and the fight
+//! begins: every second a fighting fish gets back some HP and it may change between resting
+//! and fighting. Pumping hurts a resting fish and reeling a fighting one, using the wrong skill
+//! lets the fish recover as much. The fish is caught once it has no HP left and it gets away
+//! when the time is up or it has recovered to twice its HP.
+
+use crate::packets::to_client::extended::{
+    FishingEnd, FishingHpRegen, FishingStart, FishingStartCombat,
+};
+use crate::packets::to_client::{
+    ActionFailed, CharInfo, MagicSkillUse, SystemMessage, SystemMessageParam, SystemMessageType,
+};
+use crate::pl_client::{DoLater, PlayerClient, PlayerTasks};
+use kameo::actor::ActorRef;
+use l2_core::game_objects::fishing::{
+    Fishing, FishingAction, FishingOutcome, FishingSkill, HookedFish,
+};
+use l2_core::game_objects::player::paper_doll::PaperDoll;
+use l2_core::game_objects::zone::{Location, ZoneId};
+use rand::RngExt;
+use std::f64::consts::TAU;
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+pub const FISHING: i32 = 1312;
+pub const PUMPING: i32 = 1313;
+pub const REELING: i32 = 1314;
+
+/// How far in front of the player the lure lands
+const BAIT_DISTANCE: RangeInclusive<i32> = 90..=250;
+/// How far below the player the water may be
+const MAX_WATER_DEPTH: i32 = 300;
+/// Seconds until a fish bites
+const BITE_DELAY: RangeInclusive<u64> = 3..=8;
+const FIGHT_TICK: Duration = Duration::from_secs(1);
+/// Damage of pumping and reeling at level 1 and what each level adds
+const SKILL_BASE_DAMAGE: u32 = 20;
+const SKILL_DAMAGE_PER_LEVEL: u32 = 5;
+
+/// Point `distance` away from the location in the direction it is heading to.
+#[allow(clippy::cast_possible_truncation)]
+fn point_ahead(location: &Location, distance: i32) -> (i32, i32) {
+    let angle = f64::from(location.heading) * TAU / 65536.0;
+    let distance = f64::from(distance);
+    (
+        location.x + (angle.cos() * distance).round() as i32,
+        location.y + (angle.sin() * distance).round() as i32,
+    )
+}
+
+impl PlayerClient {
+    /// Fishing, Pumping and Reeling.
+    pub async fn use_fishing_skill(
+        &mut self,
+        actor_ref: ActorRef<Self>,
+        skill_id: i32,
+    ) -> anyhow::Result<()> {
+        match skill_id {
+            FISHING => self.start_fishing(actor_ref).await,
+            PUMPING => self.pull_fish(PUMPING, FishingSkill::Pumping).await,
+            _ => self.pull_fish(REELING, FishingSkill::Reeling).await,
+        }
+    }
+
+    async fn refuse_fishing(&mut self, reason: &str) -> anyhow::Result<()> {
+        self.send_packet(SystemMessage::text(reason)?).await?;
+        self.send_packet(ActionFailed::normal()?).await
+    }
+
+    /// The lure and where it lands in the water, or why the player can't fish.
+    /// Returns the object id of the lure with the fishing state.
+    fn cast_lure(&self) -> anyhow::Result<Result<(i32, Fishing), &'static str>> {
+        let items = &self.controller.items_data;
+        let player = self.try_get_selected_char()?;
+        if player.is_fishing() {
+            return Ok(Err("You are already fishing."));
+        }
+        if player.is_inside_zone(ZoneId::Water) {
+            return Ok(Err("You can't fish while you are under water."));
+        }
+        let has_rod = player
+            .get_weapon()
+            .and_then(|w| items.get_template(w.item_model.item_id))
+            .is_some_and(|t| t.fishing_rod);
+        if !has_rod {
+            return Ok(Err("You need a fishing rod in hand to fish."));
+        }
+        let lure = player.get_item_by_slot(PaperDoll::LHand).and_then(|i| {
+            let item_id = i.item_model.item_id;
+            Some((
+                i.object_id.value(),
+                item_id,
+                items.get_template(item_id)?.lure?,
+            ))
+        });
+        let Some((lure_object_id, lure_id, grade)) = lure else {
+            return Ok(Err("You must put a lure on your hook before you can fish."));
+        };
+        let location = player.get_location();
+        let distance = rand::rng().random_range(BAIT_DISTANCE);
+        let (x, y) = point_ahead(location, distance);
+        let Some(surface) =
+            self.controller
+                .zone_manager
+                .data
+                .water_surface_at(x, y, location.z, MAX_WATER_DEPTH)
+        else {
+            return Ok(Err("You can't fish here."));
+        };
+        let geo = &self.controller.geo_engine;
+        if geo.get_nearest_z(x, y, surface) > surface
+            || !geo.can_see(location.x, location.y, location.z, x, y, surface)
+        {
+            return Ok(Err("You can't fish here."));
+        }
+        let bait = Location {
+            x,
+            y,
+            z: surface,
+            heading: 0,
+        };
+        Ok(Ok((
+            lure_object_id,
+            Fishing {
+                bait,
+                lure_id,
+                grade,
+                fish: None,
+            },
+        )))
+    }
+
+    /// Casts the lure, a fish bites after a few seconds and then the fight goes on every second
+    /// until the fishing ends.
+    pub async fn start_fishing(&mut self, actor_ref: ActorRef<Self>) -> anyhow::Result<()> {
+        let (lure_object_id, fishing) = match self.cast_lure()? {
+            Ok(cast) => cast,
+            Err(reason) => return self.refuse_fishing(reason).await,
+        };
+        if !self.destroy_item(lure_object_id, 1).await? {
+            return self
+                .refuse_fishing("You must put a lure on your hook before you can fish.")
+                .await;
+        }
+        let controller = self.controller.clone();
+        let user_name = self.try_get_user()?.username.clone();
+        let player = self.try_get_selected_char_mut()?;
+        // the last lure is gone from the hand
+        player.paperdoll = PaperDoll::restore_visible_inventory(&player.inventory.items);
+        let object_id = player.get_object_id();
+        controller.broadcast_packet(FishingStart::new(object_id, fishing.grade, &fishing.bait)?);
+        player.fishing = Some(fishing);
+        controller.broadcast_packet_with_filter(
+            CharInfo::new(player, &controller.get_cfg())?,
+            Some(Box::new(move |acc, _| !acc.eq(&user_name))),
+        );
+        self.send_packet(SystemMessage::text(
+            "You cast your line and start fishing.",
+        )?)
+        .await?;
+        let bite_delay = Duration::from_secs(rand::rng().random_range(BITE_DELAY));
+        self.schedule_task(
+            PlayerTasks::Fishing,
+            tokio::spawn(async move {
+                tokio::time::sleep(bite_delay).await;
+                let bite = DoLater {
+                    delay: Duration::ZERO,
+                    callback: Box::new(|pl: &mut PlayerClient| Box::pin(pl.fish_bites())),
+                };
+                if actor_ref.tell(bite).await.is_err() {
+                    return;
+                }
+                let mut interval = tokio::time::interval(FIGHT_TICK);
+                interval.tick().await;
+                loop {
+                    interval.tick().await;
+                    let tick = DoLater {
+                        delay: Duration::ZERO,
+                        callback: Box::new(|pl: &mut PlayerClient| Box::pin(pl.fishing_tick())),
+                    };
+                    if actor_ref.tell(tick).await.is_err() {
+                        return;
+                    }
+                }
+            }),
+        );
+        Ok(())
+    }
+
+    /// A fish of the grade of the lure bites and the fight begins.
+    pub async fn fish_bites(&mut self) -> anyhow::Result<()> {
+        let controller = self.controller.clone();
+        let player = self.try_get_selected_char_mut()?;
+        let level = player.char_model.level;
+        let object_id = player.get_object_id();
+        let Some(fishing) = player.fishing.as_mut().filter(|f| f.fish.is_none()) else {
+            return Ok(());
+        };
+        let total = controller.fish.total_chance(fishing.grade, level);
+        let template = (total > 0)
+            .then(|| rand::rng().random_range(0..total))
+            .and_then(|roll| controller.fish.pick(fishing.grade, level, roll));
+        let Some(template) = template else {
+            self.send_packet(SystemMessage::text("Nothing bites on your lure.")?)
+                .await?;
+            return self.end_fishing(false).await;
+        };
+        let fish = HookedFish::new(template);
+        let grade = fishing.grade;
+        let packet = FishingStartCombat::new(object_id, &fish, grade)?;
+        fishing.fish = Some(fish);
+        self.send_packet(packet).await?;
+        self.send_packet(SystemMessage::text(
+            "A fish is on the hook! Use Pumping while it rests and Reeling while it fights.",
+        )?)
+        .await
+    }
+
+    /// A second of the fight has passed.
+    pub async fn fishing_tick(&mut self) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char_mut()?;
+        let object_id = player.get_object_id();
+        let Some(fish) = player.fishing.as_mut().and_then(|f| f.fish.as_mut()) else {
+            return Ok(());
+        };
+        let outcome = fish.tick(rand::rng().random_range(0..100));
+        let packet = FishingHpRegen::new(object_id, fish, FishingAction::None, 0)?;
+        self.send_packet(packet).await?;
+        self.finish_fight(outcome).await
+    }
+
+    /// Pumping or reeling, the damage grows with the level of the skill.
+    async fn pull_fish(&mut self, skill_id: i32, skill: FishingSkill) -> anyhow::Result<()> {
+        let controller = self.controller.clone();
+        let player = self.try_get_selected_char_mut()?;
+        if player.fishing.as_ref().is_none_or(|f| f.fish.is_none()) {
+            return self
+                .refuse_fishing("You can use it only while a fish is on the hook.")
+                .await;
+        }
+        let level = player.get_skill_level(skill_id).unwrap_or(1).max(1);
+        if player.is_skill_disabled(skill_id) {
+            let mut sm = SystemMessage::new(
+                SystemMessageType::S1IsNotAvailableAtThisTimeBeingPreparedForReuse,
+            )?;
+            sm.add_param(SystemMessageParam::SkillName {
+                id: skill_id,
+                level,
+                sub_level: 0,
+            })?;
+            return self.send_packet(sm).await;
+        }
+        let level_u8 = u8::try_from(level).unwrap_or(1);
+        let reuse_delay = controller
+            .skills
+            .get_skill(skill_id as u32, level_u8)
+            .map_or(0, |s| s.reuse_delay_at(level_u8));
+        player.add_skill_reuse(skill_id, i32::from(level), i64::from(reuse_delay), 0);
+        let object_id = player.get_object_id();
+        let (x, y, z) = (player.get_x(), player.get_y(), player.get_z());
+        let damage = SKILL_BASE_DAMAGE + SKILL_DAMAGE_PER_LEVEL * u32::from(level_u8 - 1);
+        let Some(fish) = player.fishing.as_mut().and_then(|f| f.fish.as_mut()) else {
+            return Ok(());
+        };
+        let action = fish.pull(skill, damage);
+        let outcome = fish.outcome();
+        let packet = FishingHpRegen::new(object_id, fish, action, damage)?;
+        controller.broadcast_packet(MagicSkillUse::new(
+            object_id,
+            object_id,
+            skill_id,
+            i32::from(level),
+            0,
+            reuse_delay,
+            skill_id,
+            x,
+            y,
+            z,
+            x,
+            y,
+            z,
+        )?);
+        self.send_packet(packet).await?;
+        let text = match action {
+            FishingAction::Success => format!("Your pull did {damage} damage to the fish."),
+            _ => format!("Wrong skill, the fish recovered {damage} HP."),
+        };
+        self.send_packet(SystemMessage::text(&text)?).await?;
+        self.finish_fight(outcome).await
+    }
+
+    async fn finish_fight(&mut self, outcome: FishingOutcome) -> anyhow::Result<()> {
+        match outcome {
+            FishingOutcome::Ongoing => Ok(()),
+            FishingOutcome::Caught => self.end_fishing(true).await,
+            FishingOutcome::Escaped => self.end_fishing(false).await,
+        }
+    }
+
+    /// Reels the line in once the rod is no longer in the player's hand.
+    pub async fn check_fishing_rod(&mut self) -> anyhow::Result<()> {
+        let items = &self.controller.items_data;
+        let player = self.try_get_selected_char()?;
+        let has_rod = player
+            .get_weapon()
+            .and_then(|w| items.get_template(w.item_model.item_id))
+            .is_some_and(|t| t.fishing_rod);
+        if player.is_fishing() && !has_rod {
+            return self.end_fishing(false).await;
+        }
+        Ok(())
+    }
+
+    /// Reels the line in, the player gets the fish if it was `caught`.
+    pub async fn end_fishing(&mut self, caught: bool) -> anyhow::Result<()> {
+        self.remove_scheduled_task(PlayerTasks::Fishing);
+        let controller = self.controller.clone();
+        let user_name = self.try_get_user()?.username.clone();
+        let player = self.try_get_selected_char_mut()?;
+        let Some(fishing) = player.fishing.take() else {
+            return Ok(());
+        };
+        let object_id = player.get_object_id();
+        controller.broadcast_packet(FishingEnd::new(object_id, caught)?);
+        controller.broadcast_packet_with_filter(
+            CharInfo::new(player, &controller.get_cfg())?,
+            Some(Box::new(move |acc, _| !acc.eq(&user_name))),
+        );
+        match fishing.fish {
+            Some(fish) if caught => {
+                self.add_item_by_item_id(fish.item_id, 1).await?;
+                self.send_packet(SystemMessage::text("You caught something!")?)
+                    .await
+            }
+            Some(_) => {
+                self.send_packet(SystemMessage::text("The fish got away.")?)
+                    .await
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pl_client::GetCharInfo;
+    use crate::test_utils::test::{on_actor, spawn_player};
+    use entities::test_factories::factories::item_factory;
+    use l2_core::game_objects::fishing::FishMode;
+    use test_utils::utils::get_test_db;
+
+    /// On the shore of the lake of the elven village, facing the water
+    const SHORE: Location = Location {
+        x: 42050,
+        y: 48500,
+        z: -3480,
+        heading: 0,
+    };
+
+    #[test]
+    fn test_point_ahead() {
+        assert_eq!(point_ahead(&SHORE, 100), (42150, 48500));
+        let north = Location {
+            heading: 16384,
+            ..SHORE
+        };
+        assert_eq!(point_ahead(&north, 100), (42050, 48600));
+    }
+
+    #[tokio::test]
+    async fn test_fishing() {
+        let db_pool = get_test_db().await;
+        let (char_id, actor, _conn) = spawn_player(&db_pool, 20).await;
+        let rod = item_factory(&db_pool, |mut it| {
+            it.owner = char_id;
+            it.item_id = 6529;
+            it.enchant_level = 0;
+            it.loc_data = PaperDoll::RHand as i32;
+            it
+        })
+        .await;
+        let lures = item_factory(&db_pool, |mut it| {
+            it.owner = char_id;
+            it.item_id = 6519;
+            it.count = 2;
+            it.enchant_level = 0;
+            it.loc_data = PaperDoll::LHand as i32;
+            it
+        })
+        .await;
+        let fishing_actor = actor.clone();
+        on_actor(&actor, move |pl| {
+            Box::pin(async move {
+                let player = pl.try_get_selected_char_mut()?;
+                player.set_location(SHORE.x, SHORE.y, SHORE.z)?;
+                player.set_location_heading(SHORE.heading);
+                player.inventory.add_item(rod);
+                // no lure on the hook
+                player.paperdoll = PaperDoll::restore_visible_inventory(&player.inventory.items);
+                pl.start_fishing(fishing_actor.clone()).await?;
+                assert!(!pl.try_get_selected_char()?.is_fishing());
+
+                let player = pl.try_get_selected_char_mut()?;
+                player.inventory.add_item(lures);
+                player.paperdoll = PaperDoll::restore_visible_inventory(&player.inventory.items);
+                pl.start_fishing(fishing_actor.clone()).await?;
+                let player = pl.try_get_selected_char()?;
+                let bait = *player.get_fishing_bait_location().unwrap();
+                assert!((42140..=42300).contains(&bait.x));
+                assert_eq!((bait.y, bait.z), (48500, -3500));
+                let lure = player.get_item_by_slot(PaperDoll::LHand).unwrap();
+                assert_eq!(lure.item_model.count, 1);
+
+                // the fish is caught
+                pl.fish_bites().await?;
+                let fish = pl
+                    .try_get_selected_char_mut()?
+                    .fishing
+                    .as_mut()
+                    .and_then(|f| f.fish.as_mut())
+                    .unwrap();
+                assert!((6411..=6413).contains(&fish.item_id));
+                let item_id = fish.item_id;
+                fish.hp = SKILL_BASE_DAMAGE;
+                fish.mode = FishMode::Resting;
+                pl.use_fishing_skill(fishing_actor.clone(), PUMPING).await?;
+                let player = pl.try_get_selected_char()?;
+                assert!(!player.is_fishing());
+                assert!(player.inventory.get_item_by_item_id(item_id).is_some());
+
+                // the fish gets away when the time is up, the last lure is used up
+                pl.start_fishing(fishing_actor.clone()).await?;
+                pl.fish_bites().await?;
+                let player = pl.try_get_selected_char_mut()?;
+                assert!(player.get_item_by_slot(PaperDoll::LHand).is_none());
+                let fish = player
+                    .fishing
+                    .as_mut()
+                    .and_then(|f| f.fish.as_mut())
+                    .unwrap();
+                fish.time_left = 1;
+                pl.fishing_tick().await?;
+                assert!(!pl.try_get_selected_char()?.is_fishing());
+                // no lure left
+                pl.start_fishing(fishing_actor).await?;
+                assert!(!pl.try_get_selected_char()?.is_fishing());
+                Ok(())
+            })
+        })
+        .await;
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert!(player.get_item_by_slot(PaperDoll::LHand).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_fishing_interrupted() {
+        let db_pool = get_test_db().await;
+        let (char_id, actor, _conn) = spawn_player(&db_pool, 20).await;
+        let rod = item_factory(&db_pool, |mut it| {
+            it.owner = char_id;
+            it.item_id = 6529;
+            it.enchant_level = 0;
+            it.loc_data = PaperDoll::RHand as i32;
+            it
+        })
+        .await;
+        let lures = item_factory(&db_pool, |mut it| {
+            it.owner = char_id;
+            it.item_id = 6519;
+            it.count = 2;
+            it.enchant_level = 0;
+            it.loc_data = PaperDoll::LHand as i32;
+            it
+        })
+        .await;
+        let fishing_actor = actor.clone();
+        on_actor(&actor, move |pl| {
+            Box::pin(async move {
+                let player = pl.try_get_selected_char_mut()?;
+                player.set_location(SHORE.x, SHORE.y, SHORE.z)?;
+                player.set_location_heading(SHORE.heading);
+                let rod_id = player.inventory.add_item(rod).object_id.value();
+                player.inventory.add_item(lures);
+                player.paperdoll = PaperDoll::restore_visible_inventory(&player.inventory.items);
+
+                // leaving the shore reels the line in
+                pl.start_fishing(fishing_actor.clone()).await?;
+                assert!(pl.try_get_selected_char()?.is_fishing());
+                pl.teleport_to(SHORE.x, SHORE.y, SHORE.z).await?;
+                assert!(!pl.try_get_selected_char()?.is_fishing());
+                let player = pl.try_get_selected_char_mut()?;
+                player.set_teleporting(false);
+                player.set_location_heading(SHORE.heading);
+
+                // so does losing the rod
+                pl.start_fishing(fishing_actor).await?;
+                assert!(pl.try_get_selected_char()?.is_fishing());
+                assert!(pl.destroy_item(rod_id, 1).await?);
+                assert!(!pl.try_get_selected_char()?.is_fishing());
+                Ok(())
+            })
+        })
+        .await;
+    }
+}
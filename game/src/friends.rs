@@ -0,0 +1,451 @@
+//! Friend and block lists.
+//!
+//! Friendship is mutual: an invited char has to accept the request and both chars get each other
+//! on their lists. Friends see who is online and can send private messages to each other.
+//! The block list (and message refusal mode, which blocks everyone) makes the player refuse
+//! friend requests, friend messages and whispers.
+
+use crate::packets::to_client;
+use crate::packets::to_client::{SystemMessage, SystemMessageParam, SystemMessageType};
+use crate::pl_client::{DoLater, PlayerClient};
+use entities::dao::character_friend::FriendRelation;
+use entities::entities::{character, character_friend};
+use l2_core::game_objects::player::{Contact, Player};
+use std::time::Duration;
+use tracing::warn;
+
+impl PlayerClient {
+    const MAX_FRIEND_MESSAGE_LENGTH: usize = 300;
+
+    pub async fn send_friend_list(&mut self) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let packet = to_client::FriendList::new(player, &self.controller)?;
+        self.send_packet(packet).await
+    }
+
+    /// Called when a char enters or leaves the game
+    pub async fn on_friend_status_changed(&mut self, char_id: i32) -> anyhow::Result<()> {
+        if self.try_get_selected_char()?.is_friend(char_id) {
+            self.send_friend_list().await?;
+        }
+        Ok(())
+    }
+
+    pub async fn invite_friend(&mut self, name: &str) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let Some((friend_id, friend)) = self.controller.find_char_in_game(name) else {
+            return self
+                .send_packet(SystemMessage::new(
+                    SystemMessageType::TheTargetIsNotFoundInTheGame,
+                )?)
+                .await;
+        };
+        if friend_id == player.char_model.id
+            || player.is_friend(friend_id)
+            || player.friends.len() >= Player::MAX_FRIENDS
+        {
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        }
+        let requester = Contact::from_model(&player.char_model);
+        Self::do_later(
+            friend.actor,
+            DoLater {
+                delay: Duration::ZERO,
+                callback: Box::new(move |pl: &mut PlayerClient| {
+                    Box::pin(pl.receive_friend_invite(requester))
+                }),
+            },
+        );
+        Ok(())
+    }
+
+    async fn receive_friend_invite(&mut self, requester: Contact) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        if player.is_blocking(requester.char_id) || self.friend_request.is_some() {
+            self.send_to_char(requester.char_id, refusal_message());
+            return Ok(());
+        }
+        let packet = to_client::FriendAddRequest::new(&requester.name)?;
+        self.friend_request = Some(requester);
+        self.send_packet(packet).await
+    }
+
+    pub async fn answer_friend_invite(&mut self, accepted: bool) -> anyhow::Result<()> {
+        let Some(requester) = self.friend_request.take() else {
+            return Ok(());
+        };
+        if !accepted {
+            return Ok(());
+        }
+        if self
+            .controller
+            .get_char_in_game(requester.char_id)
+            .is_none()
+        {
+            return self
+                .send_packet(SystemMessage::new(
+                    SystemMessageType::TheTargetIsNotFoundInTheGame,
+                )?)
+                .await;
+        }
+        let player = self.try_get_selected_char()?;
+        if player.is_friend(requester.char_id) || player.friends.len() >= Player::MAX_FRIENDS {
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        }
+        let me = Contact::from_model(&player.char_model);
+        character_friend::Model::add_friends(&self.db_pool, me.char_id, requester.char_id).await?;
+        self.try_get_selected_char_mut()?
+            .add_friend(requester.clone());
+        self.send_packet(name_message(
+            SystemMessageType::S1HasBeenAddedToYourFriendsList,
+            &requester.name,
+        )?)
+        .await?;
+        self.send_friend_list().await?;
+        self.controller.run_on_char(
+            requester.char_id,
+            Box::new(move |pl: &mut PlayerClient| Box::pin(pl.on_friend_added(me))),
+        );
+        Ok(())
+    }
+
+    async fn on_friend_added(&mut self, friend: Contact) -> anyhow::Result<()> {
+        let name = friend.name.clone();
+        self.try_get_selected_char_mut()?.add_friend(friend);
+        self.send_packet(SystemMessage::new(
+            SystemMessageType::YouHaveSucceededInInvitingAFriendToYourFriendsList,
+        )?)
+        .await?;
+        self.send_packet(name_message(
+            SystemMessageType::S1HasBeenAddedToYourFriendsList,
+            &name,
+        )?)
+        .await?;
+        self.send_friend_list().await
+    }
+
+    pub async fn delete_friend(&mut self, name: &str) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let my_id = player.char_model.id;
+        let Some(friend) = player.get_friend_by_name(name).cloned() else {
+            warn!("{name} is not a friend of {}", player.char_model.name);
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        };
+        character_friend::Model::remove_friends(&self.db_pool, my_id, friend.char_id).await?;
+        self.try_get_selected_char_mut()?
+            .remove_friend(friend.char_id);
+        self.send_packet(name_message(
+            SystemMessageType::S1HasBeenRemovedFromYourFriendsList,
+            &friend.name,
+        )?)
+        .await?;
+        self.send_friend_list().await?;
+        self.controller.run_on_char(
+            friend.char_id,
+            Box::new(move |pl: &mut PlayerClient| Box::pin(pl.on_friend_removed(my_id))),
+        );
+        Ok(())
+    }
+
+    async fn on_friend_removed(&mut self, char_id: i32) -> anyhow::Result<()> {
+        let Some(friend) = self.try_get_selected_char_mut()?.remove_friend(char_id) else {
+            return Ok(());
+        };
+        self.send_packet(name_message(
+            SystemMessageType::S1HasBeenRemovedFromYourFriendsList,
+            &friend.name,
+        )?)
+        .await?;
+        self.send_friend_list().await
+    }
+
+    pub async fn send_friend_message(
+        &mut self,
+        receiver: &str,
+        message: String,
+    ) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        if message.is_empty() || message.chars().count() > Self::MAX_FRIEND_MESSAGE_LENGTH {
+            warn!("Invalid friend message from {}", player.char_model.name);
+            return Ok(());
+        }
+        let Some(friend) = player.get_friend_by_name(receiver) else {
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        };
+        let Some(target) = self.controller.get_char_in_game(friend.char_id) else {
+            return self
+                .send_packet(SystemMessage::new(
+                    SystemMessageType::TheTargetIsNotFoundInTheGame,
+                )?)
+                .await;
+        };
+        let sender_id = player.char_model.id;
+        let packet = to_client::FriendSay::new(&player.char_model.name, &friend.name, &message)?;
+        Self::do_later(
+            target.actor,
+            DoLater {
+                delay: Duration::ZERO,
+                callback: Box::new(move |pl: &mut PlayerClient| {
+                    Box::pin(pl.receive_friend_message(sender_id, packet))
+                }),
+            },
+        );
+        Ok(())
+    }
+
+    async fn receive_friend_message(
+        &mut self,
+        sender_id: i32,
+        packet: to_client::FriendSay,
+    ) -> anyhow::Result<()> {
+        if self.try_get_selected_char()?.is_blocking(sender_id) {
+            self.send_to_char(sender_id, refusal_message());
+            return Ok(());
+        }
+        self.send_packet(packet).await
+    }
+
+    pub async fn block_char(&mut self, name: &str) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        if player.char_model.name.eq_ignore_ascii_case(name) {
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        }
+        let Some(model) = character::Model::find_by_name(&self.db_pool, name).await? else {
+            return self
+                .send_packet(SystemMessage::new(
+                    SystemMessageType::TheTargetIsNotFoundInTheGame,
+                )?)
+                .await;
+        };
+        let player = self.try_get_selected_char_mut()?;
+        // friends must be removed from the friend list first
+        if player.is_friend(model.id) || !player.block(Contact::from_model(&model)) {
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        }
+        let relation = character_friend::Model {
+            char_id: player.char_model.id,
+            friend_id: model.id,
+            relation: FriendRelation::Block,
+        };
+        character_friend::Model::save(&self.db_pool, relation).await?;
+        self.send_packet(name_message(
+            SystemMessageType::S1HasBeenAddedToYourIgnoreList,
+            &model.name,
+        )?)
+        .await
+    }
+
+    pub async fn unblock_char(&mut self, name: &str) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let Some(blocked) = player.get_blocked_by_name(name).cloned() else {
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        };
+        let char_id = player.char_model.id;
+        character_friend::Model::delete(&self.db_pool, char_id, blocked.char_id).await?;
+        self.try_get_selected_char_mut()?.unblock(blocked.char_id);
+        self.send_packet(name_message(
+            SystemMessageType::S1HasBeenRemovedFromYourIgnoreList,
+            &blocked.name,
+        )?)
+        .await
+    }
+
+    pub async fn send_block_list(&mut self) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let mut lines = vec!["======<Ignore List>======".to_string()];
+        lines.extend(
+            player
+                .block_list
+                .iter()
+                .enumerate()
+                .map(|(i, b)| format!("{}. {}", i + 1, b.name)),
+        );
+        lines.push("==================".to_string());
+        for line in lines {
+            self.send_packet(name_message(SystemMessageType::S1_2, &line)?)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Message refusal mode, blocks messages and requests from everyone
+    pub async fn set_block_all(&mut self, block_all: bool) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char_mut()?;
+        player.set_block_all(block_all);
+        let status = to_client::CharEtcStatusUpdate::new(player)?;
+        self.send_packet(SystemMessage::new(if block_all {
+            SystemMessageType::MessageRefusalMode
+        } else {
+            SystemMessageType::MessageAcceptanceMode
+        })?)
+        .await?;
+        self.send_packet(status).await
+    }
+
+    /// Whisper to a char in game, the receiver refuses it when it blocks the sender.
+    pub async fn send_whisper(&mut self, receiver: &str, text: String) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let Some((receiver_id, target)) = self.controller.find_char_in_game(receiver) else {
+            return self
+                .send_packet(SystemMessage::new(
+                    SystemMessageType::TheTargetIsNotFoundInTheGame,
+                )?)
+                .await;
+        };
+        if receiver_id == player.char_model.id {
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        }
+        let sender_id = player.char_model.id;
+        let packet = to_client::CreatureSay::new(
+            player.get_object_id(),
+            to_client::ChatType::Whisper,
+            &player.char_model.name,
+            &text,
+        )?;
+        let echo = to_client::CreatureSay::new(
+            player.get_object_id(),
+            to_client::ChatType::Whisper,
+            &format!("->{}", target.name),
+            &text,
+        )?;
+        Self::do_later(
+            target.actor,
+            DoLater {
+                delay: Duration::ZERO,
+                callback: Box::new(move |pl: &mut PlayerClient| {
+                    Box::pin(pl.receive_whisper(sender_id, packet, echo))
+                }),
+            },
+        );
+        Ok(())
+    }
+
+    /// The sender sees its own whisper only once it has been delivered.
+    async fn receive_whisper(
+        &mut self,
+        sender_id: i32,
+        packet: to_client::CreatureSay,
+        echo: to_client::CreatureSay,
+    ) -> anyhow::Result<()> {
+        if self.try_get_selected_char()?.is_blocking(sender_id) {
+            self.send_to_char(sender_id, refusal_message());
+            return Ok(());
+        }
+        self.send_packet(packet).await?;
+        self.controller.run_on_char(
+            sender_id,
+            Box::new(move |pl: &mut PlayerClient| Box::pin(pl.send_packet(echo))),
+        );
+        Ok(())
+    }
+
+    fn send_to_char(&self, char_id: i32, message: anyhow::Result<SystemMessage>) {
+        match message {
+            Ok(message) => self.controller.run_on_char(
+                char_id,
+                Box::new(move |pl: &mut PlayerClient| Box::pin(pl.send_packet(message))),
+            ),
+            Err(e) => warn!("Can't build system message: {e}"),
+        }
+    }
+}
+
+fn name_message(message: SystemMessageType, name: &str) -> anyhow::Result<SystemMessage> {
+    let mut packet = SystemMessage::new(message)?;
+    packet.add_param(SystemMessageParam::Text(name.to_string()))?;
+    Ok(packet)
+}
+
+fn refusal_message() -> anyhow::Result<SystemMessage> {
+    SystemMessage::new(SystemMessageType::ThatPersonIsInMessageRefusalMode)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::controller::GameController;
+    use crate::packets::from_client::request_answer_friend_invite::RequestAnswerFriendInvite;
+    use crate::packets::from_client::request_block::{BlockRequest, RequestBlock};
+    use crate::packets::from_client::request_friend_del::RequestFriendDel;
+    use crate::packets::from_client::request_friend_invite::RequestFriendInvite;
+    use crate::pl_client::GetCharInfo;
+    use crate::test_utils::test::{enter_game, get_gs_config};
+    use entities::entities::character_friend;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use test_utils::utils::get_test_db;
+
+    async fn settle() {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    #[tokio::test]
+    async fn test_friend_invite_remove_and_block() {
+        let db_pool = get_test_db().await;
+        let controller = Arc::new(GameController::from_config(Arc::new(get_gs_config())).await);
+        let (alice_id, alice, _alice_conn) = enter_game(&controller, &db_pool, "Alice", &[]).await;
+        let (bob_id, bob, _bob_conn) = enter_game(&controller, &db_pool, "Bob", &[]).await;
+
+        alice
+            .ask(RequestFriendInvite {
+                name: "bob".to_string(),
+            })
+            .await
+            .unwrap();
+        settle().await;
+        bob.ask(RequestAnswerFriendInvite { response: 1 })
+            .await
+            .unwrap();
+        settle().await;
+        assert!(alice.ask(GetCharInfo).await.unwrap().is_friend(bob_id));
+        assert!(bob.ask(GetCharInfo).await.unwrap().is_friend(alice_id));
+        let saved = character_friend::Model::char_relations(&db_pool, alice_id)
+            .await
+            .unwrap();
+        assert_eq!(saved.len(), 1);
+
+        // a friend can't be blocked
+        bob.ask(RequestBlock {
+            request: BlockRequest::Block("Alice".to_string()),
+        })
+        .await
+        .unwrap();
+        assert!(!bob.ask(GetCharInfo).await.unwrap().is_blocked(alice_id));
+
+        bob.ask(RequestFriendDel {
+            name: "Alice".to_string(),
+        })
+        .await
+        .unwrap();
+        settle().await;
+        assert!(!alice.ask(GetCharInfo).await.unwrap().is_friend(bob_id));
+        assert!(!bob.ask(GetCharInfo).await.unwrap().is_friend(alice_id));
+
+        bob.ask(RequestBlock {
+            request: BlockRequest::Block("Alice".to_string()),
+        })
+        .await
+        .unwrap();
+        assert!(bob.ask(GetCharInfo).await.unwrap().is_blocked(alice_id));
+        // the invite is refused, so there is nothing to accept
+        alice
+            .ask(RequestFriendInvite {
+                name: "Bob".to_string(),
+            })
+            .await
+            .unwrap();
+        settle().await;
+        bob.ask(RequestAnswerFriendInvite { response: 1 })
+            .await
+            .unwrap();
+        settle().await;
+        assert!(!alice.ask(GetCharInfo).await.unwrap().is_friend(bob_id));
+        let saved = character_friend::Model::char_relations(&db_pool, bob_id)
+            .await
+            .unwrap();
+        assert_eq!(saved.len(), 1);
+
+        controller.logout_account("bob");
+        assert!(controller.get_char_in_game(bob_id).is_none());
+        assert!(controller.find_char_in_game("alice").is_some());
+    }
+}
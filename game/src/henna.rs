@@ -0,0 +1,195 @@
+//! Henna (dye symbols).
+//!
+//! Symbols are drawn for dyes and adena and modify base stats (STR, CON, DEX, INT, WIT, MEN).
+//! The amount of slots depends on the class level, removing a symbol costs adena and gives
+//! part of the dyes back.
+
+use crate::packets::to_client;
+use crate::packets::to_client::UserInfo;
+use crate::pl_client::PlayerClient;
+use entities::entities::character_henna;
+use l2_core::game_objects::player::user_info::UserInfoType;
+use tracing::warn;
+
+impl PlayerClient {
+    pub async fn send_henna_equip_list(&mut self) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let hennas = self
+            .controller
+            .hennas
+            .get_allowed_hennas(player.char_model.class_id);
+        self.send_packet(to_client::HennaEquipList::new(player, &hennas)?)
+            .await
+    }
+
+    pub async fn draw_henna(&mut self, dye_id: i32) -> anyhow::Result<()> {
+        let Some(henna) = self.controller.hennas.get_henna(dye_id).cloned() else {
+            warn!("Henna {dye_id} not found, possible cheating");
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        };
+        let player = self.try_get_selected_char()?;
+        if player.get_henna_empty_slots() == 0 {
+            return self
+                .send_packet(to_client::SystemMessage::new(
+                    to_client::SystemMessageType::NoSlotExistsToDrawTheSymbol,
+                )?)
+                .await;
+        }
+        let enough_dyes = player
+            .inventory
+            .get_item_by_item_id(henna.dye_item_id)
+            .is_some_and(|i| i.item_model.count >= henna.wear_count);
+        if !henna.is_allowed_class(player.char_model.class_id) || !enough_dyes {
+            warn!(
+                "{} can't draw henna {dye_id}, possible cheating",
+                player.char_model.name
+            );
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        }
+        // dyes are checked above, so nothing is taken when the player can't pay
+        if !self.reduce_adena(henna.wear_fee).await?
+            || !self
+                .destroy_item_by_item_id(henna.dye_item_id, henna.wear_count)
+                .await?
+        {
+            return Ok(());
+        }
+        let player = self.try_get_selected_char_mut()?;
+        let Some(slot) = player.add_henna(henna) else {
+            return Ok(());
+        };
+        let model = character_henna::Model {
+            char_id: player.char_model.id,
+            slot,
+            class_index: player.get_class_index(),
+            dye_id,
+        };
+        character_henna::Model::save(&self.db_pool, model).await?;
+        self.send_packet(to_client::SystemMessage::new(
+            to_client::SystemMessageType::TheSymbolHasBeenAdded,
+        )?)
+        .await?;
+        self.send_henna_changes().await
+    }
+
+    pub async fn remove_henna(&mut self, dye_id: i32) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let Some(slot) = player.get_henna_slot(dye_id) else {
+            warn!("Henna {dye_id} is not drawn, possible cheating");
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        };
+        let Some(henna) = self.controller.hennas.get_henna(dye_id) else {
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        };
+        let (fee, dye_item_id, dye_count) =
+            (henna.cancel_fee, henna.dye_item_id, henna.cancel_count);
+        if !self.reduce_adena(fee).await? {
+            return Ok(());
+        }
+        let player = self.try_get_selected_char_mut()?;
+        player.remove_henna(slot);
+        let (char_id, class_index) = (player.char_model.id, player.get_class_index());
+        character_henna::Model::delete(&self.db_pool, char_id, slot, class_index).await?;
+        self.add_item_by_item_id(dye_item_id, dye_count).await?;
+        self.send_packet(to_client::SystemMessage::new(
+            to_client::SystemMessageType::TheSymbolHasBeenDeleted,
+        )?)
+        .await?;
+        self.send_henna_changes().await
+    }
+
+    async fn send_henna_changes(&mut self) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let henna_info = to_client::HennaInfo::new(player)?;
+        let user_info = UserInfo::new(player, UserInfoType::all(), &self.controller).await?;
+        self.send_packet(henna_info).await?;
+        self.send_packet(user_info).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::controller::GameController;
+    use crate::packets::from_client::request_henna_equip::RequestHennaEquip;
+    use crate::packets::from_client::request_henna_remove::RequestHennaRemove;
+    use crate::pl_client::{GetCharInfo, PlayerClient};
+    use crate::test_utils::test::{get_gs_config, spawn_custom_player_client_actor};
+    use entities::dao::item::LocType;
+    use entities::entities::character_henna;
+    use entities::test_factories::factories::{char_factory, item_factory, user_factory};
+    use l2_core::data::classes::mapping::Class;
+    use l2_core::game_objects::player::Player;
+    use l2_core::game_objects::player::inventory::Inventory;
+    use std::net::Ipv4Addr;
+    use std::sync::Arc;
+    use test_utils::utils::get_test_db;
+    use tokio::io::split;
+
+    #[tokio::test]
+    async fn test_draw_and_remove_henna() {
+        let db_pool = get_test_db().await;
+        let controller = Arc::new(GameController::from_config(Arc::new(get_gs_config())).await);
+        let user = user_factory(&db_pool, |u| u).await;
+        let char = char_factory(&db_pool, |mut c| {
+            c.user_id = user.id;
+            c.class_id = Class::Warrior as i8;
+            c
+        })
+        .await;
+        let mut items = Vec::new();
+        for (item_id, count) in [(Inventory::ADENA_ID, 50_000), (4445, 10)] {
+            items.push(
+                item_factory(&db_pool, |mut i| {
+                    i.owner = char.id;
+                    i.item_id = item_id;
+                    i.count = count;
+                    i.loc = LocType::Inventory;
+                    i
+                })
+                .await,
+            );
+        }
+        let template = controller
+            .class_templates
+            .try_get_template(char.class_id)
+            .unwrap();
+        let player = Player::new(char.clone(), items, template.clone(), None);
+        let base_str = player.get_str();
+        let mut pl_client =
+            PlayerClient::new(Ipv4Addr::LOCALHOST, controller.clone(), db_pool.clone());
+        pl_client.set_user(user);
+        pl_client.set_account_chars(vec![player]);
+        pl_client.select_char(0);
+        let (_client, server) = tokio::io::duplex(65536);
+        let (r, w) = split(server);
+        let actor =
+            spawn_custom_player_client_actor(controller, db_pool.clone(), r, w, Some(pl_client))
+                .await;
+
+        actor.ask(RequestHennaEquip { symbol_id: 1 }).await.unwrap();
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert_eq!(player.get_henna_slot(1), Some(1));
+        assert_eq!(player.get_str(), base_str + 1);
+        assert_eq!(player.inventory.get_adena(), 13_000);
+        assert!(player.inventory.get_item_by_item_id(4445).is_none());
+        let saved = character_henna::Model::char_hennas(&db_pool, char.id, 0)
+            .await
+            .unwrap();
+        assert_eq!(saved.len(), 1);
+
+        actor
+            .ask(RequestHennaRemove { symbol_id: 1 })
+            .await
+            .unwrap();
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert_eq!(player.get_henna_slot(1), None);
+        assert_eq!(player.get_str(), base_str);
+        assert_eq!(player.inventory.get_adena(), 5_600);
+        let dyes = player.inventory.get_item_by_item_id(4445).unwrap();
+        assert_eq!(dyes.item_model.count, 5);
+        let saved = character_henna::Model::char_hennas(&db_pool, char.id, 0)
+            .await
+            .unwrap();
+        assert!(saved.is_empty());
+    }
+}
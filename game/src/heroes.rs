@@ -0,0 +1,564 @@
+//! Heroes.
+//!
+//! The best noble of each class becomes a hero when an olympiad period ends and stays one until
+//! the next period ends, GMs make heroes with `admin_hero <name> <days>` (0 days take the status
+//! away). The records are kept in the `hero` table, the ones still running are in the
+//! [`hero_list`](crate::controller::GameController::hero_list) of the controller.
+//!
+//! Heroes have the skills of the hero skill tree and the hero aura, talk in the hero voice chat
+//! and may take one hero weapon at the monument of heroes, where everyone can read their words
+//! and their diary. When the status runs out the skills and the hero weapons are taken away.
+
+use crate::bypass::{DialogNpc, HeroCommand};
+use crate::controller::GameController;
+use crate::packets::to_client::{
+    CharInfo, ChatType, CreatureSay, InventoryUpdate, ItemChange, SkillList, SystemMessage,
+    UserInfo,
+};
+use crate::pl_client::{DoLater, PlayerClient};
+use chrono::{DateTime, TimeDelta, Utc};
+use entities::DBPool;
+use entities::entities::{character, hero, hero_diary, item, skill};
+use kameo::actor::ActorRef;
+use l2_core::data::html::escape_html;
+use l2_core::game_objects::creature::skill::Skill;
+use l2_core::game_objects::item::ItemObject;
+use l2_core::game_objects::player::user_info::UserInfoType;
+use std::fmt::Write;
+use tracing::warn;
+
+/// A hero may talk in the hero voice chat once in this time
+const HERO_VOICE_DELAY: TimeDelta = TimeDelta::seconds(10);
+/// Diary entries shown at the monument
+const DIARY_ENTRIES: u64 = 10;
+const MAX_MESSAGE_LENGTH: usize = 300;
+
+/// Makes the char a hero until the time and writes the text to its diary, a char which is not a
+/// hero at the moment starts a new term.
+pub async fn make_hero(
+    controller: &GameController,
+    db_pool: &DBPool,
+    char: &character::Model,
+    expires_at: DateTime<Utc>,
+    text: &str,
+) -> anyhow::Result<hero::Model> {
+    let previous = hero::Model::find_by_char_id(db_pool, char.id).await?;
+    let new_term = controller.hero_until(char.id).is_none();
+    let hero = hero::Model {
+        char_id: char.id,
+        class_id: char.base_class_id,
+        count: previous.as_ref().map_or(0, |h| h.count) + i32::from(new_term),
+        expires_at: expires_at.fixed_offset(),
+        message: previous.and_then(|h| h.message),
+    };
+    hero::Model::save(db_pool, hero.clone()).await?;
+    hero_diary::Model::add(
+        db_pool,
+        char.id,
+        controller.clock.now().fixed_offset(),
+        text,
+    )
+    .await?;
+    controller.hero_list.insert(char.id, hero.clone());
+    Ok(hero)
+}
+
+impl PlayerClient {
+    /// Takes the hero status from the hero list when the player enters the world, the skill list
+    /// sent afterward has the hero skills. A status that ran out while the player was away takes
+    /// the hero weapons with it.
+    pub async fn restore_hero(&mut self) -> anyhow::Result<()> {
+        let controller = self.controller.clone();
+        let player = self.try_get_selected_char_mut()?;
+        player.hero_until = controller.hero_until(player.char_model.id);
+        let hero = player.is_hero();
+        self.set_hero_skills(hero)?;
+        if !hero {
+            self.remove_hero_items().await?;
+        }
+        Ok(())
+    }
+
+    /// Heroes have the skills of the hero skill tree, they are not stored with the class skills.
+    pub(crate) fn set_hero_skills(&mut self, hero: bool) -> anyhow::Result<()> {
+        let hero_skills = self.controller.skill_trees_data.get_hero_skills();
+        let player = self.try_get_selected_char_mut()?;
+        let char_id = player.char_model.id;
+        let class_index = player.get_class_index();
+        let skills = player.skills.get_or_insert_with(Vec::new);
+        skills.retain(|s| {
+            !hero_skills
+                .iter()
+                .any(|h| i32::try_from(h.skill_id()).is_ok_and(|id| id == s.model.id))
+        });
+        if hero {
+            skills.extend(hero_skills.iter().map(|h| {
+                Skill::from_model(skill::Model {
+                    id: i32::try_from(h.skill_id()).unwrap_or_default(),
+                    char_id,
+                    level: i16::from(h.skill_level()),
+                    sub_level: 0,
+                    class_index,
+                })
+            }));
+        }
+        Ok(())
+    }
+
+    /// Gives or takes the hero status of the player in game, the others see the aura change.
+    pub async fn set_hero(&mut self, hero_until: Option<DateTime<Utc>>) -> anyhow::Result<()> {
+        let controller = self.controller.clone();
+        let player = self.try_get_selected_char_mut()?;
+        let was_hero = player.is_hero();
+        let char_id = player.char_model.id;
+        player.hero_until = hero_until;
+        self.set_hero_skills(hero_until.is_some())?;
+        if hero_until.is_none() {
+            let removed = self.remove_hero_items().await?;
+            if !removed.is_empty() {
+                let changes: Vec<_> = removed.iter().map(|i| (ItemChange::Removed, i)).collect();
+                self.send_packet(InventoryUpdate::new(&changes)?).await?;
+            }
+        }
+        let user_name = self.try_get_user()?.username.clone();
+        let player = self.try_get_selected_char()?;
+        let skill_list = SkillList::new(player, &controller.skills)?;
+        let user_info = UserInfo::new(player, UserInfoType::all(), &controller).await?;
+        controller.broadcast_packet_with_filter(
+            CharInfo::new(player, &controller.get_cfg())?,
+            Some(Box::new(move |acc, _| !acc.eq(&user_name))),
+        );
+        self.send_packet(skill_list).await?;
+        self.send_packet(user_info).await?;
+        if was_hero != hero_until.is_some() {
+            let text = if hero_until.is_some() {
+                "You have become a hero."
+            } else {
+                "You are no longer a hero."
+            };
+            self.send_packet(SystemMessage::text(text)?).await?;
+        }
+        if let Some(char) = controller.get_char_in_game(char_id) {
+            self.schedule_hero_expiry(char.actor);
+        }
+        Ok(())
+    }
+
+    /// Checks the hero status again once it is due to run out.
+    pub fn schedule_hero_expiry(&self, actor: ActorRef<PlayerClient>) {
+        let Some(expire) = self
+            .try_get_selected_char()
+            .ok()
+            .and_then(|p| p.hero_until)
+        else {
+            return;
+        };
+        let delay = (expire - self.controller.clock.now())
+            .to_std()
+            .unwrap_or_default();
+        PlayerClient::do_later(
+            actor,
+            DoLater {
+                delay,
+                callback: Box::new(|pl: &mut PlayerClient| Box::pin(pl.check_hero_expiry())),
+            },
+        );
+    }
+
+    /// Takes the hero status away if it has run out, a prolonged one is left alone.
+    pub async fn check_hero_expiry(&mut self) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let char_id = player.char_model.id;
+        if !player.is_hero() || self.controller.hero_until(char_id).is_some() {
+            return Ok(());
+        }
+        self.controller.hero_list.remove(&char_id);
+        self.set_hero(None).await
+    }
+
+    /// Hero weapons are destroyed together with the status, the stored ones too.
+    /// Returns the items taken from the inventory.
+    async fn remove_hero_items(&mut self) -> anyhow::Result<Vec<ItemObject>> {
+        let hero_item_ids: Vec<i32> = self
+            .controller
+            .items_data
+            .hero_items()
+            .iter()
+            .map(|t| t.id)
+            .collect();
+        let player = self.try_get_selected_char_mut()?;
+        let char_id = player.char_model.id;
+        let hero_items: Vec<(i32, i64)> = player
+            .inventory
+            .items
+            .iter()
+            .filter(|(_, i)| hero_item_ids.contains(&i.item_model.item_id))
+            .map(|(object_id, i)| (*object_id, i.item_model.count))
+            .collect();
+        let mut removed = Vec::with_capacity(hero_items.len());
+        for (object_id, count) in hero_items {
+            removed.push(player.inventory.reduce_count(object_id, count)?);
+        }
+        item::Model::delete_owned(&self.db_pool, char_id, &hero_item_ids).await?;
+        Ok(removed)
+    }
+
+    /// Makes the char a hero for the days, whether it is online or not.
+    pub async fn grant_hero(&mut self, name: &str, days: i64) -> anyhow::Result<()> {
+        if days < 0 {
+            return self
+                .send_packet(SystemMessage::text("Usage: //hero <name> <days>")?)
+                .await;
+        }
+        let Some(char) = character::Model::find_by_name(&self.db_pool, name).await? else {
+            return self
+                .send_packet(SystemMessage::text(&format!(
+                    "Character {name} not found."
+                ))?)
+                .await;
+        };
+        let controller = self.controller.clone();
+        let now = controller.clock.now();
+        let hero_until = if days == 0 {
+            let Some((_, mut hero)) = controller.hero_list.remove(&char.id) else {
+                return self
+                    .send_packet(SystemMessage::text(&format!("{name} is not a hero."))?)
+                    .await;
+            };
+            hero.expires_at = now.fixed_offset();
+            hero::Model::save(&self.db_pool, hero).await?;
+            None
+        } else {
+            let start = controller.hero_until(char.id).unwrap_or(now);
+            let expires_at = start + TimeDelta::days(days);
+            let text = "Was proclaimed a hero by the gods.";
+            make_hero(&controller, &self.db_pool, &char, expires_at, text).await?;
+            Some(expires_at)
+        };
+        controller.run_on_char(
+            char.id,
+            Box::new(move |pl: &mut PlayerClient| Box::pin(pl.set_hero(hero_until))),
+        );
+        let text = hero_until.map_or_else(
+            || format!("{name} is no longer a hero."),
+            |t| format!("{name} is a hero until {}.", t.format("%Y-%m-%d %H:%M UTC")),
+        );
+        self.send_packet(SystemMessage::text(&text)?).await
+    }
+
+    /// Hero voice chat, everyone in game hears it.
+    pub async fn say_hero_voice(&mut self, text: &str) -> anyhow::Result<()> {
+        let now = self.controller.clock.now();
+        let player = self.try_get_selected_char()?;
+        if !player.is_hero() && !player.is_gm() {
+            warn!(
+                "{} is not a hero but tried to use the hero voice",
+                player.char_model.name
+            );
+            return Ok(());
+        }
+        if !player.is_gm() && self.hero_voice_at.is_some_and(|t| now < t + HERO_VOICE_DELAY) {
+            return self
+                .send_packet(SystemMessage::text(
+                    "Heroes may speak to everyone only once every 10 seconds.",
+                )?)
+                .await;
+        }
+        let packet = CreatureSay::new(
+            player.get_object_id(),
+            ChatType::HeroVoice,
+            &player.char_model.name,
+            text,
+        )?;
+        self.hero_voice_at = Some(now);
+        self.controller.broadcast_packet(packet);
+        Ok(())
+    }
+
+    /// Words of the hero shown at the monument of heroes.
+    pub async fn set_hero_message(&mut self, words: String) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let char_id = player.char_model.id;
+        if !player.is_hero() {
+            warn!(
+                "{} is not a hero but tried to write hero words",
+                player.char_model.name
+            );
+            return Ok(());
+        }
+        let message: String = words.chars().take(MAX_MESSAGE_LENGTH).collect();
+        let Some(hero) = self.controller.hero_list.get_mut(&char_id).map(|mut h| {
+            h.message = Some(message);
+            h.clone()
+        }) else {
+            return Ok(());
+        };
+        hero::Model::save(&self.db_pool, hero).await?;
+        Ok(())
+    }
+
+    pub async fn handle_hero_command(
+        &mut self,
+        npc: DialogNpc,
+        command: HeroCommand,
+    ) -> anyhow::Result<()> {
+        match command {
+            HeroCommand::List => self.show_heroes(npc).await,
+            HeroCommand::Diary(char_id) => self.show_hero_diary(npc, char_id).await,
+            HeroCommand::Weapons => self.show_hero_weapons(npc).await,
+            HeroCommand::TakeWeapon(item_id) => self.take_hero_weapon(npc, item_id).await,
+        }
+    }
+
+    async fn show_hero_refusal(&mut self, npc: DialogNpc, reason: &str) -> anyhow::Result<()> {
+        self.show_html(Some(npc), "hero/refused.htm", &[("reason", reason)])
+            .await
+    }
+
+    async fn show_heroes(&mut self, npc: DialogNpc) -> anyhow::Result<()> {
+        let mut heroes: Vec<hero::Model> = self
+            .controller
+            .hero_list
+            .iter()
+            .filter(|h| self.controller.hero_until(h.char_id).is_some())
+            .map(|h| h.clone())
+            .collect();
+        heroes.sort_by_key(|h| (h.class_id, h.char_id));
+        let ids: Vec<i32> = heroes.iter().map(|h| h.char_id).collect();
+        let chars = character::Model::find_by_ids(&self.db_pool, &ids).await?;
+        let mut list = String::new();
+        for hero in &heroes {
+            let Some(char) = chars.iter().find(|c| c.id == hero.char_id) else {
+                continue;
+            };
+            let message = escape_html(hero.message.as_deref().unwrap_or_default());
+            writeln!(
+                list,
+                "<a action=\"bypass -h npc_{}_HeroDiary {}\">{}</a>: {message}<br>",
+                npc.object_id, char.id, char.name
+            )?;
+        }
+        if list.is_empty() {
+            list.push_str("There are no heroes at the moment.<br>");
+        }
+        self.show_html_markup(Some(npc), "hero/list.htm", &[], &[("heroes", &list)])
+            .await
+    }
+
+    async fn show_hero_diary(&mut self, npc: DialogNpc, char_id: i32) -> anyhow::Result<()> {
+        let chars = if self.controller.hero_until(char_id).is_some() {
+            character::Model::find_by_ids(&self.db_pool, &[char_id]).await?
+        } else {
+            vec![]
+        };
+        let Some(char) = chars.first() else {
+            return self
+                .show_hero_refusal(npc, "There is no such hero.")
+                .await;
+        };
+        let mut entries = String::new();
+        for entry in hero_diary::Model::latest(&self.db_pool, char_id, DIARY_ENTRIES).await? {
+            writeln!(
+                entries,
+                "{}: {}<br>",
+                entry.created_at.format("%Y-%m-%d"),
+                escape_html(&entry.text)
+            )?;
+        }
+        self.show_html_markup(
+            Some(npc),
+            "hero/diary.htm",
+            &[("hero", &char.name)],
+            &[("entries", &entries)],
+        )
+        .await
+    }
+
+    fn hero_weapon_refusal(&self) -> anyhow::Result<Option<&'static str>> {
+        let player = self.try_get_selected_char()?;
+        let items_data = &self.controller.items_data;
+        let has_hero_item = player.inventory.items.values().any(|i| {
+            items_data
+                .get_template(i.item_model.item_id)
+                .is_some_and(|t| t.hero)
+        });
+        Ok(if !player.is_hero() {
+            Some("Only heroes may receive a hero weapon.")
+        } else if has_hero_item {
+            Some("You already have a hero weapon.")
+        } else {
+            None
+        })
+    }
+
+    async fn show_hero_weapons(&mut self, npc: DialogNpc) -> anyhow::Result<()> {
+        if let Some(reason) = self.hero_weapon_refusal()? {
+            return self.show_hero_refusal(npc, reason).await;
+        }
+        let mut weapons = String::new();
+        for template in self.controller.items_data.hero_items() {
+            writeln!(
+                weapons,
+                "<a action=\"bypass -h npc_{}_HeroWeapon {}\">{}</a><br>",
+                npc.object_id, template.id, template.name
+            )?;
+        }
+        self.show_html_markup(Some(npc), "hero/weapons.htm", &[], &[("weapons", &weapons)])
+            .await
+    }
+
+    async fn take_hero_weapon(&mut self, npc: DialogNpc, item_id: i32) -> anyhow::Result<()> {
+        if let Some(reason) = self.hero_weapon_refusal()? {
+            return self.show_hero_refusal(npc, reason).await;
+        }
+        let Some(name) = self
+            .controller
+            .items_data
+            .get_template(item_id)
+            .filter(|t| t.hero)
+            .map(|t| t.name.clone())
+        else {
+            return self
+                .show_hero_refusal(npc, "That is not a hero weapon.")
+                .await;
+        };
+        self.add_item_by_item_id(item_id, 1).await?;
+        self.show_html(Some(npc), "hero/received.htm", &[("weapon", &name)])
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pl_client::GetCharInfo;
+    use crate::test_utils::test::{enter_game, get_gs_config, npc, on_actor, settle};
+    use entities::dao::item::LocType;
+    use entities::test_factories::factories::item_factory;
+    use l2_core::game_objects::player::Player;
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+    use std::sync::Arc;
+    use test_utils::utils::get_test_db;
+
+    fn has_hero_skill(player: &Player) -> bool {
+        player
+            .skills
+            .as_ref()
+            .is_some_and(|s| s.iter().any(|s| s.model.id == 395))
+    }
+
+    #[tokio::test]
+    async fn test_hero_lifecycle() {
+        let db_pool = get_test_db().await;
+        let controller = Arc::new(GameController::from_config(Arc::new(get_gs_config())).await);
+        let name = "Hero".to_string();
+        let (char_id, actor, _conn) = enter_game(&controller, &db_pool, &name, &[]).await;
+
+        // only heroes get a weapon
+        on_actor(&actor, |pl| {
+            Box::pin(pl.handle_hero_command(npc(31690), HeroCommand::TakeWeapon(6611)))
+        })
+        .await;
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert!(player.inventory.get_item_by_item_id(6611).is_none());
+
+        let granted = name.clone();
+        on_actor(&actor, move |pl| {
+            Box::pin(async move { pl.grant_hero(&granted, 30).await })
+        })
+        .await;
+        settle(&actor).await;
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert!(player.is_hero());
+        assert!(has_hero_skill(&player));
+        on_actor(&actor, |pl| {
+            Box::pin(async move {
+                pl.handle_hero_command(npc(31690), HeroCommand::TakeWeapon(6611))
+                    .await?;
+                // one weapon only
+                pl.handle_hero_command(npc(31690), HeroCommand::TakeWeapon(6612))
+                    .await?;
+                pl.set_hero_message("Glory to the heroes".to_string()).await
+            })
+        })
+        .await;
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert!(player.inventory.get_item_by_item_id(6611).is_some());
+        assert!(player.inventory.get_item_by_item_id(6612).is_none());
+        // the weapon can't be stored, a copy stored before is found anyway
+        let storable = on_actor(&actor, |pl| {
+            let storable = pl.storable_items().map(|items| {
+                items
+                    .iter()
+                    .map(|i| i.item_model.item_id)
+                    .collect::<Vec<_>>()
+            });
+            Box::pin(async move { storable })
+        })
+        .await;
+        assert!(!storable.contains(&6611));
+        item_factory(&db_pool, |mut i| {
+            i.owner = char_id;
+            i.item_id = 6612;
+            i.loc = LocType::Warehouse;
+            i
+        })
+        .await;
+        let hero = hero::Model::find_by_char_id(&db_pool, char_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(hero.count, 1);
+        assert_eq!(hero.message.as_deref(), Some("Glory to the heroes"));
+        let diary = hero_diary::Model::latest(&db_pool, char_id, 10)
+            .await
+            .unwrap();
+        assert_eq!(diary.len(), 1);
+
+        // the status runs out with the time
+        controller.clock.advance(TimeDelta::days(31));
+        on_actor(&actor, |pl| Box::pin(pl.check_hero_expiry())).await;
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert!(!player.is_hero());
+        assert!(!has_hero_skill(&player));
+        assert!(player.inventory.get_item_by_item_id(6611).is_none());
+        assert!(controller.hero_list.is_empty());
+        let stored = item::Entity::find()
+            .filter(item::Column::Owner.eq(char_id))
+            .all(&db_pool)
+            .await
+            .unwrap();
+        assert!(
+            stored
+                .iter()
+                .all(|i| i.item_id != 6611 && i.item_id != 6612)
+        );
+
+        // a weapon kept after the status ran out while away is taken at the next login
+        let kept = item_factory(&db_pool, |mut i| {
+            i.owner = char_id;
+            i.item_id = 6613;
+            i.loc = LocType::Inventory;
+            i
+        })
+        .await;
+        let kept_id = kept.id;
+        on_actor(&actor, move |pl| {
+            Box::pin(async move {
+                let player = pl.try_get_selected_char_mut()?;
+                player.inventory.add_item(kept);
+                pl.restore_hero().await
+            })
+        })
+        .await;
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert!(player.inventory.get_item_by_item_id(6613).is_none());
+        assert!(
+            item::Entity::find_by_id(kept_id)
+                .one(&db_pool)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,325 @@
+//! Inventory changes made by game actions (consumables, fees).
+
+use crate::packets::to_client;
+use crate::packets::to_client::ItemChange;
+use crate::packets::to_client::extended::InventoryAdenaInfo;
+use crate::pl_client::PlayerClient;
+use anyhow::bail;
+use entities::dao::item::LocType;
+use entities::entities::item;
+use l2_core::game_objects::player::inventory::Inventory;
+use std::collections::HashMap;
+
+impl PlayerClient {
+    /// Takes `count` items of the template from the inventory, saves the stack and
+    /// notifies the client. Returns `false` when the player doesn't have enough.
+    pub async fn destroy_item_by_item_id(
+        &mut self,
+        item_id: i32,
+        count: i64,
+    ) -> anyhow::Result<bool> {
+        let object_id = self
+            .try_get_selected_char()?
+            .inventory
+            .get_item_by_item_id(item_id)
+            .map(|i| i.object_id.value());
+        match object_id {
+            Some(object_id) => self.destroy_item(object_id, count).await,
+            None => Ok(false),
+        }
+    }
+
+    /// Same as [`Self::destroy_item_by_item_id`] for the item with the object id, which may be
+    /// equipped as well.
+    ///
+    /// # Errors
+    /// - when the stack can't be saved, the player keeps the items then
+    pub async fn destroy_item(&mut self, object_id: i32, count: i64) -> anyhow::Result<bool> {
+        let stack = self
+            .try_get_selected_char()?
+            .get_item(object_id)
+            .filter(|i| count > 0 && i.item_model.count >= count)
+            .map(|i| (i.item_model.id, i.item_model.count - count));
+        let Some((id, left)) = stack else {
+            return Ok(false);
+        };
+        // the DB goes first, so a failed save doesn't take the items in game only
+        item::Model::update_count(&self.db_pool, id, left).await?;
+        let updated = self
+            .try_get_selected_char_mut()?
+            .inventory
+            .reduce_count(object_id, count)?;
+        let item_id = updated.item_model.item_id;
+        self.send_packet(to_client::InventoryUpdate::single(&updated)?)
+            .await?;
+        if item_id == Inventory::ADENA_ID {
+            let player = self.try_get_selected_char()?;
+            self.send_packet(InventoryAdenaInfo::new(player)?).await?;
+        }
+        self.check_fishing_rod().await?;
+        Ok(true)
+    }
+
+    /// Gives items to the player: stackable ones go to the existing stack, the others are
+    /// created one by one. Saves the items and notifies the client.
+    pub async fn add_item_by_item_id(&mut self, item_id: i32, count: i64) -> anyhow::Result<()> {
+        if count <= 0 {
+            return Ok(());
+        }
+        let stackable = self
+            .controller
+            .items_data
+            .try_get_template(item_id)?
+            .stackable;
+        let player = self.try_get_selected_char_mut()?;
+        let owner = player.char_model.id;
+        if stackable
+            && let Some(updated) = player.inventory.increase_count_by_item_id(item_id, count)
+        {
+            item::Model::update_count(&self.db_pool, updated.item_model.id, updated.item_model.count)
+                .await?;
+            self.send_packet(to_client::InventoryUpdate::single(&updated)?)
+                .await?;
+        } else {
+            let (models, per_item) = if stackable { (1, count) } else { (count, 1) };
+            let mut added = Vec::new();
+            for _ in 0..models {
+                let model = item::Model::create(
+                    &self.db_pool,
+                    item::Model {
+                        owner,
+                        item_id,
+                        count: per_item,
+                        loc: LocType::Inventory,
+                        ..Default::default()
+                    },
+                )
+                .await?;
+                added.push(self.try_get_selected_char_mut()?.inventory.add_item(model));
+            }
+            let changes: Vec<_> = added.iter().map(|i| (ItemChange::Added, i)).collect();
+            self.send_packet(to_client::InventoryUpdate::new(&changes)?)
+                .await?;
+        }
+        if item_id == Inventory::ADENA_ID {
+            let player = self.try_get_selected_char()?;
+            self.send_packet(InventoryAdenaInfo::new(player)?).await?;
+        }
+        Ok(())
+    }
+
+    /// Puts items which already belong to the char in DB (e.g. mail attachments) into
+    /// the inventory. Stackable items are merged into the existing stacks, a stack the items
+    /// were already merged into in DB just takes the saved count.
+    pub async fn receive_items(&mut self, items: Vec<item::Model>) -> anyhow::Result<()> {
+        let mut changed = Vec::with_capacity(items.len());
+        let mut adena_changed = false;
+        for model in items {
+            let stackable = self
+                .controller
+                .items_data
+                .try_get_template(model.item_id)?
+                .stackable;
+            adena_changed |= model.item_id == Inventory::ADENA_ID;
+            let player = self.try_get_selected_char_mut()?;
+            // the items were added to this stack in DB already
+            if let Some(stack) = player
+                .inventory
+                .items
+                .values_mut()
+                .find(|i| i.item_model.id == model.id)
+            {
+                stack.item_model = model;
+                changed.push((ItemChange::Modified, stack.clone()));
+                continue;
+            }
+            if stackable
+                && let Some(updated) = player
+                    .inventory
+                    .increase_count_by_item_id(model.item_id, model.count)
+            {
+                item::Model::update_count(
+                    &self.db_pool,
+                    updated.item_model.id,
+                    updated.item_model.count,
+                )
+                .await?;
+                // the merged stack isn't needed anymore
+                item::Model::update_count(&self.db_pool, model.id, 0).await?;
+                changed.push((ItemChange::Modified, updated));
+            } else {
+                changed.push((ItemChange::Added, player.inventory.add_item(model)));
+            }
+        }
+        if changed.is_empty() {
+            return Ok(());
+        }
+        let changes: Vec<_> = changed.iter().map(|(c, i)| (*c, i)).collect();
+        self.send_packet(to_client::InventoryUpdate::new(&changes)?)
+            .await?;
+        if adena_changed {
+            let player = self.try_get_selected_char()?;
+            self.send_packet(InventoryAdenaInfo::new(player)?).await?;
+        }
+        Ok(())
+    }
+
+    /// Shortcut for fees, sends "not enough adena" when the player can't pay.
+    pub async fn reduce_adena(&mut self, count: i64) -> anyhow::Result<bool> {
+        if count <= 0 {
+            return Ok(true);
+        }
+        if self
+            .destroy_item_by_item_id(Inventory::ADENA_ID, count)
+            .await?
+        {
+            return Ok(true);
+        }
+        self.send_packet(to_client::SystemMessage::new(
+            to_client::SystemMessageType::YouDoNotHaveEnoughAdena,
+        )?)
+        .await?;
+        Ok(false)
+    }
+
+    /// Trades inventory items for new ones, e.g. with npc shops. `take` are `(object id, count)`
+    /// of inventory items, `give` are `(item id, count, enchant level)`. Everything is saved in
+    /// one transaction, so either the whole trade happens or nothing changes.
+    ///
+    /// # Errors
+    /// - when an item to take is missing or there is not enough of it
+    pub async fn exchange_items(
+        &mut self,
+        take: &[(i32, i64)],
+        give: &[(i32, i64, i32)],
+    ) -> anyhow::Result<()> {
+        let controller = self.controller.clone();
+        let player = self.try_get_selected_char()?;
+        let owner = player.char_model.id;
+        // object id -> new stack size
+        let mut counts: HashMap<i32, i64> = HashMap::new();
+        for &(object_id, count) in take {
+            let Some(item) = player.inventory.items.get(&object_id) else {
+                bail!("Item {object_id} not found in inventory");
+            };
+            let left = counts.entry(object_id).or_insert(item.item_model.count);
+            *left -= count;
+            if count <= 0 || *left < 0 {
+                bail!("Not enough items {} to take {count}", item.item_model.item_id);
+            }
+        }
+        let mut created: Vec<item::Model> = Vec::new();
+        for &(item_id, count, enchant_level) in give {
+            if count <= 0 {
+                continue;
+            }
+            if !controller.items_data.try_get_template(item_id)?.stackable {
+                for _ in 0..count {
+                    created.push(item::Model {
+                        owner,
+                        item_id,
+                        count: 1,
+                        enchant_level,
+                        loc: LocType::Inventory,
+                        ..Default::default()
+                    });
+                }
+            } else if let Some(stack) = player.inventory.get_item_by_item_id(item_id) {
+                let object_id = stack.object_id.value();
+                *counts.entry(object_id).or_insert(stack.item_model.count) += count;
+            } else if let Some(new_stack) = created.iter_mut().find(|m| m.item_id == item_id) {
+                new_stack.count += count;
+            } else {
+                created.push(item::Model {
+                    owner,
+                    item_id,
+                    count,
+                    loc: LocType::Inventory,
+                    ..Default::default()
+                });
+            }
+        }
+        let db_counts: Vec<(i32, i64)> = counts
+            .iter()
+            .filter_map(|(object_id, count)| {
+                let item = player.inventory.items.get(object_id)?;
+                Some((item.item_model.id, *count))
+            })
+            .collect();
+        let created = item::Model::exchange(&self.db_pool, &db_counts, created).await?;
+        let player = self.try_get_selected_char_mut()?;
+        let mut changed = Vec::with_capacity(counts.len() + created.len());
+        for (object_id, count) in counts {
+            if let Some(updated) = player.inventory.set_count(object_id, count) {
+                let change = if count == 0 {
+                    ItemChange::Removed
+                } else {
+                    ItemChange::Modified
+                };
+                changed.push((change, updated));
+            }
+        }
+        for model in created {
+            changed.push((ItemChange::Added, player.inventory.add_item(model)));
+        }
+        if changed.is_empty() {
+            return Ok(());
+        }
+        let adena_changed = changed
+            .iter()
+            .any(|(_, i)| i.item_model.item_id == Inventory::ADENA_ID);
+        let changes: Vec<_> = changed.iter().map(|(c, i)| (*c, i)).collect();
+        self.send_packet(to_client::InventoryUpdate::new(&changes)?)
+            .await?;
+        if adena_changed {
+            let player = self.try_get_selected_char()?;
+            self.send_packet(InventoryAdenaInfo::new(player)?).await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::test::{on_actor, spawn_player};
+    use entities::dao::item::ItemPlace;
+    use test_utils::utils::get_test_db;
+
+    #[tokio::test]
+    async fn test_destroy_item() {
+        let db_pool = get_test_db().await;
+        let (_, actor, _conn) = spawn_player(&db_pool, 20).await;
+        on_actor(&actor, |pl| {
+            Box::pin(async move {
+                pl.add_item_by_item_id(Inventory::ADENA_ID, 100).await?;
+                assert!(pl.destroy_item_by_item_id(Inventory::ADENA_ID, 30).await?);
+                assert!(!pl.destroy_item_by_item_id(Inventory::ADENA_ID, 71).await?);
+                let adena = pl.try_get_selected_char()?.inventory.get_adena();
+                assert_eq!(adena, 70);
+
+                // a stack the DB doesn't know is kept in game as well
+                let lost = item::Model {
+                    id: 9999,
+                    item_id: 1864,
+                    count: 5,
+                    loc: LocType::Inventory,
+                    ..Default::default()
+                };
+                let player = pl.try_get_selected_char_mut()?;
+                let object_id = player.inventory.add_item(lost).object_id.value();
+                assert!(pl.destroy_item(object_id, 2).await.is_err());
+                let player = pl.try_get_selected_char()?;
+                assert_eq!(player.get_item(object_id).unwrap().item_model.count, 5);
+                let saved = item::Model::items_at(
+                    &pl.db_pool,
+                    ItemPlace::new(player.char_model.id, LocType::Inventory),
+                )
+                .await?;
+                assert_eq!(saved.iter().map(|i| i.count).sum::<i64>(), 70);
+                Ok(())
+            })
+        })
+        .await;
+    }
+}
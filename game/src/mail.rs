@@ -0,0 +1,524 @@
+//! Mail (post).
+//!
+//! Mails may carry up to 8 inventory items which stay in the `MAIL` location until the recipient
+//! takes them. A cash-on-delivery (COD) mail asks the recipient for adena, the payment is mailed
+//! to the sender. Expired mails with attachments are returned to their senders, see
+//! [`crate::managers::MailManager`].
+
+use crate::packets::from_client::extended::RequestSendPost;
+use crate::packets::to_client;
+use crate::packets::to_client::extended::{
+    ChangePostState, InventoryAdenaInfo, MAIL_FEE, MAIL_FEE_PER_ATTACHMENT, NoticePostArrived,
+    PostItemList, PostState, ReceivedPost, ReceivedPostList, ReplyWritePost, SentPost,
+    SentPostList, UnreadMailCount,
+};
+use crate::packets::to_client::{ItemChange, SystemMessage, SystemMessageType};
+use crate::pl_client::PlayerClient;
+use chrono::{Duration, Utc};
+use entities::dao::character_mail::CodPayment;
+use entities::dao::item::LocType;
+use entities::entities::{character, character_friend, character_mail};
+use l2_core::game_objects::item::ItemObject;
+use l2_core::game_objects::player::inventory::Inventory;
+use sea_orm::DbErr;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use tracing::warn;
+
+pub const MAIL_EXPIRE_DAYS: i64 = 15;
+pub const COD_MAIL_EXPIRE_HOURS: i64 = 12;
+const MAX_SUBJECT_LENGTH: usize = 128;
+const MAX_TEXT_LENGTH: usize = 512;
+
+#[must_use]
+pub fn mail_expire_at() -> DateTimeWithTimeZone {
+    (Utc::now() + Duration::days(MAIL_EXPIRE_DAYS)).fixed_offset()
+}
+
+impl PlayerClient {
+    pub async fn send_post_item_list(&mut self) -> anyhow::Result<()> {
+        let controller = self.controller.clone();
+        let player = self.try_get_selected_char()?;
+        let items: Vec<_> = player
+            .inventory
+            .items
+            .values()
+            .filter(|i| i.item_model.loc == LocType::Inventory && !i.is_quest_item())
+            .filter(|i| !controller.is_bound_item(i.item_model.item_id))
+            .filter(|i| !player.is_summoned_collar(i))
+            .collect();
+        let packet = PostItemList::new(&items)?;
+        self.send_packet(packet).await
+    }
+
+    pub async fn send_mail(&mut self, post: RequestSendPost) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let sender_id = player.char_model.id;
+        let request_adena = i32::try_from(post.request_adena).unwrap_or(-1);
+        let valid = !post.subject.is_empty()
+            && post.subject.chars().count() <= MAX_SUBJECT_LENGTH
+            && post.text.chars().count() <= MAX_TEXT_LENGTH
+            && request_adena >= 0
+            && post.is_cod == (request_adena > 0)
+            && (!post.is_cod || !post.attachments.is_empty());
+        if !valid {
+            warn!("Invalid mail from {}", player.char_model.name);
+            return self.send_packet(ReplyWritePost::new(false)?).await;
+        }
+        let Some(receiver) = character::Model::find_by_name(&self.db_pool, &post.receiver)
+            .await?
+            .filter(|r| r.id != sender_id)
+        else {
+            return self.send_packet(ReplyWritePost::new(false)?).await;
+        };
+        if character_friend::Model::is_blocked(&self.db_pool, receiver.id, sender_id).await? {
+            self.send_packet(SystemMessage::new(
+                SystemMessageType::ThatPersonIsInMessageRefusalMode,
+            )?)
+            .await?;
+            return self.send_packet(ReplyWritePost::new(false)?).await;
+        }
+        let player = self.try_get_selected_char()?;
+        let mut attachments = Vec::with_capacity(post.attachments.len());
+        let mut attached_adena = 0;
+        for &(object_id, count) in &post.attachments {
+            let item = player
+                .inventory
+                .items
+                .get(&object_id)
+                .filter(|i| i.item_model.loc == LocType::Inventory && !i.is_quest_item())
+                .filter(|i| !self.controller.is_bound_item(i.item_model.item_id))
+                .filter(|i| !player.is_summoned_collar(i))
+                .filter(|i| count > 0 && i.item_model.count >= count);
+            let Some(item) = item else {
+                warn!("Item {object_id} can't be attached, possible cheating");
+                return self.send_packet(ReplyWritePost::new(false)?).await;
+            };
+            if attachments.iter().any(|&(_, _, id)| id == object_id) {
+                return self.send_packet(ReplyWritePost::new(false)?).await;
+            }
+            if item.item_model.item_id == Inventory::ADENA_ID {
+                attached_adena += count;
+            }
+            attachments.push((item.item_model.id, count, object_id));
+        }
+        let fee = MAIL_FEE + MAIL_FEE_PER_ATTACHMENT * i64::try_from(attachments.len())?;
+        if u64::try_from(fee + attached_adena)? > player.inventory.get_adena() {
+            self.send_packet(SystemMessage::new(
+                SystemMessageType::YouDoNotHaveEnoughAdena,
+            )?)
+            .await?;
+            return self.send_packet(ReplyWritePost::new(false)?).await;
+        }
+        let expire_at = if post.is_cod {
+            Utc::now() + Duration::hours(COD_MAIL_EXPIRE_HOURS)
+        } else {
+            Utc::now() + Duration::days(MAIL_EXPIRE_DAYS)
+        };
+        let mail = character_mail::Model {
+            id: 0,
+            subject: post.subject,
+            message: Some(post.text).filter(|t| !t.is_empty()),
+            items: String::new(),
+            sender: Some(sender_id),
+            recipient: Some(receiver.id),
+            expire_at: expire_at.fixed_offset(),
+            request_adena,
+            is_unread: true,
+            is_returned: false,
+            is_deleted: false,
+        };
+        let Some((adena_id, adena_object_id)) = player
+            .inventory
+            .get_item_by_item_id(Inventory::ADENA_ID)
+            .map(|i| (i.item_model.id, i.object_id.value()))
+        else {
+            return self.send_packet(ReplyWritePost::new(false)?).await;
+        };
+        let db_attachments: Vec<_> = attachments.iter().map(|&(id, c, _)| (id, c)).collect();
+        character_mail::Model::send(&self.db_pool, mail, &db_attachments, (adena_id, fee)).await?;
+        // the items and the fee are already taken in DB, only the inventory has to follow
+        let player = self.try_get_selected_char_mut()?;
+        let mut updated = Vec::with_capacity(attachments.len() + 1);
+        for &(_, count, object_id) in &attachments {
+            updated.push(player.inventory.reduce_count(object_id, count)?);
+        }
+        updated.push(player.inventory.reduce_count(adena_object_id, fee)?);
+        let changes: Vec<_> = updated
+            .iter()
+            .map(|i| {
+                if i.item_model.count > 0 {
+                    (ItemChange::Modified, i)
+                } else {
+                    (ItemChange::Removed, i)
+                }
+            })
+            .collect();
+        self.send_packet(to_client::InventoryUpdate::new(&changes)?)
+            .await?;
+        let player = self.try_get_selected_char()?;
+        self.send_packet(InventoryAdenaInfo::new(player)?).await?;
+        self.send_packet(ReplyWritePost::new(true)?).await?;
+        self.controller.run_on_char(
+            receiver.id,
+            Box::new(|pl: &mut PlayerClient| Box::pin(pl.on_mail_arrived())),
+        );
+        Ok(())
+    }
+
+    pub async fn on_mail_arrived(&mut self) -> anyhow::Result<()> {
+        self.reload_mailbox().await?;
+        self.send_packet(NoticePostArrived::new(true)?).await?;
+        self.send_unread_mail_count().await
+    }
+
+    /// A mail was removed from the inbox by someone else (e.g. the sender cancelled it)
+    pub async fn on_mailbox_changed(&mut self) -> anyhow::Result<()> {
+        self.reload_mailbox().await?;
+        self.send_unread_mail_count().await
+    }
+
+    async fn send_unread_mail_count(&mut self) -> anyhow::Result<()> {
+        let count = self.try_get_selected_char()?.get_unread_mail_count();
+        self.send_packet(UnreadMailCount::new(u32::try_from(count)?)?)
+            .await
+    }
+
+    /// Reads the inbox from DB, returns the mails with their sender names
+    async fn reload_mailbox(
+        &mut self,
+    ) -> anyhow::Result<Vec<(character_mail::Model, Option<String>)>> {
+        let char_id = self.try_get_selected_char()?.char_model.id;
+        let inbox = character_mail::Model::inbox(&self.db_pool, char_id).await?;
+        self.try_get_selected_char_mut()?.mailbox = inbox.iter().map(|(m, _)| m.clone()).collect();
+        Ok(inbox)
+    }
+
+    async fn find_received_mail(
+        &mut self,
+        mail_id: i32,
+    ) -> anyhow::Result<Option<(character_mail::Model, Option<String>)>> {
+        let inbox = self.reload_mailbox().await?;
+        Ok(inbox.into_iter().find(|(m, _)| m.id == mail_id))
+    }
+
+    async fn find_sent_mail(
+        &self,
+        mail_id: i32,
+    ) -> anyhow::Result<Option<(character_mail::Model, Option<String>)>> {
+        let char_id = self.try_get_selected_char()?.char_model.id;
+        let outbox = character_mail::Model::outbox(&self.db_pool, char_id).await?;
+        Ok(outbox.into_iter().find(|(m, _)| m.id == mail_id))
+    }
+
+    pub async fn send_received_post_list(&mut self) -> anyhow::Result<()> {
+        let inbox = self.reload_mailbox().await?;
+        self.send_packet(ReceivedPostList::new(&inbox)?).await
+    }
+
+    pub async fn send_sent_post_list(&mut self) -> anyhow::Result<()> {
+        let char_id = self.try_get_selected_char()?.char_model.id;
+        let outbox = character_mail::Model::outbox(&self.db_pool, char_id).await?;
+        self.send_packet(SentPostList::new(&outbox)?).await
+    }
+
+    pub async fn read_received_post(&mut self, mail_id: i32) -> anyhow::Result<()> {
+        let Some((mut mail, sender)) = self.find_received_mail(mail_id).await? else {
+            warn!("Mail {mail_id} is not in the inbox, possible cheating");
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        };
+        if mail.is_unread {
+            mail.is_unread = false;
+            mail = character_mail::Model::update(&self.db_pool, mail).await?;
+            self.reload_mailbox().await?;
+        }
+        let attachments = attachments(&self.db_pool, mail_id).await?;
+        let packet = ReceivedPost::new(&mail, sender.as_deref().unwrap_or_default(), &attachments)?;
+        self.send_packet(packet).await?;
+        self.send_packet(ChangePostState::new(true, &[mail_id], PostState::Read)?)
+            .await?;
+        self.send_unread_mail_count().await
+    }
+
+    pub async fn read_sent_post(&mut self, mail_id: i32) -> anyhow::Result<()> {
+        let Some((mail, recipient)) = self.find_sent_mail(mail_id).await? else {
+            warn!("Mail {mail_id} is not in the outbox, possible cheating");
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        };
+        let attachments = attachments(&self.db_pool, mail_id).await?;
+        let packet = SentPost::new(
+            &mail,
+            recipient.as_deref().unwrap_or_default(),
+            &attachments,
+        )?;
+        self.send_packet(packet).await
+    }
+
+    /// Moves the attachments into the inventory, COD mails are paid first
+    pub async fn take_post_attachments(&mut self, mail_id: i32) -> anyhow::Result<()> {
+        let Some((mail, _)) = self
+            .find_received_mail(mail_id)
+            .await?
+            .filter(|(m, _)| m.has_attachments())
+        else {
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        };
+        let payment = i64::from(mail.request_adena);
+        let player = self.try_get_selected_char()?;
+        let char_id = player.char_model.id;
+        let adena = player
+            .inventory
+            .get_item_by_item_id(Inventory::ADENA_ID)
+            .filter(|i| i.item_model.count >= payment)
+            .map(|i| (i.item_model.id, i.object_id.value()));
+        let cod_payment = match adena {
+            Some((adena_id, _)) => Some(CodPayment {
+                adena_id,
+                expire_at: mail_expire_at(),
+            }),
+            None if payment > 0 => {
+                return self
+                    .send_packet(SystemMessage::new(
+                        SystemMessageType::YouDoNotHaveEnoughAdena,
+                    )?)
+                    .await;
+            }
+            None => None,
+        };
+        let taken =
+            character_mail::Model::take_attachments(&self.db_pool, mail.id, char_id, cod_payment)
+                .await;
+        let (_, items, payment_mail) = match taken {
+            Ok(taken) => taken,
+            Err(err) => {
+                // e.g. the sender cancelled the mail meanwhile
+                warn!("Can't take attachments of mail {mail_id}: {err}");
+                return self.send_packet(to_client::ActionFailed::normal()?).await;
+            }
+        };
+        if payment > 0
+            && let Some((_, adena_object_id)) = adena
+        {
+            // the payment is already taken in DB, only the inventory has to follow
+            let player = self.try_get_selected_char_mut()?;
+            let updated = player.inventory.reduce_count(adena_object_id, payment)?;
+            self.send_packet(to_client::InventoryUpdate::single(&updated)?)
+                .await?;
+            let player = self.try_get_selected_char()?;
+            self.send_packet(InventoryAdenaInfo::new(player)?).await?;
+        }
+        self.receive_items(items).await?;
+        if let Some(sender) = payment_mail.and_then(|m| m.recipient) {
+            self.controller.run_on_char(
+                sender,
+                Box::new(|pl: &mut PlayerClient| Box::pin(pl.on_mail_arrived())),
+            );
+        }
+        self.reload_mailbox().await?;
+        self.send_packet(ChangePostState::new(true, &[mail_id], PostState::Read)?)
+            .await
+    }
+
+    /// Sends the mail with its attachments back without paying for it
+    pub async fn reject_post(&mut self, mail_id: i32) -> anyhow::Result<()> {
+        let Some((mail, _)) = self
+            .find_received_mail(mail_id)
+            .await?
+            .filter(|(m, _)| m.has_attachments() && !m.is_returned)
+        else {
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        };
+        let returned =
+            match character_mail::Model::return_to_sender(&self.db_pool, mail.id, mail_expire_at())
+                .await
+            {
+                Ok(returned) => returned,
+                // the attachments were taken meanwhile
+                Err(DbErr::RecordNotFound(_)) => {
+                    return self.send_packet(to_client::ActionFailed::normal()?).await;
+                }
+                Err(e) => return Err(e.into()),
+            };
+        self.reload_mailbox().await?;
+        self.send_packet(ChangePostState::new(true, &[mail_id], PostState::Rejected)?)
+            .await?;
+        if let Some(sender) = returned.recipient {
+            self.controller.run_on_char(
+                sender,
+                Box::new(|pl: &mut PlayerClient| Box::pin(pl.on_mail_arrived())),
+            );
+        }
+        Ok(())
+    }
+
+    /// The sender takes the attachments back before the recipient does
+    pub async fn cancel_sent_post(&mut self, mail_id: i32) -> anyhow::Result<()> {
+        let Some((mail, _)) = self
+            .find_sent_mail(mail_id)
+            .await?
+            .filter(|(m, _)| m.has_attachments())
+        else {
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        };
+        let char_id = self.try_get_selected_char()?.char_model.id;
+        let recipient = mail.recipient;
+        let (mut mail, items, _) =
+            character_mail::Model::take_attachments(&self.db_pool, mail.id, char_id, None).await?;
+        mail.is_deleted = true;
+        character_mail::Model::update(&self.db_pool, mail).await?;
+        self.receive_items(items).await?;
+        self.send_packet(ChangePostState::new(false, &[mail_id], PostState::Deleted)?)
+            .await?;
+        if let Some(recipient) = recipient {
+            self.controller.run_on_char(
+                recipient,
+                Box::new(|pl: &mut PlayerClient| Box::pin(pl.on_mailbox_changed())),
+            );
+        }
+        Ok(())
+    }
+
+    /// Mails with attachments have to be emptied or rejected first
+    pub async fn delete_received_posts(&mut self, mail_ids: &[i32]) -> anyhow::Result<()> {
+        let inbox = self.reload_mailbox().await?;
+        let mut deleted = Vec::with_capacity(mail_ids.len());
+        for (mut mail, _) in inbox {
+            if mail_ids.contains(&mail.id) && !mail.has_attachments() {
+                mail.is_deleted = true;
+                deleted.push(character_mail::Model::update(&self.db_pool, mail).await?.id);
+            }
+        }
+        self.reload_mailbox().await?;
+        self.send_packet(ChangePostState::new(true, &deleted, PostState::Deleted)?)
+            .await?;
+        self.send_unread_mail_count().await
+    }
+}
+
+async fn attachments(db_pool: &entities::DBPool, mail_id: i32) -> anyhow::Result<Vec<ItemObject>> {
+    let items = character_mail::Model::attachments(db_pool, mail_id).await?;
+    Ok(ItemObject::from_items(items).into_values().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::controller::GameController;
+    use crate::managers::MailManager;
+    use crate::packets::from_client::extended::{RequestPostAttachment, RequestSendPost};
+    use crate::pl_client::GetCharInfo;
+    use crate::test_utils::test::{enter_game, get_gs_config};
+    use chrono::{Duration, Utc};
+    use entities::entities::{character_mail, item};
+    use l2_core::game_objects::player::inventory::Inventory;
+    use sea_orm::EntityTrait;
+    use std::sync::Arc;
+    use test_utils::utils::get_test_db;
+
+    #[tokio::test]
+    async fn test_send_cod_mail_and_take_attachments() {
+        let db_pool = get_test_db().await;
+        let controller = Arc::new(GameController::from_config(Arc::new(get_gs_config())).await);
+        let (alice_id, alice, _alice_conn) = enter_game(
+            &controller,
+            &db_pool,
+            "Alice",
+            &[(Inventory::ADENA_ID, 5_000), (4445, 10)],
+        )
+        .await;
+        let (bob_id, bob, _bob_conn) = enter_game(
+            &controller,
+            &db_pool,
+            "Bob",
+            &[(Inventory::ADENA_ID, 1_000)],
+        )
+        .await;
+        let player = alice.ask(GetCharInfo).await.unwrap();
+        let dyes = player.inventory.get_item_by_item_id(4445).unwrap();
+        let post = |request_adena| RequestSendPost {
+            receiver: "Bob".to_string(),
+            is_cod: true,
+            subject: "Dyes".to_string(),
+            text: "Pay me".to_string(),
+            attachments: vec![(dyes.object_id.value(), 4)],
+            request_adena,
+        };
+        // COD without a price is rejected and nothing moves
+        alice.ask(post(0)).await.unwrap();
+        let player = alice.ask(GetCharInfo).await.unwrap();
+        assert_eq!(player.inventory.get_adena(), 5_000);
+
+        alice.ask(post(500)).await.unwrap();
+        let player = alice.ask(GetCharInfo).await.unwrap();
+        assert_eq!(player.inventory.get_adena(), 3_900);
+        let dyes = player.inventory.get_item_by_item_id(4445).unwrap();
+        assert_eq!(dyes.item_model.count, 6);
+        let inbox = character_mail::Model::inbox(&db_pool, bob_id)
+            .await
+            .unwrap();
+        assert_eq!(inbox.len(), 1);
+        let mail_id = inbox[0].0.id;
+        assert!(inbox[0].0.is_cod());
+
+        bob.ask(RequestPostAttachment { mail_id }).await.unwrap();
+        let player = bob.ask(GetCharInfo).await.unwrap();
+        assert_eq!(player.inventory.get_adena(), 500);
+        let dyes = player.inventory.get_item_by_item_id(4445).unwrap();
+        assert_eq!(dyes.item_model.count, 4);
+        assert_eq!(player.get_unread_mail_count(), 1);
+        let mail = character_mail::Model::find_by_id(&db_pool, mail_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!mail.has_attachments());
+        // the payment is saved together with the attachments, and only paid once
+        bob.ask(RequestPostAttachment { mail_id }).await.unwrap();
+        let player = bob.ask(GetCharInfo).await.unwrap();
+        assert_eq!(player.inventory.get_adena(), 500);
+        let adena = player
+            .inventory
+            .get_item_by_item_id(Inventory::ADENA_ID)
+            .unwrap();
+        let saved = item::Entity::find_by_id(adena.item_model.id)
+            .one(&db_pool)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(saved.count, 500);
+
+        // the payment arrives as a new mail
+        let inbox = character_mail::Model::inbox(&db_pool, alice_id)
+            .await
+            .unwrap();
+        assert_eq!(inbox.len(), 1);
+        let payment = character_mail::Model::attachments(&db_pool, inbox[0].0.id)
+            .await
+            .unwrap();
+        assert_eq!(payment[0].item_id, Inventory::ADENA_ID);
+        assert_eq!(payment[0].count, 500);
+
+        // an expired mail with attachments is returned, the read one is deleted
+        let mut expired = inbox[0].0.clone();
+        expired.expire_at = (Utc::now() - Duration::minutes(1)).fixed_offset();
+        character_mail::Model::update(&db_pool, expired)
+            .await
+            .unwrap();
+        let mut read = mail;
+        read.expire_at = (Utc::now() - Duration::minutes(1)).fixed_offset();
+        character_mail::Model::update(&db_pool, read).await.unwrap();
+        let handled = MailManager::sweep(&controller, &db_pool).await.unwrap();
+        assert_eq!(handled, 2);
+        let returned = character_mail::Model::inbox(&db_pool, bob_id)
+            .await
+            .unwrap();
+        assert_eq!(returned.len(), 1);
+        assert!(returned[0].0.is_returned);
+        assert!(returned[0].0.has_attachments());
+        // returned mails holding items are left alone
+        let mut expired = returned[0].0.clone();
+        expired.expire_at = (Utc::now() - Duration::minutes(1)).fixed_offset();
+        character_mail::Model::update(&db_pool, expired)
+            .await
+            .unwrap();
+        let handled = MailManager::sweep(&controller, &db_pool).await.unwrap();
+        assert_eq!(handled, 0);
+    }
+}
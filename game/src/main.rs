@@ -1,5 +1,9 @@
 use crate::controller::GameController;
 use crate::ls_client::LoginServerClient;
+use crate::managers::{
+    CursedWeaponsManager, MailManager, ManorManager, OlympiadManager, RecommendationManager,
+    SiegeManager, VitalityManager,
+};
 use crate::pl_client::PlayerClient;
 use dotenvy::dotenv;
 use kameo::actor::Spawn;
@@ -14,16 +18,41 @@ use std::path::Path;
 use std::sync::Arc;
 use tracing::error;
 
+mod bypass;
 mod controller;
 mod cp_factory;
+mod crafting;
+mod cursed_weapons;
+mod enchanting;
+mod fishing;
+mod friends;
+mod henna;
+mod heroes;
+mod items;
 mod ls_client;
 mod lsp_factory;
+mod mail;
+mod manor;
 pub mod managers;
+mod mounts;
 mod movement;
+mod olympiad;
 mod packets;
 mod pl_client;
+mod premium;
+mod quests;
+mod recommendations;
+mod shops;
+mod shots;
+mod siege;
 mod skills;
+mod subclass;
+mod summons;
+mod teleport;
 mod test_utils;
+mod vitality;
+mod warehouse;
+mod zones;
 
 ///
 /// # Panics
@@ -50,6 +79,13 @@ pub fn main() {
     runtime.block_on(async move {
         let pool = new_db_pool(cfg.database()).await;
         let controller = Arc::new(GameController::new(cfg.clone(), &pool).await);
+        MailManager::start(controller.clone(), pool.clone());
+        SiegeManager::start(controller.clone(), pool.clone());
+        OlympiadManager::start(controller.clone(), pool.clone());
+        ManorManager::start(controller.clone(), pool.clone());
+        VitalityManager::start(controller.clone());
+        RecommendationManager::start(controller.clone());
+        CursedWeaponsManager::start(controller.clone(), pool.clone());
         let clients_listener = ConnectionListener {
             name: "PlayerListener".to_string(),
             cfg: cfg.listeners.clients.connection.clone(),
@@ -0,0 +1,51 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use std::sync::atomic::{AtomicI64, Ordering};
+
+/// Time source of the scheduled game events, like castle sieges.
+///
+/// The clock can be moved forward, so tests and GMs don't have to wait days for an event.
+#[derive(Debug, Default)]
+pub struct GameClock {
+    offset_ms: AtomicI64,
+}
+
+impl GameClock {
+    #[must_use]
+    pub fn now(&self) -> DateTime<Utc> {
+        Utc::now() + self.offset()
+    }
+
+    #[must_use]
+    pub fn offset(&self) -> TimeDelta {
+        TimeDelta::milliseconds(self.offset_ms.load(Ordering::Relaxed))
+    }
+
+    pub fn advance(&self, by: TimeDelta) {
+        self.offset_ms
+            .fetch_add(by.num_milliseconds(), Ordering::Relaxed);
+    }
+
+    /// Moves the clock to the time, so tests don't depend on when they run.
+    #[cfg(test)]
+    pub fn set(&self, at: DateTime<Utc>) {
+        self.offset_ms
+            .store((at - Utc::now()).num_milliseconds(), Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_advance() {
+        let clock = GameClock::default();
+        let before = Utc::now();
+        clock.advance(TimeDelta::days(14));
+        assert!(clock.now() >= before + TimeDelta::days(14));
+        assert_eq!(clock.offset(), TimeDelta::days(14));
+        let at = before - TimeDelta::days(1);
+        clock.set(at);
+        assert!((clock.now() - at).abs() < TimeDelta::seconds(1));
+    }
+}
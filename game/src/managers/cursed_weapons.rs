@@ -0,0 +1,222 @@
+use crate::controller::GameController;
+use crate::cursed_weapons::announce_cursed_weapon_gone;
+use crate::packets::to_client::extended::CursedWeaponLocation;
+use crate::pl_client::PlayerClient;
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use dashmap::mapref::entry::Entry;
+use entities::DBPool;
+use entities::entities::cursed_weapon;
+use l2_core::game_objects::zone::Location;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info};
+
+const TICK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Keeps track of the cursed weapons in the world, only one of each exists at a time.
+///
+/// The records of the owned weapons are kept in the `cursed_weapon` table, so the time a
+/// weapon has left runs on across restarts. Every tick the weapons which ran out are taken
+/// from their owners (also from the ones who are not in game), the owners in game report where
+/// they are and everyone is told where the weapons are.
+#[derive(Debug, Default)]
+pub struct CursedWeaponsManager {
+    /// By the item id of the weapon
+    weapons: DashMap<i32, cursed_weapon::Model>,
+    /// Where the owners were seen last, by the item id of the weapon
+    locations: DashMap<i32, Location>,
+}
+
+impl CursedWeaponsManager {
+    #[must_use]
+    pub fn new(weapons: Vec<cursed_weapon::Model>) -> Self {
+        Self {
+            weapons: weapons.into_iter().map(|w| (w.item_id, w)).collect(),
+            locations: DashMap::new(),
+        }
+    }
+
+    /**
+    # Panics
+    - If the database connection fails.
+
+    It is okay to panic here as we start the manager during the boot process of the application.
+    */
+    pub async fn load(db_pool: &DBPool) -> Self {
+        let weapons = cursed_weapon::Model::load_all(db_pool)
+            .await
+            .expect("Failed to load the cursed weapons, can not continue...");
+        info!("Loaded {} cursed weapons in the world.", weapons.len());
+        Self::new(weapons)
+    }
+
+    pub fn start(controller: Arc<GameController>, db_pool: DBPool) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(TICK_INTERVAL);
+            loop {
+                interval.tick().await;
+                if let Err(e) = Self::update(&controller, &db_pool).await {
+                    error!("Cursed weapons update failed: {e}");
+                }
+            }
+        });
+    }
+
+    /// Ends the weapons which ran out and updates the locations of the others.
+    /// Returns the item ids of the weapons which ran out.
+    pub async fn update(controller: &GameController, db_pool: &DBPool) -> anyhow::Result<Vec<i32>> {
+        let manager = &controller.cursed_weapons_manager;
+        let expired = manager.expired(controller.clock.now());
+        for weapon in &expired {
+            if controller.get_char_in_game(weapon.char_id).is_some() {
+                controller.run_on_char(
+                    weapon.char_id,
+                    Box::new(|pl: &mut PlayerClient| Box::pin(pl.end_cursed_weapon())),
+                );
+            } else {
+                cursed_weapon::Model::release_offline(db_pool, weapon).await?;
+                manager.remove(weapon.item_id);
+                announce_cursed_weapon_gone(controller, weapon.item_id)?;
+            }
+        }
+        for weapon in manager.all() {
+            controller.run_on_char(
+                weapon.char_id,
+                Box::new(|pl: &mut PlayerClient| {
+                    Box::pin(async { pl.report_cursed_weapon_location() })
+                }),
+            );
+        }
+        if !manager.weapons.is_empty() {
+            controller.broadcast_packet(CursedWeaponLocation::new(&manager.locations())?);
+        }
+        Ok(expired.into_iter().map(|w| w.item_id).collect())
+    }
+
+    #[must_use]
+    pub fn get(&self, item_id: i32) -> Option<cursed_weapon::Model> {
+        self.weapons.get(&item_id).map(|w| w.clone())
+    }
+
+    /// The weapon the char owns
+    #[must_use]
+    pub fn owned_by(&self, char_id: i32) -> Option<cursed_weapon::Model> {
+        self.weapons
+            .iter()
+            .find(|w| w.char_id == char_id)
+            .map(|w| w.clone())
+    }
+
+    #[must_use]
+    pub fn is_in_world(&self, item_id: i32) -> bool {
+        self.weapons.contains_key(&item_id)
+    }
+
+    /// Weapons in the world ordered by item id
+    #[must_use]
+    pub fn all(&self) -> Vec<cursed_weapon::Model> {
+        let mut weapons: Vec<_> = self.weapons.iter().map(|w| w.clone()).collect();
+        weapons.sort_by_key(|w| w.item_id);
+        weapons
+    }
+
+    /// Gives the weapon to its new owner, false if somebody owns it already.
+    pub fn claim(&self, weapon: cursed_weapon::Model, location: Location) -> bool {
+        match self.weapons.entry(weapon.item_id) {
+            Entry::Occupied(_) => false,
+            Entry::Vacant(entry) => {
+                self.locations.insert(weapon.item_id, location);
+                entry.insert(weapon);
+                true
+            }
+        }
+    }
+
+    /// Saves the changes of a weapon in the world, e.g. the kills of the owner.
+    pub fn update_weapon(&self, weapon: cursed_weapon::Model) {
+        if let Some(mut w) = self.weapons.get_mut(&weapon.item_id) {
+            *w = weapon;
+        }
+    }
+
+    /// Takes the weapon out of the world.
+    pub fn remove(&self, item_id: i32) -> Option<cursed_weapon::Model> {
+        self.locations.remove(&item_id);
+        self.weapons.remove(&item_id).map(|(_, w)| w)
+    }
+
+    pub fn set_location(&self, item_id: i32, location: Location) {
+        if self.weapons.contains_key(&item_id) {
+            self.locations.insert(item_id, location);
+        }
+    }
+
+    /// `(item id, location)` of the weapons in the world ordered by item id
+    #[must_use]
+    pub fn locations(&self) -> Vec<(i32, Location)> {
+        let mut locations: Vec<_> = self
+            .locations
+            .iter()
+            .map(|l| (*l.key(), *l.value()))
+            .collect();
+        locations.sort_by_key(|(item_id, _)| *item_id);
+        locations
+    }
+
+    /// Weapons whose time has run out
+    #[must_use]
+    pub fn expired(&self, now: DateTime<Utc>) -> Vec<cursed_weapon::Model> {
+        let mut expired: Vec<_> = self
+            .weapons
+            .iter()
+            .filter(|w| w.expires_at <= now)
+            .map(|w| w.clone())
+            .collect();
+        expired.sort_by_key(|w| w.item_id);
+        expired
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeDelta, TimeZone};
+
+    fn weapon(item_id: i32, char_id: i32, expires_at: DateTime<Utc>) -> cursed_weapon::Model {
+        cursed_weapon::Model {
+            item_id,
+            char_id,
+            expires_at: expires_at.fixed_offset(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_claim_and_expire() {
+        let now = Utc.with_ymd_and_hms(2025, 8, 1, 12, 0, 0).unwrap();
+        let manager = CursedWeaponsManager::new(vec![weapon(8689, 2, now + TimeDelta::hours(1))]);
+        let here = Location {
+            x: 1,
+            y: 2,
+            z: 3,
+            heading: 0,
+        };
+        assert!(manager.claim(weapon(8190, 1, now + TimeDelta::hours(5)), here));
+        // there is only one of each weapon
+        assert!(!manager.claim(weapon(8190, 3, now), here));
+        assert_eq!(manager.owned_by(1).unwrap().item_id, 8190);
+        assert!(manager.owned_by(3).is_none());
+        let locations = manager.locations();
+        assert_eq!(locations.len(), 1);
+        assert_eq!((locations[0].0, locations[0].1.x), (8190, 1));
+
+        let expired = manager.expired(now + TimeDelta::hours(2));
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].item_id, 8689);
+        manager.remove(8190);
+        assert!(!manager.is_in_world(8190));
+        assert!(manager.locations().is_empty());
+        assert_eq!(manager.all().len(), 1);
+    }
+}
@@ -0,0 +1,67 @@
+use crate::controller::GameController;
+use crate::mail::mail_expire_at;
+use crate::pl_client::PlayerClient;
+use chrono::Utc;
+use entities::DBPool;
+use entities::entities::character_mail;
+use sea_orm::DbErr;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info};
+
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Periodically handles expired mails.
+///
+/// Mails with attachments go back to the sender, mails without them are deleted.
+/// Returned mails are never deleted while they still hold items.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MailManager;
+
+impl MailManager {
+    pub fn start(controller: Arc<GameController>, db_pool: DBPool) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(SWEEP_INTERVAL);
+            loop {
+                interval.tick().await;
+                if let Err(e) = Self::sweep(&controller, &db_pool).await {
+                    error!("Mail sweep failed: {e}");
+                }
+            }
+        });
+    }
+
+    /// Returns how many mails were returned or deleted
+    pub async fn sweep(controller: &GameController, db_pool: &DBPool) -> anyhow::Result<usize> {
+        let expired = character_mail::Model::expired(db_pool, Utc::now().fixed_offset()).await?;
+        let mut handled = 0;
+        for mut mail in expired {
+            let notify = if mail.has_attachments() {
+                // the recipient may have taken the attachments meanwhile
+                match character_mail::Model::return_to_sender(db_pool, mail.id, mail_expire_at())
+                    .await
+                {
+                    Ok(mail) => mail.recipient,
+                    Err(DbErr::RecordNotFound(_)) => continue,
+                    Err(e) => return Err(e.into()),
+                }
+            } else {
+                mail.is_deleted = true;
+                character_mail::Model::update(db_pool, mail)
+                    .await?
+                    .recipient
+            };
+            if let Some(char_id) = notify {
+                controller.run_on_char(
+                    char_id,
+                    Box::new(|pl: &mut PlayerClient| Box::pin(pl.on_mailbox_changed())),
+                );
+            }
+            handled += 1;
+        }
+        if handled > 0 {
+            info!("Expired mails handled: {handled}");
+        }
+        Ok(handled)
+    }
+}
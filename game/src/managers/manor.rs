@@ -0,0 +1,692 @@
+use crate::controller::GameController;
+use crate::manor::ManorTarget;
+use chrono::{DateTime, NaiveTime, TimeDelta, Utc};
+use dashmap::DashMap;
+use entities::DBPool;
+use entities::entities::{castle_manor_procure, castle_manor_production};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{error, info};
+
+const TICK_INTERVAL: Duration = Duration::from_secs(10);
+/// Hour of the day (UTC) the next manor period takes over
+const REFRESH_HOUR: u32 = 20;
+/// After the refresh nobody may trade with the manor for a while
+const MAINTENANCE_LENGTH: TimeDelta = TimeDelta::minutes(6);
+/// Hour of the day (UTC) the settings of the next period are approved and paid
+const APPROVE_HOUR: u32 = 6;
+
+/// What the manor allows at a time of the day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManorMode {
+    /// The next period is approved, players trade with the current one
+    Approved,
+    /// The next period just took over, no trading at all
+    Maintenance,
+    /// The lords set up the next period, players trade with the current one
+    Modifiable,
+}
+
+impl ManorMode {
+    #[must_use]
+    pub fn at(now: DateTime<Utc>) -> Self {
+        let time = now.time();
+        let refresh = NaiveTime::from_hms_opt(REFRESH_HOUR, 0, 0).unwrap_or_default();
+        let approve = NaiveTime::from_hms_opt(APPROVE_HOUR, 0, 0).unwrap_or_default();
+        if time >= refresh && time < refresh + MAINTENANCE_LENGTH {
+            Self::Maintenance
+        } else if time >= approve && time < refresh {
+            Self::Approved
+        } else {
+            Self::Modifiable
+        }
+    }
+
+    /// The mode following this one during the day.
+    #[must_use]
+    fn next(self) -> Self {
+        match self {
+            Self::Approved => Self::Maintenance,
+            Self::Maintenance => Self::Modifiable,
+            Self::Modifiable => Self::Approved,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManorEvent {
+    /// The next period became the current one
+    Refreshed,
+    /// The next period was paid from the castle treasuries
+    Approved,
+}
+
+/// Seeds a castle sells in a period.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SeedProduction {
+    pub seed_id: i32,
+    /// Seeds left to sell
+    pub amount: i64,
+    pub start_amount: i64,
+    pub price: i64,
+}
+
+/// Crops a castle buys in a period.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CropProcure {
+    pub crop_id: i32,
+    /// Crops left to buy
+    pub amount: i64,
+    pub start_amount: i64,
+    pub price: i64,
+}
+
+/// Manor settings of a castle for the current and the next period.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CastleManor {
+    pub production: Vec<SeedProduction>,
+    pub procure: Vec<CropProcure>,
+    pub next_production: Vec<SeedProduction>,
+    pub next_procure: Vec<CropProcure>,
+}
+
+impl CastleManor {
+    #[must_use]
+    pub fn seed(&self, seed_id: i32) -> Option<&SeedProduction> {
+        self.production.iter().find(|s| s.seed_id == seed_id)
+    }
+
+    #[must_use]
+    pub fn crop(&self, crop_id: i32) -> Option<&CropProcure> {
+        self.procure.iter().find(|c| c.crop_id == crop_id)
+    }
+
+    /// Adena the castle pays in advance for the crops of the next period
+    #[must_use]
+    pub fn next_procure_cost(&self) -> i64 {
+        self.next_procure
+            .iter()
+            .map(|c| c.start_amount.saturating_mul(c.price))
+            .fold(0, i64::saturating_add)
+    }
+
+    /// Adena of the crops the castle paid for but nobody sold in the current period
+    #[must_use]
+    pub fn unspent_procure(&self) -> i64 {
+        self.procure
+            .iter()
+            .map(|c| c.amount.saturating_mul(c.price))
+            .fold(0, i64::saturating_add)
+    }
+
+    /// The next period takes over, and is set up the same way for the period after it.
+    fn refresh(&mut self) {
+        self.production = std::mem::take(&mut self.next_production);
+        self.procure = std::mem::take(&mut self.next_procure);
+        self.next_production = self
+            .production
+            .iter()
+            .map(|s| SeedProduction {
+                amount: s.start_amount,
+                ..*s
+            })
+            .collect();
+        self.next_procure = self
+            .procure
+            .iter()
+            .map(|c| CropProcure {
+                amount: c.start_amount,
+                ..*c
+            })
+            .collect();
+    }
+
+    fn production_models(&self, castle_id: i32) -> Vec<castle_manor_production::Model> {
+        let current = self.production.iter().map(|s| (s, false));
+        let next = self.next_production.iter().map(|s| (s, true));
+        current
+            .chain(next)
+            .map(|(s, next_period)| production_model(castle_id, s, next_period))
+            .collect()
+    }
+
+    fn procure_models(&self, castle_id: i32) -> Vec<castle_manor_procure::Model> {
+        let current = self.procure.iter().map(|c| (c, false));
+        let next = self.next_procure.iter().map(|c| (c, true));
+        current
+            .chain(next)
+            .map(|(c, next_period)| procure_model(castle_id, c, next_period))
+            .collect()
+    }
+}
+
+fn production_model(
+    castle_id: i32,
+    seed: &SeedProduction,
+    next_period: bool,
+) -> castle_manor_production::Model {
+    castle_manor_production::Model {
+        castle_id,
+        seed_id: seed.seed_id,
+        next_period,
+        amount: seed.amount,
+        start_amount: seed.start_amount,
+        price: seed.price,
+    }
+}
+
+fn procure_model(
+    castle_id: i32,
+    crop: &CropProcure,
+    next_period: bool,
+) -> castle_manor_procure::Model {
+    castle_manor_procure::Model {
+        castle_id,
+        crop_id: crop.crop_id,
+        next_period,
+        amount: crop.amount,
+        start_amount: crop.start_amount,
+        price: crop.price,
+    }
+}
+
+/// A monster a seed was sown on, by its object id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SownSeed {
+    pub sower_id: i32,
+    pub crop_id: i32,
+    /// Crops the monster gives once it is killed
+    pub count: i64,
+    /// The monster was killed and waits for the harvest
+    pub ripe: bool,
+}
+
+/// Seed production and crop procurement of the castles, and the seeds sown on monsters.
+///
+/// The manor runs in daily periods. At the refresh the settings the castle lords made for the
+/// next period take over, after a short maintenance the lords set up the following period until
+/// it is approved in the morning, when the castle treasury pays the crops in advance.
+#[derive(Debug)]
+pub struct ManorManager {
+    castles: DashMap<i32, CastleManor>,
+    mode: Mutex<ManorMode>,
+    /// Held while the current period is changed and saved, so the writes don't overtake each other
+    stock: tokio::sync::Mutex<()>,
+    /// Monsters seeds may be sown on, by their object id
+    monsters: DashMap<i32, ManorTarget>,
+    /// By the object id of the monster
+    sown: DashMap<i32, SownSeed>,
+}
+
+impl ManorManager {
+    #[must_use]
+    pub fn new(
+        production: Vec<castle_manor_production::Model>,
+        procure: Vec<castle_manor_procure::Model>,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let castles: DashMap<i32, CastleManor> = DashMap::new();
+        for row in production {
+            let seed = SeedProduction {
+                seed_id: row.seed_id,
+                amount: row.amount,
+                start_amount: row.start_amount,
+                price: row.price,
+            };
+            let mut castle = castles.entry(row.castle_id).or_default();
+            if row.next_period {
+                castle.next_production.push(seed);
+            } else {
+                castle.production.push(seed);
+            }
+        }
+        for row in procure {
+            let crop = CropProcure {
+                crop_id: row.crop_id,
+                amount: row.amount,
+                start_amount: row.start_amount,
+                price: row.price,
+            };
+            let mut castle = castles.entry(row.castle_id).or_default();
+            if row.next_period {
+                castle.next_procure.push(crop);
+            } else {
+                castle.procure.push(crop);
+            }
+        }
+        Self {
+            castles,
+            mode: Mutex::new(ManorMode::at(now)),
+            stock: tokio::sync::Mutex::new(()),
+            monsters: DashMap::new(),
+            sown: DashMap::new(),
+        }
+    }
+
+    /**
+    # Panics
+    - If the database connection fails.
+
+    It is okay to panic here as we start the manager during the boot process of the application.
+    */
+    pub async fn load(db_pool: &DBPool, now: DateTime<Utc>) -> Self {
+        let production = castle_manor_production::Model::load_all(db_pool)
+            .await
+            .expect("Failed to load the seed production, can not continue...");
+        let procure = castle_manor_procure::Model::load_all(db_pool)
+            .await
+            .expect("Failed to load the crop procurement, can not continue...");
+        Self::new(production, procure, now)
+    }
+
+    pub fn start(controller: Arc<GameController>, db_pool: DBPool) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(TICK_INTERVAL);
+            loop {
+                interval.tick().await;
+                if let Err(e) = Self::update(&controller, &db_pool).await {
+                    error!("Manor update failed: {e}");
+                }
+            }
+        });
+    }
+
+    /// Refreshes and approves the manor periods due at the current game time.
+    pub async fn update(
+        controller: &GameController,
+        db_pool: &DBPool,
+    ) -> anyhow::Result<Vec<ManorEvent>> {
+        let events = controller.manor_manager.advance(controller.clock.now());
+        for event in &events {
+            Self::on_event(controller, db_pool, *event).await?;
+        }
+        Ok(events)
+    }
+
+    /// Moves the manor to the mode of the time, the caller handles the returned events.
+    /// Modes skipped over while the server was busy still raise their events.
+    pub fn advance(&self, now: DateTime<Utc>) -> Vec<ManorEvent> {
+        let target = ManorMode::at(now);
+        let mut mode = self.mode.lock().unwrap_or_else(|e| e.into_inner());
+        let mut events = Vec::new();
+        while *mode != target {
+            *mode = mode.next();
+            match *mode {
+                ManorMode::Maintenance => events.push(ManorEvent::Refreshed),
+                ManorMode::Approved => events.push(ManorEvent::Approved),
+                ManorMode::Modifiable => {}
+            }
+        }
+        events
+    }
+
+    async fn on_event(
+        controller: &GameController,
+        db_pool: &DBPool,
+        event: ManorEvent,
+    ) -> anyhow::Result<()> {
+        let manager = &controller.manor_manager;
+        for template in controller.castles.all() {
+            let castle_id = template.id;
+            let owned = controller
+                .siege_manager
+                .get(castle_id)
+                .is_some_and(|c| c.owner.is_some());
+            // players trade with the castle while we pay, so its stock is changed in place
+            let _stock = manager.stock.lock().await;
+            let manor = manager.get(castle_id);
+            let mut pay_failed = false;
+            match event {
+                ManorEvent::Refreshed if owned => {
+                    controller
+                        .siege_manager
+                        .add_to_treasury(db_pool, castle_id, manor.unspent_procure())
+                        .await?;
+                }
+                ManorEvent::Approved if owned => {
+                    pay_failed = !controller
+                        .siege_manager
+                        .add_to_treasury(db_pool, castle_id, -manor.next_procure_cost())
+                        .await?;
+                }
+                _ => {}
+            }
+            let (production, procure) = {
+                let mut castle = manager.castles.entry(castle_id).or_default();
+                let before = castle.clone();
+                match event {
+                    ManorEvent::Refreshed if owned => castle.refresh(),
+                    ManorEvent::Approved if owned => {
+                        if pay_failed {
+                            info!("Castle {castle_id} can't pay the manor of the next period");
+                            castle.next_production.clear();
+                            castle.next_procure.clear();
+                        }
+                    }
+                    // the npcs run no manor
+                    _ => *castle = CastleManor::default(),
+                }
+                if *castle == before {
+                    continue;
+                }
+                (
+                    castle.production_models(castle_id),
+                    castle.procure_models(castle_id),
+                )
+            };
+            castle_manor_production::Model::replace_castle(db_pool, castle_id, production).await?;
+            castle_manor_procure::Model::replace_castle(db_pool, castle_id, procure).await?;
+        }
+        info!("Manor event: {event:?}");
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn mode(&self) -> ManorMode {
+        *self.mode.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Manor settings of the castle, empty if its lord made none
+    #[must_use]
+    pub fn get(&self, castle_id: i32) -> CastleManor {
+        self.castles
+            .get(&castle_id)
+            .map(|c| c.clone())
+            .unwrap_or_default()
+    }
+
+    /// Sets the seeds the castle sells in the next period, no amount stops the sale.
+    pub async fn set_next_seed(
+        &self,
+        db_pool: &DBPool,
+        castle_id: i32,
+        seed_id: i32,
+        amount: i64,
+        price: i64,
+    ) -> anyhow::Result<()> {
+        let seed = SeedProduction {
+            seed_id,
+            amount,
+            start_amount: amount,
+            price,
+        };
+        {
+            let mut castle = self.castles.entry(castle_id).or_default();
+            castle.next_production.retain(|s| s.seed_id != seed_id);
+            if amount > 0 {
+                castle.next_production.push(seed);
+                castle.next_production.sort_by_key(|s| s.seed_id);
+            }
+        }
+        if amount > 0 {
+            castle_manor_production::Model::save(db_pool, production_model(castle_id, &seed, true))
+                .await?;
+        } else {
+            castle_manor_production::Model::delete(db_pool, castle_id, seed_id, true).await?;
+        }
+        Ok(())
+    }
+
+    /// Sets the crops the castle buys in the next period, no amount stops the procurement.
+    pub async fn set_next_crop(
+        &self,
+        db_pool: &DBPool,
+        castle_id: i32,
+        crop_id: i32,
+        amount: i64,
+        price: i64,
+    ) -> anyhow::Result<()> {
+        let crop = CropProcure {
+            crop_id,
+            amount,
+            start_amount: amount,
+            price,
+        };
+        {
+            let mut castle = self.castles.entry(castle_id).or_default();
+            castle.next_procure.retain(|c| c.crop_id != crop_id);
+            if amount > 0 {
+                castle.next_procure.push(crop);
+                castle.next_procure.sort_by_key(|c| c.crop_id);
+            }
+        }
+        if amount > 0 {
+            castle_manor_procure::Model::save(db_pool, procure_model(castle_id, &crop, true))
+                .await?;
+        } else {
+            castle_manor_procure::Model::delete(db_pool, castle_id, crop_id, true).await?;
+        }
+        Ok(())
+    }
+
+    /// Takes the seeds out of the current production of the castle.
+    /// Returns the price of one seed, `None` if the castle has not that many left.
+    pub async fn take_seeds(
+        &self,
+        db_pool: &DBPool,
+        castle_id: i32,
+        seed_id: i32,
+        count: i64,
+    ) -> anyhow::Result<Option<i64>> {
+        let _stock = self.stock.lock().await;
+        let model = {
+            let Some(mut castle) = self.castles.get_mut(&castle_id) else {
+                return Ok(None);
+            };
+            let Some(seed) = castle
+                .production
+                .iter_mut()
+                .find(|s| s.seed_id == seed_id && s.amount >= count)
+            else {
+                return Ok(None);
+            };
+            seed.amount -= count;
+            production_model(castle_id, seed, false)
+        };
+        let price = model.price;
+        castle_manor_production::Model::save(db_pool, model).await?;
+        Ok(Some(price))
+    }
+
+    /// Puts seeds taken by [`Self::take_seeds`] back, when the player could not get them.
+    pub async fn return_seeds(
+        &self,
+        db_pool: &DBPool,
+        castle_id: i32,
+        seed_id: i32,
+        count: i64,
+    ) -> anyhow::Result<()> {
+        let _stock = self.stock.lock().await;
+        let model = {
+            let Some(mut castle) = self.castles.get_mut(&castle_id) else {
+                return Ok(());
+            };
+            let Some(seed) = castle.production.iter_mut().find(|s| s.seed_id == seed_id) else {
+                // the period was refreshed in between
+                return Ok(());
+            };
+            seed.amount += count;
+            production_model(castle_id, seed, false)
+        };
+        castle_manor_production::Model::save(db_pool, model).await?;
+        Ok(())
+    }
+
+    /// Buys the crops into the current procurement of the castle.
+    /// Returns the price of one crop, `None` if the castle doesn't buy that many.
+    pub async fn procure_crops(
+        &self,
+        db_pool: &DBPool,
+        castle_id: i32,
+        crop_id: i32,
+        count: i64,
+    ) -> anyhow::Result<Option<i64>> {
+        let _stock = self.stock.lock().await;
+        let model = {
+            let Some(mut castle) = self.castles.get_mut(&castle_id) else {
+                return Ok(None);
+            };
+            let Some(crop) = castle
+                .procure
+                .iter_mut()
+                .find(|c| c.crop_id == crop_id && c.amount >= count)
+            else {
+                return Ok(None);
+            };
+            crop.amount -= count;
+            procure_model(castle_id, crop, false)
+        };
+        let price = model.price;
+        castle_manor_procure::Model::save(db_pool, model).await?;
+        Ok(Some(price))
+    }
+
+    /// Gives back the procurement taken by [`Self::procure_crops`], when the sale failed.
+    pub async fn return_crops(
+        &self,
+        db_pool: &DBPool,
+        castle_id: i32,
+        crop_id: i32,
+        count: i64,
+    ) -> anyhow::Result<()> {
+        let _stock = self.stock.lock().await;
+        let model = {
+            let Some(mut castle) = self.castles.get_mut(&castle_id) else {
+                return Ok(());
+            };
+            let Some(crop) = castle.procure.iter_mut().find(|c| c.crop_id == crop_id) else {
+                // the period was refreshed in between
+                return Ok(());
+            };
+            crop.amount += count;
+            procure_model(castle_id, crop, false)
+        };
+        castle_manor_procure::Model::save(db_pool, model).await?;
+        Ok(())
+    }
+
+    /// Returns `false` if a seed was sown on the monster already.
+    pub fn sow(&self, monster_id: i32, seed: SownSeed) -> bool {
+        match self.sown.entry(monster_id) {
+            dashmap::Entry::Occupied(_) => false,
+            dashmap::Entry::Vacant(entry) => {
+                entry.insert(seed);
+                true
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn sown_seed(&self, monster_id: i32) -> Option<SownSeed> {
+        self.sown.get(&monster_id).map(|s| *s)
+    }
+
+    /// The monster entered the world, players may target it to sow and harvest.
+    pub fn on_monster_spawned(&self, monster: ManorTarget) {
+        self.monsters.insert(monster.object_id, monster);
+    }
+
+    #[must_use]
+    pub fn monster(&self, object_id: i32) -> Option<ManorTarget> {
+        self.monsters.get(&object_id).map(|m| *m)
+    }
+
+    /// The monster died, the crops of a seed sown on it may be harvested.
+    pub fn on_monster_killed(&self, monster_id: i32) {
+        if let Some(mut seed) = self.sown.get_mut(&monster_id) {
+            seed.ripe = true;
+        }
+    }
+
+    /// The monster left the world, whatever was sown on it is lost.
+    pub fn on_monster_removed(&self, monster_id: i32) {
+        self.monsters.remove(&monster_id);
+        self.sown.remove(&monster_id);
+    }
+
+    /// Takes the crops of the killed monster, only the sower may harvest them.
+    pub fn harvest(&self, monster_id: i32, char_id: i32) -> Option<SownSeed> {
+        self.sown
+            .remove_if(&monster_id, |_, s| s.ripe && s.sower_id == char_id)
+            .map(|(_, seed)| seed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(hour: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 7, 24, hour, min, 0).unwrap()
+    }
+
+    fn seed(seed_id: i32, amount: i64) -> SeedProduction {
+        SeedProduction {
+            seed_id,
+            amount,
+            start_amount: 100,
+            price: 120,
+        }
+    }
+
+    #[test]
+    fn test_manor_modes() {
+        assert_eq!(ManorMode::at(at(12, 0)), ManorMode::Approved);
+        assert_eq!(ManorMode::at(at(20, 3)), ManorMode::Maintenance);
+        assert_eq!(ManorMode::at(at(23, 0)), ManorMode::Modifiable);
+        assert_eq!(ManorMode::at(at(5, 59)), ManorMode::Modifiable);
+
+        let manager = ManorManager::new(vec![], vec![], at(12, 0));
+        assert!(manager.advance(at(19, 0)).is_empty());
+        assert_eq!(manager.advance(at(20, 1)), vec![ManorEvent::Refreshed]);
+        assert!(manager.advance(at(20, 30)).is_empty());
+        assert_eq!(manager.mode(), ManorMode::Modifiable);
+        assert_eq!(manager.advance(at(7, 0)), vec![ManorEvent::Approved]);
+        // the maintenance was missed, the refresh still happens
+        assert_eq!(manager.advance(at(21, 0)), vec![ManorEvent::Refreshed]);
+    }
+
+    #[test]
+    fn test_castle_manor_refresh() {
+        let mut manor = CastleManor {
+            production: vec![seed(5016, 3)],
+            procure: vec![CropProcure {
+                crop_id: 5073,
+                amount: 2,
+                start_amount: 10,
+                price: 400,
+            }],
+            next_production: vec![seed(5017, 100)],
+            next_procure: vec![],
+        };
+        assert_eq!(manor.unspent_procure(), 800);
+        manor.refresh();
+        assert_eq!(manor.production, vec![seed(5017, 100)]);
+        assert!(manor.procure.is_empty());
+        assert_eq!(manor.next_production, manor.production);
+        manor.next_production[0].amount = 1;
+        manor.refresh();
+        assert_eq!(manor.production, vec![seed(5017, 1)]);
+        assert_eq!(manor.next_production, vec![seed(5017, 100)]);
+    }
+
+    #[test]
+    fn test_sow_and_harvest() {
+        let manager = ManorManager::new(vec![], vec![], at(12, 0));
+        let seed = SownSeed {
+            sower_id: 1,
+            crop_id: 5073,
+            count: 2,
+            ripe: false,
+        };
+        assert!(manager.sow(100, seed));
+        assert!(!manager.sow(100, seed));
+        // the monster is still alive
+        assert!(manager.harvest(100, 1).is_none());
+        manager.on_monster_killed(100);
+        assert!(manager.harvest(100, 2).is_none());
+        assert_eq!(manager.harvest(100, 1).unwrap().count, 2);
+        assert!(manager.sown_seed(100).is_none());
+    }
+}
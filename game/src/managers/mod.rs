@@ -1,2 +1,20 @@
 mod clan_ally;
+mod clock;
+mod cursed_weapons;
+mod mail;
+mod manor;
+mod olympiad;
+mod recommendation;
+mod siege;
+mod vitality;
+mod zone;
 pub use clan_ally::*;
+pub use clock::*;
+pub use cursed_weapons::*;
+pub use mail::*;
+pub use manor::*;
+pub use olympiad::*;
+pub use recommendation::*;
+pub use siege::*;
+pub use vitality::*;
+pub use zone::*;
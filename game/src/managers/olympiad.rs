@@ -0,0 +1,883 @@
+use crate::controller::GameController;
+use crate::heroes::make_hero;
+use crate::packets::to_client::SystemMessage;
+use crate::pl_client::PlayerClient;
+use chrono::{DateTime, Datelike, Months, TimeDelta, Timelike, Utc};
+use dashmap::DashMap;
+use entities::DBPool;
+use entities::entities::{character, hero_diary, olympiad_noble, olympiad_period};
+use l2_core::data::olympiad::{OlympiadData, StadiumTemplate};
+use l2_core::network::connection::HandleOutboundPacket;
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicI32, AtomicI64, AtomicU64, Ordering};
+use std::time::Duration;
+use tracing::{error, info};
+
+const TICK_INTERVAL: Duration = Duration::from_secs(1);
+/// Points every noble starts a period with
+pub const START_POINTS: i32 = 10;
+/// Hours of the day (UTC) games are held in, registrations are dropped outside of them
+const COMPETITION_HOURS: Range<u32> = 18..24;
+/// From the teleport to the stadium until the fight starts
+const COUNTDOWN: TimeDelta = TimeDelta::seconds(20);
+/// Seconds left until the fight at which the fighters are reminded
+const COUNTDOWN_ANNOUNCEMENTS: [i64; 6] = [10, 5, 4, 3, 2, 1];
+/// A fight not decided in time ends in a tie
+const FIGHT_LENGTH: TimeDelta = TimeDelta::minutes(6);
+/// From the end of the fight until everyone goes back
+const RETURN_DELAY: TimeDelta = TimeDelta::seconds(10);
+const MAX_POINTS_TRANSFER: i32 = 10;
+/// Fewest matches of a period to become a hero
+const MIN_MATCHES_FOR_HERO: i32 = 9;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompetitionType {
+    /// Opponents of the same class only
+    Classed,
+    NonClassed,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Registration {
+    char_id: i32,
+    class_id: i8,
+    kind: CompetitionType,
+    /// Order of the registration, the first registered fight first
+    seq: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameStatus {
+    Countdown,
+    Fighting,
+    Finished,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameOutcome {
+    Won {
+        winner: i32,
+        loser: i32,
+        points: i32,
+    },
+    Draw,
+}
+
+/// A game between two nobles in a stadium, one game runs in a stadium at a time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OlympiadGame {
+    pub stadium_id: i32,
+    pub kind: CompetitionType,
+    /// Char ids of the fighters, side 1 first
+    pub fighters: [i32; 2],
+    pub created_at: DateTime<Utc>,
+    pub status: GameStatus,
+    pub spectators: HashSet<i32>,
+    /// Fighter who died or left, the game is decided on the next update
+    defeated: Option<i32>,
+    finished_at: Option<DateTime<Utc>>,
+    /// Last countdown announcement made
+    announced: i64,
+}
+
+impl OlympiadGame {
+    fn new(stadium_id: i32, kind: CompetitionType, fighters: [i32; 2], now: DateTime<Utc>) -> Self {
+        Self {
+            stadium_id,
+            kind,
+            fighters,
+            created_at: now,
+            status: GameStatus::Countdown,
+            spectators: HashSet::new(),
+            defeated: None,
+            finished_at: None,
+            announced: i64::MAX,
+        }
+    }
+
+    #[must_use]
+    pub fn fight_starts_at(&self) -> DateTime<Utc> {
+        self.created_at + COUNTDOWN
+    }
+
+    #[must_use]
+    pub fn fight_ends_at(&self) -> DateTime<Utc> {
+        self.fight_starts_at() + FIGHT_LENGTH
+    }
+
+    /// 1 or 2 for the fighters
+    #[must_use]
+    pub fn side_of(&self, char_id: i32) -> Option<u8> {
+        match self.fighters.iter().position(|f| *f == char_id)? {
+            0 => Some(1),
+            _ => Some(2),
+        }
+    }
+
+    #[must_use]
+    pub fn opponent_of(&self, char_id: i32) -> Option<i32> {
+        self.side_of(char_id)?;
+        self.fighters.iter().copied().find(|f| *f != char_id)
+    }
+
+    /// Fighters and spectators
+    pub fn participants(&self) -> impl Iterator<Item = i32> + '_ {
+        self.fighters
+            .iter()
+            .copied()
+            .chain(self.spectators.iter().copied())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OlympiadEvent {
+    /// Two nobles are matched and go to the stadium
+    GameCreated(i32),
+    /// Seconds left until the fight starts
+    Countdown(i32, i64),
+    FightStarted(i32),
+    GameEnded(i32, GameOutcome),
+    /// Everyone leaves the stadium, the game is gone from the manager
+    GameClosed(OlympiadGame),
+    PeriodEnded {
+        period: i32,
+        heroes: Vec<i32>,
+    },
+}
+
+/// Points lost by the loser of a game and won by the winner.
+#[must_use]
+pub fn points_transfer(loser_points: i32) -> i32 {
+    (loser_points / 5)
+        .clamp(1, MAX_POINTS_TRANSFER)
+        .min(loser_points.max(0))
+}
+
+/// Periods end with the month.
+fn next_period_end(now: DateTime<Utc>) -> DateTime<Utc> {
+    now.date_naive()
+        .with_day(1)
+        .and_then(|d| d.checked_add_months(Months::new(1)))
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .unwrap_or_default()
+        .and_utc()
+}
+
+#[must_use]
+pub fn is_competition_time(now: DateTime<Utc>) -> bool {
+    COMPETITION_HOURS.contains(&now.hour())
+}
+
+/// The olympiad: noble points, registrations, games in the stadiums and the monthly periods
+/// ending with the selection of the heroes.
+///
+/// Like sieges it is driven by a periodic task reading the [`GameClock`](super::GameClock).
+#[derive(Debug)]
+pub struct OlympiadManager {
+    stadiums: OlympiadData,
+    nobles: DashMap<i32, olympiad_noble::Model>,
+    registrations: DashMap<i32, Registration>,
+    next_seq: AtomicU64,
+    /// Running games by stadium id
+    games: DashMap<i32, OlympiadGame>,
+    period: AtomicI32,
+    period_ends_at_ms: AtomicI64,
+}
+
+impl OlympiadManager {
+    #[must_use]
+    pub fn new(
+        stadiums: OlympiadData,
+        nobles: Vec<olympiad_noble::Model>,
+        period: Option<olympiad_period::Model>,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let (period, ends_at) = period.map_or((1, next_period_end(now)), |p| {
+            (p.period, p.ends_at.to_utc())
+        });
+        Self {
+            stadiums,
+            nobles: nobles.into_iter().map(|n| (n.char_id, n)).collect(),
+            registrations: DashMap::new(),
+            next_seq: AtomicU64::new(0),
+            games: DashMap::new(),
+            period: AtomicI32::new(period),
+            period_ends_at_ms: AtomicI64::new(ends_at.timestamp_millis()),
+        }
+    }
+
+    /**
+    # Panics
+    - If the database connection fails.
+
+    It is okay to panic here as we start the manager during the boot process of the application.
+    */
+    pub async fn load(db_pool: &DBPool, stadiums: OlympiadData, now: DateTime<Utc>) -> Self {
+        let nobles = olympiad_noble::Model::load_all(db_pool)
+            .await
+            .expect("Failed to load olympiad nobles, can not continue...");
+        let period = olympiad_period::Model::load(db_pool)
+            .await
+            .expect("Failed to load olympiad period, can not continue...");
+        Self::new(stadiums, nobles, period, now)
+    }
+
+    pub fn start(controller: Arc<GameController>, db_pool: DBPool) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(TICK_INTERVAL);
+            loop {
+                interval.tick().await;
+                if let Err(e) = Self::update(&controller, &db_pool).await {
+                    error!("Olympiad update failed: {e}");
+                }
+            }
+        });
+    }
+
+    /// Runs the games and the period at the current game time.
+    pub async fn update(
+        controller: &GameController,
+        db_pool: &DBPool,
+    ) -> anyhow::Result<Vec<OlympiadEvent>> {
+        let events = controller.olympiad_manager.advance(controller.clock.now());
+        for event in &events {
+            Self::on_event(controller, db_pool, event).await?;
+        }
+        Ok(events)
+    }
+
+    /// Moves the olympiad forward to the time, the caller handles the returned events.
+    pub fn advance(&self, now: DateTime<Utc>) -> Vec<OlympiadEvent> {
+        let mut events = Vec::new();
+        let stadium_ids: Vec<i32> = self.games.iter().map(|g| *g.key()).collect();
+        for stadium_id in stadium_ids {
+            if let Some(event) = self.advance_game(stadium_id, now) {
+                events.push(event);
+            }
+        }
+        if now >= self.period_ends_at() {
+            events.push(self.end_period(now));
+        }
+        if is_competition_time(now) {
+            events.extend(self.match_fighters(now));
+        } else {
+            self.registrations.clear();
+        }
+        events
+    }
+
+    fn advance_game(&self, stadium_id: i32, now: DateTime<Utc>) -> Option<OlympiadEvent> {
+        let mut game = self.games.get_mut(&stadium_id)?;
+        match game.status {
+            GameStatus::Finished => {
+                if game.finished_at.is_some_and(|t| now >= t + RETURN_DELAY) {
+                    drop(game);
+                    let (_, game) = self.games.remove(&stadium_id)?;
+                    return Some(OlympiadEvent::GameClosed(game));
+                }
+                None
+            }
+            _ if game.defeated.is_some() => {
+                let outcome = self.finish(&mut game, now);
+                Some(OlympiadEvent::GameEnded(stadium_id, outcome))
+            }
+            GameStatus::Fighting if now >= game.fight_ends_at() => {
+                let outcome = self.finish(&mut game, now);
+                Some(OlympiadEvent::GameEnded(stadium_id, outcome))
+            }
+            GameStatus::Fighting => None,
+            GameStatus::Countdown => {
+                let left = (game.fight_starts_at() - now).num_seconds();
+                if now >= game.fight_starts_at() {
+                    game.status = GameStatus::Fighting;
+                    return Some(OlympiadEvent::FightStarted(stadium_id));
+                }
+                let due = COUNTDOWN_ANNOUNCEMENTS
+                    .iter()
+                    .copied()
+                    .filter(|t| left <= *t && *t < game.announced)
+                    .min()?;
+                game.announced = due;
+                Some(OlympiadEvent::Countdown(stadium_id, left.max(1)))
+            }
+        }
+    }
+
+    /// Decides the game: the defeated fighter gives points to the other one, otherwise it's a tie.
+    fn finish(&self, game: &mut OlympiadGame, now: DateTime<Utc>) -> GameOutcome {
+        game.status = GameStatus::Finished;
+        game.finished_at = Some(now);
+        let outcome = match game.defeated.and_then(|l| Some((game.opponent_of(l)?, l))) {
+            Some((winner, loser)) => {
+                let points = self.nobles.get_mut(&loser).map_or(0, |mut noble| {
+                    let points = points_transfer(noble.points);
+                    noble.points -= points;
+                    noble.losses += 1;
+                    points
+                });
+                if let Some(mut noble) = self.nobles.get_mut(&winner) {
+                    noble.points += points;
+                    noble.wins += 1;
+                }
+                GameOutcome::Won {
+                    winner,
+                    loser,
+                    points,
+                }
+            }
+            None => {
+                for char_id in game.fighters {
+                    if let Some(mut noble) = self.nobles.get_mut(&char_id) {
+                        noble.draws += 1;
+                    }
+                }
+                GameOutcome::Draw
+            }
+        };
+        for char_id in game.fighters {
+            if let Some(mut noble) = self.nobles.get_mut(&char_id) {
+                noble.matches += 1;
+            }
+        }
+        outcome
+    }
+
+    /// Pairs the registered nobles into the free stadiums, first come first served.
+    fn match_fighters(&self, now: DateTime<Utc>) -> Vec<OlympiadEvent> {
+        let mut queue: Vec<Registration> = self.registrations.iter().map(|r| *r).collect();
+        queue.sort_by_key(|r| r.seq);
+        let free: Vec<i32> = self
+            .stadiums
+            .all()
+            .map(|s| s.id)
+            .filter(|id| !self.games.contains_key(id))
+            .collect();
+        let mut events = Vec::new();
+        for stadium_id in free {
+            let Some((first, second)) = Self::next_pair(&queue) else {
+                break;
+            };
+            queue.retain(|r| r.char_id != first.char_id && r.char_id != second.char_id);
+            self.registrations.remove(&first.char_id);
+            self.registrations.remove(&second.char_id);
+            self.games.insert(
+                stadium_id,
+                OlympiadGame::new(stadium_id, first.kind, [first.char_id, second.char_id], now),
+            );
+            events.push(OlympiadEvent::GameCreated(stadium_id));
+        }
+        events
+    }
+
+    fn next_pair(queue: &[Registration]) -> Option<(Registration, Registration)> {
+        queue.iter().enumerate().find_map(|(i, first)| {
+            queue[i + 1..]
+                .iter()
+                .find(|second| {
+                    second.kind == first.kind
+                        && (first.kind == CompetitionType::NonClassed
+                            || second.class_id == first.class_id)
+                })
+                .map(|second| (*first, *second))
+        })
+    }
+
+    /// The best noble of each class becomes a hero, then everyone starts the next period anew.
+    fn end_period(&self, now: DateTime<Utc>) -> OlympiadEvent {
+        let mut best: HashMap<i8, olympiad_noble::Model> = HashMap::new();
+        for noble in self.nobles.iter() {
+            if noble.matches < MIN_MATCHES_FOR_HERO || noble.wins == 0 {
+                continue;
+            }
+            let rank = |n: &olympiad_noble::Model| (n.points, n.wins, Reverse(n.char_id));
+            let current = best.entry(noble.class_id).or_insert_with(|| noble.clone());
+            if rank(&noble) > rank(current) {
+                *current = noble.clone();
+            }
+        }
+        let mut heroes: Vec<i32> = best.values().map(|n| n.char_id).collect();
+        heroes.sort_unstable();
+        for mut noble in self.nobles.iter_mut() {
+            noble.points = START_POINTS;
+            noble.matches = 0;
+            noble.wins = 0;
+            noble.losses = 0;
+            noble.draws = 0;
+        }
+        self.registrations.clear();
+        let period = self.period.fetch_add(1, Ordering::Relaxed);
+        self.period_ends_at_ms
+            .store(next_period_end(now).timestamp_millis(), Ordering::Relaxed);
+        OlympiadEvent::PeriodEnded { period, heroes }
+    }
+
+    async fn on_event(
+        controller: &GameController,
+        db_pool: &DBPool,
+        event: &OlympiadEvent,
+    ) -> anyhow::Result<()> {
+        let manager = &controller.olympiad_manager;
+        match event {
+            OlympiadEvent::GameCreated(stadium_id) => {
+                let (Some(game), Some(stadium)) =
+                    (manager.game(*stadium_id), manager.stadium(*stadium_id))
+                else {
+                    return Ok(());
+                };
+                for (side, char_id) in (1..).zip(game.fighters) {
+                    let spawn = stadium.spawns[usize::from(side - 1)];
+                    let game_id = *stadium_id;
+                    controller.run_on_char(
+                        char_id,
+                        Box::new(move |pl: &mut PlayerClient| {
+                            Box::pin(pl.enter_olympiad_game(game_id, side, spawn))
+                        }),
+                    );
+                }
+                Self::tell_participants(
+                    controller,
+                    &game,
+                    &format!(
+                        "You have been moved to the Olympiad stadium, the game starts in {} seconds.",
+                        COUNTDOWN.num_seconds()
+                    ),
+                )?;
+            }
+            OlympiadEvent::Countdown(stadium_id, seconds) => {
+                if let Some(game) = manager.game(*stadium_id) {
+                    Self::tell_participants(
+                        controller,
+                        &game,
+                        &format!("The game starts in {seconds} second(s)."),
+                    )?;
+                }
+            }
+            OlympiadEvent::FightStarted(stadium_id) => {
+                let Some(game) = manager.game(*stadium_id) else {
+                    return Ok(());
+                };
+                for char_id in game.fighters {
+                    controller.run_on_char(
+                        char_id,
+                        Box::new(|pl: &mut PlayerClient| Box::pin(pl.set_olympiad_fighting(true))),
+                    );
+                }
+                Self::tell_participants(controller, &game, "The game has started. Fight!")?;
+            }
+            OlympiadEvent::GameEnded(stadium_id, outcome) => {
+                let Some(game) = manager.game(*stadium_id) else {
+                    return Ok(());
+                };
+                for char_id in game.fighters {
+                    if let Some(noble) = manager.noble(char_id) {
+                        olympiad_noble::Model::save(db_pool, noble).await?;
+                    }
+                    controller.run_on_char(
+                        char_id,
+                        Box::new(|pl: &mut PlayerClient| Box::pin(pl.set_olympiad_fighting(false))),
+                    );
+                }
+                let text = match outcome {
+                    GameOutcome::Won { winner, points, .. } => {
+                        let name = controller
+                            .get_char_in_game(*winner)
+                            .map_or_else(|| winner.to_string(), |c| c.name);
+                        format!("{name} has won the game and earned {points} Olympiad points.")
+                    }
+                    GameOutcome::Draw => "The game has ended in a tie.".to_string(),
+                };
+                if let GameOutcome::Won { winner, loser, .. } = outcome
+                    && controller.hero_until(*winner).is_some()
+                {
+                    let loser_name = controller
+                        .get_char_in_game(*loser)
+                        .map_or_else(|| loser.to_string(), |c| c.name);
+                    hero_diary::Model::add(
+                        db_pool,
+                        *winner,
+                        controller.clock.now().fixed_offset(),
+                        &format!("Won an Olympiad game against {loser_name}."),
+                    )
+                    .await?;
+                }
+                Self::tell_participants(controller, &game, &text)?;
+            }
+            OlympiadEvent::GameClosed(game) => {
+                for char_id in game.participants() {
+                    controller.run_on_char(
+                        char_id,
+                        Box::new(|pl: &mut PlayerClient| Box::pin(pl.leave_olympiad_game())),
+                    );
+                }
+            }
+            OlympiadEvent::PeriodEnded { period, heroes } => {
+                olympiad_noble::Model::reset_all(db_pool, START_POINTS).await?;
+                olympiad_period::Model::save(
+                    db_pool,
+                    olympiad_period::Model {
+                        id: olympiad_period::Model::ID,
+                        period: manager.period(),
+                        ends_at: manager.period_ends_at().fixed_offset(),
+                    },
+                )
+                .await?;
+                Self::select_heroes(controller, db_pool, *period, heroes).await?;
+                let text = format!("Olympiad period {period} has ended.");
+                info!("{text} Heroes: {heroes:?}");
+                controller.broadcast_packet(SystemMessage::text(&text)?);
+            }
+        }
+        Ok(())
+    }
+
+    /// Heroes of the previous period lose the status (unless a GM made them heroes for longer),
+    /// the new ones are heroes until the end of the new period.
+    async fn select_heroes(
+        controller: &GameController,
+        db_pool: &DBPool,
+        period: i32,
+        heroes: &[i32],
+    ) -> anyhow::Result<()> {
+        let now = controller.clock.now();
+        let expired: Vec<i32> = controller
+            .hero_list
+            .iter()
+            .filter(|h| h.expires_at.to_utc() <= now)
+            .map(|h| *h.key())
+            .collect();
+        for char_id in expired {
+            controller.hero_list.remove(&char_id);
+            if !heroes.contains(&char_id) {
+                controller.run_on_char(
+                    char_id,
+                    Box::new(|pl: &mut PlayerClient| Box::pin(pl.set_hero(None))),
+                );
+            }
+        }
+        let expires_at = controller.olympiad_manager.period_ends_at();
+        for model in character::Model::find_by_ids(db_pool, heroes).await? {
+            let text = format!("Became a hero of Olympiad period {period}.");
+            make_hero(controller, db_pool, &model, expires_at, &text).await?;
+            controller.run_on_char(
+                model.id,
+                Box::new(move |pl: &mut PlayerClient| Box::pin(pl.set_hero(Some(expires_at)))),
+            );
+        }
+        Ok(())
+    }
+
+    fn tell_participants(
+        controller: &GameController,
+        game: &OlympiadGame,
+        text: &str,
+    ) -> anyhow::Result<()> {
+        let message = SystemMessage::text(text)?;
+        for char_id in game.participants() {
+            if let Some(char) = controller.get_char_in_game(char_id) {
+                let message = message.clone();
+                tokio::spawn(async move {
+                    let _ = char
+                        .actor
+                        .tell(HandleOutboundPacket { packet: message })
+                        .await;
+                });
+            }
+        }
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn period(&self) -> i32 {
+        self.period.load(Ordering::Relaxed)
+    }
+
+    #[must_use]
+    pub fn period_ends_at(&self) -> DateTime<Utc> {
+        DateTime::from_timestamp_millis(self.period_ends_at_ms.load(Ordering::Relaxed))
+            .unwrap_or_default()
+    }
+
+    #[must_use]
+    pub fn stadium(&self, stadium_id: i32) -> Option<&StadiumTemplate> {
+        self.stadiums.get(stadium_id)
+    }
+
+    #[must_use]
+    pub fn game(&self, stadium_id: i32) -> Option<OlympiadGame> {
+        self.games.get(&stadium_id).map(|g| g.clone())
+    }
+
+    /// Running games ordered by stadium
+    #[must_use]
+    pub fn games(&self) -> Vec<OlympiadGame> {
+        let mut games: Vec<OlympiadGame> = self.games.iter().map(|g| g.clone()).collect();
+        games.sort_by_key(|g| g.stadium_id);
+        games
+    }
+
+    /// Game the char fights in
+    #[must_use]
+    pub fn game_of(&self, char_id: i32) -> Option<OlympiadGame> {
+        self.games
+            .iter()
+            .find(|g| g.side_of(char_id).is_some())
+            .map(|g| g.clone())
+    }
+
+    #[must_use]
+    pub fn noble(&self, char_id: i32) -> Option<olympiad_noble::Model> {
+        self.nobles.get(&char_id).map(|n| n.clone())
+    }
+
+    #[must_use]
+    pub fn registration_of(&self, char_id: i32) -> Option<CompetitionType> {
+        self.registrations.get(&char_id).map(|r| r.kind)
+    }
+
+    /// Record of the noble, a noble entering the olympiad for the first time gets one with
+    /// [`START_POINTS`].
+    pub async fn noble_or_create(
+        &self,
+        db_pool: &DBPool,
+        char_id: i32,
+        class_id: i8,
+    ) -> anyhow::Result<olympiad_noble::Model> {
+        if let Some(noble) = self.noble(char_id) {
+            return Ok(noble);
+        }
+        let noble = olympiad_noble::Model {
+            char_id,
+            class_id,
+            points: START_POINTS,
+            ..Default::default()
+        };
+        olympiad_noble::Model::save(db_pool, noble.clone()).await?;
+        self.nobles.insert(char_id, noble.clone());
+        Ok(noble)
+    }
+
+    pub fn register(&self, char_id: i32, class_id: i8, kind: CompetitionType) {
+        let seq = self.next_seq.fetch_add(1, Ordering::Relaxed);
+        self.registrations.insert(
+            char_id,
+            Registration {
+                char_id,
+                class_id,
+                kind,
+                seq,
+            },
+        );
+    }
+
+    /// Returns `false` if the char wasn't registered.
+    pub fn unregister(&self, char_id: i32) -> bool {
+        self.registrations.remove(&char_id).is_some()
+    }
+
+    /// Returns `false` if there is no game in the stadium to watch.
+    pub fn add_spectator(&self, stadium_id: i32, char_id: i32) -> bool {
+        let Some(mut game) = self.games.get_mut(&stadium_id) else {
+            return false;
+        };
+        if game.status == GameStatus::Finished || game.side_of(char_id).is_some() {
+            return false;
+        }
+        game.spectators.insert(char_id);
+        true
+    }
+
+    /// The fighter died or left the game, the opponent wins it.
+    /// Returns `false` if the char fights in no game.
+    pub fn on_defeat(&self, char_id: i32) -> bool {
+        let Some(mut game) = self
+            .games
+            .iter_mut()
+            .find(|g| g.status != GameStatus::Finished && g.side_of(char_id).is_some())
+        else {
+            return false;
+        };
+        game.defeated.get_or_insert(char_id);
+        true
+    }
+
+    /// The char left the game world: the registration is dropped, a fighter loses the game.
+    pub fn on_leave(&self, char_id: i32) {
+        self.registrations.remove(&char_id);
+        for mut game in self.games.iter_mut() {
+            game.spectators.remove(&char_id);
+        }
+        self.on_defeat(char_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use l2_core::config::traits::ConfigDirLoader;
+
+    fn at(day: u32, hour: u32) -> DateTime<Utc> {
+        NaiveDate::from_ymd_opt(2025, 7, day)
+            .and_then(|d| d.and_hms_opt(hour, 0, 0))
+            .unwrap()
+            .and_utc()
+    }
+
+    fn manager(now: DateTime<Utc>) -> OlympiadManager {
+        OlympiadManager::new(OlympiadData::load(), vec![], None, now)
+    }
+
+    fn noble(char_id: i32, class_id: i8, points: i32) -> olympiad_noble::Model {
+        olympiad_noble::Model {
+            char_id,
+            class_id,
+            points,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_points_transfer() {
+        assert_eq!(points_transfer(10), 2);
+        assert_eq!(points_transfer(3), 1);
+        assert_eq!(points_transfer(200), MAX_POINTS_TRANSFER);
+        assert_eq!(points_transfer(0), 0);
+    }
+
+    #[test]
+    fn test_period_end() {
+        assert_eq!(next_period_end(at(15, 10)), at(31, 0) + TimeDelta::days(1));
+        assert!(is_competition_time(at(1, 18)));
+        assert!(!is_competition_time(at(1, 17)));
+    }
+
+    #[test]
+    fn test_matching() {
+        let now = at(10, 19);
+        let manager = manager(now);
+        manager.register(1, 88, CompetitionType::Classed);
+        manager.register(2, 93, CompetitionType::Classed);
+        manager.register(3, 93, CompetitionType::NonClassed);
+        // no one to fight with of the same class
+        assert!(manager.advance(now).is_empty());
+        manager.register(4, 88, CompetitionType::Classed);
+        manager.register(5, 99, CompetitionType::NonClassed);
+        assert_eq!(
+            manager.advance(now),
+            vec![OlympiadEvent::GameCreated(1), OlympiadEvent::GameCreated(2)]
+        );
+        assert_eq!(manager.game(1).unwrap().fighters, [1, 4]);
+        assert_eq!(manager.game(2).unwrap().fighters, [3, 5]);
+        assert_eq!(manager.registration_of(2), Some(CompetitionType::Classed));
+        // registrations are dropped once the competition hours are over
+        manager.advance(at(11, 1));
+        assert_eq!(manager.registration_of(2), None);
+    }
+
+    #[test]
+    fn test_game_flow() {
+        let now = at(10, 19);
+        let manager = OlympiadManager::new(
+            OlympiadData::load(),
+            vec![noble(1, 88, 10), noble(2, 88, 10)],
+            None,
+            now,
+        );
+        manager.register(1, 88, CompetitionType::Classed);
+        manager.register(2, 88, CompetitionType::Classed);
+        assert_eq!(manager.advance(now), vec![OlympiadEvent::GameCreated(1)]);
+        assert_eq!(
+            manager.advance(now + COUNTDOWN - TimeDelta::seconds(10)),
+            vec![OlympiadEvent::Countdown(1, 10)]
+        );
+        assert_eq!(
+            manager.advance(now + COUNTDOWN),
+            vec![OlympiadEvent::FightStarted(1)]
+        );
+        assert!(manager.add_spectator(1, 3));
+        assert!(manager.on_defeat(2));
+        let ended = now + COUNTDOWN + TimeDelta::minutes(1);
+        assert_eq!(
+            manager.advance(ended),
+            vec![OlympiadEvent::GameEnded(
+                1,
+                GameOutcome::Won {
+                    winner: 1,
+                    loser: 2,
+                    points: 2
+                }
+            )]
+        );
+        assert_eq!(manager.noble(1).unwrap().points, 12);
+        assert_eq!(manager.noble(2).unwrap().points, 8);
+        assert_eq!(manager.noble(2).unwrap().matches, 1);
+        let closed = manager.advance(ended + RETURN_DELAY);
+        let [OlympiadEvent::GameClosed(game)] = closed.as_slice() else {
+            panic!("Game not closed: {closed:?}");
+        };
+        assert_eq!(
+            game.participants().collect::<HashSet<_>>(),
+            HashSet::from([1, 2, 3])
+        );
+        assert!(manager.games().is_empty());
+    }
+
+    #[test]
+    fn test_timeout_is_a_draw() {
+        let now = at(10, 19);
+        let manager = OlympiadManager::new(
+            OlympiadData::load(),
+            vec![noble(1, 88, 10), noble(2, 90, 10)],
+            None,
+            now,
+        );
+        manager.register(1, 88, CompetitionType::NonClassed);
+        manager.register(2, 90, CompetitionType::NonClassed);
+        manager.advance(now);
+        manager.advance(now + COUNTDOWN);
+        let game = manager.game(1).unwrap();
+        assert_eq!(
+            manager.advance(game.fight_ends_at()),
+            vec![OlympiadEvent::GameEnded(1, GameOutcome::Draw)]
+        );
+        assert_eq!(manager.noble(1).unwrap().draws, 1);
+        assert_eq!(manager.noble(2).unwrap().points, 10);
+    }
+
+    #[test]
+    fn test_heroes_of_the_period() {
+        let now = at(10, 10);
+        let record = |char_id, class_id, points, matches| olympiad_noble::Model {
+            matches,
+            wins: 5,
+            ..noble(char_id, class_id, points)
+        };
+        let manager = OlympiadManager::new(
+            OlympiadData::load(),
+            vec![
+                record(1, 88, 40, 10),
+                record(2, 88, 30, 10),
+                // more points but not enough matches
+                record(3, 93, 50, 3),
+                record(4, 93, 20, 9),
+            ],
+            None,
+            now,
+        );
+        let ends_at = manager.period_ends_at();
+        assert_eq!(
+            manager.advance(ends_at),
+            vec![OlympiadEvent::PeriodEnded {
+                period: 1,
+                heroes: vec![1, 4]
+            }]
+        );
+        assert_eq!(manager.period(), 2);
+        assert!(manager.period_ends_at() > ends_at);
+        assert_eq!(manager.noble(1).unwrap().points, START_POINTS);
+        assert_eq!(manager.noble(1).unwrap().matches, 0);
+    }
+}
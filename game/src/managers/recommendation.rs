@@ -0,0 +1,81 @@
+use crate::controller::GameController;
+use crate::pl_client::PlayerClient;
+use chrono::{DateTime, Utc};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::error;
+
+const TICK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Runs the recommendation bonus timer and the daily refresh for the players online.
+///
+/// The bonus time is only spent while the player is online, so the manager hands the time
+/// passed since its last tick to every player in game.
+#[derive(Debug)]
+pub struct RecommendationManager {
+    ticked_at: Mutex<DateTime<Utc>>,
+}
+
+impl RecommendationManager {
+    #[must_use]
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            ticked_at: Mutex::new(now),
+        }
+    }
+
+    pub fn start(controller: Arc<GameController>) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(TICK_INTERVAL);
+            loop {
+                interval.tick().await;
+                Self::update(&controller);
+            }
+        });
+    }
+
+    /// Tells the players online how many seconds of the game time passed.
+    pub fn update(controller: &GameController) -> u32 {
+        let seconds = controller
+            .recommendation_manager
+            .advance(controller.clock.now());
+        for char_id in controller.chars_in_game_ids() {
+            controller.run_on_char(
+                char_id,
+                Box::new(move |pl: &mut PlayerClient| {
+                    Box::pin(async move {
+                        if let Err(e) = pl.update_recommendations(seconds).await {
+                            error!("Recommendation update failed: {e}");
+                        }
+                        Ok(())
+                    })
+                }),
+            );
+        }
+        seconds
+    }
+
+    /// Seconds passed since the last call.
+    pub fn advance(&self, now: DateTime<Utc>) -> u32 {
+        let mut ticked_at = self.ticked_at.lock().unwrap_or_else(|e| e.into_inner());
+        let seconds = (now - *ticked_at).num_seconds().max(0);
+        *ticked_at = now;
+        u32::try_from(seconds).unwrap_or(u32::MAX)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeDelta, TimeZone};
+
+    #[test]
+    fn test_advance() {
+        let now = Utc.with_ymd_and_hms(2025, 7, 22, 6, 0, 0).unwrap();
+        let manager = RecommendationManager::new(now);
+        assert_eq!(manager.advance(now + TimeDelta::minutes(1)), 60);
+        assert_eq!(manager.advance(now + TimeDelta::minutes(3)), 120);
+        // the clock never goes back, but if it did no time passed
+        assert_eq!(manager.advance(now), 0);
+    }
+}
@@ -0,0 +1,593 @@
+use crate::controller::GameController;
+use crate::packets::to_client::SystemMessage;
+use crate::pl_client::PlayerClient;
+use anyhow::Context;
+use chrono::{DateTime, Days, TimeDelta, Utc};
+use dashmap::DashMap;
+use entities::DBPool;
+use entities::dao::siege_clan::SiegeSide;
+use entities::entities::{castle, siege_clan, siege_schedule};
+use l2_core::data::castles::{CastleData, CastleTemplate, Position};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info};
+
+const TICK_INTERVAL: Duration = Duration::from_secs(10);
+/// Days between two sieges of a castle
+const SIEGE_PERIOD_DAYS: u64 = 14;
+/// Hour of the day (UTC) sieges start at
+const SIEGE_HOUR: u32 = 20;
+const SIEGE_LENGTH: TimeDelta = TimeDelta::hours(2);
+const REGISTRATION_CLOSES_BEFORE: TimeDelta = TimeDelta::days(1);
+
+/// `siege_state` of a player fighting for the attackers
+pub const SIEGE_STATE_ATTACKER: u8 = 1;
+/// `siege_state` of a player fighting for the defenders
+pub const SIEGE_STATE_DEFENDER: u8 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SiegeStatus {
+    Registration,
+    RegistrationClosed,
+    InProgress,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SiegeEvent {
+    Started(i32),
+    /// No clan registered to attack, the castle stays with its owner
+    Cancelled(i32),
+    Ended(i32),
+}
+
+/// A castle together with its next (or running) siege.
+#[derive(Debug, Clone)]
+pub struct CastleSiege {
+    pub template: CastleTemplate,
+    /// Clan owning the castle, the npcs own it otherwise
+    pub owner: Option<i32>,
+    /// Percent
+    pub tax_rate: i32,
+    /// Adena of the castle, the manor is paid from it
+    pub treasury: i64,
+    pub starts_at: DateTime<Utc>,
+    pub registration_ends_at: DateTime<Utc>,
+    /// Registered clans, the owner defends without a registration
+    pub clans: HashMap<i32, SiegeSide>,
+    /// Headquarters built by the attackers during the siege
+    pub headquarters: HashMap<i32, Position>,
+    pub in_progress: bool,
+}
+
+impl CastleSiege {
+    fn new(template: CastleTemplate, now: DateTime<Utc>) -> Self {
+        let mut castle = Self {
+            template,
+            owner: None,
+            tax_rate: 0,
+            treasury: 0,
+            starts_at: now,
+            registration_ends_at: now,
+            clans: HashMap::new(),
+            headquarters: HashMap::new(),
+            in_progress: false,
+        };
+        castle.schedule_next(now);
+        castle
+    }
+
+    /// Takes the stored siege date unless it has passed while the server was down.
+    fn restore_schedule(
+        &mut self,
+        starts_at: DateTime<Utc>,
+        registration_ends_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) {
+        if starts_at > now {
+            self.starts_at = starts_at;
+            self.registration_ends_at = registration_ends_at;
+        }
+    }
+
+    fn schedule_next(&mut self, now: DateTime<Utc>) {
+        self.starts_at = (now.date_naive() + Days::new(SIEGE_PERIOD_DAYS))
+            .and_hms_opt(SIEGE_HOUR, 0, 0)
+            .unwrap_or_default()
+            .and_utc();
+        self.registration_ends_at = self.starts_at - REGISTRATION_CLOSES_BEFORE;
+    }
+
+    #[must_use]
+    pub fn ends_at(&self) -> DateTime<Utc> {
+        self.starts_at + SIEGE_LENGTH
+    }
+
+    #[must_use]
+    pub fn status(&self, now: DateTime<Utc>) -> SiegeStatus {
+        if self.in_progress {
+            SiegeStatus::InProgress
+        } else if now < self.registration_ends_at {
+            SiegeStatus::Registration
+        } else {
+            SiegeStatus::RegistrationClosed
+        }
+    }
+
+    #[must_use]
+    pub fn side_of(&self, clan_id: i32) -> Option<SiegeSide> {
+        if self.owner == Some(clan_id) {
+            Some(SiegeSide::Defender)
+        } else {
+            self.clans.get(&clan_id).copied()
+        }
+    }
+
+    #[must_use]
+    pub fn has_attackers(&self) -> bool {
+        self.clans.values().any(|s| *s == SiegeSide::Attacker)
+    }
+
+    /// Row of the castle, `None` while the npcs own it
+    #[must_use]
+    pub fn to_model(&self) -> Option<castle::Model> {
+        Some(castle::Model {
+            id: self.template.id,
+            name: self.template.name.clone(),
+            clan_id: self.owner?,
+            tax_rate: self.tax_rate,
+            treasury: self.treasury,
+            siege_starts_at: Some(self.starts_at.fixed_offset()),
+            registration_ends_at: Some(self.registration_ends_at.fixed_offset()),
+            show_npc_crest: true,
+            ..Default::default()
+        })
+    }
+
+    /// Saves the siege date, in the castle row when a clan owns the castle.
+    async fn save_schedule(&self, db_pool: &DBPool) -> anyhow::Result<()> {
+        if let Some(model) = self.to_model() {
+            castle::Model::save(db_pool, model).await?;
+        } else {
+            let schedule = siege_schedule::Model {
+                castle_id: self.template.id,
+                siege_starts_at: self.starts_at.fixed_offset(),
+                registration_ends_at: self.registration_ends_at.fixed_offset(),
+            };
+            siege_schedule::Model::save(db_pool, schedule).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Castle owners, siege registrations and the siege schedule.
+///
+/// Sieges are started and ended by a periodic task reading the [`GameClock`](super::GameClock),
+/// so moving the clock forward is enough to run a siege without waiting for it.
+#[derive(Debug, Default)]
+pub struct SiegeManager {
+    castles: DashMap<i32, CastleSiege>,
+}
+
+impl SiegeManager {
+    #[must_use]
+    pub fn new(
+        data: &CastleData,
+        owned: Vec<castle::Model>,
+        schedules: Vec<siege_schedule::Model>,
+        registrations: Vec<siege_clan::Model>,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let castles: DashMap<i32, CastleSiege> = data
+            .all()
+            .map(|t| (t.id, CastleSiege::new(t.clone(), now)))
+            .collect();
+        for row in owned {
+            let Some(mut castle) = castles.get_mut(&row.id) else {
+                error!("Castle {} is not in the castle data", row.id);
+                continue;
+            };
+            castle.owner = Some(row.clan_id);
+            castle.tax_rate = row.tax_rate;
+            castle.treasury = row.treasury;
+            // a siege missed while the server was down is moved to the next period
+            if let Some(starts_at) = row.siege_starts_at.map(|t| t.to_utc()) {
+                let registration_ends_at = row
+                    .registration_ends_at
+                    .map_or(starts_at - REGISTRATION_CLOSES_BEFORE, |t| t.to_utc());
+                castle.restore_schedule(starts_at, registration_ends_at, now);
+            }
+        }
+        for row in schedules {
+            if let Some(mut castle) = castles.get_mut(&row.castle_id)
+                && castle.owner.is_none()
+            {
+                castle.restore_schedule(
+                    row.siege_starts_at.to_utc(),
+                    row.registration_ends_at.to_utc(),
+                    now,
+                );
+            }
+        }
+        for reg in registrations {
+            if let Some(mut castle) = castles.get_mut(&reg.castle_id) {
+                castle.clans.insert(reg.clan_id, reg.side);
+            }
+        }
+        Self { castles }
+    }
+
+    /**
+    # Panics
+    - If the database connection fails.
+
+    It is okay to panic here as we start the manager during the boot process of the application.
+    */
+    pub async fn load(db_pool: &DBPool, data: &CastleData, now: DateTime<Utc>) -> Self {
+        let owned = castle::Model::load_all(db_pool)
+            .await
+            .expect("Failed to load castles, can not continue...");
+        let schedules = siege_schedule::Model::load_all(db_pool)
+            .await
+            .expect("Failed to load siege schedules, can not continue...");
+        let registrations = siege_clan::Model::load_all(db_pool)
+            .await
+            .expect("Failed to load siege registrations, can not continue...");
+        let manager = Self::new(data, owned, schedules, registrations, now);
+        // the dates picked now must be the same after the next restart
+        for castle in manager.castles.iter().map(|c| c.clone()) {
+            castle
+                .save_schedule(db_pool)
+                .await
+                .expect("Failed to save siege schedules, can not continue...");
+        }
+        manager
+    }
+
+    pub fn start(controller: Arc<GameController>, db_pool: DBPool) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(TICK_INTERVAL);
+            loop {
+                interval.tick().await;
+                if let Err(e) = Self::update(&controller, &db_pool).await {
+                    error!("Siege update failed: {e}");
+                }
+            }
+        });
+    }
+
+    /// Starts and ends the sieges due at the current game time.
+    pub async fn update(
+        controller: &GameController,
+        db_pool: &DBPool,
+    ) -> anyhow::Result<Vec<SiegeEvent>> {
+        let events = controller.siege_manager.advance(controller.clock.now());
+        for event in &events {
+            Self::on_event(controller, db_pool, *event).await?;
+        }
+        Ok(events)
+    }
+
+    /// Moves the sieges forward to the time, the caller handles the returned events.
+    pub fn advance(&self, now: DateTime<Utc>) -> Vec<SiegeEvent> {
+        let mut events = Vec::new();
+        for mut castle in self.castles.iter_mut() {
+            if !castle.in_progress && now >= castle.starts_at {
+                if castle.has_attackers() {
+                    castle.in_progress = true;
+                    events.push(SiegeEvent::Started(castle.template.id));
+                } else {
+                    castle.clans.clear();
+                    castle.schedule_next(now);
+                    events.push(SiegeEvent::Cancelled(castle.template.id));
+                }
+            } else if castle.in_progress && now >= castle.ends_at() {
+                castle.in_progress = false;
+                castle.clans.clear();
+                castle.headquarters.clear();
+                castle.schedule_next(now);
+                events.push(SiegeEvent::Ended(castle.template.id));
+            }
+        }
+        events
+    }
+
+    async fn on_event(
+        controller: &GameController,
+        db_pool: &DBPool,
+        event: SiegeEvent,
+    ) -> anyhow::Result<()> {
+        let (SiegeEvent::Started(castle_id)
+        | SiegeEvent::Cancelled(castle_id)
+        | SiegeEvent::Ended(castle_id)) = event;
+        let castle = controller
+            .siege_manager
+            .get(castle_id)
+            .context("Unknown castle")?;
+        let name = &castle.template.name;
+        let text = match event {
+            SiegeEvent::Started(_) => format!("The siege of {name} Castle has begun."),
+            SiegeEvent::Cancelled(_) => {
+                format!("The siege of {name} Castle has been canceled, no clan registered.")
+            }
+            SiegeEvent::Ended(_) => format!("The siege of {name} Castle has ended."),
+        };
+        info!("{text}");
+        controller.zone_manager.set_active(
+            castle.template.siege_zone,
+            event == SiegeEvent::Started(castle_id),
+        );
+        if event != SiegeEvent::Started(castle_id) {
+            siege_clan::Model::delete_castle_clans(db_pool, castle_id).await?;
+            castle.save_schedule(db_pool).await?;
+        }
+        Self::announce(controller, &text)?;
+        Self::refresh_siege_states(controller);
+        Ok(())
+    }
+
+    /// Sends the text to everyone in game.
+    pub fn announce(controller: &GameController, text: &str) -> anyhow::Result<()> {
+        controller.broadcast_packet(SystemMessage::text(text)?);
+        Ok(())
+    }
+
+    /// Chars in game pick up the siege side of their clan.
+    pub fn refresh_siege_states(controller: &GameController) {
+        for char_id in controller.chars_in_game_ids() {
+            controller.run_on_char(
+                char_id,
+                Box::new(|pl: &mut PlayerClient| Box::pin(pl.refresh_siege_state())),
+            );
+        }
+    }
+
+    #[must_use]
+    pub fn get(&self, castle_id: i32) -> Option<CastleSiege> {
+        self.castles.get(&castle_id).map(|c| c.clone())
+    }
+
+    /// `siege_state` of the clan members, 0 when the clan fights in no running siege
+    #[must_use]
+    pub fn siege_state(&self, clan_id: i32) -> u8 {
+        self.castles
+            .iter()
+            .filter(|c| c.in_progress)
+            .find_map(|c| c.side_of(clan_id))
+            .map_or(0, |side| match side {
+                SiegeSide::Attacker => SIEGE_STATE_ATTACKER,
+                SiegeSide::Defender => SIEGE_STATE_DEFENDER,
+            })
+    }
+
+    /// Running siege the clan takes part in
+    #[must_use]
+    pub fn running_siege_of(&self, clan_id: i32) -> Option<CastleSiege> {
+        self.castles
+            .iter()
+            .find(|c| c.in_progress && c.side_of(clan_id).is_some())
+            .map(|c| c.clone())
+    }
+
+    /// Castle whose siege the clan is registered for
+    #[must_use]
+    pub fn registered_castle(&self, clan_id: i32) -> Option<i32> {
+        self.castles
+            .iter()
+            .find(|c| c.clans.contains_key(&clan_id))
+            .map(|c| c.template.id)
+    }
+
+    #[must_use]
+    pub fn owned_castle(&self, clan_id: i32) -> Option<i32> {
+        self.castles
+            .iter()
+            .find(|c| c.owner == Some(clan_id))
+            .map(|c| c.template.id)
+    }
+
+    pub async fn register(
+        &self,
+        db_pool: &DBPool,
+        castle_id: i32,
+        clan_id: i32,
+        side: SiegeSide,
+    ) -> anyhow::Result<()> {
+        self.castles
+            .get_mut(&castle_id)
+            .context("Unknown castle")?
+            .clans
+            .insert(clan_id, side);
+        siege_clan::Model::save(
+            db_pool,
+            siege_clan::Model {
+                castle_id,
+                clan_id,
+                side,
+            },
+        )
+        .await?;
+        Ok(())
+    }
+
+    pub async fn unregister(
+        &self,
+        db_pool: &DBPool,
+        castle_id: i32,
+        clan_id: i32,
+    ) -> anyhow::Result<()> {
+        self.castles
+            .get_mut(&castle_id)
+            .context("Unknown castle")?
+            .clans
+            .remove(&clan_id);
+        siege_clan::Model::delete(db_pool, castle_id, clan_id).await?;
+        Ok(())
+    }
+
+    pub async fn set_tax_rate(
+        &self,
+        db_pool: &DBPool,
+        castle_id: i32,
+        tax_rate: i32,
+    ) -> anyhow::Result<()> {
+        let model = {
+            let mut castle = self.castles.get_mut(&castle_id).context("Unknown castle")?;
+            castle.tax_rate = tax_rate;
+            castle.to_model().context("Castle has no owner")?
+        };
+        castle::Model::save(db_pool, model).await?;
+        Ok(())
+    }
+
+    /// Adds the adena to the treasury of an owned castle, or takes it when negative.
+    /// Returns `false` and changes nothing if the treasury can't pay.
+    pub async fn add_to_treasury(
+        &self,
+        db_pool: &DBPool,
+        castle_id: i32,
+        amount: i64,
+    ) -> anyhow::Result<bool> {
+        let model = {
+            let mut castle = self.castles.get_mut(&castle_id).context("Unknown castle")?;
+            match castle.treasury.checked_add(amount).filter(|t| *t >= 0) {
+                Some(treasury) if castle.owner.is_some() => castle.treasury = treasury,
+                _ => return Ok(false),
+            }
+            castle.to_model().context("Castle has no owner")?
+        };
+        castle::Model::save(db_pool, model).await?;
+        Ok(true)
+    }
+
+    /// Returns `false` if the clan has its headquarters already.
+    pub fn build_headquarters(&self, castle_id: i32, clan_id: i32, position: Position) -> bool {
+        let Some(mut castle) = self.castles.get_mut(&castle_id) else {
+            return false;
+        };
+        if castle.headquarters.contains_key(&clan_id) {
+            return false;
+        }
+        castle.headquarters.insert(clan_id, position);
+        true
+    }
+
+    /// The clan sealed the artifact and takes the castle over. The siege goes on until its end:
+    /// the former defenders attack from now on and the new owner defends.
+    pub async fn engrave(
+        &self,
+        db_pool: &DBPool,
+        castle_id: i32,
+        clan_id: i32,
+    ) -> anyhow::Result<Option<i32>> {
+        let (previous, model, sides) = {
+            let mut castle = self.castles.get_mut(&castle_id).context("Unknown castle")?;
+            let previous = castle.owner.replace(clan_id);
+            castle.tax_rate = 0;
+            castle.clans.remove(&clan_id);
+            castle.headquarters.remove(&clan_id);
+            for side in castle.clans.values_mut() {
+                *side = SiegeSide::Attacker;
+            }
+            if let Some(previous) = previous {
+                castle.clans.insert(previous, SiegeSide::Attacker);
+            }
+            let sides: Vec<_> = castle.clans.iter().map(|(c, s)| (*c, *s)).collect();
+            (
+                previous,
+                castle.to_model().context("Castle has no owner")?,
+                sides,
+            )
+        };
+        castle::Model::save(db_pool, model).await?;
+        siege_clan::Model::delete(db_pool, castle_id, clan_id).await?;
+        for (clan_id, side) in sides {
+            siege_clan::Model::save(
+                db_pool,
+                siege_clan::Model {
+                    castle_id,
+                    clan_id,
+                    side,
+                },
+            )
+            .await?;
+        }
+        Ok(previous)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use l2_core::config::traits::ConfigDirLoader;
+
+    fn manager(now: DateTime<Utc>) -> SiegeManager {
+        SiegeManager::new(&CastleData::load(), vec![], vec![], vec![], now)
+    }
+
+    #[test]
+    fn test_siege_schedule() {
+        let now = Utc::now();
+        let manager = manager(now);
+        let castle = manager.get(1).unwrap();
+        assert_eq!(castle.status(now), SiegeStatus::Registration);
+        assert!(castle.starts_at > now + TimeDelta::days(13));
+        assert_eq!(
+            castle.status(castle.registration_ends_at),
+            SiegeStatus::RegistrationClosed
+        );
+        // nobody registered, the siege is moved to the next period
+        assert_eq!(
+            manager.advance(castle.starts_at),
+            vec![SiegeEvent::Cancelled(1)]
+        );
+        assert!(manager.get(1).unwrap().starts_at > castle.starts_at);
+    }
+
+    #[test]
+    fn test_stored_schedule_is_kept() {
+        let now = Utc::now();
+        let starts_at = now + TimeDelta::days(3);
+        let schedule = |castle_id, starts_at: DateTime<Utc>| siege_schedule::Model {
+            castle_id,
+            siege_starts_at: starts_at.fixed_offset(),
+            registration_ends_at: (starts_at - REGISTRATION_CLOSES_BEFORE).fixed_offset(),
+        };
+        let manager =
+            |schedule| SiegeManager::new(&CastleData::load(), vec![], vec![schedule], vec![], now);
+        assert_eq!(
+            manager(schedule(1, starts_at)).get(1).unwrap().starts_at,
+            starts_at
+        );
+        // missed while the server was down, moved to the next period
+        let missed = manager(schedule(1, now - TimeDelta::days(1)));
+        assert!(missed.get(1).unwrap().starts_at > now + TimeDelta::days(13));
+    }
+
+    #[test]
+    fn test_siege_sides() {
+        let now = Utc::now();
+        let manager = manager(now);
+        manager
+            .castles
+            .get_mut(&1)
+            .unwrap()
+            .clans
+            .extend([(10, SiegeSide::Attacker), (20, SiegeSide::Attacker)]);
+        let starts_at = manager.get(1).unwrap().starts_at;
+        assert_eq!(manager.siege_state(10), 0);
+        assert_eq!(manager.advance(starts_at), vec![SiegeEvent::Started(1)]);
+        assert_eq!(manager.siege_state(10), SIEGE_STATE_ATTACKER);
+        assert_eq!(manager.siege_state(30), 0);
+        assert!(manager.build_headquarters(1, 10, Position { x: 0, y: 0, z: 0 }));
+        assert!(!manager.build_headquarters(1, 10, Position { x: 0, y: 0, z: 0 }));
+        assert!(manager.advance(starts_at + TimeDelta::hours(1)).is_empty());
+        assert_eq!(
+            manager.advance(starts_at + SIEGE_LENGTH),
+            vec![SiegeEvent::Ended(1)]
+        );
+        let castle = manager.get(1).unwrap();
+        assert!(castle.clans.is_empty() && castle.headquarters.is_empty());
+        assert_eq!(manager.siege_state(10), 0);
+    }
+}
@@ -0,0 +1,105 @@
+use crate::controller::GameController;
+use crate::pl_client::PlayerClient;
+use chrono::{DateTime, Utc};
+use l2_core::game_objects::player::{last_daily_vitality_reset, last_weekly_vitality_reset};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{error, info};
+
+const TICK_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VitalityEvent {
+    /// The vitality items used are counted anew
+    DailyReset,
+    /// The vitality points are refilled
+    WeeklyReset,
+}
+
+/// Runs the vitality resets for the players online when they are due.
+///
+/// Offline players are reset lazily when they enter the world, so the manager only has to
+/// remember when it last looked.
+#[derive(Debug)]
+pub struct VitalityManager {
+    checked_at: Mutex<DateTime<Utc>>,
+}
+
+impl VitalityManager {
+    #[must_use]
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            checked_at: Mutex::new(now),
+        }
+    }
+
+    pub fn start(controller: Arc<GameController>) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(TICK_INTERVAL);
+            loop {
+                interval.tick().await;
+                Self::update(&controller);
+            }
+        });
+    }
+
+    /// Tells the players online to apply the resets due at the current game time.
+    pub fn update(controller: &GameController) -> Vec<VitalityEvent> {
+        let events = controller.vitality_manager.advance(controller.clock.now());
+        if events.is_empty() {
+            return events;
+        }
+        for char_id in controller.chars_in_game_ids() {
+            controller.run_on_char(
+                char_id,
+                Box::new(|pl: &mut PlayerClient| {
+                    Box::pin(async move {
+                        if let Err(e) = pl.refresh_vitality().await {
+                            error!("Vitality reset failed: {e}");
+                        }
+                        Ok(())
+                    })
+                }),
+            );
+        }
+        info!("Vitality events: {events:?}");
+        events
+    }
+
+    /// Resets that passed since the last call, the caller handles the returned events.
+    pub fn advance(&self, now: DateTime<Utc>) -> Vec<VitalityEvent> {
+        let mut checked_at = self.checked_at.lock().unwrap_or_else(|e| e.into_inner());
+        let mut events = Vec::new();
+        if last_daily_vitality_reset(now) > *checked_at {
+            events.push(VitalityEvent::DailyReset);
+        }
+        if last_weekly_vitality_reset(now) > *checked_at {
+            events.push(VitalityEvent::WeeklyReset);
+        }
+        *checked_at = now;
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeDelta, TimeZone};
+
+    #[test]
+    fn test_advance() {
+        // a tuesday
+        let now = Utc.with_ymd_and_hms(2025, 7, 22, 6, 0, 0).unwrap();
+        let manager = VitalityManager::new(now);
+        assert!(manager.advance(now + TimeDelta::minutes(10)).is_empty());
+        assert_eq!(
+            manager.advance(now + TimeDelta::hours(1)),
+            vec![VitalityEvent::DailyReset]
+        );
+        assert!(manager.advance(now + TimeDelta::hours(2)).is_empty());
+        assert_eq!(
+            manager.advance(now + TimeDelta::days(1) + TimeDelta::hours(1)),
+            vec![VitalityEvent::DailyReset, VitalityEvent::WeeklyReset]
+        );
+    }
+}
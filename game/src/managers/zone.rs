@@ -0,0 +1,91 @@
+use dashmap::DashSet;
+use l2_core::config::traits::ConfigDirLoader;
+use l2_core::data::zones::{ZoneTemplate, ZonesData};
+use l2_core::game_objects::zone::ZoneId;
+use std::collections::HashSet;
+
+/// Zones loaded from the data pack plus their runtime state.
+#[derive(Clone, Debug, Default)]
+pub struct ZoneManager {
+    pub data: ZonesData,
+    /// Zones that are active only temporarily (e.g. siege zones during a siege)
+    activated: DashSet<i32>,
+}
+
+impl ZoneManager {
+    #[must_use]
+    pub fn new(data: ZonesData) -> Self {
+        Self {
+            data,
+            activated: DashSet::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn load() -> Self {
+        Self::new(ZonesData::load())
+    }
+
+    pub fn set_active(&self, zone_id: i32, active: bool) {
+        if active {
+            self.activated.insert(zone_id);
+        } else {
+            self.activated.remove(&zone_id);
+        }
+    }
+
+    #[must_use]
+    pub fn is_active(&self, zone: &ZoneTemplate) -> bool {
+        zone.kind.active_by_default() || self.activated.contains(&zone.id)
+    }
+
+    /// Active zones containing the point.
+    pub fn zones_at(&self, x: i32, y: i32, z: i32) -> impl Iterator<Item = &ZoneTemplate> {
+        self.data
+            .zones_at(x, y, z)
+            .filter(|zone| self.is_active(zone))
+    }
+
+    /// Zone flags a creature gets at the point.
+    #[must_use]
+    pub fn flags_at(&self, x: i32, y: i32, z: i32) -> HashSet<ZoneId> {
+        self.zones_at(x, y, z)
+            .flat_map(|zone| zone.kind.flags().iter().copied())
+            .collect()
+    }
+
+    #[must_use]
+    pub fn is_inside_zone(&self, zone_id: ZoneId, x: i32, y: i32, z: i32) -> bool {
+        self.zones_at(x, y, z)
+            .any(|zone| zone.kind.flags().contains(&zone_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use l2_core::config::traits::LoadFileHandler;
+    use l2_core::data::zones::ZoneTemplateList;
+
+    #[test]
+    fn test_siege_zone_activation() {
+        let list: ZoneTemplateList = serde_yaml::from_str(
+            r"
+zones:
+  - { id: 1, name: town, kind: Town, shape: { type: cylinder, x: 0, y: 0, radius: 100, min_z: -10, max_z: 10 } }
+  - { id: 2, name: siege, kind: Siege, shape: { type: cylinder, x: 0, y: 0, radius: 500, min_z: -10, max_z: 10 } }
+",
+        )
+        .unwrap();
+        let mut data = ZonesData::default();
+        data.for_each(list);
+        let manager = ZoneManager::new(data);
+        assert_eq!(manager.flags_at(0, 0, 0), HashSet::from([ZoneId::Peace]));
+        assert!(!manager.is_inside_zone(ZoneId::Siege, 200, 0, 0));
+        manager.set_active(2, true);
+        assert!(manager.is_inside_zone(ZoneId::Siege, 200, 0, 0));
+        assert!(manager.flags_at(0, 0, 0).contains(&ZoneId::Pvp));
+        manager.set_active(2, false);
+        assert!(manager.flags_at(200, 0, 0).is_empty());
+    }
+}
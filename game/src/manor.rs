@@ -0,0 +1,699 @@
+//! Castle manor.
+//!
+//! The lord of a castle sets up at the chamberlain which seeds the castle sells and which crops
+//! it buys in the next manor period, and at which prices. Players buy the seeds at the manor
+//! manager of the castle, sow them on monsters and harvest the crops once the monsters are
+//! killed, then sell the crops back to the manor managers. The castle treasury pays for the
+//! crops in advance and earns the adena of the seeds. The daily cycle of the periods is run by
+//! the [`ManorManager`](crate::managers::ManorManager).
+//!
+//! The server spawns no monsters of its own yet, so whoever spawns them registers them with
+//! [`ManorManager::on_monster_spawned`](crate::managers::ManorManager::on_monster_spawned) to be
+//! targeted by the seeds and the harvester, and reports their death with
+//! [`ManorManager::on_monster_killed`](crate::managers::ManorManager::on_monster_killed).
+
+use crate::bypass::{DialogNpc, ManorCommand};
+use crate::managers::{CastleSiege, ManorMode, SownSeed};
+use crate::packets::to_client;
+use crate::packets::to_client::{SystemMessage, SystemMessageType};
+use crate::pl_client::PlayerClient;
+use l2_core::data::manor::{harvest_chance, price_range};
+use l2_core::game_objects::player::clan::ClanPrivilege;
+use l2_core::game_objects::player::inventory::Inventory;
+use rand::RngExt;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use tracing::warn;
+
+pub const SOWING: i32 = 2097;
+pub const HARVESTING: i32 = 2098;
+
+/// The monster a seed is sown on or harvested from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ManorTarget {
+    pub object_id: i32,
+    pub level: u8,
+}
+
+impl PlayerClient {
+    pub async fn handle_manor_command(
+        &mut self,
+        npc: DialogNpc,
+        command: ManorCommand,
+    ) -> anyhow::Result<()> {
+        let castle = self
+            .controller
+            .castles
+            .by_npc(npc.npc_id)
+            .and_then(|c| self.controller.siege_manager.get(c.id));
+        let Some(castle) = castle else {
+            warn!("Npc {} serves no castle", npc.npc_id);
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        };
+        if self.controller.manor_manager.mode() == ManorMode::Maintenance {
+            return self
+                .show_manor_refusal(npc, "The manor is under maintenance, come back later.")
+                .await;
+        }
+        match command {
+            ManorCommand::Info => self.show_manor_info(npc, &castle).await,
+            ManorCommand::Next => self.show_next_manor(npc, &castle).await,
+            ManorCommand::SetSeed {
+                seed_id,
+                amount,
+                price,
+            } => {
+                self.set_next_seed(npc, &castle, seed_id, amount, price)
+                    .await
+            }
+            ManorCommand::SetCrop {
+                crop_id,
+                amount,
+                price,
+            } => {
+                self.set_next_crop(npc, &castle, crop_id, amount, price)
+                    .await
+            }
+            ManorCommand::BuySeed { seed_id, count } => {
+                self.buy_seeds(npc, &castle, seed_id, count).await
+            }
+            ManorCommand::SellCrop { crop_id, count } => {
+                self.sell_crops(npc, &castle, crop_id, count).await
+            }
+        }
+    }
+
+    async fn show_manor_refusal(&mut self, npc: DialogNpc, reason: &str) -> anyhow::Result<()> {
+        self.show_html(Some(npc), "manor/refused.htm", &[("reason", reason)])
+            .await
+    }
+
+    fn item_name(&self, item_id: i32) -> String {
+        self.controller
+            .items_data
+            .get_template(item_id)
+            .map_or_else(|| item_id.to_string(), |t| t.name.clone())
+    }
+
+    async fn show_manor_info(
+        &mut self,
+        npc: DialogNpc,
+        castle: &CastleSiege,
+    ) -> anyhow::Result<()> {
+        let manor = self.controller.manor_manager.get(castle.template.id);
+        let object_id = npc.object_id;
+        let mut seeds = String::new();
+        for seed in &manor.production {
+            let id = seed.seed_id;
+            writeln!(
+                seeds,
+                "{}: {} left, {} adena<br><edit var=\"buy{id}\" width=60> \
+                 <a action=\"bypass -h npc_{object_id}_ManorBuySeed {id} $buy{id}\">Buy</a><br>",
+                self.item_name(id),
+                seed.amount,
+                seed.price
+            )?;
+        }
+        let mut crops = String::new();
+        for crop in &manor.procure {
+            let id = crop.crop_id;
+            writeln!(
+                crops,
+                "{}: {} wanted, {} adena<br><edit var=\"sell{id}\" width=60> \
+                 <a action=\"bypass -h npc_{object_id}_ManorSellCrop {id} $sell{id}\">Sell</a><br>",
+                self.item_name(id),
+                crop.amount,
+                crop.price
+            )?;
+        }
+        if seeds.is_empty() {
+            seeds.push_str("No seeds are sold in this period.<br>");
+        }
+        if crops.is_empty() {
+            crops.push_str("No crops are bought in this period.<br>");
+        }
+        self.show_html_markup(
+            Some(npc),
+            "manor/info.htm",
+            &[("castle", &castle.template.name)],
+            &[("seeds", &seeds), ("crops", &crops)],
+        )
+        .await
+    }
+
+    /// Reason the player may not set up the manor of the castle, if any.
+    fn manor_lord_refusal(&self, castle: &CastleSiege) -> anyhow::Result<Option<&'static str>> {
+        let player = self.try_get_selected_char()?;
+        Ok(
+            if castle.owner != Some(player.get_clan_id())
+                || !player.has_clan_privilege(ClanPrivilege::ManageManor)
+            {
+                Some("Only the castle lord or a member with the right may manage the manor.")
+            } else if self.controller.manor_manager.mode() != ManorMode::Modifiable {
+                Some("The next manor period can be set up only after the refresh in the evening.")
+            } else {
+                None
+            },
+        )
+    }
+
+    async fn show_next_manor(
+        &mut self,
+        npc: DialogNpc,
+        castle: &CastleSiege,
+    ) -> anyhow::Result<()> {
+        if let Some(reason) = self.manor_lord_refusal(castle)? {
+            return self.show_manor_refusal(npc, reason).await;
+        }
+        let castle_id = castle.template.id;
+        let manor = self.controller.manor_manager.get(castle_id);
+        let object_id = npc.object_id;
+        let mut seeds = String::new();
+        let mut crop_ids = BTreeSet::new();
+        for template in self.controller.manor.castle_seeds(castle_id) {
+            let id = template.seed_id;
+            let (amount, price) = manor
+                .next_production
+                .iter()
+                .find(|s| s.seed_id == id)
+                .map_or((0, 0), |s| (s.start_amount, s.price));
+            writeln!(
+                seeds,
+                "{}: {amount} for {price} adena, up to {}<br>\
+                 <edit var=\"sa{id}\" width=50> <edit var=\"sp{id}\" width=50> \
+                 <a action=\"bypass -h npc_{object_id}_ManorSetSeed {id} $sa{id} $sp{id}\">Set</a><br>",
+                self.item_name(id),
+                template.seed_limit
+            )?;
+            crop_ids.insert(template.crop_id);
+        }
+        let mut crops = String::new();
+        for id in crop_ids {
+            let limit = self
+                .controller
+                .manor
+                .seed_of_crop(castle_id, id)
+                .map_or(0, |s| s.crop_limit);
+            let (amount, price) = manor
+                .next_procure
+                .iter()
+                .find(|c| c.crop_id == id)
+                .map_or((0, 0), |c| (c.start_amount, c.price));
+            writeln!(
+                crops,
+                "{}: {amount} for {price} adena, up to {limit}<br>\
+                 <edit var=\"ca{id}\" width=50> <edit var=\"cp{id}\" width=50> \
+                 <a action=\"bypass -h npc_{object_id}_ManorSetCrop {id} $ca{id} $cp{id}\">Set</a><br>",
+                self.item_name(id)
+            )?;
+        }
+        let treasury = castle.treasury.to_string();
+        let cost = manor.next_procure_cost().to_string();
+        self.show_html_markup(
+            Some(npc),
+            "manor/next.htm",
+            &[
+                ("castle", &castle.template.name),
+                ("treasury", &treasury),
+                ("cost", &cost),
+            ],
+            &[("seeds", &seeds), ("crops", &crops)],
+        )
+        .await
+    }
+
+    fn manor_price_range(&self, item_id: i32) -> std::ops::RangeInclusive<i64> {
+        let reference = self
+            .controller
+            .items_data
+            .get_template(item_id)
+            .map_or(0, |t| t.price);
+        price_range(reference)
+    }
+
+    async fn set_next_seed(
+        &mut self,
+        npc: DialogNpc,
+        castle: &CastleSiege,
+        seed_id: i32,
+        amount: i32,
+        price: i32,
+    ) -> anyhow::Result<()> {
+        if let Some(reason) = self.manor_lord_refusal(castle)? {
+            return self.show_manor_refusal(npc, reason).await;
+        }
+        let castle_id = castle.template.id;
+        let Some(template) = self
+            .controller
+            .manor
+            .get_seed(seed_id)
+            .filter(|s| s.castle_id == castle_id)
+        else {
+            warn!("Seed {seed_id} is not sold at castle {castle_id}, possible cheating");
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        };
+        let (amount, price) = (i64::from(amount), i64::from(price));
+        let reason = if !(0..=template.seed_limit).contains(&amount) {
+            Some("The castle can't sell that many seeds.")
+        } else if amount > 0 && !self.manor_price_range(seed_id).contains(&price) {
+            Some("The price is out of the allowed range.")
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            return self.show_manor_refusal(npc, reason).await;
+        }
+        self.controller
+            .manor_manager
+            .set_next_seed(&self.db_pool, castle_id, seed_id, amount, price)
+            .await?;
+        self.show_next_manor(npc, castle).await
+    }
+
+    async fn set_next_crop(
+        &mut self,
+        npc: DialogNpc,
+        castle: &CastleSiege,
+        crop_id: i32,
+        amount: i32,
+        price: i32,
+    ) -> anyhow::Result<()> {
+        if let Some(reason) = self.manor_lord_refusal(castle)? {
+            return self.show_manor_refusal(npc, reason).await;
+        }
+        let castle_id = castle.template.id;
+        let Some(template) = self.controller.manor.seed_of_crop(castle_id, crop_id) else {
+            warn!("Crop {crop_id} is not bought at castle {castle_id}, possible cheating");
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        };
+        let (amount, price) = (i64::from(amount), i64::from(price));
+        let mut manor = self.controller.manor_manager.get(castle_id);
+        manor.next_procure.retain(|c| c.crop_id != crop_id);
+        let other_cost = manor.next_procure_cost();
+        let reason = if !(0..=template.crop_limit).contains(&amount) {
+            Some("The castle can't buy that many crops.")
+        } else if amount > 0 && !self.manor_price_range(crop_id).contains(&price) {
+            Some("The price is out of the allowed range.")
+        } else if other_cost.saturating_add(amount.saturating_mul(price)) > castle.treasury {
+            Some("The castle treasury can't pay for that many crops.")
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            return self.show_manor_refusal(npc, reason).await;
+        }
+        self.controller
+            .manor_manager
+            .set_next_crop(&self.db_pool, castle_id, crop_id, amount, price)
+            .await?;
+        self.show_next_manor(npc, castle).await
+    }
+
+    async fn buy_seeds(
+        &mut self,
+        npc: DialogNpc,
+        castle: &CastleSiege,
+        seed_id: i32,
+        count: i32,
+    ) -> anyhow::Result<()> {
+        let castle_id = castle.template.id;
+        let manor = self.controller.manor_manager.get(castle_id);
+        let count = i64::from(count);
+        let Some(seed) = manor.seed(seed_id).filter(|_| count > 0) else {
+            warn!("Seed {seed_id} x{count} is not sold at castle {castle_id}, possible cheating");
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        };
+        let cost = seed.price.saturating_mul(count);
+        let inventory = &self.try_get_selected_char()?.inventory;
+        let adena = inventory.get_item_by_item_id(Inventory::ADENA_ID);
+        let reason = if seed.amount < count {
+            Some("The castle has not that many seeds left.")
+        } else if u64::try_from(cost)
+            .ok()
+            .is_none_or(|cost| cost > inventory.get_adena())
+        {
+            Some("You don't have enough adena.")
+        } else if inventory.get_item_by_item_id(seed_id).is_none()
+            && inventory.items.len() >= usize::from(inventory.get_limit())
+        {
+            Some("Your inventory is full.")
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            return self.show_manor_refusal(npc, reason).await;
+        }
+        let take: Vec<(i32, i64)> = adena
+            .map(|a| (a.object_id.value(), cost))
+            .into_iter()
+            .collect();
+        let manager = self.controller.manor_manager.clone();
+        if manager
+            .take_seeds(&self.db_pool, castle_id, seed_id, count)
+            .await?
+            .is_none()
+        {
+            return self
+                .show_manor_refusal(npc, "The castle has not that many seeds left.")
+                .await;
+        }
+        if let Err(e) = self.exchange_items(&take, &[(seed_id, count, 0)]).await {
+            manager
+                .return_seeds(&self.db_pool, castle_id, seed_id, count)
+                .await?;
+            return Err(e);
+        }
+        self.controller
+            .siege_manager
+            .add_to_treasury(&self.db_pool, castle_id, cost)
+            .await?;
+        self.show_manor_info(npc, castle).await
+    }
+
+    async fn sell_crops(
+        &mut self,
+        npc: DialogNpc,
+        castle: &CastleSiege,
+        crop_id: i32,
+        count: i32,
+    ) -> anyhow::Result<()> {
+        let castle_id = castle.template.id;
+        let count = i64::from(count);
+        let crops = self
+            .try_get_selected_char()?
+            .inventory
+            .get_item_by_item_id(crop_id)
+            .filter(|i| count > 0 && i.item_model.count >= count)
+            .map(|i| i.object_id.value());
+        let Some(crops) = crops else {
+            return self
+                .show_manor_refusal(npc, "You don't have that many crops.")
+                .await;
+        };
+        let manager = self.controller.manor_manager.clone();
+        let Some(price) = manager
+            .procure_crops(&self.db_pool, castle_id, crop_id, count)
+            .await?
+        else {
+            return self
+                .show_manor_refusal(npc, "The castle doesn't buy that many of these crops.")
+                .await;
+        };
+        let income = price.saturating_mul(count);
+        if let Err(e) = self
+            .exchange_items(&[(crops, count)], &[(Inventory::ADENA_ID, income, 0)])
+            .await
+        {
+            manager
+                .return_crops(&self.db_pool, castle_id, crop_id, count)
+                .await?;
+            return Err(e);
+        }
+        self.show_manor_info(npc, castle).await
+    }
+
+    /// Sowing (with the used seed) and harvesting, on the selected monster.
+    pub async fn use_manor_skill(
+        &mut self,
+        skill_id: i32,
+        seed_id: Option<i32>,
+    ) -> anyhow::Result<()> {
+        let target = self
+            .selected_monster
+            .and_then(|id| self.controller.manor_manager.monster(id));
+        let Some(target) = target else {
+            self.send_packet(SystemMessage::new(SystemMessageType::InvalidTarget)?)
+                .await?;
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        };
+        match (skill_id, seed_id) {
+            (SOWING, Some(seed_id)) => self.sow_seed(target, seed_id).await,
+            (HARVESTING, _) => self.harvest(target).await,
+            // sowing is cast by using a seed
+            _ => self.send_packet(to_client::ActionFailed::normal()?).await,
+        }
+    }
+
+    /// Sows one of the seeds of the player on the monster.
+    pub async fn sow_seed(&mut self, target: ManorTarget, seed_id: i32) -> anyhow::Result<()> {
+        let Some(template) = self.controller.manor.get_seed(seed_id).cloned() else {
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        };
+        let manager = self.controller.manor_manager.clone();
+        let player = self.try_get_selected_char()?;
+        let (char_id, level) = (player.char_model.id, player.char_model.level);
+        let Some(seed) = player.inventory.get_item_by_item_id(seed_id) else {
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        };
+        let seed = seed.object_id.value();
+        if manager.sown_seed(target.object_id).is_some() {
+            return self
+                .send_packet(SystemMessage::text("A seed has been sown on it already.")?)
+                .await;
+        }
+        self.exchange_items(&[(seed, 1)], &[]).await?;
+        let chance = template.sow_chance(target.level, level);
+        let sown = rand::rng().random_range(0..100) < chance
+            && manager.sow(
+                target.object_id,
+                SownSeed {
+                    sower_id: char_id,
+                    crop_id: template.crop_id,
+                    count: template.harvest_count(target.level),
+                    ripe: false,
+                },
+            );
+        let text = if sown {
+            "The seed was successfully sown."
+        } else {
+            "The seed was not sown."
+        };
+        self.send_packet(SystemMessage::text(text)?).await
+    }
+
+    /// Harvests the crops of the seed the player sowed on the killed monster.
+    pub async fn harvest(&mut self, target: ManorTarget) -> anyhow::Result<()> {
+        let manager = self.controller.manor_manager.clone();
+        let player = self.try_get_selected_char()?;
+        let (char_id, level) = (player.char_model.id, player.char_model.level);
+        let Some(seed) = manager.harvest(target.object_id, char_id) else {
+            return self
+                .send_packet(SystemMessage::text("There is nothing for you to harvest.")?)
+                .await;
+        };
+        if rand::rng().random_range(0..100) >= harvest_chance(target.level, level) {
+            return self
+                .send_packet(SystemMessage::text("The harvest has failed.")?)
+                .await;
+        }
+        self.exchange_items(&[], &[(seed.crop_id, seed.count, 0)])
+            .await?;
+        let text = format!(
+            "You harvested {} {}.",
+            seed.count,
+            self.item_name(seed.crop_id)
+        );
+        self.send_packet(SystemMessage::text(&text)?).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bypass::NpcCommand;
+    use crate::managers::{ManorEvent, ManorManager};
+    use crate::pl_client::GetCharInfo;
+    use crate::test_utils::test::{get_controller, npc_command, on_actor, spawn_player};
+    use chrono::TimeDelta;
+    use entities::entities::{castle, castle_manor_procure, castle_manor_production, clan_ally};
+    use entities::test_factories::factories::clan_factory;
+    use l2_core::game_objects::player::Player;
+    use test_utils::utils::get_test_db;
+
+    fn count(player: &Player, item_id: i32) -> i64 {
+        player
+            .inventory
+            .get_items_by_item_id(item_id)
+            .map(|i| i.item_model.count)
+            .sum()
+    }
+
+    #[tokio::test]
+    async fn test_manor_period() {
+        let db_pool = get_test_db().await;
+        let (char_id, actor, _conn) = spawn_player(&db_pool, 10).await;
+        let clan = clan_factory(&db_pool, |mut c| {
+            c.leader_id = char_id;
+            c
+        })
+        .await;
+        let member_clan: clan_ally::Model = clan.clone();
+        on_actor(&actor, move |pl| {
+            Box::pin(async move {
+                let player = pl.try_get_selected_char_mut()?;
+                player.char_model.clan_id = Some(member_clan.id);
+                player.clan = Some(member_clan);
+                pl.exchange_items(&[], &[(Inventory::ADENA_ID, 10_000, 0)])
+                    .await
+            })
+        })
+        .await;
+        let controller = get_controller(&actor).await;
+        // move to the evening, after the maintenance
+        let now = controller.clock.now();
+        let mut evening = now.date_naive().and_hms_opt(23, 0, 0).unwrap().and_utc();
+        if evening < now {
+            evening += TimeDelta::days(1);
+        }
+        controller.clock.advance(evening - now);
+        ManorManager::update(&controller, &db_pool).await.unwrap();
+        assert_eq!(controller.manor_manager.mode(), ManorMode::Modifiable);
+
+        // not the lord yet
+        let set_seed = ManorCommand::SetSeed {
+            seed_id: 5016,
+            amount: 100,
+            price: 120,
+        };
+        npc_command(&actor, 35100, NpcCommand::Manor(set_seed)).await;
+        assert!(controller.manor_manager.get(1).next_production.is_empty());
+
+        controller
+            .siege_manager
+            .engrave(&db_pool, 1, clan.id)
+            .await
+            .unwrap();
+        controller
+            .siege_manager
+            .add_to_treasury(&db_pool, 1, 5_000)
+            .await
+            .unwrap();
+        npc_command(&actor, 35100, NpcCommand::Manor(set_seed)).await;
+        // too expensive, then more than the treasury can pay
+        let too_expensive = ManorCommand::SetSeed {
+            seed_id: 5017,
+            amount: 100,
+            price: 1_000,
+        };
+        npc_command(&actor, 35100, NpcCommand::Manor(too_expensive)).await;
+        let set_crop = |amount| ManorCommand::SetCrop {
+            crop_id: 5073,
+            amount,
+            price: 400,
+        };
+        npc_command(&actor, 35100, NpcCommand::Manor(set_crop(20))).await;
+        npc_command(&actor, 35100, NpcCommand::Manor(set_crop(10))).await;
+        let manor = controller.manor_manager.get(1);
+        assert_eq!(manor.next_production.len(), 1);
+        assert_eq!(manor.next_procure_cost(), 4_000);
+        assert_eq!(
+            castle_manor_production::Model::load_all(&db_pool)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        // approved in the morning, the crops are paid in advance
+        controller.clock.advance(TimeDelta::hours(8));
+        assert_eq!(
+            ManorManager::update(&controller, &db_pool).await.unwrap(),
+            vec![ManorEvent::Approved]
+        );
+        assert_eq!(controller.siege_manager.get(1).unwrap().treasury, 1_000);
+        npc_command(&actor, 35100, NpcCommand::Manor(set_crop(0))).await;
+        assert_eq!(controller.manor_manager.get(1).next_procure.len(), 1);
+
+        // the next period takes over in the evening
+        controller.clock.advance(TimeDelta::hours(13));
+        assert_eq!(
+            ManorManager::update(&controller, &db_pool).await.unwrap(),
+            vec![ManorEvent::Refreshed]
+        );
+        let buy = ManorCommand::BuySeed {
+            seed_id: 5016,
+            count: 5,
+        };
+        // no trading during the maintenance
+        npc_command(&actor, 35103, NpcCommand::Manor(buy)).await;
+        assert_eq!(count(&actor.ask(GetCharInfo).await.unwrap(), 5016), 0);
+        controller.clock.advance(TimeDelta::minutes(10));
+        ManorManager::update(&controller, &db_pool).await.unwrap();
+        npc_command(&actor, 35103, NpcCommand::Manor(buy)).await;
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert_eq!(count(&player, 5016), 5);
+        assert_eq!(count(&player, Inventory::ADENA_ID), 10_000 - 600);
+        assert_eq!(controller.siege_manager.get(1).unwrap().treasury, 1_600);
+        assert_eq!(
+            controller.manor_manager.get(1).seed(5016).unwrap().amount,
+            95
+        );
+
+        // sow until a seed takes root, then harvest the killed monster
+        let mut monster = ManorTarget {
+            object_id: 2_000_000,
+            level: 10,
+        };
+        for object_id in 2_000_000..2_000_005 {
+            monster.object_id = object_id;
+            controller.manor_manager.on_monster_spawned(monster);
+            on_actor(&actor, move |pl| {
+                pl.selected_monster = Some(object_id);
+                Box::pin(pl.use_manor_skill(SOWING, Some(5016)))
+            })
+            .await;
+            if controller.manor_manager.sown_seed(object_id).is_some() {
+                break;
+            }
+        }
+        let sown = controller
+            .manor_manager
+            .sown_seed(monster.object_id)
+            .unwrap();
+        assert_eq!(sown.crop_id, 5073);
+        // the monster is still alive
+        on_actor(&actor, |pl| Box::pin(pl.use_manor_skill(HARVESTING, None))).await;
+        assert!(
+            controller
+                .manor_manager
+                .sown_seed(monster.object_id)
+                .is_some()
+        );
+        controller
+            .manor_manager
+            .on_monster_killed(monster.object_id);
+        on_actor(&actor, |pl| Box::pin(pl.use_manor_skill(HARVESTING, None))).await;
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert_eq!(count(&player, 5073), 1);
+        assert!(count(&player, 5016) < 5);
+
+        let sell = ManorCommand::SellCrop {
+            crop_id: 5073,
+            count: 1,
+        };
+        npc_command(&actor, 35103, NpcCommand::Manor(sell)).await;
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert_eq!(count(&player, 5073), 0);
+        assert_eq!(count(&player, Inventory::ADENA_ID), 10_000 - 600 + 400);
+        let procure = castle_manor_procure::Model::load_all(&db_pool)
+            .await
+            .unwrap();
+        let current = procure.iter().find(|c| !c.next_period).unwrap();
+        assert_eq!(current.amount, 9);
+
+        // the treasury can't pay the next period, the crops nobody sold are paid back
+        controller.clock.advance(TimeDelta::hours(21));
+        assert_eq!(
+            ManorManager::update(&controller, &db_pool).await.unwrap(),
+            vec![ManorEvent::Approved]
+        );
+        controller.clock.advance(TimeDelta::hours(3));
+        assert_eq!(
+            ManorManager::update(&controller, &db_pool).await.unwrap(),
+            vec![ManorEvent::Refreshed]
+        );
+        let owned = castle::Model::load_all(&db_pool).await.unwrap();
+        assert_eq!(owned[0].treasury, 1_600 + 9 * 400);
+        assert!(controller.manor_manager.get(1).procure.is_empty());
+    }
+}
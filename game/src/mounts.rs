@@ -0,0 +1,246 @@
+//! Riding pets.
+//!
+//! Striders, wyverns and great wolves carry their owner: the Ride action mounts the pet which
+//! is out next to the owner. The pet goes back into its collar while it is ridden and the owner
+//! moves at the speeds of the mount. Riding once more gets the owner down, the pet stays in the
+//! collar until it is called out again.
+//!
+//! Nothing rides onto the battlefield of a siege, a rider who gets there is put down. A wyvern
+//! flies: it can't take off in a town, its height is not bound to the geodata and it can't land
+//! in a no-landing zone.
+
+use crate::movement::calculate_distance;
+use crate::packets::to_client::{ActionFailed, CharInfo, Ride, SystemMessage, UserInfo};
+use crate::pl_client::PlayerClient;
+use l2_core::game_objects::mount::Mount;
+use l2_core::game_objects::player::user_info::UserInfoType;
+use l2_core::game_objects::zone::ZoneId;
+
+/// How close to the owner the pet has to be to be ridden
+const RIDE_DISTANCE: f64 = 200.0;
+
+impl PlayerClient {
+    async fn refuse_ride(&mut self, reason: &str) -> anyhow::Result<()> {
+        self.send_packet(SystemMessage::text(reason)?).await?;
+        self.send_packet(ActionFailed::normal()?).await
+    }
+
+    /// The Ride action: gets on the pet or down from the mount.
+    pub async fn use_ride_action(&mut self) -> anyhow::Result<()> {
+        if self.try_get_selected_char()?.is_mounted() {
+            self.dismount().await
+        } else {
+            self.mount_pet().await
+        }
+    }
+
+    /// The owner gets on the pet, the pet goes back into its collar.
+    pub async fn mount_pet(&mut self) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let Some(summon) = player.summon.as_ref().filter(|s| s.is_pet()) else {
+            return self.refuse_ride("You have no pet to ride.").await;
+        };
+        let Some(template) = self
+            .controller
+            .summons
+            .get(summon.npc_id)
+            .filter(|t| t.mount.is_some())
+        else {
+            return self.refuse_ride("This pet can't carry you.").await;
+        };
+        let Some(mount) = Mount::of(summon, template) else {
+            return self
+                .refuse_ride("Your pet is too young to carry you.")
+                .await;
+        };
+        let pet_location = summon.get_location();
+        let distance = calculate_distance(
+            player.get_x(),
+            player.get_y(),
+            player.get_z(),
+            pet_location.x,
+            pet_location.y,
+            pet_location.z,
+        );
+        let refusal = if player.is_dead() || summon.current_hp <= 0.0 {
+            Some("You can't ride now.")
+        } else if player.is_fishing() {
+            Some("You can't ride while fishing.")
+        } else if player.cursed_weapon.is_some() {
+            Some("You can't ride while holding a cursed weapon.")
+        } else if player.is_in_olympiad_mode() {
+            Some("You can't ride during the Olympiad.")
+        } else if player.is_in_combat() || summon.is_in_combat() {
+            Some("You can't ride during a battle.")
+        } else if summon.is_hungry(template) {
+            Some("Your pet is too hungry to carry you.")
+        } else if player.is_inside_zone(ZoneId::Siege) {
+            Some("You can't ride on a battlefield.")
+        } else if mount.is_flying() && player.is_inside_zone(ZoneId::Peace) {
+            Some("A wyvern can't take off in a town.")
+        } else if distance.is_none_or(|d| d > RIDE_DISTANCE) {
+            Some("Your pet is too far away.")
+        } else {
+            None
+        };
+        if let Some(reason) = refusal {
+            return self.refuse_ride(reason).await;
+        }
+        if self.is_casting() {
+            return self.send_packet(ActionFailed::normal()?).await;
+        }
+        self.stop_movement();
+        self.unsummon().await?;
+        self.try_get_selected_char_mut()?.mount = Some(mount);
+        self.send_mount_state().await
+    }
+
+    /// The owner gets down from the mount, a flying mount can't land in a no-landing zone.
+    pub async fn dismount(&mut self) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        if !player.is_mounted() {
+            return Ok(());
+        }
+        if player.is_flying_mounted() && player.is_inside_zone(ZoneId::NoLanding) {
+            return self.refuse_ride("You can't land here.").await;
+        }
+        self.put_down_from_mount().await
+    }
+
+    /// Gets the rider down without asking, a flying rider lands on the ground below.
+    async fn put_down_from_mount(&mut self) -> anyhow::Result<()> {
+        self.stop_movement();
+        let controller = self.controller.clone();
+        let player = self.try_get_selected_char_mut()?;
+        let was_flying = player.is_flying_mounted();
+        player.mount = None;
+        if was_flying {
+            let (x, y, z) = (player.get_x(), player.get_y(), player.get_z());
+            player.set_location(x, y, controller.geo_engine.get_nearest_z(x, y, z))?;
+        }
+        self.send_mount_state().await
+    }
+
+    /// Riders who get onto a battlefield are put down.
+    pub async fn check_mount_zones(&mut self, entered: &[ZoneId]) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        if player.is_mounted() && !player.is_flying() && entered.contains(&ZoneId::Siege) {
+            self.send_packet(SystemMessage::text("You can't ride on a battlefield.")?)
+                .await?;
+            self.put_down_from_mount().await?;
+        }
+        Ok(())
+    }
+
+    /// Everyone around sees the rider get on or off, with the speeds and the relation of now.
+    async fn send_mount_state(&mut self) -> anyhow::Result<()> {
+        let controller = self.controller.clone();
+        let user_name = self.try_get_user()?.username.clone();
+        let player = self.try_get_selected_char()?.clone();
+        controller.broadcast_packet(Ride::new(&player)?);
+        controller.broadcast_packet_with_filter(
+            CharInfo::new(&player, &controller.get_cfg())?,
+            Some(Box::new(move |acc, _| !acc.eq(&user_name))),
+        );
+        self.change_movement_speed(player.get_move_speed());
+        self.send_packet(UserInfo::new(&player, UserInfoType::all(), &controller).await?)
+            .await?;
+        if let Some(actor) = controller.get_player_by_object_id(player.get_object_id()) {
+            controller.broadcast_relations(player, actor);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::test::{on_actor, spawn_player};
+    use entities::dao::item::LocType;
+    use entities::test_factories::factories::item_factory;
+    use l2_core::game_objects::summon::Summon;
+    use std::collections::HashSet;
+    use test_utils::utils::get_test_db;
+
+    const STRIDER: i32 = 12526;
+    const WYVERN: i32 = 12621;
+
+    #[tokio::test]
+    async fn test_ride() {
+        let db_pool = get_test_db().await;
+        let (char_id, actor, _conn) = spawn_player(&db_pool, 40).await;
+        let mut collars = vec![];
+        for item_id in [4422, 8663] {
+            let collar = item_factory(&db_pool, |mut it| {
+                it.owner = char_id;
+                it.item_id = item_id;
+                it.count = 1;
+                it.loc = LocType::Inventory;
+                it
+            })
+            .await;
+            collars.push(collar.id);
+        }
+        let owner = actor.clone();
+        on_actor(&actor, move |pl| {
+            Box::pin(async move {
+                let call_pet = |pl: &PlayerClient, npc_id: i32, collar_id: i32| {
+                    let template = pl.controller.summons.get(npc_id).unwrap();
+                    let model = Summon::new_pet_model(template, collar_id);
+                    let location = *pl.try_get_selected_char().unwrap().get_location();
+                    Summon::pet(template, &model, vec![], location)
+                };
+                pl.try_get_selected_char_mut()?.char_model.cur_hp = 100.0;
+                pl.use_ride_action().await?;
+                assert!(!pl.try_get_selected_char()?.is_mounted());
+
+                let strider = call_pet(pl, STRIDER, collars[0]);
+                pl.put_summon(owner.clone(), strider).await?;
+                pl.try_get_selected_char_mut()?
+                    .set_zones(HashSet::from([ZoneId::Siege]));
+                pl.use_ride_action().await?;
+                assert!(pl.try_get_selected_char()?.has_summon());
+
+                pl.try_get_selected_char_mut()?.set_zones(HashSet::new());
+                pl.use_ride_action().await?;
+                let player = pl.try_get_selected_char()?;
+                assert!(!player.has_summon());
+                assert!(player.is_mounted());
+                assert!(!player.is_flying());
+                assert_eq!(player.get_mount_npc_id(), STRIDER);
+                let mount = pl.controller.summons.get(STRIDER).unwrap().mount.clone();
+                assert_eq!(player.get_move_speed(), mount.unwrap().run_speed);
+
+                // the strider is not let onto the battlefield
+                pl.check_mount_zones(&[ZoneId::Siege]).await?;
+                assert!(!pl.try_get_selected_char()?.is_mounted());
+
+                let wyvern = call_pet(pl, WYVERN, collars[1]);
+                pl.put_summon(owner.clone(), wyvern).await?;
+                pl.try_get_selected_char_mut()?
+                    .set_zones(HashSet::from([ZoneId::Peace]));
+                pl.use_ride_action().await?;
+                assert!(!pl.try_get_selected_char()?.is_mounted());
+                pl.try_get_selected_char_mut()?.set_zones(HashSet::new());
+                pl.use_ride_action().await?;
+                let player = pl.try_get_selected_char()?;
+                assert!(player.is_flying_mounted());
+                assert_eq!(player.get_mount_type(), 2);
+
+                // it flies over the battlefield, but can't land where landing is forbidden
+                pl.check_mount_zones(&[ZoneId::Siege]).await?;
+                pl.try_get_selected_char_mut()?
+                    .set_zones(HashSet::from([ZoneId::NoLanding]));
+                pl.use_ride_action().await?;
+                assert!(pl.try_get_selected_char()?.is_flying_mounted());
+                pl.try_get_selected_char_mut()?.set_zones(HashSet::new());
+                pl.use_ride_action().await?;
+                let player = pl.try_get_selected_char()?;
+                assert!(!player.is_mounted());
+                assert!(!player.has_summon());
+                Ok(())
+            })
+        })
+        .await;
+    }
+}
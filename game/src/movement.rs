@@ -157,6 +157,17 @@ impl MovementState {
         (current_x, current_y, current_z)
     }
 
+    /// Continue the movement from the current position with another speed
+    /// (e.g. when the player enters or leaves water)
+    pub fn change_speed(&mut self, speed: u16) {
+        let (x, y, z) = self.calculate_current_position();
+        self.source_x = x;
+        self.source_y = y;
+        self.source_z = z;
+        self.start_time = Instant::now();
+        self.speed = f64::from(speed);
+    }
+
     /// Check if the player has arrived at the destination
     pub fn has_arrived(&self) -> bool {
         let elapsed = self.start_time.elapsed().as_secs_f64();
@@ -229,6 +240,15 @@ mod tests {
         assert_eq!(z, 0);
     }
 
+    #[test]
+    fn test_change_speed_keeps_destination() {
+        let mut state = MovementState::new(0, 0, 0, 1000, 0, 0, 100);
+        state.change_speed(50);
+        assert!((state.speed - 50.0).abs() < f64::EPSILON);
+        assert_eq!((state.dest_x, state.dest_y, state.dest_z), (1000, 0, 0));
+        assert!((0..10).contains(&state.source_x));
+    }
+
     #[test]
     fn test_has_arrived_immediately() {
         let state = MovementState::new(0, 0, 0, 0, 0, 0, 100);
@@ -0,0 +1,423 @@
+//! Grand Olympiad.
+//!
+//! Nobles register at the olympiad manager npc for classed games (opponents of the same class)
+//! or non-classed games during the competition hours. The
+//! [`OlympiadManager`](crate::managers::OlympiadManager) pairs them into the free stadiums:
+//! both fighters are moved there without their effects and with full HP, MP and CP, and may
+//! attack each other once the countdown is over. The loser gives a part of the noble points
+//! to the winner. Other players may watch a running game from the stadium stands.
+//!
+//! When the monthly period ends, the noble with the most points of each class becomes a
+//! [hero](crate::heroes).
+
+use crate::bypass::{DialogNpc, OlympiadCommand};
+use crate::managers::{CompetitionType, START_POINTS, is_competition_time};
+use crate::packets::to_client::{AbnormalStatusUpdate, UserInfo};
+use crate::pl_client::PlayerClient;
+use entities::entities::olympiad_noble;
+use l2_core::data::castles::Position;
+use l2_core::game_objects::player::user_info::UserInfoType;
+use l2_core::game_objects::player::{OlympiadRole, OlympiadState};
+use std::fmt::Write;
+
+impl PlayerClient {
+    pub async fn handle_olympiad_command(
+        &mut self,
+        npc: DialogNpc,
+        command: OlympiadCommand,
+    ) -> anyhow::Result<()> {
+        match command {
+            OlympiadCommand::Info => self.show_olympiad_info(npc).await,
+            OlympiadCommand::RegisterClassed => {
+                self.register_for_olympiad(npc, CompetitionType::Classed)
+                    .await
+            }
+            OlympiadCommand::RegisterNonClassed => {
+                self.register_for_olympiad(npc, CompetitionType::NonClassed)
+                    .await
+            }
+            OlympiadCommand::Unregister => self.unregister_from_olympiad(npc).await,
+            OlympiadCommand::Games => self.show_olympiad_games(npc).await,
+            OlympiadCommand::Watch(stadium_id) => self.watch_olympiad_game(npc, stadium_id).await,
+        }
+    }
+
+    async fn show_olympiad_refusal(&mut self, npc: DialogNpc, reason: &str) -> anyhow::Result<()> {
+        self.show_html(Some(npc), "olympiad/refused.htm", &[("reason", reason)])
+            .await
+    }
+
+    async fn show_olympiad_info(&mut self, npc: DialogNpc) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        if !player.is_noble() {
+            return self
+                .show_olympiad_refusal(npc, "Only nobles may take part in the Olympiad.")
+                .await;
+        }
+        let manager = &self.controller.olympiad_manager;
+        // nobles get their record with the first registration
+        let noble = manager
+            .noble(player.char_model.id)
+            .unwrap_or(olympiad_noble::Model {
+                points: START_POINTS,
+                ..Default::default()
+            });
+        let period = manager.period().to_string();
+        let ends_at = manager
+            .period_ends_at()
+            .format("%Y-%m-%d %H:%M UTC")
+            .to_string();
+        let registration = match manager.registration_of(player.char_model.id) {
+            Some(CompetitionType::Classed) => "You are registered for a classed game.",
+            Some(CompetitionType::NonClassed) => "You are registered for a non-classed game.",
+            None => "You are not registered.",
+        };
+        self.show_html(
+            Some(npc),
+            "olympiad/info.htm",
+            &[
+                ("period", &period),
+                ("ends", &ends_at),
+                ("points", &noble.points.to_string()),
+                ("matches", &noble.matches.to_string()),
+                ("wins", &noble.wins.to_string()),
+                ("losses", &noble.losses.to_string()),
+                ("draws", &noble.draws.to_string()),
+                ("registration", registration),
+            ],
+        )
+        .await
+    }
+
+    fn olympiad_registration_refusal(&self) -> anyhow::Result<Option<&'static str>> {
+        let player = self.try_get_selected_char()?;
+        let manager = &self.controller.olympiad_manager;
+        let char_id = player.char_model.id;
+        Ok(if !player.is_noble() {
+            Some("Only nobles may take part in the Olympiad.")
+        } else if player.get_class_index() != 0 {
+            Some("You may take part in the Olympiad with your base class only.")
+        } else if !is_competition_time(self.controller.clock.now()) {
+            Some("The Olympiad games are held from 18:00 till midnight only.")
+        } else if manager.registration_of(char_id).is_some() {
+            Some("You are registered already.")
+        } else if player.olympiad.is_some() || manager.game_of(char_id).is_some() {
+            Some("You are taking part in a game already.")
+        } else if manager.noble(char_id).is_some_and(|n| n.points <= 0) {
+            Some("You have no Olympiad points left in this period.")
+        } else {
+            None
+        })
+    }
+
+    async fn register_for_olympiad(
+        &mut self,
+        npc: DialogNpc,
+        kind: CompetitionType,
+    ) -> anyhow::Result<()> {
+        if let Some(reason) = self.olympiad_registration_refusal()? {
+            return self.show_olympiad_refusal(npc, reason).await;
+        }
+        let player = self.try_get_selected_char()?;
+        let (char_id, class_id) = (player.char_model.id, player.char_model.class_id);
+        let manager = self.controller.olympiad_manager.clone();
+        manager
+            .noble_or_create(&self.db_pool, char_id, class_id)
+            .await?;
+        manager.register(char_id, class_id, kind);
+        let kind = match kind {
+            CompetitionType::Classed => "classed",
+            CompetitionType::NonClassed => "non-classed",
+        };
+        self.show_html(Some(npc), "olympiad/registered.htm", &[("kind", kind)])
+            .await
+    }
+
+    async fn unregister_from_olympiad(&mut self, npc: DialogNpc) -> anyhow::Result<()> {
+        let char_id = self.try_get_selected_char()?.char_model.id;
+        if !self.controller.olympiad_manager.unregister(char_id) {
+            return self
+                .show_olympiad_refusal(npc, "You are not registered.")
+                .await;
+        }
+        self.show_html(Some(npc), "olympiad/unregistered.htm", &[])
+            .await
+    }
+
+    async fn show_olympiad_games(&mut self, npc: DialogNpc) -> anyhow::Result<()> {
+        let mut games = String::new();
+        for game in self.controller.olympiad_manager.games() {
+            let [first, second] = game.fighters.map(|char_id| {
+                self.controller
+                    .get_char_in_game(char_id)
+                    .map_or_else(|| "-".to_string(), |c| c.name)
+            });
+            writeln!(
+                games,
+                "<a action=\"bypass -h npc_{}_OlympiadWatch {}\">Stadium {}: {first} vs {second}</a><br>",
+                npc.object_id, game.stadium_id, game.stadium_id
+            )?;
+        }
+        if games.is_empty() {
+            games.push_str("There are no games at the moment.<br>");
+        }
+        self.show_html_markup(Some(npc), "olympiad/games.htm", &[], &[("games", &games)])
+            .await
+    }
+
+    async fn watch_olympiad_game(&mut self, npc: DialogNpc, stadium_id: i32) -> anyhow::Result<()> {
+        let manager = self.controller.olympiad_manager.clone();
+        let player = self.try_get_selected_char()?;
+        let char_id = player.char_model.id;
+        if player.olympiad.is_some() || manager.registration_of(char_id).is_some() {
+            return self
+                .show_olympiad_refusal(
+                    npc,
+                    "You can't watch a game while taking part in the Olympiad.",
+                )
+                .await;
+        }
+        let Some(stadium) = manager.stadium(stadium_id).cloned() else {
+            return self
+                .show_olympiad_refusal(npc, "There is no such stadium.")
+                .await;
+        };
+        if !manager.add_spectator(stadium_id, char_id) {
+            return self.show_olympiad_refusal(npc, "The game is over.").await;
+        }
+        let player = self.try_get_selected_char_mut()?;
+        player.olympiad = Some(OlympiadState {
+            game_id: stadium_id,
+            role: OlympiadRole::Spectator,
+            return_to: *player.get_location(),
+            saved_effects: vec![],
+        });
+        let at = stadium.spectator;
+        self.teleport_to(at.x, at.y, at.z).await
+    }
+
+    /// Moves the fighter to the stadium, without effects and with full HP, MP and CP.
+    /// The effects are given back after the game.
+    pub async fn enter_olympiad_game(
+        &mut self,
+        game_id: i32,
+        side: u8,
+        spawn: Position,
+    ) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char_mut()?;
+        let saved_effects = player.reset_for_olympiad();
+        player.olympiad = Some(OlympiadState {
+            game_id,
+            role: OlympiadRole::Fighter {
+                side,
+                fighting: false,
+            },
+            return_to: *player.get_location(),
+            saved_effects,
+        });
+        self.send_olympiad_status().await?;
+        self.teleport_to(spawn.x, spawn.y, spawn.z).await
+    }
+
+    /// The countdown is over (or the game ended), the fighters may (no longer) attack each other.
+    pub async fn set_olympiad_fighting(&mut self, fighting: bool) -> anyhow::Result<()> {
+        let controller = self.controller.clone();
+        let player = self.try_get_selected_char_mut()?;
+        let Some(OlympiadState {
+            role: OlympiadRole::Fighter { side, .. },
+            ..
+        }) = player.olympiad
+        else {
+            return Ok(());
+        };
+        if let Some(state) = player.olympiad.as_mut() {
+            state.role = OlympiadRole::Fighter { side, fighting };
+        }
+        let player = player.clone();
+        if let Some(actor) = controller.get_player_by_object_id(player.get_object_id()) {
+            controller.broadcast_relations(player, actor);
+        }
+        Ok(())
+    }
+
+    /// The game is over: the player goes back to where they were before it, fighters recover
+    /// and get their effects back.
+    pub async fn leave_olympiad_game(&mut self) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char_mut()?;
+        let Some(state) = player.leave_olympiad() else {
+            return Ok(());
+        };
+        if state.is_fighter() {
+            self.send_olympiad_status().await?;
+        }
+        let to = state.return_to;
+        self.teleport_to(to.x, to.y, to.z).await
+    }
+
+    async fn send_olympiad_status(&mut self) -> anyhow::Result<()> {
+        let controller = self.controller.clone();
+        let player = self.try_get_selected_char()?;
+        let user_info = UserInfo::new(player, UserInfoType::all(), &controller).await?;
+        let effects = AbnormalStatusUpdate::new(&player.stats.active_buffs)?;
+        self.send_packet(user_info).await?;
+        self.send_packet(effects).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bypass::NpcCommand;
+    use crate::managers::{GameOutcome, OlympiadEvent, OlympiadManager};
+    use crate::pl_client::{ApplyBuff, GetCharInfo};
+    use crate::test_utils::test::{get_controller, npc_command, on_actor, spawn_player};
+    use chrono::TimeDelta;
+    use entities::entities::character;
+    use entities::test_factories::factories::{char_factory, user_factory};
+    use sea_orm::EntityTrait;
+    use test_utils::utils::get_test_db;
+
+    #[tokio::test]
+    async fn test_olympiad_game() {
+        let db_pool = get_test_db().await;
+        let (char_id, actor, _conn) = spawn_player(&db_pool, 78).await;
+        let controller = get_controller(&actor).await;
+        // games are held in the evening, on the last day of the period here
+        let evening = controller.olympiad_manager.period_ends_at() - TimeDelta::hours(5);
+        controller.clock.set(evening);
+        assert!(is_competition_time(controller.clock.now()));
+        actor
+            .ask(ApplyBuff {
+                target_id: char_id,
+                skill_id: 1068,
+                skill_level: 1,
+                caster_id: char_id,
+                abnormal_type: Some("PA_UP".to_string()),
+                abnormal_level: 1,
+                abnormal_time_secs: 1200,
+                mods: vec![],
+            })
+            .await
+            .unwrap();
+
+        // only nobles take part
+        npc_command(
+            &actor,
+            31688,
+            NpcCommand::Olympiad(OlympiadCommand::RegisterClassed),
+        )
+        .await;
+        assert_eq!(controller.olympiad_manager.registration_of(char_id), None);
+        let (class_id, home) = on_actor(&actor, |pl| {
+            Box::pin(async move {
+                let player = pl.try_get_selected_char_mut()?;
+                player.char_model.nobless = true;
+                Ok((player.char_model.class_id, *player.get_location()))
+            })
+        })
+        .await;
+        npc_command(
+            &actor,
+            31688,
+            NpcCommand::Olympiad(OlympiadCommand::RegisterClassed),
+        )
+        .await;
+        let manager = controller.olympiad_manager.clone();
+        assert_eq!(
+            manager.registration_of(char_id),
+            Some(CompetitionType::Classed)
+        );
+        assert_eq!(
+            olympiad_noble::Model::load_all(&db_pool)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        let user = user_factory(&db_pool, |u| u).await;
+        let opponent = char_factory(&db_pool, |mut c| {
+            c.user_id = user.id;
+            c.name = "Opponent".to_string();
+            c
+        })
+        .await;
+        manager
+            .noble_or_create(&db_pool, opponent.id, class_id)
+            .await
+            .unwrap();
+        manager.register(opponent.id, class_id, CompetitionType::Classed);
+        assert_eq!(
+            OlympiadManager::update(&controller, &db_pool)
+                .await
+                .unwrap(),
+            vec![OlympiadEvent::GameCreated(1)]
+        );
+        let game = manager.game(1).unwrap();
+        let side = game.side_of(char_id).unwrap();
+        let spawn = manager.stadium(1).unwrap().spawns[usize::from(side - 1)];
+        on_actor(&actor, move |pl| {
+            Box::pin(pl.enter_olympiad_game(1, side, spawn))
+        })
+        .await;
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert!(player.is_in_olympiad_mode());
+        assert_eq!((player.get_x(), player.get_y()), (spawn.x, spawn.y));
+        assert_eq!(player.get_cur_hp().to_bits(), player.get_max_hp().to_bits());
+        assert!(player.stats.active_buffs.is_empty());
+
+        controller.clock.advance(TimeDelta::seconds(20));
+        assert_eq!(
+            OlympiadManager::update(&controller, &db_pool)
+                .await
+                .unwrap(),
+            vec![OlympiadEvent::FightStarted(1)]
+        );
+        on_actor(&actor, |pl| Box::pin(pl.set_olympiad_fighting(true))).await;
+
+        // the opponent leaves the game and loses it
+        controller.leave_game(opponent.id);
+        assert_eq!(
+            OlympiadManager::update(&controller, &db_pool)
+                .await
+                .unwrap(),
+            vec![OlympiadEvent::GameEnded(
+                1,
+                GameOutcome::Won {
+                    winner: char_id,
+                    loser: opponent.id,
+                    points: 2
+                }
+            )]
+        );
+        let nobles = olympiad_noble::Model::load_all(&db_pool).await.unwrap();
+        let points = |id| nobles.iter().find(|n| n.char_id == id).unwrap().points;
+        assert_eq!((points(char_id), points(opponent.id)), (12, 8));
+
+        controller.clock.advance(TimeDelta::seconds(10));
+        let events = OlympiadManager::update(&controller, &db_pool)
+            .await
+            .unwrap();
+        assert!(matches!(events.as_slice(), [OlympiadEvent::GameClosed(_)]));
+        on_actor(&actor, |pl| Box::pin(pl.leave_olympiad_game())).await;
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert!(player.olympiad.is_none());
+        assert_eq!((player.get_x(), player.get_y()), (home.x, home.y));
+        // the effects of before the game are back
+        assert_eq!(player.stats.active_buffs.len(), 1);
+
+        // a fighter disconnecting during a game is saved where they came from
+        on_actor(&actor, move |pl| {
+            Box::pin(pl.enter_olympiad_game(1, side, spawn))
+        })
+        .await;
+        actor.stop_gracefully().await.unwrap();
+        actor.wait_for_shutdown().await;
+        let saved = character::Entity::find_by_id(char_id)
+            .one(&db_pool)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!((saved.x, saved.y), (home.x, home.y));
+    }
+}
@@ -1,9 +1,8 @@
 use crate::movement::calculate_distance;
 use crate::packets::to_client::TargetSelected;
-use crate::pl_client::{GetCharInfo, PlayerClient};
+use crate::pl_client::PlayerClient;
 use bytes::BytesMut;
 use kameo::message::{Context, Message};
-use l2_core::errors::KameoAnyhowExt;
 use l2_core::shared_packets::common::ReadablePacket;
 use l2_core::shared_packets::read::ReadablePacketBuffer;
 use tracing::{error, instrument};
@@ -53,18 +52,22 @@ impl Message<Action> for PlayerClient {
         match msg.action {
             0 => {
                 if let Some(target_actor) = self.controller.get_player_by_object_id(msg.object_id) {
+                    let reselected = player_id != msg.object_id
+                        && self
+                            .selected_target
+                            .as_ref()
+                            .is_some_and(|(id, _)| *id == msg.object_id);
                     // store selected target mapping
                     let maybe_distance;
                     let config = self.controller.get_cfg();
                     let other_player_lvl;
                     if player_id != msg.object_id {
-                        let other_pl = target_actor.ask(GetCharInfo).await.anyhow()?;
-                        other_player_lvl = other_pl.char_model.level;
-                        let loc = other_pl.get_location();
+                        let other = self.get_target_stats(msg.object_id, &target_actor).await?;
+                        other_player_lvl = other.level;
                         maybe_distance = calculate_distance(
-                            loc.x,
-                            loc.y,
-                            loc.z,
+                            other.x,
+                            other.y,
+                            other.z,
                             msg.origin_x,
                             msg.origin_y,
                             msg.origin_z,
@@ -77,17 +80,31 @@ impl Message<Action> for PlayerClient {
                     if let Some(distance) = maybe_distance
                         && distance <= config.max_target_distance as f64
                     {
-                        self.selected_target = Some((msg.object_id, target_actor));
+                        self.selected_target = Some((msg.object_id, target_actor.clone()));
+                        self.selected_monster = None;
                         // notify client about target selection
                         self.send_packet(TargetSelected::new(
                             msg.object_id,
                             i16::from(level - other_player_lvl),
                         )?)
                         .await?;
+                        // clicking the selected player once more looks into its store
+                        if reselected {
+                            self.show_manufacture_shop(&target_actor).await?;
+                        }
                     }
+                } else if let Some(monster) = self.controller.manor_manager.monster(msg.object_id) {
+                    self.selected_target = None;
+                    self.selected_monster = Some(monster.object_id);
+                    self.send_packet(TargetSelected::new(
+                        msg.object_id,
+                        i16::from(level) - i16::from(monster.level),
+                    )?)
+                    .await?;
                 } else {
                     // the target not found in world registry; ignore or clear selection
                     self.selected_target = None;
+                    self.selected_monster = None;
                 }
             }
             1 => { //shift
@@ -0,0 +1,42 @@
+use crate::packets::to_client::UserInfo;
+use crate::pl_client::PlayerClient;
+use bytes::BytesMut;
+use kameo::message::{Context, Message};
+use l2_core::game_objects::player::user_info::UserInfoType;
+use l2_core::shared_packets::common::ReadablePacket;
+use tracing::instrument;
+
+/// Sent by the client when the loading screen after a teleport is finished.
+#[derive(Debug, Clone)]
+pub struct Appearing;
+
+impl ReadablePacket for Appearing {
+    const PACKET_ID: u8 = 0x3A;
+    const EX_PACKET_ID: Option<u16> = None;
+
+    fn read(_: BytesMut) -> anyhow::Result<Self> {
+        Ok(Self)
+    }
+}
+
+impl Message<Appearing> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, ctx))]
+    async fn handle(
+        &mut self,
+        _: Appearing,
+        ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char_mut()?;
+        if player.is_teleporting() {
+            player.set_teleporting(false);
+            let player = player.clone();
+            self.controller
+                .add_player_to_world(&player, ctx.actor_ref())
+                .await?;
+        }
+        let player = self.try_get_selected_char()?;
+        let p = UserInfo::new(player, UserInfoType::all(), &self.controller).await?;
+        self.send_packet(p).await
+    }
+}
@@ -1,6 +1,6 @@
 use crate::movement::calculate_distance;
 use crate::packets::to_client;
-use crate::pl_client::{ApplyDamage, GetStats, PlayerClient, PlayerTasks};
+use crate::pl_client::{ApplyDamage, PlayerClient, PlayerTasks};
 use bytes::BytesMut;
 use kameo::message::{Context, Message};
 use l2_core::errors::KameoAnyhowExt;
@@ -51,7 +51,7 @@ impl Message<Attack> for PlayerClient {
         let (x, y, z) = (player.get_x(), player.get_y(), player.get_z());
 
         if let Some(target_actor) = self.controller.get_player_by_object_id(msg.object_id) {
-            let target_stats = target_actor.ask(GetStats).await.anyhow()?;
+            let target_stats = self.get_target_stats(msg.object_id, &target_actor).await?;
             let (target_x, target_y, target_z) = (target_stats.x, target_stats.y, target_stats.z);
 
             let dist = calculate_distance(x, y, z, target_x, target_y, target_z).unwrap_or(0.0);
@@ -59,6 +59,9 @@ impl Message<Attack> for PlayerClient {
             if !self.check_visibility(target_x, target_y, target_z).await? {
                 return Ok(());
             }
+            if !self.check_peace_zone(target_x, target_y, target_z).await? {
+                return Ok(());
+            }
 
             // Physical attack range is typically short, e.g., 40.
             let attack_range = 40;
@@ -100,10 +103,12 @@ impl Message<Attack> for PlayerClient {
                 self.controller.broadcast_packet(attack_packet);
                 return Ok(());
             }
+            let shots = self.consume_shots(false)?;
             let damage =
-                Formulas::calc_phys_dam(&attacker_stats, &target_stats.stats, false, false);
+                Formulas::calc_phys_dam(&attacker_stats, &target_stats.stats, shots.ss, false);
             target_actor
                 .tell(ApplyDamage {
+                    target_id: msg.object_id,
                     damage,
                     attacker_id,
                     attacker_name,
@@ -125,6 +130,7 @@ impl Message<Attack> for PlayerClient {
             )?;
 
             self.controller.broadcast_packet(attack_packet);
+            self.recharge_shots(true, false).await?;
         }
         Ok(())
     }
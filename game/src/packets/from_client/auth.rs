@@ -5,6 +5,7 @@ use crate::packets::to_client::{CharSelectionInfo, PlayerLoginResponse};
 use crate::pl_client::{ClientStatus, PlayerClient};
 use anyhow::bail;
 use bytes::BytesMut;
+use chrono::{DateTime, Utc};
 use entities::entities::user;
 use kameo::message::Context;
 use kameo::prelude::Message;
@@ -46,10 +47,12 @@ impl ReadablePacket for AuthLogin {
 
 impl AuthLogin {
     /// Finalizes authentication by updating user status and sending necessary packets.
+    /// The premium of the account comes from the login server.
     async fn authenticate_user(
         &self,
         handler: &mut PlayerClient,
         session_key: SessionKey,
+        premium_expire: Option<DateTime<Utc>>,
     ) -> anyhow::Result<()> {
         // Notify that the player is in-game
         handler
@@ -70,6 +73,7 @@ impl AuthLogin {
             &self.login_name,
             &handler.db_pool,
             &handler.controller.class_templates,
+            &handler.controller.hennas,
         )
         .await?;
         // Prepare a character selection packet
@@ -82,7 +86,8 @@ impl AuthLogin {
 
         // Update handler with retrieved data
         handler.set_account_chars(players);
-        let user = user::Model::find_by_username(&handler.db_pool, &self.login_name).await?;
+        let mut user = user::Model::find_by_username(&handler.db_pool, &self.login_name).await?;
+        user.premium_expire = premium_expire.map(|t| t.fixed_offset());
         handler.set_user(user);
 
         // Send character selection info
@@ -134,7 +139,9 @@ impl Message<AuthLogin> for PlayerClient {
         match actor.ask(auth_request).await {
             Ok(response_future) => match response_future.await {
                 Ok(LSMessages::PlayerAuthResponse(r)) if r.is_ok => {
-                    return msg.authenticate_user(self, session_key).await;
+                    return msg
+                        .authenticate_user(self, session_key, r.premium_expire)
+                        .await;
                 }
                 Ok(LSMessages::PlayerAuthResponse(r)) => {
                     // Handle auth response that is not OK
@@ -221,7 +228,7 @@ mod tests {
         let player_actor =
             spawn_custom_player_client_actor(controller, pool, r, w, Some(player_client)).await;
         //--> auth login
-        let pb = PlayerAuthResponse::new(&auth.login_name, true);
+        let pb = PlayerAuthResponse::new(&auth.login_name, true, None);
         tokio::spawn(async move {
             //todo I don't like it, maybe send encrypted packet to _ls_client_stream instead?
             sleep(Duration::from_millis(100)).await;
@@ -42,6 +42,7 @@ impl Message<SelectChar> for PlayerClient {
 
         self.stop_movement();
         self.selected_target = None;
+        self.selected_monster = None;
 
         let game_time = self.controller.get_game_time();
         self.set_status(ClientStatus::Entering);
@@ -1,7 +1,9 @@
+use crate::controller::InGameChar;
 use crate::packets::to_client::extended::{
     AutoSoulShots, BasicActionList, BookmarkInfo, EquippedItems, InventoryAdenaInfo,
-    InventoryWeight, PledgeWaitingListAlarm, QuestItemList, Rotation, SetCompasZoneCode,
-    SubclassInfo, SubclassInfoType, UISettings, UnreadMailCount, VitalityInfo,
+    InventoryWeight, PledgeWaitingListAlarm, PremiumState, QuestItemList, Rotation,
+    SetCompasZoneCode, SubclassInfo, SubclassInfoType, UISettings, UnreadMailCount, VitalityInfo,
+    VoteSystem,
 };
 use crate::packets::to_client::{
     AbnormalStatusUpdate, AcquireSkillList, CharEtcStatusUpdate, FriendList, HennaInfo, ItemList,
@@ -11,6 +13,7 @@ use crate::packets::to_client::{
 use crate::pl_client::{ClientStatus, DoLater, PlayerClient};
 use anyhow::bail;
 use bytes::BytesMut;
+use entities::entities::character;
 use kameo::message::{Context, Message};
 use l2_core::errors::KameoAnyhowExt;
 use l2_core::game_objects::player::user_info::UserInfoType;
@@ -62,6 +65,7 @@ impl Message<EnterWorld> for PlayerClient {
         }
         self.stop_movement();
         self.selected_target = None;
+        self.selected_monster = None;
         self.set_status(ClientStatus::InGame);
         let mut addresses = Vec::with_capacity(5);
         for i in 0..5 {
@@ -94,6 +98,26 @@ impl Message<EnterWorld> for PlayerClient {
             .await
             .anyhow()?;
 
+        let controller = self.controller.clone();
+        let now = controller.clock.now();
+        let premium_until = self
+            .try_get_user()?
+            .get_premium_expire()
+            .filter(|t| *t > now);
+        let player = self.try_get_selected_char_mut()?;
+        player.premium_until = premium_until;
+        player.siege_state = controller.siege_manager.siege_state(player.get_clan_id());
+        let vitality_reset = player.refresh_vitality(now);
+        let recommendation_reset = player.refresh_recommendations(now);
+        if vitality_reset || recommendation_reset {
+            let char_model = player.char_model.clone();
+            character::Model::update_char(&self.db_pool, &char_model).await?;
+        }
+        self.restore_hero().await?;
+        self.restore_cursed_weapon()?;
+        self.try_get_selected_char_mut()?
+            .apply_enchant_stats(&controller.items_data, &controller.enchant);
+        self.update_zone_flags()?;
         let player = self.try_get_selected_char()?.clone();
         self.send_packet(UserInfo::new(&player, UserInfoType::all(), &self.controller).await?)
             .await?;
@@ -108,9 +132,11 @@ impl Message<EnterWorld> for PlayerClient {
         if player.char_model.clan_id.is_some() {
             //todo: send clan packet
         }
-        // if config.rates.enable_vitality
         self.send_packet(VitalityInfo::new(&player, &config)?)
             .await?;
+        self.send_packet(PremiumState::new(&player)?).await?;
+        self.schedule_premium_expiry(ctx.actor_ref().clone());
+        self.schedule_hero_expiry(ctx.actor_ref().clone());
         self.send_packet(UISettings::new(&player)?).await?;
         let macros_packets = MacroList::list_macros(&player)?;
         for m in macros_packets {
@@ -119,8 +145,10 @@ impl Message<EnterWorld> for PlayerClient {
 
         self.send_packet(BookmarkInfo::new(&player)?).await?;
 
-        self.send_packet(ItemList::new(&player, false)?).await?;
-        self.send_packet(QuestItemList::new(&player)?).await?;
+        self.send_packet(ItemList::new(&player, &self.controller.quests, false)?)
+            .await?;
+        self.send_packet(QuestItemList::new(&player, &self.controller.quests)?)
+            .await?;
         self.send_packet(ShortcutsInit::new(&player)?).await?;
 
         self.send_packet(BasicActionList::new(&self.controller.action_list)?)
@@ -157,7 +185,7 @@ impl Message<EnterWorld> for PlayerClient {
         self.send_packet(InventoryWeight::new(&player)?).await?;
         self.send_packet(InventoryAdenaInfo::new(&player)?).await?;
         self.send_packet(EquippedItems::new(&player, true)?).await?;
-        let unread_mails = player.mailbox.iter().map(|m| m.is_unread).len();
+        let unread_mails = player.get_unread_mail_count();
         if unread_mails > 0 {
             self.send_packet(UnreadMailCount::new(u32::try_from(unread_mails)?)?)
                 .await?;
@@ -170,8 +198,8 @@ impl Message<EnterWorld> for PlayerClient {
         //todo: check isCursedWeaponEquipped
         //todo: check if PC points enabled and send update packet
         //todo: send expand storage packet (if there is a skill for that) with a delay of 300ms
-        self.send_packet(FriendList::new(&player)?).await?;
-        //todo: send friend list logged in to all friends (broadcast)
+        self.send_packet(FriendList::new(&player, &self.controller)?)
+            .await?;
         //todo: send packet welcome to the L2 world
         //todo: show Announcements
         //todo: send message if auto restart is enabled
@@ -181,7 +209,7 @@ impl Message<EnterWorld> for PlayerClient {
         //todo: if it's dead then send Die packet
         //todo: on_player_enter hook
         self.send_packet(SkillCoolTime::new(&player)?).await?;
-        //todo: send vote system info
+        self.send_packet(VoteSystem::new(&player)?).await?;
         //todo: handle shadow items or items with mana
         //todo: do the same for items in warehouse
         //todo: send a message if recently dismissed from a clan
@@ -197,6 +225,16 @@ impl Message<EnterWorld> for PlayerClient {
         // register this player by global object_id in world registry
         self.controller
             .register_player_object(player.get_object_id(), ctx.actor_ref().clone());
+        // friends get the updated friend list
+        self.controller.enter_game(
+            player.char_model.id,
+            InGameChar {
+                account: self.try_get_user()?.username.clone(),
+                name: player.char_model.name.clone(),
+                object_id: player.get_object_id(),
+                actor: ctx.actor_ref().clone(),
+            },
+        );
 
         self.controller
             .add_player_to_world(&player, ctx.actor_ref())
@@ -208,7 +246,9 @@ impl Message<EnterWorld> for PlayerClient {
         //todo: send ExWorldChatCnt if ENABLE_WORLD_CHAT is enabled
         //todo: send ExConnectedTimeAndGettableReward
         //todo: send ExOneDayReceiveRewardList
-        self.send_packet(SetCompasZoneCode::new(0x0C)?).await?;
+        self.send_packet(SetCompasZoneCode::new(SetCompasZoneCode::zone_code(
+            &player,
+        ))?).await?;
         self.send_packet(MoveTo::new(&player, player.get_location())?)
             .await?;
         self.send_packet(AutoSoulShots::new(0, true, 0)?).await?;
@@ -236,7 +276,7 @@ mod tests {
     use super::*;
     use crate::controller::GameController;
     use crate::ls_client::LoginServerClient;
-    use crate::pl_client::ClientStatus;
+    use crate::pl_client::{ClientStatus, GetCharInfo};
     use crate::test_utils::test::{
         get_gs_config, spawn_custom_ls_client_actor, spawn_custom_player_client_actor,
     };
@@ -449,8 +489,11 @@ mod tests {
         let (ls_r, ls_w) = split(ls_server);
         let mut pl_client = prepare_pl().await;
         pl_client.set_status(ClientStatus::Entering);
-        let user = create_user(&pl_client.db_pool).await;
+        let mut user = create_user(&pl_client.db_pool).await;
         let player = create_char(&pl_client, user.id).await;
+        // the premium ran out while the player was offline
+        let expired = pl_client.controller.clock.now() - chrono::TimeDelta::days(1);
+        user.premium_expire = Some(expired.fixed_offset());
         pl_client.set_user(user);
         pl_client.set_account_chars(vec![player]);
         pl_client.select_char(0);
@@ -479,6 +522,7 @@ mod tests {
         .await;
         let res = player_actor.ask(pack).await;
         assert!(res.is_ok());
+        assert!(!player_actor.ask(GetCharInfo).await.unwrap().has_premium());
         // Collect packets: wait long enough to include DoLater(500ms) packets
         let frames = collect_packets_with_idle_timeout(
             &mut client_rx,
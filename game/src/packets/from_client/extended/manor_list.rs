@@ -24,7 +24,9 @@ impl Message<RequestManorList> for PlayerClient {
         _: RequestManorList,
         _ctx: &mut Context<Self, Self::Reply>,
     ) -> anyhow::Result<()> {
-        let p = ManorList::new()?;
+        let mut castle_ids: Vec<i32> = self.controller.castles.all().map(|c| c.id).collect();
+        castle_ids.sort_unstable();
+        let p = ManorList::new(&castle_ids)?;
         self.send_packet(p).await?;
         Ok(())
     }
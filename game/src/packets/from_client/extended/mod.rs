@@ -2,7 +2,29 @@ mod check_char_name;
 mod go_to_lobby;
 mod manor_list;
 mod req_user_ban_info;
+mod request_auto_soulshot;
+mod request_bookmark_slot_info;
+mod request_cancel_enchant_item;
+mod request_cancel_sent_post;
+mod request_cursed_weapon_list;
+mod request_cursed_weapon_location;
+mod request_delete_bookmark_slot;
+mod request_delete_received_post;
 mod request_key_mapping;
+mod request_modify_bookmark_slot;
+mod request_post_attachment;
+mod request_post_item_list;
+mod request_received_post;
+mod request_received_post_list;
+mod request_reject_post_attachment;
+mod request_save_bookmark_slot;
+mod request_send_post;
+mod request_sent_post;
+mod request_sent_post_list;
+mod request_teleport_bookmark;
+mod request_try_to_put_enchant_target_item;
+mod request_vote_new;
+mod request_write_hero_words;
 mod selected_zone_quest_id;
 mod send_client_ini;
 
@@ -10,6 +32,28 @@ pub use check_char_name::*;
 pub use go_to_lobby::*;
 pub use manor_list::*;
 pub use req_user_ban_info::*;
+pub use request_auto_soulshot::*;
+pub use request_bookmark_slot_info::*;
+pub use request_cancel_enchant_item::*;
+pub use request_cancel_sent_post::*;
+pub use request_cursed_weapon_list::*;
+pub use request_cursed_weapon_location::*;
+pub use request_delete_bookmark_slot::*;
+pub use request_delete_received_post::*;
 pub use request_key_mapping::*;
+pub use request_modify_bookmark_slot::*;
+pub use request_post_attachment::*;
+pub use request_post_item_list::*;
+pub use request_received_post::*;
+pub use request_received_post_list::*;
+pub use request_reject_post_attachment::*;
+pub use request_save_bookmark_slot::*;
+pub use request_send_post::*;
+pub use request_sent_post::*;
+pub use request_sent_post_list::*;
+pub use request_teleport_bookmark::*;
+pub use request_try_to_put_enchant_target_item::*;
+pub use request_vote_new::*;
+pub use request_write_hero_words::*;
 pub use selected_zone_quest_id::*;
 pub use send_client_ini::*;
@@ -0,0 +1,101 @@
+use crate::packets::to_client;
+use crate::packets::to_client::extended::AutoSoulShots;
+use crate::pl_client::PlayerClient;
+use bytes::BytesMut;
+use kameo::message::{Context, Message};
+use l2_core::game_objects::private_store_types::PrivateStoreType;
+use l2_core::shared_packets::common::ReadablePacket;
+use l2_core::shared_packets::read::ReadablePacketBuffer;
+use tracing::{instrument, warn};
+
+#[derive(Debug, Clone)]
+pub struct RequestAutoSoulShot {
+    pub item_id: i32,
+    pub enable: bool,
+    pub shot_type: i32,
+}
+
+impl ReadablePacket for RequestAutoSoulShot {
+    const PACKET_ID: u8 = 0xD0;
+    const EX_PACKET_ID: Option<u16> = Some(0x0D);
+
+    fn read(data: BytesMut) -> anyhow::Result<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data);
+        Ok(Self {
+            item_id: buffer.read_i32()?,
+            enable: buffer.read_i32()? == 1,
+            shot_type: buffer.read_i32()?,
+        })
+    }
+}
+
+impl Message<RequestAutoSoulShot> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: RequestAutoSoulShot,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        let Some(shot) = self
+            .controller
+            .items_data
+            .get_template(msg.item_id)
+            .and_then(|t| t.shot)
+        else {
+            warn!("Item {} is not a shot, possible cheating", msg.item_id);
+            return Ok(());
+        };
+        if msg.shot_type != shot.auto_shot_type() {
+            warn!(
+                "Shot type {} doesn't match item {}, possible cheating",
+                msg.shot_type, msg.item_id
+            );
+            return Ok(());
+        }
+        if !msg.enable {
+            return self.disable_auto_shot(msg.item_id, shot, false).await;
+        }
+        let player = self.try_get_selected_char()?;
+        if player.get_private_store_type() != PrivateStoreType::None
+            || player.inventory.get_item_by_item_id(msg.item_id).is_none()
+        {
+            return Ok(());
+        }
+        if shot.is_beast() {
+            //todo: beast shots are used by summons, enable them when we have pets
+            return Ok(());
+        }
+        self.try_get_selected_char_mut()?
+            .add_auto_soulshot(msg.item_id);
+        self.send_packet(AutoSoulShots::new(
+            msg.item_id,
+            true,
+            shot.auto_shot_type(),
+        )?)
+        .await?;
+        let mut sm = to_client::SystemMessage::new(
+            to_client::SystemMessageType::TheAutomaticUseOfS1HasBeenActivated,
+        )?;
+        sm.add_param(to_client::SystemMessageParam::ItemName(msg.item_id))?;
+        self.send_packet(sm).await?;
+        self.recharge_shots(!shot.is_magic(), shot.is_magic()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read() {
+        let mut data = BytesMut::new();
+        data.extend_from_slice(&1463i32.to_le_bytes());
+        data.extend_from_slice(&1i32.to_le_bytes());
+        data.extend_from_slice(&0i32.to_le_bytes());
+        let p = RequestAutoSoulShot::read(data).unwrap();
+        assert_eq!(p.item_id, 1463);
+        assert!(p.enable);
+        assert_eq!(p.shot_type, 0);
+    }
+}
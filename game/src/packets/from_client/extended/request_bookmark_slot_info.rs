@@ -0,0 +1,31 @@
+use crate::packets::to_client::extended::BookmarkInfo;
+use crate::pl_client::PlayerClient;
+use bytes::BytesMut;
+use kameo::message::{Context, Message};
+use l2_core::shared_packets::common::ReadablePacket;
+use tracing::instrument;
+
+#[derive(Debug, Clone)]
+pub struct RequestBookmarkSlotInfo;
+
+impl ReadablePacket for RequestBookmarkSlotInfo {
+    const PACKET_ID: u8 = 0xD0;
+    const EX_PACKET_ID: Option<u16> = Some(0x51);
+
+    fn read(_: BytesMut) -> anyhow::Result<Self> {
+        Ok(Self)
+    }
+}
+
+impl Message<RequestBookmarkSlotInfo> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        _: RequestBookmarkSlotInfo,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        self.send_packet(BookmarkInfo::new(player)?).await
+    }
+}
@@ -0,0 +1,35 @@
+use crate::pl_client::PlayerClient;
+use bytes::BytesMut;
+use kameo::message::{Context, Message};
+use l2_core::shared_packets::common::ReadablePacket;
+use l2_core::shared_packets::read::ReadablePacketBuffer;
+use tracing::instrument;
+
+#[derive(Debug, Clone)]
+pub struct RequestCancelSentPost {
+    pub mail_id: i32,
+}
+
+impl ReadablePacket for RequestCancelSentPost {
+    const PACKET_ID: u8 = 0xD0;
+    const EX_PACKET_ID: Option<u16> = Some(0x6F);
+
+    fn read(data: BytesMut) -> anyhow::Result<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data);
+        Ok(Self {
+            mail_id: buffer.read_i32()?,
+        })
+    }
+}
+
+impl Message<RequestCancelSentPost> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: RequestCancelSentPost,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        self.cancel_sent_post(msg.mail_id).await
+    }
+}
@@ -0,0 +1,29 @@
+use crate::pl_client::PlayerClient;
+use bytes::BytesMut;
+use kameo::message::{Context, Message};
+use l2_core::shared_packets::common::ReadablePacket;
+use tracing::instrument;
+
+#[derive(Debug, Clone)]
+pub struct RequestCursedWeaponList;
+
+impl ReadablePacket for RequestCursedWeaponList {
+    const PACKET_ID: u8 = 0xD0;
+    const EX_PACKET_ID: Option<u16> = Some(0x2A);
+
+    fn read(_: BytesMut) -> anyhow::Result<Self> {
+        Ok(Self)
+    }
+}
+
+impl Message<RequestCursedWeaponList> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        _: RequestCursedWeaponList,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        self.send_cursed_weapon_list().await
+    }
+}
@@ -0,0 +1,35 @@
+use crate::pl_client::PlayerClient;
+use bytes::BytesMut;
+use kameo::message::{Context, Message};
+use l2_core::shared_packets::common::ReadablePacket;
+use l2_core::shared_packets::read::ReadablePacketBuffer;
+use tracing::instrument;
+
+#[derive(Debug, Clone)]
+pub struct RequestDeleteBookmarkSlot {
+    pub id: i32,
+}
+
+impl ReadablePacket for RequestDeleteBookmarkSlot {
+    const PACKET_ID: u8 = 0xD0;
+    const EX_PACKET_ID: Option<u16> = Some(0x54);
+
+    fn read(data: BytesMut) -> anyhow::Result<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data);
+        Ok(Self {
+            id: buffer.read_i32()?,
+        })
+    }
+}
+
+impl Message<RequestDeleteBookmarkSlot> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: RequestDeleteBookmarkSlot,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        self.delete_teleport_bookmark(msg.id).await
+    }
+}
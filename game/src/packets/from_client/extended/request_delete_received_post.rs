@@ -0,0 +1,45 @@
+use crate::pl_client::PlayerClient;
+use anyhow::bail;
+use bytes::BytesMut;
+use kameo::message::{Context, Message};
+use l2_core::shared_packets::common::ReadablePacket;
+use l2_core::shared_packets::read::ReadablePacketBuffer;
+use tracing::instrument;
+
+#[derive(Debug, Clone)]
+pub struct RequestDeleteReceivedPost {
+    pub mail_ids: Vec<i32>,
+}
+
+impl RequestDeleteReceivedPost {
+    const MAX_MAILS: i32 = 100;
+}
+
+impl ReadablePacket for RequestDeleteReceivedPost {
+    const PACKET_ID: u8 = 0xD0;
+    const EX_PACKET_ID: Option<u16> = Some(0x68);
+
+    fn read(data: BytesMut) -> anyhow::Result<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data);
+        let count = buffer.read_i32()?;
+        if !(0..=Self::MAX_MAILS).contains(&count) {
+            bail!("Invalid count of mails to delete {count}");
+        }
+        let mail_ids = (0..count)
+            .map(|_| buffer.read_i32())
+            .collect::<Result<_, _>>()?;
+        Ok(Self { mail_ids })
+    }
+}
+
+impl Message<RequestDeleteReceivedPost> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: RequestDeleteReceivedPost,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        self.delete_received_posts(&msg.mail_ids).await
+    }
+}
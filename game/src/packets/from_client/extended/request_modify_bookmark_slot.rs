@@ -0,0 +1,42 @@
+use crate::pl_client::PlayerClient;
+use bytes::BytesMut;
+use kameo::message::{Context, Message};
+use l2_core::shared_packets::common::ReadablePacket;
+use l2_core::shared_packets::read::ReadablePacketBuffer;
+use tracing::instrument;
+
+#[derive(Debug, Clone)]
+pub struct RequestModifyBookmarkSlot {
+    pub id: i32,
+    pub name: String,
+    pub icon: i32,
+    pub tag: String,
+}
+
+impl ReadablePacket for RequestModifyBookmarkSlot {
+    const PACKET_ID: u8 = 0xD0;
+    const EX_PACKET_ID: Option<u16> = Some(0x53);
+
+    fn read(data: BytesMut) -> anyhow::Result<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data);
+        Ok(Self {
+            id: buffer.read_i32()?,
+            name: buffer.read_c_utf16le_string()?,
+            icon: buffer.read_i32()?,
+            tag: buffer.read_c_utf16le_string()?,
+        })
+    }
+}
+
+impl Message<RequestModifyBookmarkSlot> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: RequestModifyBookmarkSlot,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        self.modify_teleport_bookmark(msg.id, msg.name, msg.icon, msg.tag)
+            .await
+    }
+}
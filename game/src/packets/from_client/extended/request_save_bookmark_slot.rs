@@ -0,0 +1,63 @@
+use crate::pl_client::PlayerClient;
+use bytes::BytesMut;
+use kameo::message::{Context, Message};
+use l2_core::shared_packets::common::ReadablePacket;
+use l2_core::shared_packets::read::ReadablePacketBuffer;
+use tracing::instrument;
+
+#[derive(Debug, Clone)]
+pub struct RequestSaveBookmarkSlot {
+    pub name: String,
+    pub icon: i32,
+    pub tag: String,
+}
+
+impl ReadablePacket for RequestSaveBookmarkSlot {
+    const PACKET_ID: u8 = 0xD0;
+    const EX_PACKET_ID: Option<u16> = Some(0x52);
+
+    fn read(data: BytesMut) -> anyhow::Result<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data);
+        Ok(Self {
+            name: buffer.read_c_utf16le_string()?,
+            icon: buffer.read_i32()?,
+            tag: buffer.read_c_utf16le_string()?,
+        })
+    }
+}
+
+impl Message<RequestSaveBookmarkSlot> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: RequestSaveBookmarkSlot,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        self.save_teleport_bookmark(msg.name, msg.icon, msg.tag)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_string(data: &mut BytesMut, s: &str) {
+        for c in s.encode_utf16().chain([0]) {
+            data.extend_from_slice(&c.to_le_bytes());
+        }
+    }
+
+    #[test]
+    fn test_read() {
+        let mut data = BytesMut::new();
+        write_string(&mut data, "home");
+        data.extend_from_slice(&3i32.to_le_bytes());
+        write_string(&mut data, "H");
+        let p = RequestSaveBookmarkSlot::read(data).unwrap();
+        assert_eq!(p.name, "home");
+        assert_eq!(p.icon, 3);
+        assert_eq!(p.tag, "H");
+    }
+}
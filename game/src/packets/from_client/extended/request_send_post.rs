@@ -0,0 +1,62 @@
+use crate::pl_client::PlayerClient;
+use anyhow::bail;
+use bytes::BytesMut;
+use kameo::message::{Context, Message};
+use l2_core::shared_packets::common::ReadablePacket;
+use l2_core::shared_packets::read::ReadablePacketBuffer;
+use tracing::instrument;
+
+#[derive(Debug, Clone)]
+pub struct RequestSendPost {
+    pub receiver: String,
+    pub is_cod: bool,
+    pub subject: String,
+    pub text: String,
+    /// `(object id, count)` of the attached inventory items
+    pub attachments: Vec<(i32, i64)>,
+    pub request_adena: i64,
+}
+
+impl RequestSendPost {
+    pub const MAX_ATTACHMENTS: i32 = 8;
+}
+
+impl ReadablePacket for RequestSendPost {
+    const PACKET_ID: u8 = 0xD0;
+    const EX_PACKET_ID: Option<u16> = Some(0x66);
+
+    fn read(data: BytesMut) -> anyhow::Result<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data);
+        let receiver = buffer.read_c_utf16le_string()?;
+        let is_cod = buffer.read_i32()? != 0;
+        let subject = buffer.read_c_utf16le_string()?;
+        let text = buffer.read_c_utf16le_string()?;
+        let count = buffer.read_i32()?;
+        if !(0..=Self::MAX_ATTACHMENTS).contains(&count) {
+            bail!("Invalid count of mail attachments {count}");
+        }
+        let attachments = (0..count)
+            .map(|_| Ok((buffer.read_i32()?, buffer.read_i64()?)))
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self {
+            receiver,
+            is_cod,
+            subject,
+            text,
+            attachments,
+            request_adena: buffer.read_i64()?,
+        })
+    }
+}
+
+impl Message<RequestSendPost> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: RequestSendPost,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        self.send_mail(msg).await
+    }
+}
@@ -0,0 +1,48 @@
+use crate::pl_client::PlayerClient;
+use bytes::BytesMut;
+use kameo::message::{Context, Message};
+use l2_core::shared_packets::common::ReadablePacket;
+use l2_core::shared_packets::read::ReadablePacketBuffer;
+use tracing::instrument;
+
+#[derive(Debug, Clone)]
+pub struct RequestExTryToPutEnchantTargetItem {
+    pub object_id: i32,
+}
+
+impl ReadablePacket for RequestExTryToPutEnchantTargetItem {
+    const PACKET_ID: u8 = 0xD0;
+    const EX_PACKET_ID: Option<u16> = Some(0x4C);
+
+    fn read(data: BytesMut) -> anyhow::Result<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data);
+        Ok(Self {
+            object_id: buffer.read_i32()?,
+        })
+    }
+}
+
+impl Message<RequestExTryToPutEnchantTargetItem> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: RequestExTryToPutEnchantTargetItem,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        self.put_enchant_target(msg.object_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read() {
+        let mut data = BytesMut::new();
+        data.extend_from_slice(&9i32.to_le_bytes());
+        let p = RequestExTryToPutEnchantTargetItem::read(data).unwrap();
+        assert_eq!(p.object_id, 9);
+    }
+}
@@ -0,0 +1,47 @@
+use crate::pl_client::PlayerClient;
+use bytes::BytesMut;
+use kameo::message::{Context, Message};
+use l2_core::shared_packets::common::ReadablePacket;
+use l2_core::shared_packets::read::ReadablePacketBuffer;
+use tracing::instrument;
+
+#[derive(Debug, Clone)]
+pub struct RequestVoteNew {
+    pub target_id: i32,
+}
+
+impl ReadablePacket for RequestVoteNew {
+    const PACKET_ID: u8 = 0xD0;
+    const EX_PACKET_ID: Option<u16> = Some(0x7E);
+
+    fn read(data: BytesMut) -> anyhow::Result<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data);
+        Ok(Self {
+            target_id: buffer.read_i32()?,
+        })
+    }
+}
+
+impl Message<RequestVoteNew> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: RequestVoteNew,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        self.recommend(msg.target_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_vote() {
+        let data = BytesMut::from(&[0x10u8, 0x27, 0, 0][..]);
+        let packet = RequestVoteNew::read(data).unwrap();
+        assert_eq!(packet.target_id, 10_000);
+    }
+}
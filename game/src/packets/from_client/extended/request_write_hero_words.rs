@@ -0,0 +1,47 @@
+use crate::pl_client::PlayerClient;
+use bytes::BytesMut;
+use kameo::message::{Context, Message};
+use l2_core::shared_packets::common::ReadablePacket;
+use l2_core::shared_packets::read::ReadablePacketBuffer;
+use tracing::instrument;
+
+#[derive(Debug, Clone)]
+pub struct RequestWriteHeroWords {
+    pub words: String,
+}
+
+impl ReadablePacket for RequestWriteHeroWords {
+    const PACKET_ID: u8 = 0xD0;
+    const EX_PACKET_ID: Option<u16> = Some(0x05);
+
+    fn read(data: BytesMut) -> anyhow::Result<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data);
+        Ok(Self {
+            words: buffer.read_c_utf16le_string()?,
+        })
+    }
+}
+
+impl Message<RequestWriteHeroWords> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: RequestWriteHeroWords,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        self.set_hero_message(msg.words).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read() {
+        let data = BytesMut::from(&[104u8, 0, 105, 0, 0, 0][..]);
+        let packet = RequestWriteHeroWords::read(data).unwrap();
+        assert_eq!(packet.words, "hi");
+    }
+}
@@ -1,6 +1,8 @@
+use crate::packets::to_client::{ActionFailed, SystemMessage};
 use crate::pl_client::PlayerClient;
 use bytes::BytesMut;
 use kameo::message::{Context, Message};
+use l2_core::game_objects::player::Player;
 use l2_core::shared_packets::common::ReadablePacket;
 use tracing::{error, info, instrument};
 
@@ -22,8 +24,18 @@ impl Message<Logout> for PlayerClient {
         _msg: Logout,
         ctx: &mut Context<Self, Self::Reply>,
     ) -> anyhow::Result<()> {
-        //todo handle proper logout mechanism: olympiad,
+        //todo handle proper logout mechanism:
         // in battle state, on RB and so on, offline trade, etc...
+        if self
+            .try_get_selected_char()
+            .is_ok_and(Player::is_in_olympiad_mode)
+        {
+            self.send_packet(SystemMessage::text(
+                "You can't log out while taking part in the Olympiad.",
+            )?)
+            .await?;
+            return self.send_packet(ActionFailed::normal()?).await;
+        }
 
         // evaluate user before using macro
         let user = self.try_get_user()?;
@@ -1,4 +1,5 @@
 pub mod action;
+pub mod appearing;
 pub mod attack;
 pub mod auth;
 pub mod char_create;
@@ -9,13 +10,56 @@ pub mod enter_world;
 pub mod extended;
 pub mod logout;
 pub mod move_to_location;
+pub mod multi_sell_choose;
 pub mod new_char_request;
 pub mod noop;
 pub mod protocol;
 pub mod req_skill_cooltime;
+pub mod request_action_use;
+pub mod request_answer_friend_invite;
+pub mod request_block;
+pub mod request_buy_item;
+pub mod request_bypass_to_server;
 pub mod request_cancel_target;
+pub mod request_delete_macro;
+pub mod request_enchant_item;
+pub mod request_friend_del;
+pub mod request_friend_invite;
+pub mod request_friend_list;
+pub mod request_get_item_from_pet;
+pub mod request_give_item_to_pet;
+pub mod request_henna_equip;
+pub mod request_henna_item_info;
+pub mod request_henna_item_list;
+pub mod request_henna_item_remove_info;
+pub mod request_henna_remove;
+pub mod request_henna_remove_list;
+pub mod request_link_html;
 pub mod request_magic_skill_use;
+pub mod request_make_macro;
+pub mod request_package_send;
+pub mod request_package_sendable_item_list;
+pub mod request_pet_use_item;
+pub mod request_quest_abort;
+pub mod request_quest_list;
+pub mod request_recipe_book_destroy;
+pub mod request_recipe_book_open;
+pub mod request_recipe_item_make_info;
+pub mod request_recipe_item_make_self;
+pub mod request_recipe_shop_list_set;
+pub mod request_recipe_shop_make_info;
+pub mod request_recipe_shop_make_item;
+pub mod request_recipe_shop_manage_quit;
+pub mod request_recipe_shop_message_set;
+pub mod request_sell_item;
+pub mod request_send_friend_msg;
+pub mod request_shortcut_del;
+pub mod request_shortcut_reg;
 pub mod request_skill_list;
 pub mod restart;
+pub mod say2;
+pub mod send_ware_house_deposit_list;
+pub mod send_ware_house_with_draw_list;
 pub mod stop_move;
+pub mod use_item;
 pub mod validate_position;
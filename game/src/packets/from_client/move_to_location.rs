@@ -3,6 +3,7 @@ use crate::packets::to_client::ActionFailed;
 use crate::pl_client::PlayerClient;
 use bytes::BytesMut;
 use kameo::message::{Context, Message};
+use l2_core::game_objects::zone::ZoneId;
 use l2_core::shared_packets::common::ReadablePacket;
 use l2_core::shared_packets::read::ReadablePacketBuffer;
 use tracing::{instrument, warn};
@@ -43,6 +44,18 @@ impl Message<RequestMoveToLocation> for PlayerClient {
         ctx: &mut Context<Self, Self::Reply>,
     ) -> anyhow::Result<()> {
         //TODO check with geodata if the location is valid.
+        // a strider keeps to the ground, a wyvern flies and swimmers go where they are told
+        let player = self.try_get_selected_char()?;
+        let z_to = if player.is_mounted()
+            && !player.is_flying_mounted()
+            && !player.is_inside_zone(ZoneId::Water)
+        {
+            self.controller
+                .geo_engine
+                .get_nearest_z(msg.x_to, msg.y_to, msg.z_to)
+        } else {
+            msg.z_to
+        };
 
         // Get the effective current position for distance validation
         // This aligns with start_movement starting point logic (mid-move retargets included)
@@ -50,7 +63,7 @@ impl Message<RequestMoveToLocation> for PlayerClient {
 
         // Calculate distance
         let Some(distance) = calculate_distance(
-            current_x, current_y, current_z, msg.x_to, msg.y_to, msg.z_to,
+            current_x, current_y, current_z, msg.x_to, msg.y_to, z_to,
         ) else {
             let player = self.try_get_selected_char()?;
             warn!(
@@ -79,7 +92,8 @@ impl Message<RequestMoveToLocation> for PlayerClient {
         if self.is_casting() {
             self.send_packet(ActionFailed::normal()?).await?;
         } else {
-            self.start_movement(msg.x_to, msg.y_to, msg.z_to, ctx.actor_ref().clone())?;
+            self.end_fishing(false).await?;
+            self.start_movement(msg.x_to, msg.y_to, z_to, ctx.actor_ref().clone())?;
         }
 
         Ok(())
@@ -0,0 +1,41 @@
+use crate::pl_client::PlayerClient;
+use bytes::BytesMut;
+use kameo::message::{Context, Message};
+use l2_core::shared_packets::common::ReadablePacket;
+use l2_core::shared_packets::read::ReadablePacketBuffer;
+use tracing::instrument;
+
+#[derive(Debug, Clone)]
+pub struct MultiSellChoose {
+    pub list_id: i32,
+    pub entry_id: i32,
+    pub amount: i64,
+}
+
+impl ReadablePacket for MultiSellChoose {
+    const PACKET_ID: u8 = 0xB0;
+    const EX_PACKET_ID: Option<u16> = None;
+
+    fn read(data: BytesMut) -> anyhow::Result<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data);
+        // the rest are enchant level and attributes of the chosen item, decided by the server
+        Ok(Self {
+            list_id: buffer.read_i32()?,
+            entry_id: buffer.read_i32()?,
+            amount: buffer.read_i64()?,
+        })
+    }
+}
+
+impl Message<MultiSellChoose> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: MultiSellChoose,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        self.multisell_choose(msg.list_id, msg.entry_id, msg.amount)
+            .await
+    }
+}
@@ -0,0 +1,76 @@
+use crate::packets::to_client::ActionFailed;
+use crate::pl_client::PlayerClient;
+use bytes::BytesMut;
+use kameo::message::{Context, Message};
+use l2_core::shared_packets::common::ReadablePacket;
+use l2_core::shared_packets::read::ReadablePacketBuffer;
+use tracing::{instrument, warn};
+
+#[derive(Debug, Clone)]
+pub struct RequestActionUse {
+    pub action_id: i32,
+    pub ctrl_pressed: bool,
+    pub shift_pressed: bool,
+}
+
+impl ReadablePacket for RequestActionUse {
+    const PACKET_ID: u8 = 0x56;
+    const EX_PACKET_ID: Option<u16> = None;
+
+    fn read(data: BytesMut) -> anyhow::Result<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data);
+        Ok(Self {
+            action_id: buffer.read_i32()?,
+            ctrl_pressed: buffer.read_i32()? != 0,
+            shift_pressed: buffer.read_byte()? != 0,
+        })
+    }
+}
+
+impl Message<RequestActionUse> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: RequestActionUse,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        let handler = u32::try_from(msg.action_id)
+            .ok()
+            .and_then(|id| self.controller.action_list.get(id))
+            .map(|a| a.handler.clone());
+        match handler.as_deref() {
+            Some(
+                handler @ ("PetHold" | "PetAttack" | "PetStop" | "UnsummonPet" | "ServitorHold"
+                | "ServitorAttack" | "ServitorStop" | "UnsummonServitor"),
+            ) => self.use_summon_action(handler, msg.ctrl_pressed).await,
+            Some("Ride") => self.use_ride_action().await,
+            // 37 sets up the dwarven manufacture shop, 51 the common one
+            Some("PrivateStore") if matches!(msg.action_id, 37 | 51) => {
+                self.manage_manufacture_shop(msg.action_id == 37).await
+            }
+            _ => {
+                //todo: the other actions
+                warn!("Action {} is not supported", msg.action_id);
+                self.send_packet(ActionFailed::normal()?).await
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read() {
+        let mut data = BytesMut::new();
+        data.extend_from_slice(&16i32.to_le_bytes());
+        data.extend_from_slice(&1i32.to_le_bytes());
+        data.extend_from_slice(&[0]);
+        let p = RequestActionUse::read(data).unwrap();
+        assert_eq!(p.action_id, 16);
+        assert!(p.ctrl_pressed);
+        assert!(!p.shift_pressed);
+    }
+}
@@ -0,0 +1,36 @@
+use crate::pl_client::PlayerClient;
+use bytes::BytesMut;
+use kameo::message::{Context, Message};
+use l2_core::shared_packets::common::ReadablePacket;
+use l2_core::shared_packets::read::ReadablePacketBuffer;
+use tracing::instrument;
+
+#[derive(Debug, Clone)]
+pub struct RequestAnswerFriendInvite {
+    pub response: i32,
+}
+
+impl ReadablePacket for RequestAnswerFriendInvite {
+    const PACKET_ID: u8 = 0x78;
+    const EX_PACKET_ID: Option<u16> = None;
+
+    fn read(data: BytesMut) -> anyhow::Result<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data);
+        buffer.read_byte()?; // always 1
+        Ok(Self {
+            response: buffer.read_i32()?,
+        })
+    }
+}
+
+impl Message<RequestAnswerFriendInvite> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: RequestAnswerFriendInvite,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        self.answer_friend_invite(msg.response == 1).await
+    }
+}
@@ -0,0 +1,71 @@
+use crate::pl_client::PlayerClient;
+use bytes::BytesMut;
+use kameo::message::{Context, Message};
+use l2_core::shared_packets::common::ReadablePacket;
+use l2_core::shared_packets::read::ReadablePacketBuffer;
+use tracing::instrument;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockRequest {
+    Block(String),
+    Unblock(String),
+    List,
+    BlockAll,
+    UnblockAll,
+}
+
+#[derive(Debug, Clone)]
+pub struct RequestBlock {
+    pub request: BlockRequest,
+}
+
+impl ReadablePacket for RequestBlock {
+    const PACKET_ID: u8 = 0xA9;
+    const EX_PACKET_ID: Option<u16> = None;
+
+    fn read(data: BytesMut) -> anyhow::Result<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data);
+        let request = match buffer.read_i32()? {
+            0 => BlockRequest::Block(buffer.read_c_utf16le_string()?),
+            1 => BlockRequest::Unblock(buffer.read_c_utf16le_string()?),
+            2 => BlockRequest::List,
+            3 => BlockRequest::BlockAll,
+            4 => BlockRequest::UnblockAll,
+            t => anyhow::bail!("Unknown block request type {t}"),
+        };
+        Ok(Self { request })
+    }
+}
+
+impl Message<RequestBlock> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: RequestBlock,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        match msg.request {
+            BlockRequest::Block(name) => self.block_char(&name).await,
+            BlockRequest::Unblock(name) => self.unblock_char(&name).await,
+            BlockRequest::List => self.send_block_list().await,
+            BlockRequest::BlockAll => self.set_block_all(true).await,
+            BlockRequest::UnblockAll => self.set_block_all(false).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_block() {
+        let data = BytesMut::from(&[0u8, 0, 0, 0, 65, 0, 98, 0, 0, 0][..]);
+        let packet = RequestBlock::read(data).unwrap();
+        assert_eq!(packet.request, BlockRequest::Block("Ab".to_string()));
+        let data = BytesMut::from(&[3u8, 0, 0, 0][..]);
+        let packet = RequestBlock::read(data).unwrap();
+        assert_eq!(packet.request, BlockRequest::BlockAll);
+    }
+}
@@ -32,7 +32,7 @@ impl Message<RequestCancelTarget> for PlayerClient {
     ) -> anyhow::Result<()> {
         //todo stop casting
         //todo: check if target is locked (aggression from tanks)
-        if let Some(_) = self.selected_target.take() {
+        if self.selected_target.take().is_some() | self.selected_monster.take().is_some() {
             let player = self.try_get_selected_char()?;
             self.controller
                 .broadcast_packet(TargetUnselected::new(player)?);
@@ -0,0 +1,68 @@
+use crate::packets::to_client::{MacroList, MacroUpdateType, ShortcutsInit};
+use crate::pl_client::PlayerClient;
+use bytes::BytesMut;
+use entities::entities::{character_macro, character_shortcut};
+use kameo::message::{Context, Message};
+use l2_core::game_objects::player::ShortcutType;
+use l2_core::shared_packets::common::ReadablePacket;
+use l2_core::shared_packets::read::ReadablePacketBuffer;
+use tracing::{instrument, warn};
+
+#[derive(Debug, Clone)]
+pub struct RequestDeleteMacro {
+    pub id: i32,
+}
+
+impl ReadablePacket for RequestDeleteMacro {
+    const PACKET_ID: u8 = 0xCE;
+    const EX_PACKET_ID: Option<u16> = None;
+
+    fn read(data: BytesMut) -> anyhow::Result<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data);
+        Ok(Self {
+            id: buffer.read_i32()?,
+        })
+    }
+}
+
+impl Message<RequestDeleteMacro> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: RequestDeleteMacro,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char_mut()?;
+        let char_id = player.char_model.id;
+        let class_index = player.get_class_index();
+        let Some(deleted) = player.delete_macro(msg.id) else {
+            warn!("Macro {} not found", msg.id);
+            return Ok(());
+        };
+        let shortcuts = player.delete_shortcuts_to(ShortcutType::Macro, msg.id);
+        let count = u8::try_from(player.get_macros().len())?;
+        character_macro::Model::delete(&self.db_pool, char_id, msg.id).await?;
+        for sc in &shortcuts {
+            character_shortcut::Model::delete(
+                &self.db_pool,
+                char_id,
+                sc.slot,
+                sc.page,
+                class_index,
+            )
+            .await?;
+        }
+        self.send_packet(MacroList::new(
+            count,
+            Some(&deleted),
+            MacroUpdateType::Delete,
+        )?)
+        .await?;
+        if !shortcuts.is_empty() {
+            let player = self.try_get_selected_char()?;
+            self.send_packet(ShortcutsInit::new(player)?).await?;
+        }
+        Ok(())
+    }
+}
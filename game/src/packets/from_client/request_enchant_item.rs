@@ -0,0 +1,52 @@
+use crate::pl_client::PlayerClient;
+use bytes::BytesMut;
+use kameo::message::{Context, Message};
+use l2_core::shared_packets::common::ReadablePacket;
+use l2_core::shared_packets::read::ReadablePacketBuffer;
+use tracing::instrument;
+
+#[derive(Debug, Clone)]
+pub struct RequestEnchantItem {
+    pub object_id: i32,
+    /// Support stone raising the chance, not supported yet
+    pub support_id: i32,
+}
+
+impl ReadablePacket for RequestEnchantItem {
+    const PACKET_ID: u8 = 0x5F;
+    const EX_PACKET_ID: Option<u16> = None;
+
+    fn read(data: BytesMut) -> anyhow::Result<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data);
+        Ok(Self {
+            object_id: buffer.read_i32()?,
+            support_id: buffer.read_i32()?,
+        })
+    }
+}
+
+impl Message<RequestEnchantItem> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: RequestEnchantItem,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        self.enchant_item(msg.object_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read() {
+        let mut data = BytesMut::new();
+        data.extend_from_slice(&7i32.to_le_bytes());
+        data.extend_from_slice(&0i32.to_le_bytes());
+        let p = RequestEnchantItem::read(data).unwrap();
+        assert_eq!((p.object_id, p.support_id), (7, 0));
+    }
+}
@@ -0,0 +1,53 @@
+use crate::pl_client::PlayerClient;
+use bytes::BytesMut;
+use kameo::message::{Context, Message};
+use l2_core::shared_packets::common::ReadablePacket;
+use l2_core::shared_packets::read::ReadablePacketBuffer;
+use tracing::instrument;
+
+#[derive(Debug, Clone)]
+pub struct RequestGetItemFromPet {
+    pub object_id: i32,
+    pub count: i64,
+}
+
+impl ReadablePacket for RequestGetItemFromPet {
+    const PACKET_ID: u8 = 0x96;
+    const EX_PACKET_ID: Option<u16> = None;
+
+    fn read(data: BytesMut) -> anyhow::Result<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data);
+        let object_id = buffer.read_i32()?;
+        let count = buffer.read_i64()?;
+        // equipped by the pet, pets don't equip items here
+        let _unknown = buffer.read_i32()?;
+        Ok(Self { object_id, count })
+    }
+}
+
+impl Message<RequestGetItemFromPet> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: RequestGetItemFromPet,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        self.get_item_from_pet(msg.object_id, msg.count).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read() {
+        let mut data = BytesMut::new();
+        data.extend_from_slice(&268_476_204i32.to_le_bytes());
+        data.extend_from_slice(&3i64.to_le_bytes());
+        data.extend_from_slice(&0i32.to_le_bytes());
+        let p = RequestGetItemFromPet::read(data).unwrap();
+        assert_eq!((p.object_id, p.count), (268_476_204, 3));
+    }
+}
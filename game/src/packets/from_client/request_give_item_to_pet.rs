@@ -0,0 +1,37 @@
+use crate::pl_client::PlayerClient;
+use bytes::BytesMut;
+use kameo::message::{Context, Message};
+use l2_core::shared_packets::common::ReadablePacket;
+use l2_core::shared_packets::read::ReadablePacketBuffer;
+use tracing::instrument;
+
+#[derive(Debug, Clone)]
+pub struct RequestGiveItemToPet {
+    pub object_id: i32,
+    pub count: i64,
+}
+
+impl ReadablePacket for RequestGiveItemToPet {
+    const PACKET_ID: u8 = 0x95;
+    const EX_PACKET_ID: Option<u16> = None;
+
+    fn read(data: BytesMut) -> anyhow::Result<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data);
+        Ok(Self {
+            object_id: buffer.read_i32()?,
+            count: buffer.read_i64()?,
+        })
+    }
+}
+
+impl Message<RequestGiveItemToPet> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: RequestGiveItemToPet,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        self.give_item_to_pet(msg.object_id, msg.count).await
+    }
+}
@@ -0,0 +1,42 @@
+use crate::packets::to_client;
+use crate::pl_client::PlayerClient;
+use bytes::BytesMut;
+use kameo::message::{Context, Message};
+use l2_core::shared_packets::common::ReadablePacket;
+use l2_core::shared_packets::read::ReadablePacketBuffer;
+use tracing::{instrument, warn};
+
+#[derive(Debug, Clone)]
+pub struct RequestHennaItemInfo {
+    pub symbol_id: i32,
+}
+
+impl ReadablePacket for RequestHennaItemInfo {
+    const PACKET_ID: u8 = 0xC4;
+    const EX_PACKET_ID: Option<u16> = None;
+
+    fn read(data: BytesMut) -> anyhow::Result<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data);
+        Ok(Self {
+            symbol_id: buffer.read_i32()?,
+        })
+    }
+}
+
+impl Message<RequestHennaItemInfo> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: RequestHennaItemInfo,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        let Some(henna) = self.controller.hennas.get_henna(msg.symbol_id) else {
+            warn!("Henna {} not found", msg.symbol_id);
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        };
+        let player = self.try_get_selected_char()?;
+        self.send_packet(to_client::HennaItemDrawInfo::new(player, henna)?)
+            .await
+    }
+}
@@ -0,0 +1,43 @@
+use crate::packets::to_client;
+use crate::pl_client::PlayerClient;
+use bytes::BytesMut;
+use kameo::message::{Context, Message};
+use l2_core::shared_packets::common::ReadablePacket;
+use l2_core::shared_packets::read::ReadablePacketBuffer;
+use tracing::{instrument, warn};
+
+#[derive(Debug, Clone)]
+pub struct RequestHennaItemRemoveInfo {
+    pub symbol_id: i32,
+}
+
+impl ReadablePacket for RequestHennaItemRemoveInfo {
+    const PACKET_ID: u8 = 0x6D;
+    const EX_PACKET_ID: Option<u16> = None;
+
+    fn read(data: BytesMut) -> anyhow::Result<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data);
+        Ok(Self {
+            symbol_id: buffer.read_i32()?,
+        })
+    }
+}
+
+impl Message<RequestHennaItemRemoveInfo> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: RequestHennaItemRemoveInfo,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let henna = player.get_hennas().find(|h| h.dye_id == msg.symbol_id);
+        let Some(henna) = henna else {
+            warn!("Henna {} is not drawn", msg.symbol_id);
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        };
+        self.send_packet(to_client::HennaItemRemoveInfo::new(player, henna)?)
+            .await
+    }
+}
@@ -0,0 +1,32 @@
+use crate::packets::to_client;
+use crate::pl_client::PlayerClient;
+use bytes::BytesMut;
+use kameo::message::{Context, Message};
+use l2_core::shared_packets::common::ReadablePacket;
+use tracing::instrument;
+
+#[derive(Debug, Clone)]
+pub struct RequestHennaRemoveList;
+
+impl ReadablePacket for RequestHennaRemoveList {
+    const PACKET_ID: u8 = 0x6C;
+    const EX_PACKET_ID: Option<u16> = None;
+
+    fn read(_data: BytesMut) -> anyhow::Result<Self> {
+        Ok(Self)
+    }
+}
+
+impl Message<RequestHennaRemoveList> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        _msg: RequestHennaRemoveList,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        self.send_packet(to_client::HennaRemoveList::new(player)?)
+            .await
+    }
+}
@@ -1,9 +1,11 @@
 use crate::movement::{calculate_distance, calculate_nearest_hit_point};
 use crate::packets::to_client;
 use crate::packets::to_client::ActionFailed;
-use crate::pl_client::{
-    ApplyBuff, ApplyDamage, ApplyHeal, FullStats, GetStats, PlayerClient, PlayerTasks,
-};
+use crate::pl_client::{ApplyBuff, ApplyDamage, ApplyHeal, PlayerClient, PlayerTasks};
+use crate::shots::ShotCharges;
+use crate::fishing::{FISHING, PUMPING, REELING};
+use crate::manor::{HARVESTING, SOWING};
+use crate::siege::{BUILD_HEADQUARTERS, SEAL_OF_RULER};
 use crate::skills::{AffectedTarget, SkillAction, classify_effects, gather_affected_targets};
 use bytes::BytesMut;
 use kameo::actor::ActorRef;
@@ -64,6 +66,7 @@ async fn apply_skill_actions(
     magic_crit_rate: f64,
     abnormal: (Option<String>, i32, i32), // (type, level, time secs)
     skill: (i32, i32),                    // (id, level)
+    shots: ShotCharges,
     targets: &[AffectedTarget],
 ) {
     let (skill_id, skill_level) = skill;
@@ -76,13 +79,14 @@ async fn apply_skill_actions(
                         attacker_stats,
                         &target.stats.stats,
                         *power,
-                        false,
-                        false,
+                        shots.sps,
+                        shots.bss,
                         mcrit,
                     );
                     target
                         .actor
                         .tell(ApplyDamage {
+                            target_id: target.id,
                             damage,
                             attacker_id,
                             attacker_name: attacker_name.to_string(),
@@ -96,12 +100,13 @@ async fn apply_skill_actions(
                         attacker_stats,
                         &target.stats.stats,
                         *power,
-                        false,
+                        shots.ss,
                         crit,
                     );
                     target
                         .actor
                         .tell(ApplyDamage {
+                            target_id: target.id,
                             damage,
                             attacker_id,
                             attacker_name: attacker_name.to_string(),
@@ -111,10 +116,17 @@ async fn apply_skill_actions(
                 }
                 SkillAction::Heal { power } => {
                     let (amount, _crit) =
-                        Formulas::calc_heal(attacker_stats, *power, magic_crit_rate, false, false);
+                        Formulas::calc_heal(
+                        attacker_stats,
+                        *power,
+                        magic_crit_rate,
+                        shots.sps,
+                        shots.bss,
+                    );
                     target
                         .actor
                         .tell(ApplyHeal {
+                            target_id: target.id,
                             amount,
                             is_percent: false,
                             is_mp: false,
@@ -127,6 +139,7 @@ async fn apply_skill_actions(
                 SkillAction::HealPercent { percent } => target
                     .actor
                     .tell(ApplyHeal {
+                        target_id: target.id,
                         amount: *percent,
                         is_percent: true,
                         is_mp: false,
@@ -138,6 +151,7 @@ async fn apply_skill_actions(
                 SkillAction::ManaHeal { power } => target
                     .actor
                     .tell(ApplyHeal {
+                        target_id: target.id,
                         amount: *power,
                         is_percent: false,
                         is_mp: true,
@@ -149,6 +163,7 @@ async fn apply_skill_actions(
                 SkillAction::ManaHealPercent { percent } => target
                     .actor
                     .tell(ApplyHeal {
+                        target_id: target.id,
                         amount: *percent,
                         is_percent: true,
                         is_mp: true,
@@ -160,6 +175,7 @@ async fn apply_skill_actions(
                 SkillAction::Buff { mods } => target
                     .actor
                     .tell(ApplyBuff {
+                        target_id: target.id,
                         skill_id,
                         skill_level,
                         caster_id: attacker_id,
@@ -193,6 +209,25 @@ impl Message<RequestMagicSkillUse> for PlayerClient {
             self.send_packet(ActionFailed::normal()?).await?;
             return Ok(());
         }
+        if matches!(msg.skill_id, SEAL_OF_RULER | BUILD_HEADQUARTERS) {
+            return self
+                .use_siege_skill(_ctx.actor_ref().clone(), msg.skill_id)
+                .await;
+        }
+        if matches!(msg.skill_id, SOWING | HARVESTING) {
+            return self.use_manor_skill(msg.skill_id, None).await;
+        }
+        if matches!(msg.skill_id, FISHING | PUMPING | REELING) {
+            return self
+                .use_fishing_skill(_ctx.actor_ref().clone(), msg.skill_id)
+                .await;
+        }
+        if let Some(servitor) = self.controller.summons.by_summon_skill(msg.skill_id) {
+            let npc_id = servitor.npc_id;
+            return self
+                .cast_summon_servitor(_ctx.actor_ref().clone(), msg.skill_id, npc_id)
+                .await;
+        }
         let (attacker_id, attacker_name, attacker_stats, (x, y, z), level, current_mp) = {
             let player = self.try_get_selected_char()?;
             let level = player.get_skill_level(msg.skill_id).unwrap_or(1);
@@ -264,7 +299,11 @@ impl Message<RequestMagicSkillUse> for PlayerClient {
         if is_bad && target_id != attacker_id && !msg.ctrl_pressed {
             let auto_attackable = {
                 let attacker = self.try_get_selected_char()?;
-                if let Ok(target_player) = target_actor.ask(crate::pl_client::GetCharInfo).await {
+                if attacker.is_own_summon(target_id) {
+                    false
+                } else if let Ok(target_player) =
+                    target_actor.ask(crate::pl_client::GetCharInfo).await
+                {
                     target_player.is_auto_attackable(attacker)
                 } else {
                     false
@@ -277,17 +316,7 @@ impl Message<RequestMagicSkillUse> for PlayerClient {
             }
         }
 
-        let target_stats = if target_id == attacker_id {
-            let player = self.try_get_selected_char()?;
-            FullStats {
-                stats: player.stats.cached_stats.clone(),
-                x: player.get_x(),
-                y: player.get_y(),
-                z: player.get_z(),
-            }
-        } else {
-            target_actor.ask(GetStats).await.anyhow()?
-        };
+        let target_stats = self.get_target_stats(target_id, &target_actor).await?;
         let (target_x, target_y, target_z) = (target_stats.x, target_stats.y, target_stats.z);
 
         // --- Range & visibility checks ---
@@ -296,6 +325,9 @@ impl Message<RequestMagicSkillUse> for PlayerClient {
             if !self.check_visibility(target_x, target_y, target_z).await? {
                 return Ok(());
             }
+            if is_bad && !self.check_peace_zone(target_x, target_y, target_z).await? {
+                return Ok(());
+            }
             let dist = calculate_distance(x, y, z, target_x, target_y, target_z).unwrap_or(0.0);
             if cast_range > 0 && dist > f64::from(cast_range + 40) {
                 if msg.shift_pressed {
@@ -427,6 +459,9 @@ impl Message<RequestMagicSkillUse> for PlayerClient {
         let affect_range = skill_data.affect_range_at(level_u8);
         let affect_limit = skill_data.affect_limit_at(level_u8);
         let magic_crit_rate = f64::from(skill_data.magic_critical_rate_at(level_u8).max(0));
+        let is_magic = skill_data.is_magic();
+        let shots = self.consume_shots(is_magic)?;
+        self.recharge_shots(!is_magic, is_magic).await?;
         let controller: Arc<_> = self.controller.clone();
         let skill_id = msg.skill_id;
         let skill_level = i32::from(level);
@@ -471,6 +506,7 @@ impl Message<RequestMagicSkillUse> for PlayerClient {
                 magic_crit_rate,
                 abnormal,
                 (skill_id, skill_level),
+                shots,
                 &targets,
             )
             .await;
@@ -0,0 +1,138 @@
+use crate::packets::to_client::{MacroList, MacroUpdateType, SystemMessage, SystemMessageType};
+use crate::pl_client::PlayerClient;
+use bytes::BytesMut;
+use entities::entities::character_macro;
+use kameo::message::{Context, Message};
+use l2_core::game_objects::player::{MacroCommand, MacroType, PlayerMacro};
+use l2_core::shared_packets::common::ReadablePacket;
+use l2_core::shared_packets::read::ReadablePacketBuffer;
+use tracing::instrument;
+
+#[derive(Debug, Clone)]
+pub struct RequestMakeMacro {
+    pub player_macro: PlayerMacro,
+}
+
+impl RequestMakeMacro {
+    const MAX_DESCRIPTION_LEN: usize = 32;
+}
+
+impl ReadablePacket for RequestMakeMacro {
+    const PACKET_ID: u8 = 0xCD;
+    const EX_PACKET_ID: Option<u16> = None;
+
+    fn read(data: BytesMut) -> anyhow::Result<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data);
+        let id = buffer.read_i32()?;
+        let name = buffer.read_c_utf16le_string()?;
+        let description = buffer.read_c_utf16le_string()?;
+        let acronym = buffer.read_c_utf16le_string()?;
+        let icon = buffer.read_i32()?;
+        let count = usize::from(buffer.read_byte()?).min(PlayerMacro::MAX_COMMANDS);
+        let mut commands = Vec::with_capacity(count);
+        for _ in 0..count {
+            let _entry = buffer.read_byte()?;
+            let macro_type = MacroType::from(buffer.read_byte()?);
+            let d1 = buffer.read_i32()?;
+            let d2 = i32::from(buffer.read_byte()?);
+            let cmd = buffer.read_c_utf16le_string()?;
+            commands.push(MacroCommand {
+                macro_type,
+                d1,
+                d2,
+                cmd,
+            });
+        }
+        Ok(Self {
+            player_macro: PlayerMacro {
+                id,
+                name,
+                description,
+                acronym,
+                icon,
+                commands,
+            },
+        })
+    }
+}
+
+impl Message<RequestMakeMacro> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: RequestMakeMacro,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        let m = msg.player_macro;
+        let error = if m.name.is_empty() {
+            Some(SystemMessageType::EnterTheNameOfTheMacro)
+        } else if m.description.chars().count() > RequestMakeMacro::MAX_DESCRIPTION_LEN {
+            Some(SystemMessageType::MacroDescriptionsMayContainUpTo32Characters)
+        } else {
+            None
+        };
+        if let Some(error) = error {
+            return self.send_packet(SystemMessage::new(error)?).await;
+        }
+        let player = self.try_get_selected_char_mut()?;
+        let char_id = player.char_model.id;
+        let Some((saved, is_new)) = player.register_macro(m) else {
+            return self
+                .send_packet(SystemMessage::new(
+                    SystemMessageType::YouMayCreateUpTo48Macros,
+                )?)
+                .await;
+        };
+        let saved = saved.clone();
+        let count = u8::try_from(player.get_macros().len())?;
+        character_macro::Model::save(&self.db_pool, saved.to_model(char_id)?).await?;
+        let update = if is_new {
+            MacroUpdateType::Add
+        } else {
+            MacroUpdateType::Modify
+        };
+        self.send_packet(MacroList::new(count, Some(&saved), update)?)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_string(data: &mut BytesMut, s: &str) {
+        for c in s.encode_utf16().chain([0]) {
+            data.extend_from_slice(&c.to_le_bytes());
+        }
+    }
+
+    #[test]
+    fn test_read() {
+        let mut data = BytesMut::new();
+        data.extend_from_slice(&0i32.to_le_bytes());
+        write_string(&mut data, "Nuke");
+        write_string(&mut data, "");
+        write_string(&mut data, "N");
+        data.extend_from_slice(&3i32.to_le_bytes());
+        data.extend_from_slice(&[1, 1, 1]);
+        data.extend_from_slice(&1177i32.to_le_bytes());
+        data.extend_from_slice(&[2]);
+        write_string(&mut data, "");
+        let p = RequestMakeMacro::read(data).unwrap();
+        let m = p.player_macro;
+        assert_eq!(
+            (m.id, m.name.as_str(), m.acronym.as_str(), m.icon),
+            (0, "Nuke", "N", 3)
+        );
+        assert_eq!(
+            m.commands,
+            vec![MacroCommand {
+                macro_type: MacroType::Skill,
+                d1: 1177,
+                d2: 2,
+                cmd: String::new(),
+            }]
+        );
+    }
+}
@@ -0,0 +1,49 @@
+use crate::pl_client::PlayerClient;
+use anyhow::bail;
+use bytes::BytesMut;
+use kameo::message::{Context, Message};
+use l2_core::shared_packets::common::ReadablePacket;
+use l2_core::shared_packets::read::ReadablePacketBuffer;
+use tracing::instrument;
+
+/// Items a player can send at once
+const MAX_ITEMS: i32 = 500;
+
+/// Sends inventory items as freight to another char of the account.
+#[derive(Debug, Clone)]
+pub struct RequestPackageSend {
+    pub target_id: i32,
+    /// `(object id, count)`
+    pub items: Vec<(i32, i64)>,
+}
+
+impl ReadablePacket for RequestPackageSend {
+    const PACKET_ID: u8 = 0xA8;
+    const EX_PACKET_ID: Option<u16> = None;
+
+    fn read(data: BytesMut) -> anyhow::Result<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data);
+        let target_id = buffer.read_i32()?;
+        let size = buffer.read_i32()?;
+        if !(1..=MAX_ITEMS).contains(&size) {
+            bail!("Invalid number of items to send: {size}");
+        }
+        let mut items = Vec::with_capacity(usize::try_from(size)?);
+        for _ in 0..size {
+            items.push((buffer.read_i32()?, buffer.read_i64()?));
+        }
+        Ok(Self { target_id, items })
+    }
+}
+
+impl Message<RequestPackageSend> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: RequestPackageSend,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        self.send_package(msg.target_id, &msg.items).await
+    }
+}
@@ -0,0 +1,36 @@
+use crate::pl_client::PlayerClient;
+use bytes::BytesMut;
+use kameo::message::{Context, Message};
+use l2_core::shared_packets::common::ReadablePacket;
+use l2_core::shared_packets::read::ReadablePacketBuffer;
+use tracing::instrument;
+
+/// The char picked from `PackageToList`, asks for the items which can be sent to it.
+#[derive(Debug, Clone)]
+pub struct RequestPackageSendableItemList {
+    pub target_id: i32,
+}
+
+impl ReadablePacket for RequestPackageSendableItemList {
+    const PACKET_ID: u8 = 0xA7;
+    const EX_PACKET_ID: Option<u16> = None;
+
+    fn read(data: BytesMut) -> anyhow::Result<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data);
+        Ok(Self {
+            target_id: buffer.read_i32()?,
+        })
+    }
+}
+
+impl Message<RequestPackageSendableItemList> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: RequestPackageSendableItemList,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        self.show_package_sendable_list(msg.target_id).await
+    }
+}
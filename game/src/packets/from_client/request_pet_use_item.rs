@@ -0,0 +1,40 @@
+use crate::packets::to_client::ActionFailed;
+use crate::pl_client::PlayerClient;
+use bytes::BytesMut;
+use kameo::message::{Context, Message};
+use l2_core::shared_packets::common::ReadablePacket;
+use l2_core::shared_packets::read::ReadablePacketBuffer;
+use tracing::instrument;
+
+/// Item of the pet inventory used from the pet window, only food for now.
+#[derive(Debug, Clone)]
+pub struct RequestPetUseItem {
+    pub object_id: i32,
+}
+
+impl ReadablePacket for RequestPetUseItem {
+    const PACKET_ID: u8 = 0x8A;
+    const EX_PACKET_ID: Option<u16> = None;
+
+    fn read(data: BytesMut) -> anyhow::Result<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data);
+        Ok(Self {
+            object_id: buffer.read_i32()?,
+        })
+    }
+}
+
+impl Message<RequestPetUseItem> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: RequestPetUseItem,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        if !self.pet_eat(msg.object_id).await? {
+            self.send_packet(ActionFailed::normal()?).await?;
+        }
+        Ok(())
+    }
+}
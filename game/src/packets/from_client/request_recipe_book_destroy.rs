@@ -0,0 +1,48 @@
+use crate::pl_client::PlayerClient;
+use bytes::BytesMut;
+use kameo::message::{Context, Message};
+use l2_core::shared_packets::common::ReadablePacket;
+use l2_core::shared_packets::read::ReadablePacketBuffer;
+use tracing::instrument;
+
+#[derive(Debug, Clone)]
+pub struct RequestRecipeBookDestroy {
+    pub recipe_id: i32,
+}
+
+impl ReadablePacket for RequestRecipeBookDestroy {
+    const PACKET_ID: u8 = 0xB6;
+    const EX_PACKET_ID: Option<u16> = None;
+
+    fn read(data: BytesMut) -> anyhow::Result<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data);
+        Ok(Self {
+            recipe_id: buffer.read_i32()?,
+        })
+    }
+}
+
+impl Message<RequestRecipeBookDestroy> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: RequestRecipeBookDestroy,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        self.forget_recipe(msg.recipe_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read() {
+        let mut data = BytesMut::new();
+        data.extend_from_slice(&3i32.to_le_bytes());
+        let p = RequestRecipeBookDestroy::read(data).unwrap();
+        assert_eq!(p.recipe_id, 3);
+    }
+}
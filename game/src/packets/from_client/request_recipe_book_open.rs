@@ -0,0 +1,48 @@
+use crate::pl_client::PlayerClient;
+use bytes::BytesMut;
+use kameo::message::{Context, Message};
+use l2_core::shared_packets::common::ReadablePacket;
+use l2_core::shared_packets::read::ReadablePacketBuffer;
+use tracing::instrument;
+
+#[derive(Debug, Clone)]
+pub struct RequestRecipeBookOpen {
+    pub dwarven: bool,
+}
+
+impl ReadablePacket for RequestRecipeBookOpen {
+    const PACKET_ID: u8 = 0xB5;
+    const EX_PACKET_ID: Option<u16> = None;
+
+    fn read(data: BytesMut) -> anyhow::Result<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data);
+        Ok(Self {
+            dwarven: buffer.read_i32()? == 0,
+        })
+    }
+}
+
+impl Message<RequestRecipeBookOpen> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: RequestRecipeBookOpen,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        self.send_recipe_book(msg.dwarven).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read() {
+        let mut data = BytesMut::new();
+        data.extend_from_slice(&1i32.to_le_bytes());
+        let p = RequestRecipeBookOpen::read(data).unwrap();
+        assert!(!p.dwarven);
+    }
+}
@@ -0,0 +1,75 @@
+use crate::pl_client::PlayerClient;
+use anyhow::ensure;
+use bytes::BytesMut;
+use kameo::message::{Context, Message};
+use l2_core::game_objects::player::{MANUFACTURE_SHOP_LIMIT, ManufactureItem};
+use l2_core::shared_packets::common::ReadablePacket;
+use l2_core::shared_packets::read::ReadablePacketBuffer;
+use tracing::instrument;
+
+#[derive(Debug, Clone)]
+pub struct RequestRecipeShopListSet {
+    pub items: Vec<ManufactureItem>,
+}
+
+impl ReadablePacket for RequestRecipeShopListSet {
+    const PACKET_ID: u8 = 0xBB;
+    const EX_PACKET_ID: Option<u16> = None;
+
+    fn read(data: BytesMut) -> anyhow::Result<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data);
+        let count = usize::try_from(buffer.read_i32()?)?;
+        ensure!(
+            count <= MANUFACTURE_SHOP_LIMIT,
+            "Too many recipes in the manufacture shop: {count}"
+        );
+        let mut items = Vec::with_capacity(count);
+        for _ in 0..count {
+            items.push(ManufactureItem {
+                recipe_id: buffer.read_i32()?,
+                cost: buffer.read_i64()?,
+            });
+        }
+        Ok(Self { items })
+    }
+}
+
+impl Message<RequestRecipeShopListSet> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: RequestRecipeShopListSet,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        self.open_manufacture_shop(msg.items).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read() {
+        let mut data = BytesMut::new();
+        data.extend_from_slice(&1i32.to_le_bytes());
+        data.extend_from_slice(&3i32.to_le_bytes());
+        data.extend_from_slice(&500i64.to_le_bytes());
+        let p = RequestRecipeShopListSet::read(data).unwrap();
+        assert_eq!(
+            p.items,
+            vec![ManufactureItem {
+                recipe_id: 3,
+                cost: 500
+            }]
+        );
+    }
+
+    #[test]
+    fn test_read_too_many() {
+        let mut data = BytesMut::new();
+        data.extend_from_slice(&100i32.to_le_bytes());
+        assert!(RequestRecipeShopListSet::read(data).is_err());
+    }
+}
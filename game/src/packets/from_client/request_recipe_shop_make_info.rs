@@ -0,0 +1,52 @@
+use crate::pl_client::PlayerClient;
+use bytes::BytesMut;
+use kameo::message::{Context, Message};
+use l2_core::shared_packets::common::ReadablePacket;
+use l2_core::shared_packets::read::ReadablePacketBuffer;
+use tracing::instrument;
+
+#[derive(Debug, Clone)]
+pub struct RequestRecipeShopMakeInfo {
+    pub manufacturer_id: i32,
+    pub recipe_id: i32,
+}
+
+impl ReadablePacket for RequestRecipeShopMakeInfo {
+    const PACKET_ID: u8 = 0xBE;
+    const EX_PACKET_ID: Option<u16> = None;
+
+    fn read(data: BytesMut) -> anyhow::Result<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data);
+        Ok(Self {
+            manufacturer_id: buffer.read_i32()?,
+            recipe_id: buffer.read_i32()?,
+        })
+    }
+}
+
+impl Message<RequestRecipeShopMakeInfo> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: RequestRecipeShopMakeInfo,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        self.send_manufacture_item_info(msg.manufacturer_id, msg.recipe_id)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read() {
+        let mut data = BytesMut::new();
+        data.extend_from_slice(&7i32.to_le_bytes());
+        data.extend_from_slice(&3i32.to_le_bytes());
+        let p = RequestRecipeShopMakeInfo::read(data).unwrap();
+        assert_eq!((p.manufacturer_id, p.recipe_id), (7, 3));
+    }
+}
@@ -0,0 +1,55 @@
+use crate::pl_client::PlayerClient;
+use bytes::BytesMut;
+use kameo::message::{Context, Message};
+use l2_core::shared_packets::common::ReadablePacket;
+use l2_core::shared_packets::read::ReadablePacketBuffer;
+use tracing::instrument;
+
+#[derive(Debug, Clone)]
+pub struct RequestRecipeShopMakeItem {
+    pub manufacturer_id: i32,
+    pub recipe_id: i32,
+}
+
+impl ReadablePacket for RequestRecipeShopMakeItem {
+    const PACKET_ID: u8 = 0xBF;
+    const EX_PACKET_ID: Option<u16> = None;
+
+    fn read(data: BytesMut) -> anyhow::Result<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data);
+        // the price the customer saw comes last, the price of the shop is what counts
+        Ok(Self {
+            manufacturer_id: buffer.read_i32()?,
+            recipe_id: buffer.read_i32()?,
+        })
+    }
+}
+
+impl Message<RequestRecipeShopMakeItem> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: RequestRecipeShopMakeItem,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        self.order_manufacture(msg.manufacturer_id, msg.recipe_id)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read() {
+        let mut data = BytesMut::new();
+        data.extend_from_slice(&7i32.to_le_bytes());
+        data.extend_from_slice(&3i32.to_le_bytes());
+        data.extend_from_slice(&100i64.to_le_bytes());
+        let p = RequestRecipeShopMakeItem::read(data).unwrap();
+        assert_eq!((p.manufacturer_id, p.recipe_id), (7, 3));
+    }
+}
@@ -0,0 +1,48 @@
+use crate::pl_client::PlayerClient;
+use bytes::BytesMut;
+use kameo::message::{Context, Message};
+use l2_core::shared_packets::common::ReadablePacket;
+use l2_core::shared_packets::read::ReadablePacketBuffer;
+use tracing::instrument;
+
+#[derive(Debug, Clone)]
+pub struct RequestRecipeShopMessageSet {
+    pub message: String,
+}
+
+impl ReadablePacket for RequestRecipeShopMessageSet {
+    const PACKET_ID: u8 = 0xBA;
+    const EX_PACKET_ID: Option<u16> = None;
+
+    fn read(data: BytesMut) -> anyhow::Result<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data);
+        Ok(Self {
+            message: buffer.read_c_utf16le_string()?,
+        })
+    }
+}
+
+impl Message<RequestRecipeShopMessageSet> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: RequestRecipeShopMessageSet,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        self.set_manufacture_message(msg.message).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read() {
+        let mut data = BytesMut::new();
+        data.extend_from_slice(&[b'H', 0, b'i', 0, 0, 0]);
+        let p = RequestRecipeShopMessageSet::read(data).unwrap();
+        assert_eq!(p.message, "Hi");
+    }
+}
@@ -0,0 +1,48 @@
+use crate::pl_client::PlayerClient;
+use anyhow::bail;
+use bytes::BytesMut;
+use kameo::message::{Context, Message};
+use l2_core::shared_packets::common::ReadablePacket;
+use l2_core::shared_packets::read::ReadablePacketBuffer;
+use tracing::instrument;
+
+/// Items a player can sell at once
+const MAX_ITEMS: i32 = 500;
+
+#[derive(Debug, Clone)]
+pub struct RequestSellItem {
+    pub list_id: i32,
+    /// `(object id, item id, count)`
+    pub items: Vec<(i32, i32, i64)>,
+}
+
+impl ReadablePacket for RequestSellItem {
+    const PACKET_ID: u8 = 0x37;
+    const EX_PACKET_ID: Option<u16> = None;
+
+    fn read(data: BytesMut) -> anyhow::Result<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data);
+        let list_id = buffer.read_i32()?;
+        let size = buffer.read_i32()?;
+        if !(1..=MAX_ITEMS).contains(&size) {
+            bail!("Invalid number of items to sell: {size}");
+        }
+        let mut items = Vec::with_capacity(usize::try_from(size)?);
+        for _ in 0..size {
+            items.push((buffer.read_i32()?, buffer.read_i32()?, buffer.read_i64()?));
+        }
+        Ok(Self { list_id, items })
+    }
+}
+
+impl Message<RequestSellItem> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: RequestSellItem,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        self.sell_items(msg.list_id, &msg.items).await
+    }
+}
@@ -0,0 +1,37 @@
+use crate::pl_client::PlayerClient;
+use bytes::BytesMut;
+use kameo::message::{Context, Message};
+use l2_core::shared_packets::common::ReadablePacket;
+use l2_core::shared_packets::read::ReadablePacketBuffer;
+use tracing::instrument;
+
+#[derive(Debug, Clone)]
+pub struct RequestSendFriendMsg {
+    pub message: String,
+    pub receiver: String,
+}
+
+impl ReadablePacket for RequestSendFriendMsg {
+    const PACKET_ID: u8 = 0x6B;
+    const EX_PACKET_ID: Option<u16> = None;
+
+    fn read(data: BytesMut) -> anyhow::Result<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data);
+        Ok(Self {
+            message: buffer.read_c_utf16le_string()?,
+            receiver: buffer.read_c_utf16le_string()?,
+        })
+    }
+}
+
+impl Message<RequestSendFriendMsg> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: RequestSendFriendMsg,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        self.send_friend_message(&msg.receiver, msg.message).await
+    }
+}
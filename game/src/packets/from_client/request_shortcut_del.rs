@@ -0,0 +1,51 @@
+use crate::pl_client::PlayerClient;
+use bytes::BytesMut;
+use entities::entities::character_shortcut;
+use kameo::message::{Context, Message};
+use l2_core::game_objects::player::Shortcut;
+use l2_core::shared_packets::common::ReadablePacket;
+use l2_core::shared_packets::read::ReadablePacketBuffer;
+use tracing::instrument;
+
+#[derive(Debug, Clone)]
+pub struct RequestShortcutDel {
+    pub slot: i16,
+    pub page: i16,
+}
+
+impl ReadablePacket for RequestShortcutDel {
+    const PACKET_ID: u8 = 0x3F;
+    const EX_PACKET_ID: Option<u16> = None;
+
+    fn read(data: BytesMut) -> anyhow::Result<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data);
+        let (slot, page) = Shortcut::split_position(buffer.read_i32()?)?;
+        Ok(Self { slot, page })
+    }
+}
+
+impl Message<RequestShortcutDel> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: RequestShortcutDel,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        // the client doesn't need a confirmation, it's just to inform the server
+        let player = self.try_get_selected_char_mut()?;
+        if player.delete_shortcut(msg.slot, msg.page).is_some() {
+            let char_id = player.char_model.id;
+            let class_index = player.get_class_index();
+            character_shortcut::Model::delete(
+                &self.db_pool,
+                char_id,
+                msg.slot,
+                msg.page,
+                class_index,
+            )
+            .await?;
+        }
+        Ok(())
+    }
+}
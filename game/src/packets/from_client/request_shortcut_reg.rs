@@ -0,0 +1,100 @@
+use crate::packets::to_client::ShortcutRegister;
+use crate::pl_client::PlayerClient;
+use bytes::BytesMut;
+use entities::entities::character_shortcut;
+use kameo::message::{Context, Message};
+use l2_core::game_objects::player::{Shortcut, ShortcutType};
+use l2_core::shared_packets::common::ReadablePacket;
+use l2_core::shared_packets::read::ReadablePacketBuffer;
+use tracing::{instrument, warn};
+
+#[derive(Debug, Clone)]
+pub struct RequestShortcutReg {
+    pub shortcut: Shortcut,
+}
+
+impl ReadablePacket for RequestShortcutReg {
+    const PACKET_ID: u8 = 0x3D;
+    const EX_PACKET_ID: Option<u16> = None;
+
+    fn read(data: BytesMut) -> anyhow::Result<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data);
+        let shortcut_type = ShortcutType::from(buffer.read_i32()?);
+        let (slot, page) = Shortcut::split_position(buffer.read_i32()?)?;
+        Ok(Self {
+            shortcut: Shortcut {
+                slot,
+                page,
+                shortcut_type,
+                id: buffer.read_i32()?,
+                level: buffer.read_i16()?,
+                sub_level: buffer.read_i16()?,
+                character_type: buffer.read_i32()?,
+            },
+        })
+    }
+}
+
+impl Message<RequestShortcutReg> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: RequestShortcutReg,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        let mut sc = msg.shortcut;
+        let player = self.try_get_selected_char_mut()?;
+        let valid = match sc.shortcut_type {
+            ShortcutType::None => false,
+            ShortcutType::Item => player.inventory.items.contains_key(&sc.id),
+            ShortcutType::Skill => {
+                if let Some(level) = player.get_skill_level(sc.id) {
+                    sc.level = level;
+                    true
+                } else {
+                    false
+                }
+            }
+            ShortcutType::Macro => player.get_macro(sc.id).is_some(),
+            _ => true,
+        };
+        if !valid {
+            warn!("Invalid shortcut {sc:?}, possible cheating");
+            return Ok(());
+        }
+        let model = sc.to_model(player.char_model.id, player.get_class_index());
+        player.register_shortcut(sc.clone());
+        character_shortcut::Model::save(&self.db_pool, model).await?;
+        self.send_packet(ShortcutRegister::new(&sc)?).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read() {
+        let mut data = BytesMut::new();
+        data.extend_from_slice(&2i32.to_le_bytes());
+        data.extend_from_slice(&13i32.to_le_bytes());
+        data.extend_from_slice(&1177i32.to_le_bytes());
+        data.extend_from_slice(&5i16.to_le_bytes());
+        data.extend_from_slice(&0i16.to_le_bytes());
+        data.extend_from_slice(&1i32.to_le_bytes());
+        let p = RequestShortcutReg::read(data).unwrap();
+        assert_eq!(
+            p.shortcut,
+            Shortcut {
+                slot: 1,
+                page: 1,
+                shortcut_type: ShortcutType::Skill,
+                id: 1177,
+                level: 5,
+                sub_level: 0,
+                character_type: 1,
+            }
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use crate::packets::to_client::{CharSelectionInfo, DeleteObject, RestartResponse};
+use crate::packets::to_client::{CharSelectionInfo, DeleteObject, RestartResponse, SystemMessage};
 use crate::pl_client::{ClientStatus, PlayerClient};
 use bytes::BytesMut;
 use entities::entities::character;
@@ -27,12 +27,21 @@ impl Message<RequestRestart> for PlayerClient {
         _msg: RequestRestart,
         _ctx: &mut Context<Self, Self::Reply>,
     ) -> anyhow::Result<()> {
-        //todo: if can logout (olymp, pvp flag, events, etc.)
+        //todo: if can logout (pvp flag, events, etc.)
+        if self.try_get_selected_char()?.is_in_olympiad_mode() {
+            self.send_packet(SystemMessage::text(
+                "You can't restart while taking part in the Olympiad.",
+            )?)
+            .await?;
+            return self.send_packet(RestartResponse::new(false)?).await;
+        }
         let session_id = self.try_get_session_key()?.get_play_session_id();
         let user_name = self.try_get_user()?.username.clone();
 
         self.stop_movement();
+        self.end_fishing(false).await?;
         self.selected_target = None;
+        self.selected_monster = None;
 
         let (selected_slot, updated_char_model) = {
             let selected_slot = self
@@ -53,7 +62,9 @@ impl Message<RequestRestart> for PlayerClient {
         )
         .await?;
 
-        let player_obj_id = self.try_get_selected_char()?.get_object_id();
+        let player = self.try_get_selected_char()?;
+        let (player_obj_id, char_id) = (player.get_object_id(), player.char_model.id);
+        self.controller.leave_game(char_id);
         let chars = self.try_get_account_chars()?;
 
         let p = CharSelectionInfo::new(&user_name, session_id, &self.controller, chars)?;
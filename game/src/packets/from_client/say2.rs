@@ -0,0 +1,76 @@
+use crate::packets::to_client::ChatType;
+use crate::pl_client::PlayerClient;
+use bytes::BytesMut;
+use kameo::message::{Context, Message};
+use l2_core::shared_packets::common::ReadablePacket;
+use l2_core::shared_packets::read::ReadablePacketBuffer;
+use tracing::{instrument, warn};
+
+#[derive(Debug, Clone)]
+pub struct Say2 {
+    pub text: String,
+    pub chat_type: u32,
+    /// Receiver of a whisper
+    pub target: Option<String>,
+}
+
+impl ReadablePacket for Say2 {
+    const PACKET_ID: u8 = 0x49;
+    const EX_PACKET_ID: Option<u16> = None;
+
+    fn read(data: BytesMut) -> anyhow::Result<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data);
+        let text = buffer.read_c_utf16le_string()?;
+        let chat_type = buffer.read_u32()?;
+        let target = if chat_type == ChatType::Whisper as u32 {
+            Some(buffer.read_c_utf16le_string()?)
+        } else {
+            None
+        };
+        Ok(Self {
+            text,
+            chat_type,
+            target,
+        })
+    }
+}
+
+impl Message<Say2> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: Say2,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        match ChatType::try_from(msg.chat_type)? {
+            ChatType::HeroVoice => self.say_hero_voice(&msg.text).await,
+            ChatType::Whisper => {
+                let target = msg.target.unwrap_or_default();
+                self.send_whisper(&target, msg.text).await
+            }
+            //todo: other chat channels
+            chat_type => {
+                warn!("Chat {chat_type:?} is not implemented yet");
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read() {
+        let data = BytesMut::from(&[104u8, 0, 105, 0, 0, 0, 17, 0, 0, 0][..]);
+        let packet = Say2::read(data).unwrap();
+        assert_eq!(packet.text, "hi");
+        assert_eq!(packet.chat_type, 17);
+        assert_eq!(packet.target, None);
+        let data = BytesMut::from(&[104u8, 0, 0, 0, 2, 0, 0, 0, 65, 0, 0, 0][..]);
+        let packet = Say2::read(data).unwrap();
+        assert_eq!(packet.target.as_deref(), Some("A"));
+    }
+}
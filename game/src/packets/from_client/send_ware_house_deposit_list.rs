@@ -0,0 +1,47 @@
+use crate::pl_client::PlayerClient;
+use anyhow::bail;
+use bytes::BytesMut;
+use kameo::message::{Context, Message};
+use l2_core::shared_packets::common::ReadablePacket;
+use l2_core::shared_packets::read::ReadablePacketBuffer;
+use tracing::instrument;
+
+/// Items a player can move at once
+const MAX_ITEMS: i32 = 500;
+
+/// Items to put into the open warehouse.
+#[derive(Debug, Clone)]
+pub struct SendWareHouseDepositList {
+    /// `(object id, count)`
+    pub items: Vec<(i32, i64)>,
+}
+
+impl ReadablePacket for SendWareHouseDepositList {
+    const PACKET_ID: u8 = 0x3B;
+    const EX_PACKET_ID: Option<u16> = None;
+
+    fn read(data: BytesMut) -> anyhow::Result<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data);
+        let size = buffer.read_i32()?;
+        if !(1..=MAX_ITEMS).contains(&size) {
+            bail!("Invalid number of items: {size}");
+        }
+        let mut items = Vec::with_capacity(usize::try_from(size)?);
+        for _ in 0..size {
+            items.push((buffer.read_i32()?, buffer.read_i64()?));
+        }
+        Ok(Self { items })
+    }
+}
+
+impl Message<SendWareHouseDepositList> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: SendWareHouseDepositList,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        self.deposit_items(&msg.items).await
+    }
+}
@@ -0,0 +1,119 @@
+use crate::manor::{HARVESTING, SOWING};
+use crate::packets::to_client;
+use crate::pl_client::PlayerClient;
+use bytes::BytesMut;
+use kameo::message::{Context, Message};
+use l2_core::data::items::ItemHandler;
+use l2_core::shared_packets::common::ReadablePacket;
+use l2_core::shared_packets::read::ReadablePacketBuffer;
+use tracing::{instrument, warn};
+
+#[derive(Debug, Clone)]
+pub struct UseItem {
+    pub object_id: i32,
+}
+
+impl ReadablePacket for UseItem {
+    const PACKET_ID: u8 = 0x14;
+    const EX_PACKET_ID: Option<u16> = None;
+
+    fn read(data: BytesMut) -> anyhow::Result<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data);
+        Ok(Self {
+            object_id: buffer.read_i32()?,
+        })
+    }
+}
+
+impl Message<UseItem> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, ctx))]
+    async fn handle(
+        &mut self,
+        msg: UseItem,
+        ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let Some(item) = player.inventory.items.get(&msg.object_id) else {
+            warn!(
+                "Item {} is not in inventory, possible cheating",
+                msg.object_id
+            );
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        };
+        let item_id = item.item_model.item_id;
+        let template = self.controller.items_data.get_template(item_id);
+        if player.is_teleporting() || self.is_casting() {
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        }
+        // taking the rod off the hand reels the line in
+        let rod_in_hand = player
+            .get_weapon()
+            .is_some_and(|w| w.object_id.value() == msg.object_id)
+            && template.is_some_and(|t| t.fishing_rod);
+        if rod_in_hand && player.is_fishing() {
+            return self.end_fishing(false).await;
+        }
+        //todo: equip weapons and armor, use shots and other consumables
+        match template.and_then(|t| t.handler.map(|h| (h, t.skill_id))) {
+            Some((ItemHandler::Escape { cast_time_ms }, skill_id)) => {
+                self.escape_to_town(
+                    ctx.actor_ref().clone(),
+                    msg.object_id,
+                    skill_id.unwrap_or_default(),
+                    cast_time_ms,
+                )
+                .await?;
+            }
+            Some((ItemHandler::BookmarkSlots { count }, _)) => {
+                if self.increase_bookmark_slot(count).await? {
+                    self.destroy_item_by_item_id(item_id, 1).await?;
+                }
+            }
+            Some((ItemHandler::Vitality { points }, _)) => {
+                if self.use_vitality_item(points).await? {
+                    self.destroy_item_by_item_id(item_id, 1).await?;
+                }
+            }
+            Some((ItemHandler::PetCollar, _)) => {
+                self.use_pet_collar(ctx.actor_ref().clone(), msg.object_id)
+                    .await?;
+            }
+            Some((ItemHandler::PetFood { .. }, _)) => {
+                self.feed_pet(item_id).await?;
+            }
+            Some((ItemHandler::Recipe, _)) => {
+                self.learn_recipe(item_id).await?;
+            }
+            Some((ItemHandler::Seed, skill_id)) => {
+                self.use_manor_skill(skill_id.unwrap_or(SOWING), Some(item_id))
+                    .await?;
+            }
+            Some((ItemHandler::Harvester, skill_id)) => {
+                self.use_manor_skill(skill_id.unwrap_or(HARVESTING), None)
+                    .await?;
+            }
+            Some((ItemHandler::EnchantScroll { .. }, _)) => {
+                self.use_enchant_scroll(msg.object_id).await?;
+            }
+            None => {
+                self.send_packet(to_client::ActionFailed::normal()?).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read() {
+        let mut data = BytesMut::new();
+        data.extend_from_slice(&268_476_204i32.to_le_bytes());
+        data.extend_from_slice(&0i32.to_le_bytes());
+        let p = UseItem::read(data).unwrap();
+        assert_eq!(p.object_id, 268_476_204);
+    }
+}
@@ -39,10 +39,14 @@ impl Message<ValidatePosition> for PlayerClient {
     ) -> anyhow::Result<()> {
         //todo: for some reason client sends it too early so selected char is not set yet
         if let Ok(char) = self.try_get_selected_char_mut() {
-            //todo: check if char is teleporting or casting spells, if so, do not do anything
+            if char.is_teleporting() {
+                return Ok(());
+            }
+            //todo: check if char is casting spells, if so, do not do anything
             warn!("todo: validate position");
             char.set_location(msg.x, msg.y, msg.z)?;
             char.set_location_heading(msg.heading);
+            self.revalidate_zones().await?;
         }
         Ok(())
     }
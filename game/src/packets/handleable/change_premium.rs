@@ -0,0 +1,17 @@
+use crate::ls_client::LoginServerClient;
+use kameo::message::{Context, Message};
+use l2_core::shared_packets::gs_2_ls::ChangePremium;
+use l2_core::traits::ServerToServer;
+use tracing::instrument;
+
+impl Message<ChangePremium> for LoginServerClient {
+    type Reply = anyhow::Result<()>;
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: ChangePremium,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        self.send_packet(msg).await
+    }
+}
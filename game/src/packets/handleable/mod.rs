@@ -1,4 +1,5 @@
 mod change_password;
+mod change_premium;
 mod gs_auth_response;
 mod gs_login_fail;
 mod init_ls;
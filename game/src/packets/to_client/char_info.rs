@@ -88,7 +88,7 @@ impl CharInfo {
         inst.buffer.write_u16(p.get_swim_walk_speed())?;
         inst.buffer.write_u16(p.get_fly_run_speed())?;
         inst.buffer.write_u16(p.get_fly_walk_speed())?;
-        //todo: wtf is this? We need to write fly speed 2 times?
+        // the client reads the fly speeds for the flying mount and once more for the flight
         inst.buffer.write_u16(p.get_fly_run_speed())?;
         inst.buffer.write_u16(p.get_fly_walk_speed())?;
 
@@ -1,4 +1,5 @@
 use l2_core::game_objects::player::Player;
+use l2_core::game_objects::summon::Summon;
 use l2_core::shared_packets::write::SendablePacketBuffer;
 use macro_common::SendablePacket;
 
@@ -31,4 +32,26 @@ impl CharMoveToLocation {
 
         Ok(inst)
     }
+
+    /// The summon walks from where it is.
+    pub fn for_summon(
+        summon: &Summon,
+        target_x: i32,
+        target_y: i32,
+        target_z: i32,
+    ) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        let location = summon.get_location();
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_i32(summon.get_object_id())?;
+        inst.buffer.write_i32(target_x)?;
+        inst.buffer.write_i32(target_y)?;
+        inst.buffer.write_i32(target_z)?;
+        inst.buffer.write_i32(location.x)?;
+        inst.buffer.write_i32(location.y)?;
+        inst.buffer.write_i32(location.z)?;
+        Ok(inst)
+    }
 }
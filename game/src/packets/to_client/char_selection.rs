@@ -126,7 +126,7 @@ impl CharSelectionInfo {
             buffer.write_i32(i32::from(char_info.char_model.access_level != -100))?;
             buffer.write_bool(char_info.char_model.nobless)?;
             buffer.write(
-                if controller.hero_list.contains_key(&char_info.char_model.id) {
+                if controller.hero_until(char_info.char_model.id).is_some() {
                     2
                 } else {
                     0
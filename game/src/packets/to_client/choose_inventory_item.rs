@@ -0,0 +1,36 @@
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+
+/// Asks the player to pick the item the scroll is used on.
+#[derive(Debug, Clone, SendablePacket)]
+pub struct ChooseInventoryItem {
+    pub(crate) buffer: SendablePacketBuffer,
+}
+
+impl ChooseInventoryItem {
+    pub const PACKET_ID: u8 = 0x7C;
+
+    pub fn new(scroll_item_id: i32) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_i32(scroll_item_id)?;
+        Ok(inst)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use l2_core::shared_packets::common::SendablePacket;
+
+    #[test]
+    fn test_choose_inventory_item() {
+        let p = ChooseInventoryItem::new(955).unwrap();
+        assert_eq!(
+            [0x7C, 0xBB, 3, 0, 0],
+            p.get_buffer().get_data_mut(false)[2..]
+        );
+    }
+}
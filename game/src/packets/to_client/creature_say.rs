@@ -0,0 +1,84 @@
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+
+/// Chat channels, the client id of each is sent with the message.
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChatType {
+    General = 0,
+    Shout = 1,
+    Whisper = 2,
+    Party = 3,
+    Clan = 4,
+    Trade = 8,
+    Alliance = 9,
+    Announcement = 10,
+    HeroVoice = 17,
+}
+
+impl TryFrom<u32> for ChatType {
+    type Error = anyhow::Error;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        Ok(match value {
+            0 => Self::General,
+            1 => Self::Shout,
+            2 => Self::Whisper,
+            3 => Self::Party,
+            4 => Self::Clan,
+            8 => Self::Trade,
+            9 => Self::Alliance,
+            10 => Self::Announcement,
+            17 => Self::HeroVoice,
+            _ => anyhow::bail!("Unknown chat type {value}"),
+        })
+    }
+}
+
+/// Chat message of a player
+#[derive(Debug, Clone, SendablePacket)]
+pub struct CreatureSay {
+    pub(crate) buffer: SendablePacketBuffer,
+}
+
+impl CreatureSay {
+    pub const PACKET_ID: u8 = 0x4A;
+
+    pub fn new(
+        object_id: i32,
+        chat_type: ChatType,
+        name: &str,
+        text: &str,
+    ) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_i32(object_id)?;
+        inst.buffer.write_u32(chat_type as u32)?;
+        inst.buffer.write_c_utf16le_string(Some(name))?;
+        inst.buffer.write_i32(-1)?; // npc string id
+        inst.buffer.write_c_utf16le_string(Some(text))?;
+        Ok(inst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use l2_core::shared_packets::common::SendablePacket;
+
+    #[test]
+    fn test_creature_say() {
+        let packet = CreatureSay::new(7, ChatType::HeroVoice, "A", "hi").unwrap();
+        assert_eq!(
+            [
+                74, 7, 0, 0, 0, 17, 0, 0, 0, 65, 0, 0, 0, 255, 255, 255, 255, 104, 0, 105, 0, 0,
+                0
+            ],
+            packet.get_buffer().get_data_mut(false)[2..]
+        );
+        assert_eq!(ChatType::try_from(17).unwrap(), ChatType::HeroVoice);
+        assert!(ChatType::try_from(99).is_err());
+    }
+}
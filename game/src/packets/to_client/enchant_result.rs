@@ -0,0 +1,67 @@
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+
+/// How the enchant ended, the client shows the result in the enchant window
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnchantResultType {
+    Success = 0,
+    /// The item broke into the crystals
+    Crystallized = 1,
+    Cancelled = 2,
+    /// The enchant level went back to 0
+    Reset = 3,
+    /// The item broke, it had no crystals to give
+    NoCrystals = 4,
+    /// The blessed scroll kept the enchant level
+    Stayed = 5,
+}
+
+#[derive(Debug, Clone, SendablePacket)]
+pub struct EnchantResult {
+    pub(crate) buffer: SendablePacketBuffer,
+}
+
+impl EnchantResult {
+    pub const PACKET_ID: u8 = 0x87;
+
+    pub fn new(
+        result: EnchantResultType,
+        crystal_id: i32,
+        crystal_count: i64,
+        enchant_level: i32,
+    ) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_i32(result as i32)?;
+        inst.buffer.write_i32(crystal_id)?;
+        inst.buffer.write_i64(crystal_count)?;
+        inst.buffer.write_i32(enchant_level)?;
+        Ok(inst)
+    }
+
+    pub fn cancelled() -> anyhow::Result<Self> {
+        Self::new(EnchantResultType::Cancelled, 0, 0, 0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use l2_core::shared_packets::common::SendablePacket;
+
+    #[test]
+    fn test_enchant_result() {
+        let p = EnchantResult::new(EnchantResultType::Crystallized, 1458, 764, 0).unwrap();
+        let mut expected = vec![0x87];
+        expected.extend_from_slice(&1i32.to_le_bytes());
+        expected.extend_from_slice(&1458i32.to_le_bytes());
+        expected.extend_from_slice(&764i64.to_le_bytes());
+        expected.extend_from_slice(&0i32.to_le_bytes());
+        assert_eq!(expected, p.get_buffer().get_data_mut(false)[2..]);
+        let p = EnchantResult::cancelled().unwrap();
+        assert_eq!(p.get_buffer().get_data_mut(false)[3], 2);
+    }
+}
@@ -2,7 +2,6 @@ use l2_core::game_objects::player::Player;
 use l2_core::shared_packets::write::SendablePacketBuffer;
 use macro_common::SendablePacket;
 
-#[allow(unused)]
 #[derive(Debug, Clone, SendablePacket)]
 pub struct BookmarkInfo {
     pub(crate) buffer: SendablePacketBuffer,
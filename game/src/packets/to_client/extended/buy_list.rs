@@ -0,0 +1,78 @@
+use crate::packets::to_client::item_list::write_product;
+use l2_core::data::buy_lists::{BuyList as BuyListData, price_with_tax};
+use l2_core::data::items::ItemsData;
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+
+/// Buy tab of the merchant window.
+#[derive(Debug, Clone, SendablePacket)]
+pub struct BuyList {
+    pub(crate) buffer: SendablePacketBuffer,
+}
+
+impl BuyList {
+    pub const PACKET_ID: u8 = 0xFE;
+    pub const EX_PACKET_ID: u16 = 0xB8;
+
+    pub fn new(
+        list: &BuyListData,
+        items: &ItemsData,
+        adena: u64,
+        inventory_size: usize,
+        tax_rate: u8,
+    ) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        let goods: Vec<_> = list
+            .items
+            .iter()
+            .filter_map(|i| Some((i.item_id, i.get_price(items)?)))
+            .collect();
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_u16(Self::EX_PACKET_ID)?;
+        inst.buffer.write_u32(0u32)?; // buy
+        inst.buffer.write_i64(i64::try_from(adena)?)?;
+        inst.buffer.write_i32(list.id)?;
+        inst.buffer.write_u32(u32::try_from(inventory_size)?)?;
+        inst.buffer.write_u16(u16::try_from(goods.len())?)?;
+        for (item_id, price) in goods {
+            write_product(&mut inst.buffer, item_id, 0)?;
+            inst.buffer
+                .write_i64(i64::try_from(price_with_tax(price, tax_rate))?)?;
+        }
+        Ok(inst)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use l2_core::config::traits::ConfigDirLoader;
+    use l2_core::data::buy_lists::BuyListItem;
+    use l2_core::shared_packets::common::SendablePacket;
+
+    #[test]
+    fn test_buy_list() {
+        let list = BuyListData {
+            id: 3,
+            npc_ids: vec![],
+            items: vec![BuyListItem {
+                item_id: 736,
+                price: None,
+            }],
+        };
+        let p = BuyList::new(&list, &ItemsData::load(), 1000, 2, 10).unwrap();
+        let data = p.get_buffer().get_data_mut(false)[2..].to_vec();
+        assert_eq!(
+            data[..29],
+            [
+                254, 184, 0, 0, 0, 0, 0, 232, 3, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0,
+                0, 0, 0
+            ]
+        );
+        // item id of the product and the price with 10% tax
+        assert_eq!(data[30..34], [224, 2, 0, 0]);
+        assert_eq!(data[data.len() - 8..], [184, 1, 0, 0, 0, 0, 0, 0]);
+    }
+}
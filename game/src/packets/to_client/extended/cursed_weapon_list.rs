@@ -0,0 +1,42 @@
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+use std::fmt::Debug;
+
+#[derive(Debug, Clone, SendablePacket)]
+pub struct CursedWeaponList {
+    pub buffer: SendablePacketBuffer,
+}
+
+impl CursedWeaponList {
+    const PACKET_ID: u8 = 0xFE;
+    const EX_PACKET_ID: u16 = 0x47;
+
+    /// All cursed weapons the server knows, whether they are in the world or not.
+    pub fn new(item_ids: &[i32]) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_u16(Self::EX_PACKET_ID)?;
+        inst.buffer.write_u32(u32::try_from(item_ids.len())?)?;
+        for id in item_ids {
+            inst.buffer.write_i32(*id)?;
+        }
+        Ok(inst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use l2_core::shared_packets::common::SendablePacket;
+
+    #[test]
+    fn test_cursed_weapon_list() {
+        let p = CursedWeaponList::new(&[8190, 8689]).unwrap();
+        assert_eq!(
+            [254, 71, 0, 2, 0, 0, 0, 254, 31, 0, 0, 241, 33, 0, 0],
+            p.get_buffer().get_data_mut(false)[2..]
+        );
+    }
+}
@@ -0,0 +1,61 @@
+use l2_core::game_objects::zone::Location;
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+use std::fmt::Debug;
+
+#[derive(Debug, Clone, SendablePacket)]
+pub struct CursedWeaponLocation {
+    pub buffer: SendablePacketBuffer,
+}
+
+impl CursedWeaponLocation {
+    const PACKET_ID: u8 = 0xFE;
+    const EX_PACKET_ID: u16 = 0x48;
+
+    /// Where the cursed weapons in the world are, by item id.
+    pub fn new(locations: &[(i32, Location)]) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_u16(Self::EX_PACKET_ID)?;
+        inst.buffer.write_u32(u32::try_from(locations.len())?)?;
+        for (item_id, location) in locations {
+            inst.buffer.write_i32(*item_id)?;
+            inst.buffer.write_i32(1)?; // owned by a player, there are none on the ground
+            inst.buffer.write_i32(location.x)?;
+            inst.buffer.write_i32(location.y)?;
+            inst.buffer.write_i32(location.z)?;
+        }
+        Ok(inst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use l2_core::shared_packets::common::SendablePacket;
+
+    #[test]
+    fn test_cursed_weapon_location() {
+        let location = Location {
+            x: 1,
+            y: -1,
+            z: 2,
+            heading: 0,
+        };
+        let p = CursedWeaponLocation::new(&[(8190, location)]).unwrap();
+        assert_eq!(
+            [
+                254, 72, 0, 1, 0, 0, 0, 254, 31, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 255, 255, 255, 255,
+                2, 0, 0, 0
+            ],
+            p.get_buffer().get_data_mut(false)[2..]
+        );
+        let p = CursedWeaponLocation::new(&[]).unwrap();
+        assert_eq!(
+            [254, 72, 0, 0, 0, 0, 0],
+            p.get_buffer().get_data_mut(false)[2..]
+        );
+    }
+}
@@ -0,0 +1,40 @@
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+use std::fmt::Debug;
+
+#[derive(Debug, Clone, SendablePacket)]
+pub struct FishingEnd {
+    pub buffer: SendablePacketBuffer,
+}
+
+impl FishingEnd {
+    const PACKET_ID: u8 = 0xFE;
+    const EX_PACKET_ID: u16 = 0x1F;
+
+    /// The player stopped fishing, `caught` when the fish was won.
+    pub fn new(object_id: i32, caught: bool) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_u16(Self::EX_PACKET_ID)?;
+        inst.buffer.write_i32(object_id)?;
+        inst.buffer.write(caught)?;
+        Ok(inst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use l2_core::shared_packets::common::SendablePacket;
+
+    #[test]
+    fn test_fishing_end() {
+        let p = FishingEnd::new(5, true).unwrap();
+        assert_eq!(
+            [254, 31, 0, 5, 0, 0, 0, 1],
+            p.get_buffer().get_data_mut(false)[2..]
+        );
+    }
+}
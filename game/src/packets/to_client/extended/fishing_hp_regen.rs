@@ -0,0 +1,65 @@
+use l2_core::game_objects::fishing::{FishingAction, HookedFish};
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+use std::fmt::Debug;
+
+#[derive(Debug, Clone, SendablePacket)]
+pub struct FishingHpRegen {
+    pub buffer: SendablePacketBuffer,
+}
+
+impl FishingHpRegen {
+    const PACKET_ID: u8 = 0xFE;
+    const EX_PACKET_ID: u16 = 0x28;
+
+    /// State of the fight: the time left, the HP and the mode of the fish and how the last
+    /// pumping or reeling went with the damage it did.
+    pub fn new(
+        object_id: i32,
+        fish: &HookedFish,
+        action: FishingAction,
+        damage: u32,
+    ) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_u16(Self::EX_PACKET_ID)?;
+        inst.buffer.write_i32(object_id)?;
+        inst.buffer.write_u32(fish.time_left)?;
+        inst.buffer.write_u32(fish.hp)?;
+        inst.buffer.write(fish.mode as u8)?;
+        inst.buffer.write(action as u8)?;
+        inst.buffer.write(u8::from(action != FishingAction::None))?; // animation
+        inst.buffer.write_u32(damage)?;
+        inst.buffer.write(0u8)?; // hp bar color
+        Ok(inst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use l2_core::game_objects::fishing::FishMode;
+    use l2_core::shared_packets::common::SendablePacket;
+
+    #[test]
+    fn test_fishing_hp_regen() {
+        let fish = HookedFish {
+            item_id: 6411,
+            max_hp: 90,
+            hp: 70,
+            hp_regen: 2,
+            time_left: 18,
+            guts: 30,
+            mode: FishMode::Resting,
+        };
+        let p = FishingHpRegen::new(5, &fish, FishingAction::Success, 20).unwrap();
+        assert_eq!(
+            [
+                254, 40, 0, 5, 0, 0, 0, 18, 0, 0, 0, 70, 0, 0, 0, 0, 1, 1, 20, 0, 0, 0, 0
+            ],
+            p.get_buffer().get_data_mut(false)[2..]
+        );
+    }
+}
@@ -0,0 +1,56 @@
+use l2_core::data::fishing::FishGrade;
+use l2_core::game_objects::zone::Location;
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+use std::fmt::Debug;
+
+#[derive(Debug, Clone, SendablePacket)]
+pub struct FishingStart {
+    pub buffer: SendablePacketBuffer,
+}
+
+impl FishingStart {
+    const PACKET_ID: u8 = 0xFE;
+    const EX_PACKET_ID: u16 = 0x1E;
+
+    /// The player cast the lure, everyone sees it in the water at the bait location.
+    pub fn new(object_id: i32, grade: FishGrade, bait: &Location) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_u16(Self::EX_PACKET_ID)?;
+        inst.buffer.write_i32(object_id)?;
+        inst.buffer.write_i32(i32::from(grade.lure_type()))?;
+        inst.buffer.write_i32(bait.x)?;
+        inst.buffer.write_i32(bait.y)?;
+        inst.buffer.write_i32(bait.z)?;
+        inst.buffer.write(0u8)?; // night lure
+        inst.buffer.write(0u8)?; // fishing contest button
+        Ok(inst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use l2_core::shared_packets::common::SendablePacket;
+
+    #[test]
+    fn test_fishing_start() {
+        let bait = Location {
+            x: 1,
+            y: 2,
+            z: -3,
+            heading: 0,
+        };
+        let p = FishingStart::new(5, FishGrade::Normal, &bait).unwrap();
+        assert_eq!(
+            [
+                254, 30, 0, 5, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 253, 255, 255, 255, 0,
+                0
+            ],
+            p.get_buffer().get_data_mut(false)[2..]
+        );
+    }
+}
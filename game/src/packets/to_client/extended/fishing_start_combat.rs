@@ -0,0 +1,56 @@
+use l2_core::data::fishing::FishGrade;
+use l2_core::game_objects::fishing::HookedFish;
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+use std::fmt::Debug;
+
+#[derive(Debug, Clone, SendablePacket)]
+pub struct FishingStartCombat {
+    pub buffer: SendablePacketBuffer,
+}
+
+impl FishingStartCombat {
+    const PACKET_ID: u8 = 0xFE;
+    const EX_PACKET_ID: u16 = 0x27;
+
+    /// A fish bit, the client opens the fight window.
+    pub fn new(object_id: i32, fish: &HookedFish, grade: FishGrade) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_u16(Self::EX_PACKET_ID)?;
+        inst.buffer.write_i32(object_id)?;
+        inst.buffer.write_u32(fish.time_left)?;
+        inst.buffer.write_u32(fish.hp)?;
+        inst.buffer.write(fish.mode as u8)?;
+        inst.buffer.write(grade.lure_type())?;
+        inst.buffer.write(0u8)?; // deceptive mode
+        Ok(inst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use l2_core::game_objects::fishing::FishMode;
+    use l2_core::shared_packets::common::SendablePacket;
+
+    #[test]
+    fn test_fishing_start_combat() {
+        let fish = HookedFish {
+            item_id: 6411,
+            max_hp: 90,
+            hp: 90,
+            hp_regen: 2,
+            time_left: 20,
+            guts: 30,
+            mode: FishMode::Fighting,
+        };
+        let p = FishingStartCombat::new(5, &fish, FishGrade::Easy).unwrap();
+        assert_eq!(
+            [254, 39, 0, 5, 0, 0, 0, 20, 0, 0, 0, 90, 0, 0, 0, 1, 0, 0],
+            p.get_buffer().get_data_mut(false)[2..]
+        );
+    }
+}
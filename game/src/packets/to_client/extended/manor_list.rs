@@ -1,7 +1,6 @@
 use l2_core::shared_packets::write::SendablePacketBuffer;
 use macro_common::SendablePacket;
 
-#[allow(unused)]
 #[derive(Debug, Clone, SendablePacket)]
 pub struct ManorList {
     pub(crate) buffer: SendablePacketBuffer,
@@ -10,14 +9,33 @@ pub struct ManorList {
 impl ManorList {
     const PACKET_ID: u8 = 0xFE;
     const EX_PACKET_ID: u16 = 0x22;
-    pub fn new() -> anyhow::Result<Self> {
+
+    /// Castles running a manor, the client knows their names by id
+    pub fn new(castle_ids: &[i32]) -> anyhow::Result<Self> {
         let mut inst = Self {
             buffer: SendablePacketBuffer::new(),
         };
         inst.buffer.write(Self::PACKET_ID)?;
         inst.buffer.write_u16(Self::EX_PACKET_ID)?;
-        //todo: implement me
-        inst.buffer.write_i32(0)?; //size
+        inst.buffer.write_u32(u32::try_from(castle_ids.len())?)?;
+        for id in castle_ids {
+            inst.buffer.write_i32(*id)?;
+        }
         Ok(inst)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use l2_core::shared_packets::common::SendablePacket;
+
+    #[test]
+    fn test_manor_list() {
+        let p = ManorList::new(&[1, 2]).unwrap();
+        assert_eq!(
+            [254, 34, 0, 2, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0],
+            p.get_buffer().get_data_mut(false)[2..]
+        );
+    }
+}
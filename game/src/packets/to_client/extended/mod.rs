@@ -2,14 +2,29 @@ mod action_list;
 mod adena_info;
 mod auto_soulshots;
 mod bookmark_info;
+mod buy_list;
 mod char_exists_response;
+mod cursed_weapon_list;
+mod cursed_weapon_location;
 mod equipped_items;
+mod fishing_end;
+mod fishing_hp_regen;
+mod fishing_start;
+mod fishing_start_combat;
 mod inventory_weight;
 mod manor_list;
 mod pledge_waiting_list_alarm;
+mod post_details;
+mod post_item_list;
+mod post_state;
 mod premium_state;
+mod put_enchant_target_item_result;
 mod quest_item_list;
+mod quest_npc_log_list;
+mod received_post_list;
 mod rotation;
+mod sell_list;
+mod sent_post_list;
 mod set_compas_zone;
 mod storage_max_count;
 mod subclass_info;
@@ -22,15 +37,32 @@ pub use self::action_list::*;
 pub use self::adena_info::*;
 pub use self::auto_soulshots::*;
 pub use self::bookmark_info::*;
+pub use self::buy_list::*;
 pub use self::char_exists_response::*;
+pub use self::cursed_weapon_list::*;
+pub use self::cursed_weapon_location::*;
 pub use self::equipped_items::*;
+pub use self::fishing_end::*;
+pub use self::fishing_hp_regen::*;
+pub use self::fishing_start::*;
+pub use self::fishing_start_combat::*;
 pub use self::inventory_weight::*;
 pub use self::manor_list::*;
 pub use self::pledge_waiting_list_alarm::*;
+pub use self::post_details::*;
+pub use self::post_item_list::*;
+pub use self::post_state::*;
+pub use self::premium_state::*;
+pub use self::put_enchant_target_item_result::*;
 pub use self::quest_item_list::*;
+pub use self::quest_npc_log_list::*;
+pub use self::received_post_list::*;
 pub use self::rotation::*;
+pub use self::sell_list::*;
+pub use self::sent_post_list::*;
 pub use self::set_compas_zone::*;
 pub use self::subclass_info::*;
 pub use self::ui_settings::*;
 pub use self::unread_mail_count::*;
 pub use self::vitality_info::*;
+pub use self::vote_system::*;
@@ -0,0 +1,82 @@
+use crate::packets::to_client::item_list::write_item;
+use entities::entities::character_mail;
+use l2_core::game_objects::item::ItemObject;
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+
+/// A mail opened from the inbox
+#[derive(Debug, Clone, SendablePacket)]
+pub struct ReceivedPost {
+    pub(crate) buffer: SendablePacketBuffer,
+}
+
+impl ReceivedPost {
+    pub const PACKET_ID: u8 = 0xFE;
+    pub const EX_PACKET_ID: u16 = 0xAC;
+
+    pub fn new(
+        mail: &character_mail::Model,
+        sender: &str,
+        attachments: &[ItemObject],
+    ) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_u16(Self::EX_PACKET_ID)?;
+        inst.buffer.write_u32(0u32)?; // regular mail
+        inst.buffer.write_i32(mail.id)?;
+        inst.buffer.write_u32(u32::from(mail.is_cod()))?;
+        inst.buffer.write_u32(0u32)?;
+        inst.buffer.write_c_utf16le_string(Some(sender))?;
+        write_post_body(&mut inst.buffer, mail, attachments)?;
+        inst.buffer.write_u32(u32::from(mail.is_returned))?;
+        Ok(inst)
+    }
+}
+
+/// A mail opened from the outbox
+#[derive(Debug, Clone, SendablePacket)]
+pub struct SentPost {
+    pub(crate) buffer: SendablePacketBuffer,
+}
+
+impl SentPost {
+    pub const PACKET_ID: u8 = 0xFE;
+    pub const EX_PACKET_ID: u16 = 0xAE;
+
+    pub fn new(
+        mail: &character_mail::Model,
+        recipient: &str,
+        attachments: &[ItemObject],
+    ) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_u16(Self::EX_PACKET_ID)?;
+        inst.buffer.write_u32(0u32)?; // regular mail
+        inst.buffer.write_i32(mail.id)?;
+        inst.buffer.write_u32(u32::from(mail.is_cod()))?;
+        inst.buffer.write_c_utf16le_string(Some(recipient))?;
+        write_post_body(&mut inst.buffer, mail, attachments)?;
+        Ok(inst)
+    }
+}
+
+fn write_post_body(
+    buffer: &mut SendablePacketBuffer,
+    mail: &character_mail::Model,
+    attachments: &[ItemObject],
+) -> anyhow::Result<()> {
+    buffer.write_c_utf16le_string(Some(&mail.subject))?;
+    buffer.write_c_utf16le_string(Some(mail.message.as_deref().unwrap_or_default()))?;
+    buffer.write_u32(u32::try_from(attachments.len())?)?;
+    for item in attachments {
+        write_item(buffer, item)?;
+        buffer.write_i32(item.item_model.id)?;
+    }
+    buffer.write_i64(i64::from(mail.request_adena))?;
+    buffer.write_u32(u32::from(mail.has_attachments()))?;
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use crate::packets::to_client::item_list::write_item;
+use l2_core::game_objects::item::ItemObject;
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+
+/// Items which can be attached to a mail
+#[derive(Debug, Clone, SendablePacket)]
+pub struct PostItemList {
+    pub(crate) buffer: SendablePacketBuffer,
+}
+
+impl PostItemList {
+    pub const PACKET_ID: u8 = 0xFE;
+    pub const EX_PACKET_ID: u16 = 0xB2;
+
+    pub fn new(items: &[&ItemObject]) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_u16(Self::EX_PACKET_ID)?;
+        inst.buffer.write_u32(u32::try_from(items.len())?)?;
+        for item in items {
+            write_item(&mut inst.buffer, item)?;
+        }
+        Ok(inst)
+    }
+}
@@ -0,0 +1,97 @@
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PostState {
+    Deleted = 0,
+    Read = 1,
+    Rejected = 2,
+}
+
+impl From<PostState> for u32 {
+    fn from(value: PostState) -> Self {
+        value as u32
+    }
+}
+
+#[derive(Debug, Clone, SendablePacket)]
+pub struct ChangePostState {
+    pub(crate) buffer: SendablePacketBuffer,
+}
+
+impl ChangePostState {
+    pub const PACKET_ID: u8 = 0xFE;
+    pub const EX_PACKET_ID: u16 = 0xB3;
+
+    /// `received` tells if the mails are in the inbox or in the outbox
+    pub fn new(received: bool, mail_ids: &[i32], state: PostState) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_u16(Self::EX_PACKET_ID)?;
+        inst.buffer.write_u32(u32::from(received))?;
+        inst.buffer.write_u32(u32::try_from(mail_ids.len())?)?;
+        for id in mail_ids {
+            inst.buffer.write_i32(*id)?;
+            inst.buffer.write_u32(state)?;
+        }
+        Ok(inst)
+    }
+}
+
+#[derive(Debug, Clone, SendablePacket)]
+pub struct ReplyWritePost {
+    pub(crate) buffer: SendablePacketBuffer,
+}
+
+impl ReplyWritePost {
+    pub const PACKET_ID: u8 = 0xFE;
+    pub const EX_PACKET_ID: u16 = 0xB4;
+
+    pub fn new(success: bool) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_u16(Self::EX_PACKET_ID)?;
+        inst.buffer.write_u32(u32::from(success))?;
+        Ok(inst)
+    }
+}
+
+#[derive(Debug, Clone, SendablePacket)]
+pub struct NoticePostArrived {
+    pub(crate) buffer: SendablePacketBuffer,
+}
+
+impl NoticePostArrived {
+    pub const PACKET_ID: u8 = 0xFE;
+    pub const EX_PACKET_ID: u16 = 0xA9;
+
+    pub fn new(show_animation: bool) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_u16(Self::EX_PACKET_ID)?;
+        inst.buffer.write_u32(u32::from(show_animation))?;
+        Ok(inst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use l2_core::shared_packets::common::SendablePacket;
+
+    #[test]
+    fn test_change_post_state() {
+        let p = ChangePostState::new(true, &[5], PostState::Read).unwrap();
+        assert_eq!(
+            [254, 179, 0, 1, 0, 0, 0, 1, 0, 0, 0, 5, 0, 0, 0, 1, 0, 0, 0],
+            p.get_buffer().get_data_mut(false)[2..]
+        );
+    }
+}
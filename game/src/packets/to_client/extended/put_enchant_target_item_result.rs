@@ -0,0 +1,38 @@
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+
+/// The item the player put into the enchant window, 0 when it can't be enchanted.
+#[derive(Debug, Clone, SendablePacket)]
+pub struct ExPutEnchantTargetItemResult {
+    pub(crate) buffer: SendablePacketBuffer,
+}
+
+impl ExPutEnchantTargetItemResult {
+    const PACKET_ID: u8 = 0xFE;
+    const EX_PACKET_ID: u16 = 0x81;
+
+    pub fn new(object_id: i32) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_u16(Self::EX_PACKET_ID)?;
+        inst.buffer.write_i32(object_id)?;
+        Ok(inst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use l2_core::shared_packets::common::SendablePacket;
+
+    #[test]
+    fn test_put_enchant_target_item_result() {
+        let p = ExPutEnchantTargetItemResult::new(7).unwrap();
+        assert_eq!(
+            [0xFE, 0x81, 0, 7, 0, 0, 0],
+            p.get_buffer().get_data_mut(false)[2..]
+        );
+    }
+}
@@ -1,8 +1,9 @@
+use crate::packets::to_client::item_list::write_item;
+use l2_core::data::quests::QuestData;
 use l2_core::game_objects::player::Player;
 use l2_core::shared_packets::write::SendablePacketBuffer;
 use macro_common::SendablePacket;
 
-#[allow(unused)]
 #[derive(Debug, Clone, SendablePacket)]
 pub struct QuestItemList {
     pub(crate) buffer: SendablePacketBuffer,
@@ -12,14 +13,22 @@ impl QuestItemList {
     pub const PACKET_ID: u8 = 0xFE;
     pub const EX_PACKET_ID: u16 = 0xC7;
 
-    pub fn new(player: &Player) -> anyhow::Result<Self> {
+    pub fn new(player: &Player, quests: &QuestData) -> anyhow::Result<Self> {
         let mut inst = Self {
             buffer: SendablePacketBuffer::new(),
         };
+        let items: Vec<_> = player
+            .inventory
+            .items
+            .values()
+            .filter(|i| quests.is_quest_item(i.item_model.item_id))
+            .collect();
         inst.buffer.write(Self::PACKET_ID)?;
         inst.buffer.write_u16(Self::EX_PACKET_ID)?;
-        inst.buffer.write_u16(0u16)?; //quest items count
-        //todo: implement me
+        inst.buffer.write_u16(u16::try_from(items.len())?)?;
+        for i in items {
+            write_item(&mut inst.buffer, i)?;
+        }
         if player.has_inventory_block() {
             //todo: implement me
         } else {
@@ -61,7 +70,7 @@ mod test {
             .try_get_template(Class::try_from(char.class_id).unwrap())
             .unwrap();
         let player = Player::new(char, vec![], template.clone(), None);
-        let p = QuestItemList::new(&player).unwrap();
+        let p = QuestItemList::new(&player, &controller.quests).unwrap();
         assert_eq!(
             [254, 199, 0, 0, 0, 0, 0],
             p.get_buffer().get_data_mut(false)[2..]
@@ -0,0 +1,47 @@
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+
+/// Kill progress of a quest step shown in the quest window.
+#[derive(Debug, Clone, SendablePacket)]
+pub struct QuestNpcLogList {
+    pub(crate) buffer: SendablePacketBuffer,
+}
+
+impl QuestNpcLogList {
+    const PACKET_ID: u8 = 0xFE;
+    const EX_PACKET_ID: u16 = 0xC6;
+    /// The client resolves npc names by `npc_id + NPC_NAME_OFFSET`
+    const NPC_NAME_OFFSET: i32 = 1_000_000;
+
+    /// `kills` are `(npc id, killed count)` pairs
+    pub fn new(quest_id: i32, kills: &[(i32, u32)]) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_u16(Self::EX_PACKET_ID)?;
+        inst.buffer.write_i32(quest_id)?;
+        inst.buffer.write(u8::try_from(kills.len())?)?;
+        for &(npc_id, count) in kills {
+            inst.buffer.write_i32(npc_id + Self::NPC_NAME_OFFSET)?;
+            inst.buffer.write(0u8)?; // not an npc string
+            inst.buffer.write_u32(count)?;
+        }
+        Ok(inst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use l2_core::shared_packets::common::SendablePacket;
+
+    #[test]
+    fn test_quest_npc_log_list() {
+        let p = QuestNpcLogList::new(261, &[(20001, 3)]).unwrap();
+        assert_eq!(
+            [254, 198, 0, 5, 1, 0, 0, 1, 97, 144, 15, 0, 0, 3, 0, 0, 0],
+            p.get_buffer().get_data_mut(false)[2..]
+        );
+    }
+}
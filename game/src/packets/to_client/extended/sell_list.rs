@@ -0,0 +1,48 @@
+use crate::packets::to_client::item_list::write_item;
+use l2_core::game_objects::item::ItemObject;
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+
+/// Sell tab of the merchant window, items of the player the merchant buys and their prices.
+#[derive(Debug, Clone, SendablePacket)]
+pub struct SellList {
+    pub(crate) buffer: SendablePacketBuffer,
+}
+
+impl SellList {
+    pub const PACKET_ID: u8 = 0xFE;
+    pub const EX_PACKET_ID: u16 = 0xB8;
+
+    pub fn new(items: &[(&ItemObject, u64)], inventory_size: usize) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_u16(Self::EX_PACKET_ID)?;
+        inst.buffer.write_u32(1u32)?; // sell
+        inst.buffer.write_u32(u32::try_from(inventory_size)?)?;
+        inst.buffer.write_u16(u16::try_from(items.len())?)?;
+        for (item, price) in items {
+            write_item(&mut inst.buffer, item)?;
+            inst.buffer.write_i64(i64::try_from(*price)?)?;
+        }
+        inst.buffer.write_u16(0u16)?; // refund list
+        inst.buffer.write(0)?;
+        Ok(inst)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use l2_core::shared_packets::common::SendablePacket;
+
+    #[test]
+    fn test_empty_sell_list() {
+        let p = SellList::new(&[], 5).unwrap();
+        assert_eq!(
+            [254, 184, 0, 1, 0, 0, 0, 5, 0, 0, 0, 0, 0, 0, 0, 0],
+            p.get_buffer().get_data_mut(false)[2..]
+        );
+    }
+}
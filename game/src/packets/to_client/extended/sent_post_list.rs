@@ -0,0 +1,39 @@
+use chrono::Utc;
+use entities::entities::character_mail;
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+
+#[derive(Debug, Clone, SendablePacket)]
+pub struct SentPostList {
+    pub(crate) buffer: SendablePacketBuffer,
+}
+
+impl SentPostList {
+    pub const PACKET_ID: u8 = 0xFE;
+    pub const EX_PACKET_ID: u16 = 0xAD;
+
+    /// `outbox` holds mails together with their recipient names
+    pub fn new(outbox: &[(character_mail::Model, Option<String>)]) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_u16(Self::EX_PACKET_ID)?;
+        inst.buffer.write_i32(i32::try_from(Utc::now().timestamp())?)?;
+        inst.buffer.write_u32(u32::try_from(outbox.len())?)?;
+        for (mail, recipient) in outbox {
+            inst.buffer.write_i32(mail.id)?;
+            inst.buffer.write_c_utf16le_string(Some(&mail.subject))?;
+            inst.buffer
+                .write_c_utf16le_string(Some(recipient.as_deref().unwrap_or_default()))?;
+            inst.buffer.write_u32(u32::from(mail.is_cod()))?;
+            inst.buffer
+                .write_i32(i32::try_from(mail.expire_at.timestamp())?)?;
+            inst.buffer.write_u32(u32::from(mail.is_unread))?;
+            inst.buffer.write_u32(1u32)?; // can be returned
+            inst.buffer.write_u32(u32::from(mail.has_attachments()))?;
+            inst.buffer.write_u32(0u32)?;
+        }
+        Ok(inst)
+    }
+}
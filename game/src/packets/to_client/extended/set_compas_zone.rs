@@ -1,7 +1,8 @@
+use l2_core::game_objects::player::Player;
+use l2_core::game_objects::zone::ZoneId;
 use l2_core::shared_packets::write::SendablePacketBuffer;
 use macro_common::SendablePacket;
 
-#[allow(unused)]
 #[derive(Debug, Clone, Default, SendablePacket)]
 pub struct SetCompasZoneCode {
     pub buffer: SendablePacketBuffer,
@@ -10,6 +11,28 @@ pub struct SetCompasZoneCode {
 impl SetCompasZoneCode {
     pub const PACKET_ID: u8 = 0xFE;
     pub const EX_PACKET_ID: u16 = 0x33;
+    pub const ALTERED_ZONE: i32 = 0x08;
+    pub const SIEGE_WAR_ZONE: i32 = 0x0A;
+    pub const GENERAL_ZONE: i32 = 0x0C;
+    pub const PEACE_ZONE: i32 = 0x0E;
+    pub const PVP_ZONE: i32 = 0x0F;
+
+    /// Compass code for the zones the player is currently in.
+    #[must_use]
+    pub fn zone_code(player: &Player) -> i32 {
+        if player.is_inside_zone(ZoneId::Altered) {
+            Self::ALTERED_ZONE
+        } else if player.is_inside_zone(ZoneId::Siege) {
+            Self::SIEGE_WAR_ZONE
+        } else if player.is_inside_zone(ZoneId::Pvp) {
+            Self::PVP_ZONE
+        } else if player.is_inside_zone(ZoneId::Peace) {
+            Self::PEACE_ZONE
+        } else {
+            Self::GENERAL_ZONE
+        }
+    }
+
     pub fn new(compas_zone: i32) -> anyhow::Result<Self> {
         let mut buffer = SendablePacketBuffer::new();
         buffer.write(Self::PACKET_ID)?;
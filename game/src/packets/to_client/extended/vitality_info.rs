@@ -20,9 +20,10 @@ impl VitalityInfo {
         let mut inst = Self {
             buffer: SendablePacketBuffer::new(),
             points: player.char_model.vitality_points,
-            vitality_bonus: player.get_vitality_bonus(),
-            vitality_items_remaining: config.vitality_max_items_allowed
-                - player.get_vitality_used(),
+            vitality_bonus: player.get_vitality_bonus(&config.rates),
+            vitality_items_remaining: config
+                .vitality_max_items_allowed
+                .saturating_sub(player.get_vitality_used()),
         };
         inst.buffer.write(Self::PACKET_ID)?;
         inst.buffer.write_u16(Self::EX_PACKET_ID)?;
@@ -69,10 +70,18 @@ mod test {
             .class_templates
             .try_get_template(Class::try_from(char.class_id).unwrap())
             .unwrap();
-        let player = Player::new(char, vec![], template.clone(), None);
+        let mut player = Player::new(char, vec![], template.clone(), None);
         let p = VitalityInfo::new(&player, &cfg).unwrap();
         assert_eq!(
-            [254, 24, 1, 0, 0, 0, 0, 100, 0, 0, 0, 0, 0, 231, 3, 231, 3],
+            [254, 24, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 231, 3, 231, 3],
+            p.get_buffer().get_data_mut(false)[2..]
+        );
+        let mut enabled = (*cfg).clone();
+        enabled.rates.enable_vitality = true;
+        player.set_vitality_points(1000);
+        let p = VitalityInfo::new(&player, &enabled).unwrap();
+        assert_eq!(
+            [254, 24, 1, 232, 3, 0, 0, 200, 0, 0, 0, 0, 0, 231, 3, 231, 3],
             p.get_buffer().get_data_mut(false)[2..]
         );
     }
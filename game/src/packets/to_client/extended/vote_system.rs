@@ -16,12 +16,11 @@ impl VoteSystem {
         };
         inst.buffer.write(Self::PACKET_ID)?;
         inst.buffer.write_u16(Self::EX_PACKET_ID)?;
-        //todo: implement me
         inst.buffer.write_u32(p.get_recommendations_left())?;
         inst.buffer.write_u32(p.get_recommendations_have())?;
-        inst.buffer.write_u32(0u32)?; //bonus time
-        inst.buffer.write_u32(0u32)?; //bonus value
-        inst.buffer.write_u32(0u32)?; //bonus type
+        inst.buffer.write_u32(p.get_recommendation_bonus_time())?;
+        inst.buffer.write_u32(p.get_recommendation_bonus())?;
+        inst.buffer.write_u32(0u32)?; //bonus type, the timer is never paused
         Ok(inst)
     }
 }
@@ -29,6 +28,7 @@ impl VoteSystem {
 mod test {
     use crate::controller::GameController;
     use crate::packets::to_client::extended::vote_system::VoteSystem;
+    use chrono::Utc;
     use entities::test_factories::factories::{char_factory, user_factory};
     use l2_core::config::gs::GSServerConfig;
     use l2_core::data::classes::mapping::Class;
@@ -57,7 +57,7 @@ mod test {
             .class_templates
             .try_get_template(Class::try_from(char.class_id).unwrap())
             .unwrap();
-        let player = Player::new(char, vec![], template.clone(), None);
+        let mut player = Player::new(char, vec![], template.clone(), None);
         let p = VoteSystem::new(&player).unwrap();
         assert_eq!(
             [
@@ -65,5 +65,14 @@ mod test {
             ],
             p.get_buffer().get_data_mut(false)[2..]
         );
+        player.refresh_recommendations(Utc::now());
+        player.receive_recommendation();
+        let p = VoteSystem::new(&player).unwrap();
+        assert_eq!(
+            [
+                254, 202, 0, 20, 0, 0, 0, 1, 0, 0, 0, 16, 14, 0, 0, 25, 0, 0, 0, 0, 0, 0, 0
+            ],
+            p.get_buffer().get_data_mut(false)[2..]
+        );
     }
 }
@@ -0,0 +1,36 @@
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+
+#[derive(Debug, Clone, SendablePacket)]
+pub struct FriendAddRequest {
+    pub(crate) buffer: SendablePacketBuffer,
+}
+
+impl FriendAddRequest {
+    pub const PACKET_ID: u8 = 0x83;
+
+    pub fn new(requester_name: &str) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_c_utf16le_string(Some(requester_name))?;
+        inst.buffer.write_u32(0u32)?;
+        Ok(inst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use l2_core::shared_packets::common::SendablePacket;
+
+    #[test]
+    fn test_friend_add_request() {
+        let packet = FriendAddRequest::new("Ab").unwrap();
+        assert_eq!(
+            [131, 65, 0, 98, 0, 0, 0, 0, 0, 0, 0],
+            packet.get_buffer().get_data_mut(false)[2..]
+        );
+    }
+}
@@ -1,8 +1,8 @@
+use crate::controller::GameController;
 use l2_core::game_objects::player::Player;
 use l2_core::shared_packets::write::SendablePacketBuffer;
 use macro_common::SendablePacket;
 
-#[allow(unused)]
 #[derive(Debug, Clone, SendablePacket)]
 pub struct FriendList {
     pub(crate) buffer: SendablePacketBuffer,
@@ -11,14 +11,23 @@ pub struct FriendList {
 impl FriendList {
     pub const PACKET_ID: u8 = 0x75;
 
-    pub fn new(_player: &Player) -> anyhow::Result<Self> {
+    pub fn new(player: &Player, controller: &GameController) -> anyhow::Result<Self> {
         let mut inst = Self {
             buffer: SendablePacketBuffer::new(),
         };
         inst.buffer.write(Self::PACKET_ID)?;
-        let friends: Vec<u8> = vec![];
-        inst.buffer.write_u32(u32::try_from(friends.len())?)?;
-        //todo: implement me
+        inst.buffer
+            .write_u32(u32::try_from(player.friends.len())?)?;
+        for friend in &player.friends {
+            let online = controller.get_char_in_game(friend.char_id);
+            inst.buffer.write_i32(friend.char_id)?;
+            inst.buffer.write_c_utf16le_string(Some(&friend.name))?;
+            inst.buffer.write_u32(u32::from(online.is_some()))?;
+            inst.buffer.write_i32(online.map_or(0, |c| c.object_id))?;
+            inst.buffer.write_u32(u32::from(friend.level))?;
+            inst.buffer.write_i32(i32::from(friend.class_id))?;
+            inst.buffer.write_u16(0u16)?;
+        }
         Ok(inst)
     }
 }
@@ -29,6 +38,7 @@ mod tests {
     use entities::test_factories::factories::{char_factory, user_factory};
     use l2_core::config::gs::GSServerConfig;
     use l2_core::data::classes::mapping::Class;
+    use l2_core::game_objects::player::Contact;
     use l2_core::shared_packets::common::SendablePacket;
     use l2_core::traits::ServerConfig;
     use std::sync::Arc;
@@ -53,10 +63,44 @@ mod tests {
             .try_get_template(Class::try_from(char.class_id).unwrap())
             .unwrap();
         let player = Player::new(char, vec![], template.clone(), None);
-        let packet = FriendList::new(&player).unwrap();
+        let packet = FriendList::new(&player, &controller).unwrap();
         assert_eq!(
             [117, 0, 0, 0, 0],
             packet.get_buffer().get_data_mut(false)[2..]
         );
     }
+
+    #[tokio::test]
+    async fn test_friend_list_offline_friend() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let char = char_factory(&db_pool, |mut m| {
+            m.user_id = user.id;
+            m
+        })
+        .await;
+        let cfg = Arc::new(GSServerConfig::from_string(include_str!(
+            "../../../../config/game.yaml"
+        )));
+        let controller = GameController::from_config(cfg).await;
+        let template = controller
+            .class_templates
+            .try_get_template(Class::try_from(char.class_id).unwrap())
+            .unwrap();
+        let mut player = Player::new(char, vec![], template.clone(), None);
+        player.add_friend(Contact {
+            char_id: 7,
+            name: "Ab".to_string(),
+            level: 20,
+            class_id: 1,
+        });
+        let packet = FriendList::new(&player, &controller).unwrap();
+        assert_eq!(
+            [
+                117, 1, 0, 0, 0, 7, 0, 0, 0, 65, 0, 98, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 20, 0, 0,
+                0, 1, 0, 0, 0, 0, 0
+            ],
+            packet.get_buffer().get_data_mut(false)[2..]
+        );
+    }
 }
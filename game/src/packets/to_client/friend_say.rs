@@ -0,0 +1,41 @@
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+
+/// Private message between friends
+#[derive(Debug, Clone, SendablePacket)]
+pub struct FriendSay {
+    pub(crate) buffer: SendablePacketBuffer,
+}
+
+impl FriendSay {
+    pub const PACKET_ID: u8 = 0x78;
+
+    pub fn new(sender: &str, receiver: &str, message: &str) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_u32(0u32)?;
+        inst.buffer.write_c_utf16le_string(Some(receiver))?;
+        inst.buffer.write_c_utf16le_string(Some(sender))?;
+        inst.buffer.write_c_utf16le_string(Some(message))?;
+        Ok(inst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use l2_core::shared_packets::common::SendablePacket;
+
+    #[test]
+    fn test_friend_say() {
+        let packet = FriendSay::new("A", "B", "hi").unwrap();
+        assert_eq!(
+            [
+                120, 0, 0, 0, 0, 66, 0, 0, 0, 65, 0, 0, 0, 104, 0, 105, 0, 0, 0
+            ],
+            packet.get_buffer().get_data_mut(false)[2..]
+        );
+    }
+}
@@ -0,0 +1,83 @@
+use l2_core::data::hennas::HennaTemplate;
+use l2_core::game_objects::player::Player;
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+
+/// Symbols the player can draw, only those the player has dyes for are listed.
+#[derive(Debug, Clone, SendablePacket)]
+pub struct HennaEquipList {
+    pub(crate) buffer: SendablePacketBuffer,
+}
+
+impl HennaEquipList {
+    pub const PACKET_ID: u8 = 0xEE;
+
+    pub fn new(p: &Player, hennas: &[&HennaTemplate]) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        let hennas: Vec<_> = hennas
+            .iter()
+            .filter(|h| p.inventory.get_item_by_item_id(h.dye_item_id).is_some())
+            .collect();
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_u64(p.inventory.get_adena())?;
+        inst.buffer.write_u32(p.get_henna_empty_slots())?;
+        inst.buffer.write_u32(u32::try_from(hennas.len())?)?;
+        for henna in hennas {
+            inst.buffer.write_i32(henna.dye_id)?;
+            inst.buffer.write_i32(henna.dye_item_id)?;
+            inst.buffer.write_i64(henna.wear_count)?;
+            inst.buffer.write_i64(henna.wear_fee)?;
+            inst.buffer
+                .write_u32(u32::from(henna.is_allowed_class(p.char_model.class_id)))?;
+        }
+        Ok(inst)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use entities::dao::item::LocType;
+    use entities::test_factories::factories::{char_factory, item_factory, user_factory};
+    use l2_core::config::traits::ConfigDirLoader;
+    use l2_core::data::char_template::ClassTemplates;
+    use l2_core::data::classes::mapping::Class;
+    use l2_core::data::hennas::HennaData;
+    use l2_core::shared_packets::common::SendablePacket;
+    use test_utils::utils::get_test_db;
+
+    #[tokio::test]
+    async fn test_henna_equip_list() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let char = char_factory(&db_pool, |mut m| {
+            m.user_id = user.id;
+            m.class_id = Class::Warrior as i8;
+            m
+        })
+        .await;
+        let dye = item_factory(&db_pool, |mut i| {
+            i.owner = char.id;
+            i.item_id = 4445;
+            i.count = 10;
+            i.loc = LocType::Inventory;
+            i
+        })
+        .await;
+        let templates = ClassTemplates::load();
+        let template = templates.try_get_template(Class::Warrior).unwrap();
+        let player = Player::new(char, vec![dye], template.clone(), None);
+        let data = HennaData::load();
+        let p =
+            HennaEquipList::new(&player, &data.get_allowed_hennas(Class::Warrior as i8)).unwrap();
+        assert_eq!(
+            [
+                238, 0, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 93, 17, 0, 0, 10,
+                0, 0, 0, 0, 0, 0, 0, 136, 144, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0
+            ],
+            p.get_buffer().get_data_mut(false)[2..]
+        );
+    }
+}
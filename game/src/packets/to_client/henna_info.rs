@@ -16,22 +16,23 @@ impl HennaInfo {
             buffer: SendablePacketBuffer::new(),
         };
         inst.buffer.write(Self::PACKET_ID)?;
-        inst.buffer.write_u16(0u16)?; // INT
-        inst.buffer.write_u16(0u16)?; // STR
-        inst.buffer.write_u16(0u16)?; // CON
-        inst.buffer.write_u16(0u16)?; // MEN
-        inst.buffer.write_u16(0u16)?; // DEX
-        inst.buffer.write_u16(0u16)?; // WIT
+        let bonus = p.get_henna_stats();
+        inst.buffer.write_i16(bonus.int)?;
+        inst.buffer.write_i16(bonus.str)?;
+        inst.buffer.write_i16(bonus.con)?;
+        inst.buffer.write_i16(bonus.men)?;
+        inst.buffer.write_i16(bonus.dex)?;
+        inst.buffer.write_i16(bonus.wit)?;
         inst.buffer.write_u16(0u16)?; // LUC
         inst.buffer.write_u16(0u16)?; // CHA
-        inst.buffer.write_u32(3 - p.get_henna_empty_slots())?; //slots
-        inst.buffer.write_u32(0u32)?; //henna size
-        //todo: implement me
-        //for (Henna henna : _hennas)
-        // 		{
-        // 			buffer.writeInt(henna.getDyeId());
-        // 			buffer.writeInt(henna.isAllowedClass(_player.getClassId()));
-        // 		}
+        inst.buffer.write_u32(p.get_henna_slots())?;
+        let hennas: Vec<_> = p.get_hennas().collect();
+        inst.buffer.write_u32(u32::try_from(hennas.len())?)?;
+        for henna in hennas {
+            inst.buffer.write_i32(henna.dye_id)?;
+            inst.buffer
+                .write_u32(u32::from(henna.is_allowed_class(p.char_model.class_id)))?;
+        }
         inst.buffer.write_u32(0u32)?; // Premium Slot Dye ID
         inst.buffer.write_u32(0u32)?; // Premium Slot Dye Time Left
         inst.buffer.write_u32(0u32)?; // Premium Slot Dye ID isValid
@@ -73,10 +74,41 @@ mod test {
         let p = HennaInfo::new(&player).unwrap();
         assert_eq!(
             [
-                229, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                229, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0,
                 0, 0, 0, 0, 0, 0, 0, 0, 0, 0
             ],
             p.get_buffer().get_data_mut(false)[2..]
         );
     }
+
+    #[tokio::test]
+    async fn test_henna_drawn() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let char = char_factory(&db_pool, |mut m| {
+            m.user_id = user.id;
+            m.class_id = Class::Warrior as i8;
+            m
+        })
+        .await;
+        let cfg = Arc::new(GSServerConfig::from_string(include_str!(
+            "../../../../config/game.yaml"
+        )));
+        let controller = GameController::from_config(cfg).await;
+        let template = controller
+            .class_templates
+            .try_get_template(Class::Warrior)
+            .unwrap();
+        let mut player = Player::new(char, vec![], template.clone(), None);
+        let henna = controller.hennas.get_henna(1).unwrap();
+        assert_eq!(player.add_henna(henna.clone()), Some(1));
+        let p = HennaInfo::new(&player).unwrap();
+        assert_eq!(
+            [
+                229, 0, 0, 1, 0, 253, 255, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 1,
+                0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0
+            ],
+            p.get_buffer().get_data_mut(false)[2..]
+        );
+    }
 }
@@ -0,0 +1,56 @@
+use l2_core::data::hennas::HennaTemplate;
+use l2_core::game_objects::player::Player;
+use l2_core::game_objects::stats::Stat;
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+
+/// Writes the current base stats of the player together with the values
+/// after the symbol is drawn (`sign` = 1) or removed (`sign` = -1).
+pub(crate) fn write_henna_stat_changes(
+    buffer: &mut SendablePacketBuffer,
+    p: &Player,
+    henna: &HennaTemplate,
+    sign: i16,
+) -> anyhow::Result<()> {
+    for (current, stat) in [
+        (p.get_int(), Stat::Int),
+        (p.get_str(), Stat::Str),
+        (p.get_con(), Stat::Con),
+        (p.get_men(), Stat::Men),
+        (p.get_dex(), Stat::Dex),
+        (p.get_wit(), Stat::Wit),
+    ] {
+        buffer.write_u32(u32::from(current))?;
+        buffer.write_i16(i16::from(current) + sign * henna.stats.get(stat))?;
+    }
+    buffer.write_u32(0u32)?; // LUC
+    buffer.write_u16(0u16)?;
+    buffer.write_u32(0u32)?; // CHA
+    buffer.write_u16(0u16)?;
+    Ok(())
+}
+
+#[derive(Debug, Clone, SendablePacket)]
+pub struct HennaItemDrawInfo {
+    pub(crate) buffer: SendablePacketBuffer,
+}
+
+impl HennaItemDrawInfo {
+    pub const PACKET_ID: u8 = 0xE4;
+
+    pub fn new(p: &Player, henna: &HennaTemplate) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_i32(henna.dye_id)?;
+        inst.buffer.write_i32(henna.dye_item_id)?;
+        inst.buffer.write_i64(henna.wear_count)?;
+        inst.buffer.write_i64(henna.wear_fee)?;
+        inst.buffer
+            .write_u32(u32::from(henna.is_allowed_class(p.char_model.class_id)))?;
+        inst.buffer.write_u64(p.inventory.get_adena())?;
+        write_henna_stat_changes(&mut inst.buffer, p, henna, 1)?;
+        Ok(inst)
+    }
+}
@@ -0,0 +1,29 @@
+use crate::packets::to_client::henna_item_draw_info::write_henna_stat_changes;
+use l2_core::data::hennas::HennaTemplate;
+use l2_core::game_objects::player::Player;
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+
+#[derive(Debug, Clone, SendablePacket)]
+pub struct HennaItemRemoveInfo {
+    pub(crate) buffer: SendablePacketBuffer,
+}
+
+impl HennaItemRemoveInfo {
+    pub const PACKET_ID: u8 = 0xE7;
+
+    pub fn new(p: &Player, henna: &HennaTemplate) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_i32(henna.dye_id)?;
+        inst.buffer.write_i32(henna.dye_item_id)?;
+        inst.buffer.write_i64(henna.cancel_count)?;
+        inst.buffer.write_i64(henna.cancel_fee)?;
+        inst.buffer.write_u32(1u32)?; // can be removed
+        inst.buffer.write_u64(p.inventory.get_adena())?;
+        write_henna_stat_changes(&mut inst.buffer, p, henna, -1)?;
+        Ok(inst)
+    }
+}
@@ -0,0 +1,32 @@
+use l2_core::game_objects::player::Player;
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+
+#[derive(Debug, Clone, SendablePacket)]
+pub struct HennaRemoveList {
+    pub(crate) buffer: SendablePacketBuffer,
+}
+
+impl HennaRemoveList {
+    pub const PACKET_ID: u8 = 0xE6;
+
+    pub fn new(p: &Player) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        let hennas: Vec<_> = p.get_hennas().collect();
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_u64(p.inventory.get_adena())?;
+        inst.buffer.write_u32(p.get_henna_slots())?;
+        inst.buffer.write_u32(u32::try_from(hennas.len())?)?;
+        for henna in hennas {
+            inst.buffer.write_i32(henna.dye_id)?;
+            inst.buffer.write_i32(henna.dye_item_id)?;
+            inst.buffer.write_i64(henna.cancel_count)?;
+            inst.buffer.write_i64(henna.cancel_fee)?;
+            inst.buffer.write_u32(1u32)?; // can be removed
+            inst.buffer.write_u32(0u32)?;
+        }
+        Ok(inst)
+    }
+}
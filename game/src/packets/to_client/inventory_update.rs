@@ -0,0 +1,75 @@
+use crate::packets::to_client::item_list::write_item;
+use l2_core::game_objects::item::ItemObject;
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+
+#[repr(u16)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemChange {
+    Added = 1,
+    Modified = 2,
+    Removed = 3,
+}
+
+impl From<ItemChange> for u16 {
+    fn from(value: ItemChange) -> Self {
+        value as u16
+    }
+}
+
+#[derive(Debug, Clone, SendablePacket)]
+pub struct InventoryUpdate {
+    pub(crate) buffer: SendablePacketBuffer,
+}
+
+impl InventoryUpdate {
+    pub const PACKET_ID: u8 = 0x21;
+
+    pub fn new(items: &[(ItemChange, &ItemObject)]) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_u16(u16::try_from(items.len())?)?;
+        for (change, item) in items {
+            inst.buffer.write_u16(*change)?;
+            write_item(&mut inst.buffer, item)?;
+        }
+        Ok(inst)
+    }
+
+    /// Update for a single item, removed when its stack is empty.
+    pub fn single(item: &ItemObject) -> anyhow::Result<Self> {
+        let change = if item.item_model.count > 0 {
+            ItemChange::Modified
+        } else {
+            ItemChange::Removed
+        };
+        Self::new(&[(change, item)])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use entities::entities::item;
+    use l2_core::shared_packets::common::SendablePacket;
+
+    #[test]
+    fn test_write_inventory_update() {
+        let items = ItemObject::from_items(vec![item::Model {
+            id: 7,
+            item_id: 1835,
+            count: 0,
+            ..Default::default()
+        }]);
+        let it = items.values().next().unwrap();
+        let p = InventoryUpdate::single(it).unwrap();
+        let data = p.get_buffer().get_data_mut(false)[2..].to_vec();
+        assert_eq!(data[0], 0x21);
+        assert_eq!(&data[1..3], &[1, 0]);
+        assert_eq!(&data[3..5], &[3, 0]); // removed
+        assert_eq!(data[5], 0); // mask
+        assert_eq!(&data[6..10], &[7, 0, 0, 0]);
+    }
+}
@@ -1,3 +1,5 @@
+use l2_core::data::quests::QuestData;
+use l2_core::game_objects::item::ItemObject;
 use l2_core::game_objects::player::Player;
 use l2_core::shared_packets::write::SendablePacketBuffer;
 use l2_core::traits::conversion::ToU32Rounded;
@@ -13,40 +15,23 @@ pub struct ItemList {
 impl ItemList {
     pub const PACKET_ID: u8 = 0x11;
 
-    pub fn new(p: &Player, show_window: bool) -> anyhow::Result<Self> {
+    /// Quest items are not listed, they are sent with `QuestItemList`.
+    pub fn new(p: &Player, quests: &QuestData, show_window: bool) -> anyhow::Result<Self> {
         let mut inst = Self {
             buffer: SendablePacketBuffer::new(),
             show_window,
         };
+        let items: Vec<_> = p
+            .inventory
+            .items
+            .values()
+            .filter(|i| !quests.is_quest_item(i.item_model.item_id))
+            .collect();
         inst.buffer.write(Self::PACKET_ID)?;
         inst.buffer.write_u16(show_window)?;
-        inst.buffer
-            .write_u16(u16::try_from(p.inventory.items.len())?)?;
-        for i in p.inventory.items.values() {
-            let mask = i.calculate_mask();
-            inst.buffer.write(u8::try_from(mask)?)?;
-            inst.buffer.write_i32(i.item_model.id)?;
-            inst.buffer.write_i32(i.get_display_id())?;
-            if i.is_quest_item() {
-                inst.buffer.write(1)?;
-            } else if i.is_equipped() {
-                inst.buffer.write(i.get_location())?;
-            } else {
-                inst.buffer.write(0xFF)?;
-            }
-            inst.buffer.write_i64(i.item_model.count)?;
-            inst.buffer.write(i.get_type_2())?;
-            inst.buffer.write(i.get_custom_type_1())?;
-            inst.buffer.write(i.is_equipped())?;
-            inst.buffer.write_i64(i.get_body_part())?;
-            inst.buffer
-                .write(u8::try_from(i.item_model.enchant_level)?)?; // Enchant level (pet level shown in control item)
-            inst.buffer.write(i.get_custom_type_2())?; // Pet name exists or not shown in control item
-            inst.buffer
-                .write_u32(i.item_model.mana_left.to_u32_rounded()?)?;
-            inst.buffer.write_i32(i.get_time())?;
-            inst.buffer.write(i.is_available())?; // GOD Item enabled = 1 disabled (red) = 0
-            //todo: implement me
+        inst.buffer.write_u16(u16::try_from(items.len())?)?;
+        for i in items {
+            write_item(&mut inst.buffer, i)?;
         }
         if p.has_inventory_block() {
             //todo: implement me
@@ -56,6 +41,57 @@ impl ItemList {
         Ok(inst)
     }
 }
+
+/// Writes the item info block shared by the item packets (`ItemList`, `InventoryUpdate`).
+pub(crate) fn write_item(buffer: &mut SendablePacketBuffer, i: &ItemObject) -> anyhow::Result<()> {
+    let mask = i.calculate_mask();
+    buffer.write(u8::try_from(mask)?)?;
+    buffer.write_i32(i.item_model.id)?;
+    buffer.write_i32(i.get_display_id())?;
+    if i.is_quest_item() {
+        buffer.write(1)?;
+    } else if i.is_equipped() {
+        buffer.write(i.get_location())?;
+    } else {
+        buffer.write(0xFF)?;
+    }
+    buffer.write_i64(i.item_model.count)?;
+    buffer.write(i.get_type_2())?;
+    buffer.write(i.get_custom_type_1())?;
+    buffer.write(i.is_equipped())?;
+    buffer.write_i64(i.get_body_part())?;
+    buffer.write(u8::try_from(i.item_model.enchant_level)?)?; // Enchant level (pet level shown in control item)
+    buffer.write(i.get_custom_type_2())?; // Pet name exists or not shown in control item
+    buffer.write_u32(i.item_model.mana_left.to_u32_rounded()?)?;
+    buffer.write_i32(i.get_time())?;
+    buffer.write(i.is_available())?; // GOD Item enabled = 1 disabled (red) = 0
+    //todo: implement me
+    Ok(())
+}
+
+/// Same block as [`write_item`] for goods which are not items of the player (shop lists).
+pub(crate) fn write_product(
+    buffer: &mut SendablePacketBuffer,
+    item_id: i32,
+    count: i64,
+) -> anyhow::Result<()> {
+    buffer.write(0)?; // mask
+    buffer.write_i32(0)?; // there is no item object yet
+    buffer.write_i32(item_id)?;
+    buffer.write(0xFF)?;
+    buffer.write_i64(count)?;
+    buffer.write(0)?; // type 2
+    buffer.write(0)?; // custom type 1
+    buffer.write(false)?;
+    buffer.write_i64(0)?; // body part
+    buffer.write(0)?; // enchant level
+    buffer.write(0)?; // custom type 2
+    buffer.write_u32(0u32)?; // mana
+    buffer.write_i32(-9999)?; // time
+    buffer.write(true)?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
     use crate::controller::GameController;
@@ -88,7 +124,7 @@ mod test {
             .try_get_template(Class::try_from(char.class_id).unwrap())
             .unwrap();
         let player = Player::new(char, vec![], template.clone(), None);
-        let p = ItemList::new(&player, false).unwrap();
+        let p = ItemList::new(&player, &controller.quests, false).unwrap();
         assert_eq!(
             [17, 0, 0, 0, 0, 0, 0],
             p.get_buffer().get_data_mut(false)[2..]
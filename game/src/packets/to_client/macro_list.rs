@@ -81,7 +81,7 @@ impl MacroList {
                 inst.buffer.write(u8::try_from(i + 1)?)?;
                 inst.buffer.write(cmd.get_type())?;
                 inst.buffer.write_i32(cmd.get_d1())?;
-                inst.buffer.write_i32(cmd.get_d2())?;
+                inst.buffer.write(u8::try_from(cmd.get_d2())?)?;
                 inst.buffer
                     .write_c_utf16le_string(Some(cmd.get_cmd_name()))?;
             }
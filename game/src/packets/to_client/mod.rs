@@ -10,21 +10,48 @@ mod char_info;
 mod char_move_to_location;
 mod char_selected;
 mod char_selection;
+mod choose_inventory_item;
+mod creature_say;
 mod delete_object;
 pub mod extended;
+mod enchant_result;
+mod friend_add_request;
 mod friend_list;
+mod friend_say;
+mod henna_equip_list;
 mod henna_info;
+mod henna_item_draw_info;
+mod henna_item_remove_info;
+mod henna_remove_list;
+mod inventory_update;
 mod item_list;
 mod login_response;
 mod macro_list;
 mod magic_skill_launched;
 mod magic_skill_use;
 mod move_to;
+mod multi_sell_list;
 mod new_char_response;
+mod npc_html_message;
+mod package_sendable_list;
+mod package_to_list;
+mod pet_delete;
+mod pet_info;
+mod pet_item_list;
+mod pet_status_show;
+mod pet_status_update;
 mod protocol_response;
 mod quest_list;
+mod recipe_book_item_list;
+mod recipe_item_make_info;
+mod recipe_shop_item_info;
+mod recipe_shop_manage_list;
+mod recipe_shop_msg;
+mod recipe_shop_sell_list;
 mod relation_changed;
 mod restart_resp;
+mod ride;
+mod shortcut_register;
 mod shortcuts_init;
 mod skill_cooltime;
 mod skill_list;
@@ -32,7 +59,10 @@ mod status_update;
 mod system_message;
 mod target_selected;
 mod target_unselected;
+mod teleport_to_location;
 mod user_info;
+mod ware_house_deposit_list;
+mod ware_house_withdrawal_list;
 
 pub use abnormal_status_update::*;
 pub use acquire_skill_list::*;
@@ -46,20 +76,47 @@ pub use char_info::*;
 pub use char_move_to_location::*;
 pub use char_selected::*;
 pub use char_selection::*;
+pub use choose_inventory_item::*;
+pub use creature_say::*;
 pub use delete_object::*;
+pub use enchant_result::*;
+pub use friend_add_request::*;
 pub use friend_list::*;
+pub use friend_say::*;
+pub use henna_equip_list::*;
 pub use henna_info::*;
+pub use henna_item_draw_info::*;
+pub use henna_item_remove_info::*;
+pub use henna_remove_list::*;
+pub use inventory_update::*;
 pub use item_list::*;
 pub use login_response::*;
 pub use macro_list::*;
 pub use magic_skill_launched::*;
 pub use magic_skill_use::*;
 pub use move_to::*;
+pub use multi_sell_list::*;
 pub use new_char_response::*;
+pub use npc_html_message::*;
+pub use package_sendable_list::*;
+pub use package_to_list::*;
+pub use pet_delete::*;
+pub use pet_info::*;
+pub use pet_item_list::*;
+pub use pet_status_show::*;
+pub use pet_status_update::*;
 pub use protocol_response::*;
 pub use quest_list::*;
+pub use recipe_book_item_list::*;
+pub use recipe_item_make_info::*;
+pub use recipe_shop_item_info::*;
+pub use recipe_shop_manage_list::*;
+pub use recipe_shop_msg::*;
+pub use recipe_shop_sell_list::*;
 pub use relation_changed::*;
 pub use restart_resp::*;
+pub use ride::*;
+pub use shortcut_register::*;
 pub use shortcuts_init::*;
 pub use skill_cooltime::*;
 pub use skill_list::*;
@@ -67,4 +124,7 @@ pub use status_update::*;
 pub use system_message::*;
 pub use target_selected::*;
 pub use target_unselected::*;
+pub use teleport_to_location::*;
 pub use user_info::*;
+pub use ware_house_deposit_list::*;
+pub use ware_house_withdrawal_list::*;
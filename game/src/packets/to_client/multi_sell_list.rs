@@ -0,0 +1,100 @@
+use l2_core::data::items::ItemsData;
+use l2_core::data::multisell::MultisellList;
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+
+/// One page of a multisell list, long lists are sent page by page.
+#[derive(Debug, Clone, SendablePacket)]
+pub struct MultiSellList {
+    pub(crate) buffer: SendablePacketBuffer,
+}
+
+impl MultiSellList {
+    pub const PACKET_ID: u8 = 0xD0;
+    pub const PAGE_SIZE: usize = 40;
+
+    /// `page` starts from 0, entries are numbered from 1 like in [`MultisellList::get_entry`].
+    pub fn new(
+        list: &MultisellList,
+        page: usize,
+        items: &ItemsData,
+        tax_rate: u8,
+    ) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        let first = page * Self::PAGE_SIZE;
+        let entries: Vec<_> = list
+            .entries
+            .iter()
+            .enumerate()
+            .skip(first)
+            .take(Self::PAGE_SIZE)
+            .collect();
+        let finished = first + entries.len() >= list.entries.len();
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write(0)?;
+        inst.buffer.write_i32(list.id)?;
+        inst.buffer.write(0)?;
+        inst.buffer.write_u32(u32::try_from(page + 1)?)?;
+        inst.buffer.write_u32(u32::from(finished))?;
+        inst.buffer.write_u32(u32::try_from(Self::PAGE_SIZE)?)?;
+        inst.buffer.write_u32(u32::try_from(entries.len())?)?;
+        inst.buffer.write(0)?; // not a chance multisell
+        for (index, entry) in entries {
+            inst.buffer.write_i32(i32::try_from(index + 1)?)?;
+            inst.buffer.write(entry.is_stackable(items))?;
+            inst.buffer
+                .write_u16(u16::try_from(entry.products.len())?)?;
+            inst.buffer
+                .write_u16(u16::try_from(entry.ingredients.len())?)?;
+            for product in &entry.products {
+                inst.buffer.write_i32(product.id)?;
+                inst.buffer.write_i64(0)?; // body part
+                inst.buffer.write_u16(0u16)?; // type 2
+                inst.buffer.write_i64(product.count)?;
+                inst.buffer.write_u16(0u16)?; // enchant level
+                inst.buffer.write_i32(100)?; // chance
+            }
+            for ingredient in &entry.ingredients {
+                inst.buffer.write_i32(ingredient.id)?;
+                inst.buffer.write_u16(0u16)?; // type 2
+                inst.buffer
+                    .write_i64(list.ingredient_count(ingredient, tax_rate))?;
+                inst.buffer.write_u16(0u16)?; // enchant level
+            }
+        }
+        Ok(inst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use l2_core::config::traits::ConfigDirLoader;
+    use l2_core::data::multisell::{MultisellEntry, MultisellItem};
+    use l2_core::shared_packets::common::SendablePacket;
+
+    #[test]
+    fn test_multi_sell_list() {
+        let list = MultisellList {
+            id: 9,
+            npc_ids: vec![],
+            apply_taxes: true,
+            maintain_enchantment: false,
+            entries: vec![MultisellEntry {
+                ingredients: vec![MultisellItem { id: 57, count: 100 }],
+                products: vec![MultisellItem { id: 1835, count: 5 }],
+            }],
+        };
+        let p = MultiSellList::new(&list, 0, &ItemsData::load(), 10).unwrap();
+        assert_eq!(
+            [
+                208, 0, 9, 0, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 40, 0, 0, 0, 1, 0, 0, 0, 0, 1, 0, 0,
+                0, 1, 1, 0, 1, 0, 43, 7, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 5, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 100, 0, 0, 0, 57, 0, 0, 0, 0, 0, 110, 0, 0, 0, 0, 0, 0, 0, 0, 0
+            ],
+            p.get_buffer().get_data_mut(false)[2..]
+        );
+    }
+}
@@ -0,0 +1,42 @@
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+
+/// Dialog window opened by an npc or an item
+#[derive(Debug, Clone, SendablePacket)]
+pub struct NpcHtmlMessage {
+    pub(crate) buffer: SendablePacketBuffer,
+}
+
+impl NpcHtmlMessage {
+    pub const PACKET_ID: u8 = 0x19;
+
+    /// `npc_object_id` is 0 for dialogs not bound to an npc, `item_id` is set for item dialogs
+    pub fn new(npc_object_id: i32, html: &str, item_id: i32) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_i32(npc_object_id)?;
+        inst.buffer.write_c_utf16le_string(Some(html))?;
+        inst.buffer.write_i32(item_id)?;
+        inst.buffer.write_u32(0u32)?;
+        Ok(inst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use l2_core::shared_packets::common::SendablePacket;
+
+    #[test]
+    fn test_npc_html_message() {
+        let packet = NpcHtmlMessage::new(7, "<a>", 0).unwrap();
+        assert_eq!(
+            [
+                25, 7, 0, 0, 0, 60, 0, 97, 0, 62, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0
+            ],
+            packet.get_buffer().get_data_mut(false)[2..]
+        );
+    }
+}
@@ -0,0 +1,58 @@
+use crate::packets::to_client::item_list::write_item;
+use l2_core::game_objects::item::ItemObject;
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+
+/// Inventory items which can be sent as freight to the char, sent in two parts like the
+/// warehouse lists.
+#[derive(Debug, Clone, SendablePacket)]
+pub struct PackageSendableList {
+    pub(crate) buffer: SendablePacketBuffer,
+}
+
+impl PackageSendableList {
+    pub const PACKET_ID: u8 = 0xD2;
+
+    pub fn summary(target_id: i32, adena: u64, items_count: usize) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write(1)?;
+        inst.buffer.write_i32(target_id)?;
+        inst.buffer.write_i64(i64::try_from(adena)?)?;
+        inst.buffer.write_u32(u32::try_from(items_count)?)?;
+        Ok(inst)
+    }
+
+    pub fn items(items: &[&ItemObject]) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        let size = u32::try_from(items.len())?;
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write(2)?;
+        inst.buffer.write_u32(size)?;
+        inst.buffer.write_u32(size)?;
+        for item in items {
+            write_item(&mut inst.buffer, item)?;
+            inst.buffer.write_i32(item.object_id.value())?;
+        }
+        Ok(inst)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use l2_core::shared_packets::common::SendablePacket;
+
+    #[test]
+    fn test_sendable_summary() {
+        let p = PackageSendableList::summary(7, 1, 0).unwrap();
+        assert_eq!(
+            [210, 1, 7, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+            p.get_buffer().get_data_mut(false)[2..]
+        );
+    }
+}
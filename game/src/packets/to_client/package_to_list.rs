@@ -0,0 +1,41 @@
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+
+/// Chars of the account freight can be sent to.
+#[derive(Debug, Clone, SendablePacket)]
+pub struct PackageToList {
+    pub(crate) buffer: SendablePacketBuffer,
+}
+
+impl PackageToList {
+    pub const PACKET_ID: u8 = 0xC8;
+
+    /// `chars` are `(char id, name)`
+    pub fn new(chars: &[(i32, &str)]) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_u32(u32::try_from(chars.len())?)?;
+        for (char_id, name) in chars {
+            inst.buffer.write_i32(*char_id)?;
+            inst.buffer.write_c_utf16le_string(Some(name))?;
+        }
+        Ok(inst)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use l2_core::shared_packets::common::SendablePacket;
+
+    #[test]
+    fn test_package_to_list() {
+        let p = PackageToList::new(&[(7, "Al")]).unwrap();
+        assert_eq!(
+            [200, 1, 0, 0, 0, 7, 0, 0, 0, 65, 0, 108, 0, 0, 0],
+            p.get_buffer().get_data_mut(false)[2..]
+        );
+    }
+}
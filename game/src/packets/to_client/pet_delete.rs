@@ -0,0 +1,38 @@
+use l2_core::data::summons::SummonKind;
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+
+/// Closes the window of the summon which is gone.
+#[derive(Debug, Clone, SendablePacket)]
+pub struct PetDelete {
+    pub(crate) buffer: SendablePacketBuffer,
+}
+
+impl PetDelete {
+    pub const PACKET_ID: u8 = 0xB7;
+
+    pub fn new(kind: SummonKind, object_id: i32) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_i32(kind.client_type())?;
+        inst.buffer.write_i32(object_id)?;
+        Ok(inst)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use l2_core::shared_packets::common::SendablePacket;
+
+    #[test]
+    fn test_pet_delete() {
+        let p = PetDelete::new(SummonKind::Servitor, 7).unwrap();
+        assert_eq!(
+            [183, 1, 0, 0, 0, 7, 0, 0, 0],
+            p.get_buffer().get_data_mut(false)[2..]
+        );
+    }
+}
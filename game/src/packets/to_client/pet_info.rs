@@ -0,0 +1,144 @@
+use l2_core::data::summons::SummonTemplate;
+use l2_core::game_objects::stats::stat_enum::Stat;
+use l2_core::game_objects::summon::Summon;
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+use std::collections::HashMap;
+
+/// How the summon shows up
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PetInfoType {
+    Update = 1,
+    Summoned = 2,
+}
+
+/// The summon with everything its window shows, sent to the owner.
+#[derive(Debug, Clone, SendablePacket)]
+pub struct PetInfo {
+    pub(crate) buffer: SendablePacketBuffer,
+}
+
+impl PetInfo {
+    pub const PACKET_ID: u8 = 0xB2;
+    /// The client finds the npcs by the id with this added
+    const NPC_ID_OFFSET: i32 = 1_000_000;
+
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn new(
+        summon: &Summon,
+        template: &SummonTemplate,
+        owner_name: &str,
+        stats: &HashMap<Stat, f64>,
+        info_type: PetInfoType,
+    ) -> anyhow::Result<Self> {
+        let stat = |s: Stat| stats.get(&s).copied().unwrap_or_default() as i32;
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        let location = summon.get_location();
+        let run_speed = template.run_speed;
+        let walk_speed = template.walk_speed;
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write(info_type as u8)?;
+        inst.buffer.write_i32(summon.kind.client_type())?;
+        inst.buffer.write_i32(summon.get_object_id())?;
+        inst.buffer.write_i32(summon.npc_id + Self::NPC_ID_OFFSET)?;
+        inst.buffer.write_i32(location.x)?;
+        inst.buffer.write_i32(location.y)?;
+        inst.buffer.write_i32(location.z)?;
+        inst.buffer.write_i32(location.heading)?;
+        inst.buffer.write_i32(333)?; // m. atk. speed
+        inst.buffer.write_i32(300)?; // p. atk. speed
+        // run, walk, swim run, swim walk, fly run, fly walk and twice more for flying
+        for _ in 0..4 {
+            inst.buffer.write_u16(run_speed)?;
+            inst.buffer.write_u16(walk_speed)?;
+        }
+        inst.buffer.write_f64(1.0)?; // move speed multiplier
+        inst.buffer.write_f64(1.0)?; // attack speed multiplier
+        inst.buffer.write_f64(template.collision_radius)?;
+        inst.buffer.write_f64(template.collision_height)?;
+        inst.buffer.write_i32(0)?; // right hand
+        inst.buffer.write_i32(0)?; // chest
+        inst.buffer.write_i32(0)?; // left hand
+        inst.buffer.write(1)?; // has an owner
+        inst.buffer.write(1)?; // running
+        inst.buffer.write(u8::from(summon.is_in_combat()))?;
+        inst.buffer.write(u8::from(summon.current_hp <= 0.0))?;
+        inst.buffer.write(info_type as u8)?;
+        inst.buffer.write_i32(-1)?; // name npc string
+        inst.buffer.write_c_utf16le_string(Some(&summon.name))?;
+        inst.buffer.write_i32(-1)?; // title npc string
+        inst.buffer.write_c_utf16le_string(Some(owner_name))?;
+        inst.buffer.write(0)?; // pvp flag
+        inst.buffer.write_i32(0)?; // reputation
+        inst.buffer.write_u32(summon.current_feed)?;
+        inst.buffer.write_u32(summon.get_max_feed(template))?;
+        inst.buffer.write_i32(summon.current_hp as i32)?;
+        inst.buffer.write_i32(stat(Stat::MaxHp))?;
+        inst.buffer.write_i32(summon.current_mp as i32)?;
+        inst.buffer.write_i32(stat(Stat::MaxMp))?;
+        inst.buffer.write_i64(0)?; // sp
+        inst.buffer.write(summon.level)?;
+        inst.buffer.write_i64(summon.exp)?;
+        inst.buffer
+            .write_i64(template.get_level(summon.level).exp.min(summon.exp))?;
+        inst.buffer
+            .write_i64(template.exp_for_next_level(summon.level))?;
+        inst.buffer.write_i32(0)?; // load
+        inst.buffer.write_i32(0)?; // max load
+        inst.buffer.write_i32(stat(Stat::PAtk))?;
+        inst.buffer.write_i32(stat(Stat::PDef))?;
+        inst.buffer.write_i32(stat(Stat::PAccuracy))?;
+        inst.buffer.write_i32(stat(Stat::PEvasion))?;
+        inst.buffer.write_i32(stat(Stat::PCriticalRate))?;
+        inst.buffer.write_i32(stat(Stat::MAtk))?;
+        inst.buffer.write_i32(stat(Stat::MDef))?;
+        inst.buffer.write_i32(stat(Stat::MAccuracy))?;
+        inst.buffer.write_i32(stat(Stat::MEvasion))?;
+        inst.buffer.write_i32(stat(Stat::MCriticalRate))?;
+        inst.buffer.write_i32(i32::from(run_speed))?;
+        inst.buffer.write_i32(300)?; // p. atk. speed
+        inst.buffer.write_i32(333)?; // m. atk. speed
+        inst.buffer.write(0)?; // abnormal visual effects
+        inst.buffer.write(0)?; // team
+        inst.buffer.write(0)?; // soulshots per hit
+        inst.buffer.write(0)?; // spiritshots per hit
+        inst.buffer.write_i32(0)?; // form
+        inst.buffer.write_i32(0)?;
+        Ok(inst)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use l2_core::config::traits::ConfigDirLoader;
+    use l2_core::data::summons::SummonData;
+    use l2_core::game_objects::zone::Location;
+    use l2_core::shared_packets::common::SendablePacket;
+
+    #[test]
+    fn test_pet_info() {
+        let data = SummonData::load();
+        let template = data.get(12077).unwrap();
+        let model = Summon::new_pet_model(template, 7);
+        let location = Location {
+            x: 1,
+            y: 2,
+            z: 3,
+            heading: 0,
+        };
+        let pet = Summon::pet(template, &model, vec![], location);
+        let stats = pet.calc_stats(template, &HashMap::new());
+        let p = PetInfo::new(&pet, template, "Owner", &stats, PetInfoType::Summoned).unwrap();
+        let data = p.get_buffer().get_data_mut(false)[2..].to_vec();
+        let object_id = pet.get_object_id().to_le_bytes();
+        assert_eq!(data[..2], [178, 2]);
+        assert_eq!(data[2..6], 2i32.to_le_bytes());
+        assert_eq!(data[6..10], object_id);
+        assert_eq!(data[10..14], 1_012_077i32.to_le_bytes());
+        assert_eq!(data[14..26], [1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0]);
+    }
+}
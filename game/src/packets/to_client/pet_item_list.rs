@@ -0,0 +1,38 @@
+use crate::packets::to_client::item_list::write_item;
+use l2_core::game_objects::item::ItemObject;
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+
+/// Items the pet carries, shown in the inventory tab of the pet window.
+#[derive(Debug, Clone, SendablePacket)]
+pub struct PetItemList {
+    pub(crate) buffer: SendablePacketBuffer,
+}
+
+impl PetItemList {
+    pub const PACKET_ID: u8 = 0xB3;
+
+    pub fn new(items: &[&ItemObject]) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_u16(u16::try_from(items.len())?)?;
+        for item in items {
+            write_item(&mut inst.buffer, item)?;
+        }
+        Ok(inst)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use l2_core::shared_packets::common::SendablePacket;
+
+    #[test]
+    fn test_empty_pet_item_list() {
+        let p = PetItemList::new(&[]).unwrap();
+        assert_eq!([179, 0, 0], p.get_buffer().get_data_mut(false)[2..]);
+    }
+}
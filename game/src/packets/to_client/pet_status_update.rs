@@ -0,0 +1,81 @@
+use l2_core::data::summons::SummonTemplate;
+use l2_core::game_objects::summon::Summon;
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+
+/// HP, MP, food and exp of the summon, updates the bars of its window.
+#[derive(Debug, Clone, SendablePacket)]
+pub struct PetStatusUpdate {
+    pub(crate) buffer: SendablePacketBuffer,
+}
+
+impl PetStatusUpdate {
+    pub const PACKET_ID: u8 = 0xB6;
+
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn new(
+        summon: &Summon,
+        template: &SummonTemplate,
+        owner_name: &str,
+    ) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        let location = summon.get_location();
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_i32(summon.kind.client_type())?;
+        inst.buffer.write_i32(summon.get_object_id())?;
+        inst.buffer.write_i32(location.x)?;
+        inst.buffer.write_i32(location.y)?;
+        inst.buffer.write_i32(location.z)?;
+        inst.buffer.write_c_utf16le_string(Some(owner_name))?;
+        inst.buffer.write_u32(summon.current_feed)?;
+        inst.buffer.write_u32(summon.get_max_feed(template))?;
+        inst.buffer.write_i32(summon.current_hp as i32)?;
+        inst.buffer.write_i32(summon.get_max_hp(template) as i32)?;
+        inst.buffer.write_i32(summon.current_mp as i32)?;
+        inst.buffer.write_i32(summon.get_max_mp(template) as i32)?;
+        inst.buffer.write_i32(i32::from(summon.level))?;
+        inst.buffer.write_i64(summon.exp)?;
+        inst.buffer
+            .write_i64(template.get_level(summon.level).exp.min(summon.exp))?;
+        inst.buffer
+            .write_i64(template.exp_for_next_level(summon.level))?;
+        inst.buffer.write_i32(0)?;
+        Ok(inst)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use l2_core::config::traits::ConfigDirLoader;
+    use l2_core::data::summons::SummonData;
+    use l2_core::game_objects::zone::Location;
+    use l2_core::shared_packets::common::SendablePacket;
+
+    #[test]
+    fn test_pet_status_update() {
+        let data = SummonData::load();
+        let template = data.get(12077).unwrap();
+        let mut model = Summon::new_pet_model(template, 7);
+        model.cur_hp = 100.0;
+        model.fed = 600;
+        let location = Location {
+            x: 1,
+            y: 2,
+            z: 3,
+            heading: 0,
+        };
+        let pet = Summon::pet(template, &model, vec![], location);
+        let p = PetStatusUpdate::new(&pet, template, "A").unwrap();
+        let data = p.get_buffer().get_data_mut(false)[2..].to_vec();
+        assert_eq!(data[..5], [182, 2, 0, 0, 0]);
+        assert_eq!(data[9..21], [1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0]);
+        // owner name, the food and the HP
+        assert_eq!(data[21..25], [65, 0, 0, 0]);
+        assert_eq!(data[25..29], 600u32.to_le_bytes());
+        assert_eq!(data[29..33], 1200u32.to_le_bytes());
+        assert_eq!(data[33..37], 100i32.to_le_bytes());
+    }
+}
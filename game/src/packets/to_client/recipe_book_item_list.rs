@@ -0,0 +1,43 @@
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+
+/// The dwarven or the common recipe book.
+#[derive(Debug, Clone, SendablePacket)]
+pub struct RecipeBookItemList {
+    pub(crate) buffer: SendablePacketBuffer,
+}
+
+impl RecipeBookItemList {
+    pub const PACKET_ID: u8 = 0xDC;
+
+    pub fn new(dwarven: bool, max_mp: u32, recipe_ids: &[i32]) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_i32(i32::from(!dwarven))?; // 0 - dwarven, 1 - common
+        inst.buffer.write_u32(max_mp)?;
+        inst.buffer.write_u32(u32::try_from(recipe_ids.len())?)?;
+        for (i, recipe_id) in recipe_ids.iter().enumerate() {
+            inst.buffer.write_i32(*recipe_id)?;
+            inst.buffer.write_u32(u32::try_from(i + 1)?)?; // position in the book
+        }
+        Ok(inst)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use l2_core::shared_packets::common::SendablePacket;
+
+    #[test]
+    fn test_recipe_book_item_list() {
+        let p = RecipeBookItemList::new(false, 120, &[3, 9]).unwrap();
+        let mut expected = vec![0xDC];
+        for v in [1, 120, 2, 3, 1, 9, 2] {
+            expected.extend_from_slice(&i32::to_le_bytes(v));
+        }
+        assert_eq!(expected, p.get_buffer().get_data_mut(false)[2..]);
+    }
+}
@@ -0,0 +1,48 @@
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+
+/// The craft window of a recipe, with the outcome of the last craft.
+#[derive(Debug, Clone, SendablePacket)]
+pub struct RecipeItemMakeInfo {
+    pub(crate) buffer: SendablePacketBuffer,
+}
+
+impl RecipeItemMakeInfo {
+    pub const PACKET_ID: u8 = 0xDD;
+
+    /// `success` is None when the window opens without a craft.
+    pub fn new(
+        recipe_id: i32,
+        dwarven: bool,
+        cur_mp: u32,
+        max_mp: u32,
+        success: Option<bool>,
+    ) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_i32(recipe_id)?;
+        inst.buffer.write_i32(i32::from(!dwarven))?; // 0 - dwarven, 1 - common
+        inst.buffer.write_u32(cur_mp)?;
+        inst.buffer.write_u32(max_mp)?;
+        inst.buffer.write_i32(success.map_or(-1, i32::from))?;
+        Ok(inst)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use l2_core::shared_packets::common::SendablePacket;
+
+    #[test]
+    fn test_recipe_item_make_info() {
+        let p = RecipeItemMakeInfo::new(2, true, 40, 100, None).unwrap();
+        let mut expected = vec![0xDD];
+        for v in [2, 0, 40, 100, -1] {
+            expected.extend_from_slice(&i32::to_le_bytes(v));
+        }
+        assert_eq!(expected, p.get_buffer().get_data_mut(false)[2..]);
+    }
+}
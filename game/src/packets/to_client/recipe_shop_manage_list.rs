@@ -0,0 +1,67 @@
+use l2_core::game_objects::player::ManufactureShop;
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+
+/// The manufacture shop setup: the recipes of the book and the ones the shop offers.
+#[derive(Debug, Clone, SendablePacket)]
+pub struct RecipeShopManageList {
+    pub(crate) buffer: SendablePacketBuffer,
+}
+
+impl RecipeShopManageList {
+    pub const PACKET_ID: u8 = 0xDE;
+
+    pub fn new(
+        seller_id: i32,
+        adena: u64,
+        shop: &ManufactureShop,
+        recipe_ids: &[i32],
+    ) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_i32(seller_id)?;
+        inst.buffer
+            .write_i32(i32::try_from(adena).unwrap_or(i32::MAX))?;
+        inst.buffer.write_i32(i32::from(!shop.dwarven))?; // 0 - dwarven, 1 - common
+        inst.buffer.write_u32(u32::try_from(recipe_ids.len())?)?;
+        for (i, recipe_id) in recipe_ids.iter().enumerate() {
+            inst.buffer.write_i32(*recipe_id)?;
+            inst.buffer.write_u32(u32::try_from(i + 1)?)?;
+        }
+        inst.buffer.write_u32(u32::try_from(shop.items.len())?)?;
+        for item in &shop.items {
+            inst.buffer.write_i32(item.recipe_id)?;
+            inst.buffer.write_i32(0)?; // can ideally be 0
+            inst.buffer.write_i64(item.cost)?;
+        }
+        Ok(inst)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use l2_core::game_objects::player::ManufactureItem;
+    use l2_core::shared_packets::common::SendablePacket;
+
+    #[test]
+    fn test_recipe_shop_manage_list() {
+        let shop = ManufactureShop {
+            dwarven: true,
+            message: String::new(),
+            items: vec![ManufactureItem {
+                recipe_id: 1,
+                cost: 500,
+            }],
+        };
+        let p = RecipeShopManageList::new(7, 1000, &shop, &[1, 2]).unwrap();
+        let mut expected = vec![0xDE];
+        for v in [7, 1000, 0, 2, 1, 1, 2, 2, 1, 1, 0] {
+            expected.extend_from_slice(&i32::to_le_bytes(v));
+        }
+        expected.extend_from_slice(&500i64.to_le_bytes());
+        assert_eq!(expected, p.get_buffer().get_data_mut(false)[2..]);
+    }
+}
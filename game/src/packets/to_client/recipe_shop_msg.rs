@@ -0,0 +1,36 @@
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+
+/// The message over the head of a manufacturer.
+#[derive(Debug, Clone, SendablePacket)]
+pub struct RecipeShopMsg {
+    pub(crate) buffer: SendablePacketBuffer,
+}
+
+impl RecipeShopMsg {
+    pub const PACKET_ID: u8 = 0xE1;
+
+    pub fn new(manufacturer_id: i32, message: &str) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_i32(manufacturer_id)?;
+        inst.buffer.write_c_utf16le_string(Some(message))?;
+        Ok(inst)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use l2_core::shared_packets::common::SendablePacket;
+
+    #[test]
+    fn test_recipe_shop_msg() {
+        let p = RecipeShopMsg::new(7, "Hi").unwrap();
+        let mut expected = vec![0xE1, 7, 0, 0, 0];
+        expected.extend_from_slice(&[b'H', 0, b'i', 0, 0, 0]);
+        assert_eq!(expected, p.get_buffer().get_data_mut(false)[2..]);
+    }
+}
@@ -0,0 +1,62 @@
+use l2_core::game_objects::player::ManufactureItem;
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+
+/// What a manufacture shop offers, shown to the customer.
+#[derive(Debug, Clone, SendablePacket)]
+pub struct RecipeShopSellList {
+    pub(crate) buffer: SendablePacketBuffer,
+}
+
+impl RecipeShopSellList {
+    pub const PACKET_ID: u8 = 0xDF;
+
+    pub fn new(
+        manufacturer_id: i32,
+        cur_mp: u32,
+        max_mp: u32,
+        customer_adena: u64,
+        items: &[ManufactureItem],
+    ) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_i32(manufacturer_id)?;
+        inst.buffer.write_u32(cur_mp)?;
+        inst.buffer.write_u32(max_mp)?;
+        inst.buffer.write_u64(customer_adena)?;
+        inst.buffer.write_u32(u32::try_from(items.len())?)?;
+        for item in items {
+            inst.buffer.write_i32(item.recipe_id)?;
+            inst.buffer.write_i32(0)?; // can ideally be 0
+            inst.buffer.write_i64(item.cost)?;
+        }
+        Ok(inst)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use l2_core::shared_packets::common::SendablePacket;
+
+    #[test]
+    fn test_recipe_shop_sell_list() {
+        let items = [ManufactureItem {
+            recipe_id: 3,
+            cost: 100,
+        }];
+        let p = RecipeShopSellList::new(7, 50, 80, 1000, &items).unwrap();
+        let mut expected = vec![0xDF];
+        for v in [7, 50, 80] {
+            expected.extend_from_slice(&i32::to_le_bytes(v));
+        }
+        expected.extend_from_slice(&1000i64.to_le_bytes());
+        for v in [1, 3, 0] {
+            expected.extend_from_slice(&i32::to_le_bytes(v));
+        }
+        expected.extend_from_slice(&100i64.to_le_bytes());
+        assert_eq!(expected, p.get_buffer().get_data_mut(false)[2..]);
+    }
+}
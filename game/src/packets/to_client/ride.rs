@@ -0,0 +1,81 @@
+use l2_core::game_objects::player::Player;
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+
+/// The player gets on or off the mount.
+#[derive(Debug, Clone, SendablePacket)]
+pub struct Ride {
+    pub(crate) buffer: SendablePacketBuffer,
+}
+
+impl Ride {
+    pub const PACKET_ID: u8 = 0x8C;
+
+    pub fn new(player: &Player) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_i32(player.get_object_id())?;
+        inst.buffer.write_i32(i32::from(player.is_mounted()))?;
+        inst.buffer.write_i32(i32::from(player.get_mount_type()))?;
+        inst.buffer
+            .write_i32(player.get_mount_npc_id() + 1_000_000)?;
+        inst.buffer.write_i32(player.get_x())?;
+        inst.buffer.write_i32(player.get_y())?;
+        inst.buffer.write_i32(player.get_z())?;
+        Ok(inst)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::controller::GameController;
+    use crate::packets::to_client::Ride;
+    use entities::test_factories::factories::{char_factory, user_factory};
+    use l2_core::config::gs::GSServerConfig;
+    use l2_core::data::classes::mapping::Class;
+    use l2_core::data::summons::MountType;
+    use l2_core::game_objects::mount::Mount;
+    use l2_core::game_objects::player::Player;
+    use l2_core::shared_packets::common::SendablePacket;
+    use l2_core::traits::ServerConfig;
+    use std::sync::Arc;
+    use test_utils::utils::get_test_db;
+
+    #[tokio::test]
+    async fn test_ride() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let char = char_factory(&db_pool, |mut m| {
+            m.user_id = user.id;
+            m.x = 1;
+            m.y = 2;
+            m.z = 3;
+            m
+        })
+        .await;
+        let cfg = Arc::new(GSServerConfig::from_string(include_str!(
+            "../../../../config/game.yaml"
+        )));
+        let controller = GameController::from_config(cfg).await;
+        let template = controller
+            .class_templates
+            .try_get_template(Class::try_from(char.class_id).unwrap())
+            .unwrap();
+        let mut player = Player::new(char, vec![], template.clone(), None);
+        player.mount = Some(Mount {
+            npc_id: 12526,
+            kind: MountType::Strider,
+            control_item_id: 7,
+            run_speed: 180,
+            walk_speed: 100,
+        });
+        let p = Ride::new(&player).unwrap();
+        let mut expected = vec![140];
+        for v in [player.get_object_id(), 1, 1, 1_012_526, 1, 2, 3] {
+            expected.extend_from_slice(&v.to_le_bytes());
+        }
+        assert_eq!(expected, p.get_buffer().get_data_mut(false)[2..]);
+    }
+}
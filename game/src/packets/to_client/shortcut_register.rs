@@ -0,0 +1,47 @@
+use crate::packets::to_client::shortcuts_init::write_shortcut;
+use l2_core::game_objects::player::Shortcut;
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+
+#[derive(Debug, Clone, SendablePacket)]
+pub struct ShortcutRegister {
+    pub(crate) buffer: SendablePacketBuffer,
+}
+
+impl ShortcutRegister {
+    pub const PACKET_ID: u8 = 0x44;
+
+    pub fn new(sc: &Shortcut) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        write_shortcut(&mut inst.buffer, sc)?;
+        Ok(inst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use l2_core::game_objects::player::ShortcutType;
+    use l2_core::shared_packets::common::SendablePacket;
+
+    #[test]
+    fn test_shortcut_register() {
+        let sc = Shortcut {
+            slot: 0,
+            page: 0,
+            shortcut_type: ShortcutType::Action,
+            id: 2,
+            level: 0,
+            sub_level: 0,
+            character_type: 0,
+        };
+        let p = ShortcutRegister::new(&sc).unwrap();
+        assert_eq!(
+            [68, 3, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0],
+            p.get_buffer().get_data_mut(false)[2..]
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use l2_core::game_objects::player::Player;
+use l2_core::game_objects::player::{Player, Shortcut, ShortcutType};
 use l2_core::shared_packets::write::SendablePacketBuffer;
 use macro_common::SendablePacket;
 
@@ -10,16 +10,48 @@ pub struct ShortcutsInit {
 impl ShortcutsInit {
     pub const PACKET_ID: u8 = 0x45;
 
-    pub fn new(_p: &Player) -> anyhow::Result<Self> {
+    pub fn new(p: &Player) -> anyhow::Result<Self> {
         let mut inst = Self {
             buffer: SendablePacketBuffer::new(),
         };
         inst.buffer.write(Self::PACKET_ID)?;
-        inst.buffer.write_u32(0u16)?; //count
-        //todo: implement me
+        let shortcuts = p.get_shortcuts();
+        inst.buffer.write_u32(u32::try_from(shortcuts.len())?)?;
+        for sc in shortcuts {
+            write_shortcut(&mut inst.buffer, sc)?;
+        }
         Ok(inst)
     }
 }
+
+/// Shortcut layout shared by `ShortcutsInit` and `ShortcutRegister`.
+pub(crate) fn write_shortcut(
+    buffer: &mut SendablePacketBuffer,
+    sc: &Shortcut,
+) -> anyhow::Result<()> {
+    buffer.write_i32(sc.shortcut_type as i32)?;
+    buffer.write_i32(sc.position())?;
+    buffer.write_i32(sc.id)?;
+    match sc.shortcut_type {
+        ShortcutType::Item => {
+            buffer.write_i32(sc.character_type)?;
+            buffer.write_i32(-1)?; // shared reuse group
+            buffer.write_i32(0)?; // remaining reuse time
+            buffer.write_i32(0)?; // reuse time
+            buffer.write_i64(0)?; // augmentation
+            buffer.write_i32(0)?; // visual id
+        }
+        ShortcutType::Skill => {
+            buffer.write_i16(sc.level)?;
+            buffer.write_i16(sc.sub_level)?;
+            buffer.write_i32(-1)?; // shared reuse group
+            buffer.write(0u8)?;
+            buffer.write_i32(sc.character_type)?;
+        }
+        _ => buffer.write_i32(sc.character_type)?,
+    }
+    Ok(())
+}
 #[cfg(test)]
 mod test {
     use crate::controller::GameController;
@@ -27,7 +59,7 @@ mod test {
     use entities::test_factories::factories::{char_factory, user_factory};
     use l2_core::config::gs::GSServerConfig;
     use l2_core::data::classes::mapping::Class;
-    use l2_core::game_objects::player::Player;
+    use l2_core::game_objects::player::{Player, Shortcut, ShortcutType};
     use l2_core::shared_packets::common::SendablePacket;
     use l2_core::traits::ServerConfig;
     use std::sync::Arc;
@@ -54,5 +86,32 @@ mod test {
         let player = Player::new(char, vec![], template.clone(), None);
         let p = ShortcutsInit::new(&player).unwrap();
         assert_eq!([69, 0, 0, 0, 0], p.get_buffer().get_data_mut(false)[2..]);
+        let mut player = player;
+        player.register_shortcut(Shortcut {
+            slot: 1,
+            page: 1,
+            shortcut_type: ShortcutType::Skill,
+            id: 1177,
+            level: 5,
+            sub_level: 0,
+            character_type: 1,
+        });
+        player.register_shortcut(Shortcut {
+            slot: 2,
+            page: 0,
+            shortcut_type: ShortcutType::Macro,
+            id: 1000,
+            level: 0,
+            sub_level: 0,
+            character_type: 0,
+        });
+        let p = ShortcutsInit::new(&player).unwrap();
+        assert_eq!(
+            [
+                69, 2, 0, 0, 0, 2, 0, 0, 0, 13, 0, 0, 0, 153, 4, 0, 0, 5, 0, 0, 0, 255, 255, 255,
+                255, 0, 1, 0, 0, 0, 4, 0, 0, 0, 2, 0, 0, 0, 232, 3, 0, 0, 0, 0, 0, 0
+            ],
+            p.get_buffer().get_data_mut(false)[2..]
+        );
     }
 }
@@ -25,6 +25,51 @@ pub enum SystemMessageType {
     S2HpHasBeenRestoredByC1 = 1067,                           // $s2 HP has been restored by $c1.
     S1MpHasBeenRestored = 1068,                               // $s1 MP has been restored.
     S2MpHasBeenRestoredByC1 = 1069,                           // $s2 MP has been restored by $c1.
+    TheSoulshotYouAreAttemptingToUseDoesNotMatchTheGradeOfYourEquippedWeapon = 337, // The soulshot you are attempting to use does not match the grade of your equipped weapon.
+    YouDoNotHaveEnoughSoulshotsForThat = 338, // You do not have enough soulshots for that.
+    YourSpiritshotDoesNotMatchTheWeaponSGrade = 530, // Your spiritshot does not match the weapon's grade.
+    YouDoNotHaveEnoughSpiritshotsForThat = 531, // You do not have enough spiritshots for that.
+    TheAutomaticUseOfS1HasBeenActivated = 1433, // The automatic use of $s1 has been activated.
+    TheAutomaticUseOfS1HasBeenDeactivated = 1434, // The automatic use of $s1 has been deactivated.
+    DueToInsufficientS1TheAutomaticUseFunctionHasBeenDeactivated = 1435, // Due to insufficient $s1, the automatic use function has been deactivated.
+    YouMayNotAttackInAPeacefulZone = 84, // You may not attack in a peaceful zone.
+    YouMayNotAttackThisTargetInAPeacefulZone = 85, // You may not attack this target in a peaceful zone.
+    YouDoNotHaveEnoughAdena = 279, // You do not have enough adena.
+    YouMayCreateUpTo48Macros = 797, // You may create up to 48 macros.
+    TheSymbolHasBeenAdded = 877, // The symbol has been added.
+    NoSlotExistsToDrawTheSymbol = 899, // No slot exists to draw the symbol.
+    TheSymbolHasBeenDeleted = 1317, // The symbol has been deleted.
+    MacroDescriptionsMayContainUpTo32Characters = 837, // Macro descriptions may contain up to 32 characters.
+    EnterTheNameOfTheMacro = 838, // Enter the name of the macro.
+    YouCannotUseMyTeleportsDuringABattle = 2348, // You cannot use My Teleports during a battle.
+    YouCannotUseMyTeleportsWhileParticipatingInALargeScaleBattle = 2349, // You cannot use My Teleports while participating a large-scale battle such as a castle siege, fortress siege, or clan hall siege.
+    YouCannotUseMyTeleportsInThisArea = 2354, // You cannot use My Teleports in this area.
+    YouHaveNoSpaceToSaveTheTeleportLocation = 2358, // You have no space to save the teleport location.
+    YouCannotTeleportBecauseYouDoNotHaveATeleportItem = 2359, // You cannot teleport because you do not have a teleport item.
+    YourNumberOfMyTeleportsSlotsHasReachedItsMaximumLimit = 2390, // Your number of My Teleports slots has reached its maximum limit.
+    YouHaveSucceededInInvitingAFriendToYourFriendsList = 132, // You have succeeded in inviting a friend to your friends list.
+    S1HasBeenAddedToYourFriendsList = 133, // $s1 has been added to your friends list.
+    S1HasBeenRemovedFromYourFriendsList = 134, // $s1 has been removed from your friends list.
+    TheTargetIsNotFoundInTheGame = 145, // The target is not found in the game.
+    MessageRefusalMode = 176, // Message refusal mode.
+    MessageAcceptanceMode = 177, // Message acceptance mode.
+    ThatPersonIsInMessageRefusalMode = 178, // That person is in message refusal mode.
+    S1HasBeenAddedToYourIgnoreList = 617, // $s1 has been added to your Ignore List.
+    S1HasBeenRemovedFromYourIgnoreList = 618, // $s1 has been removed from your Ignore List.
+    YourInventoryIsFull = 129, // Your inventory is full.
+    YouDoNotHaveEnoughRequiredItems = 701, // You do not have enough required items.
+    YourWarehouseIsFull = 130, // Your warehouse is full.
+    YouAreNotAClanMemberAndCannotPerformThisAction = 212, // You are not a clan member and cannot perform this action.
+    YouDoNotHaveTheRightToUseTheClanWarehouse = 566, // You do not have the right to use the clan warehouse.
+    OnlyClansOfClanLevel1OrAboveCanUseAWarehouse = 1039, // Only clans of clan level 1 or above can use a warehouse.
+    TheNewSubclassHasBeenAdded = 1269, // The new sub class has been added.
+    TheTransferOfSubclassHasBeenCompleted = 1270, // The transfer of sub-class has been completed.
+    YouCannotRecommendYourself = 829, // You cannot recommend yourself.
+    YouHaveRecommendedC1YouHaveS2RecommendationsLeft = 830, // You have recommended $c1. You have $s2 recommendations left.
+    YouHaveBeenRecommendedByC1 = 831, // You have been recommended by $c1.
+    ThatCharacterHasAlreadyBeenRecommended = 832, // That character has already been recommended.
+    YouAreNotAuthorizedToMakeFurtherRecommendationsAtThisTime = 833, // You are not authorized to make further recommendations at this time. You will receive more recommendation credits each day at 1 p.m.
+    OnlyCharactersOfLevel10OrAboveAreAuthorizedToMakeRecommendations = 898, // Only characters of level 10 or above are authorized to make recommendations.
 }
 
 impl From<SystemMessageType> for u16 {
@@ -33,6 +78,7 @@ impl From<SystemMessageType> for u16 {
     }
 }
 
+#[derive(Debug, Clone)]
 pub enum SystemMessageParam {
     Text(String),
     Int(i32),
@@ -129,6 +175,7 @@ impl SystemMessageParam {
     }
 }
 
+#[derive(Debug, Clone)]
 pub struct SystemMessage {
     pub message: SystemMessageType,
     pub params: Vec<SystemMessageParam>,
@@ -147,6 +194,13 @@ impl SystemMessage {
         self.params.push(param);
         Ok(())
     }
+
+    /// Message showing the text as is, for texts the client has no message of its own for.
+    pub fn text(text: &str) -> anyhow::Result<Self> {
+        let mut message = Self::new(SystemMessageType::S1_2)?;
+        message.add_param(SystemMessageParam::Text(text.to_string()))?;
+        Ok(message)
+    }
 }
 
 impl l2_core::shared_packets::common::SendablePacket for SystemMessage {
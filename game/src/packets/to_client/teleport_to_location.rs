@@ -0,0 +1,44 @@
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+
+#[derive(Debug, Clone, SendablePacket)]
+pub struct TeleportToLocation {
+    pub(crate) buffer: SendablePacketBuffer,
+}
+
+impl TeleportToLocation {
+    pub const PACKET_ID: u8 = 0x22;
+
+    pub fn new(obj_id: i32, x: i32, y: i32, z: i32, heading: i32) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_i32(obj_id)?;
+        inst.buffer.write_i32(x)?;
+        inst.buffer.write_i32(y)?;
+        inst.buffer.write_i32(z)?;
+        inst.buffer.write_i32(0)?; // is validation
+        inst.buffer.write_i32(heading)?;
+        inst.buffer.write_i32(0)?; // unknown
+        Ok(inst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use l2_core::shared_packets::common::SendablePacket;
+
+    #[test]
+    fn test_teleport_to_location() {
+        let p = TeleportToLocation::new(268_476_204, -84318, 244_579, -3730, 0).unwrap();
+        assert_eq!(
+            [
+                34, 44, 159, 0, 16, 162, 182, 254, 255, 99, 187, 3, 0, 110, 241, 255, 255, 0, 0, 0,
+                0, 0, 0, 0, 0, 0, 0, 0, 0
+            ],
+            p.get_buffer().get_data_mut(false)[2..]
+        );
+    }
+}
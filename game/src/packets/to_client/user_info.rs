@@ -30,7 +30,8 @@ impl UserInfo {
         user_info_flags: BitMask,
         controller: &GameController,
     ) -> anyhow::Result<Self> {
-        //todo: check is subclass locked
+        // no subclass lock: a class change runs within one message of the player actor, so
+        // the info is never built from a half changed class
         let mut block_size = 5;
         block_size += UserInfoType::calculate_block_size(&user_info_flags);
         let mut visible_name_size = 0;
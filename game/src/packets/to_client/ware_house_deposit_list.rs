@@ -0,0 +1,73 @@
+use crate::packets::to_client::item_list::write_item;
+use l2_core::game_objects::item::ItemObject;
+use l2_core::game_objects::player::warehouse::WarehouseKind;
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+
+/// Deposit window of a warehouse, sent in two parts: the summary and the items of the
+/// inventory which can be deposited.
+#[derive(Debug, Clone, SendablePacket)]
+pub struct WareHouseDepositList {
+    pub(crate) buffer: SendablePacketBuffer,
+}
+
+impl WareHouseDepositList {
+    pub const PACKET_ID: u8 = 0x41;
+
+    /// `stackable` are item ids the client may merge with the stacks already stored.
+    pub fn summary(
+        kind: WarehouseKind,
+        adena: u64,
+        warehouse_size: usize,
+        stackable: &[i32],
+    ) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write(1)?;
+        inst.buffer.write_u16(kind.client_type())?;
+        inst.buffer.write_i64(i64::try_from(adena)?)?;
+        inst.buffer.write_u32(u32::try_from(warehouse_size)?)?;
+        inst.buffer.write_u16(u16::try_from(stackable.len())?)?;
+        for item_id in stackable {
+            inst.buffer.write_i32(*item_id)?;
+        }
+        Ok(inst)
+    }
+
+    pub fn items(items: &[&ItemObject]) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        let size = u32::try_from(items.len())?;
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write(2)?;
+        inst.buffer.write_u32(size)?;
+        inst.buffer.write_u32(size)?;
+        for item in items {
+            write_item(&mut inst.buffer, item)?;
+            inst.buffer.write_i32(item.object_id.value())?;
+            inst.buffer.write_i32(0)?;
+            inst.buffer.write_i32(0)?;
+        }
+        Ok(inst)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use l2_core::shared_packets::common::SendablePacket;
+
+    #[test]
+    fn test_deposit_summary() {
+        let p = WareHouseDepositList::summary(WarehouseKind::Clan, 1000, 3, &[57]).unwrap();
+        assert_eq!(
+            [
+                65, 1, 2, 0, 232, 3, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 1, 0, 57, 0, 0, 0
+            ],
+            p.get_buffer().get_data_mut(false)[2..]
+        );
+    }
+}
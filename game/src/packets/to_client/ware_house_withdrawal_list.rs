@@ -0,0 +1,66 @@
+use crate::packets::to_client::item_list::write_item;
+use l2_core::game_objects::item::ItemObject;
+use l2_core::game_objects::player::warehouse::WarehouseKind;
+use l2_core::shared_packets::write::SendablePacketBuffer;
+use macro_common::SendablePacket;
+
+/// Withdraw window of a warehouse, sent in two parts: the summary and the stored items.
+#[derive(Debug, Clone, SendablePacket)]
+pub struct WareHouseWithdrawalList {
+    pub(crate) buffer: SendablePacketBuffer,
+}
+
+impl WareHouseWithdrawalList {
+    pub const PACKET_ID: u8 = 0x42;
+
+    pub fn summary(
+        kind: WarehouseKind,
+        adena: u64,
+        inventory_size: usize,
+        items_count: usize,
+    ) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write(1)?;
+        inst.buffer.write_u16(kind.client_type())?;
+        inst.buffer.write_i64(i64::try_from(adena)?)?;
+        inst.buffer.write_u32(u32::try_from(inventory_size)?)?;
+        inst.buffer.write_u32(u32::try_from(items_count)?)?;
+        Ok(inst)
+    }
+
+    pub fn items(inventory_size: usize, items: &[&ItemObject]) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write(2)?;
+        inst.buffer.write_u16(0u16)?;
+        inst.buffer.write_u32(u32::try_from(inventory_size)?)?;
+        inst.buffer.write_u32(u32::try_from(items.len())?)?;
+        for item in items {
+            write_item(&mut inst.buffer, item)?;
+            inst.buffer.write_i32(item.object_id.value())?;
+            inst.buffer.write_i32(0)?;
+            inst.buffer.write_i32(0)?;
+        }
+        Ok(inst)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use l2_core::shared_packets::common::SendablePacket;
+
+    #[test]
+    fn test_withdrawal_summary() {
+        let p = WareHouseWithdrawalList::summary(WarehouseKind::Private, 5, 10, 2).unwrap();
+        assert_eq!(
+            [66, 1, 1, 0, 5, 0, 0, 0, 0, 0, 0, 0, 10, 0, 0, 0, 2, 0, 0, 0],
+            p.get_buffer().get_data_mut(false)[2..]
+        );
+    }
+}
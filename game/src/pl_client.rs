@@ -1,11 +1,14 @@
+use crate::bypass::HtmlDialog;
 use crate::controller::GameController;
 use crate::cp_factory::build_client_packet;
 use crate::movement::{Arrived, MovementState};
 use crate::packets::to_client;
 use crate::packets::to_client::CharMoveToLocation;
+use crate::shops::OpenShop;
+use crate::warehouse::OpenWarehouse;
 use anyhow::{anyhow, bail};
 use bytes::BytesMut;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use entities::DBPool;
 use entities::entities::{character, user};
 use kameo::Actor;
@@ -15,10 +18,12 @@ use kameo::message::{Context, Message};
 use l2_core::crypt::game::GameClientEncryption;
 use l2_core::crypt::generate_blowfish_key;
 use l2_core::crypt::login::Encryption;
+use l2_core::errors::KameoAnyhowExt;
 use l2_core::game_objects::creature::buff::AppliedBuff;
-use l2_core::game_objects::player::Player;
+use l2_core::game_objects::player::{Contact, Player};
 use l2_core::game_objects::stats::calculator::Modifier;
 use l2_core::game_objects::stats::stat_enum::Stat;
+use l2_core::game_objects::zone::ZoneId;
 use l2_core::network::connection::{
     ConnectionActor, HandleIncomingPacket, HandleOutboundPacket, send_delayed_packet, send_packet,
     send_packet_blocking,
@@ -41,7 +46,7 @@ use tokio::task::JoinHandle;
 use tokio::time::sleep;
 use tracing::{error, info, instrument};
 
-type BoxedClosure = Box<
+pub(crate) type BoxedClosure = Box<
     dyn for<'a> FnOnce(
             &'a mut PlayerClient,
         ) -> Pin<Box<dyn Future<Output = anyhow::Result<()>> + Send + 'a>>
@@ -69,6 +74,14 @@ pub enum ClientStatus {
 pub enum PlayerTasks {
     ActionIntent,
     CauseDamage,
+    /// Scroll of escape cast, broken by damage and movement
+    Escape,
+    Fishing,
+    /// Seal of Ruler cast on the artifact of a castle
+    SealOfRuler,
+    Summon,
+    /// Summoning cast, the summon appears once it's over
+    SummonCast,
 }
 
 pub struct PlayerClient {
@@ -81,11 +94,23 @@ pub struct PlayerClient {
     account_chars: Option<Vec<Player>>,
     pub(crate) selected_char: Option<i32>,
     pub selected_target: Option<(i32, ActorRef<PlayerClient>)>,
+    /// Object id of the selected monster, players are in [`Self::selected_target`]
+    pub selected_monster: Option<i32>,
     pub packet_sender: Option<ActorRef<ConnectionActor<Self>>>,
     session_key: Option<SessionKey>,
     user: Option<user::Model>,
     movement_state: Option<MovementState>,
     player_tasks: HashMap<PlayerTasks, (JoinHandle<()>, Option<Arc<Notify>>)>,
+    /// The char which invited us to its friend list and waits for an answer
+    pub(crate) friend_request: Option<Contact>,
+    /// The last dialog sent, only the links it offers are accepted back
+    pub(crate) html_dialog: HtmlDialog,
+    /// Trades are accepted only for the shop the player has open
+    pub(crate) open_shop: Option<OpenShop>,
+    /// Items are moved only for the warehouse window the player has open
+    pub(crate) open_warehouse: Option<OpenWarehouse>,
+    /// Game time of the last hero voice message, it may be used once in a while only
+    pub(crate) hero_voice_at: Option<DateTime<Utc>>,
 }
 
 impl Debug for PlayerClient {
@@ -119,11 +144,17 @@ impl PlayerClient {
             protocol: None,
             user: None,
             selected_target: None,
+            selected_monster: None,
             session_key: None,
             selected_char: None,
             packet_sender: None,
             movement_state: None,
             player_tasks: HashMap::new(),
+            friend_request: None,
+            html_dialog: HtmlDialog::default(),
+            open_shop: None,
+            open_warehouse: None,
+            hero_voice_at: None,
         }
     }
 
@@ -403,9 +434,18 @@ impl PlayerClient {
     pub fn is_casting(&self) -> bool {
         //todo: it's not good to rely on this, because damage can be caused by other actions not only casting magic skills
         // but while auto attack it's possible to cancel it.
-        self.player_tasks
-            .get(&PlayerTasks::CauseDamage)
-            .is_some_and(|(handle, _)| !handle.is_finished())
+        [
+            PlayerTasks::CauseDamage,
+            PlayerTasks::Escape,
+            PlayerTasks::SealOfRuler,
+            PlayerTasks::SummonCast,
+        ]
+        .iter()
+        .any(|task| {
+            self.player_tasks
+                .get(task)
+                .is_some_and(|(handle, _)| !handle.is_finished())
+        })
     }
     /// Start or restart player movement
     pub fn start_movement(
@@ -419,6 +459,7 @@ impl PlayerClient {
             bail!("Can't start movement. PlayerClient is not in game");
         }
         self.remove_scheduled_task(PlayerTasks::ActionIntent);
+        self.interrupt_escape();
 
         // Compute effective current position consistent with validation logic
         let (current_x, current_y, current_z) = self.effective_current_position()?;
@@ -430,11 +471,7 @@ impl PlayerClient {
 
         // Get player speed
         let player = self.try_get_selected_char()?;
-        let speed = if player.is_running() {
-            player.get_run_speed()
-        } else {
-            player.get_walk_speed()
-        };
+        let speed = player.get_move_speed();
 
         // Create a new movement state
         let mut movement = MovementState::new(
@@ -479,6 +516,13 @@ impl PlayerClient {
         Ok((current_x, current_y, current_z))
     }
 
+    /// Apply a new speed to the current movement, if any
+    pub fn change_movement_speed(&mut self, speed: u16) {
+        if let Some(movement) = self.movement_state.as_mut() {
+            movement.change_speed(speed);
+        }
+    }
+
     /// Stop current movement and return the current interpolated position
     pub fn stop_movement(&mut self) -> Option<(i32, i32, i32)> {
         if let Some(mut movement) = self.movement_state.take() {
@@ -543,6 +587,10 @@ impl Actor for PlayerClient {
         if let Ok(player) = self.try_get_selected_char() {
             self.controller
                 .unregister_player_object(player.get_object_id());
+            if let Some(summon) = player.summon.as_ref() {
+                self.controller.unregister_summon(summon.get_object_id());
+            }
+            self.controller.leave_game(player.char_model.id);
         }
         if let Some(sender) = self.packet_sender.take() {
             let _ = sender.stop_gracefully().await;
@@ -565,6 +613,11 @@ impl Actor for PlayerClient {
     ) -> anyhow::Result<()> {
         info!("Disconnecting Client...");
         self.stop_movement();
+        if self.try_get_selected_char().is_ok_and(Player::is_fishing)
+            && let Err(e) = self.end_fishing(false).await
+        {
+            error!("Unable to end fishing for {}: {e:?}", self.ip);
+        }
         if let Some(s) = self.packet_sender.as_ref() {
             if s.is_alive() {
                 let _ = s.stop_gracefully().await; //ignore errors is it is already dead
@@ -575,6 +628,17 @@ impl Actor for PlayerClient {
         if let Ok(player) = self.try_get_selected_char() {
             self.controller
                 .unregister_player_object(player.get_object_id());
+            if let Some(summon) = player.summon.as_ref() {
+                self.controller.unregister_summon(summon.get_object_id());
+            }
+        }
+        self.save_pet().await;
+        // a game isn't resumed on the next login, the player is saved where they came from
+        if let Ok(player) = self.try_get_selected_char_mut()
+            && let Some(state) = player.leave_olympiad()
+        {
+            let to = state.return_to;
+            player.set_location(to.x, to.y, to.z)?;
         }
         let Some(user) = self.user.as_ref() else {
             return Ok(());
@@ -648,11 +712,16 @@ impl Message<DoLater> for PlayerClient {
     }
 }
 
+/// Stats of the player or of its summon, whichever `target_id` is.
 #[derive(Debug, Clone)]
-pub struct GetStats;
+pub struct GetStats {
+    pub target_id: i32,
+}
 
 #[derive(Debug, Clone)]
 pub struct ApplyDamage {
+    /// The player or its summon
+    pub target_id: i32,
     pub damage: f64,
     pub attacker_id: i32,
     pub attacker_name: String,
@@ -664,22 +733,50 @@ pub struct FullStats {
     pub x: i32,
     pub y: i32,
     pub z: i32,
+    pub level: u8,
+    pub current_hp: f64,
 }
 
 impl Message<GetStats> for PlayerClient {
     type Reply = anyhow::Result<FullStats>;
     async fn handle(
         &mut self,
-        _msg: GetStats,
+        msg: GetStats,
         _ctx: &mut Context<Self, Self::Reply>,
     ) -> Self::Reply {
+        self.get_own_stats(msg.target_id)?
+            .ok_or_else(|| anyhow!("Object {} is not here", msg.target_id))
+    }
+}
+
+impl PlayerClient {
+    /// Stats of the player or of its summon, None when the object is neither of them.
+    pub fn get_own_stats(&self, object_id: i32) -> anyhow::Result<Option<FullStats>> {
         let player = self.try_get_selected_char()?;
-        Ok(FullStats {
+        if player.get_object_id() != object_id {
+            return self.get_summon_stats(object_id);
+        }
+        Ok(Some(FullStats {
             stats: player.stats.cached_stats.clone(),
             x: player.get_x(),
             y: player.get_y(),
             z: player.get_z(),
-        })
+            level: player.char_model.level,
+            current_hp: player.stats.current_hp,
+        }))
+    }
+
+    /// Stats of the target, the own ones are taken directly because asking the own actor
+    /// would wait for itself forever.
+    pub async fn get_target_stats(
+        &self,
+        target_id: i32,
+        target_actor: &ActorRef<Self>,
+    ) -> anyhow::Result<FullStats> {
+        if let Some(stats) = self.get_own_stats(target_id)? {
+            return Ok(stats);
+        }
+        target_actor.ask(GetStats { target_id }).await.anyhow()
     }
 }
 
@@ -690,8 +787,18 @@ impl Message<ApplyDamage> for PlayerClient {
         msg: ApplyDamage,
         _ctx: &mut Context<Self, Self::Reply>,
     ) -> Self::Reply {
-        let (victim_id, victim_name) = {
+        if self.try_get_selected_char()?.is_own_summon(msg.target_id) {
+            return self.damage_summon(msg).await;
+        }
+        if msg.damage > 0.0 && self.interrupt_escape() {
+            self.send_packet(to_client::SystemMessage::text(
+                "Your casting has been interrupted.",
+            )?)
+            .await?;
+        }
+        let (victim_id, victim_name, killed, victim_flagged, victim_in_pvp_zone, in_olympiad) = {
             let character = self.try_get_selected_char_mut()?;
+            let was_alive = character.stats.current_hp > 0.0;
             character.stats.current_hp -= msg.damage;
             if character.stats.current_hp < 0.0 {
                 character.stats.current_hp = 0.0;
@@ -700,8 +807,20 @@ impl Message<ApplyDamage> for PlayerClient {
             (
                 character.get_object_id(),
                 character.get_visible_name().to_string(),
+                was_alive && character.stats.current_hp <= 0.0,
+                character.get_pvp_flag(),
+                character.is_inside_zone(ZoneId::Pvp),
+                character.is_in_olympiad_mode(),
             )
         };
+        if killed && in_olympiad {
+            let char_id = self.try_get_selected_char()?.char_model.id;
+            self.controller.olympiad_manager.on_defeat(char_id);
+        }
+        if killed {
+            self.end_fishing(false).await?;
+            self.on_cursed_weapon_owner_death(msg.attacker_id).await?;
+        }
 
         // Notify victim about the damage
         let damage_val = msg.damage as i32;
@@ -734,6 +853,15 @@ impl Message<ApplyDamage> for PlayerClient {
 
         // Notify attacker about damage dealt
         if let Some(attacker_actor) = self.controller.get_player_by_object_id(msg.attacker_id) {
+            // Kills inside PvP zones (arenas, active sieges) and olympiad games are not counted.
+            if killed && !victim_in_pvp_zone && !in_olympiad {
+                let _ = attacker_actor
+                    .tell(RegisterKill {
+                        victim_id,
+                        victim_flagged,
+                    })
+                    .await;
+            }
             let damage = msg.damage as i32;
             let attacker_id = msg.attacker_id;
             let attacker_name = msg.attacker_name.clone();
@@ -771,9 +899,51 @@ impl Message<ApplyDamage> for PlayerClient {
     }
 }
 
+/// Sent to the killer of a player outside PvP zones: killing a flagged player is a PvP
+/// kill, anything else is a PK.
+#[derive(Debug, Clone)]
+pub struct RegisterKill {
+    pub victim_id: i32,
+    pub victim_flagged: bool,
+}
+
+impl Message<RegisterKill> for PlayerClient {
+    type Reply = anyhow::Result<()>;
+    async fn handle(
+        &mut self,
+        msg: RegisterKill,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> Self::Reply {
+        let player = self.try_get_selected_char_mut()?;
+        if player.is_inside_zone(ZoneId::Pvp) {
+            return Ok(());
+        }
+        if player.cursed_weapon.is_some() {
+            return self.increase_cursed_weapon_kills().await;
+        }
+        if msg.victim_flagged {
+            player.char_model.pvp_kills += 1;
+        } else {
+            //todo: karma gain
+            player.char_model.pk_kills += 1;
+        }
+        info!(
+            "Player {} killed {} (pvp: {})",
+            player.get_object_id(),
+            msg.victim_id,
+            msg.victim_flagged
+        );
+        let char_model = player.char_model.clone();
+        character::Model::update_char(&self.db_pool, &char_model).await?;
+        Ok(())
+    }
+}
+
 /// Heals the player's HP or MP (flat amount or percent of max), sent by a caster actor.
 #[derive(Debug, Clone)]
 pub struct ApplyHeal {
+    /// The player or its summon
+    pub target_id: i32,
     pub amount: f64,
     /// When set, `amount` is a percent of the max HP/MP instead of a flat value.
     pub is_percent: bool,
@@ -789,6 +959,9 @@ impl Message<ApplyHeal> for PlayerClient {
         msg: ApplyHeal,
         _ctx: &mut Context<Self, Self::Reply>,
     ) -> Self::Reply {
+        if self.try_get_selected_char()?.is_own_summon(msg.target_id) {
+            return self.heal_summon(&msg).await;
+        }
         let (object_id, healed, current_hp, current_mp, max_hp, max_mp) = {
             let player = self.try_get_selected_char_mut()?;
             let (current, max) = if msg.is_mp {
@@ -847,6 +1020,8 @@ impl Message<ApplyHeal> for PlayerClient {
 /// Applies a continuous effect (buff/debuff) to the player and schedules its expiry.
 #[derive(Debug, Clone)]
 pub struct ApplyBuff {
+    /// The player or its summon
+    pub target_id: i32,
     pub skill_id: i32,
     pub skill_level: i32,
     pub caster_id: i32,
@@ -863,6 +1038,10 @@ impl Message<ApplyBuff> for PlayerClient {
         msg: ApplyBuff,
         ctx: &mut Context<Self, Self::Reply>,
     ) -> Self::Reply {
+        //todo: buffs on summons
+        if self.try_get_selected_char()?.is_own_summon(msg.target_id) {
+            return Ok(());
+        }
         let duration_secs = msg.abnormal_time_secs.max(1);
         let (added, buffs) = {
             let player = self.try_get_selected_char_mut()?;
@@ -978,6 +1157,7 @@ impl Message<GetMovementPosition> for PlayerClient {
             if let Ok(player) = self.try_get_selected_char_mut() {
                 let _ = player.set_location(x, y, z);
             }
+            self.revalidate_zones().await?;
 
             Ok(Some((x, y, z, has_arrived)))
         } else {
@@ -985,3 +1165,32 @@ impl Message<GetMovementPosition> for PlayerClient {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::test::spawn_player;
+    use sea_orm::EntityTrait;
+    use test_utils::utils::get_test_db;
+
+    #[tokio::test]
+    async fn test_register_kill_saves_counts() {
+        let db_pool = get_test_db().await;
+        let (char_id, actor, _conn) = spawn_player(&db_pool, 20).await;
+        for victim_flagged in [true, false, false] {
+            actor
+                .ask(RegisterKill {
+                    victim_id: 1,
+                    victim_flagged,
+                })
+                .await
+                .unwrap();
+        }
+        let saved = character::Entity::find_by_id(char_id)
+            .one(&db_pool)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!((saved.pvp_kills, saved.pk_kills), (1, 2));
+    }
+}
@@ -0,0 +1,174 @@
+//! Premium accounts.
+//!
+//! Characters of an account with premium get the exp and sp of killed monsters multiplied by
+//! the premium rates of the config. The expiry is stored with the account by the
+//! login server, which hands it over when the player logs in. GMs grant premium days with
+//! `admin_premium <account> <days>`, and the premium runs out while the player is online too.
+
+use crate::packets::to_client::SystemMessage;
+use crate::packets::to_client::extended::PremiumState;
+use crate::pl_client::{ClientStatus, DoLater, PlayerClient};
+use chrono::{DateTime, TimeDelta, Utc};
+use entities::entities::user;
+use kameo::actor::ActorRef;
+use l2_core::shared_packets::gs_2_ls::ChangePremium;
+
+impl PlayerClient {
+    /// Extends the premium of the account by the days, whether it is online or not.
+    pub async fn grant_premium(&mut self, account: &str, days: i64) -> anyhow::Result<()> {
+        if days < 1 {
+            return self
+                .send_packet(SystemMessage::text("Usage: //premium <account> <days>")?)
+                .await;
+        }
+        let Some(current) = user::Model::find_some_by_username(&self.db_pool, account).await?
+        else {
+            return self
+                .send_packet(SystemMessage::text(&format!(
+                    "Account {account} not found."
+                ))?)
+                .await;
+        };
+        let now = self.controller.clock.now();
+        let start = current
+            .get_premium_expire()
+            .filter(|t| *t > now)
+            .unwrap_or(now);
+        let expire = start + TimeDelta::days(days);
+        self.controller
+            .try_get_ls_actor()
+            .await?
+            .tell(ChangePremium::new(account, Some(expire))?)
+            .await?;
+        if let Some(actor) = self.controller.get_online_account(account) {
+            PlayerClient::do_later(
+                actor.clone(),
+                DoLater {
+                    delay: std::time::Duration::ZERO,
+                    callback: Box::new(move |pl: &mut PlayerClient| {
+                        Box::pin(pl.set_premium(Some(expire), actor))
+                    }),
+                },
+            );
+        }
+        self.send_packet(SystemMessage::text(&format!(
+            "The premium of {account} lasts until {}.",
+            expire.format("%Y-%m-%d %H:%M UTC")
+        ))?)
+        .await
+    }
+
+    /// Takes the new premium expiry of the account, the actor is the client itself.
+    pub async fn set_premium(
+        &mut self,
+        premium_expire: Option<DateTime<Utc>>,
+        actor: ActorRef<PlayerClient>,
+    ) -> anyhow::Result<()> {
+        if let Some(mut user) = self.get_user().cloned() {
+            user.premium_expire = premium_expire.map(|t| t.fixed_offset());
+            self.set_user(user);
+        }
+        if self.get_status() != &ClientStatus::InGame {
+            return Ok(());
+        }
+        let player = self.try_get_selected_char_mut()?;
+        player.premium_until = premium_expire;
+        let packet = PremiumState::new(player)?;
+        self.send_packet(packet).await?;
+        self.schedule_premium_expiry(actor);
+        Ok(())
+    }
+
+    /// Checks the premium again once it is due to run out.
+    pub fn schedule_premium_expiry(&self, actor: ActorRef<PlayerClient>) {
+        let Some(expire) = self
+            .try_get_selected_char()
+            .ok()
+            .and_then(|p| p.premium_until)
+        else {
+            return;
+        };
+        let delay = (expire - self.controller.clock.now())
+            .to_std()
+            .unwrap_or_default();
+        PlayerClient::do_later(
+            actor,
+            DoLater {
+                delay,
+                callback: Box::new(|pl: &mut PlayerClient| Box::pin(pl.check_premium_expiry())),
+            },
+        );
+    }
+
+    /// Takes the premium away if it has run out, an extended one is left alone.
+    pub async fn check_premium_expiry(&mut self) -> anyhow::Result<()> {
+        let now = self.controller.clock.now();
+        let player = self.try_get_selected_char_mut()?;
+        if player.premium_until.is_none_or(|t| t > now) {
+            return Ok(());
+        }
+        player.premium_until = None;
+        let packet = PremiumState::new(player)?;
+        if let Some(mut user) = self.get_user().cloned() {
+            user.premium_expire = None;
+            self.set_user(user);
+        }
+        self.send_packet(packet).await?;
+        self.send_packet(SystemMessage::text("Your premium account has expired.")?)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pl_client::{ClientStatus, GetCharInfo};
+    use crate::test_utils::test::{on_actor, spawn_player};
+    use chrono::TimeDelta;
+    use test_utils::utils::get_test_db;
+
+    #[tokio::test]
+    async fn test_premium_rates_and_expiry() {
+        let db_pool = get_test_db().await;
+        let (_, actor, _conn) = spawn_player(&db_pool, 10).await;
+        let pl_actor = actor.clone();
+        on_actor(&actor, move |pl| {
+            Box::pin(async move {
+                pl.set_status(ClientStatus::InGame);
+                let expire = pl.controller.clock.now() + TimeDelta::days(1);
+                pl.set_premium(Some(expire), pl_actor).await
+            })
+        })
+        .await;
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert!(player.has_premium());
+        let (exp, sp) = (player.char_model.exp, player.char_model.sp);
+
+        // the rates of the config double everything
+        on_actor(&actor, |pl| {
+            Box::pin(pl.reward_kill_exp_and_sp(10, 100, 10))
+        })
+        .await;
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert_eq!(player.char_model.exp, exp + 200);
+        assert_eq!(player.char_model.sp, sp + 20);
+
+        // not yet
+        on_actor(&actor, |pl| Box::pin(pl.check_premium_expiry())).await;
+        assert!(actor.ask(GetCharInfo).await.unwrap().has_premium());
+        on_actor(&actor, |pl| {
+            Box::pin(async move {
+                pl.controller.clock.advance(TimeDelta::days(2));
+                pl.check_premium_expiry().await
+            })
+        })
+        .await;
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert!(!player.has_premium());
+        on_actor(&actor, |pl| {
+            Box::pin(pl.reward_kill_exp_and_sp(10, 100, 10))
+        })
+        .await;
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert_eq!(player.char_model.exp, exp + 300);
+    }
+}
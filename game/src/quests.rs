@@ -0,0 +1,391 @@
+//! Quest engine.
+//!
+//! Quests are state machines defined in `config/data/quests`. A started quest waits for the
+//! trigger of the step matching its condition. Game events (talking to an npc, kills, pickups,
+//! entering a zone, level ups) are fed to [`PlayerClient::notify_quest_event`], which moves
+//! every concerned quest forward, hands out step items and the final reward.
+
+use crate::packets::to_client::extended::{QuestItemList, QuestNpcLogList};
+use crate::packets::to_client::{QuestList, UserInfo};
+use crate::pl_client::PlayerClient;
+use entities::entities::{character, quest};
+use l2_core::data::quests::{QuestEvent, QuestTemplate, QuestTrigger};
+use l2_core::game_objects::player::inventory::Inventory;
+use l2_core::game_objects::player::quest::Quest;
+use l2_core::game_objects::player::user_info::UserInfoType;
+use rand::RngExt;
+use tracing::warn;
+
+impl PlayerClient {
+    pub async fn send_quest_list(&mut self) -> anyhow::Result<()> {
+        let packet = QuestList::new(self.try_get_selected_char()?)?;
+        self.send_packet(packet).await
+    }
+
+    async fn send_quest_item_list(&mut self) -> anyhow::Result<()> {
+        let packet = QuestItemList::new(self.try_get_selected_char()?, &self.controller.quests)?;
+        self.send_packet(packet).await
+    }
+
+    /// Accepts the quest, returns `false` when the player can't take it.
+    pub async fn start_quest(&mut self, quest_id: i32) -> anyhow::Result<bool> {
+        let Some(template) = self.controller.quests.get_quest(quest_id).cloned() else {
+            warn!("Unknown quest {quest_id}");
+            return Ok(false);
+        };
+        let player = self.try_get_selected_char()?;
+        let allowed = match player.get_quest(quest_id) {
+            Some(q) if q.is_started() => false,
+            Some(q) if q.is_completed() => template.repeatable,
+            _ => true,
+        };
+        if !allowed
+            || !template.is_level_allowed(player.char_model.level)
+            || player.get_active_quest_count() >= usize::from(player.get_quest_limit_count())
+        {
+            return Ok(false);
+        }
+        let quest = Quest::start(player.char_model.id, quest_id, &template.name);
+        quest::Model::save(&self.db_pool, quest.model.clone()).await?;
+        let player = self.try_get_selected_char_mut()?;
+        player.quests.retain(|q| q.get_id() != quest_id);
+        player.quests.push(quest);
+        for item in &template.start_items {
+            self.add_item_by_item_id(item.id, item.count).await?;
+        }
+        self.send_quest_list().await?;
+        self.send_quest_item_list().await?;
+        Ok(true)
+    }
+
+    /// Drops a started quest together with its quest items.
+    pub async fn abort_quest(&mut self, quest_id: i32) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let Some(quest) = player.get_quest(quest_id).filter(|q| q.is_started()) else {
+            return Ok(());
+        };
+        let char_id = player.char_model.id;
+        quest::Model::delete(&self.db_pool, char_id, &quest.model.name).await?;
+        self.try_get_selected_char_mut()?
+            .quests
+            .retain(|q| q.get_id() != quest_id);
+        if let Some(template) = self.controller.quests.get_quest(quest_id).cloned() {
+            self.destroy_quest_items(&template).await?;
+        }
+        self.send_quest_list().await
+    }
+
+    /// Moves forward every started quest waiting for the event.
+    /// Returns ids of the quests which reacted to it.
+    pub async fn notify_quest_event(&mut self, event: QuestEvent) -> anyhow::Result<Vec<i32>> {
+        let started: Vec<(i32, u32)> = self
+            .try_get_selected_char()?
+            .quests
+            .iter()
+            .filter(|q| q.is_started())
+            .map(|q| (q.get_id(), q.get_condition()))
+            .collect();
+        let mut reacted = Vec::new();
+        for (quest_id, condition) in started {
+            let Some(template) = self.controller.quests.get_quest(quest_id).cloned() else {
+                continue;
+            };
+            let Some(step) = template.get_step(condition) else {
+                warn!("Quest {quest_id} has no step for condition {condition}");
+                continue;
+            };
+            if !step.on.matches(event) {
+                continue;
+            }
+            let done = match &step.on {
+                QuestTrigger::Talk { .. }
+                | QuestTrigger::EnterZone { .. }
+                | QuestTrigger::ReachLevel { .. } => true,
+                QuestTrigger::Kill { count, .. } => {
+                    let QuestEvent::Kill { npc_id } = event else {
+                        continue;
+                    };
+                    self.count_quest_kill(quest_id, npc_id, *count).await?
+                }
+                QuestTrigger::Collect {
+                    item_id,
+                    count,
+                    chance,
+                    ..
+                } => self.collect_quest_item(*item_id, *count, *chance).await?,
+                QuestTrigger::PickUp { item_id, count } => self.count_item(*item_id) >= *count,
+            };
+            if done {
+                self.finish_quest_step(&template, condition).await?;
+            }
+            reacted.push(quest_id);
+        }
+        Ok(reacted)
+    }
+
+    /// Checks zone steps of the started quests, called when the position changes.
+    pub async fn check_quest_zones(&mut self) -> anyhow::Result<()> {
+        let controller = self.controller.clone();
+        let player = self.try_get_selected_char()?;
+        let waited: Vec<i32> = player
+            .quests
+            .iter()
+            .filter(|q| q.is_started())
+            .filter_map(|q| {
+                let step = controller
+                    .quests
+                    .get_quest(q.get_id())?
+                    .get_step(q.get_condition())?;
+                match step.on {
+                    QuestTrigger::EnterZone { zone_id } => Some(zone_id),
+                    _ => None,
+                }
+            })
+            .collect();
+        if waited.is_empty() {
+            return Ok(());
+        }
+        let (x, y, z) = (player.get_x(), player.get_y(), player.get_z());
+        let entered: Vec<i32> = controller
+            .zone_manager
+            .zones_at(x, y, z)
+            .map(|zone| zone.id)
+            .filter(|id| waited.contains(id))
+            .collect();
+        for zone_id in entered {
+            self.notify_quest_event(QuestEvent::EnterZone { zone_id })
+                .await?;
+        }
+        Ok(())
+    }
+
+    fn count_item(&self, item_id: i32) -> i64 {
+        self.try_get_selected_char()
+            .ok()
+            .and_then(|p| p.inventory.get_item_by_item_id(item_id))
+            .map_or(0, |i| i.item_model.count)
+    }
+
+    /// Returns `true` when enough monsters were killed
+    async fn count_quest_kill(
+        &mut self,
+        quest_id: i32,
+        npc_id: i32,
+        needed: u32,
+    ) -> anyhow::Result<bool> {
+        let player = self.try_get_selected_char_mut()?;
+        let Some(quest) = player.get_quest_mut(quest_id) else {
+            return Ok(false);
+        };
+        let kills = (quest.get_kills() + 1).min(needed);
+        quest.set_kills(kills);
+        let model = quest.model.clone();
+        quest::Model::save(&self.db_pool, model).await?;
+        self.send_packet(QuestNpcLogList::new(quest_id, &[(npc_id, kills)])?)
+            .await?;
+        Ok(kills >= needed)
+    }
+
+    /// Rolls the drop of a quest item, returns `true` when enough items were collected
+    async fn collect_quest_item(
+        &mut self,
+        item_id: i32,
+        needed: i64,
+        chance: u8,
+    ) -> anyhow::Result<bool> {
+        let have = self.count_item(item_id);
+        if have >= needed {
+            return Ok(true);
+        }
+        if rand::rng().random_range(0..100) >= chance {
+            return Ok(false);
+        }
+        self.add_item_by_item_id(item_id, 1).await?;
+        self.send_quest_item_list().await?;
+        Ok(have + 1 >= needed)
+    }
+
+    async fn finish_quest_step(
+        &mut self,
+        template: &QuestTemplate,
+        condition: u32,
+    ) -> anyhow::Result<()> {
+        if let Some(step) = template.get_step(condition) {
+            for item in &step.take_items {
+                if !self.destroy_item_by_item_id(item.id, item.count).await? {
+                    warn!("Quest {} item {} is missing", template.id, item.id);
+                }
+            }
+            for item in &step.give_items {
+                self.add_item_by_item_id(item.id, item.count).await?;
+            }
+        }
+        if template.is_last_step(condition) {
+            return self.complete_quest(template).await;
+        }
+        let player = self.try_get_selected_char_mut()?;
+        let Some(quest) = player.get_quest_mut(template.id) else {
+            return Ok(());
+        };
+        quest.set_condition(condition + 1);
+        quest.set_kills(0);
+        let model = quest.model.clone();
+        quest::Model::save(&self.db_pool, model).await?;
+        self.send_quest_list().await?;
+        self.send_quest_item_list().await
+    }
+
+    async fn complete_quest(&mut self, template: &QuestTemplate) -> anyhow::Result<()> {
+        self.destroy_quest_items(template).await?;
+        let reward = &template.reward;
+        self.add_item_by_item_id(Inventory::ADENA_ID, reward.adena)
+            .await?;
+        for item in &reward.items {
+            self.add_item_by_item_id(item.id, item.count).await?;
+        }
+        let player = self.try_get_selected_char_mut()?;
+        if template.repeatable {
+            player.quests.retain(|q| q.get_id() != template.id);
+            let char_id = player.char_model.id;
+            quest::Model::delete(&self.db_pool, char_id, &template.name).await?;
+        } else if let Some(quest) = player.get_quest_mut(template.id) {
+            quest.set_state(Quest::COMPLETED);
+            let model = quest.model.clone();
+            quest::Model::save(&self.db_pool, model).await?;
+        }
+        self.send_quest_list().await?;
+        if reward.exp != 0 || reward.sp != 0 {
+            self.reward_exp_and_sp(reward.exp, reward.sp).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn reward_exp_and_sp(&mut self, exp: i64, sp: i64) -> anyhow::Result<()> {
+        let exp = self.share_exp_with_pet(exp).await?;
+        let controller = self.controller.clone();
+        let player = self.try_get_selected_char_mut()?;
+        let new_level = player.add_exp_and_sp(exp, sp, &controller.exp_table);
+        let char_model = player.char_model.clone();
+        character::Model::update_char(&self.db_pool, &char_model).await?;
+        let player = self.try_get_selected_char()?;
+        let user_info = UserInfo::new(player, UserInfoType::all(), &controller).await?;
+        self.send_packet(user_info).await?;
+        if let Some(level) = new_level {
+            Box::pin(self.notify_quest_event(QuestEvent::LevelUp { level })).await?;
+        }
+        Ok(())
+    }
+
+    /// Destroys all quest items of the quest the player has
+    async fn destroy_quest_items(&mut self, template: &QuestTemplate) -> anyhow::Result<()> {
+        for &item_id in &template.items {
+            let count = self.count_item(item_id);
+            if count > 0 {
+                self.destroy_item_by_item_id(item_id, count).await?;
+            }
+        }
+        self.send_quest_item_list().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::packets::from_client::request_quest_abort::RequestQuestAbort;
+    use crate::pl_client::GetCharInfo;
+    use crate::test_utils::test::{on_actor, spawn_player};
+    use entities::entities::quest;
+    use l2_core::data::quests::QuestEvent;
+    use l2_core::game_objects::player::Player;
+    use l2_core::game_objects::player::inventory::Inventory;
+    use test_utils::utils::get_test_db;
+
+    fn count(player: &Player, item_id: i32) -> i64 {
+        player
+            .inventory
+            .get_item_by_item_id(item_id)
+            .map_or(0, |i| i.item_model.count)
+    }
+
+    #[tokio::test]
+    async fn test_talk_quest_is_completed_with_reward() {
+        let db_pool = get_test_db().await;
+        let (char_id, actor, _conn) = spawn_player(&db_pool, 2).await;
+
+        assert!(on_actor(&actor, |pl| Box::pin(pl.start_quest(1))).await);
+        // can't take it twice
+        assert!(!on_actor(&actor, |pl| Box::pin(pl.start_quest(1))).await);
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert_eq!(count(&player, 687), 1);
+        assert_eq!(player.get_quest(1).unwrap().get_condition(), 1);
+
+        // the wrong npc does nothing
+        let reacted = on_actor(&actor, |pl| {
+            Box::pin(pl.notify_quest_event(QuestEvent::Talk { npc_id: 30033 }))
+        })
+        .await;
+        assert!(reacted.is_empty());
+        for npc_id in [30006, 30048] {
+            let reacted = on_actor(&actor, move |pl| {
+                Box::pin(pl.notify_quest_event(QuestEvent::Talk { npc_id }))
+            })
+            .await;
+            assert_eq!(reacted, vec![1]);
+        }
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert_eq!(player.get_quest(1).unwrap().get_condition(), 3);
+        assert_eq!(count(&player, 687), 0);
+        assert_eq!(count(&player, 688), 0);
+        assert_eq!(count(&player, 1079), 1);
+        let exp = player.char_model.exp;
+
+        on_actor(&actor, |pl| {
+            Box::pin(pl.notify_quest_event(QuestEvent::Talk { npc_id: 30033 }))
+        })
+        .await;
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        let quest = player.get_quest(1).unwrap();
+        assert!(quest.is_completed());
+        assert_eq!(quest.get_condition_bit_set(), 0);
+        assert_eq!(count(&player, 1079), 0);
+        assert_eq!(count(&player, Inventory::ADENA_ID), 2466);
+        assert_eq!(count(&player, 1835), 100);
+        assert_eq!(player.char_model.exp, exp + 5672);
+        assert_eq!(player.char_model.sp, 446);
+        let saved = quest::Model::char_quests(&db_pool, char_id).await.unwrap();
+        assert_eq!(saved[0].variables["state"], "completed");
+        // not repeatable
+        assert!(!on_actor(&actor, |pl| Box::pin(pl.start_quest(1))).await);
+    }
+
+    #[tokio::test]
+    async fn test_collect_quest_and_abort() {
+        let db_pool = get_test_db().await;
+        let (char_id, actor, _conn) = spawn_player(&db_pool, 15).await;
+        // too low level for the first quest
+        assert!(!on_actor(&actor, |pl| Box::pin(pl.start_quest(1))).await);
+        assert!(on_actor(&actor, |pl| Box::pin(pl.start_quest(261))).await);
+        for _ in 0..8 {
+            on_actor(&actor, |pl| {
+                Box::pin(pl.notify_quest_event(QuestEvent::Kill { npc_id: 20308 }))
+            })
+            .await;
+        }
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert_eq!(count(&player, 1087), 8);
+        assert_eq!(player.get_quest(261).unwrap().get_condition(), 2);
+
+        actor
+            .ask(RequestQuestAbort { quest_id: 261 })
+            .await
+            .unwrap();
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert!(player.get_quest(261).is_none());
+        assert_eq!(count(&player, 1087), 0);
+        assert!(
+            quest::Model::char_quests(&db_pool, char_id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,217 @@
+//! Recommendations.
+//!
+//! A player of level 10 or above gets 20 recommendations a day to give to other players,
+//! each of them only once a day. Recommendations had give an exp bonus for the kills of the
+//! player, which lasts an hour of play a day. The daily refresh refills the recommendations
+//! and the bonus time and takes some of the recommendations had away, it is checked when the
+//! player enters the world and by the
+//! [`RecommendationManager`](crate::managers::RecommendationManager), which also runs the bonus
+//! timer of the players online.
+
+use crate::packets::to_client::extended::VoteSystem;
+use crate::packets::to_client::{
+    CharInfo, SystemMessage, SystemMessageParam, SystemMessageType, UserInfo,
+};
+use crate::pl_client::{DoLater, GetCharInfo, PlayerClient};
+use entities::entities::character;
+use l2_core::bitmask::BitMask;
+use l2_core::errors::KameoAnyhowExt;
+use l2_core::game_objects::player::user_info::UserInfoType;
+use l2_core::game_objects::player::{MAX_RECOMMENDATIONS_HAVE, MIN_RECOMMEND_LEVEL};
+use std::time::Duration;
+
+impl PlayerClient {
+    /// Recommends the selected target, the client sends its object id.
+    pub async fn recommend(&mut self, target_object_id: i32) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        if player.get_object_id() == target_object_id {
+            return self
+                .send_packet(SystemMessage::new(
+                    SystemMessageType::YouCannotRecommendYourself,
+                )?)
+                .await;
+        }
+        let Some(target_actor) = self
+            .selected_target
+            .as_ref()
+            .filter(|(object_id, _)| *object_id == target_object_id)
+            .map(|(_, actor)| actor.clone())
+        else {
+            return self
+                .send_packet(SystemMessage::new(SystemMessageType::InvalidTarget)?)
+                .await;
+        };
+        let refusal = if player.char_model.level < MIN_RECOMMEND_LEVEL {
+            Some(
+                SystemMessageType::OnlyCharactersOfLevel10OrAboveAreAuthorizedToMakeRecommendations,
+            )
+        } else if player.get_recommendations_left() == 0 {
+            Some(SystemMessageType::YouAreNotAuthorizedToMakeFurtherRecommendationsAtThisTime)
+        } else {
+            None
+        };
+        if let Some(message) = refusal {
+            return self.send_packet(SystemMessage::new(message)?).await;
+        }
+        let target = target_actor.ask(GetCharInfo).await.anyhow()?;
+        if player.has_recommended(target.char_model.id) {
+            return self
+                .send_packet(SystemMessage::new(
+                    SystemMessageType::ThatCharacterHasAlreadyBeenRecommended,
+                )?)
+                .await;
+        }
+        if target.get_recommendations_have() >= MAX_RECOMMENDATIONS_HAVE {
+            return self
+                .send_packet(SystemMessage::text(
+                    "Your selected target can no longer receive a recommendation.",
+                )?)
+                .await;
+        }
+        let player = self.try_get_selected_char_mut()?;
+        player.give_recommendation(target.char_model.id);
+        let left = player.get_recommendations_left();
+        let name = player.char_model.name.clone();
+        let char_model = player.char_model.clone();
+        character::Model::update_char(&self.db_pool, &char_model).await?;
+        PlayerClient::do_later(
+            target_actor,
+            DoLater {
+                delay: Duration::ZERO,
+                callback: Box::new(move |pl: &mut PlayerClient| {
+                    Box::pin(pl.receive_recommendation(name))
+                }),
+            },
+        );
+        let mut message = SystemMessage::new(
+            SystemMessageType::YouHaveRecommendedC1YouHaveS2RecommendationsLeft,
+        )?;
+        message.add_param(SystemMessageParam::PcName(target.char_model.name.clone()))?;
+        message.add_param(SystemMessageParam::Int(i32::from(left)))?;
+        self.send_packet(message).await?;
+        self.send_recommendations(false).await
+    }
+
+    /// Counts the recommendation of the player with the name.
+    pub async fn receive_recommendation(&mut self, from: String) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char_mut()?;
+        if !player.receive_recommendation() {
+            return Ok(());
+        }
+        let char_model = player.char_model.clone();
+        character::Model::update_char(&self.db_pool, &char_model).await?;
+        let mut message = SystemMessage::new(SystemMessageType::YouHaveBeenRecommendedByC1)?;
+        message.add_param(SystemMessageParam::PcName(from))?;
+        self.send_packet(message).await?;
+        self.send_recommendations(true).await
+    }
+
+    /// Applies the daily refresh if due and runs the bonus timer for the seconds played.
+    pub async fn update_recommendations(&mut self, seconds: u32) -> anyhow::Result<()> {
+        let now = self.controller.clock.now();
+        let player = self.try_get_selected_char_mut()?;
+        if player.refresh_recommendations(now) {
+            let char_model = player.char_model.clone();
+            character::Model::update_char(&self.db_pool, &char_model).await?;
+            return self.send_recommendations(true).await;
+        }
+        if player.consume_recommendation_bonus_time(seconds) {
+            let packet = VoteSystem::new(player)?;
+            return self.send_packet(packet).await;
+        }
+        Ok(())
+    }
+
+    /// Sends the counts to the player, the recommendations had are shown to everyone else
+    /// only if they changed.
+    async fn send_recommendations(&mut self, have_changed: bool) -> anyhow::Result<()> {
+        let controller = self.controller.clone();
+        let user_name = self.try_get_user()?.username.clone();
+        let player = self.try_get_selected_char()?;
+        let mut flags = BitMask::new(24);
+        flags.add_mask(UserInfoType::Social);
+        let user_info = UserInfo::new(player, flags, &controller).await?;
+        let vote_system = VoteSystem::new(player)?;
+        if have_changed {
+            controller.broadcast_packet_with_filter(
+                CharInfo::new(player, &controller.get_cfg())?,
+                Some(Box::new(move |acc, _| !acc.eq(&user_name))),
+            );
+        }
+        self.send_packet(user_info).await?;
+        self.send_packet(vote_system).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pl_client::{GetCharInfo, PlayerClient};
+    use crate::test_utils::test::{on_actor, settle, spawn_player};
+    use chrono::TimeDelta;
+    use l2_core::game_objects::player::{DAILY_RECOMMENDATIONS, RECOMMENDATION_BONUS_TIME};
+    use test_utils::utils::get_test_db;
+
+    #[tokio::test]
+    async fn test_recommend() {
+        let db_pool = get_test_db().await;
+        let (alice_id, alice, _alice_conn) = spawn_player(&db_pool, 20).await;
+        let (bob_id, bob, _bob_conn) = spawn_player(&db_pool, 20).await;
+        for actor in [&alice, &bob] {
+            on_actor(actor, |pl| Box::pin(pl.update_recommendations(0))).await;
+        }
+        let bob_object_id = bob.ask(GetCharInfo).await.unwrap().get_object_id();
+
+        // without the target selected nothing happens
+        on_actor(&alice, move |pl| Box::pin(pl.recommend(bob_object_id))).await;
+        let player = alice.ask(GetCharInfo).await.unwrap();
+        assert_eq!(player.get_recommendations_left(), DAILY_RECOMMENDATIONS);
+
+        let target = bob.clone();
+        on_actor(&alice, move |pl: &mut PlayerClient| {
+            Box::pin(async move {
+                pl.selected_target = Some((bob_object_id, target));
+                pl.recommend(bob_object_id).await?;
+                // only once a day
+                pl.recommend(bob_object_id).await
+            })
+        })
+        .await;
+        let player = alice.ask(GetCharInfo).await.unwrap();
+        assert_eq!(player.get_recommendations_left(), DAILY_RECOMMENDATIONS - 1);
+        assert!(player.has_recommended(bob_id));
+        assert!(!player.has_recommended(alice_id));
+        // the target counts it in its own actor
+        settle(&bob).await;
+        let player = bob.ask(GetCharInfo).await.unwrap();
+        assert_eq!(player.get_recommendations_have(), 1);
+        assert_eq!(player.get_recommendation_bonus(), 12);
+
+        // the bonus adds to the exp of kills until the time is up
+        let exp = player.char_model.exp;
+        on_actor(&bob, |pl| Box::pin(pl.reward_kill_exp_and_sp(20, 100, 10))).await;
+        let player = bob.ask(GetCharInfo).await.unwrap();
+        assert_eq!(player.char_model.exp, exp + 112);
+        on_actor(&bob, |pl| {
+            Box::pin(pl.update_recommendations(RECOMMENDATION_BONUS_TIME))
+        })
+        .await;
+        on_actor(&bob, |pl| Box::pin(pl.reward_kill_exp_and_sp(20, 100, 10))).await;
+        let player = bob.ask(GetCharInfo).await.unwrap();
+        assert_eq!(player.char_model.exp, exp + 212);
+
+        // the next day brings the time back
+        on_actor(&bob, |pl| {
+            Box::pin(async move {
+                pl.controller.clock.advance(TimeDelta::days(1));
+                pl.update_recommendations(0).await
+            })
+        })
+        .await;
+        let player = bob.ask(GetCharInfo).await.unwrap();
+        assert_eq!(
+            player.get_recommendation_bonus_time(),
+            RECOMMENDATION_BONUS_TIME
+        );
+        assert_eq!(player.get_recommendations_have(), 0);
+    }
+}
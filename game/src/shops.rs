@@ -0,0 +1,439 @@
+//! Npc shops.
+//!
+//! Merchants sell the goods of their buy lists and buy the items of the players for half of
+//! the reference price. Multisell lists exchange items for other items. Both are opened from
+//! npc dialogs, a trade is accepted only for the list the player has open. Shop prices and
+//! taxed multisell adena are raised by the tax of the town the player trades in, which the
+//! lord of the castle owning the town sets.
+
+use crate::bypass::DialogNpc;
+use crate::packets::to_client;
+use crate::packets::to_client::MultiSellList;
+use crate::packets::to_client::extended::{BuyList, SellList};
+use crate::pl_client::PlayerClient;
+use entities::dao::item::LocType;
+use l2_core::data::buy_lists::{price_with_tax, sell_price};
+use l2_core::data::items::ItemKind;
+use l2_core::game_objects::player::inventory::Inventory;
+use std::collections::HashMap;
+use tracing::warn;
+
+/// Items of one kind a player can trade at once
+const MAX_COUNT: i64 = i32::MAX as i64;
+
+/// The shop window the player opened from an npc dialog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpenShop {
+    BuyList(i32),
+    Multisell(i32),
+}
+
+impl PlayerClient {
+    /// Shop tax of the town the player stands in, set by the lord of its castle if it has one.
+    fn shop_tax_rate(&self) -> anyhow::Result<u8> {
+        let player = self.try_get_selected_char()?;
+        let (x, y, z) = (player.get_x(), player.get_y(), player.get_z());
+        let zones = &self.controller.zone_manager.data;
+        let castle_tax = zones
+            .castle_at(x, y, z)
+            .and_then(|id| self.controller.siege_manager.get(id))
+            .filter(|castle| castle.owner.is_some())
+            .map(|castle| castle.tax_rate);
+        match castle_tax {
+            Some(rate) => Ok(u8::try_from(rate)?),
+            None => Ok(zones.tax_rate_at(x, y, z)),
+        }
+    }
+
+    fn is_shop_open(&self, shop: OpenShop) -> bool {
+        if self.open_shop == Some(shop) {
+            return true;
+        }
+        warn!("Shop {shop:?} is not open, possible cheating");
+        false
+    }
+
+    async fn send_not_enough_items(&mut self) -> anyhow::Result<()> {
+        self.send_packet(to_client::SystemMessage::new(
+            to_client::SystemMessageType::YouDoNotHaveEnoughRequiredItems,
+        )?)
+        .await
+    }
+
+    /// Opens the merchant window with the goods of the list and the items the merchant buys.
+    pub async fn show_buy_list(&mut self, npc: &DialogNpc, list_id: i32) -> anyhow::Result<()> {
+        let controller = self.controller.clone();
+        let Some(list) = controller
+            .buy_lists
+            .get_list(list_id)
+            .filter(|l| l.is_offered_by(npc.npc_id))
+        else {
+            warn!("Npc {} has no buy list {list_id}", npc.npc_id);
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        };
+        let tax_rate = self.shop_tax_rate()?;
+        let player = self.try_get_selected_char()?;
+        let inventory = &player.inventory;
+        let packet = BuyList::new(
+            list,
+            &controller.items_data,
+            inventory.get_adena(),
+            inventory.items.len(),
+            tax_rate,
+        )?;
+        self.open_shop = Some(OpenShop::BuyList(list_id));
+        self.send_packet(packet).await?;
+        self.send_sell_list().await
+    }
+
+    async fn send_sell_list(&mut self) -> anyhow::Result<()> {
+        let controller = self.controller.clone();
+        let player = self.try_get_selected_char()?;
+        let inventory = &player.inventory;
+        let sellable: Vec<_> = inventory
+            .items
+            .values()
+            .filter(|i| {
+                i.item_model.loc == LocType::Inventory
+                    && i.item_model.item_id != Inventory::ADENA_ID
+                    && !controller.quests.is_quest_item(i.item_model.item_id)
+                    && !controller.is_bound_item(i.item_model.item_id)
+                    && !player.is_summoned_collar(i)
+            })
+            .filter_map(|i| {
+                let template = controller.items_data.get_template(i.item_model.item_id)?;
+                Some((i, sell_price(template.price)))
+            })
+            .filter(|(_, price)| *price > 0)
+            .collect();
+        let packet = SellList::new(&sellable, inventory.items.len())?;
+        self.send_packet(packet).await
+    }
+
+    /// Buys `(item id, count)` goods of the open buy list.
+    pub async fn buy_items(&mut self, list_id: i32, items: &[(i32, i64)]) -> anyhow::Result<()> {
+        if !self.is_shop_open(OpenShop::BuyList(list_id)) {
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        }
+        let controller = self.controller.clone();
+        let Some(list) = controller.buy_lists.get_list(list_id) else {
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        };
+        let tax_rate = self.shop_tax_rate()?;
+        let inventory = &self.try_get_selected_char()?.inventory;
+        let mut total: u64 = 0;
+        let mut new_slots = 0;
+        for &(item_id, count) in items {
+            let price = list
+                .get_item(item_id)
+                .and_then(|i| i.get_price(&controller.items_data));
+            let template = controller.items_data.get_template(item_id);
+            let (Some(price), Some(template)) = (price, template) else {
+                warn!("Item {item_id} is not sold in buy list {list_id}, possible cheating");
+                return self.send_packet(to_client::ActionFailed::normal()?).await;
+            };
+            let cost = u64::try_from(count)
+                .ok()
+                .filter(|_| (1..=MAX_COUNT).contains(&count))
+                .and_then(|c| price_with_tax(price, tax_rate).checked_mul(c))
+                .and_then(|cost| total.checked_add(cost));
+            let Some(cost) = cost else {
+                warn!("Invalid count {count} of item {item_id} to buy");
+                return self.send_packet(to_client::ActionFailed::normal()?).await;
+            };
+            total = cost;
+            if !template.stackable {
+                new_slots += usize::try_from(count)?;
+            } else if inventory.get_item_by_item_id(item_id).is_none() {
+                new_slots += 1;
+            }
+        }
+        if inventory.items.len() + new_slots > usize::from(inventory.get_limit()) {
+            return self
+                .send_packet(to_client::SystemMessage::new(
+                    to_client::SystemMessageType::YourInventoryIsFull,
+                )?)
+                .await;
+        }
+        if total > inventory.get_adena() {
+            return self
+                .send_packet(to_client::SystemMessage::new(
+                    to_client::SystemMessageType::YouDoNotHaveEnoughAdena,
+                )?)
+                .await;
+        }
+        let mut take = Vec::new();
+        if let Some(adena) = inventory.get_item_by_item_id(Inventory::ADENA_ID)
+            && total > 0
+        {
+            take.push((adena.object_id.value(), i64::try_from(total)?));
+        }
+        let give: Vec<_> = items.iter().map(|&(id, count)| (id, count, 0)).collect();
+        self.exchange_items(&take, &give).await?;
+        self.send_sell_list().await
+    }
+
+    /// Sells `(object id, item id, count)` inventory items to the merchant of the open list.
+    pub async fn sell_items(
+        &mut self,
+        list_id: i32,
+        items: &[(i32, i32, i64)],
+    ) -> anyhow::Result<()> {
+        if !self.is_shop_open(OpenShop::BuyList(list_id)) {
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        }
+        let controller = self.controller.clone();
+        let player = self.try_get_selected_char()?;
+        let inventory = &player.inventory;
+        let mut total: u64 = 0;
+        let mut take = Vec::with_capacity(items.len());
+        for &(object_id, item_id, count) in items {
+            let price = inventory
+                .items
+                .get(&object_id)
+                .filter(|i| {
+                    i.item_model.item_id == item_id
+                        && i.item_model.loc == LocType::Inventory
+                        && item_id != Inventory::ADENA_ID
+                        && !controller.quests.is_quest_item(item_id)
+                        && !controller.is_bound_item(item_id)
+                        && !player.is_summoned_collar(i)
+                        && (1..=i.item_model.count).contains(&count)
+                })
+                .and_then(|_| controller.items_data.get_template(item_id))
+                .map(|t| sell_price(t.price))
+                .filter(|price| *price > 0);
+            let income = price
+                .zip(u64::try_from(count).ok())
+                .and_then(|(price, c)| price.checked_mul(c))
+                .and_then(|income| total.checked_add(income));
+            let Some(income) = income else {
+                warn!("Item {object_id} ({item_id}) can't be sold, possible cheating");
+                return self.send_packet(to_client::ActionFailed::normal()?).await;
+            };
+            total = income;
+            take.push((object_id, count));
+        }
+        let give = [(Inventory::ADENA_ID, i64::try_from(total)?, 0)];
+        self.exchange_items(&take, &give).await?;
+        self.send_sell_list().await
+    }
+
+    /// Opens the multisell list, long lists are sent page by page.
+    pub async fn show_multisell(&mut self, npc: &DialogNpc, list_id: i32) -> anyhow::Result<()> {
+        let controller = self.controller.clone();
+        let Some(list) = controller
+            .multisell
+            .get_list(list_id)
+            .filter(|l| l.is_offered_by(npc.npc_id))
+        else {
+            warn!("Npc {} has no multisell {list_id}", npc.npc_id);
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        };
+        let tax_rate = self.shop_tax_rate()?;
+        self.open_shop = Some(OpenShop::Multisell(list_id));
+        let pages = list.entries.len().div_ceil(MultiSellList::PAGE_SIZE).max(1);
+        for page in 0..pages {
+            let packet = MultiSellList::new(list, page, &controller.items_data, tax_rate)?;
+            self.send_packet(packet).await?;
+        }
+        Ok(())
+    }
+
+    /// Exchanges the ingredients of the entry for its products `amount` times.
+    pub async fn multisell_choose(
+        &mut self,
+        list_id: i32,
+        entry_id: i32,
+        amount: i64,
+    ) -> anyhow::Result<()> {
+        if !self.is_shop_open(OpenShop::Multisell(list_id)) {
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        }
+        let controller = self.controller.clone();
+        let items_data = &controller.items_data;
+        let Some(list) = controller.multisell.get_list(list_id) else {
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        };
+        let Some(entry) = list.get_entry(entry_id) else {
+            warn!("Multisell {list_id} has no entry {entry_id}, possible cheating");
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        };
+        if !(1..=MAX_COUNT).contains(&amount) || (amount > 1 && !entry.is_stackable(items_data)) {
+            warn!("Invalid amount {amount} of multisell {list_id} entry {entry_id}");
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        }
+        let tax_rate = self.shop_tax_rate()?;
+        let inventory = &self.try_get_selected_char()?.inventory;
+        // object id -> count to take
+        let mut take: HashMap<i32, i64> = HashMap::new();
+        let mut enchant_level = 0;
+        for ingredient in &entry.ingredients {
+            let Some(need) = list
+                .ingredient_count(ingredient, tax_rate)
+                .checked_mul(amount)
+            else {
+                return self.send_packet(to_client::ActionFailed::normal()?).await;
+            };
+            let template = items_data.try_get_template(ingredient.id)?;
+            if template.stackable {
+                let stack = inventory.get_item_by_item_id(ingredient.id).filter(|s| {
+                    let taken = take.get(&s.object_id.value()).copied().unwrap_or(0);
+                    s.item_model.count - taken >= need
+                });
+                let Some(stack) = stack else {
+                    return self.send_not_enough_items().await;
+                };
+                *take.entry(stack.object_id.value()).or_insert(0) += need;
+                continue;
+            }
+            // the most enchanted items are given away only when the enchantment is kept
+            let mut candidates: Vec<_> = inventory
+                .get_items_by_item_id(ingredient.id)
+                .filter(|i| !take.contains_key(&i.object_id.value()))
+                .collect();
+            candidates.sort_by_key(|i| i.item_model.enchant_level);
+            if list.maintain_enchantment {
+                candidates.reverse();
+            }
+            if i64::try_from(candidates.len())? < need {
+                return self.send_not_enough_items().await;
+            }
+            for item in candidates.into_iter().take(usize::try_from(need)?) {
+                take.insert(item.object_id.value(), 1);
+                if template.kind != ItemKind::EtcItem {
+                    enchant_level = enchant_level.max(item.item_model.enchant_level);
+                }
+            }
+        }
+        let mut give = Vec::with_capacity(entry.products.len());
+        for product in &entry.products {
+            let Some(count) = product.count.checked_mul(amount) else {
+                return self.send_packet(to_client::ActionFailed::normal()?).await;
+            };
+            let kind = items_data.try_get_template(product.id)?.kind;
+            let enchant = if list.maintain_enchantment && kind != ItemKind::EtcItem {
+                enchant_level
+            } else {
+                0
+            };
+            give.push((product.id, count, enchant));
+        }
+        let take: Vec<_> = take.into_iter().collect();
+        self.exchange_items(&take, &give).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pl_client::GetCharInfo;
+    use crate::test_utils::test::{npc, on_actor, spawn_player};
+    use kameo::actor::ActorRef;
+    use l2_core::game_objects::player::Player;
+    use test_utils::utils::get_test_db;
+
+    fn count(player: &Player, item_id: i32) -> i64 {
+        player
+            .inventory
+            .get_items_by_item_id(item_id)
+            .map(|i| i.item_model.count)
+            .sum()
+    }
+
+    async fn give(actor: &ActorRef<PlayerClient>, items: Vec<(i32, i64, i32)>) {
+        on_actor(actor, move |pl| {
+            Box::pin(async move { pl.exchange_items(&[], &items).await })
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_buy_and_sell() {
+        let db_pool = get_test_db().await;
+        let (_, actor, _conn) = spawn_player(&db_pool, 1).await;
+        give(&actor, vec![(Inventory::ADENA_ID, 10_000, 0), (1, 1, 0)]).await;
+
+        // nothing is open yet
+        on_actor(&actor, |pl| Box::pin(pl.buy_items(1, &[(736, 1)]))).await;
+        assert_eq!(count(&actor.ask(GetCharInfo).await.unwrap(), 736), 0);
+
+        // in Gludio, where the tax is 10%
+        on_actor(&actor, |pl| {
+            Box::pin(async move {
+                pl.try_get_selected_char_mut()?
+                    .set_location(-12672, 122776, -3116)?;
+                pl.show_buy_list(&npc(30001), 1).await
+            })
+        })
+        .await;
+        on_actor(&actor, |pl| Box::pin(pl.buy_items(1, &[(736, 2)]))).await;
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert_eq!(count(&player, 736), 2);
+        assert_eq!(count(&player, Inventory::ADENA_ID), 10_000 - 2 * 440);
+
+        // goods of another list, too expensive goods
+        on_actor(&actor, |pl| Box::pin(pl.buy_items(1, &[(2, 1)]))).await;
+        on_actor(&actor, |pl| Box::pin(pl.buy_items(1, &[(13016, 100)]))).await;
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert_eq!(count(&player, 2), 0);
+        assert_eq!(count(&player, 13016), 0);
+        assert_eq!(count(&player, Inventory::ADENA_ID), 9120);
+
+        let sword = player.inventory.get_item_by_item_id(1).unwrap();
+        let sword = (sword.object_id.value(), 1, 1);
+        // adena can't be sold
+        let adena = player.inventory.get_item_by_item_id(57).unwrap();
+        let adena = (adena.object_id.value(), 57, 100);
+        on_actor(&actor, move |pl| {
+            Box::pin(async move { pl.sell_items(1, &[sword, adena]).await })
+        })
+        .await;
+        assert_eq!(count(&actor.ask(GetCharInfo).await.unwrap(), 1), 1);
+        on_actor(&actor, move |pl| {
+            Box::pin(async move { pl.sell_items(1, &[sword]).await })
+        })
+        .await;
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert_eq!(count(&player, 1), 0);
+        assert_eq!(count(&player, Inventory::ADENA_ID), 9120 + 384);
+    }
+
+    #[tokio::test]
+    async fn test_multisell_keeps_enchantment() {
+        let db_pool = get_test_db().await;
+        let (_, actor, _conn) = spawn_player(&db_pool, 1).await;
+        give(
+            &actor,
+            vec![(Inventory::ADENA_ID, 3000, 0), (1, 1, 0), (1, 1, 5)],
+        )
+        .await;
+        on_actor(&actor, |pl| {
+            Box::pin(async move { pl.show_multisell(&npc(30283), 1001).await })
+        })
+        .await;
+
+        // weapons are exchanged one by one
+        on_actor(&actor, |pl| Box::pin(pl.multisell_choose(1001, 1, 2))).await;
+        assert_eq!(count(&actor.ask(GetCharInfo).await.unwrap(), 4), 0);
+
+        on_actor(&actor, |pl| Box::pin(pl.multisell_choose(1001, 1, 1))).await;
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        let club = player.inventory.get_item_by_item_id(4).unwrap();
+        assert_eq!(club.item_model.enchant_level, 5);
+        let sword = player.inventory.get_item_by_item_id(1).unwrap();
+        assert_eq!(sword.item_model.enchant_level, 0);
+        assert_eq!(count(&player, Inventory::ADENA_ID), 2500);
+
+        on_actor(&actor, |pl| Box::pin(pl.multisell_choose(1001, 3, 2))).await;
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert_eq!(count(&player, 1835), 200);
+        assert_eq!(count(&player, Inventory::ADENA_ID), 500);
+
+        // not enough adena left
+        on_actor(&actor, |pl| Box::pin(pl.multisell_choose(1001, 3, 1))).await;
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert_eq!(count(&player, 1835), 200);
+        assert_eq!(count(&player, Inventory::ADENA_ID), 500);
+    }
+}
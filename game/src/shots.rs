@@ -0,0 +1,147 @@
+//! Soulshot/spiritshot charging.
+//!
+//! A shot with enabled auto-use is consumed from inventory as soon as the player can
+//! use it (equipped weapon of the same grade), the charge is spent by the next attack
+//! (soulshots) or magic cast (spiritshots) and recharged right after.
+
+use crate::packets::to_client;
+use crate::packets::to_client::extended::AutoSoulShots;
+use crate::pl_client::PlayerClient;
+use l2_core::data::items::ItemTemplate;
+use l2_core::game_objects::item::shot::ShotType;
+
+/// Shot flags fed into the damage formulas.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ShotCharges {
+    pub ss: bool,
+    pub sps: bool,
+    pub bss: bool,
+}
+
+impl PlayerClient {
+    /// Spends the charge needed by an attack (`magic == false`) or a skill cast.
+    /// Magic skills use spiritshots (blessed first), physical ones use soulshots.
+    pub fn consume_shots(&mut self, magic: bool) -> anyhow::Result<ShotCharges> {
+        let player = self.try_get_selected_char_mut()?;
+        let mut charges = ShotCharges::default();
+        if magic {
+            charges.bss = player.consume_charged_shot(ShotType::BlessedSpiritshots);
+            charges.sps = !charges.bss && player.consume_charged_shot(ShotType::Spiritshots);
+        } else {
+            charges.ss = player.consume_charged_shot(ShotType::Soulshots);
+        }
+        Ok(charges)
+    }
+
+    /// Charges shots from every auto-use item that fits the equipped weapon.
+    pub async fn recharge_shots(&mut self, physical: bool, magic: bool) -> anyhow::Result<()> {
+        let controller = self.controller.clone();
+        let (auto_shots, weapon) = {
+            let player = self.try_get_selected_char()?;
+            let weapon = player
+                .get_weapon()
+                .and_then(|w| controller.items_data.get_template(w.item_model.item_id))
+                .filter(|t| t.is_weapon());
+            (player.get_auto_soulshots().clone(), weapon)
+        };
+        let Some(weapon) = weapon else {
+            return Ok(());
+        };
+        for item_id in auto_shots {
+            let Some(shot_item) = controller.items_data.get_template(item_id) else {
+                continue;
+            };
+            let Some(shot) = shot_item.shot else {
+                continue;
+            };
+            if shot.is_beast() || (shot.is_magic() && !magic) || (!shot.is_magic() && !physical)
+            {
+                continue;
+            }
+            self.charge_shot(weapon, shot_item, shot).await?;
+        }
+        Ok(())
+    }
+
+    async fn charge_shot(
+        &mut self,
+        weapon: &ItemTemplate,
+        shot_item: &ItemTemplate,
+        shot: ShotType,
+    ) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let already_charged = if shot.is_magic() {
+            player.is_charged_shot(ShotType::Spiritshots)
+                || player.is_charged_shot(ShotType::BlessedSpiritshots)
+        } else {
+            player.is_charged_shot(shot)
+        };
+        if already_charged {
+            return Ok(());
+        }
+        let count = if shot.is_magic() {
+            weapon.spiritshots
+        } else {
+            weapon.soulshots
+        };
+        if count == 0 {
+            return Ok(());
+        }
+        if shot_item.crystal_type != weapon.crystal_type.shot_grade() {
+            let msg = if shot.is_magic() {
+                to_client::SystemMessageType::YourSpiritshotDoesNotMatchTheWeaponSGrade
+            } else {
+                to_client::SystemMessageType::TheSoulshotYouAreAttemptingToUseDoesNotMatchTheGradeOfYourEquippedWeapon
+            };
+            return self.send_packet(to_client::SystemMessage::new(msg)?).await;
+        }
+        if !self
+            .destroy_item_by_item_id(shot_item.id, i64::from(count))
+            .await?
+        {
+            return self.disable_auto_shot(shot_item.id, shot, true).await;
+        }
+        let (char_id, x, y, z) = {
+            let player = self.try_get_selected_char_mut()?;
+            player.set_charged_shot(shot, true);
+            (
+                player.get_object_id(),
+                player.get_x(),
+                player.get_y(),
+                player.get_z(),
+            )
+        };
+        if let Some(skill_id) = shot_item.skill_id {
+            self.controller
+                .broadcast_packet(to_client::MagicSkillUse::new(
+                    char_id, char_id, skill_id, 1, 0, 0, 0, x, y, z, x, y, z,
+                )?);
+        }
+        Ok(())
+    }
+
+    /// Turns off auto-use of the shot, `insufficient` explains the reason to the player.
+    pub async fn disable_auto_shot(
+        &mut self,
+        item_id: i32,
+        shot: ShotType,
+        insufficient: bool,
+    ) -> anyhow::Result<()> {
+        let removed = self
+            .try_get_selected_char_mut()?
+            .remove_auto_soulshot(item_id);
+        if !removed {
+            return Ok(());
+        }
+        self.send_packet(AutoSoulShots::new(item_id, false, shot.auto_shot_type())?)
+            .await?;
+        let msg_type = if insufficient {
+            to_client::SystemMessageType::DueToInsufficientS1TheAutomaticUseFunctionHasBeenDeactivated
+        } else {
+            to_client::SystemMessageType::TheAutomaticUseOfS1HasBeenDeactivated
+        };
+        let mut sm = to_client::SystemMessage::new(msg_type)?;
+        sm.add_param(to_client::SystemMessageParam::ItemName(item_id))?;
+        self.send_packet(sm).await
+    }
+}
@@ -0,0 +1,516 @@
+//! Castle sieges.
+//!
+//! Clans register at the siege manager npc of a castle until a day before the siege, the
+//! owner clan defends without a registration. During the siege the siege zone of the castle is
+//! active, attackers build their headquarters inside it and the attacking clan leader who seals
+//! the artifact takes the castle over for the clan. The owner sets the tax rate at the
+//! chamberlain.
+//!
+//! Registered clan members get the `siege_state` of their side while the siege is running,
+//! which is what the relation of players and the auto attack on the battlefield depend on.
+
+use crate::bypass::{DialogNpc, SiegeCommand};
+use crate::managers::{CastleSiege, SiegeManager, SiegeStatus};
+use crate::movement::calculate_distance;
+use crate::packets::to_client;
+use crate::packets::to_client::{SystemMessage, UserInfo};
+use crate::pl_client::{DoLater, PlayerClient, PlayerTasks};
+use entities::dao::siege_clan::SiegeSide;
+use kameo::actor::ActorRef;
+use l2_core::bitmask::BitMask;
+use l2_core::data::castles::Position;
+use l2_core::game_objects::player::clan::ClanPrivilege;
+use l2_core::game_objects::player::user_info::UserInfoType;
+use std::time::Duration;
+use tracing::warn;
+
+/// Lowest level of a clan taking part in a siege
+const MIN_CLAN_LEVEL: i8 = 4;
+pub const SEAL_OF_RULER: i32 = 246;
+pub const BUILD_HEADQUARTERS: i32 = 247;
+/// How close to the artifact the clan leader must stand to seal it
+const ARTIFACT_RANGE: f64 = 200.0;
+
+impl PlayerClient {
+    pub async fn handle_siege_command(
+        &mut self,
+        npc: DialogNpc,
+        command: SiegeCommand,
+    ) -> anyhow::Result<()> {
+        let castle = self
+            .controller
+            .castles
+            .by_npc(npc.npc_id)
+            .and_then(|c| self.controller.siege_manager.get(c.id));
+        let Some(castle) = castle else {
+            warn!("Npc {} serves no castle", npc.npc_id);
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        };
+        match command {
+            SiegeCommand::Info => self.show_siege_info(npc, &castle).await,
+            SiegeCommand::RegisterAttacker => {
+                self.register_for_siege(npc, &castle, SiegeSide::Attacker)
+                    .await
+            }
+            SiegeCommand::RegisterDefender => {
+                self.register_for_siege(npc, &castle, SiegeSide::Defender)
+                    .await
+            }
+            SiegeCommand::Unregister => self.unregister_from_siege(npc, &castle).await,
+            SiegeCommand::TaxInfo => self.show_castle_tax(npc, &castle).await,
+            SiegeCommand::SetTax(rate) => self.set_castle_tax(npc, &castle, rate).await,
+        }
+    }
+
+    async fn show_siege_refusal(&mut self, npc: DialogNpc, reason: &str) -> anyhow::Result<()> {
+        self.show_html(Some(npc), "siege/refused.htm", &[("reason", reason)])
+            .await
+    }
+
+    async fn show_siege_info(
+        &mut self,
+        npc: DialogNpc,
+        castle: &CastleSiege,
+    ) -> anyhow::Result<()> {
+        let owner = match castle.owner {
+            Some(clan_id) => self
+                .controller
+                .clan_ally_manager
+                .read()
+                .await
+                .clan_list
+                .get(&clan_id)
+                .map_or_else(|| clan_id.to_string(), |c| c.name.clone()),
+            None => "None".to_string(),
+        };
+        let status = match castle.status(self.controller.clock.now()) {
+            SiegeStatus::Registration => "Registration is open",
+            SiegeStatus::RegistrationClosed => "Registration is closed",
+            SiegeStatus::InProgress => "The siege is in progress",
+        };
+        let date = castle.starts_at.format("%Y-%m-%d %H:%M UTC").to_string();
+        self.show_html(
+            Some(npc),
+            "siege/info.htm",
+            &[
+                ("castle", &castle.template.name),
+                ("owner", &owner),
+                ("date", &date),
+                ("status", status),
+            ],
+        )
+        .await
+    }
+
+    fn siege_registration_refusal(
+        &self,
+        castle: &CastleSiege,
+        side: SiegeSide,
+    ) -> anyhow::Result<Option<&'static str>> {
+        let player = self.try_get_selected_char()?;
+        let manager = &self.controller.siege_manager;
+        let clan_id = player.get_clan_id();
+        Ok(if !player.has_clan_privilege(ClanPrivilege::ManageSiege) {
+            Some("Only the clan leader or a member with the right may register the clan.")
+        } else if player
+            .clan
+            .as_ref()
+            .is_some_and(|c| c.level < MIN_CLAN_LEVEL)
+        {
+            Some("Only clans of level 4 or above may take part in a siege.")
+        } else if castle.status(self.controller.clock.now()) != SiegeStatus::Registration {
+            Some("The registration period is over.")
+        } else if manager.owned_castle(clan_id).is_some() {
+            Some("A clan owning a castle can't register for a siege.")
+        } else if manager.registered_castle(clan_id).is_some() {
+            Some("Your clan is registered for a siege already.")
+        } else if side == SiegeSide::Defender && castle.owner.is_none() {
+            Some("The castle has no lord to defend it for.")
+        } else {
+            None
+        })
+    }
+
+    async fn register_for_siege(
+        &mut self,
+        npc: DialogNpc,
+        castle: &CastleSiege,
+        side: SiegeSide,
+    ) -> anyhow::Result<()> {
+        if let Some(reason) = self.siege_registration_refusal(castle, side)? {
+            return self.show_siege_refusal(npc, reason).await;
+        }
+        let clan_id = self.try_get_selected_char()?.get_clan_id();
+        self.controller
+            .siege_manager
+            .register(&self.db_pool, castle.template.id, clan_id, side)
+            .await?;
+        let side = match side {
+            SiegeSide::Attacker => "attackers",
+            SiegeSide::Defender => "defenders",
+        };
+        self.show_html(
+            Some(npc),
+            "siege/registered.htm",
+            &[("castle", &castle.template.name), ("side", side)],
+        )
+        .await
+    }
+
+    async fn unregister_from_siege(
+        &mut self,
+        npc: DialogNpc,
+        castle: &CastleSiege,
+    ) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let clan_id = player.get_clan_id();
+        let reason = if !player.has_clan_privilege(ClanPrivilege::ManageSiege) {
+            Some("Only the clan leader or a member with the right may cancel the registration.")
+        } else if !castle.clans.contains_key(&clan_id) {
+            Some("Your clan is not registered for this siege.")
+        } else if castle.status(self.controller.clock.now()) != SiegeStatus::Registration {
+            Some("The registration period is over.")
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            return self.show_siege_refusal(npc, reason).await;
+        }
+        self.controller
+            .siege_manager
+            .unregister(&self.db_pool, castle.template.id, clan_id)
+            .await?;
+        self.show_html(
+            Some(npc),
+            "siege/unregistered.htm",
+            &[("castle", &castle.template.name)],
+        )
+        .await
+    }
+
+    async fn show_castle_tax(
+        &mut self,
+        npc: DialogNpc,
+        castle: &CastleSiege,
+    ) -> anyhow::Result<()> {
+        let rate = castle.tax_rate.to_string();
+        let max = castle.template.max_tax_rate.to_string();
+        self.show_html(
+            Some(npc),
+            "siege/tax.htm",
+            &[
+                ("castle", &castle.template.name),
+                ("rate", &rate),
+                ("max", &max),
+            ],
+        )
+        .await
+    }
+
+    async fn set_castle_tax(
+        &mut self,
+        npc: DialogNpc,
+        castle: &CastleSiege,
+        rate: i32,
+    ) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let reason = if castle.owner != Some(player.get_clan_id())
+            || !player.has_clan_privilege(ClanPrivilege::ManageTaxes)
+        {
+            Some("Only the castle lord or a member with the right may set the taxes.")
+        } else if castle.in_progress {
+            Some("The taxes can't be changed during the siege.")
+        } else if !(0..=castle.template.max_tax_rate).contains(&rate) {
+            Some("The tax rate is out of the allowed range.")
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            return self.show_siege_refusal(npc, reason).await;
+        }
+        self.controller
+            .siege_manager
+            .set_tax_rate(&self.db_pool, castle.template.id, rate)
+            .await?;
+        let rate = rate.to_string();
+        self.show_html(
+            Some(npc),
+            "siege/tax_set.htm",
+            &[("castle", &castle.template.name), ("rate", &rate)],
+        )
+        .await
+    }
+
+    /// Castle the attacking clan leader may use the siege skill at.
+    fn siege_skill_castle(
+        &self,
+        skill_id: i32,
+    ) -> anyhow::Result<Result<CastleSiege, &'static str>> {
+        let player = self.try_get_selected_char()?;
+        if !player.is_clan_leader() {
+            return Ok(Err("Only the clan leader may use this skill."));
+        }
+        let Some(castle) = self
+            .controller
+            .siege_manager
+            .running_siege_of(player.get_clan_id())
+            .filter(|c| c.side_of(player.get_clan_id()) == Some(SiegeSide::Attacker))
+        else {
+            return Ok(Err("This skill is used by the attackers during a siege."));
+        };
+        let (x, y, z) = (player.get_x(), player.get_y(), player.get_z());
+        let on_battlefield = self
+            .controller
+            .zone_manager
+            .zones_at(x, y, z)
+            .any(|zone| zone.id == castle.template.siege_zone);
+        if !on_battlefield {
+            return Ok(Err("This skill is used on the battlefield of the siege."));
+        }
+        if skill_id == SEAL_OF_RULER {
+            let a = castle.template.artifact;
+            let distance = calculate_distance(x, y, z, a.x, a.y, a.z).unwrap_or(f64::MAX);
+            if distance > ARTIFACT_RANGE {
+                return Ok(Err("You are too far from the artifact."));
+            }
+        }
+        Ok(Ok(castle))
+    }
+
+    async fn refuse_siege_skill(&mut self, reason: &str) -> anyhow::Result<()> {
+        self.send_packet(SystemMessage::text(reason)?).await?;
+        self.send_packet(to_client::ActionFailed::normal()?).await
+    }
+
+    /// Seal of Ruler and Build Headquarters, the clan leader skills of the sieges.
+    pub async fn use_siege_skill(
+        &mut self,
+        actor_ref: ActorRef<Self>,
+        skill_id: i32,
+    ) -> anyhow::Result<()> {
+        let castle = match self.siege_skill_castle(skill_id)? {
+            Ok(castle) => castle,
+            Err(reason) => return self.refuse_siege_skill(reason).await,
+        };
+        if skill_id == BUILD_HEADQUARTERS {
+            return self.build_headquarters(&castle).await;
+        }
+        let cast_time = self
+            .controller
+            .skills
+            .get_skill(SEAL_OF_RULER as u32, 1)
+            .map_or(0, |s| s.hit_time_at(1));
+        let player = self.try_get_selected_char()?;
+        let obj_id = player.get_object_id();
+        let (x, y, z) = (player.get_x(), player.get_y(), player.get_z());
+        let a = castle.template.artifact;
+        self.controller
+            .broadcast_packet(to_client::MagicSkillUse::new(
+                obj_id,
+                obj_id,
+                SEAL_OF_RULER,
+                1,
+                cast_time,
+                0,
+                0,
+                x,
+                y,
+                z,
+                a.x,
+                a.y,
+                a.z,
+            )?);
+        let delay = Duration::from_millis(u64::try_from(cast_time)?);
+        self.schedule_task(
+            PlayerTasks::SealOfRuler,
+            tokio::spawn(async move {
+                tokio::time::sleep(delay).await;
+                let _ = actor_ref
+                    .tell(DoLater {
+                        delay: Duration::ZERO,
+                        callback: Box::new(|pl: &mut PlayerClient| Box::pin(pl.engrave_castle())),
+                    })
+                    .await;
+            }),
+        );
+        Ok(())
+    }
+
+    async fn build_headquarters(&mut self, castle: &CastleSiege) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let position = Position {
+            x: player.get_x(),
+            y: player.get_y(),
+            z: player.get_z(),
+        };
+        let built = self.controller.siege_manager.build_headquarters(
+            castle.template.id,
+            player.get_clan_id(),
+            position,
+        );
+        if !built {
+            return self
+                .refuse_siege_skill("Your clan has built its headquarters already.")
+                .await;
+        }
+        self.send_packet(SystemMessage::text("The headquarters have been built.")?)
+            .await
+    }
+
+    /// The seal is finished, the clan of the player takes the castle over.
+    pub async fn engrave_castle(&mut self) -> anyhow::Result<()> {
+        let castle = match self.siege_skill_castle(SEAL_OF_RULER)? {
+            Ok(castle) => castle,
+            Err(reason) => return self.refuse_siege_skill(reason).await,
+        };
+        let player = self.try_get_selected_char()?;
+        let clan_id = player.get_clan_id();
+        let clan_name = player
+            .clan
+            .as_ref()
+            .map_or_else(String::new, |c| c.name.clone());
+        self.controller
+            .siege_manager
+            .engrave(&self.db_pool, castle.template.id, clan_id)
+            .await?;
+        SiegeManager::announce(
+            &self.controller,
+            &format!(
+                "Clan {clan_name} has engraved the ruler of {} Castle.",
+                castle.template.name
+            ),
+        )?;
+        SiegeManager::refresh_siege_states(&self.controller);
+        Ok(())
+    }
+
+    /// Takes the siege side of the clan and lets the players around know the new relation.
+    pub async fn refresh_siege_state(&mut self) -> anyhow::Result<()> {
+        let controller = self.controller.clone();
+        let player = self.try_get_selected_char_mut()?;
+        let state = controller.siege_manager.siege_state(player.get_clan_id());
+        if player.siege_state == state {
+            return Ok(());
+        }
+        player.siege_state = state;
+        let player = player.clone();
+        let mut flags = BitMask::new(24);
+        flags.add_mask(UserInfoType::Relation);
+        self.send_packet(UserInfo::new(&player, flags, &controller).await?)
+            .await?;
+        if let Some(actor) = controller.get_player_by_object_id(player.get_object_id()) {
+            controller.broadcast_relations(player, actor);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bypass::NpcCommand;
+    use crate::managers::{SIEGE_STATE_ATTACKER, SIEGE_STATE_DEFENDER, SiegeEvent};
+    use crate::pl_client::GetCharInfo;
+    use crate::test_utils::test::{get_controller, npc_command, on_actor, spawn_player};
+    use chrono::TimeDelta;
+    use entities::entities::{castle, clan_ally, siege_clan};
+    use entities::test_factories::factories::clan_factory;
+    use test_utils::utils::get_test_db;
+
+    #[tokio::test]
+    async fn test_siege_registration_engrave_and_tax() {
+        let db_pool = get_test_db().await;
+        let (char_id, actor, _conn) = spawn_player(&db_pool, 75).await;
+        let clan = clan_factory(&db_pool, |mut c| {
+            c.leader_id = char_id;
+            c
+        })
+        .await;
+        let member_clan: clan_ally::Model = clan.clone();
+        on_actor(&actor, move |pl| {
+            Box::pin(async move {
+                let player = pl.try_get_selected_char_mut()?;
+                player.char_model.clan_id = Some(member_clan.id);
+                player.clan = Some(member_clan);
+                Ok(())
+            })
+        })
+        .await;
+        npc_command(
+            &actor,
+            35104,
+            NpcCommand::Siege(SiegeCommand::RegisterAttacker),
+        )
+        .await;
+        let registrations = siege_clan::Model::load_all(&db_pool).await.unwrap();
+        assert_eq!(registrations.len(), 1);
+        assert_eq!(registrations[0].side, SiegeSide::Attacker);
+
+        // the castle has no owner to defend and the clan is registered already
+        npc_command(
+            &actor,
+            35104,
+            NpcCommand::Siege(SiegeCommand::RegisterDefender),
+        )
+        .await;
+        assert_eq!(
+            siege_clan::Model::load_all(&db_pool).await.unwrap().len(),
+            1
+        );
+
+        let controller = get_controller(&actor).await;
+        let castle = controller.siege_manager.get(1).unwrap();
+        controller
+            .clock
+            .advance(castle.starts_at - controller.clock.now() + TimeDelta::minutes(1));
+        assert_eq!(
+            SiegeManager::update(&controller, &db_pool).await.unwrap(),
+            vec![SiegeEvent::Started(1)]
+        );
+        let siege_zone = castle.template.siege_zone;
+        let zone = controller.zone_manager.data.get_zone(siege_zone).unwrap();
+        assert!(controller.zone_manager.is_active(zone));
+        on_actor(&actor, |pl| Box::pin(pl.refresh_siege_state())).await;
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert_eq!(player.siege_state, SIEGE_STATE_ATTACKER);
+
+        // the leader seals the artifact standing next to it
+        let a = castle.template.artifact;
+        on_actor(&actor, move |pl| {
+            Box::pin(async move {
+                pl.try_get_selected_char_mut()?
+                    .set_location(a.x, a.y, a.z)?;
+                pl.engrave_castle().await
+            })
+        })
+        .await;
+        let owned = castle::Model::load_all(&db_pool).await.unwrap();
+        assert_eq!(owned.len(), 1);
+        assert_eq!(owned[0].clan_id, clan.id);
+        assert!(
+            siege_clan::Model::load_all(&db_pool)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            controller.siege_manager.siege_state(clan.id),
+            SIEGE_STATE_DEFENDER
+        );
+
+        // no taxes during the siege
+        npc_command(&actor, 35100, NpcCommand::Siege(SiegeCommand::SetTax(10))).await;
+        assert_eq!(controller.siege_manager.get(1).unwrap().tax_rate, 0);
+
+        controller.clock.advance(TimeDelta::hours(2));
+        assert_eq!(
+            SiegeManager::update(&controller, &db_pool).await.unwrap(),
+            vec![SiegeEvent::Ended(1)]
+        );
+        assert_eq!(controller.siege_manager.siege_state(clan.id), 0);
+        npc_command(&actor, 35100, NpcCommand::Siege(SiegeCommand::SetTax(10))).await;
+        let owned = castle::Model::load_all(&db_pool).await.unwrap();
+        assert_eq!(owned[0].tax_rate, 10);
+        assert!(owned[0].siege_starts_at.unwrap().to_utc() > controller.clock.now());
+    }
+}
@@ -186,7 +186,7 @@ pub async fn gather_affected_targets(
                 if id == caster_id {
                     continue;
                 }
-                let Ok(stats) = actor.ask(GetStats).await else {
+                let Ok(stats) = actor.ask(GetStats { target_id: id }).await else {
                     continue;
                 };
                 let dist =
@@ -0,0 +1,491 @@
+//! Subclasses.
+//!
+//! Village masters teach up to three classes besides the base one. Every class has its own
+//! level, exp, sp, skills, shortcuts, hennas and effects, kept by the class index (0 is the
+//! base class), and only one of them is active at a time. The char row holds the active class,
+//! the others are saved in `character_subclass`.
+//!
+//! A subclass is added when all the classes of the char are level 75, only nobles may have
+//! more than one. Classes are changed outside of combat and the Olympiad.
+
+use crate::bypass::{DialogNpc, SubclassCommand};
+use crate::controller::GameController;
+use crate::packets::to_client;
+use crate::packets::to_client::extended::{SubclassInfo, SubclassInfoType};
+use crate::packets::to_client::{
+    AcquireSkillList, HennaInfo, ShortcutsInit, SkillList, SystemMessage, SystemMessageType,
+    UserInfo,
+};
+use crate::pl_client::PlayerClient;
+use crate::skills::{SkillAction, classify_effects};
+use entities::entities::{
+    character, character_henna, character_shortcut, character_subclass, character_subclass_effect,
+    skill,
+};
+use l2_core::data::classes::mapping::Class;
+use l2_core::game_objects::creature::buff::AppliedBuff;
+use l2_core::game_objects::creature::skill::Skill;
+use l2_core::game_objects::player::user_info::UserInfoType;
+use l2_core::game_objects::player::{Shortcut, Subclass};
+use std::collections::HashMap;
+use std::fmt::Write;
+use tracing::warn;
+
+impl PlayerClient {
+    pub async fn handle_subclass_command(
+        &mut self,
+        npc: DialogNpc,
+        command: SubclassCommand,
+    ) -> anyhow::Result<()> {
+        match command {
+            SubclassCommand::Menu => self.show_html(Some(npc), "subclass/menu.htm", &[]).await,
+            SubclassCommand::AddList => self.show_subclass_add_list(npc).await,
+            SubclassCommand::Add(class_id) => self.add_subclass(npc, class_id).await,
+            SubclassCommand::ChangeList => self.show_subclass_change_list(npc).await,
+            SubclassCommand::Change(index) => self.change_subclass(npc, index).await,
+            SubclassCommand::CancelList => self.show_subclass_cancel_list(npc).await,
+            SubclassCommand::Cancel(index) => self.cancel_subclass(npc, index).await,
+        }
+    }
+
+    /// Links to the bypass `command` for `(argument, class)` pairs
+    fn class_links(
+        npc: &DialogNpc,
+        command: &str,
+        classes: impl Iterator<Item = (i32, Option<Class>)>,
+    ) -> anyhow::Result<String> {
+        let mut links = String::new();
+        for (arg, class) in classes {
+            let Some(class) = class else {
+                continue;
+            };
+            writeln!(
+                links,
+                "<a action=\"bypass -h npc_{}_{command} {arg}\">{class:?}</a><br>",
+                npc.object_id
+            )?;
+        }
+        Ok(links)
+    }
+
+    /// Classes are changed only when the player is not busy with something else.
+    fn can_change_class(&self) -> anyhow::Result<bool> {
+        let player = self.try_get_selected_char()?;
+        Ok(!player.is_dead() && !player.is_in_combat() && !player.is_in_olympiad_mode())
+    }
+
+    async fn show_subclass_add_list(&mut self, npc: DialogNpc) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        if !player.can_add_subclass() {
+            return self
+                .show_html(Some(npc), "subclass/cant_add.htm", &[])
+                .await;
+        }
+        let available = player.get_available_subclasses();
+        let classes = available.into_iter().map(|c| (i32::from(c as u8), Some(c)));
+        let links = Self::class_links(&npc, "SubclassAdd", classes)?;
+        self.show_html_markup(Some(npc), "subclass/add.htm", &[], &[("classes", &links)])
+            .await
+    }
+
+    async fn show_subclass_change_list(&mut self, npc: DialogNpc) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        if player.get_subclass_count() == 0 {
+            return self
+                .show_html(Some(npc), "subclass/no_subclass.htm", &[])
+                .await;
+        }
+        let active = player.get_class_index();
+        let classes = player
+            .get_subclasses()
+            .iter()
+            .filter(|s| s.index != active)
+            .map(|s| (s.index, Class::try_from(s.class_id).ok()));
+        let links = Self::class_links(&npc, "SubclassChange", classes)?;
+        self.show_html_markup(Some(npc), "subclass/change.htm", &[], &[("classes", &links)])
+            .await
+    }
+
+    async fn show_subclass_cancel_list(&mut self, npc: DialogNpc) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        if player.get_subclass_count() == 0 {
+            return self
+                .show_html(Some(npc), "subclass/no_subclass.htm", &[])
+                .await;
+        }
+        let classes = player
+            .get_subclasses()
+            .iter()
+            .filter(|s| s.index > 0)
+            .map(|s| (s.index, Class::try_from(s.class_id).ok()));
+        let links = Self::class_links(&npc, "SubclassCancel", classes)?;
+        self.show_html_markup(Some(npc), "subclass/cancel.htm", &[], &[("classes", &links)])
+            .await
+    }
+
+    /// Adds the subclass with its initial skills and makes it active.
+    async fn add_subclass(&mut self, npc: DialogNpc, class_id: i32) -> anyhow::Result<()> {
+        if !self.can_change_class()? {
+            return self
+                .show_html(Some(npc), "subclass/cant_change.htm", &[])
+                .await;
+        }
+        let player = self.try_get_selected_char()?;
+        if !player.can_add_subclass() {
+            return self
+                .show_html(Some(npc), "subclass/cant_add.htm", &[])
+                .await;
+        }
+        let class = u8::try_from(class_id)
+            .ok()
+            .and_then(|id| Class::try_from(id).ok())
+            .filter(|c| player.get_available_subclasses().contains(c));
+        let (Some(class), Some(index)) = (class, player.get_free_class_index()) else {
+            warn!(
+                "{} can't take class {class_id} as a subclass, possible cheating",
+                player.char_model.name
+            );
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        };
+        let controller = self.controller.clone();
+        controller.class_templates.try_get_template(class)?;
+        let char_id = player.char_model.id;
+        // the tree has every level up to the char's one, the highest is learned
+        let mut levels: HashMap<u32, u8> = HashMap::new();
+        for s in controller
+            .skill_trees_data
+            .get_initial_skills(class, Subclass::STARTING_LEVEL)
+        {
+            let level = levels.entry(s.skill_id()).or_default();
+            *level = (*level).max(s.skill_level());
+        }
+        let skills = levels
+            .into_iter()
+            .map(|(id, level)| -> anyhow::Result<skill::Model> {
+                Ok(skill::Model {
+                    id: i32::try_from(id)?,
+                    char_id,
+                    level: level.into(),
+                    sub_level: 0,
+                    class_index: index,
+                })
+            })
+            .collect::<anyhow::Result<_>>()?;
+        let sub = Subclass::new(index, class, &controller.exp_table);
+        character_subclass::Model::add(&self.db_pool, sub.to_model(char_id), skills).await?;
+        self.try_get_selected_char_mut()?.add_subclass(sub);
+        self.send_packet(SystemMessage::new(
+            SystemMessageType::TheNewSubclassHasBeenAdded,
+        )?)
+        .await?;
+        self.set_active_class(index).await?;
+        self.show_html(Some(npc), "subclass/done.htm", &[]).await
+    }
+
+    async fn change_subclass(&mut self, npc: DialogNpc, index: i32) -> anyhow::Result<()> {
+        if !self.can_change_class()? {
+            return self
+                .show_html(Some(npc), "subclass/cant_change.htm", &[])
+                .await;
+        }
+        let player = self.try_get_selected_char()?;
+        if player.get_subclass(index).is_none() || player.get_class_index() == index {
+            warn!(
+                "{} can't change to class index {index}, possible cheating",
+                player.char_model.name
+            );
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        }
+        self.set_active_class(index).await?;
+        self.show_html(Some(npc), "subclass/done.htm", &[]).await
+    }
+
+    /// Removes the subclass with everything learned on it, the char goes back to
+    /// the base class when it was the active one.
+    async fn cancel_subclass(&mut self, npc: DialogNpc, index: i32) -> anyhow::Result<()> {
+        if !self.can_change_class()? {
+            return self
+                .show_html(Some(npc), "subclass/cant_change.htm", &[])
+                .await;
+        }
+        let player = self.try_get_selected_char()?;
+        if index == 0 || player.get_subclass(index).is_none() {
+            warn!(
+                "{} can't cancel class index {index}, possible cheating",
+                player.char_model.name
+            );
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        }
+        if player.get_class_index() == index {
+            self.set_active_class(0).await?;
+        }
+        let player = self.try_get_selected_char_mut()?;
+        player.remove_subclass(index);
+        let char_id = player.char_model.id;
+        character_subclass::Model::delete_with_class_data(&self.db_pool, char_id, index).await?;
+        let player = self.try_get_selected_char()?;
+        self.send_packet(SubclassInfo::new(player, SubclassInfoType::NoChanges)?)
+            .await?;
+        self.show_html(Some(npc), "subclass/done.htm", &[]).await
+    }
+
+    /// Switches to the class and restores what the player had on it.
+    async fn set_active_class(&mut self, index: i32) -> anyhow::Result<()> {
+        let controller = self.controller.clone();
+        let player = self.try_get_selected_char_mut()?;
+        player.sync_active_class();
+        let char_id = player.char_model.id;
+        let old_index = player.get_class_index();
+        let old = player.get_subclass(old_index).cloned();
+        let class_id = player
+            .get_subclass(index)
+            .map(|s| s.class_id)
+            .ok_or_else(|| anyhow::anyhow!("Char {char_id} has no class {index}"))?;
+        let template = controller
+            .class_templates
+            .try_get_template(Class::try_from(class_id)?)?
+            .clone();
+        let old_effects = player
+            .set_active_class(index, template, &controller.base_stats_table)?
+            .into_iter()
+            .filter(|b| !b.is_expired())
+            .map(|b| effect_model(char_id, old_index, b))
+            .collect();
+        self.restore_class_data(char_id, index).await?;
+        if let Some(old) = old {
+            character_subclass::Model::save(&self.db_pool, old.to_model(char_id)).await?;
+        }
+        character_subclass::Model::save_effects(&self.db_pool, char_id, old_index, old_effects)
+            .await?;
+        let player = self.try_get_selected_char()?;
+        character::Model::update_char(&self.db_pool, &player.char_model).await?;
+
+        let player = self.try_get_selected_char()?;
+        let user_info = UserInfo::new(player, UserInfoType::all(), &controller).await?;
+        let skill_list = SkillList::new(player, &controller.skills)?;
+        let acquire_skill_list = AcquireSkillList::new(player, &controller.skill_trees_data)?;
+        let shortcuts = ShortcutsInit::new(player)?;
+        let henna_info = HennaInfo::new(player)?;
+        let subclass_info = SubclassInfo::new(player, SubclassInfoType::ClassChanged)?;
+        self.send_packet(user_info).await?;
+        self.send_packet(skill_list).await?;
+        self.send_packet(acquire_skill_list).await?;
+        self.send_packet(shortcuts).await?;
+        self.send_packet(henna_info).await?;
+        self.send_packet(subclass_info).await?;
+        self.send_packet(SystemMessage::new(
+            SystemMessageType::TheTransferOfSubclassHasBeenCompleted,
+        )?)
+        .await
+    }
+
+    /// Skills, shortcuts, hennas and kept effects of the class
+    async fn restore_class_data(&mut self, char_id: i32, index: i32) -> anyhow::Result<()> {
+        let skills = skill::Model::char_skills(&self.db_pool, char_id).await?;
+        let shortcuts = character_shortcut::Model::char_shortcuts(&self.db_pool, char_id).await?;
+        let hennas = character_henna::Model::char_hennas(&self.db_pool, char_id, index).await?;
+        let effects =
+            character_subclass::Model::take_effects(&self.db_pool, char_id, index).await?;
+        let controller = self.controller.clone();
+        let player = self.try_get_selected_char_mut()?;
+        player.stats.active_buffs = effects
+            .into_iter()
+            .filter_map(|e| restore_effect(&controller, e))
+            .collect();
+        player.stats.update_cache();
+        player.skills = Some(
+            skills
+                .into_iter()
+                .filter(|s| s.class_index == index)
+                .map(Skill::from_model)
+                .collect(),
+        );
+        player.shortcuts = shortcuts
+            .into_iter()
+            .filter(|sc| sc.class_index == index)
+            .map(Shortcut::from_model)
+            .collect();
+        for henna in hennas {
+            if let Some(template) = controller.hennas.get_henna(henna.dye_id) {
+                player.set_henna(henna.slot, template.clone())?;
+            } else {
+                warn!("Unknown henna {} of char {char_id}", henna.dye_id);
+            }
+        }
+        // hero skills are not stored with the class skills
+        let hero = player.is_hero();
+        self.set_hero_skills(hero)
+    }
+}
+
+fn effect_model(
+    char_id: i32,
+    class_index: i32,
+    buff: AppliedBuff,
+) -> character_subclass_effect::Model {
+    character_subclass_effect::Model {
+        char_id,
+        class_index,
+        skill_id: buff.skill_id,
+        skill_level: buff.skill_level,
+        caster_id: buff.caster_id,
+        abnormal_type: buff.abnormal_type,
+        abnormal_level: buff.abnormal_level,
+        end_time: buff.end_time.into(),
+    }
+}
+
+/// The kept effect with the stat modifiers of its skill, `None` when it's over.
+fn restore_effect(
+    controller: &GameController,
+    effect: character_subclass_effect::Model,
+) -> Option<AppliedBuff> {
+    let level = u8::try_from(effect.skill_level).ok()?;
+    let Some(skill) = controller.skills.get_skill(effect.skill_id as u32, level) else {
+        warn!("Unknown skill {} of a kept effect", effect.skill_id);
+        return None;
+    };
+    let mods = classify_effects(skill, level)
+        .into_iter()
+        .find_map(|a| match a {
+            SkillAction::Buff { mods } => Some(mods),
+            _ => None,
+        })?;
+    let buff = AppliedBuff {
+        skill_id: effect.skill_id,
+        skill_level: effect.skill_level,
+        caster_id: effect.caster_id,
+        abnormal_type: effect.abnormal_type,
+        abnormal_level: effect.abnormal_level,
+        end_time: effect.end_time.into(),
+        mods,
+    };
+    (!buff.is_expired()).then_some(buff)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bypass::{NpcCommand, SubclassCommand};
+    use crate::pl_client::{ApplyBuff, GetCharInfo};
+    use crate::test_utils::test::{npc_command, on_actor, spawn_player};
+    use entities::entities::{character, character_subclass, character_subclass_effect, skill};
+    use l2_core::data::classes::mapping::Class;
+    use sea_orm::EntityTrait;
+    use test_utils::utils::get_test_db;
+
+    #[tokio::test]
+    async fn test_add_change_and_cancel_subclass() {
+        let db_pool = get_test_db().await;
+        let (char_id, actor, _conn) = spawn_player(&db_pool, 75).await;
+        on_actor(&actor, |pl| {
+            Box::pin(async move {
+                pl.try_get_selected_char_mut()?.char_model.cur_hp = 100.0;
+                Ok(())
+            })
+        })
+        .await;
+        let base_class = actor.ask(GetCharInfo).await.unwrap().char_model.class_id;
+        actor
+            .ask(ApplyBuff {
+                target_id: char_id,
+                skill_id: 1068,
+                skill_level: 1,
+                caster_id: char_id,
+                abnormal_type: Some("PA_UP".to_string()),
+                abnormal_level: 1,
+                abnormal_time_secs: 1200,
+                mods: vec![],
+            })
+            .await
+            .unwrap();
+
+        // the base class can't be taken again
+        let player = npc_command(
+            &actor,
+            30026,
+            NpcCommand::Subclass(SubclassCommand::Add(i32::from(base_class))),
+        )
+        .await;
+        assert_eq!(player.get_subclass_count(), 0);
+
+        let sorcerer = Class::Sorcerer as i8;
+        let player = npc_command(
+            &actor,
+            30026,
+            NpcCommand::Subclass(SubclassCommand::Add(i32::from(sorcerer))),
+        )
+        .await;
+        assert_eq!(player.get_class_index(), 1);
+        assert_eq!(player.char_model.class_id, sorcerer);
+        assert_eq!(player.char_model.level, 40);
+        assert_eq!(player.template.class_id, Class::Sorcerer);
+        assert!(player.stats.active_buffs.is_empty());
+        let kept = character_subclass_effect::Entity::find()
+            .all(&db_pool)
+            .await
+            .unwrap();
+        assert_eq!(kept.len(), 1);
+        assert_eq!((kept[0].class_index, kept[0].skill_id), (0, 1068));
+        let saved = character::Entity::find_by_id(char_id)
+            .one(&db_pool)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(saved.class_id, sorcerer);
+        let subs = character_subclass::Model::char_subclasses(&db_pool, char_id)
+            .await
+            .unwrap();
+        assert_eq!(subs.len(), 2);
+        assert_eq!((subs[0].class_id, subs[0].level), (base_class, 75));
+        let skills = skill::Model::char_skills(&db_pool, char_id).await.unwrap();
+        assert!(skills.iter().all(|s| s.class_index == 1));
+        assert_eq!(player.skills.as_ref().map(Vec::len), Some(skills.len()));
+
+        // the subclass is below 75 and the char is not a noble
+        let player = npc_command(
+            &actor,
+            30026,
+            NpcCommand::Subclass(SubclassCommand::Add(Class::Warlock as i32)),
+        )
+        .await;
+        assert_eq!(player.get_subclass_count(), 1);
+
+        let player = npc_command(
+            &actor,
+            30026,
+            NpcCommand::Subclass(SubclassCommand::Change(0)),
+        )
+        .await;
+        assert_eq!(player.get_class_index(), 0);
+        assert_eq!(player.char_model.class_id, base_class);
+        assert_eq!(player.char_model.level, 75);
+        assert_eq!(player.get_subclass(1).unwrap().level, 40);
+        // the effect of the base class is taken back, the one of the subclass is kept
+        let kept = character_subclass_effect::Entity::find()
+            .all(&db_pool)
+            .await
+            .unwrap();
+        assert!(kept.iter().all(|e| e.class_index == 1));
+
+        let player = npc_command(
+            &actor,
+            30026,
+            NpcCommand::Subclass(SubclassCommand::Cancel(1)),
+        )
+        .await;
+        assert_eq!(player.get_subclass_count(), 0);
+        assert!(
+            character_subclass::Model::char_subclasses(&db_pool, char_id)
+                .await
+                .unwrap()
+                .iter()
+                .all(|s| s.class_index == 0)
+        );
+        assert!(
+            skill::Model::char_skills(&db_pool, char_id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,1078 @@
+//! Servitors and pets.
+//!
+//! A servitor is called by a summon skill and stays until it is dismissed or killed. A pet
+//! lives in its collar: using the collar calls the pet out or sends it back, and its level,
+//! exp, food and the items it carries are kept by the DB id of the collar (see [`pet::Model`]
+//! and [`ItemPlace::pet`]). While it is out the summon is in the world registry under its own
+//! object id, the messages to it go to the actor of the owner.
+//!
+//! Every second the summon follows the owner or fights the target it was sent to, and every
+//! minute it eats: more while it fights. A hungry pet eats the food it carries by itself and
+//! a pet with nothing left to eat leaves its owner.
+
+use crate::movement::{calculate_distance, calculate_nearest_hit_point};
+use crate::packets::to_client;
+use crate::packets::to_client::{
+    ActionFailed, CharMoveToLocation, DeleteObject, PetDelete, PetInfo, PetInfoType, PetItemList,
+    PetStatusShow, PetStatusUpdate, SystemMessage,
+};
+use crate::pl_client::{
+    ApplyDamage, ApplyHeal, DoLater, FullStats, GetCharInfo, GetStats, PlayerClient, PlayerTasks,
+};
+use crate::warehouse::pick_items;
+use entities::dao::item::{ItemPlace, LocType};
+use entities::entities::{item, pet};
+use kameo::actor::ActorRef;
+use l2_core::data::items::ItemHandler;
+use l2_core::data::summons::SummonTemplate;
+use l2_core::errors::KameoAnyhowExt;
+use l2_core::game_objects::stats::Formulas;
+use l2_core::game_objects::summon::{Summon, SummonIntention};
+use std::time::Duration;
+use tracing::{error, warn};
+
+const AI_TICK: Duration = Duration::from_secs(1);
+/// AI ticks between the meals
+const FEED_TICKS: u32 = 60;
+/// The summon walks after the owner once it is this far away
+const FOLLOW_DISTANCE: i32 = 200;
+/// How close to the owner the summon stays
+const FOLLOW_OFFSET: i32 = 50;
+const ATTACK_RANGE: i32 = 80;
+
+impl PlayerClient {
+    async fn refuse_summon(&mut self, reason: &str) -> anyhow::Result<()> {
+        self.send_packet(SystemMessage::text(reason)?).await?;
+        self.send_packet(ActionFailed::normal()?).await
+    }
+
+    fn summon_template(&self, npc_id: i32) -> anyhow::Result<SummonTemplate> {
+        self.controller
+            .summons
+            .get(npc_id)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("No summon template {npc_id}"))
+    }
+
+    /// Stats of the summon of the player with the object id, the owner shares a part of its.
+    pub fn get_summon_stats(&self, object_id: i32) -> anyhow::Result<Option<FullStats>> {
+        let player = self.try_get_selected_char()?;
+        let Some(summon) = player
+            .summon
+            .as_ref()
+            .filter(|s| s.get_object_id() == object_id)
+        else {
+            return Ok(None);
+        };
+        let template = self.summon_template(summon.npc_id)?;
+        let location = summon.get_location();
+        Ok(Some(FullStats {
+            stats: summon.calc_stats(&template, &player.stats.cached_stats),
+            x: location.x,
+            y: location.y,
+            z: location.z,
+            level: summon.level,
+            current_hp: summon.current_hp,
+        }))
+    }
+
+    /// Summon skill of a servitor: the owner casts and the servitor appears next to them.
+    pub async fn cast_summon_servitor(
+        &mut self,
+        actor_ref: ActorRef<Self>,
+        skill_id: i32,
+        npc_id: i32,
+    ) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        if player.is_mounted() {
+            return self.refuse_summon("You can't summon while riding.").await;
+        }
+        if player.has_summon() {
+            return self
+                .refuse_summon("You already have a servitor or pet.")
+                .await;
+        }
+        let template = self.summon_template(npc_id)?;
+        let location = *self.try_get_selected_char()?.get_location();
+        let servitor = Summon::servitor(&template, location);
+        self.cast_summon(actor_ref, skill_id, template.cast_time_ms, servitor)
+    }
+
+    /// The collar calls the pet out, or sends it back when it is already out.
+    pub async fn use_pet_collar(
+        &mut self,
+        actor_ref: ActorRef<Self>,
+        object_id: i32,
+    ) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        if player.is_mounted() {
+            return self.refuse_summon("You can't summon while riding.").await;
+        }
+        let char_id = player.char_model.id;
+        let location = *player.get_location();
+        let Some(collar) = player.inventory.items.get(&object_id) else {
+            return self.send_packet(ActionFailed::normal()?).await;
+        };
+        let (collar_id, collar_item_id) = (collar.item_model.id, collar.item_model.item_id);
+        if let Some(summon) = player.summon.as_ref() {
+            if summon.control_item_id == Some(collar_id) {
+                return self.unsummon().await;
+            }
+            return self
+                .refuse_summon("You already have a servitor or pet.")
+                .await;
+        }
+        let Some(template) = self
+            .controller
+            .summons
+            .by_control_item(collar_item_id)
+            .cloned()
+        else {
+            warn!("No pet lives in the item {collar_item_id}");
+            return self.send_packet(ActionFailed::normal()?).await;
+        };
+        let model = match pet::Model::find(&self.db_pool, collar_id).await? {
+            Some(model) => model,
+            None => {
+                let model = Summon::new_pet_model(&template, collar_id);
+                pet::Model::save(&self.db_pool, model.clone()).await?;
+                model
+            }
+        };
+        let items =
+            item::Model::items_at(&self.db_pool, ItemPlace::pet(char_id, collar_id)).await?;
+        let mut pet = Summon::pet(&template, &model, items, location);
+        if pet.current_hp <= 0.0 {
+            // the pet was killed the last time it was out
+            pet.current_hp = pet.get_max_hp(&template);
+        }
+        self.cast_summon(actor_ref, 0, template.cast_time_ms, pet)
+    }
+
+    /// The owner casts for the time of the template, the summon appears afterwards.
+    fn cast_summon(
+        &mut self,
+        actor_ref: ActorRef<Self>,
+        skill_id: i32,
+        cast_time_ms: u32,
+        summon: Summon,
+    ) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let obj_id = player.get_object_id();
+        let (x, y, z) = (player.get_x(), player.get_y(), player.get_z());
+        self.controller
+            .broadcast_packet(to_client::MagicSkillUse::new(
+                obj_id,
+                obj_id,
+                skill_id,
+                1,
+                i32::try_from(cast_time_ms)?,
+                0,
+                0,
+                x,
+                y,
+                z,
+                x,
+                y,
+                z,
+            )?);
+        let delay = Duration::from_millis(u64::from(cast_time_ms));
+        self.schedule_task(
+            PlayerTasks::SummonCast,
+            tokio::spawn(async move {
+                tokio::time::sleep(delay).await;
+                let owner = actor_ref.clone();
+                let _ = actor_ref
+                    .tell(DoLater {
+                        delay: Duration::ZERO,
+                        callback: Box::new(move |pl: &mut PlayerClient| {
+                            Box::pin(pl.put_summon(owner, summon))
+                        }),
+                    })
+                    .await;
+            }),
+        );
+        Ok(())
+    }
+
+    /// The summon appears next to the owner and its window opens.
+    pub async fn put_summon(
+        &mut self,
+        actor_ref: ActorRef<Self>,
+        mut summon: Summon,
+    ) -> anyhow::Result<()> {
+        let template = self.summon_template(summon.npc_id)?;
+        let player = self.try_get_selected_char()?;
+        if player.has_summon() {
+            return Ok(());
+        }
+        let owner_id = player.get_object_id();
+        let owner_name = player.get_visible_name().to_string();
+        let location = player.get_location();
+        summon.set_location(location.x + FOLLOW_OFFSET, location.y, location.z);
+        let stats = summon.calc_stats(&template, &player.stats.cached_stats);
+        let summon_id = summon.get_object_id();
+        let kind = summon.kind;
+        self.send_packet(PetInfo::new(
+            &summon,
+            &template,
+            &owner_name,
+            &stats,
+            PetInfoType::Summoned,
+        )?)
+        .await?;
+        self.send_packet(PetStatusShow::new(kind, summon_id)?)
+            .await?;
+        if summon.is_pet() {
+            let items: Vec<_> = summon.inventory.items.values().collect();
+            self.send_packet(PetItemList::new(&items)?).await?;
+        }
+        self.controller
+            .register_summon(summon_id, owner_id, actor_ref.clone());
+        self.try_get_selected_char_mut()?.summon = Some(summon);
+        self.schedule_task(
+            PlayerTasks::Summon,
+            tokio::spawn(async move {
+                let mut interval = tokio::time::interval(AI_TICK);
+                interval.tick().await;
+                let mut ticks = 0;
+                loop {
+                    interval.tick().await;
+                    ticks += 1;
+                    let tick = DoLater {
+                        delay: Duration::ZERO,
+                        callback: Box::new(|pl: &mut PlayerClient| Box::pin(pl.summon_tick())),
+                    };
+                    if actor_ref.tell(tick).await.is_err() {
+                        return;
+                    }
+                    if ticks % FEED_TICKS == 0 {
+                        let feed = DoLater {
+                            delay: Duration::ZERO,
+                            callback: Box::new(|pl: &mut PlayerClient| {
+                                Box::pin(pl.summon_feed_tick())
+                            }),
+                        };
+                        if actor_ref.tell(feed).await.is_err() {
+                            return;
+                        }
+                    }
+                }
+            }),
+        );
+        Ok(())
+    }
+
+    /// Saves the pet as it is now, the errors are only logged.
+    pub async fn save_pet(&self) {
+        let Some(model) = self
+            .try_get_selected_char()
+            .ok()
+            .and_then(|p| p.summon.as_ref())
+            .and_then(Summon::to_pet_model)
+        else {
+            return;
+        };
+        if let Err(err) = pet::Model::save(&self.db_pool, model).await {
+            error!("Can't save the pet: {err}");
+        }
+    }
+
+    /// The summon leaves the world, a pet goes back into its collar.
+    pub async fn unsummon(&mut self) -> anyhow::Result<()> {
+        self.remove_scheduled_task(PlayerTasks::Summon);
+        self.save_pet().await;
+        let Some(summon) = self.try_get_selected_char_mut()?.summon.take() else {
+            return Ok(());
+        };
+        let summon_id = summon.get_object_id();
+        self.controller.unregister_summon(summon_id);
+        if self
+            .selected_target
+            .as_ref()
+            .is_some_and(|(id, _)| *id == summon_id)
+        {
+            self.selected_target = None;
+        }
+        self.send_packet(PetDelete::new(summon.kind, summon_id)?)
+            .await?;
+        self.controller
+            .broadcast_packet(DeleteObject::new(summon_id)?);
+        Ok(())
+    }
+
+    async fn send_summon_status(&mut self) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let Some(summon) = player.summon.as_ref() else {
+            return Ok(());
+        };
+        let template = self.summon_template(summon.npc_id)?;
+        let packet = PetStatusUpdate::new(summon, &template, player.get_visible_name())?;
+        self.send_packet(packet).await
+    }
+
+    async fn send_pet_items(&mut self) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let Some(summon) = player.summon.as_ref() else {
+            return Ok(());
+        };
+        let items: Vec<_> = summon.inventory.items.values().collect();
+        let packet = PetItemList::new(&items)?;
+        self.send_packet(packet).await
+    }
+
+    /// A second has passed: the summon follows the owner or fights.
+    pub async fn summon_tick(&mut self) -> anyhow::Result<()> {
+        let Some(intention) = self
+            .try_get_selected_char()?
+            .summon
+            .as_ref()
+            .map(|s| s.intention)
+        else {
+            return Ok(());
+        };
+        match intention {
+            SummonIntention::Idle => Ok(()),
+            SummonIntention::Follow => self.follow_owner(),
+            SummonIntention::Attack { target_id, forced } => {
+                self.summon_attack(target_id, forced).await
+            }
+        }
+    }
+
+    /// Walks the summon `range` away from the location.
+    fn move_summon_to(&mut self, to: (i32, i32, i32), range: i32) -> anyhow::Result<()> {
+        let controller = self.controller.clone();
+        let Some(summon) = self.try_get_selected_char_mut()?.summon.as_mut() else {
+            return Ok(());
+        };
+        let from = summon.get_location();
+        let from = (from.x, from.y, from.z);
+        let dist = calculate_distance(from.0, from.1, from.2, to.0, to.1, to.2).unwrap_or(0.0);
+        let (x, y, z) = calculate_nearest_hit_point(from, to, dist, range);
+        controller.broadcast_packet(CharMoveToLocation::for_summon(summon, x, y, z)?);
+        summon.set_location(x, y, z);
+        Ok(())
+    }
+
+    fn follow_owner(&mut self) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let Some(summon) = player.summon.as_ref() else {
+            return Ok(());
+        };
+        let owner = player.get_location();
+        let at = summon.get_location();
+        let dist = calculate_distance(at.x, at.y, at.z, owner.x, owner.y, owner.z).unwrap_or(0.0);
+        if dist <= f64::from(FOLLOW_DISTANCE) {
+            return Ok(());
+        }
+        let to = (owner.x, owner.y, owner.z);
+        self.move_summon_to(to, FOLLOW_OFFSET)
+    }
+
+    fn stop_summon_attack(&mut self) -> anyhow::Result<()> {
+        if let Some(summon) = self.try_get_selected_char_mut()?.summon.as_mut() {
+            summon.intention = SummonIntention::Follow;
+        }
+        Ok(())
+    }
+
+    /// Hits the target, or walks to it when it is too far. Only enemies are attacked unless the
+    /// attack is `forced`, and never in a peace zone.
+    async fn summon_attack(&mut self, target_id: i32, forced: bool) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let Some(target_actor) = self
+            .controller
+            .get_player_by_object_id(target_id)
+            .filter(|_| player.get_object_id() != target_id && !player.is_own_summon(target_id))
+        else {
+            return self.stop_summon_attack();
+        };
+        let Ok(target) = target_actor.ask(GetStats { target_id }).await else {
+            return self.stop_summon_attack();
+        };
+        if target.current_hp <= 0.0 {
+            return self.stop_summon_attack();
+        }
+        if !self.check_peace_zone(target.x, target.y, target.z).await? {
+            return self.stop_summon_attack();
+        }
+        if !forced {
+            let auto_attackable = match target_actor.ask(GetCharInfo).await {
+                Ok(target_player) => {
+                    target_player.is_auto_attackable(self.try_get_selected_char()?)
+                }
+                Err(_) => false,
+            };
+            if !auto_attackable {
+                self.stop_summon_attack()?;
+                return self.refuse_summon("That is an incorrect target.").await;
+            }
+        }
+        let player = self.try_get_selected_char()?;
+        let Some(summon) = player.summon.as_ref() else {
+            return Ok(());
+        };
+        let template = self.summon_template(summon.npc_id)?;
+        let at = *summon.get_location();
+        let dist =
+            calculate_distance(at.x, at.y, at.z, target.x, target.y, target.z).unwrap_or(f64::MAX);
+        if dist > f64::from(ATTACK_RANGE) {
+            return self.move_summon_to((target.x, target.y, target.z), ATTACK_RANGE / 2);
+        }
+        let stats = summon.calc_stats(&template, &player.stats.cached_stats);
+        let summon_id = summon.get_object_id();
+        let summon_name = summon.name.clone();
+        let miss = Formulas::calc_hit_miss(&stats, &target.stats);
+        let damage = if miss {
+            0.0
+        } else {
+            Formulas::calc_phys_dam(&stats, &target.stats, false, false)
+        };
+        if !miss {
+            target_actor
+                .tell(ApplyDamage {
+                    target_id,
+                    damage,
+                    attacker_id: summon_id,
+                    attacker_name: summon_name,
+                })
+                .await
+                .anyhow()?;
+        }
+        self.controller.broadcast_packet(to_client::Attack::new(
+            summon_id,
+            target_id,
+            damage as i32,
+            i32::from(miss),
+            at.x,
+            at.y,
+            at.z,
+            target.x,
+            target.y,
+            target.z,
+        )?);
+        Ok(())
+    }
+
+    /// A minute has passed: the summon eats, a hungry pet eats the food it carries and a pet
+    /// with nothing left to eat leaves.
+    pub async fn summon_feed_tick(&mut self) -> anyhow::Result<()> {
+        let controller = self.controller.clone();
+        let Some(summon) = self.try_get_selected_char_mut()?.summon.as_mut() else {
+            return Ok(());
+        };
+        let template = controller.summons.get(summon.npc_id).cloned();
+        let Some(template) = template else {
+            return Ok(());
+        };
+        if summon.consume_feed(&template) {
+            self.send_packet(SystemMessage::text(
+                "Your pet is starving and has left you.",
+            )?)
+            .await?;
+            return self.unsummon().await;
+        }
+        if summon.is_hungry(&template) {
+            let food = summon
+                .inventory
+                .items
+                .values()
+                .find(|i| template.food.contains(&i.item_model.item_id))
+                .map(|i| i.object_id.value());
+            let ate = match food {
+                Some(object_id) => self.pet_eat(object_id).await?,
+                None => false,
+            };
+            if !ate {
+                self.send_packet(SystemMessage::text(
+                    "Your pet is hungry. Give it some food or it will leave you.",
+                )?)
+                .await?;
+            }
+        }
+        self.send_summon_status().await
+    }
+
+    /// How much a pet of the template gets from the item, None when it doesn't eat it.
+    fn pet_food_value(&self, template: &SummonTemplate, item_id: i32) -> Option<u32> {
+        if !template.food.contains(&item_id) {
+            return None;
+        }
+        match self.controller.items_data.get_template(item_id)?.handler {
+            Some(ItemHandler::PetFood { feed }) => Some(feed),
+            _ => None,
+        }
+    }
+
+    /// The pet eats a piece of the food it carries. Returns false when it can't eat the item.
+    pub async fn pet_eat(&mut self, object_id: i32) -> anyhow::Result<bool> {
+        let player = self.try_get_selected_char()?;
+        let Some(summon) = player.summon.as_ref() else {
+            return Ok(false);
+        };
+        let template = self.summon_template(summon.npc_id)?;
+        let Some(item_id) = summon
+            .inventory
+            .items
+            .get(&object_id)
+            .map(|i| i.item_model.item_id)
+        else {
+            return Ok(false);
+        };
+        let Some(feed) = self.pet_food_value(&template, item_id) else {
+            return Ok(false);
+        };
+        let Some(summon) = self.try_get_selected_char_mut()?.summon.as_mut() else {
+            return Ok(false);
+        };
+        let eaten = summon.inventory.reduce_count(object_id, 1)?;
+        summon.eat(&template, feed);
+        item::Model::update_count(&self.db_pool, eaten.item_model.id, eaten.item_model.count)
+            .await?;
+        self.send_pet_items().await?;
+        self.send_summon_status().await?;
+        Ok(true)
+    }
+
+    /// Pet food used from the inventory of the owner.
+    pub async fn feed_pet(&mut self, item_id: i32) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let Some(summon) = player.summon.as_ref().filter(|s| s.is_pet()) else {
+            return self.refuse_summon("You have no pet to feed.").await;
+        };
+        let template = self.summon_template(summon.npc_id)?;
+        let Some(feed) = self.pet_food_value(&template, item_id) else {
+            return self.refuse_summon("Your pet doesn't eat this.").await;
+        };
+        if !self.destroy_item_by_item_id(item_id, 1).await? {
+            return Ok(());
+        }
+        if let Some(summon) = self.try_get_selected_char_mut()?.summon.as_mut() {
+            summon.eat(&template, feed);
+        }
+        self.send_summon_status().await
+    }
+
+    /// The summon takes the damage, it attacks back unless it is busy and leaves when killed.
+    /// The attacker is hit back only if it is an enemy, see [`Self::summon_attack`].
+    pub async fn damage_summon(&mut self, msg: ApplyDamage) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char_mut()?;
+        let owner_id = player.get_object_id();
+        let Some(summon) = player.summon.as_mut() else {
+            return Ok(());
+        };
+        let killed = summon.apply_damage(msg.damage);
+        if msg.attacker_id != owner_id && !summon.is_in_combat() {
+            summon.intention = SummonIntention::Attack {
+                target_id: msg.attacker_id,
+                forced: false,
+            };
+        }
+        let summon_id = summon.get_object_id();
+        let current_hp = summon.current_hp;
+        let mut status_update = to_client::StatusUpdate::new(summon_id)?;
+        status_update.add_update(to_client::StatusUpdateType::CurHp, current_hp as i32)?;
+        self.controller.broadcast_packet(status_update);
+        self.send_packet(SystemMessage::text(&format!(
+            "Your summon received {} damage from {}.",
+            msg.damage as i32, msg.attacker_name
+        ))?)
+        .await?;
+        if killed {
+            self.send_packet(SystemMessage::text("Your summon has been killed.")?)
+                .await?;
+            return self.unsummon().await;
+        }
+        self.send_summon_status().await
+    }
+
+    /// Heals the summon, no overheal.
+    pub async fn heal_summon(&mut self, msg: &ApplyHeal) -> anyhow::Result<()> {
+        let controller = self.controller.clone();
+        let Some(summon) = self.try_get_selected_char_mut()?.summon.as_mut() else {
+            return Ok(());
+        };
+        let template = controller.summons.get(summon.npc_id).cloned();
+        let Some(template) = template else {
+            return Ok(());
+        };
+        let (max_hp, max_mp) = (summon.get_max_hp(&template), summon.get_max_mp(&template));
+        let (current, max) = if msg.is_mp {
+            (&mut summon.current_mp, max_mp)
+        } else {
+            (&mut summon.current_hp, max_hp)
+        };
+        let amount = if msg.is_percent {
+            max * msg.amount / 100.0
+        } else {
+            msg.amount
+        };
+        *current = (*current + amount.max(0.0)).min(max.max(*current));
+        self.send_summon_status().await
+    }
+
+    /// Owner actions of the action bar for the summon, by the handler of the action list.
+    /// An attack ordered with `ctrl_pressed` is forced.
+    pub async fn use_summon_action(
+        &mut self,
+        handler: &str,
+        ctrl_pressed: bool,
+    ) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let is_pet_action = handler.starts_with("Pet") || handler == "UnsummonPet";
+        let Some(summon) = player
+            .summon
+            .as_ref()
+            .filter(|s| s.is_pet() == is_pet_action)
+        else {
+            return self.refuse_summon("You have no servitor or pet.").await;
+        };
+        let intention = summon.intention;
+        let owner_id = player.get_object_id();
+        let summon_id = summon.get_object_id();
+        let new_intention = match handler {
+            "PetHold" | "ServitorHold" => {
+                if intention == SummonIntention::Idle {
+                    SummonIntention::Follow
+                } else {
+                    SummonIntention::Idle
+                }
+            }
+            "PetStop" | "ServitorStop" => SummonIntention::Idle,
+            "PetAttack" | "ServitorAttack" => match &self.selected_target {
+                Some((target_id, _)) if *target_id != owner_id && *target_id != summon_id => {
+                    SummonIntention::Attack {
+                        target_id: *target_id,
+                        forced: ctrl_pressed,
+                    }
+                }
+                _ => {
+                    return self.refuse_summon("That is an incorrect target.").await;
+                }
+            },
+            "UnsummonPet" | "UnsummonServitor" => return self.unsummon().await,
+            _ => {
+                warn!("Summon action {handler} is not supported");
+                return self.send_packet(ActionFailed::normal()?).await;
+            }
+        };
+        if let Some(summon) = self.try_get_selected_char_mut()?.summon.as_mut() {
+            summon.intention = new_intention;
+        }
+        Ok(())
+    }
+
+    /// The pet takes its share of the exp the owner got, returns the exp left for the owner.
+    pub async fn share_exp_with_pet(&mut self, exp: i64) -> anyhow::Result<i64> {
+        if exp <= 0 {
+            return Ok(exp);
+        }
+        let controller = self.controller.clone();
+        let player = self.try_get_selected_char_mut()?;
+        let Some(summon) = player.summon.as_mut().filter(|s| s.is_pet()) else {
+            return Ok(exp);
+        };
+        let Some(template) = controller.summons.get(summon.npc_id) else {
+            return Ok(exp);
+        };
+        let pet_exp = exp * i64::from(template.exp_penalty) / 100;
+        if let Some(level) = summon.add_exp(template, pet_exp) {
+            self.send_packet(SystemMessage::text(&format!(
+                "Your pet has reached level {level}."
+            ))?)
+            .await?;
+            self.send_pet_info().await?;
+        } else {
+            self.send_summon_status().await?;
+        }
+        Ok(exp - pet_exp)
+    }
+
+    async fn send_pet_info(&mut self) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let Some(summon) = player.summon.as_ref() else {
+            return Ok(());
+        };
+        let template = self.summon_template(summon.npc_id)?;
+        let stats = summon.calc_stats(&template, &player.stats.cached_stats);
+        let packet = PetInfo::new(
+            summon,
+            &template,
+            player.get_visible_name(),
+            &stats,
+            PetInfoType::Update,
+        )?;
+        self.send_packet(packet).await
+    }
+
+    /// Gives `count` of the inventory item to the pet to carry.
+    pub async fn give_item_to_pet(&mut self, object_id: i32, count: i64) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let char_id = player.char_model.id;
+        let Some((collar_id, carried)) = player
+            .summon
+            .as_ref()
+            .and_then(|s| Some((s.control_item_id?, s.inventory.clone())))
+        else {
+            return self.refuse_summon("You have no pet.").await;
+        };
+        let storable: Vec<_> = self
+            .storable_items()?
+            .iter()
+            .filter(|i| i.item_model.id != collar_id)
+            .map(|i| i.object_id.value())
+            .collect();
+        let inventory = &self.try_get_selected_char()?.inventory;
+        let Some(moves) = pick_items(&inventory.items, &[(object_id, count)], |i| {
+            storable.contains(&i.object_id.value())
+        }) else {
+            warn!("Item {object_id} can't be given to the pet, possible cheating");
+            return self.send_packet(ActionFailed::normal()?).await;
+        };
+        let (planned, new_slots) = self.plan_transfer(&moves, |item_id| {
+            carried
+                .get_item_by_item_id(item_id)
+                .map(|s| s.item_model.id)
+        })?;
+        if !carried.has_room_for(new_slots) {
+            return self
+                .refuse_summon("Your pet can't carry any more items.")
+                .await;
+        }
+        let from = ItemPlace::new(char_id, LocType::Inventory);
+        let to = ItemPlace::pet(char_id, collar_id);
+        let stored = item::Model::transfer(&self.db_pool, from, to, &planned, None).await?;
+        self.take_from_inventory(&moves).await?;
+        if let Some(summon) = self.try_get_selected_char_mut()?.summon.as_mut() {
+            for model in stored {
+                summon.inventory.put_item(model);
+            }
+        }
+        self.send_pet_items().await
+    }
+
+    /// Takes `count` of the item the pet carries into the inventory.
+    pub async fn get_item_from_pet(&mut self, object_id: i32, count: i64) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let char_id = player.char_model.id;
+        let Some((collar_id, carried)) = player
+            .summon
+            .as_ref()
+            .and_then(|s| Some((s.control_item_id?, &s.inventory)))
+        else {
+            return self.refuse_summon("You have no pet.").await;
+        };
+        let Some(moves) = pick_items(&carried.items, &[(object_id, count)], |_| true) else {
+            warn!("Item {object_id} can't be taken from the pet, possible cheating");
+            return self.send_packet(ActionFailed::normal()?).await;
+        };
+        let inventory = &player.inventory;
+        let (planned, new_slots) = self.plan_transfer(&moves, |item_id| {
+            inventory
+                .get_item_by_item_id(item_id)
+                .map(|s| s.item_model.id)
+        })?;
+        if inventory.items.len() + new_slots > usize::from(inventory.get_limit()) {
+            return self.refuse_summon("Your inventory is full.").await;
+        }
+        let from = ItemPlace::pet(char_id, collar_id);
+        let to = ItemPlace::new(char_id, LocType::Inventory);
+        let taken = item::Model::transfer(&self.db_pool, from, to, &planned, None).await?;
+        if let Some(summon) = self.try_get_selected_char_mut()?.summon.as_mut() {
+            for m in &moves {
+                summon.inventory.reduce_count(m.object_id, m.count)?;
+            }
+        }
+        self.receive_items(taken).await?;
+        self.send_pet_items().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controller::GameController;
+    use crate::test_utils::test::{enter_game, get_gs_config, on_actor, spawn_player};
+    use entities::test_factories::factories::item_factory;
+    use l2_core::game_objects::stats::stat_enum::Stat;
+    use std::sync::Arc;
+    use test_utils::utils::get_test_db;
+
+    const WOLF: i32 = 12077;
+    const KAT: i32 = 14111;
+    const COLLAR: i32 = 2375;
+    const FOOD: i32 = 2515;
+
+    #[tokio::test]
+    async fn test_summon_attacks_enemies() {
+        let db_pool = get_test_db().await;
+        let controller = Arc::new(GameController::from_config(Arc::new(get_gs_config())).await);
+        let (_, owner, _conn) = enter_game(&controller, &db_pool, "Owner", &[]).await;
+        let (_, bob, _bob_conn) = enter_game(&controller, &db_pool, "Bob", &[]).await;
+        let bob_id = on_actor(&bob, |pl| {
+            Box::pin(async move {
+                let player = pl.try_get_selected_char_mut()?;
+                player.set_location(20_000, 20_000, -3000)?;
+                player.stats.current_hp = 100.0;
+                Ok(player.get_object_id())
+            })
+        })
+        .await;
+        controller.register_player_object(bob_id, bob.clone());
+        let owner_actor = owner.clone();
+        on_actor(&owner, move |pl| {
+            Box::pin(async move {
+                pl.try_get_selected_char_mut()?
+                    .set_location(20_010, 20_000, -3000)?;
+                pl.revalidate_zones().await?;
+                let template = pl.summon_template(KAT)?;
+                let servitor =
+                    Summon::servitor(&template, *pl.try_get_selected_char()?.get_location());
+                pl.put_summon(owner_actor, servitor).await?;
+                pl.selected_target = Some((bob_id, bob.clone()));
+                let intention = |pl: &PlayerClient| {
+                    pl.try_get_selected_char()
+                        .map(|p| p.summon.as_ref().unwrap().intention)
+                };
+
+                // bob is no enemy
+                pl.use_summon_action("ServitorAttack", false).await?;
+                pl.summon_tick().await?;
+                assert_eq!(intention(pl)?, SummonIntention::Follow);
+                // unless the attack is forced
+                pl.use_summon_action("ServitorAttack", true).await?;
+                pl.summon_tick().await?;
+                let forced = SummonIntention::Attack {
+                    target_id: bob_id,
+                    forced: true,
+                };
+                assert_eq!(intention(pl)?, forced);
+                // not in a town though
+                on_actor(&bob, |pl| {
+                    Box::pin(async move {
+                        pl.try_get_selected_char_mut()?
+                            .set_location(46934, 51467, -2977)
+                    })
+                })
+                .await;
+                pl.summon_tick().await?;
+                assert_eq!(intention(pl)?, SummonIntention::Follow);
+                Ok(())
+            })
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_servitor() {
+        let db_pool = get_test_db().await;
+        let (_, actor, _conn) = spawn_player(&db_pool, 20).await;
+        let owner = actor.clone();
+        on_actor(&actor, move |pl| {
+            Box::pin(async move {
+                let template = pl.summon_template(KAT)?;
+                let servitor =
+                    Summon::servitor(&template, *pl.try_get_selected_char()?.get_location());
+                pl.put_summon(owner.clone(), servitor).await?;
+                let player = pl.try_get_selected_char()?;
+                let summon_id = player.summon.as_ref().unwrap().get_object_id();
+                let owner_id = player.get_object_id();
+                assert!(pl.controller.get_player_by_object_id(summon_id).is_some());
+                assert_eq!(pl.controller.get_summon_owner(summon_id), Some(owner_id));
+                assert!(
+                    pl.controller
+                        .all_players()
+                        .iter()
+                        .all(|(id, _)| *id != summon_id)
+                );
+
+                // the servitor gets a share of the owner's attack
+                let stats = pl.get_own_stats(summon_id)?.unwrap();
+                let level = template.get_level(template.min_level());
+                let owner_atk = player
+                    .stats
+                    .cached_stats
+                    .get(&Stat::PAtk)
+                    .copied()
+                    .unwrap_or_default();
+                let expected = level.p_atk + owner_atk * f64::from(template.owner_share) / 100.0;
+                assert!((stats.stats[&Stat::PAtk] - expected).abs() < f64::EPSILON);
+
+                pl.use_summon_action("ServitorStop", false).await?;
+                let summon = pl.try_get_selected_char()?.summon.as_ref().unwrap();
+                assert_eq!(summon.intention, SummonIntention::Idle);
+                // pet actions are not for servitors
+                pl.use_summon_action("UnsummonPet", false).await?;
+                assert!(pl.try_get_selected_char()?.has_summon());
+
+                pl.damage_summon(ApplyDamage {
+                    target_id: summon_id,
+                    damage: 1.0,
+                    attacker_id: 42,
+                    attacker_name: "Orc".to_string(),
+                })
+                .await?;
+                let summon = pl.try_get_selected_char()?.summon.as_ref().unwrap();
+                assert_eq!(
+                    summon.intention,
+                    SummonIntention::Attack {
+                        target_id: 42,
+                        forced: false
+                    }
+                );
+                // the attacker is gone, back to the owner
+                pl.summon_tick().await?;
+                let summon = pl.try_get_selected_char()?.summon.as_ref().unwrap();
+                assert_eq!(summon.intention, SummonIntention::Follow);
+
+                pl.damage_summon(ApplyDamage {
+                    target_id: summon_id,
+                    damage: 1_000_000.0,
+                    attacker_id: 42,
+                    attacker_name: "Orc".to_string(),
+                })
+                .await?;
+                assert!(!pl.try_get_selected_char()?.has_summon());
+                assert!(pl.controller.get_player_by_object_id(summon_id).is_none());
+                Ok(())
+            })
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_pet() {
+        let db_pool = get_test_db().await;
+        let (char_id, actor, _conn) = spawn_player(&db_pool, 20).await;
+        let collar = item_factory(&db_pool, |mut it| {
+            it.owner = char_id;
+            it.item_id = COLLAR;
+            it.count = 1;
+            it.loc = LocType::Inventory;
+            it
+        })
+        .await;
+        let food = item_factory(&db_pool, |mut it| {
+            it.owner = char_id;
+            it.item_id = FOOD;
+            it.count = 5;
+            it.loc = LocType::Inventory;
+            it
+        })
+        .await;
+        let collar_id = collar.id;
+        let owner = actor.clone();
+        on_actor(&actor, move |pl| {
+            Box::pin(async move {
+                let player = pl.try_get_selected_char_mut()?;
+                let collar_obj = player.inventory.add_item(collar).object_id.value();
+                let food_obj = player.inventory.add_item(food).object_id.value();
+
+                // the first summon creates the pet, it appears after the cast
+                pl.use_pet_collar(owner.clone(), collar_obj).await?;
+                assert!(pl.is_casting());
+                pl.remove_scheduled_task(PlayerTasks::SummonCast);
+                assert!(pet::Model::find(&pl.db_pool, collar_id).await?.is_some());
+                let template = pl.summon_template(WOLF)?;
+                let model = pet::Model::find(&pl.db_pool, collar_id).await?.unwrap();
+                let items =
+                    item::Model::items_at(&pl.db_pool, ItemPlace::pet(char_id, collar_id)).await?;
+                let location = *pl.try_get_selected_char()?.get_location();
+                pl.put_summon(
+                    owner.clone(),
+                    Summon::pet(&template, &model, items, location),
+                )
+                .await?;
+                assert!(
+                    pl.try_get_selected_char()?
+                        .summon
+                        .as_ref()
+                        .unwrap()
+                        .is_pet()
+                );
+                // the collar stays with the owner while the pet is out
+                let storable = |pl: &PlayerClient| {
+                    pl.storable_items()
+                        .map(|items| items.iter().any(|i| i.object_id.value() == collar_obj))
+                };
+                assert!(!storable(pl)?);
+
+                // the pet carries the food and eats it when it gets hungry
+                pl.give_item_to_pet(food_obj, 2).await?;
+                let player = pl.try_get_selected_char()?;
+                assert_eq!(
+                    player
+                        .inventory
+                        .get_item_by_item_id(FOOD)
+                        .unwrap()
+                        .item_model
+                        .count,
+                    3
+                );
+                let summon = player.summon.as_ref().unwrap();
+                let carried = summon
+                    .inventory
+                    .get_item_by_item_id(FOOD)
+                    .unwrap()
+                    .object_id
+                    .value();
+                let max_feed = summon.get_max_feed(&template);
+                pl.try_get_selected_char_mut()?
+                    .summon
+                    .as_mut()
+                    .unwrap()
+                    .current_feed = 10;
+                pl.summon_feed_tick().await?;
+                let summon = pl.try_get_selected_char()?.summon.as_ref().unwrap();
+                assert_eq!(summon.current_feed, 10 - template.feed_normal + 300);
+                assert_eq!(summon.inventory.items[&carried].item_model.count, 1);
+
+                // the rest of the food is taken back
+                pl.get_item_from_pet(carried, 1).await?;
+                let player = pl.try_get_selected_char()?;
+                assert!(player.summon.as_ref().unwrap().inventory.items.is_empty());
+                assert_eq!(
+                    player
+                        .inventory
+                        .get_item_by_item_id(FOOD)
+                        .unwrap()
+                        .item_model
+                        .count,
+                    4
+                );
+                // merged into the inventory stack in DB as well
+                let place = ItemPlace::new(player.char_model.id, LocType::Inventory);
+                let saved = item::Model::items_at(&pl.db_pool, place).await?;
+                assert_eq!(saved.iter().filter(|i| i.item_id == FOOD).count(), 1);
+                pl.feed_pet(FOOD).await?;
+                let player = pl.try_get_selected_char()?;
+                assert_eq!(
+                    player.summon.as_ref().unwrap().current_feed,
+                    10 - template.feed_normal + 600
+                );
+                assert!(player.summon.as_ref().unwrap().current_feed <= max_feed);
+
+                // the pet takes its share of the exp
+                let left = pl.share_exp_with_pet(1000).await?;
+                assert_eq!(left, 1000 - i64::from(template.exp_penalty) * 10);
+
+                // the collar sends it back, it is saved
+                pl.use_pet_collar(owner.clone(), collar_obj).await?;
+                assert!(!pl.try_get_selected_char()?.has_summon());
+                let model = pet::Model::find(&pl.db_pool, collar_id).await?.unwrap();
+                assert_eq!(model.fed, 10 - template.feed_normal + 600);
+                assert!(storable(pl)?);
+                assert!(
+                    item::Model::items_at(&pl.db_pool, ItemPlace::pet(char_id, collar_id))
+                        .await?
+                        .is_empty()
+                );
+                Ok(())
+            })
+        })
+        .await;
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert!(!player.has_summon());
+    }
+}
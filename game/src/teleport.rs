@@ -0,0 +1,349 @@
+//! Teleports.
+//!
+//! Every kind of teleport (gatekeepers, bookmarks, scrolls of escape) ends up in
+//! [`PlayerClient::teleport_to`]. The player disappears for everyone else and shows the
+//! loading screen, once the client has loaded the destination it sends `Appearing`
+//! and the player is spawned again.
+
+use crate::packets::to_client;
+use crate::packets::to_client::extended::BookmarkInfo;
+use crate::pl_client::{DoLater, PlayerClient, PlayerTasks};
+use entities::entities::{character, teleport_bookmark};
+use kameo::actor::ActorRef;
+use l2_core::game_objects::zone::ZoneId;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// "My Teleport Scroll", consumed by each bookmark teleport
+pub const MY_TELEPORT_SCROLL_ID: i32 = 13016;
+
+impl PlayerClient {
+    /// Moves the player to the location, `z` is corrected by geodata.
+    pub async fn teleport_to(&mut self, x: i32, y: i32, z: i32) -> anyhow::Result<()> {
+        self.stop_movement();
+        self.remove_scheduled_task(PlayerTasks::ActionIntent);
+        self.remove_scheduled_task(PlayerTasks::CauseDamage);
+        self.remove_scheduled_task(PlayerTasks::SealOfRuler);
+        self.remove_scheduled_task(PlayerTasks::SummonCast);
+        self.interrupt_escape();
+        self.end_fishing(false).await?;
+        self.selected_target = None;
+        self.selected_monster = None;
+        let z = self.controller.geo_engine.get_nearest_z(x, y, z);
+        let user_name = self.try_get_user()?.username.clone();
+        let player = self.try_get_selected_char_mut()?;
+        player.set_teleporting(true);
+        let obj_id = player.get_object_id();
+        let heading = player.get_location().heading;
+        info!("Teleporting {} to ({x}, {y}, {z})", player.char_model.name);
+        self.controller.broadcast_packet_with_filter(
+            to_client::DeleteObject::new(obj_id)?,
+            Some(Box::new(move |acc, _| !acc.eq(&user_name))),
+        );
+        self.send_packet(to_client::TeleportToLocation::new(
+            obj_id, x, y, z, heading,
+        )?)
+        .await?;
+        self.try_get_selected_char_mut()?.set_location(x, y, z)?;
+        self.revalidate_zones().await
+    }
+
+    /// Restart point of the town closest to the player.
+    pub fn nearest_town_location(&self) -> anyhow::Result<[i32; 3]> {
+        let player = self.try_get_selected_char()?;
+        self.controller
+            .zone_manager
+            .data
+            .nearest_town_spawn(player.get_x(), player.get_y())
+            .ok_or_else(|| anyhow::anyhow!("No town restart points loaded"))
+    }
+
+    pub async fn teleport_to_nearest_town(&mut self) -> anyhow::Result<()> {
+        let [x, y, z] = self.nearest_town_location()?;
+        self.teleport_to(x, y, z).await
+    }
+
+    /// Casts the scroll of escape. The scroll is used up and the player teleported when the
+    /// cast is finished, damage or movement before that break the cast and keep the scroll.
+    pub async fn escape_to_town(
+        &mut self,
+        actor_ref: ActorRef<Self>,
+        scroll_object_id: i32,
+        skill_id: i32,
+        cast_time_ms: u32,
+    ) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let obj_id = player.get_object_id();
+        let (x, y, z) = (player.get_x(), player.get_y(), player.get_z());
+        self.stop_movement();
+        self.controller
+            .broadcast_packet(to_client::MagicSkillUse::new(
+                obj_id,
+                obj_id,
+                skill_id,
+                1,
+                i32::try_from(cast_time_ms)?,
+                0,
+                0,
+                x,
+                y,
+                z,
+                x,
+                y,
+                z,
+            )?);
+        let delay = Duration::from_millis(u64::from(cast_time_ms));
+        self.schedule_task(
+            PlayerTasks::Escape,
+            tokio::spawn(async move {
+                tokio::time::sleep(delay).await;
+                let _ = actor_ref
+                    .tell(DoLater {
+                        delay: Duration::ZERO,
+                        callback: Box::new(move |actor: &mut PlayerClient| {
+                            Box::pin(actor.finish_escape(scroll_object_id))
+                        }),
+                    })
+                    .await;
+            }),
+        );
+        Ok(())
+    }
+
+    /// The escape cast is over: the scroll is used up and the player goes to town.
+    async fn finish_escape(&mut self, scroll_object_id: i32) -> anyhow::Result<()> {
+        if self.take_scheduled_task(PlayerTasks::Escape).is_none() {
+            // the cast was broken in the meantime
+            return Ok(());
+        }
+        if self.destroy_item(scroll_object_id, 1).await? {
+            self.teleport_to_nearest_town().await?;
+        }
+        Ok(())
+    }
+
+    /// Breaks the escape cast, returns `true` when the player was casting it.
+    pub fn interrupt_escape(&mut self) -> bool {
+        self.take_scheduled_task(PlayerTasks::Escape)
+            .map(|(task, _)| task.abort())
+            .is_some()
+    }
+
+    /// Teleport offered by a gatekeeper NPC, the player pays the location price.
+    pub async fn teleport_by_list(&mut self, npc_id: i32, location_id: i32) -> anyhow::Result<()> {
+        let Some(location) = self
+            .controller
+            .teleports
+            .get_location(npc_id, location_id)
+            .cloned()
+        else {
+            warn!("No teleport {location_id} for npc {npc_id}, possible cheating");
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        };
+        if self.reduce_adena(location.price).await? {
+            self.teleport_to(location.x, location.y, location.z).await?;
+        }
+        Ok(())
+    }
+
+    /// Bookmarks the current location and saves it.
+    pub async fn save_teleport_bookmark(
+        &mut self,
+        name: String,
+        icon: i32,
+        tag: String,
+    ) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char_mut()?;
+        let char_id = player.char_model.id;
+        let Some(bookmark) = player.add_teleport_bookmark(name, icon, tag) else {
+            return self
+                .send_packet(to_client::SystemMessage::new(
+                    to_client::SystemMessageType::YouHaveNoSpaceToSaveTheTeleportLocation,
+                )?)
+                .await;
+        };
+        let model = bookmark.to_model(char_id);
+        teleport_bookmark::Model::save(&self.db_pool, model).await?;
+        self.send_bookmark_info().await
+    }
+
+    pub async fn modify_teleport_bookmark(
+        &mut self,
+        id: i32,
+        name: String,
+        icon: i32,
+        tag: String,
+    ) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char_mut()?;
+        let char_id = player.char_model.id;
+        let Some(bookmark) = player.modify_teleport_bookmark(id, name, icon, tag) else {
+            warn!("Bookmark {id} not found");
+            return Ok(());
+        };
+        let model = bookmark.to_model(char_id);
+        teleport_bookmark::Model::save(&self.db_pool, model).await?;
+        self.send_bookmark_info().await
+    }
+
+    pub async fn delete_teleport_bookmark(&mut self, id: i32) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char_mut()?;
+        let char_id = player.char_model.id;
+        if player.remove_teleport_bookmark(id) {
+            teleport_bookmark::Model::delete(&self.db_pool, char_id, id).await?;
+        }
+        self.send_bookmark_info().await
+    }
+
+    /// Adds bookmark slots (teleport spellbooks), returns false when the limit is reached.
+    pub async fn increase_bookmark_slot(&mut self, count: i16) -> anyhow::Result<bool> {
+        let player = self.try_get_selected_char_mut()?;
+        if !player.increase_bookmark_slot(count) {
+            self.send_packet(to_client::SystemMessage::new(
+                to_client::SystemMessageType::YourNumberOfMyTeleportsSlotsHasReachedItsMaximumLimit,
+            )?)
+            .await?;
+            return Ok(false);
+        }
+        let char_model = player.char_model.clone();
+        character::Model::update_char(&self.db_pool, &char_model).await?;
+        self.send_bookmark_info().await?;
+        Ok(true)
+    }
+
+    pub async fn teleport_to_bookmark(&mut self, id: i32) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let Some(bookmark) = player.get_teleport_bookmark(id).cloned() else {
+            warn!("Bookmark {id} not found");
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        };
+        let msg = if player.is_in_combat() {
+            Some(to_client::SystemMessageType::YouCannotUseMyTeleportsDuringABattle)
+        } else if player.is_inside_zone(ZoneId::Siege) {
+            Some(
+                to_client::SystemMessageType::YouCannotUseMyTeleportsWhileParticipatingInALargeScaleBattle,
+            )
+        } else if player.is_inside_zone(ZoneId::NoBookmark)
+            || self.controller.zone_manager.is_inside_zone(
+                ZoneId::NoBookmark,
+                bookmark.x,
+                bookmark.y,
+                bookmark.z,
+            )
+        {
+            Some(to_client::SystemMessageType::YouCannotUseMyTeleportsInThisArea)
+        } else {
+            None
+        };
+        if let Some(msg) = msg {
+            return self.send_packet(to_client::SystemMessage::new(msg)?).await;
+        }
+        if !self
+            .destroy_item_by_item_id(MY_TELEPORT_SCROLL_ID, 1)
+            .await?
+        {
+            return self
+                .send_packet(to_client::SystemMessage::new(
+                    to_client::SystemMessageType::YouCannotTeleportBecauseYouDoNotHaveATeleportItem,
+                )?)
+                .await;
+        }
+        self.teleport_to(bookmark.x, bookmark.y, bookmark.z).await?;
+        self.send_bookmark_info().await
+    }
+
+    async fn send_bookmark_info(&mut self) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        self.send_packet(BookmarkInfo::new(player)?).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pl_client::ApplyDamage;
+    use crate::test_utils::test::{on_actor, spawn_player};
+    use sea_orm::EntityTrait;
+    use test_utils::utils::get_test_db;
+
+    const SCROLL_OF_ESCAPE: i32 = 736;
+
+    fn scroll_count(pl: &PlayerClient) -> i64 {
+        pl.try_get_selected_char()
+            .unwrap()
+            .inventory
+            .get_item_by_item_id(SCROLL_OF_ESCAPE)
+            .map_or(0, |i| i.item_model.count)
+    }
+
+    #[tokio::test]
+    async fn test_escape_to_town() {
+        let db_pool = get_test_db().await;
+        let (_, actor, _conn) = spawn_player(&db_pool, 20).await;
+        let owner = actor.clone();
+        let scroll = on_actor(&actor, move |pl| {
+            Box::pin(async move {
+                pl.add_item_by_item_id(SCROLL_OF_ESCAPE, 2).await?;
+                let scroll = pl
+                    .try_get_selected_char()?
+                    .inventory
+                    .get_item_by_item_id(SCROLL_OF_ESCAPE)
+                    .map(|i| i.object_id.value())
+                    .unwrap();
+                pl.escape_to_town(owner, scroll, 2013, 20_000).await?;
+                assert!(pl.is_casting());
+                Ok(scroll)
+            })
+        })
+        .await;
+        let player_id = on_actor(&actor, |pl| {
+            Box::pin(async move { Ok(pl.try_get_selected_char()?.get_object_id()) })
+        })
+        .await;
+        actor
+            .ask(ApplyDamage {
+                target_id: player_id,
+                damage: 1.0,
+                attacker_id: 0,
+                attacker_name: String::new(),
+            })
+            .await
+            .unwrap();
+        let owner = actor.clone();
+        on_actor(&actor, move |pl| {
+            Box::pin(async move {
+                // the broken cast keeps the scroll
+                assert!(!pl.is_casting());
+                pl.finish_escape(scroll).await?;
+                assert_eq!(scroll_count(pl), 2);
+
+                pl.escape_to_town(owner, scroll, 2013, 20_000).await?;
+                pl.finish_escape(scroll).await?;
+                assert_eq!(scroll_count(pl), 1);
+                let [x, y, _] = pl.nearest_town_location()?;
+                let player = pl.try_get_selected_char()?;
+                assert_eq!((player.get_x(), player.get_y()), (x, y));
+                Ok(())
+            })
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_bookmark_slots_are_saved() {
+        let db_pool = get_test_db().await;
+        let (char_id, actor, _conn) = spawn_player(&db_pool, 20).await;
+        let slots = on_actor(&actor, |pl| {
+            Box::pin(async move {
+                assert!(pl.increase_bookmark_slot(3).await?);
+                Ok(pl.try_get_selected_char()?.char_model.bookmark_slot)
+            })
+        })
+        .await;
+        let saved = character::Entity::find_by_id(char_id)
+            .one(&db_pool)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(saved.bookmark_slot, slots);
+    }
+}
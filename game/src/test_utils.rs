@@ -1,16 +1,22 @@
 #[cfg(test)]
 pub mod test {
-    use crate::controller::GameController;
+    use crate::bypass::{DialogNpc, NpcCommand};
+    use crate::controller::{GameController, InGameChar};
     use crate::ls_client::LoginServerClient;
-    use crate::pl_client::PlayerClient;
+    use crate::pl_client::{DoLater, GetCharInfo, PlayerClient};
     use entities::DBPool;
+    use entities::dao::item::LocType;
+    use entities::test_factories::factories::{char_factory, item_factory, user_factory};
     use kameo::actor::{ActorRef, Spawn};
     use l2_core::config::gs::GSServerConfig;
+    use l2_core::game_objects::player::Player;
     use l2_core::traits::ServerConfig;
     use std::net::Ipv4Addr;
     use std::path::PathBuf;
     use std::sync::{Arc, OnceLock};
-    use tokio::io::{DuplexStream, ReadHalf, WriteHalf};
+    use std::time::Duration;
+    use tokio::io::{DuplexStream, ReadHalf, WriteHalf, split};
+    use tokio::sync::oneshot;
     static CONFIG: OnceLock<GSServerConfig> = OnceLock::new();
     pub fn get_gs_config() -> GSServerConfig {
         CONFIG
@@ -79,4 +85,176 @@ pub mod test {
     ) -> ActorRef<PlayerClient> {
         spawn_custom_player_client_actor(lc, db, r, w, None).await
     }
+
+    /// Spawns an actor for a new char of the level, already selected and ready to play
+    pub async fn spawn_player(
+        db_pool: &DBPool,
+        level: u8,
+    ) -> (i32, ActorRef<PlayerClient>, DuplexStream) {
+        let controller = Arc::new(GameController::from_config(Arc::new(get_gs_config())).await);
+        let user = user_factory(db_pool, |u| u).await;
+        let char = char_factory(db_pool, |mut c| {
+            c.user_id = user.id;
+            c.level = level;
+            c.exp = controller.exp_table.get_exp(level);
+            c
+        })
+        .await;
+        let template = controller
+            .class_templates
+            .try_get_template(char.class_id)
+            .unwrap();
+        let player = Player::new(char.clone(), vec![], template.clone(), None);
+        let mut pl_client =
+            PlayerClient::new(Ipv4Addr::LOCALHOST, controller.clone(), db_pool.clone());
+        pl_client.set_user(user);
+        pl_client.set_account_chars(vec![player]);
+        pl_client.select_char(0);
+        let (client, server) = tokio::io::duplex(65536);
+        let (r, w) = split(server);
+        let actor =
+            spawn_custom_player_client_actor(controller, db_pool.clone(), r, w, Some(pl_client))
+                .await;
+        (char.id, actor, client)
+    }
+
+    /// Spawns an actor for a new char with the name and `(item id, count)` inventory items,
+    /// registered as in game on the controller
+    pub async fn enter_game(
+        controller: &Arc<GameController>,
+        db_pool: &DBPool,
+        name: &str,
+        inventory: &[(i32, i64)],
+    ) -> (i32, ActorRef<PlayerClient>, DuplexStream) {
+        let user = user_factory(db_pool, |mut u| {
+            u.username = name.to_lowercase();
+            u
+        })
+        .await;
+        let char = char_factory(db_pool, |mut c| {
+            c.user_id = user.id;
+            c.name = name.to_string();
+            c
+        })
+        .await;
+        let mut items = Vec::with_capacity(inventory.len());
+        for &(item_id, count) in inventory {
+            items.push(
+                item_factory(db_pool, |mut i| {
+                    i.owner = char.id;
+                    i.item_id = item_id;
+                    i.count = count;
+                    i.loc = LocType::Inventory;
+                    i
+                })
+                .await,
+            );
+        }
+        let template = controller
+            .class_templates
+            .try_get_template(char.class_id)
+            .unwrap();
+        let player = Player::new(char.clone(), items, template.clone(), None);
+        let object_id = player.get_object_id();
+        let mut pl_client =
+            PlayerClient::new(Ipv4Addr::LOCALHOST, controller.clone(), db_pool.clone());
+        pl_client.set_user(user.clone());
+        pl_client.set_account_chars(vec![player]);
+        pl_client.select_char(0);
+        let (client, server) = tokio::io::duplex(65536);
+        let (r, w) = split(server);
+        let actor = spawn_custom_player_client_actor(
+            controller.clone(),
+            db_pool.clone(),
+            r,
+            w,
+            Some(pl_client),
+        )
+        .await;
+        controller.enter_game(
+            char.id,
+            InGameChar {
+                account: user.username,
+                name: char.name,
+                object_id,
+                actor: actor.clone(),
+            },
+        );
+        (char.id, actor, client)
+    }
+
+    /// Runs the engine call inside the actor and waits for its result
+    pub async fn on_actor<T: Send + 'static>(
+        actor: &ActorRef<PlayerClient>,
+        f: impl for<'a> FnOnce(
+            &'a mut PlayerClient,
+        )
+            -> std::pin::Pin<Box<dyn Future<Output = anyhow::Result<T>> + Send + 'a>>
+        + Send
+        + 'static,
+    ) -> T {
+        let (tx, rx) = oneshot::channel();
+        actor
+            .tell(DoLater {
+                delay: Duration::ZERO,
+                callback: Box::new(move |pl: &mut PlayerClient| {
+                    Box::pin(async move {
+                        let result = f(pl).await?;
+                        let _ = tx.send(result);
+                        Ok(())
+                    })
+                }),
+            })
+            .await
+            .unwrap();
+        rx.await.unwrap()
+    }
+
+    /// The npc a test talks to
+    pub fn npc(npc_id: i32) -> DialogNpc {
+        DialogNpc {
+            object_id: 1_000_000,
+            npc_id,
+            name: "Npc".to_string(),
+        }
+    }
+
+    /// Runs the service of the npc like its bypass does, returns the player after it
+    pub async fn npc_command(
+        actor: &ActorRef<PlayerClient>,
+        npc_id: i32,
+        command: NpcCommand,
+    ) -> Player {
+        on_actor(actor, move |pl| {
+            Box::pin(pl.handle_npc_command(npc(npc_id), command))
+        })
+        .await;
+        actor.ask(GetCharInfo).await.unwrap()
+    }
+
+    pub async fn get_controller(actor: &ActorRef<PlayerClient>) -> Arc<GameController> {
+        on_actor(actor, |pl| {
+            let controller = pl.controller.clone();
+            Box::pin(async move { Ok(controller) })
+        })
+        .await
+    }
+
+    /// Waits until the actor ran the work deferred to it so far with [`PlayerClient::do_later`]
+    pub async fn settle(actor: &ActorRef<PlayerClient>) {
+        let (tx, rx) = oneshot::channel();
+        PlayerClient::do_later(
+            actor.clone(),
+            DoLater {
+                delay: Duration::ZERO,
+                callback: Box::new(move |_: &mut PlayerClient| {
+                    Box::pin(async move {
+                        let _ = tx.send(());
+                        Ok(())
+                    })
+                }),
+            },
+        );
+        rx.await.unwrap();
+    }
 }
@@ -0,0 +1,171 @@
+//! Vitality.
+//!
+//! While vitality is enabled and a player has vitality points left, the exp and sp of killed
+//! monsters are multiplied by the configured rate and the exp spends some of the points.
+//! The points are refilled once a week and the vitality items a player may use are counted
+//! per day, both resets are checked when the player enters the world and by the
+//! [`VitalityManager`](crate::managers::VitalityManager) while the player is online.
+//!
+//! The server spawns no monsters of its own yet, so the exp of a kill is handed over with
+//! [`PlayerClient::reward_kill_exp_and_sp`] by whoever handles the monster.
+
+use crate::packets::to_client::extended::VitalityInfo;
+use crate::packets::to_client::{SystemMessage, UserInfo};
+use crate::pl_client::PlayerClient;
+use entities::entities::character;
+use l2_core::bitmask::BitMask;
+use l2_core::game_objects::player::user_info::UserInfoType;
+use l2_core::game_objects::player::{MAX_VITALITY_POINTS, vitality_consumption};
+
+impl PlayerClient {
+    /// Rewards the exp and sp of a killed monster of the level, with the vitality bonus, the
+    /// recommendation bonus (exp only) and the premium rates.
+    pub async fn reward_kill_exp_and_sp(
+        &mut self,
+        monster_level: u8,
+        exp: i64,
+        sp: i64,
+    ) -> anyhow::Result<()> {
+        let config = self.controller.get_cfg();
+        let player = self.try_get_selected_char_mut()?;
+        let bonus = player.get_vitality_bonus(&config.rates);
+        let rates = &config.rates;
+        let reward_exp = player.apply_premium_rate(
+            player.apply_recommendation_bonus(player.apply_vitality_bonus(exp, rates)),
+            rates.premium_exp_multiplier,
+        );
+        let reward_sp = player.apply_premium_rate(
+            player.apply_vitality_bonus(sp, rates),
+            rates.premium_sp_multiplier,
+        );
+        if bonus > 0 {
+            let spent = vitality_consumption(exp, player.char_model.level, monster_level);
+            player.add_vitality_points(-i64::from(spent));
+        }
+        let bonus_changed = player.get_vitality_bonus(&config.rates) != bonus;
+        self.reward_exp_and_sp(reward_exp, reward_sp).await?;
+        if bonus_changed {
+            self.send_vitality_info().await?;
+        }
+        Ok(())
+    }
+
+    /// Restores vitality points with an item, returns false if the player may not use it now.
+    pub async fn use_vitality_item(&mut self, points: u32) -> anyhow::Result<bool> {
+        let config = self.controller.get_cfg();
+        let player = self.try_get_selected_char_mut()?;
+        let refusal = if player.get_vitality_points() >= MAX_VITALITY_POINTS {
+            Some("Your vitality is already full.")
+        } else if !player.use_vitality_item(config.vitality_max_items_allowed) {
+            Some("You have used all the vitality items allowed for today.")
+        } else {
+            None
+        };
+        if let Some(text) = refusal {
+            self.send_packet(SystemMessage::text(text)?).await?;
+            return Ok(false);
+        }
+        player.add_vitality_points(i64::from(points));
+        let char_model = player.char_model.clone();
+        character::Model::update_char(&self.db_pool, &char_model).await?;
+        self.send_vitality_info().await?;
+        Ok(true)
+    }
+
+    /// Applies the vitality resets due since the player was last checked.
+    pub async fn refresh_vitality(&mut self) -> anyhow::Result<()> {
+        let now = self.controller.clock.now();
+        let player = self.try_get_selected_char_mut()?;
+        if !player.refresh_vitality(now) {
+            return Ok(());
+        }
+        let char_model = player.char_model.clone();
+        character::Model::update_char(&self.db_pool, &char_model).await?;
+        self.send_vitality_info().await
+    }
+
+    async fn send_vitality_info(&mut self) -> anyhow::Result<()> {
+        let controller = self.controller.clone();
+        let player = self.try_get_selected_char()?;
+        let mut flags = BitMask::new(24);
+        flags.add_mask(UserInfoType::VitaFame);
+        let user_info = UserInfo::new(player, flags, &controller).await?;
+        let vitality_info = VitalityInfo::new(player, &controller.get_cfg())?;
+        self.send_packet(user_info).await?;
+        self.send_packet(vitality_info).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::controller::GameController;
+    use crate::pl_client::GetCharInfo;
+    use crate::test_utils::test::{get_gs_config, on_actor, spawn_player};
+    use chrono::TimeDelta;
+    use l2_core::game_objects::player::MAX_VITALITY_POINTS;
+    use std::sync::Arc;
+    use test_utils::utils::get_test_db;
+
+    #[tokio::test]
+    async fn test_vitality_bonus_and_items() {
+        let db_pool = get_test_db().await;
+        let (_, actor, _conn) = spawn_player(&db_pool, 10).await;
+        let mut cfg = get_gs_config();
+        cfg.rates.enable_vitality = true;
+        cfg.rates.vitality_exp_multiplier = 2;
+        cfg.vitality_max_items_allowed = 1;
+        let controller = Arc::new(GameController::from_config(Arc::new(cfg)).await);
+        on_actor(&actor, move |pl| {
+            Box::pin(async move {
+                pl.controller = controller;
+                pl.refresh_vitality().await
+            })
+        })
+        .await;
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert_eq!(player.get_vitality_points(), MAX_VITALITY_POINTS);
+        let exp = player.char_model.exp;
+        // full vitality refuses the item
+        assert!(!on_actor(&actor, |pl| Box::pin(pl.use_vitality_item(10_000))).await);
+
+        // the bonus doubles the exp, the points pay for the exp of the monster
+        on_actor(&actor, |pl| {
+            Box::pin(pl.reward_kill_exp_and_sp(10, 5000, 10))
+        })
+        .await;
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert_eq!(player.char_model.exp, exp + 10_000);
+        assert_eq!(player.get_vitality_points(), MAX_VITALITY_POINTS - 5);
+
+        // without points there is no bonus, the second item of the day is refused
+        on_actor(&actor, |pl| {
+            Box::pin(async move {
+                pl.try_get_selected_char_mut()?.set_vitality_points(0);
+                Ok(())
+            })
+        })
+        .await;
+        on_actor(&actor, |pl| {
+            Box::pin(pl.reward_kill_exp_and_sp(10, 5000, 10))
+        })
+        .await;
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert_eq!(player.char_model.exp, exp + 15_000);
+        assert!(on_actor(&actor, |pl| Box::pin(pl.use_vitality_item(10_000))).await);
+        assert!(!on_actor(&actor, |pl| Box::pin(pl.use_vitality_item(10_000))).await);
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert_eq!(player.get_vitality_points(), 10_000);
+        assert_eq!(player.get_vitality_used(), 1);
+
+        // the next day the items may be used again
+        on_actor(&actor, |pl| {
+            Box::pin(async move {
+                pl.controller.clock.advance(TimeDelta::days(1));
+                pl.refresh_vitality().await
+            })
+        })
+        .await;
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert_eq!(player.get_vitality_used(), 0);
+    }
+}
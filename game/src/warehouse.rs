@@ -0,0 +1,797 @@
+//! Warehouses.
+//!
+//! Warehouse keepers store the items of the player (private warehouse), of the clan (clan
+//! warehouse, shared by the members) and deliver freight sent by the other chars of the
+//! account. The stored items stay in the item table with the `loc` of their warehouse, see
+//! [`ItemPlace`]. Items are moved only for the window the player opened from an npc dialog.
+
+use crate::packets::to_client;
+use crate::packets::to_client::extended::InventoryAdenaInfo;
+use crate::packets::to_client::{
+    ItemChange, PackageSendableList, PackageToList, SystemMessage, SystemMessageType,
+    WareHouseDepositList, WareHouseWithdrawalList,
+};
+use crate::pl_client::PlayerClient;
+use entities::dao::item::{ItemPlace, LocType};
+use entities::entities::item;
+use l2_core::game_objects::item::ItemObject;
+use l2_core::game_objects::player::clan::ClanPrivilege;
+use l2_core::game_objects::player::inventory::Inventory;
+use l2_core::game_objects::player::warehouse::{Warehouse, WarehouseKind};
+use std::collections::HashMap;
+use tracing::warn;
+
+/// Adena paid for every item put into the private warehouse
+pub const PRIVATE_DEPOSIT_FEE: i64 = 30;
+/// Adena paid for every item sent as freight
+pub const FREIGHT_FEE: i64 = 1000;
+
+/// The warehouse window the player opened from an npc dialog.
+#[derive(Debug, Clone)]
+pub enum OpenWarehouse {
+    Private,
+    /// The clan warehouse as it was when the window was opened
+    Clan(Warehouse),
+    /// Freight delivered to the char
+    Freight(Warehouse),
+    /// Freight to another char of the account, `None` until the char is picked
+    FreightTo(Option<i32>),
+}
+
+impl OpenWarehouse {
+    /// The warehouse items can be deposited to or withdrawn from
+    #[must_use]
+    pub fn kind(&self) -> Option<WarehouseKind> {
+        match self {
+            Self::Private => Some(WarehouseKind::Private),
+            Self::Clan(_) => Some(WarehouseKind::Clan),
+            Self::Freight(_) => Some(WarehouseKind::Freight),
+            Self::FreightTo(_) => None,
+        }
+    }
+}
+
+/// An item the player chose to move.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ItemMove {
+    pub(crate) object_id: i32,
+    pub(crate) db_id: i32,
+    pub(crate) item_id: i32,
+    pub(crate) count: i64,
+}
+
+/// `(item id, count, stack id)` as [`item::Model::transfer`] takes them
+pub(crate) type PlannedMove = (i32, i64, Option<i32>);
+
+/// Checks the `(object id, count)` items asked for by the client, `None` when any of them is
+/// missing, not allowed, asked for twice or there is not enough of it.
+pub(crate) fn pick_items(
+    items: &HashMap<i32, ItemObject>,
+    requested: &[(i32, i64)],
+    allowed: impl Fn(&ItemObject) -> bool,
+) -> Option<Vec<ItemMove>> {
+    let mut picked: Vec<ItemMove> = Vec::with_capacity(requested.len());
+    for &(object_id, count) in requested {
+        let item = items
+            .get(&object_id)
+            .filter(|i| allowed(i) && (1..=i.item_model.count).contains(&count))?;
+        if picked.iter().any(|m| m.object_id == object_id) {
+            return None;
+        }
+        picked.push(ItemMove {
+            object_id,
+            db_id: item.item_model.id,
+            item_id: item.item_model.item_id,
+            count,
+        });
+    }
+    Some(picked)
+}
+
+fn adena_count(moves: &[ItemMove]) -> i64 {
+    moves
+        .iter()
+        .filter(|m| m.item_id == Inventory::ADENA_ID)
+        .map(|m| m.count)
+        .sum()
+}
+
+impl PlayerClient {
+    async fn send_system_message(&mut self, message: SystemMessageType) -> anyhow::Result<()> {
+        self.send_packet(SystemMessage::new(message)?).await
+    }
+
+    /// Items of the inventory which can be stored, quest and bound items stay with the player.
+    pub(crate) fn storable_items(&self) -> anyhow::Result<Vec<&ItemObject>> {
+        let controller = &self.controller;
+        let player = self.try_get_selected_char()?;
+        Ok(player
+            .inventory
+            .items
+            .values()
+            .filter(|i| {
+                i.item_model.loc == LocType::Inventory
+                    && !controller.quests.is_quest_item(i.item_model.item_id)
+                    && !controller.is_bound_item(i.item_model.item_id)
+                    && !player.is_summoned_collar(i)
+            })
+            .collect())
+    }
+
+    /// Clan warehouse is for the members of clans of level 1 or above, taking items out needs
+    /// the warehouse right of the member's rank. Returns the clan id when it can be used.
+    async fn clan_warehouse_id(&mut self, withdraw: bool) -> anyhow::Result<Option<i32>> {
+        let player = self.try_get_selected_char()?;
+        let refusal = match &player.clan {
+            None => Some(SystemMessageType::YouAreNotAClanMemberAndCannotPerformThisAction),
+            Some(clan) if clan.level < 1 => {
+                Some(SystemMessageType::OnlyClansOfClanLevel1OrAboveCanUseAWarehouse)
+            }
+            Some(_) if withdraw && !player.has_clan_privilege(ClanPrivilege::ViewWarehouse) => {
+                Some(SystemMessageType::YouDoNotHaveTheRightToUseTheClanWarehouse)
+            }
+            Some(clan) => return Ok(Some(clan.id)),
+        };
+        if let Some(message) = refusal {
+            self.send_system_message(message).await?;
+        }
+        Ok(None)
+    }
+
+    async fn load_warehouse(&self, place: ItemPlace, limit: u32) -> anyhow::Result<Warehouse> {
+        let items = item::Model::items_at(&self.db_pool, place).await?;
+        Ok(Warehouse::from_items(items, limit))
+    }
+
+    /// Opens the deposit window of the private or the clan warehouse.
+    pub async fn show_deposit_list(&mut self, kind: WarehouseKind) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let char_id = player.char_model.id;
+        let clan_limit = player.get_clan_warehouse_max_limit();
+        let (open, warehouse) = match kind {
+            WarehouseKind::Private => (OpenWarehouse::Private, player.warehouse.clone()),
+            WarehouseKind::Clan => {
+                let Some(clan_id) = self.clan_warehouse_id(false).await? else {
+                    return Ok(());
+                };
+                let place = ItemPlace::clan_warehouse(char_id, clan_id);
+                let warehouse = self.load_warehouse(place, clan_limit).await?;
+                (OpenWarehouse::Clan(warehouse.clone()), warehouse)
+            }
+            WarehouseKind::Freight => {
+                warn!("Freight is sent with the package lists");
+                return self.send_packet(to_client::ActionFailed::normal()?).await;
+            }
+        };
+        let items_data = &self.controller.items_data;
+        let items = self.storable_items()?;
+        let mut stackable: Vec<_> = items
+            .iter()
+            .map(|i| i.item_model.item_id)
+            .filter(|id| items_data.get_template(*id).is_some_and(|t| t.stackable))
+            .collect();
+        stackable.dedup();
+        let adena = self.try_get_selected_char()?.inventory.get_adena();
+        let summary = WareHouseDepositList::summary(kind, adena, warehouse.get_size(), &stackable)?;
+        let list = WareHouseDepositList::items(&items)?;
+        self.open_warehouse = Some(open);
+        self.send_packet(summary).await?;
+        self.send_packet(list).await
+    }
+
+    /// Opens the withdraw window of the private or the clan warehouse, or the freight.
+    pub async fn show_withdraw_list(&mut self, kind: WarehouseKind) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let char_id = player.char_model.id;
+        let clan_limit = player.get_clan_warehouse_max_limit();
+        let freight_limit = player.get_freight_slots();
+        let (open, warehouse) = match kind {
+            WarehouseKind::Private => (OpenWarehouse::Private, player.warehouse.clone()),
+            WarehouseKind::Clan => {
+                let Some(clan_id) = self.clan_warehouse_id(true).await? else {
+                    return Ok(());
+                };
+                let place = ItemPlace::clan_warehouse(char_id, clan_id);
+                let warehouse = self.load_warehouse(place, clan_limit).await?;
+                (OpenWarehouse::Clan(warehouse.clone()), warehouse)
+            }
+            WarehouseKind::Freight => {
+                let place = ItemPlace::new(char_id, LocType::Freight);
+                let warehouse = self.load_warehouse(place, freight_limit).await?;
+                (OpenWarehouse::Freight(warehouse.clone()), warehouse)
+            }
+        };
+        let inventory = &self.try_get_selected_char()?.inventory;
+        let items: Vec<_> = warehouse.items.values().collect();
+        let summary = WareHouseWithdrawalList::summary(
+            kind,
+            inventory.get_adena(),
+            inventory.items.len(),
+            items.len(),
+        )?;
+        let list = WareHouseWithdrawalList::items(inventory.items.len(), &items)?;
+        self.open_warehouse = Some(open);
+        self.send_packet(summary).await?;
+        self.send_packet(list).await
+    }
+
+    /// Plans the moves for [`item::Model::transfer`]: stackable items go to the stack the destination already has. Also returns the number
+    /// of new slots the items take.
+    pub(crate) fn plan_transfer(
+        &self,
+        moves: &[ItemMove],
+        find_stack: impl Fn(i32) -> Option<i32>,
+    ) -> anyhow::Result<(Vec<PlannedMove>, usize)> {
+        let mut planned = Vec::with_capacity(moves.len());
+        let mut new_slots = 0;
+        for m in moves {
+            let stackable = self
+                .controller
+                .items_data
+                .try_get_template(m.item_id)?
+                .stackable;
+            let stack = if stackable {
+                find_stack(m.item_id)
+            } else {
+                None
+            };
+            if stack.is_none() {
+                new_slots += 1;
+            }
+            planned.push((m.db_id, m.count, stack));
+        }
+        Ok((planned, new_slots))
+    }
+
+    /// Removes the moved items from the inventory, they are already moved in DB.
+    pub(crate) async fn take_from_inventory(&mut self, moves: &[ItemMove]) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char_mut()?;
+        let mut updated = Vec::with_capacity(moves.len());
+        for m in moves {
+            updated.push(player.inventory.reduce_count(m.object_id, m.count)?);
+        }
+        let changes: Vec<_> = updated
+            .iter()
+            .map(|i| {
+                if i.item_model.count > 0 {
+                    (ItemChange::Modified, i)
+                } else {
+                    (ItemChange::Removed, i)
+                }
+            })
+            .collect();
+        self.send_packet(to_client::InventoryUpdate::new(&changes)?)
+            .await?;
+        if adena_count(moves) > 0 {
+            let player = self.try_get_selected_char()?;
+            self.send_packet(InventoryAdenaInfo::new(player)?).await?;
+        }
+        Ok(())
+    }
+
+    /// The adena the fee is paid with, `Some(None)` when there is no fee. When the player
+    /// doesn't have the adena for the fee besides the adena being moved, sends
+    /// "not enough adena" and returns `None`.
+    async fn fee_move(
+        &mut self,
+        fee: i64,
+        moves: &[ItemMove],
+    ) -> anyhow::Result<Option<Option<ItemMove>>> {
+        if fee <= 0 {
+            return Ok(Some(None));
+        }
+        let inventory = &self.try_get_selected_char()?.inventory;
+        let adena = inventory.get_item_by_item_id(Inventory::ADENA_ID);
+        if let Some(adena) = adena
+            && fee + adena_count(moves) <= adena.item_model.count
+        {
+            return Ok(Some(Some(ItemMove {
+                object_id: adena.object_id.value(),
+                db_id: adena.item_model.id,
+                item_id: Inventory::ADENA_ID,
+                count: fee,
+            })));
+        }
+        self.send_system_message(SystemMessageType::YouDoNotHaveEnoughAdena)
+            .await?;
+        Ok(None)
+    }
+
+    /// Takes the fee from the inventory adena, it is already paid in DB.
+    async fn take_fee_from_inventory(&mut self, fee: Option<ItemMove>) -> anyhow::Result<()> {
+        match fee {
+            Some(fee) => self.take_from_inventory(&[fee]).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Puts `(object id, count)` inventory items into the open warehouse.
+    pub async fn deposit_items(&mut self, items: &[(i32, i64)]) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let char_id = player.char_model.id;
+        let clan_limit = player.get_clan_warehouse_max_limit();
+        let kind = self.open_warehouse.as_ref().and_then(OpenWarehouse::kind);
+        let (place, warehouse) = match kind {
+            Some(WarehouseKind::Private) => (
+                ItemPlace::new(char_id, LocType::Warehouse),
+                player.warehouse.clone(),
+            ),
+            Some(WarehouseKind::Clan) => {
+                let Some(clan_id) = self.clan_warehouse_id(false).await? else {
+                    return Ok(());
+                };
+                // other members may have changed it since the window was opened
+                let place = ItemPlace::clan_warehouse(char_id, clan_id);
+                (place, self.load_warehouse(place, clan_limit).await?)
+            }
+            _ => {
+                warn!("No warehouse to deposit to, possible cheating");
+                return self.send_packet(to_client::ActionFailed::normal()?).await;
+            }
+        };
+        let storable: Vec<_> = self
+            .storable_items()?
+            .iter()
+            .map(|i| i.object_id.value())
+            .collect();
+        let inventory = &self.try_get_selected_char()?.inventory;
+        let Some(moves) = pick_items(&inventory.items, items, |i| {
+            storable.contains(&i.object_id.value())
+        }) else {
+            warn!("Items {items:?} can't be deposited, possible cheating");
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        };
+        let (planned, new_slots) = self.plan_transfer(&moves, |item_id| {
+            warehouse
+                .get_item_by_item_id(item_id)
+                .map(|s| s.item_model.id)
+        })?;
+        if !warehouse.has_room_for(new_slots) {
+            return self
+                .send_system_message(SystemMessageType::YourWarehouseIsFull)
+                .await;
+        }
+        let fee = if kind == Some(WarehouseKind::Private) {
+            PRIVATE_DEPOSIT_FEE * i64::try_from(moves.len())?
+        } else {
+            0
+        };
+        let Some(fee) = self.fee_move(fee, &moves).await? else {
+            return Ok(());
+        };
+        let from = ItemPlace::new(char_id, LocType::Inventory);
+        let paid = fee.map(|f| (f.db_id, f.count));
+        let stored = item::Model::transfer(&self.db_pool, from, place, &planned, paid).await?;
+        self.take_from_inventory(&moves).await?;
+        self.take_fee_from_inventory(fee).await?;
+        if let Some(OpenWarehouse::Clan(shown)) = &mut self.open_warehouse {
+            for model in stored {
+                shown.put_item(model);
+            }
+        } else {
+            let player = self.try_get_selected_char_mut()?;
+            for model in stored {
+                player.warehouse.put_item(model);
+            }
+        }
+        Ok(())
+    }
+
+    /// Takes `(object id, count)` items out of the open warehouse into the inventory.
+    pub async fn withdraw_items(&mut self, items: &[(i32, i64)]) -> anyhow::Result<()> {
+        let player = self.try_get_selected_char()?;
+        let char_id = player.char_model.id;
+        let (place, warehouse) = match self.open_warehouse.clone() {
+            Some(OpenWarehouse::Private) => (
+                ItemPlace::new(char_id, LocType::Warehouse),
+                player.warehouse.clone(),
+            ),
+            Some(OpenWarehouse::Clan(shown)) => {
+                let Some(clan_id) = self.clan_warehouse_id(true).await? else {
+                    return Ok(());
+                };
+                (ItemPlace::clan_warehouse(char_id, clan_id), shown)
+            }
+            Some(OpenWarehouse::Freight(shown)) => {
+                (ItemPlace::new(char_id, LocType::Freight), shown)
+            }
+            _ => {
+                warn!("No warehouse to withdraw from, possible cheating");
+                return self.send_packet(to_client::ActionFailed::normal()?).await;
+            }
+        };
+        let Some(moves) = pick_items(&warehouse.items, items, |_| true) else {
+            warn!("Items {items:?} can't be withdrawn, possible cheating");
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        };
+        let inventory = &self.try_get_selected_char()?.inventory;
+        let (planned, new_slots) = self.plan_transfer(&moves, |item_id| {
+            inventory
+                .get_item_by_item_id(item_id)
+                .map(|s| s.item_model.id)
+        })?;
+        if inventory.items.len() + new_slots > usize::from(inventory.get_limit()) {
+            return self
+                .send_system_message(SystemMessageType::YourInventoryIsFull)
+                .await;
+        }
+        let to = ItemPlace::new(char_id, LocType::Inventory);
+        let taken = item::Model::transfer(&self.db_pool, place, to, &planned, None).await?;
+        if let Some(OpenWarehouse::Clan(shown) | OpenWarehouse::Freight(shown)) =
+            &mut self.open_warehouse
+        {
+            for m in &moves {
+                shown.reduce_count(m.object_id, m.count)?;
+            }
+        } else {
+            let player = self.try_get_selected_char_mut()?;
+            for m in &moves {
+                player.warehouse.reduce_count(m.object_id, m.count)?;
+            }
+        }
+        self.receive_items(taken).await
+    }
+
+    /// Lists the other chars of the account freight can be sent to.
+    pub async fn show_package_to_list(&mut self) -> anyhow::Result<()> {
+        let char_id = self.try_get_selected_char()?.char_model.id;
+        let chars: Vec<_> = self
+            .try_get_account_chars()?
+            .iter()
+            .filter(|p| p.char_model.id != char_id)
+            .map(|p| (p.char_model.id, p.char_model.name.as_str()))
+            .collect();
+        let packet = PackageToList::new(&chars)?;
+        self.open_warehouse = Some(OpenWarehouse::FreightTo(None));
+        self.send_packet(packet).await
+    }
+
+    /// Chars freight can be sent to: the other chars of the account.
+    fn is_freight_target(&self, target_id: i32) -> anyhow::Result<bool> {
+        let char_id = self.try_get_selected_char()?.char_model.id;
+        Ok(target_id != char_id
+            && self
+                .try_get_account_chars()?
+                .iter()
+                .any(|p| p.char_model.id == target_id))
+    }
+
+    /// The char picked from the package list, sends the items which can be sent to it.
+    pub async fn show_package_sendable_list(&mut self, target_id: i32) -> anyhow::Result<()> {
+        if !matches!(self.open_warehouse, Some(OpenWarehouse::FreightTo(_)))
+            || !self.is_freight_target(target_id)?
+        {
+            warn!("Freight can't be sent to {target_id}, possible cheating");
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        }
+        let items = self.storable_items()?;
+        let adena = self.try_get_selected_char()?.inventory.get_adena();
+        let summary = PackageSendableList::summary(target_id, adena, items.len())?;
+        let list = PackageSendableList::items(&items)?;
+        self.open_warehouse = Some(OpenWarehouse::FreightTo(Some(target_id)));
+        self.send_packet(summary).await?;
+        self.send_packet(list).await
+    }
+
+    /// Sends `(object id, count)` inventory items to the freight of another char of the account.
+    pub async fn send_package(
+        &mut self,
+        target_id: i32,
+        items: &[(i32, i64)],
+    ) -> anyhow::Result<()> {
+        if !matches!(self.open_warehouse, Some(OpenWarehouse::FreightTo(Some(id))) if id == target_id)
+        {
+            warn!("Freight to {target_id} was not opened, possible cheating");
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        }
+        let char_id = self.try_get_selected_char()?.char_model.id;
+        let Some(target) = self
+            .try_get_account_chars()?
+            .iter()
+            .find(|p| p.char_model.id == target_id)
+        else {
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        };
+        let place = ItemPlace::new(target_id, LocType::Freight);
+        let freight = self
+            .load_warehouse(place, target.get_freight_slots())
+            .await?;
+        let storable: Vec<_> = self
+            .storable_items()?
+            .iter()
+            .map(|i| i.object_id.value())
+            .collect();
+        let inventory = &self.try_get_selected_char()?.inventory;
+        let Some(moves) = pick_items(&inventory.items, items, |i| {
+            storable.contains(&i.object_id.value())
+        }) else {
+            warn!("Items {items:?} can't be sent, possible cheating");
+            return self.send_packet(to_client::ActionFailed::normal()?).await;
+        };
+        let (planned, new_slots) = self.plan_transfer(&moves, |item_id| {
+            freight
+                .get_item_by_item_id(item_id)
+                .map(|s| s.item_model.id)
+        })?;
+        if !freight.has_room_for(new_slots) {
+            return self
+                .send_system_message(SystemMessageType::YourWarehouseIsFull)
+                .await;
+        }
+        let fee = FREIGHT_FEE * i64::try_from(moves.len())?;
+        let Some(fee) = self.fee_move(fee, &moves).await? else {
+            return Ok(());
+        };
+        let from = ItemPlace::new(char_id, LocType::Inventory);
+        let paid = fee.map(|f| (f.db_id, f.count));
+        item::Model::transfer(&self.db_pool, from, place, &planned, paid).await?;
+        self.take_from_inventory(&moves).await?;
+        self.take_fee_from_inventory(fee).await?;
+        self.open_warehouse = None;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pl_client::GetCharInfo;
+    use crate::test_utils::test::{on_actor, spawn_player};
+    use entities::entities::clan_ally;
+    use entities::test_factories::factories::char_factory;
+    use kameo::actor::ActorRef;
+    use l2_core::game_objects::player::Player;
+    use test_utils::utils::get_test_db;
+
+    fn count(player: &Player, item_id: i32) -> i64 {
+        player
+            .inventory
+            .get_items_by_item_id(item_id)
+            .map(|i| i.item_model.count)
+            .sum()
+    }
+
+    fn object_id(player: &Player, item_id: i32) -> i32 {
+        player
+            .inventory
+            .get_item_by_item_id(item_id)
+            .unwrap()
+            .object_id
+            .value()
+    }
+
+    async fn give(actor: &ActorRef<PlayerClient>, items: Vec<(i32, i64, i32)>) {
+        on_actor(actor, move |pl| {
+            Box::pin(async move { pl.exchange_items(&[], &items).await })
+        })
+        .await;
+    }
+
+    async fn deposit(actor: &ActorRef<PlayerClient>, items: Vec<(i32, i64)>) {
+        on_actor(actor, move |pl| {
+            Box::pin(async move { pl.deposit_items(&items).await })
+        })
+        .await;
+    }
+
+    async fn withdraw(actor: &ActorRef<PlayerClient>, items: Vec<(i32, i64)>) {
+        on_actor(actor, move |pl| {
+            Box::pin(async move { pl.withdraw_items(&items).await })
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_private_warehouse() {
+        let db_pool = get_test_db().await;
+        let (char_id, actor, _conn) = spawn_player(&db_pool, 1).await;
+        give(&actor, vec![(Inventory::ADENA_ID, 10_000, 0), (1, 1, 0)]).await;
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        let adena = object_id(&player, Inventory::ADENA_ID);
+        let sword = object_id(&player, 1);
+
+        // the window is not open
+        deposit(&actor, vec![(sword, 1)]).await;
+        assert_eq!(count(&actor.ask(GetCharInfo).await.unwrap(), 1), 1);
+
+        on_actor(&actor, |pl| {
+            Box::pin(pl.show_deposit_list(WarehouseKind::Private))
+        })
+        .await;
+        deposit(&actor, vec![(adena, 1000), (sword, 1)]).await;
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert_eq!(count(&player, 1), 0);
+        assert_eq!(count(&player, Inventory::ADENA_ID), 10_000 - 1000 - 60);
+        assert_eq!(player.warehouse.get_size(), 2);
+
+        // adena go to the stored stack
+        deposit(&actor, vec![(adena, 500)]).await;
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert_eq!(player.warehouse.get_size(), 2);
+        let stored_adena = player.warehouse.get_item_by_item_id(Inventory::ADENA_ID);
+        assert_eq!(stored_adena.unwrap().item_model.count, 1500);
+        let place = ItemPlace::new(char_id, LocType::Warehouse);
+        let saved = item::Model::items_at(&db_pool, place).await.unwrap();
+        assert_eq!(saved.len(), 2);
+
+        on_actor(&actor, |pl| {
+            Box::pin(pl.show_withdraw_list(WarehouseKind::Private))
+        })
+        .await;
+        let stored: Vec<_> = player
+            .warehouse
+            .items
+            .values()
+            .map(|i| (i.object_id.value(), i.item_model.count))
+            .collect();
+        // more than stored
+        let too_many: Vec<_> = stored.iter().map(|&(id, c)| (id, c + 1)).collect();
+        withdraw(&actor, too_many).await;
+        assert_eq!(
+            actor.ask(GetCharInfo).await.unwrap().warehouse.get_size(),
+            2
+        );
+        withdraw(&actor, stored).await;
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert_eq!(player.warehouse.get_size(), 0);
+        assert_eq!(count(&player, 1), 1);
+        assert_eq!(count(&player, Inventory::ADENA_ID), 10_000 - 90);
+        assert!(
+            item::Model::items_at(&db_pool, place)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        // the withdrawn adena are merged into the inventory stack
+        let inventory = ItemPlace::new(char_id, LocType::Inventory);
+        let saved = item::Model::items_at(&db_pool, inventory).await.unwrap();
+        let adena_rows: Vec<_> = saved
+            .iter()
+            .filter(|i| i.item_id == Inventory::ADENA_ID)
+            .collect();
+        assert_eq!(adena_rows.len(), 1);
+        assert_eq!(adena_rows[0].count, 10_000 - 90);
+        assert_eq!(
+            player
+                .inventory
+                .get_items_by_item_id(Inventory::ADENA_ID)
+                .count(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_clan_warehouse_rights() {
+        let db_pool = get_test_db().await;
+        let (_, actor, _conn) = spawn_player(&db_pool, 1).await;
+        give(&actor, vec![(1, 1, 0)]).await;
+        let sword = object_id(&actor.ask(GetCharInfo).await.unwrap(), 1);
+        let is_open = |actor: &ActorRef<PlayerClient>| {
+            let actor = actor.clone();
+            async move {
+                on_actor(&actor, |pl| {
+                    Box::pin(async move { Ok(pl.open_warehouse.is_some()) })
+                })
+                .await
+            }
+        };
+
+        // not in a clan
+        on_actor(&actor, |pl| {
+            Box::pin(pl.show_deposit_list(WarehouseKind::Clan))
+        })
+        .await;
+        assert!(!is_open(&actor).await);
+
+        // level 0 clan
+        on_actor(&actor, |pl| {
+            Box::pin(async move {
+                pl.try_get_selected_char_mut()?.clan = Some(clan_ally::Model {
+                    id: 5,
+                    level: 0,
+                    ..Default::default()
+                });
+                pl.show_deposit_list(WarehouseKind::Clan).await
+            })
+        })
+        .await;
+        assert!(!is_open(&actor).await);
+
+        on_actor(&actor, |pl| {
+            Box::pin(async move {
+                if let Some(clan) = &mut pl.try_get_selected_char_mut()?.clan {
+                    clan.level = 1;
+                }
+                pl.show_deposit_list(WarehouseKind::Clan).await
+            })
+        })
+        .await;
+        deposit(&actor, vec![(sword, 1)]).await;
+        assert_eq!(count(&actor.ask(GetCharInfo).await.unwrap(), 1), 0);
+        let clan_items = item::Model::items_at(&db_pool, ItemPlace::clan_warehouse(0, 5))
+            .await
+            .unwrap();
+        assert_eq!(clan_items.len(), 1);
+
+        // every member can deposit, but the rank needs the right to withdraw
+        on_actor(&actor, |pl| {
+            Box::pin(async move {
+                pl.open_warehouse = None;
+                pl.show_withdraw_list(WarehouseKind::Clan).await
+            })
+        })
+        .await;
+        assert!(!is_open(&actor).await);
+        let stored = on_actor(&actor, |pl| {
+            Box::pin(async move {
+                pl.try_get_selected_char_mut()?.char_model.clan_privs =
+                    Some(ClanPrivilege::ViewWarehouse as i32);
+                pl.show_withdraw_list(WarehouseKind::Clan).await?;
+                let Some(OpenWarehouse::Clan(shown)) = &pl.open_warehouse else {
+                    anyhow::bail!("Clan warehouse is not open");
+                };
+                Ok(shown.items.keys().map(|id| (*id, 1)).collect::<Vec<_>>())
+            })
+        })
+        .await;
+        withdraw(&actor, stored).await;
+        assert_eq!(count(&actor.ask(GetCharInfo).await.unwrap(), 1), 1);
+    }
+
+    #[tokio::test]
+    async fn test_freight_to_account_chars() {
+        let db_pool = get_test_db().await;
+        let (char_id, actor, _conn) = spawn_player(&db_pool, 1).await;
+        give(&actor, vec![(Inventory::ADENA_ID, 5000, 0)]).await;
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        let adena = object_id(&player, Inventory::ADENA_ID);
+        let user_id = player.char_model.user_id;
+        let other = char_factory(&db_pool, |mut c| {
+            c.user_id = user_id;
+            c.name = "Second".to_string();
+            c
+        })
+        .await;
+        let other_id = other.id;
+        on_actor(&actor, move |pl| {
+            Box::pin(async move {
+                let template = pl
+                    .controller
+                    .class_templates
+                    .try_get_template(other.class_id)?;
+                pl.add_character(Player::new(other, vec![], template.clone(), None))
+            })
+        })
+        .await;
+
+        on_actor(&actor, |pl| Box::pin(pl.show_package_to_list())).await;
+        // own char can't be picked
+        on_actor(&actor, move |pl| {
+            Box::pin(pl.show_package_sendable_list(char_id))
+        })
+        .await;
+        on_actor(&actor, move |pl| {
+            Box::pin(async move { pl.send_package(char_id, &[(adena, 100)]).await })
+        })
+        .await;
+        assert_eq!(count(&actor.ask(GetCharInfo).await.unwrap(), 57), 5000);
+
+        on_actor(&actor, move |pl| {
+            Box::pin(pl.show_package_sendable_list(other_id))
+        })
+        .await;
+        on_actor(&actor, move |pl| {
+            Box::pin(async move { pl.send_package(other_id, &[(adena, 1500)]).await })
+        })
+        .await;
+        let player = actor.ask(GetCharInfo).await.unwrap();
+        assert_eq!(
+            count(&player, Inventory::ADENA_ID),
+            5000 - 1500 - FREIGHT_FEE
+        );
+        let place = ItemPlace::new(other_id, LocType::Freight);
+        let freight = item::Model::items_at(&db_pool, place).await.unwrap();
+        assert_eq!(freight.len(), 1);
+        assert_eq!(freight[0].count, 1500);
+    }
+}
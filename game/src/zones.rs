@@ -0,0 +1,81 @@
+//! Zone tracking.
+//!
+//! Zone flags of a player are recalculated from the zone data every time the position
+//! changes (movement ticks, position validation). Entering or leaving a zone updates the
+//! compass, switches swim speeds in water, puts riders down on a battlefield and is what
+//! peace/PvP zone rules rely on.
+//! Quests waiting for the player to reach a zone are checked on every revalidation.
+
+use crate::packets::to_client;
+use crate::packets::to_client::extended::SetCompasZoneCode;
+use crate::pl_client::PlayerClient;
+use l2_core::bitmask::BitMask;
+use l2_core::game_objects::player::user_info::UserInfoType;
+use l2_core::game_objects::zone::ZoneId;
+use tracing::debug;
+
+impl PlayerClient {
+    /// Recalculates zone flags at the player position without notifying the client,
+    /// returns `(entered, exited)` zones.
+    pub fn update_zone_flags(&mut self) -> anyhow::Result<(Vec<ZoneId>, Vec<ZoneId>)> {
+        let controller = self.controller.clone();
+        let player = self.try_get_selected_char_mut()?;
+        let flags = controller
+            .zone_manager
+            .flags_at(player.get_x(), player.get_y(), player.get_z());
+        Ok(player.set_zones(flags))
+    }
+
+    /// Recalculates zone flags and fires enter/exit events.
+    pub async fn revalidate_zones(&mut self) -> anyhow::Result<()> {
+        let old_compass = SetCompasZoneCode::zone_code(self.try_get_selected_char()?);
+        let (entered, exited) = self.update_zone_flags()?;
+        self.check_quest_zones().await?;
+        if entered.is_empty() && exited.is_empty() {
+            return Ok(());
+        }
+        debug!("Zones entered: {entered:?}, exited: {exited:?}");
+        self.check_mount_zones(&entered).await?;
+        let player = self.try_get_selected_char()?;
+        let compass = SetCompasZoneCode::zone_code(player);
+        let speed = player.get_move_speed();
+        if compass != old_compass {
+            self.send_packet(SetCompasZoneCode::new(compass)?).await?;
+        }
+        if entered.contains(&ZoneId::Water) || exited.contains(&ZoneId::Water) {
+            self.change_movement_speed(speed);
+            let mut flags = BitMask::new(24);
+            flags.add_mask(UserInfoType::Speed);
+            flags.add_mask(UserInfoType::Movements);
+            let player = self.try_get_selected_char()?;
+            let user_info = to_client::UserInfo::new(player, flags, &self.controller).await?;
+            self.send_packet(user_info).await?;
+        }
+        Ok(())
+    }
+
+    /// Attacks are not allowed when the attacker or the target stands in a peace zone.
+    /// Notifies the player and returns `false` in that case.
+    pub async fn check_peace_zone(
+        &mut self,
+        target_x: i32,
+        target_y: i32,
+        target_z: i32,
+    ) -> anyhow::Result<bool> {
+        let msg = if self.try_get_selected_char()?.is_inside_zone(ZoneId::Peace) {
+            to_client::SystemMessageType::YouMayNotAttackInAPeacefulZone
+        } else if self.controller.zone_manager.is_inside_zone(
+            ZoneId::Peace,
+            target_x,
+            target_y,
+            target_z,
+        ) {
+            to_client::SystemMessageType::YouMayNotAttackThisTargetInAPeacefulZone
+        } else {
+            return Ok(true);
+        };
+        self.send_packet(to_client::SystemMessage::new(msg)?).await?;
+        self.send_packet(to_client::ActionFailed::normal()?).await?;
+        Ok(false)
+    }
+}
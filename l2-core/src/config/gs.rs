@@ -201,6 +201,14 @@ pub struct Client {
 pub struct Rates {
     pub vitality_exp_multiplier: u32,
     pub enable_vitality: bool,
+    #[serde(default = "default_premium_rate")]
+    pub premium_exp_multiplier: f64,
+    #[serde(default = "default_premium_rate")]
+    pub premium_sp_multiplier: f64,
+}
+
+fn default_premium_rate() -> f64 {
+    1.0
 }
 
 #[cfg(test)]
@@ -14,3 +14,10 @@ pub struct Action {
     pub handler: String,
     pub option: Option<u32>,
 }
+
+impl ActionList {
+    #[must_use]
+    pub fn get(&self, id: u32) -> Option<&Action> {
+        self.actions.iter().find(|a| a.id == id)
+    }
+}
@@ -0,0 +1,120 @@
+use crate as l2_core;
+use crate::config::traits::{LoadFileHandler, Loadable};
+use crate::data::items::ItemsData;
+use macro_common::config_dir;
+use serde::Deserialize;
+use std::collections::HashMap;
+use tracing::info;
+
+/// Price the player pays in a town with the tax rate (percent).
+#[must_use]
+pub fn price_with_tax(price: u64, tax_rate: u8) -> u64 {
+    price + price * u64::from(tax_rate) / 100
+}
+
+/// Price an npc pays for an item sold by the player, half of the reference price.
+#[must_use]
+pub fn sell_price(reference_price: u64) -> u64 {
+    reference_price / 2
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct BuyListItem {
+    pub item_id: i32,
+    /// Overrides the reference price of the item template
+    pub price: Option<u64>,
+}
+
+impl BuyListItem {
+    /// Price before taxes, `None` when the item template is unknown.
+    #[must_use]
+    pub fn get_price(&self, items: &ItemsData) -> Option<u64> {
+        self.price
+            .or_else(|| items.get_template(self.item_id).map(|t| t.price))
+    }
+}
+
+/// Items an npc merchant sells, the players can sell their items to it as well.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BuyList {
+    pub id: i32,
+    /// Merchants allowed to open the list
+    pub npc_ids: Vec<i32>,
+    pub items: Vec<BuyListItem>,
+}
+
+impl BuyList {
+    #[must_use]
+    pub fn get_item(&self, item_id: i32) -> Option<&BuyListItem> {
+        self.items.iter().find(|i| i.item_id == item_id)
+    }
+
+    #[must_use]
+    pub fn is_offered_by(&self, npc_id: i32) -> bool {
+        self.npc_ids.contains(&npc_id)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BuyListFile {
+    pub buy_lists: Vec<BuyList>,
+}
+
+#[derive(Debug, Clone, Default)]
+#[config_dir(path = "config/data/buylists", post_load)]
+pub struct BuyListData {
+    lists: HashMap<i32, BuyList>,
+}
+
+impl Loadable for BuyListData {
+    fn post_load(&self) {
+        info!("Loaded {} buy lists.", self.lists.len());
+    }
+}
+
+impl BuyListData {
+    #[must_use]
+    pub fn get_list(&self, list_id: i32) -> Option<&BuyList> {
+        self.lists.get(&list_id)
+    }
+}
+
+impl LoadFileHandler for BuyListData {
+    type TargetConfigType = BuyListFile;
+    fn for_each(&mut self, item: Self::TargetConfigType) {
+        for list in item.buy_lists {
+            if let Some(l) = self.lists.insert(list.id, list) {
+                panic!("Duplicate buy list: {:?}", l.id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::traits::ConfigDirLoader;
+    use crate::data::buy_lists::{BuyListData, price_with_tax, sell_price};
+    use crate::data::items::ItemsData;
+
+    #[test]
+    fn test_load_buy_lists() {
+        let data = BuyListData::load();
+        let list = data.get_list(1).unwrap();
+        assert!(list.is_offered_by(30001));
+        assert!(!list.is_offered_by(30006));
+        assert!(list.get_item(736).is_some());
+        assert!(list.get_item(57).is_none());
+        let items = ItemsData::load();
+        assert_eq!(list.get_item(736).unwrap().get_price(&items), Some(400));
+        assert_eq!(list.get_item(13016).unwrap().get_price(&items), Some(3000));
+    }
+
+    #[test]
+    fn test_prices() {
+        assert_eq!(price_with_tax(400, 0), 400);
+        assert_eq!(price_with_tax(400, 10), 440);
+        assert_eq!(price_with_tax(7, 10), 7);
+        assert_eq!(sell_price(768), 384);
+        assert_eq!(sell_price(1), 0);
+    }
+}
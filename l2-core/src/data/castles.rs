@@ -0,0 +1,94 @@
+use crate as l2_core;
+use crate::config::traits::{LoadFileHandler, Loadable};
+use macro_common::config_dir;
+use serde::Deserialize;
+use std::collections::HashMap;
+use tracing::info;
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+pub struct Position {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CastleTemplate {
+    pub id: i32,
+    pub name: String,
+    /// Zone activated while the siege is in progress
+    pub siege_zone: i32,
+    pub siege_manager_npc: i32,
+    pub chamberlain_npc: i32,
+    /// Npc selling the seeds and buying the crops of the castle
+    pub manor_manager_npc: i32,
+    pub artifact: Position,
+    /// Percent
+    pub max_tax_rate: i32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CastleListFile {
+    pub castles: Vec<CastleTemplate>,
+}
+
+#[derive(Debug, Clone, Default)]
+#[config_dir(path = "config/data/castles", post_load)]
+pub struct CastleData {
+    castles: HashMap<i32, CastleTemplate>,
+}
+
+impl Loadable for CastleData {
+    fn post_load(&self) {
+        info!("Loaded {} castles.", self.castles.len());
+    }
+}
+
+impl CastleData {
+    #[must_use]
+    pub fn get(&self, castle_id: i32) -> Option<&CastleTemplate> {
+        self.castles.get(&castle_id)
+    }
+
+    pub fn all(&self) -> impl Iterator<Item = &CastleTemplate> {
+        self.castles.values()
+    }
+
+    /// Castle served by the npc, as its siege manager, its chamberlain or its manor manager.
+    #[must_use]
+    pub fn by_npc(&self, npc_id: i32) -> Option<&CastleTemplate> {
+        self.castles.values().find(|c| {
+            c.siege_manager_npc == npc_id
+                || c.chamberlain_npc == npc_id
+                || c.manor_manager_npc == npc_id
+        })
+    }
+}
+
+impl LoadFileHandler for CastleData {
+    type TargetConfigType = CastleListFile;
+    fn for_each(&mut self, item: Self::TargetConfigType) {
+        for castle in item.castles {
+            if let Some(c) = self.castles.insert(castle.id, castle) {
+                panic!("Duplicate castle: {:?}", c.id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::traits::ConfigDirLoader;
+    use crate::data::castles::CastleData;
+
+    #[test]
+    fn test_load_castles() {
+        let data = CastleData::load();
+        let gludio = data.get(1).unwrap();
+        assert_eq!(gludio.name, "Gludio");
+        assert_eq!(data.by_npc(35100).unwrap().id, 1);
+        assert_eq!(data.by_npc(35104).unwrap().id, 1);
+        assert_eq!(data.by_npc(35103).unwrap().id, 1);
+        assert!(data.by_npc(30006).is_none());
+    }
+}
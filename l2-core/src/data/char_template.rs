@@ -124,7 +124,7 @@ where
 impl CharTemplate {
     /// # Errors
     /// - when something wrong with templates
-    #[allow(clippy::cast_sign_loss, clippy::similar_names)]
+    #[allow(clippy::cast_sign_loss)]
     pub fn initialize_character(
         &self,
         target: &mut character::Model,
@@ -139,14 +139,7 @@ impl CharTemplate {
         target.base_class_id = self.class_id as i8;
         target.access_level = 0;
         target.race_id = self.class_id.get_class().race as i8;
-        let base_max_hp = self.get_base_max_parameter(target.level, &CreatureParameter::HP)?;
-        let base_max_mp = self.get_base_max_parameter(target.level, &CreatureParameter::MP)?;
-        let base_max_cp = self.get_base_max_parameter(target.level, &CreatureParameter::CP)?;
-        let base_con = base_stats.con_bonus(self.static_data.base_con)?;
-        let base_men = base_stats.con_bonus(self.static_data.base_men)?;
-        target.max_hp = f64::from(base_max_hp) * base_con;
-        target.max_mp = f64::from(base_max_mp) * base_men;
-        target.max_cp = f64::from(base_max_cp) * base_con;
+        self.set_max_parameters(target, base_stats)?;
         target.cur_hp = target.max_hp;
         target.cur_mp = target.max_mp;
         target.cur_cp = target.max_cp;
@@ -163,6 +156,25 @@ impl CharTemplate {
         //todo starting adena
         Ok(())
     }
+    /// Max HP, MP and CP of the class at the level of the char.
+    /// # Errors
+    /// - when lvl is higher than we have data for it in th template.
+    #[allow(clippy::similar_names)]
+    pub fn set_max_parameters(
+        &self,
+        target: &mut character::Model,
+        base_stats: &BaseStat,
+    ) -> anyhow::Result<()> {
+        let base_max_hp = self.get_base_max_parameter(target.level, &CreatureParameter::HP)?;
+        let base_max_mp = self.get_base_max_parameter(target.level, &CreatureParameter::MP)?;
+        let base_max_cp = self.get_base_max_parameter(target.level, &CreatureParameter::CP)?;
+        let base_con = base_stats.con_bonus(self.static_data.base_con)?;
+        let base_men = base_stats.con_bonus(self.static_data.base_men)?;
+        target.max_hp = f64::from(base_max_hp) * base_con;
+        target.max_mp = f64::from(base_max_mp) * base_men;
+        target.max_cp = f64::from(base_max_cp) * base_con;
+        Ok(())
+    }
     /// # Errors
     /// - when lvl is higher than we have data for it in th template.
     pub fn get_base_max_parameter(
@@ -296,6 +296,11 @@ impl Class {
             self.get_class()
         }
     }
+    /// Profession level: 0 for a starting class, 1 after the first class change, etc.
+    #[must_use]
+    pub fn level(self) -> u8 {
+        self.get_class().parent.map_or(0, |parent| parent.level() + 1)
+    }
     #[must_use]
     #[allow(clippy::too_many_lines)]
     pub fn get_class(self) -> CharClass {
@@ -437,4 +442,11 @@ mod test {
             let _ = cls.get_class();
         }
     }
+
+    #[test]
+    fn test_class_level() {
+        assert_eq!(Class::Fighter.level(), 0);
+        assert_eq!(Class::Warrior.level(), 1);
+        assert_eq!(Class::Gladiator.level(), 2);
+    }
 }
@@ -0,0 +1,116 @@
+use crate as l2_core;
+use crate::config::traits::{LoadFileHandler, Loadable};
+use chrono::TimeDelta;
+use macro_common::config_dir;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use tracing::info;
+
+/// Drop rates are given in this many parts
+pub const DROP_RATE_BASE: u32 = 100_000;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CursedWeaponTemplate {
+    pub item_id: i32,
+    pub name: String,
+    /// Skill of the owner, its level is the stage of the weapon
+    pub skill_id: i32,
+    /// The owner turns into this
+    pub transform_id: i16,
+    /// Chance in [`DROP_RATE_BASE`] parts to drop from a killed monster
+    pub drop_rate: u32,
+    /// Minutes the weapon stays in the world after it dropped from a monster
+    pub duration: i64,
+    /// Minutes taken from the time left with every kill of the owner
+    pub duration_lost: i64,
+    /// Chance in percent the weapon disappears when the owner dies
+    pub disappear_chance: u8,
+    /// Kills needed for the next stage
+    pub stage_kills: u32,
+    pub max_stage: u8,
+}
+
+impl CursedWeaponTemplate {
+    #[must_use]
+    pub fn duration(&self) -> TimeDelta {
+        TimeDelta::minutes(self.duration)
+    }
+
+    #[must_use]
+    pub fn duration_lost(&self) -> TimeDelta {
+        TimeDelta::minutes(self.duration_lost)
+    }
+
+    /// Stage of the weapon after the kills, starting with 1.
+    #[must_use]
+    pub fn stage(&self, kills: u32) -> u8 {
+        let stage = 1 + kills / self.stage_kills.max(1);
+        u8::try_from(stage)
+            .unwrap_or(u8::MAX)
+            .min(self.max_stage.max(1))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CursedWeaponListFile {
+    pub weapons: Vec<CursedWeaponTemplate>,
+}
+
+#[derive(Debug, Clone, Default)]
+#[config_dir(path = "config/data/cursed_weapons", post_load)]
+pub struct CursedWeaponsData {
+    weapons: BTreeMap<i32, CursedWeaponTemplate>,
+}
+
+impl Loadable for CursedWeaponsData {
+    fn post_load(&self) {
+        info!("Loaded {} cursed weapons.", self.weapons.len());
+    }
+}
+
+impl CursedWeaponsData {
+    #[must_use]
+    pub fn get(&self, item_id: i32) -> Option<&CursedWeaponTemplate> {
+        self.weapons.get(&item_id)
+    }
+
+    #[must_use]
+    pub fn is_cursed(&self, item_id: i32) -> bool {
+        self.weapons.contains_key(&item_id)
+    }
+
+    /// Weapons ordered by item id
+    pub fn all(&self) -> impl Iterator<Item = &CursedWeaponTemplate> {
+        self.weapons.values()
+    }
+}
+
+impl LoadFileHandler for CursedWeaponsData {
+    type TargetConfigType = CursedWeaponListFile;
+    fn for_each(&mut self, item: Self::TargetConfigType) {
+        for weapon in item.weapons {
+            if let Some(w) = self.weapons.insert(weapon.item_id, weapon) {
+                panic!("Duplicate cursed weapon: {:?}", w.item_id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::traits::ConfigDirLoader;
+    use crate::data::cursed_weapons::CursedWeaponsData;
+
+    #[test]
+    fn test_load_cursed_weapons() {
+        let data = CursedWeaponsData::load();
+        let ids: Vec<i32> = data.all().map(|w| w.item_id).collect();
+        assert_eq!(ids, vec![8190, 8689]);
+        let zariche = data.get(8190).unwrap();
+        assert_eq!(zariche.skill_id, 3603);
+        assert_eq!(zariche.stage(0), 1);
+        assert_eq!(zariche.stage(9), 1);
+        assert_eq!(zariche.stage(10), 2);
+        assert_eq!(zariche.stage(1000), zariche.max_stage);
+    }
+}
@@ -0,0 +1,169 @@
+use crate as l2_core;
+use crate::config::traits::{LoadFileHandler, Loadable};
+use crate::data::items::{CrystalType, ItemKind, ItemTemplate};
+use macro_common::config_dir;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use tracing::info;
+
+/// What an enchant scroll enchants, weapons and armor (jewels included) have a table each.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EnchantTarget {
+    Weapon,
+    Armor,
+}
+
+impl EnchantTarget {
+    #[must_use]
+    pub fn of(kind: ItemKind) -> Option<Self> {
+        match kind {
+            ItemKind::Weapon => Some(Self::Weapon),
+            ItemKind::Armor => Some(Self::Armor),
+            ItemKind::EtcItem => None,
+        }
+    }
+}
+
+/// What happens to the item when the enchant fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EnchantFailure {
+    /// The item breaks into crystals of its grade
+    Crystallize,
+    /// The enchant level goes back to 0
+    Reset,
+    /// The item keeps its enchant level
+    Stay,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct EnchantTable {
+    pub target: EnchantTarget,
+    /// Levels up to which the enchant can't fail
+    pub safe_level: i32,
+    pub max_level: i32,
+    /// Chance in percent to enchant the item from the safe level on, by the level it has.
+    /// The last chance stands for the higher levels.
+    pub success: Vec<u8>,
+    /// Stat bonus of an enchant level by grade, it counts twice for the levels over the safe one
+    pub bonus: BTreeMap<CrystalType, f64>,
+    /// Everyone learns about an item enchanted to the level
+    pub announce_level: i32,
+}
+
+impl EnchantTable {
+    /// Chance in percent to enchant an item of the level once more.
+    #[must_use]
+    pub fn success_rate(&self, level: i32) -> u8 {
+        let Ok(index) = usize::try_from(level - self.safe_level) else {
+            return 100;
+        };
+        self.success
+            .get(index)
+            .or(self.success.last())
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Bonus of an item of the grade enchanted to the level, the levels over the safe one
+    /// count twice.
+    #[must_use]
+    pub fn stat_bonus(&self, grade: CrystalType, level: i32) -> f64 {
+        let per_level = self
+            .bonus
+            .get(&grade.enchant_grade())
+            .copied()
+            .unwrap_or(0.0);
+        let levels = level.max(0) + (level - self.safe_level).max(0);
+        per_level * f64::from(levels)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct EnchantFile {
+    pub tables: Vec<EnchantTable>,
+}
+
+#[derive(Debug, Clone, Default)]
+#[config_dir(path = "config/data/enchant", post_load)]
+pub struct EnchantData {
+    tables: HashMap<EnchantTarget, EnchantTable>,
+}
+
+impl Loadable for EnchantData {
+    fn post_load(&self) {
+        info!("Loaded {} enchant tables.", self.tables.len());
+    }
+}
+
+impl EnchantData {
+    #[must_use]
+    pub fn get(&self, target: EnchantTarget) -> Option<&EnchantTable> {
+        self.tables.get(&target)
+    }
+
+    /// The table the item is enchanted by, None when the item can't be enchanted.
+    #[must_use]
+    pub fn table_for(&self, template: &ItemTemplate) -> Option<&EnchantTable> {
+        if template.crystal_type == CrystalType::None || template.hero {
+            return None;
+        }
+        self.get(EnchantTarget::of(template.kind)?)
+    }
+}
+
+impl LoadFileHandler for EnchantData {
+    type TargetConfigType = EnchantFile;
+    fn for_each(&mut self, item: Self::TargetConfigType) {
+        for table in item.tables {
+            assert!(
+                !table.success.is_empty(),
+                "Enchant table {:?} has no success chances",
+                table.target
+            );
+            if let Some(t) = self.tables.insert(table.target, table) {
+                panic!("Duplicate enchant table: {:?}", t.target);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::traits::ConfigDirLoader;
+    use crate::data::enchant::{EnchantData, EnchantTable, EnchantTarget};
+    use crate::data::items::CrystalType;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_load_enchant_tables() {
+        let data = EnchantData::load();
+        let weapon = data.get(EnchantTarget::Weapon).unwrap();
+        assert_eq!(weapon.success_rate(0), 100);
+        assert_eq!(weapon.success_rate(weapon.safe_level - 1), 100);
+        assert!(weapon.success_rate(weapon.safe_level) < 100);
+        assert_eq!(
+            weapon.success_rate(weapon.max_level),
+            *weapon.success.last().unwrap()
+        );
+        assert!(data.get(EnchantTarget::Armor).is_some());
+    }
+
+    #[test]
+    fn test_stat_bonus() {
+        let table = EnchantTable {
+            target: EnchantTarget::Weapon,
+            safe_level: 3,
+            max_level: 16,
+            success: vec![50],
+            bonus: BTreeMap::from([(CrystalType::S, 5.0)]),
+            announce_level: 7,
+        };
+        assert!(table.stat_bonus(CrystalType::S, 0).abs() < f64::EPSILON);
+        assert!((table.stat_bonus(CrystalType::S, 3) - 15.0).abs() < f64::EPSILON);
+        // +2 over the safe level
+        assert!((table.stat_bonus(CrystalType::S84, 5) - 35.0).abs() < f64::EPSILON);
+        assert!(table.stat_bonus(CrystalType::A, 5).abs() < f64::EPSILON);
+    }
+}
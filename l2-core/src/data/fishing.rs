@@ -0,0 +1,129 @@
+use crate as l2_core;
+use crate::config::traits::{LoadFileHandler, Loadable};
+use macro_common::config_dir;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use tracing::info;
+
+/// Lures of a grade attract the fish of the same grade, the harder ones fight longer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FishGrade {
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl FishGrade {
+    /// Lure type shown by the client in the fight window
+    #[must_use]
+    pub fn lure_type(self) -> u8 {
+        self as u8
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FishTemplate {
+    /// Item the player gets for the fish
+    pub item_id: i32,
+    pub name: String,
+    pub grade: FishGrade,
+    /// Levels of the players the fish bites for
+    pub min_level: u8,
+    pub max_level: u8,
+    pub hp: u32,
+    /// HP the fish gets back every second while it fights
+    pub hp_regen: u32,
+    /// Seconds the player has to catch the fish
+    pub duration: u32,
+    /// Chance in percent the fish changes between resting and fighting every second
+    pub guts: u8,
+    /// Weight of the fish among the ones which may bite
+    pub chance: u32,
+}
+
+impl FishTemplate {
+    #[must_use]
+    pub fn bites_for(&self, grade: FishGrade, level: u8) -> bool {
+        self.grade == grade && (self.min_level..=self.max_level).contains(&level)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FishListFile {
+    pub fish: Vec<FishTemplate>,
+}
+
+#[derive(Debug, Clone, Default)]
+#[config_dir(path = "config/data/fishing", post_load)]
+pub struct FishData {
+    fish: BTreeMap<i32, FishTemplate>,
+}
+
+impl Loadable for FishData {
+    fn post_load(&self) {
+        info!("Loaded {} fish.", self.fish.len());
+    }
+}
+
+impl FishData {
+    #[must_use]
+    pub fn get(&self, item_id: i32) -> Option<&FishTemplate> {
+        self.fish.get(&item_id)
+    }
+
+    /// Sum of the chances of the fish which bite on the lure for the player.
+    #[must_use]
+    pub fn total_chance(&self, grade: FishGrade, level: u8) -> u32 {
+        self.fish
+            .values()
+            .filter(|f| f.bites_for(grade, level))
+            .map(|f| f.chance)
+            .sum()
+    }
+
+    /// The fish which bites for the roll, `roll` is in `0..total_chance(grade, level)`.
+    #[must_use]
+    pub fn pick(&self, grade: FishGrade, level: u8, mut roll: u32) -> Option<&FishTemplate> {
+        self.fish
+            .values()
+            .filter(|f| f.bites_for(grade, level))
+            .find(|f| {
+                if roll < f.chance {
+                    return true;
+                }
+                roll -= f.chance;
+                false
+            })
+    }
+}
+
+impl LoadFileHandler for FishData {
+    type TargetConfigType = FishListFile;
+    fn for_each(&mut self, item: Self::TargetConfigType) {
+        for fish in item.fish {
+            if let Some(f) = self.fish.insert(fish.item_id, fish) {
+                panic!("Duplicate fish: {:?}", f.item_id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::traits::ConfigDirLoader;
+    use crate::data::fishing::{FishData, FishGrade};
+
+    #[test]
+    fn test_load_fish() {
+        let data = FishData::load();
+        let total = data.total_chance(FishGrade::Easy, 20);
+        assert!(total > 0);
+        let first = data.pick(FishGrade::Easy, 20, 0).unwrap();
+        assert!(first.bites_for(FishGrade::Easy, 20));
+        let last = data.pick(FishGrade::Easy, 20, total - 1).unwrap();
+        assert!(last.bites_for(FishGrade::Easy, 20));
+        assert!(data.pick(FishGrade::Easy, 20, total).is_none());
+        assert_eq!(data.total_chance(FishGrade::Hard, 1), 0);
+    }
+}
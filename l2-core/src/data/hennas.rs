@@ -0,0 +1,171 @@
+use crate as l2_core;
+use crate::config::traits::{LoadFileHandler, Loadable};
+use crate::game_objects::stats::Stat;
+use macro_common::config_dir;
+use serde::Deserialize;
+use std::collections::HashMap;
+use tracing::info;
+
+/// Base stat modifiers of a dye, can be negative.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub struct HennaStats {
+    #[serde(default)]
+    pub str: i16,
+    #[serde(default)]
+    pub con: i16,
+    #[serde(default)]
+    pub dex: i16,
+    #[serde(default)]
+    pub int: i16,
+    #[serde(default)]
+    pub wit: i16,
+    #[serde(default)]
+    pub men: i16,
+}
+
+impl HennaStats {
+    /// Highest bonus all the drawn dyes can give to a single stat
+    pub const MAX_BONUS: i16 = 5;
+
+    #[must_use]
+    pub fn get(&self, stat: Stat) -> i16 {
+        match stat {
+            Stat::Str => self.str,
+            Stat::Con => self.con,
+            Stat::Dex => self.dex,
+            Stat::Int => self.int,
+            Stat::Wit => self.wit,
+            Stat::Men => self.men,
+            _ => 0,
+        }
+    }
+
+    /// Sums the modifiers of all dyes, positive totals are capped at [`Self::MAX_BONUS`].
+    #[must_use]
+    pub fn sum<'a>(all: impl IntoIterator<Item = &'a HennaStats>) -> Self {
+        let total = all.into_iter().fold(Self::default(), |acc, s| Self {
+            str: acc.str + s.str,
+            con: acc.con + s.con,
+            dex: acc.dex + s.dex,
+            int: acc.int + s.int,
+            wit: acc.wit + s.wit,
+            men: acc.men + s.men,
+        });
+        Self {
+            str: total.str.min(Self::MAX_BONUS),
+            con: total.con.min(Self::MAX_BONUS),
+            dex: total.dex.min(Self::MAX_BONUS),
+            int: total.int.min(Self::MAX_BONUS),
+            wit: total.wit.min(Self::MAX_BONUS),
+            men: total.men.min(Self::MAX_BONUS),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct HennaTemplate {
+    pub dye_id: i32,
+    pub name: String,
+    /// Dye item consumed by drawing the symbol
+    pub dye_item_id: i32,
+    #[serde(default)]
+    pub stats: HennaStats,
+    /// Amount of dye items needed to draw
+    pub wear_count: i64,
+    /// Adena paid to draw
+    pub wear_fee: i64,
+    /// Amount of dye items given back on removal
+    pub cancel_count: i64,
+    /// Adena paid to remove
+    pub cancel_fee: i64,
+    /// Classes allowed to draw the symbol
+    pub class_ids: Vec<i8>,
+}
+
+impl HennaTemplate {
+    #[must_use]
+    pub fn is_allowed_class(&self, class_id: i8) -> bool {
+        self.class_ids.contains(&class_id)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct HennaFile {
+    pub hennas: Vec<HennaTemplate>,
+}
+
+#[derive(Debug, Clone, Default)]
+#[config_dir(path = "config/data/hennas", post_load)]
+pub struct HennaData {
+    hennas: HashMap<i32, HennaTemplate>,
+}
+
+impl Loadable for HennaData {
+    fn post_load(&self) {
+        info!("Loaded {} hennas.", self.hennas.len());
+    }
+}
+
+impl HennaData {
+    #[must_use]
+    pub fn get_henna(&self, dye_id: i32) -> Option<&HennaTemplate> {
+        self.hennas.get(&dye_id)
+    }
+
+    /// Symbols the class can draw, ordered by dye id.
+    #[must_use]
+    pub fn get_allowed_hennas(&self, class_id: i8) -> Vec<&HennaTemplate> {
+        let mut hennas: Vec<_> = self
+            .hennas
+            .values()
+            .filter(|h| h.is_allowed_class(class_id))
+            .collect();
+        hennas.sort_by_key(|h| h.dye_id);
+        hennas
+    }
+}
+
+impl LoadFileHandler for HennaData {
+    type TargetConfigType = HennaFile;
+    fn for_each(&mut self, item: Self::TargetConfigType) {
+        for henna in item.hennas {
+            if let Some(h) = self.hennas.insert(henna.dye_id, henna) {
+                panic!("Duplicate henna: {:?}", h.dye_id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::traits::ConfigDirLoader;
+    use crate::data::hennas::{HennaData, HennaStats};
+
+    #[test]
+    fn test_load_hennas() {
+        let data = HennaData::load();
+        let henna = data.get_henna(1).unwrap();
+        assert_eq!(henna.dye_item_id, 4445);
+        assert_eq!(henna.stats.str, 1);
+        assert_eq!(henna.stats.con, -3);
+        // warrior can draw STR dyes but not INT ones
+        assert!(henna.is_allowed_class(1));
+        let allowed = data.get_allowed_hennas(1);
+        assert!(allowed.iter().all(|h| h.stats.int <= 0));
+        assert!(allowed.windows(2).all(|w| w[0].dye_id < w[1].dye_id));
+        // human fighter has no profession yet
+        assert!(data.get_allowed_hennas(0).is_empty());
+    }
+
+    #[test]
+    fn test_sum_is_capped() {
+        let dye = HennaStats {
+            str: 3,
+            con: -3,
+            ..HennaStats::default()
+        };
+        let total = HennaStats::sum([&dye, &dye, &dye]);
+        assert_eq!(total.str, HennaStats::MAX_BONUS);
+        assert_eq!(total.con, -9);
+    }
+}
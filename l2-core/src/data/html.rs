@@ -0,0 +1,211 @@
+use dashmap::DashMap;
+use std::env;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use tracing::warn;
+
+/// Something a dialog lets the player do, only offered actions are accepted back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HtmlAction {
+    /// `bypass [-h] <command>`, sent with `RequestBypassToServer`
+    Bypass(String),
+    /// `link <path>`, sent with `RequestLinkHtml`
+    Link(String),
+}
+
+impl HtmlAction {
+    /// Collects the actions of all links and buttons of the page.
+    #[must_use]
+    pub fn extract(html: &str) -> Vec<Self> {
+        // actions are attribute values, so they are always enclosed in quotes
+        html.split('"')
+            .filter_map(|value| {
+                let value = value.trim();
+                if let Some(cmd) = value
+                    .strip_prefix("bypass -h ")
+                    .or_else(|| value.strip_prefix("bypass "))
+                {
+                    Some(Self::Bypass(cmd.trim().to_string()))
+                } else {
+                    value
+                        .strip_prefix("link ")
+                        .map(|path| Self::Link(path.trim().to_string()))
+                }
+            })
+            .collect()
+    }
+
+    /// If the command sent by the client is this action. The command must match word by word,
+    /// input fields are sent in place of their `$name` placeholders, so such a word may be any
+    /// word (starting with the text before the placeholder). A placeholder at the end takes
+    /// the rest of the command, the input may have spaces.
+    #[must_use]
+    pub fn allows_bypass(&self, command: &str) -> bool {
+        let Self::Bypass(offered) = self else {
+            return false;
+        };
+        let offered: Vec<&str> = offered.split_whitespace().collect();
+        let sent: Vec<&str> = command.split_whitespace().collect();
+        let open_end = offered.last().is_some_and(|w| w.starts_with('$'));
+        let words_match = if open_end {
+            sent.len() >= offered.len()
+        } else {
+            sent.len() == offered.len()
+        };
+        words_match
+            && offered.iter().zip(&sent).all(|(o, s)| match o.find('$') {
+                Some(pos) => s.starts_with(&o[..pos]) && s.len() > pos,
+                None => o == s,
+            })
+    }
+}
+
+/// Escapes the text, so it is shown as is and can't add links or other markup to the page.
+#[must_use]
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Replaces `%name%` placeholders of the page with their values, the values are escaped.
+#[must_use]
+pub fn fill_html(html: &str, vars: &[(&str, &str)]) -> String {
+    vars.iter().fold(html.to_string(), |html, (name, value)| {
+        html.replace(&format!("%{name}%"), &escape_html(value))
+    })
+}
+
+/// Like [`fill_html`] for markup made by the server, e.g. lists of links, which is put into
+/// the page as is. Any text of players in it must be escaped with [`escape_html`].
+#[must_use]
+pub fn fill_html_markup(html: &str, vars: &[(&str, &str)]) -> String {
+    vars.iter().fold(html.to_string(), |html, (name, value)| {
+        html.replace(&format!("%{name}%"), value)
+    })
+}
+
+/// Dialog pages from `config/data/html`, read on first use and cached.
+#[derive(Debug, Clone)]
+pub struct HtmlCache {
+    root: PathBuf,
+    pages: Arc<DashMap<String, Arc<str>>>,
+}
+
+impl Default for HtmlCache {
+    fn default() -> Self {
+        let config_base = env::var("L2_CONFIG").unwrap_or_else(|_| "./".to_string());
+        Self::new(PathBuf::from(config_base).join("config/data/html"))
+    }
+}
+
+impl HtmlCache {
+    #[must_use]
+    pub fn new(root: PathBuf) -> Self {
+        Self {
+            root,
+            pages: Arc::new(DashMap::new()),
+        }
+    }
+
+    /// Only relative paths of `.htm` files inside the root are valid,
+    /// the client can ask for any path with `RequestLinkHtml`.
+    #[must_use]
+    pub fn is_valid_path(path: &str) -> bool {
+        let path = Path::new(path);
+        path.extension().is_some_and(|ext| ext == "htm")
+            && path.components().all(|c| matches!(c, Component::Normal(_)))
+    }
+
+    #[must_use]
+    pub fn get(&self, path: &str) -> Option<Arc<str>> {
+        if !Self::is_valid_path(path) {
+            warn!("Invalid html path: {path}");
+            return None;
+        }
+        if let Some(page) = self.pages.get(path) {
+            return Some(page.clone());
+        }
+        let page: Arc<str> = std::fs::read_to_string(self.root.join(path))
+            .ok()?
+            .into();
+        self.pages.insert(path.to_string(), page.clone());
+        Some(page)
+    }
+
+    #[must_use]
+    pub fn exists(&self, path: &str) -> bool {
+        self.get(path).is_some()
+    }
+
+    /// Drops the cached pages, they are read again on next use
+    pub fn reload(&self) {
+        self.pages.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_actions() {
+        let html = r#"<html><body>Hi<br>
+            <a action="bypass -h npc_%objectId%_Chat 1">Talk</a>
+            <button value="Go" action="bypass npc_1_Teleport $loc" width=60>
+            <a action="link default/30006-1.htm">More</a>
+            </body></html>"#;
+        let actions = HtmlAction::extract(html);
+        assert_eq!(
+            actions,
+            vec![
+                HtmlAction::Bypass("npc_%objectId%_Chat 1".to_string()),
+                HtmlAction::Bypass("npc_1_Teleport $loc".to_string()),
+                HtmlAction::Link("default/30006-1.htm".to_string()),
+            ]
+        );
+        assert!(actions[1].allows_bypass("npc_1_Teleport 3"));
+        assert!(!actions[1].allows_bypass("npc_2_Teleport 3"));
+        // the words before the input must be whole
+        assert!(!actions[1].allows_bypass("npc_1_TeleportX 3"));
+        assert!(!actions[1].allows_bypass("npc_1_Teleport"));
+        let action = HtmlAction::Bypass("npc_1_Chat 1".to_string());
+        assert!(action.allows_bypass("npc_1_Chat 1"));
+        assert!(!action.allows_bypass("npc_1_Chat 12"));
+        assert!(!action.allows_bypass("npc_1_Chat 1 2"));
+        let action = HtmlAction::Bypass("npc_1_ManorSetCrop 5 $ca5 $cp5".to_string());
+        assert!(action.allows_bypass("npc_1_ManorSetCrop 5 10 200"));
+        assert!(!action.allows_bypass("npc_1_ManorSetCrop 6 10 200"));
+        assert!(!action.allows_bypass("npc_1_ManorSetCrop 5 10"));
+        assert!(!actions[0].allows_bypass("npc_%objectId%_Chat 12"));
+        assert!(!actions[2].allows_bypass("default/30006-1.htm"));
+    }
+
+    #[test]
+    fn test_fill_html() {
+        let html = fill_html(
+            "<a action=\"bypass -h npc_%objectId%_Quest\">%name%</a>",
+            &[("objectId", "7"), ("name", "Roxxy")],
+        );
+        assert_eq!(html, "<a action=\"bypass -h npc_7_Quest\">Roxxy</a>");
+        let html = fill_html("Hi %name%", &[("name", "<a action=\"bypass x\">")]);
+        assert_eq!(html, "Hi &lt;a action=&quot;bypass x&quot;&gt;");
+        assert!(HtmlAction::extract(&html).is_empty());
+        let html = fill_html_markup("%list%", &[("list", "<br>")]);
+        assert_eq!(html, "<br>");
+    }
+
+    #[test]
+    fn test_cache() {
+        assert!(HtmlCache::is_valid_path("default/30006.htm"));
+        assert!(!HtmlCache::is_valid_path("../game.yaml"));
+        assert!(!HtmlCache::is_valid_path("/etc/passwd.htm"));
+        assert!(!HtmlCache::is_valid_path("default/30006.txt"));
+        let cache = HtmlCache::default();
+        let page = cache.get("default/30006.htm").unwrap();
+        assert!(page.contains("%objectId%"));
+        assert!(Arc::ptr_eq(&page, &cache.get("default/30006.htm").unwrap()));
+        assert!(cache.get("default/0.htm").is_none());
+    }
+}
@@ -0,0 +1,258 @@
+use crate as l2_core;
+use crate::config::traits::{LoadFileHandler, Loadable};
+use crate::data::enchant::{EnchantFailure, EnchantTarget};
+use crate::data::fishing::FishGrade;
+use crate::game_objects::item::shot::ShotType;
+use macro_common::config_dir;
+use serde::Deserialize;
+use std::collections::HashMap;
+use tracing::info;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize)]
+pub enum ItemKind {
+    Weapon,
+    Armor,
+    #[default]
+    EtcItem,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize)]
+pub enum CrystalType {
+    #[default]
+    None,
+    D,
+    C,
+    B,
+    A,
+    S,
+    S80,
+    S84,
+}
+
+impl CrystalType {
+    /// S80 and S84 items use S-grade shots.
+    #[must_use]
+    pub fn shot_grade(self) -> Self {
+        match self {
+            Self::S80 | Self::S84 => Self::S,
+            other => other,
+        }
+    }
+
+    /// S80 and S84 items take S-grade enchant scrolls.
+    #[must_use]
+    pub fn enchant_grade(self) -> Self {
+        self.shot_grade()
+    }
+
+    /// Crystals the items of the grade break into, None for items without a grade.
+    #[must_use]
+    pub fn crystal_item_id(self) -> Option<i32> {
+        match self {
+            Self::None => None,
+            Self::D => Some(1458),
+            Self::C => Some(1459),
+            Self::B => Some(1460),
+            Self::A => Some(1461),
+            Self::S | Self::S80 | Self::S84 => Some(1462),
+        }
+    }
+}
+
+/// What happens when a player uses the item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ItemHandler {
+    /// Returns the player to the nearest town after casting
+    Escape { cast_time_ms: u32 },
+    /// Adds slots for teleport bookmarks
+    BookmarkSlots { count: i16 },
+    /// Restores vitality points, limited by the vitality items a player may use
+    Vitality { points: u32 },
+    /// Calls the pet living in the collar, or sends it back
+    PetCollar,
+    /// Food for pets, the pet eats it itself when it gets hungry
+    PetFood { feed: u32 },
+    /// Registers the recipe made from the item in the recipe book
+    Recipe,
+    /// Manor seed, sown on the selected monster
+    Seed,
+    /// Harvests the crops of the seed sown on the selected killed monster
+    Harvester,
+    /// Enchants a weapon or armor of the grade, the failure tells what a failed enchant does
+    EnchantScroll {
+        target: EnchantTarget,
+        grade: CrystalType,
+        failure: EnchantFailure,
+    },
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ItemTemplate {
+    pub id: i32,
+    pub name: String,
+    #[serde(default)]
+    pub kind: ItemKind,
+    #[serde(default)]
+    pub crystal_type: CrystalType,
+    /// Crystals of its grade the item breaks into
+    #[serde(default)]
+    pub crystal_count: i64,
+    #[serde(default)]
+    pub stackable: bool,
+    #[serde(default)]
+    pub weight: u32,
+    #[serde(default)]
+    pub price: u64,
+    /// Soulshots consumed by the weapon per attack
+    #[serde(default)]
+    pub soulshots: u32,
+    /// Spiritshots consumed by the weapon per cast
+    #[serde(default)]
+    pub spiritshots: u32,
+    /// Set when the item is a soulshot/spiritshot
+    pub shot: Option<ShotType>,
+    pub skill_id: Option<i32>,
+    pub handler: Option<ItemHandler>,
+    /// Hero weapons, only heroes may have them
+    #[serde(default)]
+    pub hero: bool,
+    /// Fishing rods, the player needs one in hand to fish
+    #[serde(default)]
+    pub fishing_rod: bool,
+    /// Set when the item is a lure, the fish of its grade bite on it
+    pub lure: Option<FishGrade>,
+}
+
+impl ItemTemplate {
+    #[must_use]
+    pub fn is_weapon(&self) -> bool {
+        self.kind == ItemKind::Weapon
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ItemTemplateList {
+    pub items: Vec<ItemTemplate>,
+}
+
+#[derive(Debug, Clone, Default)]
+#[config_dir(path = "config/data/items", post_load)]
+pub struct ItemsData {
+    templates: HashMap<i32, ItemTemplate>,
+}
+
+impl Loadable for ItemsData {
+    fn post_load(&self) {
+        info!("Loaded {} item templates.", self.templates.len());
+    }
+}
+
+impl ItemsData {
+    #[must_use]
+    pub fn get_template(&self, item_id: i32) -> Option<&ItemTemplate> {
+        self.templates.get(&item_id)
+    }
+
+    /// Templates of the hero weapons ordered by id
+    #[must_use]
+    pub fn hero_items(&self) -> Vec<&ItemTemplate> {
+        let mut items: Vec<&ItemTemplate> = self.templates.values().filter(|t| t.hero).collect();
+        items.sort_by_key(|t| t.id);
+        items
+    }
+
+    /// # Errors
+    /// - when template is not found
+    pub fn try_get_template(&self, item_id: i32) -> anyhow::Result<&ItemTemplate> {
+        self.get_template(item_id)
+            .ok_or_else(|| anyhow::anyhow!("Item template {item_id} not found"))
+    }
+}
+
+impl LoadFileHandler for ItemsData {
+    type TargetConfigType = ItemTemplateList;
+    fn for_each(&mut self, item: Self::TargetConfigType) {
+        for template in item.items {
+            if let Some(i) = self.templates.insert(template.id, template) {
+                panic!("Duplicate item template id: {:?}", i.id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_shot_template() {
+        let list: ItemTemplateList = serde_yaml::from_str(
+            r"
+items:
+  - { id: 1463, name: Soulshot (D-Grade), crystal_type: D, stackable: true, shot: soulshots, skill_id: 2150 }
+  - { id: 127, name: Crimson Sword, kind: Weapon, crystal_type: D, soulshots: 2, spiritshots: 2 }
+  - { id: 6611, name: Infinity Blade, kind: Weapon, crystal_type: S, soulshots: 1, spiritshots: 1, hero: true }
+  - { id: 736, name: Scroll of Escape, stackable: true, skill_id: 2013, handler: { type: escape, cast_time_ms: 20000 } }
+  - { id: 6529, name: Baby Duck Rod, kind: Weapon, fishing_rod: true }
+  - { id: 6520, name: Lure, stackable: true, lure: normal }
+  - { id: 2375, name: Wolf Collar, handler: { type: pet_collar } }
+  - { id: 2515, name: Food For Wolves, stackable: true, handler: { type: pet_food, feed: 300 } }
+  - { id: 6577, name: Blessed Scroll, handler: { type: enchant_scroll, target: weapon, grade: S, failure: stay } }
+",
+        )
+        .unwrap();
+        let mut data = ItemsData::default();
+        data.for_each(list);
+        let shot = data.try_get_template(1463).unwrap();
+        assert_eq!(shot.shot, Some(ShotType::Soulshots));
+        assert_eq!(shot.kind, ItemKind::EtcItem);
+        assert_eq!(shot.skill_id, Some(2150));
+        let weapon = data.try_get_template(127).unwrap();
+        assert!(weapon.is_weapon());
+        assert_eq!(weapon.crystal_type, CrystalType::D);
+        assert_eq!(weapon.soulshots, 2);
+        assert!(!weapon.hero);
+        assert!(data.try_get_template(6611).unwrap().hero);
+        assert_eq!(data.hero_items().len(), 1);
+        assert!(data.get_template(1).is_none());
+        assert!(data.try_get_template(6529).unwrap().fishing_rod);
+        assert!(!weapon.fishing_rod);
+        assert_eq!(
+            data.try_get_template(6520).unwrap().lure,
+            Some(FishGrade::Normal)
+        );
+        assert_eq!(shot.lure, None);
+        assert_eq!(
+            data.try_get_template(736).unwrap().handler,
+            Some(ItemHandler::Escape {
+                cast_time_ms: 20000
+            })
+        );
+        assert_eq!(
+            data.try_get_template(2375).unwrap().handler,
+            Some(ItemHandler::PetCollar)
+        );
+        assert_eq!(
+            data.try_get_template(2515).unwrap().handler,
+            Some(ItemHandler::PetFood { feed: 300 })
+        );
+        assert_eq!(
+            data.try_get_template(6577).unwrap().handler,
+            Some(ItemHandler::EnchantScroll {
+                target: EnchantTarget::Weapon,
+                grade: CrystalType::S,
+                failure: EnchantFailure::Stay,
+            })
+        );
+    }
+
+    #[test]
+    fn test_shot_grade() {
+        assert_eq!(CrystalType::S84.shot_grade(), CrystalType::S);
+        assert_eq!(CrystalType::S80.shot_grade(), CrystalType::S);
+        assert_eq!(CrystalType::B.shot_grade(), CrystalType::B);
+        assert_eq!(CrystalType::S80.crystal_item_id(), Some(1462));
+        assert_eq!(CrystalType::None.crystal_item_id(), None);
+    }
+}
@@ -0,0 +1,155 @@
+use crate as l2_core;
+use crate::config::traits::{LoadFileHandler, Loadable};
+use macro_common::config_dir;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+use tracing::info;
+
+/// Chance in percent to sow a seed of the right level
+const SOW_CHANCE: i32 = 90;
+/// Alternative seeds give better crops but are harder to sow
+const ALTERNATIVE_SOW_CHANCE: i32 = 20;
+const HARVEST_CHANCE: i32 = 100;
+/// Levels the monster (or the player) may differ from the seed before the chance drops
+const LEVEL_TOLERANCE: i32 = 5;
+/// Chance lost for each level beyond the tolerance
+const PENALTY_PER_LEVEL: i32 = 5;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SeedTemplate {
+    pub seed_id: i32,
+    /// Crop harvested from the monsters the seed was sown on
+    pub crop_id: i32,
+    /// Castle selling the seed and procuring its crop
+    pub castle_id: i32,
+    pub level: u8,
+    #[serde(default)]
+    pub alternative: bool,
+    /// Most seeds the castle may sell in a period
+    pub seed_limit: i64,
+    /// Most crops the castle may procure in a period
+    pub crop_limit: i64,
+}
+
+impl SeedTemplate {
+    /// Chance in percent the seed takes root on the monster.
+    #[must_use]
+    pub fn sow_chance(&self, monster_level: u8, player_level: u8) -> i32 {
+        let base = if self.alternative {
+            ALTERNATIVE_SOW_CHANCE
+        } else {
+            SOW_CHANCE
+        };
+        let seed_diff = i32::from(monster_level).abs_diff(i32::from(self.level));
+        let player_diff = i32::from(monster_level).abs_diff(i32::from(player_level));
+        (base - level_penalty(seed_diff) - level_penalty(player_diff)).max(1)
+    }
+
+    /// Crops a monster of the level gives once it is killed, stronger monsters give more.
+    #[must_use]
+    pub fn harvest_count(&self, monster_level: u8) -> i64 {
+        let diff = i64::from(monster_level) - i64::from(self.level) - i64::from(LEVEL_TOLERANCE);
+        1 + diff.max(0)
+    }
+}
+
+/// Chance in percent the player harvests the crops of a killed monster.
+#[must_use]
+pub fn harvest_chance(monster_level: u8, player_level: u8) -> i32 {
+    let diff = i32::from(monster_level).abs_diff(i32::from(player_level));
+    (HARVEST_CHANCE - level_penalty(diff)).max(1)
+}
+
+fn level_penalty(diff: u32) -> i32 {
+    let beyond = i32::try_from(diff).unwrap_or(i32::MAX) - LEVEL_TOLERANCE;
+    beyond.max(0).saturating_mul(PENALTY_PER_LEVEL)
+}
+
+/// Prices a castle lord may set for a seed or crop of the reference price.
+#[must_use]
+pub fn price_range(reference_price: u64) -> RangeInclusive<i64> {
+    let reference = i64::try_from(reference_price).unwrap_or(i64::MAX);
+    (reference / 2).max(1)..=reference.saturating_mul(3)
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SeedListFile {
+    pub seeds: Vec<SeedTemplate>,
+}
+
+#[derive(Debug, Clone, Default)]
+#[config_dir(path = "config/data/manor", post_load)]
+pub struct ManorData {
+    seeds: BTreeMap<i32, SeedTemplate>,
+}
+
+impl Loadable for ManorData {
+    fn post_load(&self) {
+        info!("Loaded {} manor seeds.", self.seeds.len());
+    }
+}
+
+impl ManorData {
+    #[must_use]
+    pub fn get_seed(&self, seed_id: i32) -> Option<&SeedTemplate> {
+        self.seeds.get(&seed_id)
+    }
+
+    /// Seed growing the crop at the castle
+    #[must_use]
+    pub fn seed_of_crop(&self, castle_id: i32, crop_id: i32) -> Option<&SeedTemplate> {
+        self.seeds
+            .values()
+            .find(|s| s.castle_id == castle_id && s.crop_id == crop_id)
+    }
+
+    /// Seeds of the castle ordered by id
+    pub fn castle_seeds(&self, castle_id: i32) -> impl Iterator<Item = &SeedTemplate> {
+        self.seeds
+            .values()
+            .filter(move |s| s.castle_id == castle_id)
+    }
+}
+
+impl LoadFileHandler for ManorData {
+    type TargetConfigType = SeedListFile;
+    fn for_each(&mut self, item: Self::TargetConfigType) {
+        for seed in item.seeds {
+            if let Some(s) = self.seeds.insert(seed.seed_id, seed) {
+                panic!("Duplicate manor seed: {:?}", s.seed_id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::traits::ConfigDirLoader;
+    use crate::data::manor::{ManorData, harvest_chance, price_range};
+
+    #[test]
+    fn test_load_seeds() {
+        let data = ManorData::load();
+        let seed = data.get_seed(5016).unwrap();
+        assert_eq!(seed.castle_id, 1);
+        assert_eq!(data.seed_of_crop(1, seed.crop_id).unwrap().seed_id, 5016);
+        assert!(data.seed_of_crop(2, seed.crop_id).is_none());
+        assert!(data.castle_seeds(1).all(|s| s.castle_id == 1));
+    }
+
+    #[test]
+    fn test_manor_chances() {
+        let data = ManorData::load();
+        let seed = data.get_seed(5016).unwrap();
+        assert_eq!(seed.sow_chance(seed.level, seed.level), 90);
+        // 3 levels beyond the tolerance of the seed, then of the seed and the player
+        assert_eq!(seed.sow_chance(seed.level + 8, seed.level + 8), 75);
+        assert_eq!(seed.sow_chance(seed.level + 8, seed.level), 60);
+        assert_eq!(seed.sow_chance(seed.level + 60, 1), 1);
+        assert_eq!(seed.harvest_count(seed.level), 1);
+        assert_eq!(seed.harvest_count(seed.level + 7), 3);
+        assert_eq!(harvest_chance(20, 27), 90);
+        assert_eq!(price_range(100), 50..=300);
+    }
+}
@@ -4,4 +4,20 @@ pub mod classes;
 pub mod base_stat;
 pub mod action_list;
 pub mod skills;
-pub mod skill_tree_data;
\ No newline at end of file
+pub mod skill_tree_data;
+pub mod items;
+pub mod zones;
+pub mod teleports;
+pub mod hennas;
+pub mod html;
+pub mod quests;
+pub mod buy_lists;
+pub mod multisell;
+pub mod castles;
+pub mod olympiad;
+pub mod manor;
+pub mod cursed_weapons;
+pub mod fishing;
+pub mod summons;
+pub mod recipes;
+pub mod enchant;
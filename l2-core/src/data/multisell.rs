@@ -0,0 +1,138 @@
+use crate as l2_core;
+use crate::config::traits::{LoadFileHandler, Loadable};
+use crate::data::buy_lists::price_with_tax;
+use crate::data::items::ItemsData;
+use crate::game_objects::player::inventory::Inventory;
+use macro_common::config_dir;
+use serde::Deserialize;
+use std::collections::HashMap;
+use tracing::info;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct MultisellItem {
+    pub id: i32,
+    pub count: i64,
+}
+
+/// One exchange of the list: all the ingredients are taken for the products.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MultisellEntry {
+    pub ingredients: Vec<MultisellItem>,
+    pub products: Vec<MultisellItem>,
+}
+
+impl MultisellEntry {
+    /// Only entries made of stackable items can be exchanged several times at once.
+    #[must_use]
+    pub fn is_stackable(&self, items: &ItemsData) -> bool {
+        self.ingredients
+            .iter()
+            .chain(&self.products)
+            .all(|i| items.get_template(i.id).is_some_and(|t| t.stackable))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MultisellList {
+    pub id: i32,
+    /// Npcs allowed to open the list
+    pub npc_ids: Vec<i32>,
+    /// Adena ingredients are raised by the town tax
+    #[serde(default)]
+    pub apply_taxes: bool,
+    /// The products keep the enchant level of the exchanged weapon or armor
+    #[serde(default)]
+    pub maintain_enchantment: bool,
+    pub entries: Vec<MultisellEntry>,
+}
+
+impl MultisellList {
+    /// Entries are numbered from 1 in the order of the data file.
+    #[must_use]
+    pub fn get_entry(&self, entry_id: i32) -> Option<&MultisellEntry> {
+        let index = usize::try_from(entry_id.checked_sub(1)?).ok()?;
+        self.entries.get(index)
+    }
+
+    #[must_use]
+    pub fn is_offered_by(&self, npc_id: i32) -> bool {
+        self.npc_ids.contains(&npc_id)
+    }
+
+    /// Count of the ingredient for one exchange, adena is raised by the tax when it applies.
+    #[must_use]
+    pub fn ingredient_count(&self, ingredient: &MultisellItem, tax_rate: u8) -> i64 {
+        match u64::try_from(ingredient.count) {
+            Ok(count) if self.apply_taxes && ingredient.id == Inventory::ADENA_ID => {
+                i64::try_from(price_with_tax(count, tax_rate)).unwrap_or(i64::MAX)
+            }
+            _ => ingredient.count,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MultisellFile {
+    pub multisells: Vec<MultisellList>,
+}
+
+#[derive(Debug, Clone, Default)]
+#[config_dir(path = "config/data/multisell", post_load)]
+pub struct MultisellData {
+    lists: HashMap<i32, MultisellList>,
+}
+
+impl Loadable for MultisellData {
+    fn post_load(&self) {
+        info!("Loaded {} multisell lists.", self.lists.len());
+    }
+}
+
+impl MultisellData {
+    #[must_use]
+    pub fn get_list(&self, list_id: i32) -> Option<&MultisellList> {
+        self.lists.get(&list_id)
+    }
+}
+
+impl LoadFileHandler for MultisellData {
+    type TargetConfigType = MultisellFile;
+    fn for_each(&mut self, item: Self::TargetConfigType) {
+        for list in item.multisells {
+            for entry in &list.entries {
+                assert!(
+                    !entry.ingredients.is_empty() && !entry.products.is_empty(),
+                    "Multisell {} has an empty entry",
+                    list.id
+                );
+            }
+            if let Some(l) = self.lists.insert(list.id, list) {
+                panic!("Duplicate multisell: {:?}", l.id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::traits::ConfigDirLoader;
+    use crate::data::items::ItemsData;
+    use crate::data::multisell::MultisellData;
+
+    #[test]
+    fn test_load_multisell() {
+        let data = MultisellData::load();
+        let list = data.get_list(1001).unwrap();
+        assert!(list.is_offered_by(30283));
+        assert!(list.maintain_enchantment);
+        assert!(list.get_entry(0).is_none());
+        assert_eq!(list.get_entry(1).unwrap().products[0].id, 4);
+        assert!(list.get_entry(i32::try_from(list.entries.len()).unwrap() + 1).is_none());
+        let items = ItemsData::load();
+        let weapon = list.get_entry(1).unwrap();
+        assert!(!weapon.is_stackable(&items));
+        assert_eq!(list.ingredient_count(&weapon.ingredients[0], 10), 1);
+        assert_eq!(list.ingredient_count(&weapon.ingredients[1], 10), 550);
+        assert!(list.get_entry(3).unwrap().is_stackable(&items));
+    }
+}
@@ -0,0 +1,70 @@
+use crate as l2_core;
+use crate::config::traits::{LoadFileHandler, Loadable};
+use crate::data::castles::Position;
+use macro_common::config_dir;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use tracing::info;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct StadiumTemplate {
+    pub id: i32,
+    /// Spawn point of each side
+    pub spawns: [Position; 2],
+    pub spectator: Position,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct StadiumListFile {
+    pub stadiums: Vec<StadiumTemplate>,
+}
+
+#[derive(Debug, Clone, Default)]
+#[config_dir(path = "config/data/olympiad", post_load)]
+pub struct OlympiadData {
+    stadiums: BTreeMap<i32, StadiumTemplate>,
+}
+
+impl Loadable for OlympiadData {
+    fn post_load(&self) {
+        info!("Loaded {} olympiad stadiums.", self.stadiums.len());
+    }
+}
+
+impl OlympiadData {
+    #[must_use]
+    pub fn get(&self, stadium_id: i32) -> Option<&StadiumTemplate> {
+        self.stadiums.get(&stadium_id)
+    }
+
+    /// Stadiums ordered by id
+    pub fn all(&self) -> impl Iterator<Item = &StadiumTemplate> {
+        self.stadiums.values()
+    }
+}
+
+impl LoadFileHandler for OlympiadData {
+    type TargetConfigType = StadiumListFile;
+    fn for_each(&mut self, item: Self::TargetConfigType) {
+        for stadium in item.stadiums {
+            if let Some(s) = self.stadiums.insert(stadium.id, stadium) {
+                panic!("Duplicate olympiad stadium: {:?}", s.id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::traits::ConfigDirLoader;
+    use crate::data::olympiad::OlympiadData;
+
+    #[test]
+    fn test_load_stadiums() {
+        let data = OlympiadData::load();
+        let ids: Vec<i32> = data.all().map(|s| s.id).collect();
+        assert_eq!(ids, vec![1, 2, 3, 4]);
+        let stadium = data.get(1).unwrap();
+        assert_ne!(stadium.spawns[0], stadium.spawns[1]);
+    }
+}
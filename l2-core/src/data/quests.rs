@@ -0,0 +1,223 @@
+use crate as l2_core;
+use crate::config::traits::{LoadFileHandler, Loadable};
+use macro_common::config_dir;
+use serde::Deserialize;
+use std::collections::HashMap;
+use tracing::info;
+
+/// Something that happened to the player and may move a quest forward.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuestEvent {
+    Talk { npc_id: i32 },
+    Kill { npc_id: i32 },
+    PickUp { item_id: i32 },
+    EnterZone { zone_id: i32 },
+    LevelUp { level: u8 },
+}
+
+/// What the current step of a quest waits for.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum QuestTrigger {
+    /// Talk to the npc
+    Talk { npc_id: i32 },
+    /// Kill `count` of the listed monsters, the progress is shown in the quest window
+    Kill { npc_ids: Vec<i32>, count: u32 },
+    /// The listed monsters drop the quest item with `chance` percent until there are `count`
+    Collect {
+        npc_ids: Vec<i32>,
+        item_id: i32,
+        count: i64,
+        chance: u8,
+    },
+    /// Have `count` items picked up from the ground
+    PickUp { item_id: i32, count: i64 },
+    /// Enter the zone
+    EnterZone { zone_id: i32 },
+    /// Reach the level
+    ReachLevel { level: u8 },
+}
+
+impl QuestTrigger {
+    /// If the event concerns this step, the actual progress is checked by the caller.
+    #[must_use]
+    pub fn matches(&self, event: QuestEvent) -> bool {
+        match (self, event) {
+            (Self::Talk { npc_id }, QuestEvent::Talk { npc_id: id }) => *npc_id == id,
+            (
+                Self::Kill { npc_ids, .. } | Self::Collect { npc_ids, .. },
+                QuestEvent::Kill { npc_id },
+            ) => npc_ids.contains(&npc_id),
+            (Self::PickUp { item_id, .. }, QuestEvent::PickUp { item_id: id }) => *item_id == id,
+            (Self::EnterZone { zone_id }, QuestEvent::EnterZone { zone_id: id }) => *zone_id == id,
+            (Self::ReachLevel { level }, QuestEvent::LevelUp { level: reached }) => {
+                reached >= *level
+            }
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct QuestItem {
+    pub id: i32,
+    pub count: i64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct QuestStep {
+    pub on: QuestTrigger,
+    /// Given when the step is done
+    #[serde(default)]
+    pub give_items: Vec<QuestItem>,
+    /// Taken when the step is done
+    #[serde(default)]
+    pub take_items: Vec<QuestItem>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct QuestReward {
+    #[serde(default)]
+    pub exp: i64,
+    #[serde(default)]
+    pub sp: i64,
+    #[serde(default)]
+    pub adena: i64,
+    #[serde(default)]
+    pub items: Vec<QuestItem>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct QuestTemplate {
+    pub id: i32,
+    /// Unique key the quest state is saved under
+    pub name: String,
+    pub min_level: u8,
+    #[serde(default = "QuestTemplate::default_max_level")]
+    pub max_level: u8,
+    /// Npc offering the quest
+    pub start_npc: i32,
+    #[serde(default)]
+    pub repeatable: bool,
+    /// Given when the quest is accepted
+    #[serde(default)]
+    pub start_items: Vec<QuestItem>,
+    /// Quest items destroyed when the quest is finished or aborted
+    #[serde(default)]
+    pub items: Vec<i32>,
+    /// Condition `n` of a started quest waits for `steps[n - 1]`
+    pub steps: Vec<QuestStep>,
+    #[serde(default)]
+    pub reward: QuestReward,
+}
+
+impl QuestTemplate {
+    fn default_max_level() -> u8 {
+        u8::MAX
+    }
+
+    #[must_use]
+    pub fn get_step(&self, condition: u32) -> Option<&QuestStep> {
+        let index = usize::try_from(condition.checked_sub(1)?).ok()?;
+        self.steps.get(index)
+    }
+
+    #[must_use]
+    pub fn is_last_step(&self, condition: u32) -> bool {
+        usize::try_from(condition).is_ok_and(|c| c == self.steps.len())
+    }
+
+    #[must_use]
+    pub fn is_level_allowed(&self, level: u8) -> bool {
+        (self.min_level..=self.max_level).contains(&level)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct QuestTemplateList {
+    pub quests: Vec<QuestTemplate>,
+}
+
+#[derive(Debug, Clone, Default)]
+#[config_dir(path = "config/data/quests", post_load)]
+pub struct QuestData {
+    quests: HashMap<i32, QuestTemplate>,
+}
+
+impl Loadable for QuestData {
+    fn post_load(&self) {
+        info!("Loaded {} quests.", self.quests.len());
+    }
+}
+
+impl QuestData {
+    #[must_use]
+    pub fn get_quest(&self, quest_id: i32) -> Option<&QuestTemplate> {
+        self.quests.get(&quest_id)
+    }
+
+    /// Quests offered by the npc, ordered by id.
+    #[must_use]
+    pub fn quests_of_npc(&self, npc_id: i32) -> Vec<&QuestTemplate> {
+        let mut quests: Vec<_> = self
+            .quests
+            .values()
+            .filter(|q| q.start_npc == npc_id)
+            .collect();
+        quests.sort_by_key(|q| q.id);
+        quests
+    }
+
+    #[must_use]
+    pub fn is_quest_item(&self, item_id: i32) -> bool {
+        self.quests.values().any(|q| q.items.contains(&item_id))
+    }
+}
+
+impl LoadFileHandler for QuestData {
+    type TargetConfigType = QuestTemplateList;
+    fn for_each(&mut self, item: Self::TargetConfigType) {
+        for quest in item.quests {
+            assert!(!quest.steps.is_empty(), "Quest {} has no steps", quest.id);
+            if let Some(q) = self.quests.insert(quest.id, quest) {
+                panic!("Duplicate quest: {:?}", q.id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::traits::ConfigDirLoader;
+    use crate::data::quests::{QuestData, QuestEvent, QuestTrigger};
+
+    #[test]
+    fn test_load_quests() {
+        let data = QuestData::load();
+        let quest = data.get_quest(1).unwrap();
+        assert_eq!(quest.name, "Q00001_LettersOfLove");
+        assert!(quest.get_step(0).is_none());
+        assert!(quest.get_step(1).is_some());
+        assert!(quest.is_last_step(u32::try_from(quest.steps.len()).unwrap()));
+        assert!(!quest.is_level_allowed(1));
+        assert!(data.is_quest_item(687));
+        assert!(!data.is_quest_item(57));
+        assert_eq!(data.quests_of_npc(quest.start_npc)[0].id, 1);
+    }
+
+    #[test]
+    fn test_trigger_matches() {
+        let collect = QuestTrigger::Collect {
+            npc_ids: vec![20001, 20002],
+            item_id: 1,
+            count: 5,
+            chance: 50,
+        };
+        assert!(collect.matches(QuestEvent::Kill { npc_id: 20002 }));
+        assert!(!collect.matches(QuestEvent::Kill { npc_id: 20003 }));
+        assert!(!collect.matches(QuestEvent::Talk { npc_id: 20001 }));
+        let level = QuestTrigger::ReachLevel { level: 10 };
+        assert!(level.matches(QuestEvent::LevelUp { level: 11 }));
+        assert!(!level.matches(QuestEvent::LevelUp { level: 9 }));
+    }
+}
@@ -0,0 +1,110 @@
+use crate as l2_core;
+use crate::config::traits::{LoadFileHandler, Loadable};
+use macro_common::config_dir;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use tracing::info;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct RecipeItem {
+    pub item_id: i32,
+    pub count: i64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RecipeTemplate {
+    pub id: i32,
+    pub name: String,
+    /// The item the recipe is learned from
+    pub recipe_item: i32,
+    /// Dwarven recipes need the Create Item skill, the others the Create Common Item one
+    pub dwarven: bool,
+    /// Level of the craft skill needed
+    pub level: i16,
+    pub mp_cost: u32,
+    /// Chance in percent that the craft succeeds, the ingredients are used up either way
+    pub success_rate: u8,
+    pub ingredients: Vec<RecipeItem>,
+    pub product: RecipeItem,
+}
+
+impl RecipeTemplate {
+    /// Create Item
+    pub const DWARVEN_CRAFT_SKILL: i32 = 172;
+    /// Create Common Item
+    pub const COMMON_CRAFT_SKILL: i32 = 1320;
+
+    /// The skill which crafts the recipe, its level limits the recipes a player can use.
+    #[must_use]
+    pub fn craft_skill_id(&self) -> i32 {
+        if self.dwarven {
+            Self::DWARVEN_CRAFT_SKILL
+        } else {
+            Self::COMMON_CRAFT_SKILL
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RecipeListFile {
+    pub recipes: Vec<RecipeTemplate>,
+}
+
+#[derive(Debug, Clone, Default)]
+#[config_dir(path = "config/data/recipes", post_load)]
+pub struct RecipeData {
+    recipes: BTreeMap<i32, RecipeTemplate>,
+}
+
+impl Loadable for RecipeData {
+    fn post_load(&self) {
+        info!("Loaded {} recipes.", self.recipes.len());
+    }
+}
+
+impl RecipeData {
+    #[must_use]
+    pub fn get(&self, id: i32) -> Option<&RecipeTemplate> {
+        self.recipes.get(&id)
+    }
+
+    /// The recipe the item teaches.
+    #[must_use]
+    pub fn by_recipe_item(&self, item_id: i32) -> Option<&RecipeTemplate> {
+        self.recipes.values().find(|r| r.recipe_item == item_id)
+    }
+}
+
+impl LoadFileHandler for RecipeData {
+    type TargetConfigType = RecipeListFile;
+    fn for_each(&mut self, item: Self::TargetConfigType) {
+        for recipe in item.recipes {
+            assert!(
+                !recipe.ingredients.is_empty(),
+                "Recipe {} has no ingredients",
+                recipe.id
+            );
+            if let Some(r) = self.recipes.insert(recipe.id, recipe) {
+                panic!("Duplicate recipe: {:?}", r.id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::traits::ConfigDirLoader;
+    use crate::data::recipes::{RecipeData, RecipeTemplate};
+
+    #[test]
+    fn test_load_recipes() {
+        let data = RecipeData::load();
+        let arrow = data.by_recipe_item(1666).unwrap();
+        assert!(arrow.dwarven);
+        assert_eq!(arrow.craft_skill_id(), RecipeTemplate::DWARVEN_CRAFT_SKILL);
+        assert_eq!(data.get(arrow.id).unwrap().recipe_item, 1666);
+        let common = data.by_recipe_item(1804).unwrap();
+        assert_eq!(common.craft_skill_id(), RecipeTemplate::COMMON_CRAFT_SKILL);
+        assert!(data.by_recipe_item(57).is_none());
+    }
+}
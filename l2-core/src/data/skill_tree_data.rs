@@ -143,6 +143,7 @@ impl RemoveTreeSkill {
 pub struct SkillTreesData {
     class_skill_trees: HashMap<Class, Arc<SkillTree>>,
     common_skill_trees: Arc<SkillTree>,
+    hero_skill_tree: Arc<SkillTree>,
 }
 
 #[derive(Debug, Deserialize, Clone, Default, Eq, PartialEq, Hash, Copy)]
@@ -207,6 +208,10 @@ impl SkillTreesData {
             "Loaded {} skills for common tree.",
             self.common_skill_trees.skills.len()
         );
+        info!(
+            "Loaded {} skills for hero tree.",
+            self.hero_skill_tree.skills.len()
+        );
     }
 
     #[must_use]
@@ -377,6 +382,14 @@ impl SkillTreesData {
 
         initial_skills
     }
+
+    /// Skills every hero has while being a hero, regardless of the class.
+    #[must_use]
+    pub fn get_hero_skills(&self) -> Vec<TreeSkill> {
+        let mut skills: Vec<TreeSkill> = self.hero_skill_tree.skills.values().cloned().collect();
+        skills.sort_by_key(TreeSkill::hash);
+        skills
+    }
 }
 
 impl Loadable for SkillTreesData {}
@@ -391,6 +404,9 @@ impl LoadFileHandler for SkillTreesData {
             (SkillTreeType::ClassSkillTree, _) => {
                 self.common_skill_trees = item;
             }
+            (SkillTreeType::HeroSkillTree, _) => {
+                self.hero_skill_tree = item;
+            }
             (_, _) => {}
         }
     }
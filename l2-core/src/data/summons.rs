@@ -0,0 +1,238 @@
+use crate as l2_core;
+use crate::config::traits::{LoadFileHandler, Loadable};
+use macro_common::config_dir;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use tracing::info;
+
+/// Servitors are called by a skill and stay until they are dismissed, pets live in a collar
+/// item and keep their level, exp and items between the summons.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SummonKind {
+    Servitor,
+    Pet,
+}
+
+impl SummonKind {
+    /// Summon type the client shows the windows for
+    #[must_use]
+    pub fn client_type(self) -> i32 {
+        match self {
+            Self::Servitor => 1,
+            Self::Pet => 2,
+        }
+    }
+}
+
+/// What the owner rides, the values are the ones the client knows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MountType {
+    Strider = 1,
+    Wyvern = 2,
+    Wolf = 3,
+}
+
+impl MountType {
+    #[must_use]
+    pub fn is_flying(self) -> bool {
+        self == Self::Wyvern
+    }
+}
+
+/// How a pet carries its owner.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MountTemplate {
+    pub kind: MountType,
+    /// Level the pet needs to carry the owner
+    #[serde(default)]
+    pub min_level: u8,
+    pub run_speed: u16,
+    pub walk_speed: u16,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SummonLevel {
+    pub level: u8,
+    /// Exp needed to reach the level
+    #[serde(default)]
+    pub exp: i64,
+    pub max_hp: f64,
+    pub max_mp: f64,
+    pub p_atk: f64,
+    pub p_def: f64,
+    pub m_atk: f64,
+    pub m_def: f64,
+    /// How much food the pet can eat, 0 for the summons which never get hungry
+    #[serde(default)]
+    pub max_feed: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SummonTemplate {
+    pub npc_id: i32,
+    pub name: String,
+    pub kind: SummonKind,
+    /// Servitors: the skill which calls the servitor
+    pub summon_skill: Option<i32>,
+    /// Pets: the collar the pet lives in
+    pub control_item: Option<i32>,
+    /// Milliseconds the owner casts before the summon appears
+    #[serde(default)]
+    pub cast_time_ms: u32,
+    /// Percent of the attack and defence of the owner the summon gets on top of its own
+    #[serde(default)]
+    pub owner_share: u8,
+    /// Percent of the exp of the owner the pet takes while it is out
+    #[serde(default)]
+    pub exp_penalty: u8,
+    /// Items the pet eats by itself when it gets hungry
+    #[serde(default)]
+    pub food: Vec<i32>,
+    /// Food eaten every minute
+    #[serde(default)]
+    pub feed_normal: u32,
+    /// Food eaten every minute while the summon fights
+    #[serde(default)]
+    pub feed_battle: u32,
+    pub collision_radius: f64,
+    pub collision_height: f64,
+    pub run_speed: u16,
+    pub walk_speed: u16,
+    /// Pets the owner can ride
+    #[serde(default)]
+    pub mount: Option<MountTemplate>,
+    /// Stats by level, sorted by the level
+    pub levels: Vec<SummonLevel>,
+}
+
+impl SummonTemplate {
+    /// Stats of the level, the closest lower level is used when the level is missing.
+    ///
+    /// # Panics
+    /// - when the template has no levels, it is checked when the data is loaded
+    #[must_use]
+    pub fn get_level(&self, level: u8) -> &SummonLevel {
+        self.levels
+            .iter()
+            .rev()
+            .find(|l| l.level <= level)
+            .unwrap_or_else(|| &self.levels[0])
+    }
+
+    #[must_use]
+    pub fn min_level(&self) -> u8 {
+        self.levels.first().map_or(1, |l| l.level)
+    }
+
+    #[must_use]
+    pub fn max_level(&self) -> u8 {
+        self.levels.last().map_or(1, |l| l.level)
+    }
+
+    /// Level the exp is enough for.
+    #[must_use]
+    pub fn level_for_exp(&self, exp: i64) -> u8 {
+        self.levels
+            .iter()
+            .rev()
+            .find(|l| l.exp <= exp)
+            .map_or_else(|| self.min_level(), |l| l.level)
+    }
+
+    /// Exp needed for the level after the given one, the exp of the max level at the top.
+    #[must_use]
+    pub fn exp_for_next_level(&self, level: u8) -> i64 {
+        self.levels
+            .iter()
+            .find(|l| l.level > level)
+            .or(self.levels.last())
+            .map_or(0, |l| l.exp)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SummonListFile {
+    pub summons: Vec<SummonTemplate>,
+}
+
+#[derive(Debug, Clone, Default)]
+#[config_dir(path = "config/data/summons", post_load)]
+pub struct SummonData {
+    summons: BTreeMap<i32, SummonTemplate>,
+}
+
+impl Loadable for SummonData {
+    fn post_load(&self) {
+        info!("Loaded {} summons.", self.summons.len());
+    }
+}
+
+impl SummonData {
+    #[must_use]
+    pub fn get(&self, npc_id: i32) -> Option<&SummonTemplate> {
+        self.summons.get(&npc_id)
+    }
+
+    /// The servitor the skill calls.
+    #[must_use]
+    pub fn by_summon_skill(&self, skill_id: i32) -> Option<&SummonTemplate> {
+        self.summons
+            .values()
+            .find(|s| s.summon_skill == Some(skill_id))
+    }
+
+    /// The pet living in the collar.
+    #[must_use]
+    pub fn by_control_item(&self, item_id: i32) -> Option<&SummonTemplate> {
+        self.summons
+            .values()
+            .find(|s| s.control_item == Some(item_id))
+    }
+}
+
+impl LoadFileHandler for SummonData {
+    type TargetConfigType = SummonListFile;
+    fn for_each(&mut self, item: Self::TargetConfigType) {
+        for mut summon in item.summons {
+            assert!(
+                !summon.levels.is_empty(),
+                "Summon {} has no levels",
+                summon.npc_id
+            );
+            summon.levels.sort_by_key(|l| l.level);
+            if let Some(s) = self.summons.insert(summon.npc_id, summon) {
+                panic!("Duplicate summon: {:?}", s.npc_id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::traits::ConfigDirLoader;
+    use crate::data::summons::{MountType, SummonData, SummonKind};
+
+    #[test]
+    fn test_load_summons() {
+        let data = SummonData::load();
+        let servitor = data.by_summon_skill(1111).unwrap();
+        assert_eq!(servitor.kind, SummonKind::Servitor);
+        assert!(servitor.control_item.is_none());
+        let pet = data.by_control_item(2375).unwrap();
+        assert_eq!(pet.kind, SummonKind::Pet);
+        assert!(!pet.food.is_empty());
+        let (min, max) = (pet.min_level(), pet.max_level());
+        assert!(min < max);
+        assert_eq!(pet.level_for_exp(0), min);
+        assert_eq!(pet.level_for_exp(pet.exp_for_next_level(min)), min + 1);
+        assert_eq!(pet.level_for_exp(i64::MAX), max);
+        assert_eq!(pet.get_level(u8::MAX).level, max);
+        assert!(pet.mount.is_none());
+        let strider = data.by_control_item(4422).unwrap();
+        assert_eq!(strider.mount.as_ref().unwrap().kind, MountType::Strider);
+        let wyvern = data.by_control_item(8663).unwrap();
+        assert!(wyvern.mount.as_ref().unwrap().kind.is_flying());
+    }
+}
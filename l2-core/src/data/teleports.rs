@@ -0,0 +1,83 @@
+use crate as l2_core;
+use crate::config::traits::{LoadFileHandler, Loadable};
+use macro_common::config_dir;
+use serde::Deserialize;
+use std::collections::HashMap;
+use tracing::info;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TeleportLocation {
+    pub id: i32,
+    pub name: String,
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+    /// Price in adena
+    #[serde(default)]
+    pub price: i64,
+}
+
+/// Destinations offered by a gatekeeper NPC.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TeleportList {
+    pub npc_id: i32,
+    pub locations: Vec<TeleportLocation>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TeleportListFile {
+    pub teleports: Vec<TeleportList>,
+}
+
+#[derive(Debug, Clone, Default)]
+#[config_dir(path = "config/data/teleports", post_load)]
+pub struct TeleportData {
+    lists: HashMap<i32, TeleportList>,
+}
+
+impl Loadable for TeleportData {
+    fn post_load(&self) {
+        info!("Loaded {} teleport lists.", self.lists.len());
+    }
+}
+
+impl TeleportData {
+    #[must_use]
+    pub fn get_list(&self, npc_id: i32) -> Option<&TeleportList> {
+        self.lists.get(&npc_id)
+    }
+
+    #[must_use]
+    pub fn get_location(&self, npc_id: i32, location_id: i32) -> Option<&TeleportLocation> {
+        self.get_list(npc_id)?
+            .locations
+            .iter()
+            .find(|l| l.id == location_id)
+    }
+}
+
+impl LoadFileHandler for TeleportData {
+    type TargetConfigType = TeleportListFile;
+    fn for_each(&mut self, item: Self::TargetConfigType) {
+        for list in item.teleports {
+            if let Some(l) = self.lists.insert(list.npc_id, list) {
+                panic!("Duplicate teleport list for npc: {:?}", l.npc_id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::traits::ConfigDirLoader;
+    use crate::data::teleports::TeleportData;
+
+    #[test]
+    fn test_load_teleports() {
+        let data = TeleportData::load();
+        let loc = data.get_location(30006, 1).unwrap();
+        assert_eq!(loc.name, "Talking Island Village");
+        assert!(data.get_location(30006, 9999).is_none());
+        assert!(data.get_list(1).is_none());
+    }
+}
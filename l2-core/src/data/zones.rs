@@ -0,0 +1,386 @@
+use crate as l2_core;
+use crate::config::traits::{LoadFileHandler, Loadable};
+use crate::game_objects::zone::ZoneId;
+use macro_common::config_dir;
+use serde::Deserialize;
+use std::collections::HashMap;
+use tracing::info;
+
+/// Size of a spatial index cell in game units.
+const CELL_SIZE: i32 = 2048;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum ZoneKind {
+    Town,
+    Peace,
+    Arena,
+    Water,
+    NoLanding,
+    Siege,
+}
+
+impl ZoneKind {
+    /// Flags a creature gets while standing inside the zone.
+    #[must_use]
+    pub fn flags(self) -> &'static [ZoneId] {
+        match self {
+            Self::Town | Self::Peace => &[ZoneId::Peace],
+            Self::Arena => &[ZoneId::Pvp],
+            Self::Water => &[ZoneId::Water],
+            Self::NoLanding => &[ZoneId::NoLanding],
+            Self::Siege => &[ZoneId::Siege, ZoneId::Pvp, ZoneId::NoSummonFriend],
+        }
+    }
+
+    /// Siege zones have effect only while a siege is in progress.
+    #[must_use]
+    pub fn active_by_default(self) -> bool {
+        self != Self::Siege
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ZoneShape {
+    Cuboid {
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        min_z: i32,
+        max_z: i32,
+    },
+    Cylinder {
+        x: i32,
+        y: i32,
+        radius: i32,
+        min_z: i32,
+        max_z: i32,
+    },
+    /// Polygon prism, points are `[x, y]` pairs.
+    Polygon {
+        points: Vec<[i32; 2]>,
+        min_z: i32,
+        max_z: i32,
+    },
+}
+
+impl ZoneShape {
+    #[must_use]
+    pub fn is_inside(&self, x: i32, y: i32, z: i32) -> bool {
+        match self {
+            Self::Cuboid {
+                x1,
+                y1,
+                x2,
+                y2,
+                min_z,
+                max_z,
+            } => {
+                x >= *x1.min(x2)
+                    && x <= *x1.max(x2)
+                    && y >= *y1.min(y2)
+                    && y <= *y1.max(y2)
+                    && z >= *min_z
+                    && z <= *max_z
+            }
+            Self::Cylinder {
+                x: cx,
+                y: cy,
+                radius,
+                min_z,
+                max_z,
+            } => {
+                let dx = i64::from(x) - i64::from(*cx);
+                let dy = i64::from(y) - i64::from(*cy);
+                let r = i64::from(*radius);
+                z >= *min_z && z <= *max_z && dx * dx + dy * dy <= r * r
+            }
+            Self::Polygon {
+                points,
+                min_z,
+                max_z,
+            } => z >= *min_z && z <= *max_z && Self::polygon_contains(points, x, y),
+        }
+    }
+
+    /// Ray casting point-in-polygon test.
+    fn polygon_contains(points: &[[i32; 2]], x: i32, y: i32) -> bool {
+        let (px, py) = (f64::from(x), f64::from(y));
+        let mut inside = false;
+        let mut j = points.len().wrapping_sub(1);
+        for (i, [xi, yi]) in points.iter().enumerate() {
+            let [xj, yj] = points[j];
+            let (xi, yi, xj, yj) = (f64::from(*xi), f64::from(*yi), f64::from(xj), f64::from(yj));
+            if (yi > py) != (yj > py) && px < (xj - xi) * (py - yi) / (yj - yi) + xi {
+                inside = !inside;
+            }
+            j = i;
+        }
+        inside
+    }
+
+    /// Top of the zone, e.g. the surface of water.
+    #[must_use]
+    pub fn max_z(&self) -> i32 {
+        match self {
+            Self::Cuboid { max_z, .. }
+            | Self::Cylinder { max_z, .. }
+            | Self::Polygon { max_z, .. } => *max_z,
+        }
+    }
+
+    /// 2D bounding box as `(min_x, min_y, max_x, max_y)`.
+    #[must_use]
+    pub fn bounds(&self) -> (i32, i32, i32, i32) {
+        match self {
+            Self::Cuboid { x1, y1, x2, y2, .. } => {
+                (*x1.min(x2), *y1.min(y2), *x1.max(x2), *y1.max(y2))
+            }
+            Self::Cylinder { x, y, radius, .. } => (x - radius, y - radius, x + radius, y + radius),
+            Self::Polygon { points, .. } => points.iter().fold(
+                (i32::MAX, i32::MAX, i32::MIN, i32::MIN),
+                |(min_x, min_y, max_x, max_y), [x, y]| {
+                    (min_x.min(*x), min_y.min(*y), max_x.max(*x), max_y.max(*y))
+                },
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ZoneTemplate {
+    pub id: i32,
+    pub name: String,
+    pub kind: ZoneKind,
+    pub shape: ZoneShape,
+    /// Restart points `[x, y, z]` of a town
+    #[serde(default)]
+    pub spawns: Vec<[i32; 3]>,
+    /// Percent added to npc shop prices in a town
+    #[serde(default)]
+    pub tax_rate: u8,
+    /// Castle whose lord sets the taxes of the town
+    #[serde(default)]
+    pub castle: Option<i32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ZoneTemplateList {
+    pub zones: Vec<ZoneTemplate>,
+}
+
+#[derive(Debug, Clone, Default)]
+#[config_dir(path = "config/data/zones", post_load)]
+pub struct ZonesData {
+    zones: Vec<ZoneTemplate>,
+    /// Spatial index: cell `(x, y)` -> indexes of zones whose bounds overlap the cell
+    index: HashMap<(i32, i32), Vec<usize>>,
+}
+
+impl Loadable for ZonesData {
+    fn post_load(&self) {
+        info!("Loaded {} zones.", self.zones.len());
+    }
+}
+
+impl ZonesData {
+    fn cell_of(x: i32, y: i32) -> (i32, i32) {
+        (x.div_euclid(CELL_SIZE), y.div_euclid(CELL_SIZE))
+    }
+
+    fn add_zone(&mut self, zone: ZoneTemplate) {
+        assert!(
+            self.get_zone(zone.id).is_none(),
+            "Duplicate zone id: {:?}",
+            zone.id
+        );
+        let idx = self.zones.len();
+        let (min_x, min_y, max_x, max_y) = zone.shape.bounds();
+        let (cx1, cy1) = Self::cell_of(min_x, min_y);
+        let (cx2, cy2) = Self::cell_of(max_x, max_y);
+        for cx in cx1..=cx2 {
+            for cy in cy1..=cy2 {
+                self.index.entry((cx, cy)).or_default().push(idx);
+            }
+        }
+        self.zones.push(zone);
+    }
+
+    #[must_use]
+    pub fn get_zone(&self, id: i32) -> Option<&ZoneTemplate> {
+        self.zones.iter().find(|z| z.id == id)
+    }
+
+    pub fn all_zones(&self) -> impl Iterator<Item = &ZoneTemplate> {
+        self.zones.iter()
+    }
+
+    /// Town restart point closest to the given position.
+    #[must_use]
+    pub fn nearest_town_spawn(&self, x: i32, y: i32) -> Option<[i32; 3]> {
+        self.zones
+            .iter()
+            .filter(|zone| zone.kind == ZoneKind::Town)
+            .flat_map(|zone| zone.spawns.iter().copied())
+            .min_by_key(|[sx, sy, _]| {
+                let dx = i64::from(*sx) - i64::from(x);
+                let dy = i64::from(*sy) - i64::from(y);
+                dx * dx + dy * dy
+            })
+    }
+
+    /// Shop tax of the town containing the point, 0 outside of towns.
+    #[must_use]
+    pub fn tax_rate_at(&self, x: i32, y: i32, z: i32) -> u8 {
+        self.zones_at(x, y, z)
+            .filter(|zone| zone.kind == ZoneKind::Town)
+            .map(|zone| zone.tax_rate)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Castle the town containing the point belongs to.
+    #[must_use]
+    pub fn castle_at(&self, x: i32, y: i32, z: i32) -> Option<i32> {
+        self.zones_at(x, y, z)
+            .filter(|zone| zone.kind == ZoneKind::Town)
+            .find_map(|zone| zone.castle)
+    }
+
+    /// Surface of the water at `(x, y)` which is at most `max_depth` below `z` and not above it.
+    #[must_use]
+    pub fn water_surface_at(&self, x: i32, y: i32, z: i32, max_depth: i32) -> Option<i32> {
+        self.index
+            .get(&Self::cell_of(x, y))
+            .into_iter()
+            .flatten()
+            .map(|idx| &self.zones[*idx])
+            .filter(|zone| zone.kind == ZoneKind::Water)
+            .map(|zone| (zone, zone.shape.max_z()))
+            .filter(|(zone, surface)| zone.shape.is_inside(x, y, *surface))
+            .map(|(_, surface)| surface)
+            .filter(|surface| (z - max_depth..=z).contains(surface))
+            .max()
+    }
+
+    /// All zones containing the point.
+    pub fn zones_at(&self, x: i32, y: i32, z: i32) -> impl Iterator<Item = &ZoneTemplate> {
+        self.index
+            .get(&Self::cell_of(x, y))
+            .into_iter()
+            .flatten()
+            .map(|idx| &self.zones[*idx])
+            .filter(move |zone| zone.shape.is_inside(x, y, z))
+    }
+}
+
+impl LoadFileHandler for ZonesData {
+    type TargetConfigType = ZoneTemplateList;
+    fn for_each(&mut self, item: Self::TargetConfigType) {
+        for zone in item.zones {
+            self.add_zone(zone);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_data() -> ZonesData {
+        let list: ZoneTemplateList = serde_yaml::from_str(
+            r"
+zones:
+  - id: 1
+    name: box
+    kind: Peace
+    shape: { type: cuboid, x1: 100, y1: 100, x2: -100, y2: -100, min_z: -50, max_z: 50 }
+  - id: 2
+    name: round
+    kind: Arena
+    shape: { type: cylinder, x: 5000, y: 5000, radius: 1000, min_z: -50, max_z: 50 }
+  - id: 3
+    name: triangle
+    kind: Water
+    shape:
+      type: polygon
+      points: [ [ 0, 0 ], [ 4000, 0 ], [ 0, 4000 ] ]
+      min_z: -100
+      max_z: 100
+",
+        )
+        .unwrap();
+        let mut data = ZonesData::default();
+        data.for_each(list);
+        data
+    }
+
+    fn ids_at(data: &ZonesData, x: i32, y: i32, z: i32) -> Vec<i32> {
+        let mut ids: Vec<_> = data.zones_at(x, y, z).map(|z| z.id).collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    #[test]
+    fn test_zones_at() {
+        let data = test_data();
+        assert_eq!(ids_at(&data, 10, 10, 0), vec![1, 3]);
+        assert_eq!(ids_at(&data, -10, -10, 0), vec![1]);
+        assert_eq!(ids_at(&data, -10, -10, 60), Vec::<i32>::new());
+        assert_eq!(ids_at(&data, 5500, 5500, 0), vec![2]);
+        assert_eq!(ids_at(&data, 5800, 5800, 0), Vec::<i32>::new());
+        assert_eq!(ids_at(&data, 1900, 1900, 0), vec![3]);
+        assert_eq!(ids_at(&data, 2100, 2100, 0), Vec::<i32>::new());
+    }
+
+    #[test]
+    fn test_water_surface_at() {
+        let data = test_data();
+        assert_eq!(data.water_surface_at(10, 10, 150, 100), Some(100));
+        assert_eq!(data.water_surface_at(10, 10, 300, 100), None);
+        // below the surface
+        assert_eq!(data.water_surface_at(10, 10, 50, 100), None);
+        assert_eq!(data.water_surface_at(2100, 2100, 150, 100), None);
+    }
+
+    #[test]
+    fn test_nearest_town_spawn() {
+        let mut data = test_data();
+        assert_eq!(data.nearest_town_spawn(0, 0), None);
+        let list: ZoneTemplateList = serde_yaml::from_str(
+            r"
+zones:
+  - { id: 10, name: a, kind: Town, shape: { type: cylinder, x: 0, y: 0, radius: 10, min_z: 0, max_z: 1 }, spawns: [ [ 0, 0, 5 ] ] }
+  - { id: 11, name: b, kind: Town, shape: { type: cylinder, x: 9000, y: 0, radius: 10, min_z: 0, max_z: 1 }, spawns: [ [ 9000, 0, 7 ], [ 8000, 0, 6 ] ] }
+",
+        )
+        .unwrap();
+        data.for_each(list);
+        assert_eq!(data.nearest_town_spawn(100, 100), Some([0, 0, 5]));
+        assert_eq!(data.nearest_town_spawn(6000, 0), Some([8000, 0, 6]));
+    }
+
+    #[test]
+    fn test_tax_rate_at() {
+        let mut data = test_data();
+        let list: ZoneTemplateList = serde_yaml::from_str(
+            "zones: [ { id: 10, name: a, kind: Town, shape: { type: cylinder, x: 0, y: 0, radius: 10, min_z: 0, max_z: 1 }, tax_rate: 15 } ]",
+        )
+        .unwrap();
+        data.for_each(list);
+        assert_eq!(data.tax_rate_at(0, 0, 0), 15);
+        assert_eq!(data.tax_rate_at(50, 50, 0), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Duplicate zone id")]
+    fn test_duplicate_zone() {
+        let mut data = test_data();
+        let list: ZoneTemplateList = serde_yaml::from_str(
+            "zones: [ { id: 1, name: dup, kind: Water, shape: { type: cylinder, x: 0, y: 0, radius: 1, min_z: 0, max_z: 1 } } ]",
+        )
+        .unwrap();
+        data.for_each(list);
+    }
+}
@@ -1,3 +1,16 @@
 pub trait CursedWeapon {
     fn get_lvl(&self) -> u8;
 }
+
+/// The cursed weapon the player owns, the stage grows with the kills of the owner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OwnedCursedWeapon {
+    pub item_id: i32,
+    pub stage: u8,
+}
+
+impl CursedWeapon for OwnedCursedWeapon {
+    fn get_lvl(&self) -> u8 {
+        self.stage
+    }
+}
@@ -0,0 +1,170 @@
+use crate::data::fishing::{FishGrade, FishTemplate};
+use crate::game_objects::zone::Location;
+
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FishMode {
+    Resting = 0,
+    Fighting = 1,
+}
+
+/// Pumping is the right skill while the fish rests, reeling while it fights.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FishingSkill {
+    Pumping,
+    Reeling,
+}
+
+impl FishingSkill {
+    #[must_use]
+    pub fn effective_on(self) -> FishMode {
+        match self {
+            Self::Pumping => FishMode::Resting,
+            Self::Reeling => FishMode::Fighting,
+        }
+    }
+}
+
+/// How the last pumping or reeling went, the client shows it on the fish HP bar
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FishingAction {
+    None = 0,
+    Success = 1,
+    Failure = 2,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FishingOutcome {
+    Ongoing,
+    Caught,
+    Escaped,
+}
+
+/// The player is fishing, the lure is in the water.
+#[derive(Debug, Clone)]
+pub struct Fishing {
+    /// Where the lure is
+    pub bait: Location,
+    pub lure_id: i32,
+    pub grade: FishGrade,
+    /// The fish on the hook, None until one bites
+    pub fish: Option<HookedFish>,
+}
+
+#[derive(Debug, Clone)]
+pub struct HookedFish {
+    pub item_id: i32,
+    pub max_hp: u32,
+    pub hp: u32,
+    pub hp_regen: u32,
+    /// Seconds the player has left to catch the fish
+    pub time_left: u32,
+    pub guts: u8,
+    pub mode: FishMode,
+}
+
+impl HookedFish {
+    #[must_use]
+    pub fn new(template: &FishTemplate) -> Self {
+        Self {
+            item_id: template.item_id,
+            max_hp: template.hp,
+            hp: template.hp,
+            hp_regen: template.hp_regen,
+            time_left: template.duration,
+            guts: template.guts,
+            mode: FishMode::Resting,
+        }
+    }
+
+    /// A second of the fight has passed: a fighting fish gets back some HP and the fish may
+    /// change its mode, `switch_roll` is in `0..100`.
+    pub fn tick(&mut self, switch_roll: u8) -> FishingOutcome {
+        if self.mode == FishMode::Fighting {
+            self.hp = self.hp.saturating_add(self.hp_regen);
+        }
+        self.time_left = self.time_left.saturating_sub(1);
+        if switch_roll < self.guts {
+            self.mode = match self.mode {
+                FishMode::Resting => FishMode::Fighting,
+                FishMode::Fighting => FishMode::Resting,
+            };
+        }
+        self.outcome()
+    }
+
+    /// The right skill hurts the fish, the wrong one lets it recover as much.
+    pub fn pull(&mut self, skill: FishingSkill, damage: u32) -> FishingAction {
+        if skill.effective_on() == self.mode {
+            self.hp = self.hp.saturating_sub(damage);
+            FishingAction::Success
+        } else {
+            self.hp = self.hp.saturating_add(damage);
+            FishingAction::Failure
+        }
+    }
+
+    /// The fish is caught once it has no HP left, it gets away when the time is up or it has
+    /// recovered to twice its HP.
+    #[must_use]
+    pub fn outcome(&self) -> FishingOutcome {
+        if self.hp == 0 {
+            FishingOutcome::Caught
+        } else if self.time_left == 0 || self.hp >= self.max_hp.saturating_mul(2) {
+            FishingOutcome::Escaped
+        } else {
+            FishingOutcome::Ongoing
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fish() -> HookedFish {
+        HookedFish {
+            item_id: 6411,
+            max_hp: 100,
+            hp: 100,
+            hp_regen: 10,
+            time_left: 3,
+            guts: 30,
+            mode: FishMode::Resting,
+        }
+    }
+
+    #[test]
+    fn test_fight() {
+        let mut fish = fish();
+        assert_eq!(fish.pull(FishingSkill::Reeling, 20), FishingAction::Failure);
+        assert_eq!(fish.hp, 120);
+        assert_eq!(fish.pull(FishingSkill::Pumping, 50), FishingAction::Success);
+        assert_eq!(fish.hp, 70);
+        // a resting fish does not recover, the roll below the guts makes it fight
+        assert_eq!(fish.tick(29), FishingOutcome::Ongoing);
+        assert_eq!(
+            (fish.hp, fish.mode, fish.time_left),
+            (70, FishMode::Fighting, 2)
+        );
+        assert_eq!(fish.tick(99), FishingOutcome::Ongoing);
+        assert_eq!((fish.hp, fish.mode), (80, FishMode::Fighting));
+        assert_eq!(
+            fish.pull(FishingSkill::Reeling, 100),
+            FishingAction::Success
+        );
+        assert_eq!(fish.outcome(), FishingOutcome::Caught);
+    }
+
+    #[test]
+    fn test_escape() {
+        let mut fish = fish();
+        fish.pull(FishingSkill::Reeling, 100);
+        assert_eq!(fish.outcome(), FishingOutcome::Escaped);
+        let mut fish = self::fish();
+        fish.tick(99);
+        fish.tick(99);
+        assert_eq!(fish.tick(99), FishingOutcome::Escaped);
+    }
+}
@@ -0,0 +1,86 @@
+use crate::data::enchant::{EnchantTable, EnchantTarget};
+use crate::data::items::ItemTemplate;
+use crate::game_objects::item::ItemObject;
+use crate::game_objects::stats::calculator::Modifier;
+use crate::game_objects::stats::stat_enum::Stat;
+
+impl ItemObject {
+    #[must_use]
+    pub fn get_enchant_level(&self) -> i32 {
+        self.item_model.enchant_level
+    }
+
+    /// Stats the enchant level of the item adds: attack for weapons, defence for armor.
+    #[must_use]
+    pub fn get_enchant_modifiers(
+        &self,
+        template: &ItemTemplate,
+        table: &EnchantTable,
+    ) -> Vec<(Stat, Modifier)> {
+        let bonus = table.stat_bonus(template.crystal_type, self.get_enchant_level());
+        if bonus <= 0.0 {
+            return vec![];
+        }
+        match table.target {
+            EnchantTarget::Weapon => vec![
+                (Stat::PAtk, Modifier::Add(bonus)),
+                (Stat::MAtk, Modifier::Add(bonus)),
+            ],
+            EnchantTarget::Armor => vec![(Stat::PDef, Modifier::Add(bonus))],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::items::{CrystalType, ItemKind};
+    use crate::id_factory::IdFactory;
+    use entities::entities::item;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_enchant_modifiers() {
+        let table = EnchantTable {
+            target: EnchantTarget::Armor,
+            safe_level: 3,
+            max_level: 16,
+            success: vec![50],
+            bonus: BTreeMap::from([(CrystalType::D, 1.0)]),
+            announce_level: 6,
+        };
+        let template = ItemTemplate {
+            id: 352,
+            name: "Brigandine Tunic".to_string(),
+            kind: ItemKind::Armor,
+            crystal_type: CrystalType::D,
+            crystal_count: 268,
+            stackable: false,
+            weight: 0,
+            price: 0,
+            soulshots: 0,
+            spiritshots: 0,
+            shot: None,
+            skill_id: None,
+            handler: None,
+            hero: false,
+            fishing_rod: false,
+            lure: None,
+        };
+        let mut armor = ItemObject {
+            object_id: IdFactory::instance().get_next_id(),
+            item_model: item::Model {
+                item_id: 352,
+                count: 1,
+                ..Default::default()
+            },
+        };
+        assert!(armor.get_enchant_modifiers(&template, &table).is_empty());
+        armor.item_model.enchant_level = 4;
+        let mods = armor.get_enchant_modifiers(&template, &table);
+        assert_eq!(mods.len(), 1);
+        assert!(
+            matches!(mods[0], (Stat::PDef, Modifier::Add(v)) if (v - 5.0).abs() < f64::EPSILON)
+        );
+    }
+}
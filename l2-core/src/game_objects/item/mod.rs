@@ -1,4 +1,6 @@
 mod _item;
 pub mod attribute;
+pub mod enchant;
+pub mod shot;
 
-pub use _item::*;
\ No newline at end of file
+pub use _item::*;
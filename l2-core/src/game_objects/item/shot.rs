@@ -0,0 +1,41 @@
+use serde::Deserialize;
+
+/// Charge a shot item puts on its owner until the next attack or cast.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShotType {
+    Soulshots,
+    Spiritshots,
+    BlessedSpiritshots,
+    BeastSoulshots,
+    BeastSpiritshots,
+}
+
+impl ShotType {
+    /// Type used by `ExAutoSoulShot`: 0 - soulshots, 1 - spiritshots, 2 - beast soulshots,
+    /// 3 - beast spiritshots.
+    #[must_use]
+    pub fn auto_shot_type(self) -> i32 {
+        match self {
+            Self::Soulshots => 0,
+            Self::Spiritshots | Self::BlessedSpiritshots => 1,
+            Self::BeastSoulshots => 2,
+            Self::BeastSpiritshots => 3,
+        }
+    }
+
+    /// Spiritshots boost magic skills, soulshots boost physical attacks.
+    #[must_use]
+    pub fn is_magic(self) -> bool {
+        matches!(
+            self,
+            Self::Spiritshots | Self::BlessedSpiritshots | Self::BeastSpiritshots
+        )
+    }
+
+    /// Beast shots are used by pets and servitors, not by the player itself.
+    #[must_use]
+    pub fn is_beast(self) -> bool {
+        matches!(self, Self::BeastSoulshots | Self::BeastSpiritshots)
+    }
+}
@@ -5,4 +5,7 @@ pub mod creature;
 pub mod zone;
 pub mod cursed_weapon;
 pub mod item;
-pub mod private_store_types;
\ No newline at end of file
+pub mod private_store_types;
+pub mod fishing;
+pub mod summon;
+pub mod mount;
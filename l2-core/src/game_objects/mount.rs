@@ -0,0 +1,104 @@
+use crate::data::summons::{MountType, SummonTemplate};
+use crate::game_objects::summon::Summon;
+
+/// The pet a player rides. The pet stays in its collar while it carries the owner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mount {
+    pub npc_id: i32,
+    pub kind: MountType,
+    /// DB id of the collar the pet lives in
+    pub control_item_id: i32,
+    pub run_speed: u16,
+    pub walk_speed: u16,
+}
+
+impl Mount {
+    /// The pet as a mount, None when the pet can't carry the owner (yet).
+    #[must_use]
+    pub fn of(summon: &Summon, template: &SummonTemplate) -> Option<Self> {
+        let mount = template.mount.as_ref()?;
+        if summon.level < mount.min_level {
+            return None;
+        }
+        Some(Self {
+            npc_id: summon.npc_id,
+            kind: mount.kind,
+            control_item_id: summon.control_item_id?,
+            run_speed: mount.run_speed,
+            walk_speed: mount.walk_speed,
+        })
+    }
+
+    #[must_use]
+    pub fn is_flying(&self) -> bool {
+        self.kind.is_flying()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::summons::{MountTemplate, SummonKind, SummonLevel};
+    use crate::game_objects::zone::Location;
+
+    const HERE: Location = Location {
+        x: 0,
+        y: 0,
+        z: 0,
+        heading: 0,
+    };
+
+    fn template(mount: Option<MountTemplate>) -> SummonTemplate {
+        SummonTemplate {
+            npc_id: 12526,
+            name: "Wind Strider".to_string(),
+            kind: SummonKind::Pet,
+            summon_skill: None,
+            control_item: Some(4422),
+            cast_time_ms: 0,
+            owner_share: 0,
+            exp_penalty: 10,
+            food: vec![],
+            feed_normal: 0,
+            feed_battle: 0,
+            collision_radius: 16.0,
+            collision_height: 30.0,
+            run_speed: 150,
+            walk_speed: 60,
+            mount,
+            levels: vec![SummonLevel {
+                level: 20,
+                exp: 0,
+                max_hp: 100.0,
+                max_mp: 100.0,
+                p_atk: 10.0,
+                p_def: 10.0,
+                m_atk: 10.0,
+                m_def: 10.0,
+                max_feed: 0,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_mount_of_pet() {
+        let strider = MountTemplate {
+            kind: MountType::Strider,
+            min_level: 20,
+            run_speed: 180,
+            walk_speed: 100,
+        };
+        let template = template(Some(strider));
+        let model = Summon::new_pet_model(&template, 7);
+        let mut pet = Summon::pet(&template, &model, vec![], HERE);
+        let mount = Mount::of(&pet, &template).unwrap();
+        assert_eq!((mount.control_item_id, mount.run_speed), (7, 180));
+        assert!(!mount.is_flying());
+        assert!(Mount::of(&pet, &self::template(None)).is_none());
+        pet.level = 19;
+        assert!(Mount::of(&pet, &template).is_none());
+        // servitors live in no collar
+        let servitor = Summon::servitor(&template, HERE);
+        assert!(Mount::of(&servitor, &template).is_none());
+    }
+}
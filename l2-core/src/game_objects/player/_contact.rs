@@ -0,0 +1,22 @@
+use entities::entities::character;
+
+/// Another character on the friend or block list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Contact {
+    pub char_id: i32,
+    pub name: String,
+    pub level: u8,
+    pub class_id: i8,
+}
+
+impl Contact {
+    #[must_use]
+    pub fn from_model(model: &character::Model) -> Self {
+        Self {
+            char_id: model.id,
+            name: model.name.clone(),
+            level: model.level,
+            class_id: model.class_id,
+        }
+    }
+}
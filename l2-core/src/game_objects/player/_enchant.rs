@@ -0,0 +1,204 @@
+use crate::data::enchant::{EnchantData, EnchantFailure, EnchantTable, EnchantTarget};
+use crate::data::items::{CrystalType, ItemHandler, ItemsData};
+use crate::game_objects::player::Player;
+use crate::game_objects::private_store_types::PrivateStoreType;
+use crate::game_objects::stats::calculator::ModifierSource;
+use entities::dao::item::LocType;
+
+/// An enchant the player has started with a scroll, the item is put in by the next request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnchantRequest {
+    pub scroll_object_id: i32,
+    pub item_object_id: Option<i32>,
+}
+
+/// What the scroll does to the item it enchants
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnchantScroll {
+    pub target: EnchantTarget,
+    pub grade: CrystalType,
+    pub failure: EnchantFailure,
+}
+
+impl EnchantScroll {
+    #[must_use]
+    pub fn of(handler: Option<ItemHandler>) -> Option<Self> {
+        match handler? {
+            ItemHandler::EnchantScroll {
+                target,
+                grade,
+                failure,
+            } => Some(Self {
+                target,
+                grade,
+                failure,
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl Player {
+    /// The scroll of the started enchant, None when the player has no such scroll any more.
+    #[must_use]
+    pub fn get_enchant_scroll(&self, items: &ItemsData) -> Option<EnchantScroll> {
+        let request = self.enchant_request?;
+        let scroll = self.get_item(request.scroll_object_id)?;
+        EnchantScroll::of(items.get_template(scroll.item_model.item_id)?.handler)
+    }
+
+    /// The table the scroll of the started enchant enchants the item by,
+    /// otherwise why the item can't be enchanted.
+    pub fn check_enchant_target<'a>(
+        &self,
+        items: &ItemsData,
+        enchant: &'a EnchantData,
+        item_object_id: i32,
+    ) -> Result<&'a EnchantTable, &'static str> {
+        if self.get_private_store_type() != PrivateStoreType::None {
+            return Err("You can't enchant while operating a private store.");
+        }
+        let Some(scroll) = self.get_enchant_scroll(items) else {
+            return Err("Inappropriate enchant conditions.");
+        };
+        let Some(item) = self.get_item(item_object_id) else {
+            return Err("Inappropriate enchant conditions.");
+        };
+        let table = items
+            .get_template(item.item_model.item_id)
+            .filter(|t| t.crystal_type.enchant_grade() == scroll.grade)
+            .and_then(|t| enchant.table_for(t))
+            .filter(|t| t.target == scroll.target)
+            .ok_or("Does not fit strengthening conditions of the scroll.")?;
+        if item.get_enchant_level() >= table.max_level {
+            return Err("The item can't be enchanted any further.");
+        }
+        Ok(table)
+    }
+
+    /// Enchant bonuses of the worn items replace the previous enchant modifiers,
+    /// modifiers of other sources stay as they are.
+    pub fn apply_enchant_stats(&mut self, items: &ItemsData, enchant: &EnchantData) {
+        let mut bonus = Vec::new();
+        let worn = self
+            .inventory
+            .items
+            .values()
+            .filter(|i| i.item_model.loc == LocType::Paperdoll);
+        for item in worn {
+            let Some(template) = items.get_template(item.item_model.item_id) else {
+                continue;
+            };
+            let Some(table) = enchant.table_for(template) else {
+                continue;
+            };
+            bonus.extend(item.get_enchant_modifiers(template, table));
+        }
+        self.stats
+            .calculator
+            .set_source_modifiers(ModifierSource::Enchant, bonus);
+        self.stats.update_cache();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::traits::ConfigDirLoader;
+    use crate::data::char_template::ClassTemplates;
+    use crate::game_objects::stats::calculator::Modifier;
+    use crate::game_objects::stats::stat_enum::Stat;
+    use entities::entities::item;
+    use entities::test_factories::factories::{char_factory, user_factory};
+    use test_utils::utils::get_test_db;
+
+    #[tokio::test]
+    async fn test_enchant_target_and_stats() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let char = char_factory(&db_pool, |mut ch| {
+            ch.user_id = user.id;
+            ch
+        })
+        .await;
+        let templates = ClassTemplates::load();
+        let temp = templates.try_get_template(char.class_id).unwrap();
+        let item = |id: i32, item_id: i32, enchant_level: i32, loc: LocType| item::Model {
+            id,
+            owner: char.id,
+            item_id,
+            count: 1,
+            enchant_level,
+            loc,
+            ..Default::default()
+        };
+        let items = vec![
+            item(1, 955, 0, LocType::Inventory),
+            item(2, 127, 5, LocType::Paperdoll),
+            item(3, 71, 0, LocType::Inventory),
+            item(4, 352, 0, LocType::Inventory),
+        ];
+        let mut player = Player::new(char, items, temp.clone(), None);
+        let object_id = |player: &Player, id: i32| {
+            player
+                .inventory
+                .items
+                .iter()
+                .find(|(_, i)| i.item_model.id == id)
+                .map(|(k, _)| *k)
+                .unwrap()
+        };
+        let (scroll, sword, flamberge, tunic) = (
+            object_id(&player, 1),
+            object_id(&player, 2),
+            object_id(&player, 3),
+            object_id(&player, 4),
+        );
+        let items_data = ItemsData::load();
+        let enchant = EnchantData::load();
+        assert!(
+            player
+                .check_enchant_target(&items_data, &enchant, sword)
+                .is_err()
+        );
+        player.enchant_request = Some(EnchantRequest {
+            scroll_object_id: scroll,
+            item_object_id: None,
+        });
+        let table = player
+            .check_enchant_target(&items_data, &enchant, sword)
+            .unwrap();
+        assert_eq!(table.target, EnchantTarget::Weapon);
+        // a D-grade weapon scroll fits neither a C-grade weapon nor an armor
+        assert!(
+            player
+                .check_enchant_target(&items_data, &enchant, flamberge)
+                .is_err()
+        );
+        assert!(
+            player
+                .check_enchant_target(&items_data, &enchant, tunic)
+                .is_err()
+        );
+
+        // modifiers of other sources survive enchant changes
+        player
+            .stats
+            .calculator
+            .modifiers
+            .insert(Stat::PAtk, vec![Modifier::Add(10.0)]);
+        player.stats.update_cache();
+        let p_atk = player.stats.get_stat(Stat::PAtk);
+        player.apply_enchant_stats(&items_data, &enchant);
+        assert!(player.stats.get_stat(Stat::PAtk) > p_atk);
+        player
+            .inventory
+            .items
+            .get_mut(&sword)
+            .unwrap()
+            .item_model
+            .enchant_level = 0;
+        player.apply_enchant_stats(&items_data, &enchant);
+        assert!((player.stats.get_stat(Stat::PAtk) - p_atk).abs() < f64::EPSILON);
+    }
+}
@@ -1,7 +1,9 @@
-//todo: it's just a stub, we need to store it in DB
+use entities::entities::character_macro;
+use serde::{Deserialize, Serialize};
 
 #[repr(u8)]
-#[derive(Clone, Debug, PartialEq, Eq, Hash, Copy, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Copy, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(from = "u8", into = "u8")]
 pub enum MacroType {
     None,
     Skill,
@@ -37,40 +39,37 @@ impl From<MacroType> for u8 {
         }
     }
 }
-#[derive(Debug, Clone)]
-pub struct MacroCommand(String);
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MacroCommand {
+    pub macro_type: MacroType,
+    /// Skill ID, item ID, page ID, depending on the macro type
+    pub d1: i32,
+    /// Skill level, shortcut ID, depending on the macro type
+    pub d2: i32,
+    pub cmd: String,
+}
 
 impl MacroCommand {
     /// Gets the skill ID, item ID, page ID, depending on the marco use
     #[must_use]
     pub fn get_d1(&self) -> i32 {
-        //todo: implement me
-        0i32
+        self.d1
     }
     /// Gets the skill level, shortcut ID, depending on the marco use.
     #[must_use]
     pub fn get_d2(&self) -> i32 {
-        //todo: implement me
-        0i32
+        self.d2
     }
     #[must_use]
     pub fn get_cmd_name(&self) -> &str {
-        //todo: implement me
-        &self.0
+        &self.cmd
     }
     #[must_use]
     pub fn get_type(&self) -> MacroType {
-        //todo: implement me
-        if self.0.starts_with("/delay") {
-            MacroType::Delay
-        } else if self.0.starts_with("/useskill") {
-            MacroType::Skill
-        } else {
-            MacroType::None
-        }
+        self.macro_type
     }
 }
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PlayerMacro {
     pub id: i32,
     pub name: String,
@@ -79,3 +78,73 @@ pub struct PlayerMacro {
     pub icon: i32,
     pub commands: Vec<MacroCommand>,
 }
+
+impl PlayerMacro {
+    /// Player can't have more macros than that
+    pub const MAX_MACROS: usize = 48;
+    /// Macro can't have more commands than that
+    pub const MAX_COMMANDS: usize = 12;
+    /// Ids of new macros start from this value
+    pub const FIRST_ID: i32 = 1000;
+
+    /// # Errors
+    /// - when commands stored in DB are malformed
+    pub fn from_model(model: character_macro::Model) -> anyhow::Result<Self> {
+        Ok(Self {
+            id: model.id,
+            name: model.name,
+            description: model.description,
+            acronym: model.acronym,
+            icon: model.icon,
+            commands: serde_json::from_value(model.commands)?,
+        })
+    }
+
+    /// # Errors
+    /// - when commands can't be serialized
+    pub fn to_model(&self, char_id: i32) -> anyhow::Result<character_macro::Model> {
+        Ok(character_macro::Model {
+            char_id,
+            id: self.id,
+            icon: self.icon,
+            name: self.name.clone(),
+            description: self.description.clone(),
+            acronym: self.acronym.clone(),
+            commands: serde_json::to_value(&self.commands)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_model_roundtrip() {
+        let m = PlayerMacro {
+            id: 1000,
+            name: "Nuke".to_string(),
+            description: "wind strike".to_string(),
+            acronym: "WS".to_string(),
+            icon: 2,
+            commands: vec![
+                MacroCommand {
+                    macro_type: MacroType::Skill,
+                    d1: 1177,
+                    d2: 1,
+                    cmd: String::new(),
+                },
+                MacroCommand {
+                    macro_type: MacroType::Delay,
+                    d1: 1,
+                    d2: 0,
+                    cmd: String::new(),
+                },
+            ],
+        };
+        let model = m.to_model(7).unwrap();
+        assert_eq!(model.char_id, 7);
+        assert_eq!(model.commands[0]["macro_type"], 1);
+        assert_eq!(PlayerMacro::from_model(model).unwrap(), m);
+    }
+}
@@ -0,0 +1,46 @@
+use crate::game_objects::creature::buff::AppliedBuff;
+use crate::game_objects::zone::Location;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OlympiadRole {
+    /// `side` is 1 or 2, `fighting` is set once the countdown is over
+    Fighter {
+        side: u8,
+        fighting: bool,
+    },
+    Spectator,
+}
+
+/// A player taking part in or watching an olympiad game.
+#[derive(Debug, Clone)]
+pub struct OlympiadState {
+    /// Id of the stadium the game runs in
+    pub game_id: i32,
+    pub role: OlympiadRole,
+    /// Where the player goes back to once the game is over
+    pub return_to: Location,
+    /// Effects a fighter had before the game, given back after it
+    pub saved_effects: Vec<AppliedBuff>,
+}
+
+impl OlympiadState {
+    #[must_use]
+    pub fn is_fighter(&self) -> bool {
+        matches!(self.role, OlympiadRole::Fighter { .. })
+    }
+
+    /// Opponents in the same game may attack each other once the fight has started.
+    #[must_use]
+    pub fn is_opponent(&self, other: &OlympiadState) -> bool {
+        match (self.role, other.role) {
+            (
+                OlympiadRole::Fighter { side, fighting },
+                OlympiadRole::Fighter {
+                    side: other_side,
+                    fighting: other_fighting,
+                },
+            ) => self.game_id == other.game_id && side != other_side && fighting && other_fighting,
+            _ => false,
+        }
+    }
+}
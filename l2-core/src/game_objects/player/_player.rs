@@ -1,10 +1,18 @@
+use crate::data::base_stat::BaseStat;
 use crate::data::char_template::CharTemplate;
+use crate::data::classes::mapping::Class;
+use crate::data::exp_table::ExpTable;
+use crate::data::hennas::{HennaStats, HennaTemplate};
+use crate::game_objects::creature::buff::AppliedBuff;
 use crate::game_objects::creature::skill::{Skill, SkillReuse};
-use crate::game_objects::cursed_weapon::CursedWeapon;
+use crate::game_objects::cursed_weapon::{CursedWeapon, OwnedCursedWeapon};
+use crate::game_objects::fishing::Fishing;
 use crate::game_objects::item::ItemObject;
+use crate::game_objects::item::shot::ShotType;
+use crate::game_objects::mount::Mount;
 use crate::game_objects::player::_subclass::Subclass;
 use crate::game_objects::player::appearance::Appearance;
-use crate::game_objects::player::clan::ClanSubUnit;
+use crate::game_objects::player::clan::{ClanPrivilege, ClanSubUnit};
 use crate::game_objects::player::effect::abnormal_effect::AbnormalVisualEffect;
 use crate::game_objects::player::inventory::Inventory;
 use crate::game_objects::player::paper_doll::PaperDoll;
@@ -13,17 +21,24 @@ use crate::game_objects::player::quest::Quest;
 use crate::game_objects::player::relation::RelationChanges;
 use crate::game_objects::player::vars::CharVariables;
 use crate::game_objects::player::warehouse::Warehouse;
-use crate::game_objects::player::{PlayerMacro, SubclassType, TeleportBookmark};
+use crate::game_objects::player::{
+    Contact, EnchantRequest, ManufactureShop, OlympiadState, PlayerMacro, Shortcut, ShortcutType, SubclassType,
+    TeleportBookmark,
+};
 use crate::game_objects::private_store_types::PrivateStoreType;
 use crate::game_objects::race::Race;
+use crate::game_objects::stats::calculator::{Modifier, ModifierSource};
 use crate::game_objects::stats::creature::CreatureStats;
 use crate::game_objects::stats::stat_enum::Stat;
+use crate::game_objects::summon::Summon;
 use crate::game_objects::zone::{Location, ZoneId};
 use crate::id_factory::{IdFactory, ObjectId};
-use chrono::Utc;
-use entities::entities::{character, character_mail, clan_ally, item};
+use chrono::{DateTime, Utc};
+use entities::dao::item::LocType;
+use entities::entities::{character, character_mail, character_subclass, clan_ally, item};
 use log::info;
 use serde_json::Value;
+use std::collections::{BTreeSet, HashSet};
 use std::fmt::Debug;
 use std::sync::Arc;
 
@@ -68,13 +83,54 @@ pub struct Player {
     pub clan: Option<clan_ally::Model>,
     pub template: Arc<CharTemplate>,
     pub siege_state: u8,
+    /// Set while the player fights in or watches an olympiad game
+    pub olympiad: Option<OlympiadState>,
     pub quest_zone_id: Option<i32>,
     pub stats: CreatureStats,
     pub skill_reused: Vec<SkillReuse>,
+    pub teleport_bookmarks: Vec<TeleportBookmark>,
+    pub macros: Vec<PlayerMacro>,
+    /// Shortcuts of the active class
+    pub shortcuts: Vec<Shortcut>,
+    /// Symbols drawn on the active class, index is the slot - 1
+    hennas: [Option<HennaTemplate>; Player::MAX_HENNA_SLOTS],
+    pub friends: Vec<Contact>,
+    pub block_list: Vec<Contact>,
+    charged_shots: HashSet<ShotType>,
+    /// Item ids of shots with enabled auto-use
+    auto_soulshots: HashSet<i32>,
+    zones: HashSet<ZoneId>,
+    /// Set from the teleport until the client reports it has appeared at the destination
+    teleporting: bool,
+    /// When the premium of the account runs out, None without premium
+    pub premium_until: Option<DateTime<Utc>>,
+    /// When the hero status runs out, None for players who are not heroes
+    pub hero_until: Option<DateTime<Utc>>,
+    /// The cursed weapon the player owns
+    pub cursed_weapon: Option<OwnedCursedWeapon>,
+    /// Set while the player is fishing
+    pub fishing: Option<Fishing>,
+    /// The servitor or pet the player has summoned
+    pub summon: Option<Summon>,
+    /// The pet the player rides
+    pub mount: Option<Mount>,
+    /// Ids of the recipes the player has learned, dwarven and common ones
+    pub recipe_book: BTreeSet<i32>,
+    private_store_type: PrivateStoreType,
+    /// What the manufacture shop offers, kept when the shop closes
+    pub manufacture: ManufactureShop,
+    /// Set while the player enchants an item
+    pub enchant_request: Option<EnchantRequest>,
 }
 
 #[allow(clippy::missing_errors_doc)]
 impl Player {
+    pub const MAX_BOOKMARK_SLOTS: i16 = 9;
+    pub const MAX_HENNA_SLOTS: usize = 3;
+    pub const MAX_FRIENDS: usize = 128;
+    pub const MAX_BLOCKED: usize = 128;
+    const HENNA_STATS: [Stat; 6] = [Stat::Str, Stat::Con, Stat::Dex, Stat::Int, Stat::Wit, Stat::Men];
+
     /// # Panics
     /// - when a char template is different from `class_id` in a user model
     #[must_use]
@@ -84,11 +140,94 @@ impl Player {
         template: Arc<CharTemplate>,
         skills: Option<Vec<Skill>>,
     ) -> Self {
+        let (stored, items) = items
+            .into_iter()
+            .partition(|i| i.loc == LocType::Warehouse);
+        let warehouse_limit = if Race::try_from(char_model.race_id).is_ok_and(|r| r == Race::Dwarf)
+        {
+            Warehouse::DWARF_PRIVATE_LIMIT
+        } else {
+            Warehouse::PRIVATE_LIMIT
+        };
         let inventory = Inventory::from_items(items);
         let paperdoll = PaperDoll::restore_visible_inventory(&inventory.items);
         assert_eq!(char_model.class_id, template.class_id as i8);
         let object_id = IdFactory::instance().get_next_id();
         let mut stats = CreatureStats::new();
+        Self::init_base_stats(&mut stats, &template);
+        stats.update_cache();
+        // Seed runtime HP/MP/CP from the persisted character; fall back to the
+        // maximums for models that don't carry current values (e.g. test fixtures).
+        stats.current_hp = if char_model.cur_hp > 0.0 {
+            char_model.cur_hp
+        } else {
+            stats.get_stat(Stat::MaxHp)
+        };
+        stats.current_mp = if char_model.cur_mp > 0.0 {
+            char_model.cur_mp
+        } else {
+            char_model.max_mp
+        };
+        stats.current_cp = char_model.cur_cp;
+
+        Self {
+            object_id,
+            location: Location {
+                x: char_model.x,
+                y: char_model.y,
+                z: char_model.z,
+                heading: 0,
+            },
+            clan: None,
+            template,
+            warehouse: Warehouse::from_items(stored, warehouse_limit),
+            sub_classes: vec![Subclass {
+                index: 0,
+                class_id: char_model.class_id,
+                level: char_model.level,
+                exp: char_model.exp,
+                sp: char_model.sp,
+                class_type: SubclassType::BaseClass,
+            }],
+            char_model,
+            party: None,
+            paperdoll,
+            quests: Vec::new(),
+            team: Team::None,
+            skills,
+            mailbox: Vec::new(),
+            siege_state: 0,
+            olympiad: None,
+            appearance: Appearance,
+            quest_zone_id: None,
+            inventory,
+            stats,
+            skill_reused: vec![],
+            teleport_bookmarks: Vec::new(),
+            macros: Vec::new(),
+            shortcuts: Vec::new(),
+            hennas: Default::default(),
+            friends: Vec::new(),
+            block_list: Vec::new(),
+            charged_shots: HashSet::new(),
+            auto_soulshots: HashSet::new(),
+            zones: HashSet::new(),
+            teleporting: false,
+            premium_until: None,
+            hero_until: None,
+            cursed_weapon: None,
+            fishing: None,
+            summon: None,
+            mount: None,
+            recipe_book: BTreeSet::new(),
+            private_store_type: PrivateStoreType::None,
+            manufacture: ManufactureShop::default(),
+            enchant_request: None,
+        }
+    }
+
+    /// Base values of the stats taken from the class template
+    fn init_base_stats(stats: &mut CreatureStats, template: &CharTemplate) {
         stats
             .calculator
             .base_values
@@ -139,54 +278,6 @@ impl Player {
             .calculator
             .base_values
             .insert(Stat::MCriticalDamage, 2.0);
-
-        stats.update_cache();
-        // Seed runtime HP/MP/CP from the persisted character; fall back to the
-        // maximums for models that don't carry current values (e.g. test fixtures).
-        stats.current_hp = if char_model.cur_hp > 0.0 {
-            char_model.cur_hp
-        } else {
-            stats.get_stat(Stat::MaxHp)
-        };
-        stats.current_mp = if char_model.cur_mp > 0.0 {
-            char_model.cur_mp
-        } else {
-            char_model.max_mp
-        };
-        stats.current_cp = char_model.cur_cp;
-
-        Self {
-            object_id,
-            location: Location {
-                x: char_model.x,
-                y: char_model.y,
-                z: char_model.z,
-                heading: 0,
-            },
-            clan: None,
-            template,
-            warehouse: Warehouse::empty(), //todo: fill it
-            //todo: implement subclasses
-            sub_classes: vec![Subclass {
-                index: 0,
-                class_id: char_model.class_id,
-                level: char_model.level,
-                class_type: SubclassType::BaseClass,
-            }],
-            char_model,
-            party: None,
-            paperdoll,
-            quests: Vec::new(),
-            team: Team::None,
-            skills,
-            mailbox: Vec::new(),
-            siege_state: 0,
-            appearance: Appearance,
-            quest_zone_id: None,
-            inventory,
-            stats,
-            skill_reused: vec![],
-        }
     }
 
     #[must_use]
@@ -234,14 +325,156 @@ impl Player {
 
     #[must_use]
     pub fn get_macros(&self) -> &Vec<PlayerMacro> {
-        //todo: implement me
-        static EMPTY: Vec<PlayerMacro> = Vec::new();
-        &EMPTY
+        &self.macros
+    }
+    #[must_use]
+    pub fn get_macro(&self, id: i32) -> Option<&PlayerMacro> {
+        self.macros.iter().find(|m| m.id == id)
+    }
+    /// Adds a new macro (id 0) or replaces an existing one.
+    /// Returns `None` when a new macro doesn't fit, otherwise the saved macro and
+    /// whether it's new.
+    pub fn register_macro(&mut self, mut m: PlayerMacro) -> Option<(&PlayerMacro, bool)> {
+        if let Some(idx) = self.macros.iter().position(|old| old.id == m.id) {
+            self.macros[idx] = m;
+            return Some((&self.macros[idx], false));
+        }
+        if self.macros.len() >= PlayerMacro::MAX_MACROS {
+            return None;
+        }
+        if m.id == 0 {
+            m.id = (PlayerMacro::FIRST_ID..)
+                .find(|id| self.get_macro(*id).is_none())
+                .unwrap_or_default();
+        }
+        self.macros.push(m);
+        self.macros.last().map(|m| (m, true))
+    }
+    pub fn delete_macro(&mut self, id: i32) -> Option<PlayerMacro> {
+        let idx = self.macros.iter().position(|m| m.id == id)?;
+        Some(self.macros.remove(idx))
+    }
+
+    #[must_use]
+    pub fn get_class_index(&self) -> i32 {
+        self.sub_classes
+            .iter()
+            .find(|s| s.class_id == self.char_model.class_id)
+            .map_or(0, |s| s.index)
     }
+    #[must_use]
+    pub fn get_shortcuts(&self) -> &Vec<Shortcut> {
+        &self.shortcuts
+    }
+    /// Puts the shortcut into its slot, replacing the old one.
+    pub fn register_shortcut(&mut self, sc: Shortcut) {
+        self.shortcuts
+            .retain(|old| old.slot != sc.slot || old.page != sc.page);
+        self.shortcuts.push(sc);
+    }
+    pub fn delete_shortcut(&mut self, slot: i16, page: i16) -> Option<Shortcut> {
+        let idx = self
+            .shortcuts
+            .iter()
+            .position(|sc| sc.slot == slot && sc.page == page)?;
+        Some(self.shortcuts.remove(idx))
+    }
+    /// Removes all shortcuts pointing to the object (e.g. a deleted macro).
+    pub fn delete_shortcuts_to(&mut self, shortcut_type: ShortcutType, id: i32) -> Vec<Shortcut> {
+        let (removed, kept) = std::mem::take(&mut self.shortcuts)
+            .into_iter()
+            .partition(|sc| sc.shortcut_type == shortcut_type && sc.id == id);
+        self.shortcuts = kept;
+        removed
+    }
+    /// Symbols the class can have: none before the first class change,
+    /// 2 with the first profession and 3 after the second one.
+    #[must_use]
+    pub fn get_henna_slots(&self) -> u32 {
+        match Class::try_from(self.char_model.class_id).map(Class::level) {
+            Ok(1) => 2,
+            Ok(level) if level > 1 => 3,
+            _ => 0,
+        }
+    }
+
     #[must_use]
     pub fn get_henna_empty_slots(&self) -> u32 {
-        //todo: implement me
-        0
+        let used = self.hennas.iter().flatten().count();
+        self.get_henna_slots()
+            .saturating_sub(u32::try_from(used).unwrap_or(u32::MAX))
+    }
+
+    pub fn get_hennas(&self) -> impl Iterator<Item = &HennaTemplate> {
+        self.hennas.iter().flatten()
+    }
+
+    /// Slot (starting from 1) of the drawn symbol
+    #[must_use]
+    pub fn get_henna_slot(&self, dye_id: i32) -> Option<i16> {
+        self.hennas
+            .iter()
+            .position(|h| h.as_ref().is_some_and(|h| h.dye_id == dye_id))
+            .and_then(|i| i16::try_from(i + 1).ok())
+    }
+
+    /// Total stat modifiers of all the drawn symbols.
+    #[must_use]
+    pub fn get_henna_stats(&self) -> HennaStats {
+        HennaStats::sum(self.get_hennas().map(|h| &h.stats))
+    }
+
+    /// Puts the symbol into a slot (starting from 1) without any checks, used on restore.
+    pub fn set_henna(&mut self, slot: i16, henna: HennaTemplate) -> anyhow::Result<()> {
+        let index = usize::try_from(slot - 1)?;
+        let place = self
+            .hennas
+            .get_mut(index)
+            .ok_or_else(|| anyhow::anyhow!("Henna slot {slot} is out of range"))?;
+        *place = Some(henna);
+        self.apply_henna_stats();
+        Ok(())
+    }
+
+    /// Draws the symbol into the first free slot, returns the slot (starting from 1)
+    /// or `None` when the class has no free slots.
+    pub fn add_henna(&mut self, henna: HennaTemplate) -> Option<i16> {
+        if self.get_henna_empty_slots() == 0 {
+            return None;
+        }
+        let index = self.hennas.iter().position(Option::is_none)?;
+        self.hennas[index] = Some(henna);
+        self.apply_henna_stats();
+        i16::try_from(index + 1).ok()
+    }
+
+    pub fn remove_henna(&mut self, slot: i16) -> Option<HennaTemplate> {
+        let index = usize::try_from(slot - 1).ok()?;
+        let removed = self.hennas.get_mut(index)?.take();
+        if removed.is_some() {
+            self.apply_henna_stats();
+        }
+        removed
+    }
+
+    /// Replaces the henna modifiers of STR, CON, DEX, INT, WIT and MEN,
+    /// modifiers of other sources stay as they are.
+    fn apply_henna_stats(&mut self) {
+        let bonus = self.get_henna_stats();
+        let mods = Self::HENNA_STATS
+            .into_iter()
+            .filter(|stat| bonus.get(*stat) != 0)
+            .map(|stat| (stat, Modifier::Add(f64::from(bonus.get(stat)))))
+            .collect();
+        self.stats
+            .calculator
+            .set_source_modifiers(ModifierSource::Henna, mods);
+        self.stats.update_cache();
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn get_base_stat(&self, stat: Stat) -> u8 {
+        self.stats.get_stat(stat).clamp(0.0, f64::from(u8::MAX)) as u8
     }
 
     /// 1-7 increase force, level
@@ -259,8 +492,78 @@ impl Player {
     /// If the player doesn't want to receive messages at all
     #[must_use]
     pub fn block_all(&self) -> bool {
-        //todo: implement me
-        false
+        self.char_model
+            .variables
+            .get(CharVariables::MessageRefusal.as_key())
+            .and_then(Value::as_bool)
+            .unwrap_or(false)
+    }
+
+    pub fn set_block_all(&mut self, block_all: bool) {
+        if !self.char_model.variables.is_object() {
+            self.char_model.variables = Value::Object(serde_json::Map::new());
+        }
+        self.char_model.variables[CharVariables::MessageRefusal.as_key()] = Value::Bool(block_all);
+    }
+
+    #[must_use]
+    pub fn is_friend(&self, char_id: i32) -> bool {
+        self.friends.iter().any(|f| f.char_id == char_id)
+    }
+
+    #[must_use]
+    pub fn get_friend_by_name(&self, name: &str) -> Option<&Contact> {
+        self.friends
+            .iter()
+            .find(|f| f.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Returns false if the friend list is full or the char is already there
+    pub fn add_friend(&mut self, friend: Contact) -> bool {
+        if self.friends.len() >= Self::MAX_FRIENDS || self.is_friend(friend.char_id) {
+            return false;
+        }
+        self.friends.push(friend);
+        true
+    }
+
+    pub fn remove_friend(&mut self, char_id: i32) -> Option<Contact> {
+        let index = self.friends.iter().position(|f| f.char_id == char_id)?;
+        Some(self.friends.remove(index))
+    }
+
+    /// If the char is on the block list
+    #[must_use]
+    pub fn is_blocked(&self, char_id: i32) -> bool {
+        self.block_list.iter().any(|b| b.char_id == char_id)
+    }
+
+    /// If messages, trade and party requests from the char must be refused,
+    /// either because of the block list or because everything is blocked
+    #[must_use]
+    pub fn is_blocking(&self, char_id: i32) -> bool {
+        self.block_all() || self.is_blocked(char_id)
+    }
+
+    #[must_use]
+    pub fn get_blocked_by_name(&self, name: &str) -> Option<&Contact> {
+        self.block_list
+            .iter()
+            .find(|b| b.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Returns false if the block list is full or the char is already blocked
+    pub fn block(&mut self, contact: Contact) -> bool {
+        if self.block_list.len() >= Self::MAX_BLOCKED || self.is_blocked(contact.char_id) {
+            return false;
+        }
+        self.block_list.push(contact);
+        true
+    }
+
+    pub fn unblock(&mut self, char_id: i32) -> Option<Contact> {
+        let index = self.block_list.iter().position(|b| b.char_id == char_id)?;
+        Some(self.block_list.remove(index))
     }
     #[must_use]
     pub fn chat_banned(&self) -> bool {
@@ -279,8 +582,7 @@ impl Player {
     }
     #[must_use]
     pub fn is_in_olympiad_mode(&self) -> bool {
-        //todo: implement me
-        false
+        self.olympiad.as_ref().is_some_and(OlympiadState::is_fighter)
     }
     #[must_use]
     pub fn is_alike_dead(&self) -> bool {
@@ -288,9 +590,17 @@ impl Player {
         self.is_dead()
     }
     #[must_use]
-    pub fn is_auto_attackable(&self, _another: &Player) -> bool {
-        //todo: implement me
-        false
+    pub fn is_auto_attackable(&self, another: &Player) -> bool {
+        //todo: implement me, pvp flags, karma and clan wars
+        if let (Some(mine), Some(theirs)) = (&self.olympiad, &another.olympiad) {
+            return mine.is_opponent(theirs);
+        }
+        // the sides of a siege fight each other on the battlefield
+        self.siege_state != 0
+            && another.siege_state != 0
+            && self.siege_state != another.siege_state
+            && self.is_inside_zone(ZoneId::Siege)
+            && another.is_inside_zone(ZoneId::Siege)
     }
 
     #[must_use]
@@ -380,34 +690,28 @@ impl Player {
 
     #[must_use]
     pub fn get_str(&self) -> u8 {
-        //todo: implement me
-        self.template.static_data.base_str
+        self.get_base_stat(Stat::Str)
     }
     #[must_use]
     pub fn get_con(&self) -> u8 {
-        //todo: implement me
-        self.template.static_data.base_con
+        self.get_base_stat(Stat::Con)
     }
     #[must_use]
     pub fn get_dex(&self) -> u8 {
-        //todo: implement me
-        self.template.static_data.base_dex
+        self.get_base_stat(Stat::Dex)
     }
     #[must_use]
     pub fn get_men(&self) -> u8 {
-        //todo: implement me
-        self.template.static_data.base_men
+        self.get_base_stat(Stat::Men)
     }
 
     #[must_use]
     pub fn get_int(&self) -> u8 {
-        //todo: implement me
-        self.template.static_data.base_int
+        self.get_base_stat(Stat::Int)
     }
     #[must_use]
     pub fn get_wit(&self) -> u8 {
-        //todo: implement me
-        self.template.static_data.base_wit
+        self.get_base_stat(Stat::Wit)
     }
 
     #[must_use]
@@ -433,13 +737,11 @@ impl Player {
     }
     #[must_use]
     pub fn is_noble(&self) -> bool {
-        // todo: implement me
-        false
+        self.char_model.nobless
     }
     #[must_use]
     pub fn is_hero(&self) -> bool {
-        // todo: implement me
-        false
+        self.hero_until.is_some()
     }
     #[must_use]
     pub fn get_pledge_type(&self) -> i16 {
@@ -453,19 +755,24 @@ impl Player {
     }
 
     #[must_use]
-    pub fn get_recommendations_left(&self) -> u16 {
-        //todo: implement me
-        0
+    pub fn is_inside_zone(&self, zone: ZoneId) -> bool {
+        self.zones.contains(&zone)
     }
+
     #[must_use]
-    pub fn get_recommendations_have(&self) -> u16 {
-        //todo: implement me
-        0
+    pub fn is_teleporting(&self) -> bool {
+        self.teleporting
     }
-    #[must_use]
-    pub fn is_inside_zone(&self, _zone: ZoneId) -> bool {
-        //todo: implement me
-        false
+    pub fn set_teleporting(&mut self, teleporting: bool) {
+        self.teleporting = teleporting;
+    }
+
+    /// Replaces zone flags of the player, returns `(entered, exited)` zones.
+    pub fn set_zones(&mut self, zones: HashSet<ZoneId>) -> (Vec<ZoneId>, Vec<ZoneId>) {
+        let entered = zones.difference(&self.zones).copied().collect();
+        let exited = self.zones.difference(&zones).copied().collect();
+        self.zones = zones;
+        (entered, exited)
     }
 
     #[must_use]
@@ -501,8 +808,17 @@ impl Player {
 
     #[must_use]
     pub fn is_clan_leader(&self) -> bool {
-        //todo: implement me
-        false
+        self.clan
+            .as_ref()
+            .is_some_and(|c| c.leader_id == self.char_model.id)
+    }
+
+    /// The leader has all the rights, the members have the ones of their rank.
+    #[must_use]
+    pub fn has_clan_privilege(&self, privilege: ClanPrivilege) -> bool {
+        self.clan.is_some()
+            && (self.is_clan_leader()
+                || privilege.is_granted(self.char_model.clan_privs.unwrap_or(0)))
     }
     #[must_use]
     pub fn get_visible_name_length(&self) -> usize {
@@ -532,6 +848,142 @@ impl Player {
         &self.sub_classes
     }
 
+    #[must_use]
+    pub fn get_subclass(&self, index: i32) -> Option<&Subclass> {
+        self.sub_classes.iter().find(|s| s.index == index)
+    }
+
+    /// Restores the classes saved in DB. The progress of the active class is kept in the char,
+    /// the saved base class (index 0) is only there while a subclass is active.
+    pub fn set_subclasses(&mut self, models: &[character_subclass::Model]) {
+        let mut subs: Vec<Subclass> = models.iter().map(Subclass::from_model).collect();
+        if !subs.iter().any(|s| s.index == 0) {
+            subs.insert(
+                0,
+                Subclass {
+                    index: 0,
+                    class_id: self.char_model.class_id,
+                    level: self.char_model.level,
+                    exp: self.char_model.exp,
+                    sp: self.char_model.sp,
+                    class_type: SubclassType::BaseClass,
+                },
+            );
+        }
+        self.sub_classes = subs;
+        self.sync_active_class();
+    }
+
+    /// Copies level, exp and sp of the char into its active class.
+    pub fn sync_active_class(&mut self) {
+        let index = self.get_class_index();
+        let model = &self.char_model;
+        if let Some(active) = self.sub_classes.iter_mut().find(|s| s.index == index) {
+            active.level = model.level;
+            active.exp = model.exp;
+            active.sp = model.sp;
+        }
+    }
+
+    /// Subclasses only, without the base class
+    #[must_use]
+    pub fn get_subclass_count(&self) -> usize {
+        self.sub_classes.iter().filter(|s| s.index > 0).count()
+    }
+
+    /// Free index for a new subclass
+    #[must_use]
+    pub fn get_free_class_index(&self) -> Option<i32> {
+        (1..=i32::try_from(Subclass::MAX_SUBCLASSES).ok()?)
+            .find(|i| self.get_subclass(*i).is_none())
+    }
+
+    /// A subclass is added when all the classes of the char have reached the required level,
+    /// only nobles can have more than one subclass.
+    #[must_use]
+    pub fn can_add_subclass(&self) -> bool {
+        let count = self.get_subclass_count();
+        count < Subclass::MAX_SUBCLASSES
+            && (count == 0 || self.is_noble())
+            && self
+                .sub_classes
+                .iter()
+                .all(|s| s.level >= Subclass::REQUIRED_LEVEL)
+    }
+
+    /// Classes the char may take as a new subclass
+    #[must_use]
+    pub fn get_available_subclasses(&self) -> Vec<Class> {
+        let Ok(race) = self.try_get_race() else {
+            return Vec::new();
+        };
+        let owned: Vec<Class> = self
+            .sub_classes
+            .iter()
+            .filter_map(|s| Class::try_from(s.class_id).ok())
+            .collect();
+        (0..=u8::MAX)
+            .filter_map(|id| Class::try_from(id).ok())
+            .filter(|c| Subclass::is_allowed_class(*c, race, &owned))
+            .collect()
+    }
+
+    /// Adds a subclass, it doesn't become active.
+    pub fn add_subclass(&mut self, sub: Subclass) {
+        self.sub_classes.push(sub);
+    }
+
+    /// Removes an inactive subclass, the base class can't be removed.
+    pub fn remove_subclass(&mut self, index: i32) -> Option<Subclass> {
+        if index == 0 || index == self.get_class_index() {
+            return None;
+        }
+        let pos = self.sub_classes.iter().position(|s| s.index == index)?;
+        Some(self.sub_classes.remove(pos))
+    }
+
+    /// Makes the class active: level, exp, sp, template and stats come from it. Effects,
+    /// skills, shortcuts and hennas are cleared, the caller restores those of the class
+    /// from DB. Returns the effects of the old class for the caller to keep.
+    ///
+    /// # Errors
+    /// - when the char has no class with the index or the template is of another class
+    pub fn set_active_class(
+        &mut self,
+        index: i32,
+        template: Arc<CharTemplate>,
+        base_stats: &BaseStat,
+    ) -> anyhow::Result<Vec<AppliedBuff>> {
+        let Some(target) = self.get_subclass(index).cloned() else {
+            anyhow::bail!("Char {} has no class {index}", self.char_model.id);
+        };
+        if template.class_id as i8 != target.class_id {
+            anyhow::bail!("Template {:?} is not of class {}", template.class_id, target.class_id);
+        }
+        self.sync_active_class();
+        let model = &mut self.char_model;
+        model.class_id = target.class_id;
+        model.level = target.level;
+        model.exp = target.exp;
+        model.sp = target.sp;
+        template.set_max_parameters(model, base_stats)?;
+        model.cur_hp = model.cur_hp.min(model.max_hp);
+        model.cur_mp = model.cur_mp.min(model.max_mp);
+        model.cur_cp = model.cur_cp.min(model.max_cp);
+        let buffs = std::mem::take(&mut self.stats.active_buffs);
+        self.stats.calculator.base_values.clear();
+        Self::init_base_stats(&mut self.stats, &template);
+        self.template = template;
+        self.stats.current_hp = self.char_model.cur_hp;
+        self.stats.current_mp = self.char_model.cur_mp;
+        self.stats.current_cp = self.char_model.cur_cp;
+        self.skills = Some(Vec::new());
+        self.shortcuts.clear();
+        self.hennas = Default::default();
+        self.apply_henna_stats();
+        Ok(buffs)
+    }
+
     #[must_use]
     pub fn is_dead(&self) -> bool {
         self.char_model.cur_hp <= 0.5
@@ -556,65 +1008,93 @@ impl Player {
     }
     #[must_use]
     pub fn get_run_speed(&self) -> u16 {
+        if let Some(mount) = self.mount {
+            return mount.run_speed;
+        }
         //todo: implement me
         159
     }
     #[must_use]
     pub fn get_walk_speed(&self) -> u16 {
+        if let Some(mount) = self.mount {
+            return mount.walk_speed;
+        }
         //todo: implement me
         113
     }
 
+    /// Speeds in the air, 0 on the ground.
     #[must_use]
     pub fn get_fly_run_speed(&self) -> u16 {
-        //todo: implement me
-        159
+        if self.is_flying() {
+            self.get_run_speed()
+        } else {
+            0
+        }
     }
     #[must_use]
     pub fn get_fly_walk_speed(&self) -> u16 {
-        //todo: implement me
-        113
+        if self.is_flying() {
+            self.get_walk_speed()
+        } else {
+            0
+        }
     }
 
     #[must_use]
     pub fn is_flying(&self) -> bool {
-        //todo: implement me
-        false
+        //todo: flying transformations
+        self.is_flying_mounted()
     }
 
     #[must_use]
     pub fn is_fishing(&self) -> bool {
-        //todo: implement me
-        false
+        self.fishing.is_some()
     }
     #[must_use]
     pub fn get_fishing_bait_location(&self) -> Option<&Location> {
-        //todo: implement me
-        None
+        self.fishing.as_ref().map(|f| &f.bait)
+    }
+
+    #[must_use]
+    pub fn has_summon(&self) -> bool {
+        self.summon.is_some()
+    }
+    /// If the object is the summon of the player
+    #[must_use]
+    pub fn is_own_summon(&self, object_id: i32) -> bool {
+        self.summon
+            .as_ref()
+            .is_some_and(|s| s.get_object_id() == object_id)
+    }
+    /// If the item is the collar of the pet which is out, it can't leave the owner then
+    #[must_use]
+    pub fn is_summoned_collar(&self, item: &ItemObject) -> bool {
+        self.summon
+            .as_ref()
+            .and_then(|s| s.control_item_id)
+            .is_some_and(|id| id == item.item_model.id)
     }
 
     #[must_use]
     pub fn is_flying_mounted(&self) -> bool {
-        //todo: implement me
-        false
+        self.mount.as_ref().is_some_and(Mount::is_flying)
     }
 
     #[must_use]
     pub fn get_mount_npc_id(&self) -> i32 {
-        //todo: implement me
-        0
+        self.mount.map_or(0, |m| m.npc_id)
     }
 
     #[must_use]
     pub fn is_sitting(&self) -> bool {
-        //todo: implement me
-        false
+        //todo: sitting down without a store
+        self.private_store_type != PrivateStoreType::None
     }
 
     #[must_use]
     pub fn get_cursed_weapon(&self) -> Option<&dyn CursedWeapon> {
-        //todo: implement me
-        None
+        self.cursed_weapon.as_ref().map(|c| c as &dyn CursedWeapon)
     }
     #[must_use]
     pub fn is_running(&self) -> bool {
@@ -632,11 +1112,27 @@ impl Player {
         //todo: implement me
         85
     }
+    /// Current movement speed, swim speeds are used in water and fly speeds in the air.
+    #[must_use]
+    pub fn get_move_speed(&self) -> u16 {
+        if self.is_flying() {
+            return if self.is_running() {
+                self.get_fly_run_speed()
+            } else {
+                self.get_fly_walk_speed()
+            };
+        }
+        match (self.is_inside_zone(ZoneId::Water), self.is_running()) {
+            (true, true) => self.get_swim_run_speed(),
+            (true, false) => self.get_swim_walk_speed(),
+            (false, true) => self.get_run_speed(),
+            (false, false) => self.get_walk_speed(),
+        }
+    }
     #[must_use]
     pub fn get_mount_type(&self) -> u8 {
-        //todo: implement me
         // 1-on Strider, 2-on Wyvern, 3-on Great Wolf, 0-no mount
-        0
+        self.mount.map_or(0, |m| m.kind as u8)
     }
     #[must_use]
     pub fn get_team(&self) -> Team {
@@ -654,8 +1150,18 @@ impl Player {
     }
     #[must_use]
     pub fn has_premium(&self) -> bool {
-        //todo: implement me
-        false
+        self.premium_until.is_some()
+    }
+
+    /// Exp, sp or an item count multiplied by the premium rate, if the account has premium.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    pub fn apply_premium_rate(&self, value: i64, rate: f64) -> i64 {
+        if self.has_premium() {
+            (value as f64 * rate) as i64
+        } else {
+            value
+        }
     }
 
     #[must_use]
@@ -672,8 +1178,11 @@ impl Player {
 
     #[must_use]
     pub fn get_private_store_type(&self) -> PrivateStoreType {
-        //todo: implement me
-        PrivateStoreType::None
+        self.private_store_type
+    }
+
+    pub fn set_private_store_type(&mut self, store_type: PrivateStoreType) {
+        self.private_store_type = store_type;
     }
     #[must_use]
     pub fn get_private_store_sell_limit(&self) -> u8 {
@@ -703,6 +1212,37 @@ impl Player {
         100
     }
 
+    #[must_use]
+    pub fn get_quest(&self, quest_id: i32) -> Option<&Quest> {
+        self.quests.iter().find(|q| q.get_id() == quest_id)
+    }
+
+    pub fn get_quest_mut(&mut self, quest_id: i32) -> Option<&mut Quest> {
+        self.quests.iter_mut().find(|q| q.get_id() == quest_id)
+    }
+
+    /// Quests in progress, finished ones don't count towards the limit
+    #[must_use]
+    pub fn get_active_quest_count(&self) -> usize {
+        self.quests.iter().filter(|q| q.is_started()).count()
+    }
+
+    /// Adds exp and sp, the level follows the exp table. Returns the new level when it changed.
+    pub fn add_exp_and_sp(&mut self, exp: i64, sp: i64, exp_table: &ExpTable) -> Option<u8> {
+        let model = &mut self.char_model;
+        model.exp = (model.exp + exp).clamp(0, exp_table.get_exp(exp_table.max_level));
+        model.sp = (model.sp + sp).max(0);
+        let old_level = model.level;
+        while model.level < exp_table.max_level
+            && model.exp >= exp_table.get_exp_for_next_lvl(model.level)
+        {
+            model.level += 1;
+        }
+        let new_level = model.level;
+        self.sync_active_class();
+        (new_level != old_level).then_some(new_level)
+    }
+
     #[must_use]
     pub fn get_p_attack(&self) -> u32 {
         //todo: implement me
@@ -900,8 +1440,7 @@ impl Player {
 impl Player {
     #[must_use]
     pub fn get_transformation_display_id(&self) -> i32 {
-        //todo: implement me
-        0
+        self.get_transform_id()
     }
     #[must_use]
     pub fn get_agation_id(&self) -> i32 {
@@ -924,6 +1463,30 @@ impl Player {
         self.char_model.max_cp
     }
 
+    /// Olympiad games start from the same ground for both sides: effects are removed
+    /// and HP, MP and CP are full. Returns the removed effects.
+    pub fn reset_for_olympiad(&mut self) -> Vec<AppliedBuff> {
+        let effects = std::mem::take(&mut self.stats.active_buffs);
+        self.stats.update_cache();
+        self.stats.current_hp = self.get_max_hp();
+        self.stats.current_mp = self.get_max_mp();
+        self.stats.current_cp = self.get_max_cp();
+        self.sync_vitals_to_model();
+        effects
+    }
+
+    /// Ends the olympiad mode, a fighter recovers and gets the effects of before the game
+    /// back. The caller moves the player to [`OlympiadState::return_to`].
+    pub fn leave_olympiad(&mut self) -> Option<OlympiadState> {
+        let mut state = self.olympiad.take()?;
+        if state.is_fighter() {
+            self.reset_for_olympiad();
+            self.stats.active_buffs = std::mem::take(&mut state.saved_effects);
+            self.stats.update_cache();
+        }
+        Some(state)
+    }
+
     /// Copies the runtime HP/MP/CP into the persisted character model so packets
     /// built from the model and the logout save see the current values.
     pub fn sync_vitals_to_model(&mut self) {
@@ -1025,20 +1588,72 @@ impl Player {
             .unwrap_or(0)
     }
     #[must_use]
-    pub fn get_vitality_bonus(&self) -> u32 {
-        // todo: implement me
-        100
+    pub fn get_unread_mail_count(&self) -> usize {
+        self.mailbox.iter().filter(|m| m.is_unread).count()
     }
+
     #[must_use]
     pub fn get_teleport_bookmarks(&self) -> &Vec<TeleportBookmark> {
-        //todo: implement me
-        static EMPTY: Vec<TeleportBookmark> = Vec::new();
-        &EMPTY
+        &self.teleport_bookmarks
+    }
+    #[must_use]
+    pub fn get_teleport_bookmark(&self, id: i32) -> Option<&TeleportBookmark> {
+        self.teleport_bookmarks.iter().find(|b| b.id == id)
     }
     #[must_use]
     pub fn get_bookmark_slot(&self) -> i32 {
-        // todo: implement me
-        0
+        i32::from(self.char_model.bookmark_slot)
+    }
+    /// Adds slots for teleport bookmarks, returns false when the limit is already reached.
+    pub fn increase_bookmark_slot(&mut self, count: i16) -> bool {
+        if self.char_model.bookmark_slot >= Self::MAX_BOOKMARK_SLOTS {
+            return false;
+        }
+        self.char_model.bookmark_slot =
+            (self.char_model.bookmark_slot + count).min(Self::MAX_BOOKMARK_SLOTS);
+        true
+    }
+    /// Bookmarks the current location, returns `None` when there are no free slots.
+    pub fn add_teleport_bookmark(
+        &mut self,
+        name: String,
+        icon: i32,
+        tag: String,
+    ) -> Option<&TeleportBookmark> {
+        if self.teleport_bookmarks.len() >= usize::try_from(self.get_bookmark_slot()).ok()? {
+            return None;
+        }
+        let id = (1..)
+            .find(|id| self.get_teleport_bookmark(*id).is_none())
+            .unwrap_or_default();
+        self.teleport_bookmarks.push(TeleportBookmark {
+            id,
+            x: self.location.x,
+            y: self.location.y,
+            z: self.location.z,
+            name,
+            icon,
+            tag,
+        });
+        self.teleport_bookmarks.last()
+    }
+    pub fn modify_teleport_bookmark(
+        &mut self,
+        id: i32,
+        name: String,
+        icon: i32,
+        tag: String,
+    ) -> Option<&TeleportBookmark> {
+        let bookmark = self.teleport_bookmarks.iter_mut().find(|b| b.id == id)?;
+        bookmark.name = name;
+        bookmark.icon = icon;
+        bookmark.tag = tag;
+        Some(bookmark)
+    }
+    pub fn remove_teleport_bookmark(&mut self, id: i32) -> bool {
+        let len = self.teleport_bookmarks.len();
+        self.teleport_bookmarks.retain(|b| b.id != id);
+        len != self.teleport_bookmarks.len()
     }
 
     #[must_use]
@@ -1074,6 +1689,37 @@ impl Player {
         })
     }
 
+    #[must_use]
+    pub fn is_charged_shot(&self, shot: ShotType) -> bool {
+        self.charged_shots.contains(&shot)
+    }
+
+    pub fn set_charged_shot(&mut self, shot: ShotType, charged: bool) {
+        if charged {
+            self.charged_shots.insert(shot);
+        } else {
+            self.charged_shots.remove(&shot);
+        }
+    }
+
+    /// Uncharges the shot, returns true if it was charged (so the hit is boosted).
+    pub fn consume_charged_shot(&mut self, shot: ShotType) -> bool {
+        self.charged_shots.remove(&shot)
+    }
+
+    #[must_use]
+    pub fn get_auto_soulshots(&self) -> &HashSet<i32> {
+        &self.auto_soulshots
+    }
+
+    pub fn add_auto_soulshot(&mut self, item_id: i32) {
+        self.auto_soulshots.insert(item_id);
+    }
+
+    pub fn remove_auto_soulshot(&mut self, item_id: i32) -> bool {
+        self.auto_soulshots.remove(&item_id)
+    }
+
     #[must_use]
     pub fn get_skill_level(&self, skill_id: i32) -> Option<i16> {
         self.skills.as_ref().and_then(|skills| {
@@ -1113,8 +1759,10 @@ impl Player {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::traits::ConfigDirLoader;
+    use crate::config::traits::{ConfigDirLoader, ConfigFileLoader};
     use crate::data::char_template::ClassTemplates;
+    use crate::data::summons::MountType;
+    use crate::game_objects::player::OlympiadRole;
     use entities::dao::item::{ItemVariables, ItemVariations, LocType};
     use entities::test_factories::factories::{char_factory, item_factory, user_factory};
     use serde_json::json;
@@ -1300,4 +1948,394 @@ mod tests {
         assert_eq!(char_info.get_delete_timer(), 0);
         assert_eq!(char_info.get_vitality_used(), 10);
     }
+
+    #[tokio::test]
+    async fn test_shots_charge_and_consume() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let char = char_factory(&db_pool, |mut ch| {
+            ch.user_id = user.id;
+            ch
+        })
+        .await;
+        let shots = item_factory(&db_pool, |mut it| {
+            it.owner = char.id;
+            it.item_id = 1835;
+            it.count = 3;
+            it.enchant_level = 0;
+            it.loc = LocType::Inventory;
+            it.loc_data = 0;
+            it
+        })
+        .await;
+        let templates = ClassTemplates::load();
+        let temp = templates.try_get_template(char.class_id).unwrap();
+        let mut player = Player::new(char, vec![shots], temp.clone(), None);
+
+        player.add_auto_soulshot(1835);
+        assert!(player.get_auto_soulshots().contains(&1835));
+        assert!(!player.consume_charged_shot(ShotType::Soulshots));
+        player.set_charged_shot(ShotType::Soulshots, true);
+        assert!(player.is_charged_shot(ShotType::Soulshots));
+        assert!(player.consume_charged_shot(ShotType::Soulshots));
+        assert!(!player.is_charged_shot(ShotType::Soulshots));
+
+        let obj_id = player
+            .inventory
+            .get_item_by_item_id(1835)
+            .unwrap()
+            .object_id
+            .value();
+        let left = player.inventory.reduce_count(obj_id, 2).unwrap();
+        assert_eq!(left.item_model.count, 1);
+        assert!(player.inventory.reduce_count(obj_id, 2).is_err());
+        let left = player.inventory.reduce_count(obj_id, 1).unwrap();
+        assert_eq!(left.item_model.count, 0);
+        assert!(player.inventory.get_item_by_item_id(1835).is_none());
+        assert!(player.remove_auto_soulshot(1835));
+    }
+
+    #[tokio::test]
+    async fn test_zones_enter_exit() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let char = char_factory(&db_pool, |mut ch| {
+            ch.user_id = user.id;
+            ch
+        })
+        .await;
+        let templates = ClassTemplates::load();
+        let temp = templates.try_get_template(char.class_id).unwrap();
+        let mut player = Player::new(char, vec![], temp.clone(), None);
+        let land_speed = player.get_move_speed();
+
+        let (entered, exited) = player.set_zones(HashSet::from([ZoneId::Water]));
+        assert_eq!(entered, vec![ZoneId::Water]);
+        assert!(exited.is_empty());
+        assert!(player.is_inside_zone(ZoneId::Water));
+        assert_eq!(player.get_move_speed(), player.get_swim_run_speed());
+
+        let (entered, exited) = player.set_zones(HashSet::from([ZoneId::Peace]));
+        assert_eq!(entered, vec![ZoneId::Peace]);
+        assert_eq!(exited, vec![ZoneId::Water]);
+        assert!(!player.is_inside_zone(ZoneId::Water));
+        assert_eq!(player.get_move_speed(), land_speed);
+    }
+
+    #[tokio::test]
+    async fn test_mount_speeds() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let char = char_factory(&db_pool, |mut ch| {
+            ch.user_id = user.id;
+            ch
+        })
+        .await;
+        let templates = ClassTemplates::load();
+        let temp = templates.try_get_template(char.class_id).unwrap();
+        let mut player = Player::new(char, vec![], temp.clone(), None);
+        assert!(!player.is_mounted());
+        assert_eq!(player.get_fly_run_speed(), 0);
+
+        let mut strider = Mount {
+            npc_id: 12526,
+            kind: MountType::Strider,
+            control_item_id: 7,
+            run_speed: 180,
+            walk_speed: 100,
+        };
+        player.mount = Some(strider);
+        assert!(player.is_mounted());
+        assert!(!player.is_flying());
+        assert_eq!(player.get_mount_type(), 1);
+        assert_eq!(player.get_mount_npc_id(), 12526);
+        assert_eq!(player.get_move_speed(), 180);
+        assert_eq!(player.get_fly_run_speed(), 0);
+
+        strider.kind = MountType::Wyvern;
+        player.mount = Some(strider);
+        player.set_zones(HashSet::from([ZoneId::Water]));
+        // in the air the water doesn't matter
+        assert!(player.is_flying_mounted());
+        assert_eq!(player.get_mount_type(), 2);
+        assert_eq!(player.get_move_speed(), 180);
+        assert_eq!(player.get_fly_walk_speed(), 100);
+    }
+
+    #[tokio::test]
+    async fn test_teleport_bookmarks() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let char = char_factory(&db_pool, |mut ch| {
+            ch.user_id = user.id;
+            ch.bookmark_slot = 2;
+            ch
+        })
+        .await;
+        let templates = ClassTemplates::load();
+        let temp = templates.try_get_template(char.class_id).unwrap();
+        let mut player = Player::new(char, vec![], temp.clone(), None);
+        player.set_location(10, 20, 30).unwrap();
+
+        let first = player
+            .add_teleport_bookmark("home".to_string(), 1, "H".to_string())
+            .unwrap();
+        assert_eq!((first.id, first.x, first.y, first.z), (1, 10, 20, 30));
+        assert_eq!(
+            player
+                .add_teleport_bookmark("work".to_string(), 2, "W".to_string())
+                .unwrap()
+                .id,
+            2
+        );
+        assert!(
+            player
+                .add_teleport_bookmark("full".to_string(), 3, String::new())
+                .is_none()
+        );
+        assert!(player.remove_teleport_bookmark(1));
+        assert!(!player.remove_teleport_bookmark(1));
+        assert_eq!(
+            player
+                .add_teleport_bookmark("again".to_string(), 3, String::new())
+                .unwrap()
+                .id,
+            1
+        );
+        let modified = player
+            .modify_teleport_bookmark(2, "office".to_string(), 4, "O".to_string())
+            .unwrap();
+        assert_eq!((modified.name.as_str(), modified.icon), ("office", 4));
+        assert!(player.modify_teleport_bookmark(5, String::new(), 0, String::new()).is_none());
+
+        assert!(player.increase_bookmark_slot(3));
+        assert_eq!(player.get_bookmark_slot(), 5);
+        assert!(player.increase_bookmark_slot(100));
+        assert_eq!(player.get_bookmark_slot(), i32::from(Player::MAX_BOOKMARK_SLOTS));
+        assert!(!player.increase_bookmark_slot(1));
+    }
+
+    #[tokio::test]
+    async fn test_macros_and_shortcuts() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let char = char_factory(&db_pool, |mut ch| {
+            ch.user_id = user.id;
+            ch
+        })
+        .await;
+        let templates = ClassTemplates::load();
+        let temp = templates.try_get_template(char.class_id).unwrap();
+        let mut player = Player::new(char, vec![], temp.clone(), None);
+        let m = PlayerMacro {
+            id: 0,
+            name: "Buff".to_string(),
+            description: String::new(),
+            acronym: "B".to_string(),
+            icon: 1,
+            commands: vec![],
+        };
+        let (saved, is_new) = player.register_macro(m.clone()).unwrap();
+        assert!(is_new);
+        assert_eq!(saved.id, PlayerMacro::FIRST_ID);
+        let (saved, _) = player.register_macro(m.clone()).unwrap();
+        assert_eq!(saved.id, PlayerMacro::FIRST_ID + 1);
+        let mut modified = m.clone();
+        modified.id = PlayerMacro::FIRST_ID;
+        modified.name = "Heal".to_string();
+        let (saved, is_new) = player.register_macro(modified).unwrap();
+        assert!(!is_new);
+        assert_eq!(saved.name, "Heal");
+        assert_eq!(player.get_macros().len(), 2);
+
+        let sc = Shortcut {
+            slot: 0,
+            page: 0,
+            shortcut_type: ShortcutType::Macro,
+            id: PlayerMacro::FIRST_ID,
+            level: 0,
+            sub_level: 0,
+            character_type: 0,
+        };
+        player.register_shortcut(sc.clone());
+        player.register_shortcut(Shortcut { slot: 1, ..sc.clone() });
+        player.register_shortcut(Shortcut {
+            id: PlayerMacro::FIRST_ID + 1,
+            ..sc.clone()
+        });
+        assert_eq!(player.get_shortcuts().len(), 2);
+        assert!(player.delete_macro(PlayerMacro::FIRST_ID).is_some());
+        let removed = player.delete_shortcuts_to(ShortcutType::Macro, PlayerMacro::FIRST_ID);
+        assert_eq!(removed.len(), 1);
+        assert_eq!(player.get_shortcuts().len(), 1);
+        assert!(player.delete_shortcut(0, 0).is_some());
+        assert!(player.delete_shortcut(0, 0).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_hennas() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let char = char_factory(&db_pool, |mut ch| {
+            ch.user_id = user.id;
+            ch.class_id = Class::Warrior as i8;
+            ch
+        })
+        .await;
+        let templates = ClassTemplates::load();
+        let temp = templates.try_get_template(char.class_id).unwrap();
+        let mut player = Player::new(char, vec![], temp.clone(), None);
+        let base_str = player.get_str();
+        let base_con = player.get_con();
+        let base_p_atk = player.stats.get_stat(Stat::PAtk);
+        // first profession has 2 slots
+        assert_eq!(player.get_henna_empty_slots(), 2);
+        let henna = HennaTemplate {
+            dye_id: 1,
+            name: "Str+1 Con-3".to_string(),
+            dye_item_id: 4445,
+            stats: HennaStats {
+                str: 1,
+                con: -3,
+                ..HennaStats::default()
+            },
+            wear_count: 10,
+            wear_fee: 37000,
+            cancel_count: 5,
+            cancel_fee: 7400,
+            class_ids: vec![Class::Warrior as i8],
+        };
+        assert_eq!(player.add_henna(henna.clone()), Some(1));
+        assert_eq!(player.add_henna(henna.clone()), Some(2));
+        assert_eq!(player.add_henna(henna.clone()), None);
+        assert_eq!(player.get_henna_empty_slots(), 0);
+        assert_eq!(player.get_str(), base_str + 2);
+        assert_eq!(player.get_con(), base_con - 6);
+        assert!(player.stats.get_stat(Stat::PAtk) > base_p_atk);
+
+        assert_eq!(player.get_henna_slot(1), Some(1));
+        assert!(player.remove_henna(1).is_some());
+        assert!(player.remove_henna(1).is_none());
+        assert_eq!(player.get_henna_slot(1), Some(2));
+        assert_eq!(player.get_str(), base_str + 1);
+        // the freed slot is used first
+        assert_eq!(player.add_henna(henna), Some(1));
+
+        // modifiers of other sources survive henna changes
+        player
+            .stats
+            .calculator
+            .modifiers
+            .insert(Stat::Str, vec![Modifier::Add(3.0)]);
+        assert!(player.remove_henna(1).is_some());
+        assert_eq!(player.get_str(), base_str + 4);
+    }
+
+    #[tokio::test]
+    async fn test_friends_and_block_list() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let char = char_factory(&db_pool, |mut ch| {
+            ch.user_id = user.id;
+            ch
+        })
+        .await;
+        let templates = ClassTemplates::load();
+        let temp = templates.try_get_template(char.class_id).unwrap();
+        let mut player = Player::new(char, vec![], temp.clone(), None);
+        let contact = Contact {
+            char_id: 100,
+            name: "Friend".to_string(),
+            level: 20,
+            class_id: 0,
+        };
+        assert!(player.add_friend(contact.clone()));
+        assert!(!player.add_friend(contact.clone()));
+        assert!(player.is_friend(100));
+        assert_eq!(player.get_friend_by_name("friend"), Some(&contact));
+        assert_eq!(player.remove_friend(100), Some(contact.clone()));
+        assert!(!player.is_friend(100));
+
+        assert!(!player.is_blocking(100));
+        assert!(player.block(contact.clone()));
+        assert!(!player.block(contact.clone()));
+        assert!(player.is_blocked(100));
+        assert!(player.is_blocking(100));
+        assert!(player.unblock(100).is_some());
+        assert!(!player.is_blocking(100));
+
+        assert!(!player.block_all());
+        player.set_block_all(true);
+        assert!(player.block_all());
+        assert!(player.is_blocking(100));
+        player.set_block_all(false);
+        assert!(!player.block_all());
+    }
+
+    #[tokio::test]
+    async fn test_add_exp_and_sp() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let char = char_factory(&db_pool, |mut ch| {
+            ch.user_id = user.id;
+            ch.level = 1;
+            ch.exp = 0;
+            ch
+        })
+        .await;
+        let templates = ClassTemplates::load();
+        let temp = templates.try_get_template(char.class_id).unwrap();
+        let mut player = Player::new(char, vec![], temp.clone(), None);
+        let exp_table = ExpTable::load();
+        assert_eq!(player.add_exp_and_sp(1, 10, &exp_table), None);
+        assert_eq!(player.char_model.sp, 10);
+        let exp = exp_table.get_exp(3) - 1;
+        assert_eq!(player.add_exp_and_sp(exp, 0, &exp_table), Some(3));
+        assert_eq!(
+            player.add_exp_and_sp(i64::MAX / 2, 0, &exp_table),
+            Some(exp_table.max_level)
+        );
+        assert_eq!(player.char_model.exp, exp_table.get_exp(exp_table.max_level));
+    }
+
+    #[tokio::test]
+    async fn test_olympiad_opponents() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let char = char_factory(&db_pool, |mut ch| {
+            ch.user_id = user.id;
+            ch
+        })
+        .await;
+        let templates = ClassTemplates::load();
+        let temp = templates.try_get_template(char.class_id).unwrap();
+        let mut first = Player::new(char.clone(), vec![], temp.clone(), None);
+        let mut second = Player::new(char, vec![], temp.clone(), None);
+        let location = *first.get_location();
+        let fighter = |side, fighting| OlympiadState {
+            game_id: 1,
+            role: OlympiadRole::Fighter { side, fighting },
+            return_to: location,
+            saved_effects: vec![],
+        };
+        assert!(!first.is_in_olympiad_mode());
+        first.olympiad = Some(fighter(1, false));
+        second.olympiad = Some(fighter(2, false));
+        assert!(first.is_in_olympiad_mode());
+        // not before the countdown is over
+        assert!(!first.is_auto_attackable(&second));
+        first.olympiad = Some(fighter(1, true));
+        second.olympiad = Some(fighter(2, true));
+        assert!(first.is_auto_attackable(&second));
+        second.olympiad = Some(OlympiadState {
+            role: OlympiadRole::Spectator,
+            ..fighter(2, true)
+        });
+        assert!(!second.is_in_olympiad_mode());
+        assert!(!first.is_auto_attackable(&second));
+
+        first.stats.current_hp = 1.0;
+        first.reset_for_olympiad();
+        assert_eq!(first.get_cur_hp().to_bits(), first.get_max_hp().to_bits());
+    }
 }
@@ -1,9 +1,16 @@
 use crate::data::char_template::ClassTemplates;
+use crate::data::hennas::HennaData;
 use crate::game_objects::creature::skill::Skill;
-use crate::game_objects::player::Player;
+use crate::game_objects::player::quest::Quest;
+use crate::game_objects::player::{Contact, Player, PlayerMacro, Shortcut, TeleportBookmark};
+use entities::dao::character_friend::FriendRelation;
 use entities::dao::item::LocType;
-use entities::entities::{character, skill};
+use entities::entities::{
+    character, character_friend, character_henna, character_mail, character_recipe,
+    character_subclass, quest, skill, teleport_bookmark,
+};
 use entities::DBPool;
+use log::warn;
 
 impl Player {
     /// # Errors
@@ -13,17 +20,78 @@ impl Player {
         acc_name: &str,
         db_pool: &DBPool,
         templates: &ClassTemplates,
+        hennas: &HennaData,
     ) -> anyhow::Result<Vec<Self>> {
         // Fetch user characters from the database
-        let characters =
-            character::Model::load_chars_with_data(db_pool, acc_name, LocType::Paperdoll).await?;
+        let characters = character::Model::load_chars_with_data(
+            db_pool,
+            acc_name,
+            &[LocType::Paperdoll, LocType::Inventory, LocType::Warehouse],
+        )
+        .await?;
         let mut players = Vec::with_capacity(characters.len());
-        for (ch, items, clan) in characters {
+        for (ch, items, clan, macros, shortcuts) in characters {
             let template = templates.try_get_template(ch.class_id)?;
+            let subclasses = character_subclass::Model::char_subclasses(db_pool, ch.id).await?;
+            let class_index = subclasses
+                .iter()
+                .find(|s| s.class_index > 0 && s.class_id == ch.class_id)
+                .map_or(0, |s| s.class_index);
             let skills = skill::Model::char_skills(db_pool, ch.id).await?;
-            let player_skills: Vec<Skill> = skills.into_iter().map(Skill::from_model).collect();
+            let player_skills: Vec<Skill> = skills
+                .into_iter()
+                .filter(|s| s.class_index == class_index)
+                .map(Skill::from_model)
+                .collect();
             let mut p = Player::new(ch, items, template.clone(), Some(player_skills));
+            p.set_subclasses(&subclasses);
             p.clan = clan;
+            p.macros = macros
+                .into_iter()
+                .map(PlayerMacro::from_model)
+                .collect::<anyhow::Result<_>>()?;
+            p.shortcuts = shortcuts
+                .into_iter()
+                .filter(|sc| sc.class_index == class_index)
+                .map(Shortcut::from_model)
+                .collect();
+            p.teleport_bookmarks = teleport_bookmark::Model::char_bookmarks(db_pool, p.char_model.id)
+                .await?
+                .into_iter()
+                .map(TeleportBookmark::from_model)
+                .collect();
+            for henna in
+                character_henna::Model::char_hennas(db_pool, p.char_model.id, class_index).await?
+            {
+                if let Some(template) = hennas.get_henna(henna.dye_id) {
+                    p.set_henna(henna.slot, template.clone())?;
+                } else {
+                    warn!("Unknown henna {} of char {}", henna.dye_id, p.char_model.id);
+                }
+            }
+            for (relation, other) in
+                character_friend::Model::char_relations(db_pool, p.char_model.id).await?
+            {
+                let contact = Contact::from_model(&other);
+                match relation.relation {
+                    FriendRelation::Friend => p.friends.push(contact),
+                    FriendRelation::Block => p.block_list.push(contact),
+                }
+            }
+            p.recipe_book = character_recipe::Model::char_recipes(db_pool, p.char_model.id)
+                .await?
+                .into_iter()
+                .collect();
+            p.mailbox = character_mail::Model::inbox(db_pool, p.char_model.id)
+                .await?
+                .into_iter()
+                .map(|(mail, _)| mail)
+                .collect();
+            p.quests = quest::Model::char_quests(db_pool, p.char_model.id)
+                .await?
+                .into_iter()
+                .map(|model| Quest { model })
+                .collect();
             players.push(p);
         }
         Ok(players)
@@ -0,0 +1,133 @@
+use crate::data::recipes::{RecipeData, RecipeTemplate};
+use crate::game_objects::player::Player;
+use crate::game_objects::private_store_types::PrivateStoreType;
+
+/// Recipes the dwarven and the common recipe book take each
+pub const RECIPE_BOOK_LIMIT: usize = 100;
+/// Recipes a manufacture shop offers at most
+pub const MANUFACTURE_SHOP_LIMIT: usize = 20;
+
+/// A recipe the manufacturer crafts for others
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ManufactureItem {
+    pub recipe_id: i32,
+    /// Adena the manufacturer takes for a craft, the ingredients come from the customer
+    pub cost: i64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ManufactureShop {
+    /// The shop offers recipes of the dwarven book, otherwise of the common one
+    pub dwarven: bool,
+    pub message: String,
+    pub items: Vec<ManufactureItem>,
+}
+
+impl ManufactureShop {
+    #[must_use]
+    pub fn get_item(&self, recipe_id: i32) -> Option<&ManufactureItem> {
+        self.items.iter().find(|i| i.recipe_id == recipe_id)
+    }
+}
+
+impl Player {
+    #[must_use]
+    pub fn has_recipe(&self, recipe_id: i32) -> bool {
+        self.recipe_book.contains(&recipe_id)
+    }
+
+    /// Level of the skill crafting the recipes of the book, 0 without the skill.
+    #[must_use]
+    pub fn get_craft_level(&self, dwarven: bool) -> i16 {
+        let skill_id = if dwarven {
+            RecipeTemplate::DWARVEN_CRAFT_SKILL
+        } else {
+            RecipeTemplate::COMMON_CRAFT_SKILL
+        };
+        self.get_skill_level(skill_id).unwrap_or(0)
+    }
+
+    /// The recipes of the dwarven or the common book.
+    #[must_use]
+    pub fn get_recipe_book<'a>(
+        &self,
+        recipes: &'a RecipeData,
+        dwarven: bool,
+    ) -> Vec<&'a RecipeTemplate> {
+        self.recipe_book
+            .iter()
+            .filter_map(|id| recipes.get(*id))
+            .filter(|r| r.dwarven == dwarven)
+            .collect()
+    }
+
+    /// Why the recipe can't go into the book, None when the player can learn it.
+    #[must_use]
+    pub fn check_learn_recipe(
+        &self,
+        recipes: &RecipeData,
+        recipe: &RecipeTemplate,
+    ) -> Option<&'static str> {
+        let craft_level = self.get_craft_level(recipe.dwarven);
+        if self.has_recipe(recipe.id) {
+            Some("That recipe is already registered.")
+        } else if craft_level == 0 {
+            Some("You don't have the ability to create items.")
+        } else if craft_level < recipe.level {
+            Some("Your create item level is too low to register this recipe.")
+        } else if self.get_recipe_book(recipes, recipe.dwarven).len() >= RECIPE_BOOK_LIMIT {
+            Some("No further recipes may be registered.")
+        } else {
+            None
+        }
+    }
+
+    #[must_use]
+    pub fn is_manufacturing(&self) -> bool {
+        self.get_private_store_type() == PrivateStoreType::Manufacture
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::traits::ConfigDirLoader;
+    use crate::data::char_template::ClassTemplates;
+    use crate::game_objects::creature::skill::Skill;
+    use entities::entities::skill;
+    use entities::test_factories::factories::{char_factory, user_factory};
+    use test_utils::utils::get_test_db;
+
+    #[tokio::test]
+    async fn test_learn_recipe() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let char = char_factory(&db_pool, |mut ch| {
+            ch.user_id = user.id;
+            ch
+        })
+        .await;
+        let templates = ClassTemplates::load();
+        let temp = templates.try_get_template(char.class_id).unwrap();
+        let mut player = Player::new(char, vec![], temp.clone(), None);
+        let recipes = RecipeData::load();
+        let arrow = recipes.by_recipe_item(1666).unwrap();
+        let steel = recipes.by_recipe_item(1786).unwrap();
+        assert!(player.check_learn_recipe(&recipes, arrow).is_some());
+
+        player.skills = Some(vec![Skill::from_model(skill::Model {
+            id: RecipeTemplate::DWARVEN_CRAFT_SKILL,
+            level: 1,
+            ..Default::default()
+        })]);
+        assert_eq!(player.get_craft_level(true), 1);
+        assert_eq!(player.get_craft_level(false), 0);
+        assert!(player.check_learn_recipe(&recipes, arrow).is_none());
+        assert!(player.check_learn_recipe(&recipes, steel).is_some());
+
+        player.recipe_book.insert(arrow.id);
+        assert!(player.check_learn_recipe(&recipes, arrow).is_some());
+        assert_eq!(player.get_recipe_book(&recipes, true).len(), 1);
+        assert!(player.get_recipe_book(&recipes, false).is_empty());
+    }
+}
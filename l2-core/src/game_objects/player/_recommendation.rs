@@ -0,0 +1,239 @@
+use crate::game_objects::player::Player;
+use crate::game_objects::player::vars::CharVariables;
+use chrono::{DateTime, NaiveTime, TimeDelta, Utc};
+use serde_json::Value;
+
+/// Recommendations a player may give per day
+pub const DAILY_RECOMMENDATIONS: u16 = 20;
+/// A player can't be recommended any more with this many
+pub const MAX_RECOMMENDATIONS_HAVE: u16 = 255;
+/// Players below the level may not recommend others
+pub const MIN_RECOMMEND_LEVEL: u8 = 10;
+/// Seconds of recommendation bonus a player gets per day
+pub const RECOMMENDATION_BONUS_TIME: u32 = 3600;
+/// Hour of the day (UTC) the recommendations are refreshed, the 1 p.m. of the system messages
+const RESET_HOUR: u32 = 13;
+/// Exp bonus in percent by level (tens) and recommendations had (tens)
+const RECOMMENDATION_BONUS: [[u32; 10]; 9] = [
+    [25, 50, 50, 50, 50, 50, 50, 50, 50, 50],
+    [16, 33, 50, 50, 50, 50, 50, 50, 50, 50],
+    [12, 25, 37, 50, 50, 50, 50, 50, 50, 50],
+    [10, 20, 30, 40, 50, 50, 50, 50, 50, 50],
+    [8, 16, 25, 33, 41, 50, 50, 50, 50, 50],
+    [7, 14, 21, 28, 35, 42, 50, 50, 50, 50],
+    [6, 12, 18, 25, 31, 37, 43, 50, 50, 50],
+    [5, 11, 16, 22, 27, 33, 38, 44, 50, 50],
+    [5, 10, 15, 20, 25, 30, 35, 40, 45, 50],
+];
+
+/// The latest daily refresh of the recommendations at or before the time.
+#[must_use]
+pub fn last_recommendation_reset(now: DateTime<Utc>) -> DateTime<Utc> {
+    let time = NaiveTime::from_hms_opt(RESET_HOUR, 0, 0).unwrap_or_default();
+    let today = now.date_naive().and_time(time).and_utc();
+    if today <= now {
+        today
+    } else {
+        today - TimeDelta::days(1)
+    }
+}
+
+/// Exp bonus in percent of a player of the level with the recommendations.
+#[must_use]
+pub fn recommendation_bonus(level: u8, recommendations_have: u16) -> u32 {
+    if recommendations_have == 0 {
+        return 0;
+    }
+    let row = usize::from(level / 10).min(RECOMMENDATION_BONUS.len() - 1);
+    let column = usize::from((recommendations_have.min(100) - 1) / 10);
+    RECOMMENDATION_BONUS[row][column]
+}
+
+impl Player {
+    #[must_use]
+    pub fn get_recommendations_left(&self) -> u16 {
+        self.char_model.rec_left
+    }
+
+    #[must_use]
+    pub fn get_recommendations_have(&self) -> u16 {
+        self.char_model.rec_have
+    }
+
+    /// Seconds left of the recommendation bonus today
+    #[must_use]
+    pub fn get_recommendation_bonus_time(&self) -> u32 {
+        self.char_model.rec_bonus_time
+    }
+
+    /// Exp bonus in percent, 0 when the player has no recommendations or the time is up.
+    #[must_use]
+    pub fn get_recommendation_bonus(&self) -> u32 {
+        if self.get_recommendation_bonus_time() == 0 {
+            return 0;
+        }
+        recommendation_bonus(self.char_model.level, self.get_recommendations_have())
+    }
+
+    /// Exp with the recommendation bonus applied
+    #[must_use]
+    pub fn apply_recommendation_bonus(&self, exp: i64) -> i64 {
+        match self.get_recommendation_bonus() {
+            0 => exp,
+            bonus => exp.saturating_mul(i64::from(100 + bonus)) / 100,
+        }
+    }
+
+    fn get_recommended_chars(&self) -> Vec<i32> {
+        self.char_model
+            .variables
+            .get(CharVariables::RecommendedChars.as_key())
+            .and_then(Value::as_array)
+            .map(|ids| {
+                ids.iter()
+                    .filter_map(Value::as_i64)
+                    .filter_map(|id| i32::try_from(id).ok())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn set_recommended_chars(&mut self, char_ids: Vec<i32>) {
+        if !self.char_model.variables.is_object() {
+            self.char_model.variables = Value::Object(serde_json::Map::new());
+        }
+        self.char_model.variables[CharVariables::RecommendedChars.as_key()] = Value::from(char_ids);
+    }
+
+    /// If the player already recommended the char today
+    #[must_use]
+    pub fn has_recommended(&self, char_id: i32) -> bool {
+        self.get_recommended_chars().contains(&char_id)
+    }
+
+    /// Spends one of the recommendations left on the char, the caller checks they may.
+    pub fn give_recommendation(&mut self, char_id: i32) {
+        self.char_model.rec_left = self.char_model.rec_left.saturating_sub(1);
+        let mut recommended = self.get_recommended_chars();
+        recommended.push(char_id);
+        self.set_recommended_chars(recommended);
+    }
+
+    /// Counts a recommendation from another player, returns false if the player has the maximum.
+    pub fn receive_recommendation(&mut self) -> bool {
+        if self.char_model.rec_have >= MAX_RECOMMENDATIONS_HAVE {
+            return false;
+        }
+        self.char_model.rec_have += 1;
+        true
+    }
+
+    /// Runs the bonus timer for the seconds, returns true if the bonus ran out now.
+    pub fn consume_recommendation_bonus_time(&mut self, seconds: u32) -> bool {
+        if self.get_recommendation_bonus() == 0 {
+            return false;
+        }
+        self.char_model.rec_bonus_time = self.char_model.rec_bonus_time.saturating_sub(seconds);
+        self.char_model.rec_bonus_time == 0
+    }
+
+    /// Applies the daily refresh if it is due, returns true if it was.
+    /// The recommendations left and the bonus time are refilled and the player loses some of
+    /// the recommendations had, a character that was never checked gets the refresh too.
+    pub fn refresh_recommendations(&mut self, now: DateTime<Utc>) -> bool {
+        let checked_at = self
+            .char_model
+            .variables
+            .get(CharVariables::RecommendationResetAt.as_key())
+            .and_then(Value::as_i64)
+            .and_then(DateTime::from_timestamp_millis);
+        if checked_at.is_some_and(|t| t >= last_recommendation_reset(now)) {
+            return false;
+        }
+        self.char_model.rec_left = DAILY_RECOMMENDATIONS;
+        self.char_model.rec_have = self
+            .char_model
+            .rec_have
+            .saturating_sub(DAILY_RECOMMENDATIONS);
+        self.char_model.rec_bonus_time = RECOMMENDATION_BONUS_TIME;
+        self.set_recommended_chars(vec![]);
+        self.char_model.variables[CharVariables::RecommendationResetAt.as_key()] =
+            Value::from(now.timestamp_millis());
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::traits::ConfigDirLoader;
+    use crate::data::char_template::ClassTemplates;
+    use chrono::{TimeDelta, TimeZone};
+    use entities::test_factories::factories::{char_factory, user_factory};
+    use test_utils::utils::get_test_db;
+
+    #[test]
+    fn test_last_recommendation_reset() {
+        let reset = Utc.with_ymd_and_hms(2025, 7, 21, 13, 0, 0).unwrap();
+        assert_eq!(last_recommendation_reset(reset), reset);
+        assert_eq!(
+            last_recommendation_reset(reset - TimeDelta::minutes(1)),
+            reset - TimeDelta::days(1)
+        );
+    }
+
+    #[test]
+    fn test_recommendation_bonus() {
+        assert_eq!(recommendation_bonus(20, 0), 0);
+        assert_eq!(recommendation_bonus(5, 1), 25);
+        assert_eq!(recommendation_bonus(20, 25), 37);
+        assert_eq!(recommendation_bonus(85, 255), 50);
+        assert_eq!(recommendation_bonus(99, 1), 5);
+    }
+
+    #[tokio::test]
+    async fn test_recommendations() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let char = char_factory(&db_pool, |mut ch| {
+            ch.user_id = user.id;
+            ch.level = 20;
+            ch.rec_have = 25;
+            ch
+        })
+        .await;
+        let templates = ClassTemplates::load();
+        let temp = templates.try_get_template(char.class_id).unwrap();
+        let mut player = Player::new(char, vec![], temp.clone(), None);
+        assert_eq!(player.get_recommendation_bonus(), 0);
+
+        let now = Utc.with_ymd_and_hms(2025, 7, 21, 13, 0, 0).unwrap();
+        assert!(player.refresh_recommendations(now));
+        assert!(!player.refresh_recommendations(now + TimeDelta::hours(23)));
+        assert_eq!(player.get_recommendations_left(), DAILY_RECOMMENDATIONS);
+        assert_eq!(player.get_recommendations_have(), 5);
+        assert_eq!(player.get_recommendation_bonus(), 12);
+        assert_eq!(player.apply_recommendation_bonus(100), 112);
+
+        player.give_recommendation(7);
+        assert!(player.has_recommended(7));
+        assert_eq!(player.get_recommendations_left(), DAILY_RECOMMENDATIONS - 1);
+        assert!(player.receive_recommendation());
+        assert_eq!(player.get_recommendations_have(), 6);
+
+        assert!(!player.consume_recommendation_bonus_time(600));
+        assert!(player.consume_recommendation_bonus_time(RECOMMENDATION_BONUS_TIME));
+        assert_eq!(player.get_recommendation_bonus(), 0);
+        assert_eq!(player.apply_recommendation_bonus(100), 100);
+
+        // the next day everything is refilled
+        assert!(player.refresh_recommendations(now + TimeDelta::days(1)));
+        assert!(!player.has_recommended(7));
+        assert_eq!(player.get_recommendations_left(), DAILY_RECOMMENDATIONS);
+        assert_eq!(player.get_recommendations_have(), 0);
+        assert_eq!(
+            player.get_recommendation_bonus_time(),
+            RECOMMENDATION_BONUS_TIME
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use entities::entities::character_shortcut;
+
+#[repr(u8)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Copy)]
+pub enum ShortcutType {
+    None,
+    Item,
+    Skill,
+    Action,
+    Macro,
+    Recipe,
+    Bookmark,
+}
+
+impl From<i32> for ShortcutType {
+    fn from(value: i32) -> Self {
+        match value {
+            1 => ShortcutType::Item,
+            2 => ShortcutType::Skill,
+            3 => ShortcutType::Action,
+            4 => ShortcutType::Macro,
+            5 => ShortcutType::Recipe,
+            6 => ShortcutType::Bookmark,
+            _ => ShortcutType::None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shortcut {
+    /// Slot on the page, 0..12
+    pub slot: i16,
+    pub page: i16,
+    pub shortcut_type: ShortcutType,
+    /// Skill id, item object id, macro id, etc. depending on the type
+    pub id: i32,
+    pub level: i16,
+    pub sub_level: i16,
+    pub character_type: i32,
+}
+
+impl Shortcut {
+    pub const SLOTS_PER_PAGE: i32 = 12;
+    pub const MAX_PAGE: i16 = 23;
+
+    /// Client addresses shortcuts by a single position across all pages.
+    #[must_use]
+    pub fn position(&self) -> i32 {
+        i32::from(self.slot) + i32::from(self.page) * Self::SLOTS_PER_PAGE
+    }
+
+    /// Splits a client position into `(slot, page)`.
+    ///
+    /// # Errors
+    /// - when the position is out of the shortcut panels
+    pub fn split_position(position: i32) -> anyhow::Result<(i16, i16)> {
+        let slot = i16::try_from(position % Self::SLOTS_PER_PAGE)?;
+        let page = i16::try_from(position / Self::SLOTS_PER_PAGE)?;
+        if position < 0 || page > Self::MAX_PAGE {
+            anyhow::bail!("Invalid shortcut position {position}");
+        }
+        Ok((slot, page))
+    }
+
+    #[must_use]
+    pub fn from_model(model: character_shortcut::Model) -> Self {
+        Self {
+            slot: model.slot,
+            page: model.page,
+            shortcut_type: ShortcutType::from(i32::from(model.shortcut_type)),
+            id: model.shortcut_id,
+            level: model.level,
+            sub_level: model.sub_level,
+            character_type: model.character_type,
+        }
+    }
+
+    #[must_use]
+    pub fn to_model(&self, char_id: i32, class_index: i32) -> character_shortcut::Model {
+        character_shortcut::Model {
+            char_id,
+            slot: self.slot,
+            page: self.page,
+            class_index,
+            shortcut_type: i16::from(self.shortcut_type as u8),
+            shortcut_id: self.id,
+            level: self.level,
+            sub_level: self.sub_level,
+            character_type: self.character_type,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position() {
+        assert_eq!(Shortcut::split_position(27).unwrap(), (3, 2));
+        assert!(Shortcut::split_position(-1).is_err());
+        assert!(Shortcut::split_position(24 * 12).is_err());
+        let sc = Shortcut {
+            slot: 3,
+            page: 2,
+            shortcut_type: ShortcutType::Macro,
+            id: 1000,
+            level: 0,
+            sub_level: 0,
+            character_type: 0,
+        };
+        assert_eq!(sc.position(), 27);
+        assert_eq!(Shortcut::from_model(sc.to_model(1, 0)), sc);
+    }
+}
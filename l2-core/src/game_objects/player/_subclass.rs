@@ -1,20 +1,151 @@
+use crate::data::classes::mapping::Class;
+use crate::data::exp_table::ExpTable;
+use crate::game_objects::race::Race;
+use entities::entities::character_subclass;
+
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum SubclassType{
+pub enum SubclassType {
     BaseClass,
     DualClass,
-    Subclass
+    Subclass,
 }
 #[derive(Debug, Clone)]
-pub struct Subclass{
-    pub index:i32,
-    pub class_id:i8,
+pub struct Subclass {
+    pub index: i32,
+    pub class_id: i8,
     pub level: u8,
-    pub class_type: SubclassType
+    pub exp: i64,
+    pub sp: i64,
+    pub class_type: SubclassType,
 }
 
-impl From<SubclassType> for u8{
+impl From<SubclassType> for u8 {
     fn from(value: SubclassType) -> Self {
         value as u8
     }
-}
\ No newline at end of file
+}
+
+impl Subclass {
+    pub const MAX_SUBCLASSES: usize = 3;
+    /// Level of a new subclass
+    pub const STARTING_LEVEL: u8 = 40;
+    /// Level every class of the char needs before a subclass can be added
+    pub const REQUIRED_LEVEL: u8 = 75;
+
+    /// Classes sharing the role, a char may have only one of each set
+    const CLASS_SETS: [&[Class]; 5] = [
+        &[
+            Class::Paladin,
+            Class::DarkAvenger,
+            Class::TempleKnight,
+            Class::ShillienKnight,
+        ],
+        &[Class::TreasureHunter, Class::PlainsWalker, Class::AbyssWalker],
+        &[Class::Hawkeye, Class::SilverRanger, Class::PhantomRanger],
+        &[Class::Warlock, Class::ElementalSummoner, Class::PhantomSummoner],
+        &[Class::Sorcerer, Class::Spellsinger, Class::Spellhowler],
+    ];
+
+    /// A new subclass starting at [`Subclass::STARTING_LEVEL`]
+    #[must_use]
+    pub fn new(index: i32, class: Class, exp_table: &ExpTable) -> Self {
+        Self {
+            index,
+            class_id: class as i8,
+            level: Self::STARTING_LEVEL,
+            exp: exp_table.get_exp(Self::STARTING_LEVEL),
+            sp: 0,
+            class_type: SubclassType::Subclass,
+        }
+    }
+
+    #[must_use]
+    pub fn from_model(model: &character_subclass::Model) -> Self {
+        Self {
+            index: model.class_index,
+            class_id: model.class_id,
+            level: model.level,
+            exp: model.exp,
+            sp: model.sp,
+            class_type: if model.class_index == 0 {
+                SubclassType::BaseClass
+            } else {
+                SubclassType::Subclass
+            },
+        }
+    }
+
+    #[must_use]
+    pub fn to_model(&self, char_id: i32) -> character_subclass::Model {
+        character_subclass::Model {
+            char_id,
+            class_index: self.index,
+            class_id: self.class_id,
+            level: self.level,
+            exp: self.exp,
+            sp: self.sp,
+        }
+    }
+
+    /// If the class can be taken as a subclass by a char of the race owning `owned` classes.
+    /// Only second professions are allowed, except Overlord and Warsmith, elves and dark elves
+    /// can't take each other's classes and a class is allowed once per role.
+    #[must_use]
+    pub fn is_allowed_class(class: Class, race: Race, owned: &[Class]) -> bool {
+        let class_race = class.get_class().race;
+        let same_set = |c: &Class| {
+            Self::CLASS_SETS
+                .iter()
+                .any(|set| set.contains(&class) && set.contains(c))
+        };
+        class.level() == 2
+            && !matches!(class, Class::Overlord | Class::Warsmith)
+            && !matches!(
+                (race, class_race),
+                (Race::Elf, Race::DarkElf) | (Race::DarkElf, Race::Elf)
+            )
+            && !owned.iter().any(|c| *c == class || same_set(c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allowed_classes() {
+        let owned = [Class::Gladiator];
+        assert!(Subclass::is_allowed_class(
+            Class::Sorcerer,
+            Race::Human,
+            &owned
+        ));
+        assert!(!Subclass::is_allowed_class(
+            Class::Gladiator,
+            Race::Human,
+            &owned
+        ));
+        assert!(!Subclass::is_allowed_class(
+            Class::Warrior,
+            Race::Human,
+            &owned
+        ));
+        assert!(!Subclass::is_allowed_class(
+            Class::Overlord,
+            Race::Human,
+            &owned
+        ));
+        assert!(!Subclass::is_allowed_class(
+            Class::Spellhowler,
+            Race::Elf,
+            &owned
+        ));
+        let owned = [Class::Gladiator, Class::Paladin];
+        assert!(!Subclass::is_allowed_class(
+            Class::ShillienKnight,
+            Race::Human,
+            &owned
+        ));
+    }
+}
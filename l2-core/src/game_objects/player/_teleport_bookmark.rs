@@ -1,5 +1,7 @@
-#[derive(Debug, Clone)]
-pub struct TeleportBookmark{
+use entities::entities::teleport_bookmark;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TeleportBookmark {
     pub id: i32,
     pub x: i32,
     pub y: i32,
@@ -7,4 +9,33 @@ pub struct TeleportBookmark{
     pub name: String,
     pub icon: i32,
     pub tag: String,
-}
\ No newline at end of file
+}
+
+impl TeleportBookmark {
+    #[must_use]
+    pub fn from_model(model: teleport_bookmark::Model) -> Self {
+        Self {
+            id: model.id,
+            x: model.x,
+            y: model.y,
+            z: model.z,
+            name: model.name,
+            icon: model.icon,
+            tag: model.tag,
+        }
+    }
+
+    #[must_use]
+    pub fn to_model(&self, char_id: i32) -> teleport_bookmark::Model {
+        teleport_bookmark::Model {
+            char_id,
+            id: self.id,
+            x: self.x,
+            y: self.y,
+            z: self.z,
+            icon: self.icon,
+            tag: self.tag.clone(),
+            name: self.name.clone(),
+        }
+    }
+}
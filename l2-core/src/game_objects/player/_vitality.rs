@@ -0,0 +1,200 @@
+use crate::config::gs::Rates;
+use crate::game_objects::player::Player;
+use crate::game_objects::player::vars::CharVariables;
+use chrono::{DateTime, Datelike, NaiveTime, TimeDelta, Utc, Weekday};
+use serde_json::Value;
+
+pub const MAX_VITALITY_POINTS: u32 = 140_000;
+/// Time of the day (UTC) the vitality resets happen
+const RESET_HOUR: u32 = 6;
+const RESET_MINUTE: u32 = 30;
+/// Day of the week the vitality points are refilled
+const WEEKLY_RESET_DAY: Weekday = Weekday::Wed;
+/// Exp of a monster that costs one vitality point
+const EXP_PER_POINT: i64 = 1000;
+
+/// The latest daily reset (used vitality items) at or before the time.
+#[must_use]
+pub fn last_daily_vitality_reset(now: DateTime<Utc>) -> DateTime<Utc> {
+    let time = NaiveTime::from_hms_opt(RESET_HOUR, RESET_MINUTE, 0).unwrap_or_default();
+    let today = now.date_naive().and_time(time).and_utc();
+    if today <= now {
+        today
+    } else {
+        today - TimeDelta::days(1)
+    }
+}
+
+/// The latest weekly reset (vitality points) at or before the time.
+#[must_use]
+pub fn last_weekly_vitality_reset(now: DateTime<Utc>) -> DateTime<Utc> {
+    let mut reset = last_daily_vitality_reset(now);
+    while reset.weekday() != WEEKLY_RESET_DAY {
+        reset -= TimeDelta::days(1);
+    }
+    reset
+}
+
+/// Vitality points a player of the level spends for the exp of a monster of the level.
+/// Killing monsters far below the own level wastes more of them.
+#[must_use]
+pub fn vitality_consumption(exp: i64, player_level: u8, monster_level: u8) -> u32 {
+    let level_diff = i64::from(player_level) - i64::from(monster_level);
+    let points = (exp / EXP_PER_POINT).saturating_mul(level_diff.max(1));
+    u32::try_from(points.max(1)).unwrap_or(u32::MAX)
+}
+
+impl Player {
+    #[must_use]
+    pub fn get_vitality_points(&self) -> u32 {
+        self.char_model.vitality_points
+    }
+
+    pub fn set_vitality_points(&mut self, points: u32) {
+        self.char_model.vitality_points = points.min(MAX_VITALITY_POINTS);
+    }
+
+    /// Adds (or takes with a negative amount) vitality points within the limits.
+    pub fn add_vitality_points(&mut self, points: i64) {
+        let new_points = (i64::from(self.get_vitality_points()) + points).max(0);
+        self.set_vitality_points(u32::try_from(new_points).unwrap_or(MAX_VITALITY_POINTS));
+    }
+
+    /// Exp and sp bonus in percent, 0 when vitality is disabled or the points are spent.
+    #[must_use]
+    pub fn get_vitality_bonus(&self, rates: &Rates) -> u32 {
+        if rates.enable_vitality && self.get_vitality_points() > 0 {
+            rates.vitality_exp_multiplier * 100
+        } else {
+            0
+        }
+    }
+
+    /// Exp or sp with the vitality bonus applied
+    #[must_use]
+    pub fn apply_vitality_bonus(&self, value: i64, rates: &Rates) -> i64 {
+        match self.get_vitality_bonus(rates) {
+            0 => value,
+            bonus => value.saturating_mul(i64::from(bonus)) / 100,
+        }
+    }
+
+    fn set_vitality_used(&mut self, used: u32) {
+        if !self.char_model.variables.is_object() {
+            self.char_model.variables = Value::Object(serde_json::Map::new());
+        }
+        self.char_model.variables[CharVariables::VitalityItemsUsed.as_key()] = Value::from(used);
+    }
+
+    /// Counts a used vitality item, returns false if the player already used as many as allowed.
+    pub fn use_vitality_item(&mut self, max_items: u32) -> bool {
+        let used = self.get_vitality_used();
+        if used >= max_items {
+            return false;
+        }
+        self.set_vitality_used(used + 1);
+        true
+    }
+
+    /// Applies the daily and weekly resets due since the last check, returns true if any was.
+    /// A character that was never checked gets both.
+    pub fn refresh_vitality(&mut self, now: DateTime<Utc>) -> bool {
+        let checked_at = self
+            .char_model
+            .variables
+            .get(CharVariables::VitalityResetAt.as_key())
+            .and_then(Value::as_i64)
+            .and_then(DateTime::from_timestamp_millis);
+        let mut changed = false;
+        if checked_at.is_none_or(|t| t < last_weekly_vitality_reset(now)) {
+            self.set_vitality_points(MAX_VITALITY_POINTS);
+            changed = true;
+        }
+        if checked_at.is_none_or(|t| t < last_daily_vitality_reset(now)) {
+            self.set_vitality_used(0);
+            changed = true;
+        }
+        if changed {
+            self.char_model.variables[CharVariables::VitalityResetAt.as_key()] =
+                Value::from(now.timestamp_millis());
+        }
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::traits::ConfigDirLoader;
+    use crate::data::char_template::ClassTemplates;
+    use chrono::TimeZone;
+    use entities::test_factories::factories::{char_factory, user_factory};
+    use test_utils::utils::get_test_db;
+
+    #[test]
+    fn test_reset_schedule() {
+        // a monday
+        let now = Utc.with_ymd_and_hms(2025, 7, 21, 5, 0, 0).unwrap();
+        assert_eq!(
+            last_daily_vitality_reset(now),
+            Utc.with_ymd_and_hms(2025, 7, 20, 6, 30, 0).unwrap()
+        );
+        assert_eq!(
+            last_weekly_vitality_reset(now),
+            Utc.with_ymd_and_hms(2025, 7, 16, 6, 30, 0).unwrap()
+        );
+        let wednesday = Utc.with_ymd_and_hms(2025, 7, 23, 6, 30, 0).unwrap();
+        assert_eq!(last_daily_vitality_reset(wednesday), wednesday);
+        assert_eq!(last_weekly_vitality_reset(wednesday), wednesday);
+    }
+
+    #[test]
+    fn test_vitality_consumption() {
+        assert_eq!(vitality_consumption(500, 20, 20), 1);
+        assert_eq!(vitality_consumption(5000, 20, 22), 5);
+        assert_eq!(vitality_consumption(5000, 23, 20), 15);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_vitality() {
+        let db_pool = get_test_db().await;
+        let user = user_factory(&db_pool, |u| u).await;
+        let char = char_factory(&db_pool, |mut ch| {
+            ch.user_id = user.id;
+            ch.vitality_points = 0;
+            ch
+        })
+        .await;
+        let templates = ClassTemplates::load();
+        let temp = templates.try_get_template(char.class_id).unwrap();
+        let mut player = Player::new(char, vec![], temp.clone(), None);
+        let rates = Rates {
+            vitality_exp_multiplier: 2,
+            enable_vitality: true,
+            premium_exp_multiplier: 1.0,
+            premium_sp_multiplier: 1.0,
+        };
+        assert_eq!(player.get_vitality_bonus(&rates), 0);
+        assert_eq!(player.apply_vitality_bonus(100, &rates), 100);
+
+        // a monday, the first check fills the points
+        let now = Utc.with_ymd_and_hms(2025, 7, 21, 12, 0, 0).unwrap();
+        assert!(player.refresh_vitality(now));
+        assert_eq!(player.get_vitality_points(), MAX_VITALITY_POINTS);
+        assert_eq!(player.get_vitality_bonus(&rates), 200);
+        assert_eq!(player.apply_vitality_bonus(100, &rates), 200);
+        assert!(player.use_vitality_item(1));
+        assert!(!player.use_vitality_item(1));
+        player.add_vitality_points(-200_000);
+        assert_eq!(player.get_vitality_points(), 0);
+        assert!(!player.refresh_vitality(now + TimeDelta::hours(1)));
+
+        // the next morning only the items are reset
+        assert!(player.refresh_vitality(now + TimeDelta::days(1)));
+        assert_eq!(player.get_vitality_used(), 0);
+        assert_eq!(player.get_vitality_points(), 0);
+        // wednesday the points are back
+        assert!(player.refresh_vitality(now + TimeDelta::days(2)));
+        assert_eq!(player.get_vitality_points(), MAX_VITALITY_POINTS);
+    }
+}
@@ -17,3 +17,26 @@ pub enum ClanSubUnit {
     /** Clan subunit type of Order of Knights B-2 */
     Knight4 = 2002,
 }
+
+/// Rights a clan rank gives to its members, the bits of `clan_privs`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(i32)]
+pub enum ClanPrivilege {
+    JoinClan = 1 << 1,
+    GiveTitle = 1 << 2,
+    ViewWarehouse = 1 << 3,
+    ManageRanks = 1 << 4,
+    PledgeWar = 1 << 5,
+    Dismiss = 1 << 6,
+    RegisterCrest = 1 << 7,
+    ManageSiege = 1 << 18,
+    ManageManor = 1 << 20,
+    ManageTaxes = 1 << 21,
+}
+
+impl ClanPrivilege {
+    #[must_use]
+    pub fn is_granted(self, privileges: i32) -> bool {
+        privileges & self as i32 != 0
+    }
+}
@@ -1,6 +1,9 @@
 use std::collections::HashMap;
 use crate::game_objects::item::ItemObject;
 use crate::game_objects::player::paper_doll::PaperDoll;
+use crate::id_factory::IdFactory;
+use anyhow::bail;
+use entities::dao::item::LocType;
 use entities::entities::item;
 use sea_orm::EnumIter;
 
@@ -10,6 +13,8 @@ pub struct Inventory {
 }
 
 impl Inventory {
+    pub const ADENA_ID: i32 = 57;
+
     #[must_use] 
     pub fn from_items(items: Vec<item::Model>) -> Self {
         Self {
@@ -41,12 +46,102 @@ impl Inventory {
     }
     #[must_use]
     pub fn get_adena(&self) -> u64 {
-        0u64 //todo: implement me
+        self.get_item_by_item_id(Self::ADENA_ID)
+            .and_then(|i| u64::try_from(i.item_model.count).ok())
+            .unwrap_or(0)
     }
     #[must_use]
     pub fn get_size(&self) -> u16 {
         0u16 //todo: implement me
     }
+
+    /// Finds a not equipped item (a stack for stackable items) by its template id.
+    #[must_use]
+    pub fn get_item_by_item_id(&self, item_id: i32) -> Option<&ItemObject> {
+        self.items
+            .values()
+            .find(|i| i.item_model.item_id == item_id && i.item_model.loc == LocType::Inventory)
+    }
+
+    /// All not equipped items of the template, non-stackable items have one object each.
+    pub fn get_items_by_item_id(&self, item_id: i32) -> impl Iterator<Item = &ItemObject> {
+        self.items.values().filter(move |i| {
+            i.item_model.item_id == item_id && i.item_model.loc == LocType::Inventory
+        })
+    }
+
+    /// Takes `count` pieces from the item stack.
+    /// Returns the updated item, the stack is removed from inventory when it reaches 0.
+    ///
+    /// # Errors
+    /// - when the item is missing or there is not enough of it
+    pub fn reduce_count(&mut self, object_id: i32, count: i64) -> anyhow::Result<ItemObject> {
+        let Some(item) = self.items.get_mut(&object_id) else {
+            bail!("Item {object_id} not found in inventory");
+        };
+        if count <= 0 || item.item_model.count < count {
+            bail!(
+                "Not enough items {} to take {count}, have {}",
+                item.item_model.item_id,
+                item.item_model.count
+            );
+        }
+        item.item_model.count -= count;
+        let updated = item.clone();
+        if updated.item_model.count == 0 {
+            self.items.remove(&object_id);
+        }
+        Ok(updated)
+    }
+
+    /// Puts a new item into the inventory, returns it with a fresh object id.
+    pub fn add_item(&mut self, item_model: item::Model) -> ItemObject {
+        let object_id = IdFactory::instance().get_next_id();
+        let item = ItemObject {
+            object_id: object_id.clone(),
+            item_model,
+        };
+        self.items.insert(object_id.into(), item.clone());
+        item
+    }
+
+    /// Adds `count` pieces to the stack of the template, returns `None` when there is no stack.
+    pub fn increase_count_by_item_id(&mut self, item_id: i32, count: i64) -> Option<ItemObject> {
+        let object_id = self.get_item_by_item_id(item_id)?.object_id.value();
+        let item = self.items.get_mut(&object_id)?;
+        item.item_model.count += count;
+        Some(item.clone())
+    }
+
+    /// Sets the stack size, the item is removed from inventory when nothing is left.
+    /// Returns the updated item.
+    pub fn set_count(&mut self, object_id: i32, count: i64) -> Option<ItemObject> {
+        let item = self.items.get_mut(&object_id)?;
+        item.item_model.count = count.max(0);
+        let updated = item.clone();
+        if updated.item_model.count == 0 {
+            self.items.remove(&object_id);
+        }
+        Some(updated)
+    }
+
+    /// Same as [`Self::reduce_count`] but looks the stack up by its template id.
+    ///
+    /// # Errors
+    /// - when the item is missing or there is not enough of it
+    pub fn reduce_count_by_item_id(
+        &mut self,
+        item_id: i32,
+        count: i64,
+    ) -> anyhow::Result<ItemObject> {
+        let Some(object_id) = self
+            .get_item_by_item_id(item_id)
+            .map(|i| i.object_id.value())
+        else {
+            bail!("Item {item_id} not found in inventory");
+        };
+        self.reduce_count(object_id, count)
+    }
 }
 
 #[repr(u8)]
@@ -6,10 +6,17 @@ pub mod paper_doll;
 pub mod inventory;
 pub mod appearance;
 mod _macro;
+mod _contact;
+mod _shortcut;
 mod _teleport_bookmark;
 mod _subclass;
+mod _olympiad;
+mod _vitality;
+mod _recommendation;
+mod _recipe;
+mod _enchant;
 pub mod quest;
-mod warehouse;
+pub mod warehouse;
 pub mod effect;
 mod _player_db;
 pub mod relation;
@@ -17,5 +24,12 @@ pub mod clan;
 
 pub use _player::*;
 pub use _macro::*;
+pub use _contact::*;
+pub use _shortcut::*;
 pub use _teleport_bookmark::*;
-pub use _subclass::*;
\ No newline at end of file
+pub use _subclass::*;
+pub use _olympiad::*;
+pub use _vitality::*;
+pub use _recommendation::*;
+pub use _recipe::*;
+pub use _enchant::*;
\ No newline at end of file
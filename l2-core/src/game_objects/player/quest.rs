@@ -7,6 +7,25 @@ pub struct Quest {
     pub model: quest::Model,
 }
 impl Quest {
+    pub const STARTED: &'static str = "started";
+    pub const COMPLETED: &'static str = "completed";
+
+    /// Freshly accepted quest waiting for its first step
+    #[must_use]
+    pub fn start(char_id: i32, quest_id: i32, name: &str) -> Self {
+        let mut quest = Self {
+            model: quest::Model {
+                char_id,
+                quest_id,
+                name: name.to_string(),
+                variables: JsonValue::Object(serde_json::Map::new()),
+            },
+        };
+        quest.set_state(Self::STARTED);
+        quest.set_condition(1);
+        quest
+    }
+
     #[must_use]
     pub fn get_id(&self) -> i32 {
         self.model.quest_id
@@ -21,11 +40,53 @@ impl Quest {
 
     #[must_use]
     pub fn is_started(&self) -> bool {
-        self.has_state("started")
+        self.has_state(Self::STARTED)
     }
     #[must_use]
     pub fn is_completed(&self) -> bool {
-        self.has_state("completed")
+        self.has_state(Self::COMPLETED)
+    }
+
+    pub fn set_state(&mut self, state: &str) {
+        self.set_var(QuestVariables::State, JsonValue::from(state));
+    }
+
+    /// Current step of a started quest, starts with 1
+    #[must_use]
+    pub fn get_condition(&self) -> u32 {
+        self.get_u32(QuestVariables::Condition)
+    }
+
+    pub fn set_condition(&mut self, condition: u32) {
+        self.set_var(QuestVariables::Condition, JsonValue::from(condition));
+    }
+
+    /// Monsters killed for the current step
+    #[must_use]
+    pub fn get_kills(&self) -> u32 {
+        self.get_u32(QuestVariables::Kills)
+    }
+
+    pub fn set_kills(&mut self, kills: u32) {
+        self.set_var(QuestVariables::Kills, JsonValue::from(kills));
+    }
+
+    fn get_u32(&self, var: QuestVariables) -> u32 {
+        self.model
+            .variables
+            .get(var.as_key())
+            .and_then(JsonValue::as_u64)
+            .and_then(|n| u32::try_from(n).ok())
+            .unwrap_or(0)
+    }
+
+    fn set_var(&mut self, var: QuestVariables, value: JsonValue) {
+        if !self.model.variables.is_object() {
+            self.model.variables = JsonValue::Object(serde_json::Map::new());
+        }
+        if let Some(vars) = self.model.variables.as_object_mut() {
+            vars.insert(var.as_key().to_string(), value);
+        }
     }
 
     #[must_use]
@@ -53,3 +114,23 @@ impl Quest {
         0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quest_progress() {
+        let mut quest = Quest::start(1, 261, "Q00261_CollectorsDream");
+        assert!(quest.is_started());
+        assert_eq!(quest.get_condition(), 1);
+        assert_eq!(quest.get_condition_bit_set(), 1);
+        quest.set_kills(3);
+        quest.set_condition(2);
+        assert_eq!(quest.get_kills(), 3);
+        assert_eq!(quest.get_condition_bit_set(), 2);
+        quest.set_state(Quest::COMPLETED);
+        assert!(quest.is_completed());
+        assert_eq!(quest.get_condition_bit_set(), 0);
+    }
+}
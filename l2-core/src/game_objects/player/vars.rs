@@ -5,6 +5,10 @@ pub enum CharVariables {
     VisualFaceId,
     HairAccessoryEnabled,
     VitalityItemsUsed,
+    VitalityResetAt,
+    MessageRefusal,
+    RecommendedChars,
+    RecommendationResetAt,
 }
 
 impl CharVariables {
@@ -16,6 +20,10 @@ impl CharVariables {
             CharVariables::VisualFaceId => "visualFaceId",
             CharVariables::HairAccessoryEnabled => "hairAccessoryEnabled",
             CharVariables::VitalityItemsUsed => "vitalityItemsUsed",
+            CharVariables::VitalityResetAt => "vitalityResetAt",
+            CharVariables::MessageRefusal => "messageRefusal",
+            CharVariables::RecommendedChars => "recommendedChars",
+            CharVariables::RecommendationResetAt => "recommendationResetAt",
         }
     }
 }
@@ -24,6 +32,7 @@ pub enum QuestVariables {
     State,
     Condition,
     MemoState,
+    Kills,
 }
 impl QuestVariables {
     #[must_use]
@@ -32,6 +41,7 @@ impl QuestVariables {
             QuestVariables::State => "state",
             QuestVariables::Condition => "condition",
             QuestVariables::MemoState => "memoSate",
+            QuestVariables::Kills => "kills",
         }
     }
 }
\ No newline at end of file
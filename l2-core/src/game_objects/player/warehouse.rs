@@ -1,28 +1,55 @@
 use crate::game_objects::item::ItemObject;
+use crate::id_factory::IdFactory;
+use anyhow::bail;
 use entities::entities::item;
 use std::collections::HashMap;
 
+/// Kinds of storage kept by npc warehouse keepers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WarehouseKind {
+    Private,
+    Clan,
+    /// Items sent by the other chars of the account
+    Freight,
+}
+
+impl WarehouseKind {
+    /// Warehouse type the client shows in the window title
+    #[must_use]
+    pub fn client_type(self) -> u16 {
+        match self {
+            Self::Private | Self::Freight => 1,
+            Self::Clan => 2,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Warehouse {
     pub items: HashMap<i32, ItemObject>,
+    limit: u32,
 }
 
 impl Warehouse {
-    pub fn from_items(items: Vec<item::Model>) -> Self {
+    pub const PRIVATE_LIMIT: u32 = 100;
+    pub const DWARF_PRIVATE_LIMIT: u32 = 120;
+
+    #[must_use]
+    pub fn from_items(items: Vec<item::Model>, limit: u32) -> Self {
         Self {
             items: ItemObject::from_items(items),
+            limit,
         }
     }
-    pub fn empty() -> Self {
-        Self {
-            items: HashMap::new(),
-        }
+    #[must_use]
+    pub fn empty(limit: u32) -> Self {
+        Self::from_items(Vec::new(), limit)
     }
 
+    /// Number of slots, every stack takes one
     #[must_use]
-    pub fn get_limit(&self) -> u8 {
-        //todo: implement me
-        100
+    pub fn get_limit(&self) -> u32 {
+        self.limit
     }
     #[must_use]
     pub fn get_current_load(&self) -> i32 {
@@ -34,7 +61,110 @@ impl Warehouse {
     }
 
     #[must_use]
-    pub fn get_size(&self) -> u16 {
-        0u16 //todo: implement me
+    pub fn get_size(&self) -> usize {
+        self.items.len()
+    }
+
+    /// If `new_slots` more items fit into the warehouse
+    #[must_use]
+    pub fn has_room_for(&self, new_slots: usize) -> bool {
+        self.get_size() + new_slots <= usize::try_from(self.limit).unwrap_or(usize::MAX)
+    }
+
+    /// Finds the stack of the template.
+    #[must_use]
+    pub fn get_item_by_item_id(&self, item_id: i32) -> Option<&ItemObject> {
+        self.items
+            .values()
+            .find(|i| i.item_model.item_id == item_id)
+    }
+
+    /// Puts the saved item into the warehouse, an item with the same DB id (a stack the
+    /// items were added to) is replaced. Returns it with its object id.
+    pub fn put_item(&mut self, item_model: item::Model) -> ItemObject {
+        if let Some(item) = self
+            .items
+            .values_mut()
+            .find(|i| i.item_model.id == item_model.id)
+        {
+            item.item_model = item_model;
+            return item.clone();
+        }
+        let object_id = IdFactory::instance().get_next_id();
+        let item = ItemObject {
+            object_id: object_id.clone(),
+            item_model,
+        };
+        self.items.insert(object_id.into(), item.clone());
+        item
+    }
+
+    /// Takes `count` pieces from the stack, the stack is removed when it reaches 0.
+    ///
+    /// # Errors
+    /// - when the item is missing or there is not enough of it
+    pub fn reduce_count(&mut self, object_id: i32, count: i64) -> anyhow::Result<ItemObject> {
+        let Some(item) = self.items.get_mut(&object_id) else {
+            bail!("Item {object_id} not found in warehouse");
+        };
+        if count <= 0 || item.item_model.count < count {
+            bail!(
+                "Not enough items {} to take {count}, have {}",
+                item.item_model.item_id,
+                item.item_model.count
+            );
+        }
+        item.item_model.count -= count;
+        let updated = item.clone();
+        if updated.item_model.count == 0 {
+            self.items.remove(&object_id);
+        }
+        Ok(updated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use entities::dao::item::LocType;
+
+    #[test]
+    fn test_put_and_take() {
+        let mut warehouse = Warehouse::empty(2);
+        let adena = warehouse.put_item(item::Model {
+            id: 1,
+            item_id: 57,
+            count: 100,
+            loc: LocType::Warehouse,
+            ..Default::default()
+        });
+        assert!(warehouse.has_room_for(1));
+        assert!(!warehouse.has_room_for(2));
+        // the stack the adena were added to
+        let stack = warehouse.put_item(item::Model {
+            id: 1,
+            item_id: 57,
+            count: 150,
+            loc: LocType::Warehouse,
+            ..Default::default()
+        });
+        assert_eq!(stack.object_id.value(), adena.object_id.value());
+        assert_eq!(warehouse.get_size(), 1);
+        assert_eq!(
+            warehouse.get_item_by_item_id(57).unwrap().item_model.count,
+            150
+        );
+        let object_id = adena.object_id.value();
+        assert!(warehouse.reduce_count(object_id, 151).is_err());
+        assert_eq!(
+            warehouse
+                .reduce_count(object_id, 50)
+                .unwrap()
+                .item_model
+                .count,
+            100
+        );
+        warehouse.reduce_count(object_id, 100).unwrap();
+        assert_eq!(warehouse.get_size(), 0);
     }
 }
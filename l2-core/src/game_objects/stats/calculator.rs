@@ -8,6 +8,14 @@ pub enum Modifier {
     Mul(f64),
 }
 
+/// Owner of a set of modifiers that gets replaced as a whole, see
+/// [`StatCalculator::set_source_modifiers`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ModifierSource {
+    Henna,
+    Enchant,
+}
+
 pub trait StatFinalizer: Send + Sync {
     fn finalize(&self, base: f64, add: f64, mul: f64, stats: &HashMap<Stat, f64>) -> f64;
 }
@@ -33,6 +41,7 @@ impl StatFinalizer for PAtkFinalizer {
 pub struct StatCalculator {
     pub base_values: HashMap<Stat, f64>,
     pub modifiers: HashMap<Stat, Vec<Modifier>>,
+    pub source_modifiers: HashMap<ModifierSource, Vec<(Stat, Modifier)>>,
     pub finalizers: HashMap<Stat, Box<dyn StatFinalizer>>,
 }
 
@@ -41,6 +50,7 @@ impl fmt::Debug for StatCalculator {
         f.debug_struct("StatCalculator")
             .field("base_values", &self.base_values)
             .field("modifiers", &self.modifiers)
+            .field("source_modifiers", &self.source_modifiers)
             .finish()
     }
 }
@@ -50,6 +60,7 @@ impl Clone for StatCalculator {
         let mut cloned = Self::new(); // keeps built-in finalizers like PAtkFinalizer
         cloned.base_values = self.base_values.clone();
         cloned.modifiers = self.modifiers.clone();
+        cloned.source_modifiers = self.source_modifiers.clone();
         cloned
     }
 }
@@ -62,10 +73,20 @@ impl StatCalculator {
         Self {
             base_values: HashMap::new(),
             modifiers: HashMap::new(),
+            source_modifiers: HashMap::new(),
             finalizers,
         }
     }
 
+    /// Replaces all the modifiers of one source, leaving the other sources untouched.
+    pub fn set_source_modifiers(&mut self, source: ModifierSource, mods: Vec<(Stat, Modifier)>) {
+        if mods.is_empty() {
+            self.source_modifiers.remove(&source);
+        } else {
+            self.source_modifiers.insert(source, mods);
+        }
+    }
+
     pub fn calculate_all(&self) -> HashMap<Stat, f64> {
         let mut results = HashMap::new();
 
@@ -97,12 +118,16 @@ impl StatCalculator {
         let mut add = 0.0;
         let mut mul = 1.0;
 
-        if let Some(mods) = self.modifiers.get(&stat) {
-            for m in mods {
-                match m {
-                    Modifier::Add(v) => add += v,
-                    Modifier::Mul(v) => mul *= v,
-                }
+        let sourced = self
+            .source_modifiers
+            .values()
+            .flatten()
+            .filter(|(s, _)| *s == stat)
+            .map(|(_, m)| m);
+        for m in self.modifiers.get(&stat).into_iter().flatten().chain(sourced) {
+            match m {
+                Modifier::Add(v) => add += v,
+                Modifier::Mul(v) => mul *= v,
             }
         }
 
@@ -0,0 +1,371 @@
+use crate::data::summons::{SummonKind, SummonTemplate};
+use crate::game_objects::player::warehouse::Warehouse;
+use crate::game_objects::stats::stat_enum::Stat;
+use crate::game_objects::zone::Location;
+use crate::id_factory::{IdFactory, ObjectId};
+use entities::entities::{item, pet};
+use std::collections::HashMap;
+
+/// What the summon does when the owner doesn't tell it anything new.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SummonIntention {
+    /// Stays where it is
+    Idle,
+    /// Walks after the owner
+    Follow,
+    Attack {
+        target_id: i32,
+        /// Ordered with Ctrl, the target doesn't have to be an enemy
+        forced: bool,
+    },
+}
+
+/// Stats of the owner a summon gets a share of
+const SHARED_STATS: [Stat; 4] = [Stat::PAtk, Stat::PDef, Stat::MAtk, Stat::MDef];
+
+/// A servitor or a pet of a player.
+#[derive(Debug, Clone)]
+pub struct Summon {
+    pub object_id: ObjectId,
+    pub npc_id: i32,
+    pub kind: SummonKind,
+    pub name: String,
+    /// Pets: DB id of the collar the pet lives in
+    pub control_item_id: Option<i32>,
+    pub level: u8,
+    pub exp: i64,
+    pub current_hp: f64,
+    pub current_mp: f64,
+    pub current_feed: u32,
+    location: Location,
+    pub intention: SummonIntention,
+    /// Items the pet carries, it doesn't equip them
+    pub inventory: Warehouse,
+}
+
+impl Summon {
+    /// Slots of the pet inventory
+    pub const INVENTORY_LIMIT: u32 = 12;
+    /// Below this percent of food the pet is hungry and eats the food it carries
+    pub const HUNGRY_PERCENT: u32 = 55;
+
+    /// A fresh servitor at the lowest level of the template.
+    #[must_use]
+    pub fn servitor(template: &SummonTemplate, location: Location) -> Self {
+        let stats = &template.levels[0];
+        Self {
+            object_id: IdFactory::instance().get_next_id(),
+            npc_id: template.npc_id,
+            kind: template.kind,
+            name: template.name.clone(),
+            control_item_id: None,
+            level: stats.level,
+            exp: stats.exp,
+            current_hp: stats.max_hp,
+            current_mp: stats.max_mp,
+            current_feed: stats.max_feed,
+            location,
+            intention: SummonIntention::Follow,
+            inventory: Warehouse::empty(Self::INVENTORY_LIMIT),
+        }
+    }
+
+    /// The pet as it was saved, with the items it carries.
+    #[must_use]
+    pub fn pet(
+        template: &SummonTemplate,
+        model: &pet::Model,
+        items: Vec<item::Model>,
+        location: Location,
+    ) -> Self {
+        Self {
+            object_id: IdFactory::instance().get_next_id(),
+            npc_id: template.npc_id,
+            kind: template.kind,
+            name: model.name.clone().unwrap_or_else(|| template.name.clone()),
+            control_item_id: Some(model.item_id),
+            level: model.level,
+            exp: model.exp,
+            current_hp: model.cur_hp,
+            current_mp: model.cur_mp,
+            current_feed: model.fed,
+            location,
+            intention: SummonIntention::Follow,
+            inventory: Warehouse::from_items(items, Self::INVENTORY_LIMIT),
+        }
+    }
+
+    /// A pet summoned for the first time, fed and at the lowest level of the template.
+    #[must_use]
+    pub fn new_pet_model(template: &SummonTemplate, control_item_id: i32) -> pet::Model {
+        let stats = &template.levels[0];
+        pet::Model {
+            item_id: control_item_id,
+            name: None,
+            level: stats.level,
+            exp: stats.exp,
+            cur_hp: stats.max_hp,
+            cur_mp: stats.max_mp,
+            fed: stats.max_feed,
+        }
+    }
+
+    /// What is saved of a pet, None for servitors.
+    #[must_use]
+    pub fn to_pet_model(&self) -> Option<pet::Model> {
+        let item_id = self.control_item_id?;
+        Some(pet::Model {
+            item_id,
+            name: None,
+            level: self.level,
+            exp: self.exp,
+            cur_hp: self.current_hp,
+            cur_mp: self.current_mp,
+            fed: self.current_feed,
+        })
+    }
+
+    #[must_use]
+    pub fn get_object_id(&self) -> i32 {
+        self.object_id.value()
+    }
+
+    #[must_use]
+    pub fn get_location(&self) -> &Location {
+        &self.location
+    }
+
+    pub fn set_location(&mut self, x: i32, y: i32, z: i32) {
+        self.location.x = x;
+        self.location.y = y;
+        self.location.z = z;
+    }
+
+    #[must_use]
+    pub fn is_pet(&self) -> bool {
+        self.kind == SummonKind::Pet
+    }
+
+    #[must_use]
+    pub fn is_in_combat(&self) -> bool {
+        matches!(self.intention, SummonIntention::Attack { .. })
+    }
+
+    #[must_use]
+    pub fn get_max_hp(&self, template: &SummonTemplate) -> f64 {
+        template.get_level(self.level).max_hp
+    }
+
+    #[must_use]
+    pub fn get_max_mp(&self, template: &SummonTemplate) -> f64 {
+        template.get_level(self.level).max_mp
+    }
+
+    #[must_use]
+    pub fn get_max_feed(&self, template: &SummonTemplate) -> u32 {
+        template.get_level(self.level).max_feed
+    }
+
+    /// Stats of the level, the attack and defence get the share of the ones of the owner.
+    #[must_use]
+    pub fn calc_stats(
+        &self,
+        template: &SummonTemplate,
+        owner_stats: &HashMap<Stat, f64>,
+    ) -> HashMap<Stat, f64> {
+        let level = template.get_level(self.level);
+        let mut stats = HashMap::from([
+            (Stat::MaxHp, level.max_hp),
+            (Stat::MaxMp, level.max_mp),
+            (Stat::PAtk, level.p_atk),
+            (Stat::PDef, level.p_def),
+            (Stat::MAtk, level.m_atk),
+            (Stat::MDef, level.m_def),
+        ]);
+        let share = f64::from(template.owner_share) / 100.0;
+        for stat in SHARED_STATS {
+            let owner = owner_stats.get(&stat).copied().unwrap_or_default();
+            *stats.entry(stat).or_default() += owner * share;
+        }
+        stats
+    }
+
+    /// Takes the damage, returns true when the summon dies of it.
+    pub fn apply_damage(&mut self, damage: f64) -> bool {
+        let was_alive = self.current_hp > 0.0;
+        self.current_hp = (self.current_hp - damage).max(0.0);
+        was_alive && self.current_hp <= 0.0
+    }
+
+    /// Food left in percent, summons which never get hungry are always full.
+    #[must_use]
+    pub fn get_feed_percent(&self, template: &SummonTemplate) -> u32 {
+        let max = self.get_max_feed(template);
+        if max == 0 {
+            return 100;
+        }
+        u32::try_from(u64::from(self.current_feed) * 100 / u64::from(max)).unwrap_or(100)
+    }
+
+    #[must_use]
+    pub fn is_hungry(&self, template: &SummonTemplate) -> bool {
+        self.get_feed_percent(template) < Self::HUNGRY_PERCENT
+    }
+
+    /// A minute has passed, the summon eats more while it fights. Returns true when it has
+    /// nothing left and starves.
+    pub fn consume_feed(&mut self, template: &SummonTemplate) -> bool {
+        if self.get_max_feed(template) == 0 {
+            return false;
+        }
+        let eaten = if self.is_in_combat() {
+            template.feed_battle
+        } else {
+            template.feed_normal
+        };
+        self.current_feed = self.current_feed.saturating_sub(eaten);
+        self.current_feed == 0
+    }
+
+    pub fn eat(&mut self, template: &SummonTemplate, feed: u32) {
+        self.current_feed = self
+            .current_feed
+            .saturating_add(feed)
+            .min(self.get_max_feed(template));
+    }
+
+    /// Adds exp, the level follows the levels of the template. Returns the new level when it
+    /// changed.
+    pub fn add_exp(&mut self, template: &SummonTemplate, exp: i64) -> Option<u8> {
+        let max_exp = template.get_level(template.max_level()).exp;
+        self.exp = self.exp.saturating_add(exp).clamp(0, max_exp);
+        let old_level = self.level;
+        self.level = template.level_for_exp(self.exp);
+        (self.level != old_level).then_some(self.level)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::summons::SummonLevel;
+
+    fn level(level: u8, exp: i64) -> SummonLevel {
+        SummonLevel {
+            level,
+            exp,
+            max_hp: f64::from(level) * 10.0,
+            max_mp: 50.0,
+            p_atk: 100.0,
+            p_def: 50.0,
+            m_atk: 40.0,
+            m_def: 30.0,
+            max_feed: 1000,
+        }
+    }
+
+    fn template() -> SummonTemplate {
+        SummonTemplate {
+            npc_id: 12077,
+            name: "Wolf".to_string(),
+            kind: SummonKind::Pet,
+            summon_skill: None,
+            control_item: Some(2375),
+            cast_time_ms: 0,
+            owner_share: 10,
+            exp_penalty: 10,
+            food: vec![2515],
+            feed_normal: 100,
+            feed_battle: 300,
+            collision_radius: 11.0,
+            collision_height: 17.0,
+            run_speed: 140,
+            walk_speed: 60,
+            mount: None,
+            levels: vec![level(15, 100), level(16, 200), level(17, 400)],
+        }
+    }
+
+    const HERE: Location = Location {
+        x: 0,
+        y: 0,
+        z: 0,
+        heading: 0,
+    };
+
+    fn wolf(template: &SummonTemplate) -> Summon {
+        let model = Summon::new_pet_model(template, 7);
+        Summon::pet(template, &model, vec![], HERE)
+    }
+
+    #[test]
+    fn test_pet_model() {
+        let template = template();
+        let mut wolf = wolf(&template);
+        assert_eq!((wolf.level, wolf.exp), (15, 100));
+        assert_eq!(wolf.name, "Wolf");
+        assert!(wolf.is_pet());
+        wolf.current_feed = 10;
+        let model = wolf.to_pet_model().unwrap();
+        assert_eq!((model.item_id, model.fed, model.cur_hp), (7, 10, 150.0));
+        let servitor = Summon::servitor(&template, HERE);
+        assert!(servitor.to_pet_model().is_none());
+    }
+
+    #[test]
+    fn test_stats_share() {
+        let template = template();
+        let wolf = wolf(&template);
+        let owner = HashMap::from([(Stat::PAtk, 500.0), (Stat::MaxHp, 2000.0)]);
+        let stats = wolf.calc_stats(&template, &owner);
+        assert!((stats[&Stat::PAtk] - 150.0).abs() < f64::EPSILON);
+        assert!((stats[&Stat::PDef] - 50.0).abs() < f64::EPSILON);
+        // only the attack and defence are shared
+        assert!((stats[&Stat::MaxHp] - 150.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_hunger() {
+        let template = template();
+        let mut wolf = wolf(&template);
+        assert_eq!(wolf.get_feed_percent(&template), 100);
+        assert!(!wolf.consume_feed(&template));
+        assert_eq!(wolf.current_feed, 900);
+        wolf.intention = SummonIntention::Attack {
+            target_id: 1,
+            forced: false,
+        };
+        assert!(!wolf.consume_feed(&template));
+        assert_eq!(wolf.current_feed, 600);
+        assert!(!wolf.is_hungry(&template));
+        assert!(!wolf.consume_feed(&template));
+        assert!(wolf.is_hungry(&template));
+        wolf.eat(&template, 5000);
+        assert_eq!(wolf.current_feed, 1000);
+        wolf.current_feed = 200;
+        assert!(wolf.consume_feed(&template));
+        assert_eq!(wolf.current_feed, 0);
+    }
+
+    #[test]
+    fn test_add_exp() {
+        let template = template();
+        let mut wolf = wolf(&template);
+        assert_eq!(wolf.add_exp(&template, 50), None);
+        assert_eq!(wolf.add_exp(&template, 50), Some(16));
+        assert_eq!(wolf.add_exp(&template, 10_000), Some(17));
+        assert_eq!(wolf.exp, 400);
+        assert_eq!(wolf.add_exp(&template, -1000), Some(15));
+        assert_eq!(wolf.exp, 0);
+    }
+
+    #[test]
+    fn test_apply_damage() {
+        let template = template();
+        let mut wolf = wolf(&template);
+        assert!(!wolf.apply_damage(100.0));
+        assert!(wolf.apply_damage(100.0));
+        assert!(wolf.current_hp.abs() < f64::EPSILON);
+        assert!(!wolf.apply_damage(100.0));
+    }
+}
@@ -1,3 +1,5 @@
+use serde::Deserialize;
+
 #[derive(Debug, Clone, Copy)]
 pub struct Location {
     pub x: i32,
@@ -37,7 +39,7 @@ impl Location {
         self.z = z;
     }
 }
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
 pub enum ZoneId {
     Pvp,
     Peace,
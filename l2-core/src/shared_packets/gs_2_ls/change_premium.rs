@@ -0,0 +1,77 @@
+use crate as l2_core;
+use crate::shared_packets::common::ReadablePacket;
+use crate::shared_packets::read::ReadablePacketBuffer;
+use crate::shared_packets::write::SendablePacketBuffer;
+use bytes::BytesMut;
+use chrono::{DateTime, Utc};
+use macro_common::SendablePacket;
+
+#[derive(Clone, Debug, SendablePacket)]
+pub struct ChangePremium {
+    pub buffer: SendablePacketBuffer,
+    pub account: String,
+    /// None takes the premium away
+    pub premium_expire: Option<DateTime<Utc>>,
+}
+
+impl ChangePremium {
+    /// # Errors
+    /// - when write packet bytes fails
+    pub fn new(account: &str, premium_expire: Option<DateTime<Utc>>) -> anyhow::Result<Self> {
+        let mut inst = Self {
+            buffer: SendablePacketBuffer::new(),
+            account: account.to_string(),
+            premium_expire,
+        };
+        inst.buffer.write(Self::PACKET_ID)?;
+        inst.buffer.write_c_utf16le_string(Some(&inst.account))?;
+        inst.buffer
+            .write_i64(premium_expire.map_or(0, |t| t.timestamp_millis()))?;
+        Ok(inst)
+    }
+}
+
+impl ReadablePacket for ChangePremium {
+    const PACKET_ID: u8 = 0x0C;
+    const EX_PACKET_ID: Option<u16> = None;
+
+    fn read(data: BytesMut) -> anyhow::Result<Self> {
+        let mut buffer = ReadablePacketBuffer::new(data);
+        buffer.read_byte()?;
+        let account = buffer.read_c_utf16le_string()?;
+        let premium_expire = match buffer.read_i64()? {
+            0 => None,
+            millis => DateTime::from_timestamp_millis(millis),
+        };
+        Ok(Self {
+            buffer: SendablePacketBuffer::empty(),
+            account,
+            premium_expire,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_change_premium() {
+        let expire = DateTime::from_timestamp_millis(1_753_500_000_000);
+        let mut packet = ChangePremium::new("test", expire).unwrap();
+        let data = packet.buffer.get_data_mut(false);
+        let read = ChangePremium::read(BytesMut::from(&data[2..])).unwrap();
+        assert_eq!(read.account, "test");
+        assert_eq!(read.premium_expire, expire);
+
+        let mut packet = ChangePremium::new("test", None).unwrap();
+        let data = packet.buffer.get_data_mut(false);
+        assert_eq!(&data[data.len() - 8..], &[0; 8]);
+        assert!(
+            ChangePremium::read(BytesMut::from(&data[2..]))
+                .unwrap()
+                .premium_expire
+                .is_none()
+        );
+    }
+}
@@ -2,6 +2,7 @@ mod access_level;
 mod auth;
 mod blowfish;
 mod change_password;
+mod change_premium;
 mod gs_status;
 mod player_auth_request;
 mod player_in_game;
@@ -12,7 +13,7 @@ mod request_temp_ban;
 
 pub use self::{
     access_level::ChangeAL as ChangeAccessLevel, auth::RequestAuthGS, blowfish::BlowFish,
-    change_password::ChangePassword, gs_status::GSStatusUpdate,
+    change_password::ChangePassword, change_premium::ChangePremium, gs_status::GSStatusUpdate,
     player_auth_request::PlayerAuthRequest, player_in_game::PlayerInGame,
     player_logout::PlayerLogout, player_tracert::PlayerTracert, reply_chars::ReplyChars,
     request_temp_ban::RequestTempBan
@@ -4,21 +4,29 @@ use crate::shared_packets::{
     common::ReadablePacket, read::ReadablePacketBuffer, write::SendablePacketBuffer,
 };
 use crate as l2_core;
+use chrono::{DateTime, Utc};
 
 #[derive(Debug, Clone, SendablePacket)]
 pub struct PlayerAuthResponse {
     pub buffer: SendablePacketBuffer,
     pub account: String,
     pub is_ok: bool,
+    /// When the premium of the account runs out, None without premium
+    pub premium_expire: Option<DateTime<Utc>>,
 }
 
 impl PlayerAuthResponse {
     #[must_use]
-    pub fn new(account: &str, is_ok: bool) -> PlayerAuthResponse {
+    pub fn new(
+        account: &str,
+        is_ok: bool,
+        premium_expire: Option<DateTime<Utc>>,
+    ) -> PlayerAuthResponse {
         let mut gg = PlayerAuthResponse {
             buffer: SendablePacketBuffer::new(),
             account: account.to_string(),
             is_ok,
+            premium_expire,
         };
         let _ = gg.write_all();
         gg
@@ -27,6 +35,8 @@ impl PlayerAuthResponse {
         self.buffer.write_u8(0x03)?;
         self.buffer.write_c_utf16le_string(Some(&self.account))?;
         self.buffer.write_u8(u8::from(self.is_ok))?;
+        self.buffer
+            .write_i64(self.premium_expire.map_or(0, |t| t.timestamp_millis()))?;
         Ok(())
     }
 }
@@ -40,10 +50,15 @@ impl ReadablePacket for PlayerAuthResponse {
         let _packet_id = buffer.read_byte()?;
         let account = buffer.read_c_utf16le_string()?;
         let is_ok = buffer.read_boolean()?;
+        let premium_expire = match buffer.read_i64()? {
+            0 => None,
+            millis => DateTime::from_timestamp_millis(millis),
+        };
         Ok(Self {
             buffer: SendablePacketBuffer::empty(),
             is_ok,
             account,
+            premium_expire,
         })
     }
 }
@@ -54,15 +69,23 @@ mod test {
     #[test]
     fn test_player_auth_response() {
         let acc = "test";
-        let mut packet = PlayerAuthResponse::new(acc, true);
+        let mut packet = PlayerAuthResponse::new(acc, true, None);
         let data = packet.buffer.get_data_mut(false);
-        assert_eq!(data, [14, 0, 3, 116, 0, 101, 0, 115, 0, 116, 0, 0, 0, 1]);
+        assert_eq!(
+            data,
+            [22, 0, 3, 116, 0, 101, 0, 115, 0, 116, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0]
+        );
     }
     #[test]
     fn test_player_auth_response_read() {
-        let buff = BytesMut::from(&[3, 116, 0, 101, 0, 115, 0, 116, 0, 1][..]);
-        let packet = PlayerAuthResponse::read(buff).unwrap();
+        let mut data = vec![3, 116, 0, 101, 0, 115, 0, 116, 0, 0, 0, 1];
+        data.extend(1_753_500_000_000i64.to_le_bytes());
+        let packet = PlayerAuthResponse::read(BytesMut::from(&data[..])).unwrap();
         assert_eq!(packet.account, "test");
         assert!(packet.is_ok);
+        assert_eq!(
+            packet.premium_expire,
+            DateTime::from_timestamp_millis(1_753_500_000_000)
+        );
     }
 }
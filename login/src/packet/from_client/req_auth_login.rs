@@ -50,6 +50,7 @@ impl Message<RequestAuthLogin> for LoginClient {
                 access_level: ActiveValue::Set(0),
                 ban_duration: ActiveValue::NotSet,
                 ban_ip: ActiveValue::NotSet,
+                premium_expire: ActiveValue::NotSet,
             };
             user_record.save(&self.db_pool).await?;
         } else {
@@ -3,8 +3,9 @@ use bytes::BytesMut;
 use l2_core::shared_packets::{
     common::ReadablePacket,
     gs_2_ls::{
-        BlowFish, ChangeAccessLevel, ChangePassword, GSStatusUpdate, PlayerAuthRequest,
-        PlayerInGame, PlayerLogout, PlayerTracert, ReplyChars, RequestAuthGS, RequestTempBan,
+        BlowFish, ChangeAccessLevel, ChangePassword, ChangePremium, GSStatusUpdate,
+        PlayerAuthRequest, PlayerInGame, PlayerLogout, PlayerTracert, ReplyChars, RequestAuthGS,
+        RequestTempBan,
     },
 };
 use macro_common::PacketEnum;
@@ -23,6 +24,7 @@ pub enum GSPackets {
     ReplyChars(ReplyChars),
     RequestTempBan(RequestTempBan),
     ChangePassword(ChangePassword),
+    ChangePremium(ChangePremium),
 }
 
 pub fn build_gs_packet(data: BytesMut) -> anyhow::Result<GSPackets> {
@@ -45,6 +47,7 @@ pub fn build_gs_packet(data: BytesMut) -> anyhow::Result<GSPackets> {
         ReplyChars::PACKET_ID => Ok(GSPackets::ReplyChars(ReplyChars::read(data)?)),
         RequestTempBan::PACKET_ID => Ok(GSPackets::RequestTempBan(RequestTempBan::read(data)?)),
         ChangePassword::PACKET_ID => Ok(GSPackets::ChangePassword(ChangePassword::read(data)?)),
+        ChangePremium::PACKET_ID => Ok(GSPackets::ChangePremium(ChangePremium::read(data)?)),
         _ => {
             bail!("Unknown GS packet ID:0x{:02X}", data[0]);
         }
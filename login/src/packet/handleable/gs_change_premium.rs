@@ -0,0 +1,60 @@
+use crate::gs_client::GameServerClient;
+use entities::entities::user;
+use kameo::message::{Context, Message};
+use l2_core::shared_packets::gs_2_ls::ChangePremium;
+use tracing::{info, instrument};
+
+impl Message<ChangePremium> for GameServerClient {
+    type Reply = anyhow::Result<()>;
+
+    #[instrument(skip(self, _ctx))]
+    async fn handle(
+        &mut self,
+        msg: ChangePremium,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> anyhow::Result<()> {
+        user::Model::set_premium_expire(&self.db_pool, &msg.account, msg.premium_expire).await?;
+        info!(
+            "[change premium] OK {} until {:?}",
+            msg.account, msg.premium_expire
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controller::LoginController;
+    use crate::test_utils::test::spawn_gs_client_actor;
+    use chrono::DateTime;
+    use entities::test_factories::factories::user_factory;
+    use l2_core::config::login::LoginServerConfig;
+    use l2_core::traits::ServerConfig;
+    use std::sync::Arc;
+    use test_utils::utils::get_test_db;
+    use tokio::io::split;
+
+    #[tokio::test]
+    async fn change_premium() {
+        let expire = DateTime::from_timestamp(1_753_500_000, 0);
+        let packet = ChangePremium::new("admin", expire).unwrap();
+        let db_pool = get_test_db().await;
+        user_factory(&db_pool, |mut u| {
+            u.username = "admin".to_string();
+            u
+        })
+        .await;
+        let (_client, server) = tokio::io::duplex(1024);
+        let cfg = LoginServerConfig::from_string(include_str!("../../../../config/login.yaml"));
+        let lc = Arc::new(LoginController::new(Arc::new(cfg)));
+        let (r, w) = split(server);
+        let gs_actor = spawn_gs_client_actor(lc, db_pool.clone(), r, w).await;
+        let res = gs_actor.ask(packet).await;
+        assert!(res.is_ok());
+        let user_model = user::Model::find_by_username(&db_pool, "admin")
+            .await
+            .unwrap();
+        assert_eq!(user_model.get_premium_expire(), expire);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::gs_client::GameServerClient;
+use entities::entities::user;
 use kameo::message::{Context, Message};
 use l2_core::shared_packets::{gs_2_ls::PlayerAuthRequest, ls_2_gs::PlayerAuthResponse};
 use l2_core::traits::ServerToServer;
@@ -23,8 +24,19 @@ impl Message<PlayerAuthRequest> for GameServerClient {
             }
             false // the operation wasn't successful
         });
-        self.send_packet(PlayerAuthResponse::new(&msg.account_name, operation_ok))
-            .await?;
+        let premium_expire = if operation_ok {
+            user::Model::find_some_by_username(&self.db_pool, &msg.account_name)
+                .await?
+                .and_then(|u| u.get_premium_expire())
+        } else {
+            None
+        };
+        self.send_packet(PlayerAuthResponse::new(
+            &msg.account_name,
+            operation_ok,
+            premium_expire,
+        ))
+        .await?;
         if !operation_ok {
             error!("Not authed, so closing connection.");
         }
@@ -1,6 +1,7 @@
 mod gs_blowfish;
 mod gs_change_al;
 mod gs_change_password;
+mod gs_change_premium;
 mod gs_kick_player;
 mod gs_reply_chars;
 mod gs_request_auth;
@@ -9,6 +9,21 @@ mod m20250302_195001_create_crest;
 mod m20250628_150308_create_skills;
 mod m20250629_171706_create_mail;
 mod m20250702_213205_create_quest;
+mod m20250710_120000_create_teleport_bookmark;
+mod m20250712_101500_create_macro;
+mod m20250712_101600_create_shortcut;
+mod m20250714_093000_create_henna;
+mod m20250716_120000_create_friend;
+mod m20250718_120000_create_subclass;
+mod m20250720_120000_create_siege;
+mod m20250722_120000_create_olympiad;
+mod m20250724_120000_create_manor;
+mod m20250726_120000_add_user_premium;
+mod m20250728_120000_add_char_recommendations;
+mod m20250730_120000_create_hero;
+mod m20250801_120000_create_cursed_weapon;
+mod m20250803_120000_create_pet;
+mod m20250805_120000_create_recipe;
 
 pub struct Migrator;
 
@@ -25,6 +40,21 @@ impl MigratorTrait for Migrator {
             Box::new(m20250628_150308_create_skills::Migration),
             Box::new(m20250629_171706_create_mail::Migration),
             Box::new(m20250702_213205_create_quest::Migration),
+            Box::new(m20250710_120000_create_teleport_bookmark::Migration),
+            Box::new(m20250712_101500_create_macro::Migration),
+            Box::new(m20250712_101600_create_shortcut::Migration),
+            Box::new(m20250714_093000_create_henna::Migration),
+            Box::new(m20250716_120000_create_friend::Migration),
+            Box::new(m20250718_120000_create_subclass::Migration),
+            Box::new(m20250720_120000_create_siege::Migration),
+            Box::new(m20250722_120000_create_olympiad::Migration),
+            Box::new(m20250724_120000_create_manor::Migration),
+            Box::new(m20250726_120000_add_user_premium::Migration),
+            Box::new(m20250728_120000_add_char_recommendations::Migration),
+            Box::new(m20250730_120000_create_hero::Migration),
+            Box::new(m20250801_120000_create_cursed_weapon::Migration),
+            Box::new(m20250803_120000_create_pet::Migration),
+            Box::new(m20250805_120000_create_recipe::Migration),
         ]
     }
 }
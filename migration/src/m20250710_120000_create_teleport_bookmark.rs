@@ -0,0 +1,60 @@
+use crate::m20241213_210106_create_char::Character;
+use sea_orm_migration::{prelude::*, schema::{integer, string}};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const CHAR_ID_FOREIGN_KEY_NAME: &str = "fk_char_id_teleport_bookmark";
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TeleportBookmark::Table)
+                    .if_not_exists()
+                    .col(integer(TeleportBookmark::CharId))
+                    .col(integer(TeleportBookmark::Id))
+                    .col(integer(TeleportBookmark::X))
+                    .col(integer(TeleportBookmark::Y))
+                    .col(integer(TeleportBookmark::Z))
+                    .col(integer(TeleportBookmark::Icon))
+                    .col(string(TeleportBookmark::Tag))
+                    .col(string(TeleportBookmark::Name))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name(CHAR_ID_FOREIGN_KEY_NAME)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .from(TeleportBookmark::Table, TeleportBookmark::CharId)
+                            .to(Character::Table, Character::Id),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(TeleportBookmark::CharId)
+                            .col(TeleportBookmark::Id),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TeleportBookmark::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TeleportBookmark {
+    Table,
+    CharId,
+    Id,
+    X,
+    Y,
+    Z,
+    Icon,
+    Tag,
+    Name,
+}
@@ -0,0 +1,62 @@
+use crate::m20241213_210106_create_char::Character;
+use sea_orm::JsonValue;
+use sea_orm_migration::{
+    prelude::*,
+    schema::{integer, json_binary, string},
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const CHAR_ID_FOREIGN_KEY_NAME: &str = "fk_char_id_character_macro";
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(CharacterMacro::Table)
+                    .if_not_exists()
+                    .col(integer(CharacterMacro::CharId))
+                    .col(integer(CharacterMacro::Id))
+                    .col(integer(CharacterMacro::Icon))
+                    .col(string(CharacterMacro::Name))
+                    .col(string(CharacterMacro::Description))
+                    .col(string(CharacterMacro::Acronym))
+                    .col(json_binary(CharacterMacro::Commands).default(JsonValue::Array(vec![])))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name(CHAR_ID_FOREIGN_KEY_NAME)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .from(CharacterMacro::Table, CharacterMacro::CharId)
+                            .to(Character::Table, Character::Id),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(CharacterMacro::CharId)
+                            .col(CharacterMacro::Id),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(CharacterMacro::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum CharacterMacro {
+    Table,
+    CharId,
+    Id,
+    Icon,
+    Name,
+    Description,
+    Acronym,
+    Commands,
+}
@@ -0,0 +1,67 @@
+use crate::m20241213_210106_create_char::Character;
+use sea_orm_migration::{
+    prelude::*,
+    schema::{integer, small_integer},
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const CHAR_ID_FOREIGN_KEY_NAME: &str = "fk_char_id_character_shortcut";
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(CharacterShortcut::Table)
+                    .if_not_exists()
+                    .col(integer(CharacterShortcut::CharId))
+                    .col(small_integer(CharacterShortcut::Slot))
+                    .col(small_integer(CharacterShortcut::Page))
+                    .col(integer(CharacterShortcut::ClassIndex))
+                    .col(small_integer(CharacterShortcut::ShortcutType))
+                    .col(integer(CharacterShortcut::ShortcutId))
+                    .col(small_integer(CharacterShortcut::Level))
+                    .col(small_integer(CharacterShortcut::SubLevel))
+                    .col(integer(CharacterShortcut::CharacterType))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name(CHAR_ID_FOREIGN_KEY_NAME)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .from(CharacterShortcut::Table, CharacterShortcut::CharId)
+                            .to(Character::Table, Character::Id),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(CharacterShortcut::CharId)
+                            .col(CharacterShortcut::ClassIndex)
+                            .col(CharacterShortcut::Page)
+                            .col(CharacterShortcut::Slot),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(CharacterShortcut::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum CharacterShortcut {
+    Table,
+    CharId,
+    Slot,
+    Page,
+    ClassIndex,
+    ShortcutType,
+    ShortcutId,
+    Level,
+    SubLevel,
+    CharacterType,
+}
@@ -0,0 +1,56 @@
+use crate::m20241213_210106_create_char::Character;
+use sea_orm_migration::{
+    prelude::*,
+    schema::{integer, small_integer},
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const CHAR_ID_FOREIGN_KEY_NAME: &str = "fk_char_id_character_henna";
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(CharacterHenna::Table)
+                    .if_not_exists()
+                    .col(integer(CharacterHenna::CharId))
+                    .col(small_integer(CharacterHenna::Slot))
+                    .col(integer(CharacterHenna::ClassIndex))
+                    .col(integer(CharacterHenna::DyeId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name(CHAR_ID_FOREIGN_KEY_NAME)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .from(CharacterHenna::Table, CharacterHenna::CharId)
+                            .to(Character::Table, Character::Id),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(CharacterHenna::CharId)
+                            .col(CharacterHenna::ClassIndex)
+                            .col(CharacterHenna::Slot),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(CharacterHenna::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum CharacterHenna {
+    Table,
+    CharId,
+    Slot,
+    ClassIndex,
+    DyeId,
+}
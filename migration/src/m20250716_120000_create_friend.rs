@@ -0,0 +1,61 @@
+use crate::m20241213_210106_create_char::Character;
+use sea_orm_migration::{
+    prelude::*,
+    schema::{integer, small_integer},
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const CHAR_ID_FOREIGN_KEY_NAME: &str = "fk_char_id_character_friend";
+const FRIEND_ID_FOREIGN_KEY_NAME: &str = "fk_friend_id_character_friend";
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(CharacterFriend::Table)
+                    .if_not_exists()
+                    .col(integer(CharacterFriend::CharId))
+                    .col(integer(CharacterFriend::FriendId))
+                    .col(small_integer(CharacterFriend::Relation))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name(CHAR_ID_FOREIGN_KEY_NAME)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .from(CharacterFriend::Table, CharacterFriend::CharId)
+                            .to(Character::Table, Character::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name(FRIEND_ID_FOREIGN_KEY_NAME)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .from(CharacterFriend::Table, CharacterFriend::FriendId)
+                            .to(Character::Table, Character::Id),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(CharacterFriend::CharId)
+                            .col(CharacterFriend::FriendId),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(CharacterFriend::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum CharacterFriend {
+    Table,
+    CharId,
+    FriendId,
+    Relation,
+}
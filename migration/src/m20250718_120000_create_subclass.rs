@@ -0,0 +1,113 @@
+use crate::m20241213_210106_create_char::Character;
+use sea_orm_migration::{
+    prelude::*,
+    schema::{big_unsigned, integer, string_len_null, timestamp_with_time_zone, tiny_unsigned},
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const CHAR_ID_FOREIGN_KEY_NAME: &str = "fk_char_id_character_subclass";
+const EFFECT_CHAR_ID_FOREIGN_KEY_NAME: &str = "fk_char_id_character_subclass_effect";
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(CharacterSubclass::Table)
+                    .if_not_exists()
+                    .col(integer(CharacterSubclass::CharId))
+                    .col(integer(CharacterSubclass::ClassIndex))
+                    .col(tiny_unsigned(CharacterSubclass::ClassId))
+                    .col(tiny_unsigned(CharacterSubclass::Level))
+                    .col(big_unsigned(CharacterSubclass::Exp).default(0))
+                    .col(big_unsigned(CharacterSubclass::Sp).default(0))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name(CHAR_ID_FOREIGN_KEY_NAME)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .from(CharacterSubclass::Table, CharacterSubclass::CharId)
+                            .to(Character::Table, Character::Id),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(CharacterSubclass::CharId)
+                            .col(CharacterSubclass::ClassIndex),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_table(
+                Table::create()
+                    .table(CharacterSubclassEffect::Table)
+                    .if_not_exists()
+                    .col(integer(CharacterSubclassEffect::CharId))
+                    .col(integer(CharacterSubclassEffect::ClassIndex))
+                    .col(integer(CharacterSubclassEffect::SkillId))
+                    .col(integer(CharacterSubclassEffect::SkillLevel))
+                    .col(integer(CharacterSubclassEffect::CasterId))
+                    .col(string_len_null(CharacterSubclassEffect::AbnormalType, 64))
+                    .col(integer(CharacterSubclassEffect::AbnormalLevel))
+                    .col(timestamp_with_time_zone(CharacterSubclassEffect::EndTime))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name(EFFECT_CHAR_ID_FOREIGN_KEY_NAME)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .from(
+                                CharacterSubclassEffect::Table,
+                                CharacterSubclassEffect::CharId,
+                            )
+                            .to(Character::Table, Character::Id),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(CharacterSubclassEffect::CharId)
+                            .col(CharacterSubclassEffect::ClassIndex)
+                            .col(CharacterSubclassEffect::SkillId),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(CharacterSubclassEffect::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(CharacterSubclass::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum CharacterSubclass {
+    Table,
+    CharId,
+    ClassIndex,
+    ClassId,
+    Level,
+    Exp,
+    Sp,
+}
+
+/// Effects of a class that is not active, they come back when it's active again.
+#[derive(DeriveIden)]
+enum CharacterSubclassEffect {
+    Table,
+    CharId,
+    ClassIndex,
+    SkillId,
+    SkillLevel,
+    CasterId,
+    AbnormalType,
+    AbnormalLevel,
+    EndTime,
+}
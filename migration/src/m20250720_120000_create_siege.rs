@@ -0,0 +1,101 @@
+use crate::m20250302_182532_create_clan::ClanAlly;
+use sea_orm_migration::{
+    prelude::*,
+    schema::{integer, string, timestamp_with_time_zone},
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const CLAN_ID_FOREIGN_KEY_NAME: &str = "fk_clan_id_siege_clan";
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SiegeClan::Table)
+                    .if_not_exists()
+                    .col(integer(SiegeClan::CastleId))
+                    .col(integer(SiegeClan::ClanId))
+                    .col(string(SiegeClan::Side))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name(CLAN_ID_FOREIGN_KEY_NAME)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .from(SiegeClan::Table, SiegeClan::ClanId)
+                            .to(ClanAlly::Table, ClanAlly::Id),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(SiegeClan::CastleId)
+                            .col(SiegeClan::ClanId),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_table(
+                Table::create()
+                    .table(SiegeSchedule::Table)
+                    .if_not_exists()
+                    .col(integer(SiegeSchedule::CastleId).primary_key())
+                    .col(timestamp_with_time_zone(SiegeSchedule::SiegeStartsAt))
+                    .col(timestamp_with_time_zone(SiegeSchedule::RegistrationEndsAt))
+                    .to_owned(),
+            )
+            .await?;
+        if !manager.has_column("castle", "tax_rate").await? {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Castle::Table)
+                        .add_column(integer(Castle::TaxRate).default(0))
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Castle::Table)
+                    .drop_column(Castle::TaxRate)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(SiegeSchedule::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(SiegeClan::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SiegeClan {
+    Table,
+    CastleId,
+    ClanId,
+    Side,
+}
+
+/// Next siege of the castles the npcs own, owned castles keep it in their row
+#[derive(DeriveIden)]
+enum SiegeSchedule {
+    Table,
+    CastleId,
+    SiegeStartsAt,
+    RegistrationEndsAt,
+}
+
+#[derive(DeriveIden)]
+enum Castle {
+    Table,
+    TaxRate,
+}
@@ -0,0 +1,78 @@
+use crate::m20241213_210106_create_char::Character;
+use sea_orm_migration::{
+    prelude::*,
+    schema::{integer, timestamp_with_time_zone, tiny_unsigned},
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const CHAR_ID_FOREIGN_KEY_NAME: &str = "fk_char_id_olympiad_noble";
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(OlympiadNoble::Table)
+                    .if_not_exists()
+                    .col(integer(OlympiadNoble::CharId).primary_key())
+                    .col(tiny_unsigned(OlympiadNoble::ClassId))
+                    .col(integer(OlympiadNoble::Points).default(0))
+                    .col(integer(OlympiadNoble::Matches).default(0))
+                    .col(integer(OlympiadNoble::Wins).default(0))
+                    .col(integer(OlympiadNoble::Losses).default(0))
+                    .col(integer(OlympiadNoble::Draws).default(0))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name(CHAR_ID_FOREIGN_KEY_NAME)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .from(OlympiadNoble::Table, OlympiadNoble::CharId)
+                            .to(Character::Table, Character::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_table(
+                Table::create()
+                    .table(OlympiadPeriod::Table)
+                    .if_not_exists()
+                    .col(integer(OlympiadPeriod::Id).primary_key())
+                    .col(integer(OlympiadPeriod::Period))
+                    .col(timestamp_with_time_zone(OlympiadPeriod::EndsAt))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(OlympiadPeriod::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(OlympiadNoble::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum OlympiadNoble {
+    Table,
+    CharId,
+    ClassId,
+    Points,
+    Matches,
+    Wins,
+    Losses,
+    Draws,
+}
+
+#[derive(DeriveIden)]
+enum OlympiadPeriod {
+    Table,
+    Id,
+    Period,
+    EndsAt,
+}
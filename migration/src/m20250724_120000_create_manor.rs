@@ -0,0 +1,84 @@
+use sea_orm_migration::{
+    prelude::*,
+    schema::{big_integer, boolean, integer},
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(CastleManorProduction::Table)
+                    .if_not_exists()
+                    .col(integer(CastleManorProduction::CastleId))
+                    .col(integer(CastleManorProduction::SeedId))
+                    .col(boolean(CastleManorProduction::NextPeriod))
+                    .col(big_integer(CastleManorProduction::Amount))
+                    .col(big_integer(CastleManorProduction::StartAmount))
+                    .col(big_integer(CastleManorProduction::Price))
+                    .primary_key(
+                        Index::create()
+                            .col(CastleManorProduction::CastleId)
+                            .col(CastleManorProduction::SeedId)
+                            .col(CastleManorProduction::NextPeriod),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_table(
+                Table::create()
+                    .table(CastleManorProcure::Table)
+                    .if_not_exists()
+                    .col(integer(CastleManorProcure::CastleId))
+                    .col(integer(CastleManorProcure::CropId))
+                    .col(boolean(CastleManorProcure::NextPeriod))
+                    .col(big_integer(CastleManorProcure::Amount))
+                    .col(big_integer(CastleManorProcure::StartAmount))
+                    .col(big_integer(CastleManorProcure::Price))
+                    .primary_key(
+                        Index::create()
+                            .col(CastleManorProcure::CastleId)
+                            .col(CastleManorProcure::CropId)
+                            .col(CastleManorProcure::NextPeriod),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(CastleManorProcure::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(CastleManorProduction::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum CastleManorProduction {
+    Table,
+    CastleId,
+    SeedId,
+    NextPeriod,
+    Amount,
+    StartAmount,
+    Price,
+}
+
+#[derive(DeriveIden)]
+enum CastleManorProcure {
+    Table,
+    CastleId,
+    CropId,
+    NextPeriod,
+    Amount,
+    StartAmount,
+    Price,
+}
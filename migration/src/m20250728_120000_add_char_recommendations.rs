@@ -0,0 +1,65 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::{small_unsigned, unsigned};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // sqlite can only add one column per statement
+        let columns = [
+            (
+                "rec_left",
+                small_unsigned(Character::RecLeft).default(0).to_owned(),
+            ),
+            (
+                "rec_have",
+                small_unsigned(Character::RecHave).default(0).to_owned(),
+            ),
+            (
+                "rec_bonus_time",
+                unsigned(Character::RecBonusTime).default(0).to_owned(),
+            ),
+        ];
+        for (name, mut column) in columns {
+            if !manager.has_column("character", name).await? {
+                manager
+                    .alter_table(
+                        Table::alter()
+                            .table(Character::Table)
+                            .add_column(&mut column)
+                            .to_owned(),
+                    )
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in [
+            Character::RecLeft,
+            Character::RecHave,
+            Character::RecBonusTime,
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Character::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Character {
+    Table,
+    RecLeft,
+    RecHave,
+    RecBonusTime,
+}
@@ -0,0 +1,84 @@
+use crate::m20241213_210106_create_char::Character;
+use sea_orm_migration::{
+    prelude::*,
+    schema::{integer, pk_auto, string, string_null, timestamp_with_time_zone, tiny_unsigned},
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const CHAR_ID_FOREIGN_KEY_NAME: &str = "fk_char_id_hero";
+const DIARY_CHAR_ID_FOREIGN_KEY_NAME: &str = "fk_char_id_hero_diary";
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Hero::Table)
+                    .if_not_exists()
+                    .col(integer(Hero::CharId).primary_key())
+                    .col(tiny_unsigned(Hero::ClassId))
+                    .col(integer(Hero::Count).default(0))
+                    .col(timestamp_with_time_zone(Hero::ExpiresAt))
+                    .col(string_null(Hero::Message))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name(CHAR_ID_FOREIGN_KEY_NAME)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .from(Hero::Table, Hero::CharId)
+                            .to(Character::Table, Character::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_table(
+                Table::create()
+                    .table(HeroDiary::Table)
+                    .if_not_exists()
+                    .col(pk_auto(HeroDiary::Id))
+                    .col(integer(HeroDiary::CharId))
+                    .col(timestamp_with_time_zone(HeroDiary::CreatedAt))
+                    .col(string(HeroDiary::Text))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name(DIARY_CHAR_ID_FOREIGN_KEY_NAME)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .from(HeroDiary::Table, HeroDiary::CharId)
+                            .to(Character::Table, Character::Id),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(HeroDiary::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Hero::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Hero {
+    Table,
+    CharId,
+    ClassId,
+    Count,
+    ExpiresAt,
+    Message,
+}
+
+#[derive(DeriveIden)]
+enum HeroDiary {
+    Table,
+    Id,
+    CharId,
+    CreatedAt,
+    Text,
+}
@@ -0,0 +1,54 @@
+use crate::m20241213_210106_create_char::Character;
+use sea_orm_migration::{
+    prelude::*,
+    schema::{integer, timestamp_with_time_zone, unsigned},
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const CHAR_ID_FOREIGN_KEY_NAME: &str = "fk_char_id_cursed_weapon";
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(CursedWeapon::Table)
+                    .if_not_exists()
+                    .col(integer(CursedWeapon::ItemId).primary_key())
+                    .col(integer(CursedWeapon::CharId).unique_key())
+                    .col(unsigned(CursedWeapon::PlayerReputation).default(0))
+                    .col(integer(CursedWeapon::PlayerPkKills).default(0))
+                    .col(integer(CursedWeapon::Kills).default(0))
+                    .col(timestamp_with_time_zone(CursedWeapon::ExpiresAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name(CHAR_ID_FOREIGN_KEY_NAME)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .from(CursedWeapon::Table, CursedWeapon::CharId)
+                            .to(Character::Table, Character::Id),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(CursedWeapon::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum CursedWeapon {
+    Table,
+    ItemId,
+    CharId,
+    PlayerReputation,
+    PlayerPkKills,
+    Kills,
+    ExpiresAt,
+}
@@ -0,0 +1,56 @@
+use crate::m20250103_134559_create_items::Item;
+use sea_orm_migration::{
+    prelude::*,
+    schema::{big_unsigned, double, integer, string_null, tiny_unsigned, unsigned},
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const ITEM_ID_FOREIGN_KEY_NAME: &str = "fk_item_id_pet";
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Pet::Table)
+                    .if_not_exists()
+                    .col(integer(Pet::ItemId).primary_key())
+                    .col(string_null(Pet::Name))
+                    .col(tiny_unsigned(Pet::Level))
+                    .col(big_unsigned(Pet::Exp).default(0))
+                    .col(double(Pet::CurHp).default(0))
+                    .col(double(Pet::CurMp).default(0))
+                    .col(unsigned(Pet::Fed).default(0))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name(ITEM_ID_FOREIGN_KEY_NAME)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .from(Pet::Table, Pet::ItemId)
+                            .to(Item::Table, Item::Id),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Pet::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Pet {
+    Table,
+    ItemId,
+    Name,
+    Level,
+    Exp,
+    CurHp,
+    CurMp,
+    Fed,
+}
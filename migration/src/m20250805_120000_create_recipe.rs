@@ -0,0 +1,48 @@
+use crate::m20241213_210106_create_char::Character;
+use sea_orm_migration::{prelude::*, schema::integer};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const CHAR_ID_FOREIGN_KEY_NAME: &str = "fk_char_id_character_recipe";
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(CharacterRecipe::Table)
+                    .if_not_exists()
+                    .col(integer(CharacterRecipe::CharId))
+                    .col(integer(CharacterRecipe::RecipeId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name(CHAR_ID_FOREIGN_KEY_NAME)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .from(CharacterRecipe::Table, CharacterRecipe::CharId)
+                            .to(Character::Table, Character::Id),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(CharacterRecipe::CharId)
+                            .col(CharacterRecipe::RecipeId),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(CharacterRecipe::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum CharacterRecipe {
+    Table,
+    CharId,
+    RecipeId,
+}